@@ -0,0 +1,170 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::factorization::radical;
+use crate::algebra::gcd::gcd_u64;
+use crate::errors::MathError;
+
+/// Coprime `a + b = c` with its radical and quality.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AbcTriple {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+    /// `rad(abc)`, the product of the distinct primes dividing `abc`.
+    pub radical: u128,
+    /// `ln c / ln rad(abc)`, above 1 exactly when `rad(abc) < c`.
+    pub quality: f64,
+}
+
+/// Quality `ln c / ln rad(abc)` of the triple `a + b = c`:
+/// - `abc_quality(1, 8) = ln 9 / ln 6 ≈ 1.2263`
+/// - `abc_quality(2, 6436341) ≈ 1.6299`, Reyssat's triple, the best known
+///
+/// The abc conjecture says only finitely many triples exceed any quality `1 + ε`.
+/// `rad` comes from `factorization::radical`, so the cost is trial division of `a`, `b`
+/// and `c`. Errors with `PositifIntegerRequired` if `a` or `b` is 0, `OutOfRange` if
+/// they are not coprime and `Overflow` if `a + b` does not fit in `u64`.
+pub fn abc_quality(a: u64, b: u64) -> Result<f64, MathError> {
+    abc_triple(a, b).map(|t| t.quality)
+}
+
+/// The `AbcTriple` of `a + b = c`, with the errors of `abc_quality`.
+pub fn abc_triple(a: u64, b: u64) -> Result<AbcTriple, MathError> {
+    if a == 0 || b == 0 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    if gcd_u64(a, b) != 1 {
+        return Err(MathError::OutOfRange);
+    }
+    let c = a.checked_add(b).ok_or(MathError::Overflow)?;
+    // coprime, so rad(abc) = rad(a)·rad(b)·rad(c) <= abc < 2^192; the product of the
+    // three can still overflow u128
+    let (rad_a, rad_b, rad_c) = (radical(a)?, radical(b)?, radical(c)?);
+    let rad = rad_a
+        .checked_mul(rad_b)
+        .and_then(|r| r.checked_mul(rad_c))
+        .ok_or(MathError::Overflow)?;
+    Ok(triple(a, b, c, rad))
+}
+
+/// Every triple `a < b`, `a + b = c <= max_c` with quality at least `min_quality`,
+/// ordered by `c` then `a`:
+/// - `abc_triples(100, 1.0)`: `1 + 8 = 9`, `5 + 27 = 32`, `1 + 48 = 49`, `1 + 63 = 64`,
+///   `1 + 80 = 81`, `32 + 49 = 81`
+///
+/// Radicals of `1..=max_c` come from one sieve pass. A quality of `q` needs
+/// `rad(a)·rad(b)·rad(c) <= c^(1/q)`, so a `c` whose own radical is already too large
+/// is skipped at once, and so is every `a` whose product of radicals is, before any
+/// gcd or logarithm. O(max_c²) in the worst case, far less for `min_quality > 1`.
+/// `MathError::OutOfRange` if `min_quality` is not positive.
+pub fn abc_triples(max_c: usize, min_quality: f64) -> Result<Vec<AbcTriple>, MathError> {
+    if min_quality.is_nan() || min_quality <= 0.0 {
+        return Err(MathError::OutOfRange);
+    }
+    let rad = radicals(max_c);
+    let mut triples = Vec::new();
+    for c in 3..=max_c {
+        let bound = (c as f64).powf(1.0 / min_quality);
+        let rad_c = rad[c] as f64;
+        if rad_c > bound {
+            continue;
+        }
+        for a in 1..c.div_ceil(2) {
+            let (rad_a, rad_b) = (rad[a] as f64, rad[c - a] as f64);
+            if rad_a * rad_b * rad_c > bound {
+                continue;
+            }
+            if gcd_u64(a as u64, c as u64) != 1 {
+                continue;
+            }
+            let r = rad[a] as u128 * rad[c - a] as u128 * rad[c] as u128;
+            let t = triple(a as u64, (c - a) as u64, c as u64, r);
+            if t.quality >= min_quality {
+                triples.push(t);
+            }
+        }
+    }
+    Ok(triples)
+}
+
+fn triple(a: u64, b: u64, c: u64, radical: u128) -> AbcTriple {
+    AbcTriple {
+        a,
+        b,
+        c,
+        radical,
+        quality: (c as f64).ln() / (radical as f64).ln(),
+    }
+}
+
+/// `rad(n)` for every `0 <= n <= limit`, with `rad(0) = 0`.
+fn radicals(limit: usize) -> Vec<usize> {
+    let mut rad = vec![1; limit + 1];
+    rad[0] = 0;
+    for p in 2..=limit {
+        // still 1: no smaller prime divides p
+        if rad[p] == 1 {
+            for multiple in (p..=limit).step_by(p) {
+                rad[multiple] *= p;
+            }
+        }
+    }
+    rad
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abc_quality() {
+        let q = abc_quality(1, 8).unwrap();
+        assert!((q - 9f64.ln() / 6f64.ln()).abs() < 1e-12);
+        let t = abc_triple(2, 6_436_341).unwrap();
+        // 2 + 3^10·109 = 23^5, rad = 2·3·109·23
+        assert_eq!(t.radical, 15_042);
+        assert!((t.quality - 1.629_911_684).abs() < 1e-9);
+        assert!(abc_quality(1, 2).unwrap() < 1.0);
+        assert_eq!(abc_quality(0, 5), Err(MathError::PositifIntegerRequired));
+        assert_eq!(abc_quality(4, 6), Err(MathError::OutOfRange));
+        assert_eq!(abc_quality(u64::MAX, 1), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_abc_triples() {
+        let hits: Vec<(u64, u64, u64)> = abc_triples(100, 1.0)
+            .unwrap()
+            .iter()
+            .map(|t| (t.a, t.b, t.c))
+            .collect();
+        assert_eq!(
+            hits,
+            vec![
+                (1, 8, 9),
+                (5, 27, 32),
+                (1, 48, 49),
+                (1, 63, 64),
+                (1, 80, 81),
+                (32, 49, 81),
+            ]
+        );
+        // the 31 abc hits with c < 1000
+        assert_eq!(abc_triples(999, 1.0).unwrap().len(), 31);
+        assert_eq!(abc_triples(100, 0.0), Err(MathError::OutOfRange));
+        assert_eq!(abc_triples(100, f64::NAN), Err(MathError::OutOfRange));
+        assert_eq!(abc_triples(2, 1.0), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_abc_triples_match_abc_triple() {
+        for t in abc_triples(3000, 1.2).unwrap() {
+            assert_eq!(abc_triple(t.a, t.b), Ok(t));
+            assert!(t.quality >= 1.2);
+        }
+        let best = abc_triples(5000, 1.4).unwrap();
+        // 3 + 125 = 128, 1 + 2400 = 2401 and 1 + 4374 = 4375 (1.5679)
+        let best: Vec<u64> = best.iter().map(|t| t.c).collect();
+        assert_eq!(best, vec![128, 2401, 4375]);
+    }
+}
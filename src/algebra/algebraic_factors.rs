@@ -0,0 +1,290 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Algebraic identity behind an `IdentitySplit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Identity {
+    /// `a² - 1 = (a - 1)(a + 1)`
+    DifferenceOfSquares,
+    /// `a³ - 1 = (a - 1)(a² + a + 1)`
+    DifferenceOfCubes,
+    /// `a³ + 1 = (a + 1)(a² - a + 1)`
+    SumOfCubes,
+    /// `a^p - 1 = (a - 1)(a^(p-1) + ... + 1)` for a prime `p >= 5`
+    DifferenceOfPowers,
+    /// `a^p + 1 = (a + 1)(a^(p-1) - ... + 1)` for a prime `p >= 5`
+    SumOfOddPowers,
+    /// `a⁴ + 4b⁴ = (a² - 2ab + 2b²)(a² + 2ab + 2b²)`, with `a = 1` or `b = 1`
+    SophieGermain,
+    /// `2^(4m+2) + 1` and `3^(6m+3) + 1` split further than their cyclotomic factors
+    Aurifeuillian,
+}
+
+/// A factorization of `n` read off an algebraic form of `n`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentitySplit {
+    pub identity: Identity,
+    /// Factors above 1, ascending, whose product is `n`; not necessarily prime.
+    pub factors: Vec<u128>,
+}
+
+/// Every split of `n` that an identity gives away, from writing `n` as `b^k ± 1`,
+/// `a⁴ + 4` or `4b⁴ + 1`:
+/// - `identity_splits(63)`: `DifferenceOfSquares` `[7, 9]` and `DifferenceOfCubes`
+///   `[3, 21]`, from `63 = 2^6 - 1`
+/// - `identity_splits(1025)`: `SumOfOddPowers` `[5, 205]` and `Aurifeuillian`
+///   `[25, 41]`, from `1025 = 2^10 + 1`
+///
+/// `b^k ± 1` is taken with the smallest base `b`, and each prime `p | k` gives the split
+/// of `a^p ± 1` for `a = b^(k/p)`. Splits with a factor of 1 are dropped, so the result
+/// is empty for Mersenne numbers with a prime exponent, and a split found by two
+/// identities is reported once, under the first. Costs a few integer roots; nothing is
+/// factored, which makes this a cheap pass before a general-purpose method.
+pub fn identity_splits(n: u128) -> Vec<IdentitySplit> {
+    let mut splits = Vec::new();
+    let mut push = |identity, mut factors: Vec<u128>| {
+        factors.sort_unstable();
+        if factors.iter().all(|&f| f > 1)
+            && splits.iter().all(|s: &IdentitySplit| s.factors != factors)
+        {
+            splits.push(IdentitySplit { identity, factors });
+        }
+    };
+
+    // n = b^k - 1
+    if let Some((b, k)) = n.checked_add(1).and_then(smallest_base_power) {
+        for p in prime_divisors(k) {
+            let a = b.pow(k / p);
+            let identity = match p {
+                2 => Identity::DifferenceOfSquares,
+                3 => Identity::DifferenceOfCubes,
+                _ => Identity::DifferenceOfPowers,
+            };
+            push(identity, vec![a - 1, n / (a - 1)]);
+        }
+    }
+
+    // n = b^k + 1
+    if let Some((b, k)) = n.checked_sub(1).and_then(smallest_base_power) {
+        for p in prime_divisors(k).into_iter().filter(|&p| p != 2) {
+            let a = b.pow(k / p);
+            let identity = match p {
+                3 => Identity::SumOfCubes,
+                _ => Identity::SumOfOddPowers,
+            };
+            push(identity, vec![a + 1, n / (a + 1)]);
+        }
+        if b == 2 && k % 4 == 2 {
+            // 2^(4m+2) + 1 = (2^(2m+1) - 2^(m+1) + 1)(2^(2m+1) + 2^(m+1) + 1)
+            let m = (k - 2) / 4;
+            let (square, middle) = (1u128 << (2 * m + 1), 1u128 << (m + 1));
+            push(
+                Identity::Aurifeuillian,
+                vec![square - middle + 1, square + middle + 1],
+            );
+        }
+        if b == 3 && k % 6 == 3 {
+            // 3^(3h) + 1 = (3^h + 1)(3^h - 3^(m+1) + 1)(3^h + 3^(m+1) + 1) for h = 2m + 1
+            let h = k / 3;
+            let (power, middle) = (3u128.pow(h), 3u128.pow(h / 2 + 1));
+            push(
+                Identity::Aurifeuillian,
+                vec![power + 1, power - middle + 1, power + middle + 1],
+            );
+        }
+    }
+
+    // n = a⁴ + 4·1⁴ or n = 1⁴ + 4b⁴
+    let sophie_germain = |a: u128, b: u128| {
+        let (outer, middle) = (a * a + 2 * b * b, 2 * a * b);
+        vec![outer - middle, outer + middle]
+    };
+    if let Some(a) = n.checked_sub(4).and_then(|m| exact_root(m, 4)) {
+        push(Identity::SophieGermain, sophie_germain(a, 1));
+    }
+    if let Some(b) = n
+        .checked_sub(1)
+        .filter(|m| m.is_multiple_of(4))
+        .and_then(|m| exact_root(m / 4, 4))
+    {
+        push(Identity::SophieGermain, sophie_germain(1, b));
+    }
+    splits
+}
+
+/// Fermat's method: `n = a² - b² = (a - b)(a + b)` for the first `a >= ⌈√n⌉` that makes
+/// `a² - n` a square, trying at most `max_steps` values of `a`:
+/// - `difference_of_squares_split(5959, 10) = Some((59, 101))`, `5959 = 80² - 21²`
+///
+/// Fast when `n` has two factors close to `√n`; `None` if no nontrivial split turns up
+/// in time (always for primes and for `n ≡ 2 (mod 4)`).
+pub fn difference_of_squares_split(n: u128, max_steps: u64) -> Option<(u128, u128)> {
+    let root = n.isqrt();
+    let start = if root * root == n { root } else { root + 1 };
+    for a in (start..).take(max_steps as usize) {
+        let excess = a.checked_mul(a)? - n;
+        let b = excess.isqrt();
+        if b * b == excess {
+            return (a - b > 1).then_some((a - b, a + b));
+        }
+    }
+    None
+}
+
+/// `m = b^k` with `k >= 2` as large as possible, so that `b` is not a perfect power.
+fn smallest_base_power(m: u128) -> Option<(u128, u32)> {
+    if m < 4 {
+        return None;
+    }
+    (2..=m.ilog2())
+        .rev()
+        .find_map(|k| exact_root(m, k).map(|b| (b, k)))
+}
+
+/// `b` with `b^k = m`, if there is one.
+fn exact_root(m: u128, k: u32) -> Option<u128> {
+    let root = integer_root(m, k);
+    (root.checked_pow(k) == Some(m)).then_some(root)
+}
+
+/// `⌊m^(1/k)⌋` for `k >= 2`, by bisection below `2^(⌊log2 m⌋ / k + 1)`.
+fn integer_root(m: u128, k: u32) -> u128 {
+    if m < 2 {
+        return m;
+    }
+    let (mut lo, mut hi) = (1u128, 1u128 << (m.ilog2() / k + 1));
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if mid.checked_pow(k).is_some_and(|power| power <= m) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+fn prime_divisors(mut k: u32) -> Vec<u32> {
+    let mut primes = Vec::new();
+    let mut p = 2;
+    while p * p <= k {
+        if k.is_multiple_of(p) {
+            primes.push(p);
+            while k.is_multiple_of(p) {
+                k /= p;
+            }
+        }
+        p += 1;
+    }
+    if k > 1 {
+        primes.push(k);
+    }
+    primes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(identity: Identity, factors: &[u128]) -> IdentitySplit {
+        IdentitySplit {
+            identity,
+            factors: factors.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_identity_splits() {
+        assert_eq!(
+            identity_splits(63),
+            vec![
+                split(Identity::DifferenceOfSquares, &[7, 9]),
+                split(Identity::DifferenceOfCubes, &[3, 21]),
+            ]
+        );
+        assert_eq!(
+            identity_splits(1025),
+            vec![
+                split(Identity::SumOfOddPowers, &[5, 205]),
+                split(Identity::Aurifeuillian, &[25, 41]),
+            ]
+        );
+        assert_eq!(
+            identity_splits(3u128.pow(9) + 1),
+            vec![
+                split(Identity::SumOfCubes, &[28, 703]),
+                split(Identity::Aurifeuillian, &[19, 28, 37]),
+            ]
+        );
+        assert_eq!(
+            identity_splits(242),
+            vec![split(Identity::DifferenceOfPowers, &[2, 121])]
+        );
+        // 5⁴ + 4 = 629
+        assert_eq!(
+            identity_splits(629),
+            vec![split(Identity::SophieGermain, &[17, 37])]
+        );
+        // 4·3⁴ + 1 = 325 = 18² + 1, not of the form b^k ± 1
+        assert_eq!(
+            identity_splits(325),
+            vec![split(Identity::SophieGermain, &[13, 25])]
+        );
+        // Mersenne numbers with prime exponent, Fermat numbers and primes have none
+        assert_eq!(identity_splits(2047), vec![]);
+        assert_eq!(identity_splits(65537), vec![]);
+        assert_eq!(identity_splits(97), vec![]);
+        assert_eq!(identity_splits(0), vec![]);
+        assert_eq!(identity_splits(1), vec![]);
+    }
+
+    #[test]
+    fn test_identity_splits_large() {
+        let n = (1u128 << 120) - 1;
+        let splits = identity_splits(n);
+        // 120 = 2^3·3·5
+        assert_eq!(splits.len(), 3);
+        assert_eq!(splits[0].factors, vec![(1 << 60) - 1, (1 << 60) + 1]);
+        let n = (1u128 << 126) + 1;
+        assert!(
+            identity_splits(n)
+                .iter()
+                .any(|s| s.identity == Identity::Aurifeuillian)
+        );
+    }
+
+    #[test]
+    fn test_splits_multiply_back() {
+        for b in 2u128..40 {
+            for k in 2..=(127 / b.ilog2()).min(40) {
+                let Some(power) = b.checked_pow(k) else {
+                    continue;
+                };
+                for n in [power - 1, power + 1] {
+                    for s in identity_splits(n) {
+                        let product = s
+                            .factors
+                            .iter()
+                            .try_fold(1u128, |acc, &f| acc.checked_mul(f));
+                        assert_eq!(product, Some(n), "{n}: {s:?}");
+                        assert!(s.factors.iter().all(|&f| f > 1));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_difference_of_squares_split() {
+        assert_eq!(difference_of_squares_split(5959, 10), Some((59, 101)));
+        assert_eq!(difference_of_squares_split(49, 10), Some((7, 7)));
+        assert_eq!(difference_of_squares_split(97, 1000), None);
+        assert_eq!(difference_of_squares_split(6, 1000), None);
+        // factors far from √n need many steps
+        assert_eq!(difference_of_squares_split(3 * 1009, 10), None);
+        assert_eq!(difference_of_squares_split(3 * 1009, 1000), Some((3, 1009)));
+        let (p, q) = (1_000_000_007u128, 1_000_000_009u128);
+        assert_eq!(difference_of_squares_split(p * q, 1), Some((p, q)));
+        assert_eq!(difference_of_squares_split(u128::MAX, 10), None);
+    }
+}
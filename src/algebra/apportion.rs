@@ -0,0 +1,158 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::errors::MathError;
+
+/// Splits `total` in proportion to `weights` by the largest remainder (Hamilton) method.
+///
+/// Each part first gets the integer part of its exact quota `total · wᵢ / Σw`; the units
+/// left over go one each to the largest fractional parts, ties to the earlier index:
+/// - `apportion(10, [5, 3, 2]) = [5, 3, 2]`
+/// - `apportion(10, [1, 1, 1]) = [4, 3, 3]`
+///
+/// Quotas are compared exactly in `u128`, so the parts always sum to `total`. Errors
+/// with `EmptyInput` for no weights and `DivisionByZero` if they are all zero.
+pub fn apportion(total: u64, weights: &[u64]) -> Result<Vec<u64>, MathError> {
+    let sum = weight_sum(weights)?;
+    let mut parts = Vec::with_capacity(weights.len());
+    let mut remainders = Vec::with_capacity(weights.len());
+    for (i, &w) in weights.iter().enumerate() {
+        let scaled = total as u128 * w as u128;
+        parts.push((scaled / sum) as u64);
+        remainders.push((scaled % sum, i));
+    }
+    let leftover = total - parts.iter().sum::<u64>();
+    // largest remainder first, then lowest index
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for &(_, i) in remainders.iter().take(leftover as usize) {
+        parts[i] += 1;
+    }
+    Ok(parts)
+}
+
+/// Splits `total` by the d'Hondt (Jefferson) method: unit after unit goes to the largest
+/// `wᵢ / (sᵢ + 1)`, where `sᵢ` is what part `i` holds so far:
+/// - `apportion_dhondt(8, [100_000, 80_000, 30_000, 20_000]) = [4, 3, 1, 0]`
+///
+/// Favours large weights. Ties go to the earlier index; O(total · weights) steps. Same
+/// errors as `apportion`.
+pub fn apportion_dhondt(total: u64, weights: &[u64]) -> Result<Vec<u64>, MathError> {
+    highest_averages(total, weights, |s| s as u128 + 1)
+}
+
+/// Splits `total` by the Sainte-Laguë (Webster) method, with divisors `2sᵢ + 1`:
+/// - `apportion_sainte_lague(8, [100_000, 80_000, 30_000, 20_000]) = [3, 3, 1, 1]`
+///
+/// Neutral between large and small weights. Ties go to the earlier index;
+/// O(total · weights) steps. Same errors as `apportion`.
+pub fn apportion_sainte_lague(total: u64, weights: &[u64]) -> Result<Vec<u64>, MathError> {
+    highest_averages(total, weights, |s| 2 * s as u128 + 1)
+}
+
+fn weight_sum(weights: &[u64]) -> Result<u128, MathError> {
+    if weights.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    match weights.iter().map(|&w| w as u128).sum() {
+        0 => Err(MathError::DivisionByZero),
+        sum => Ok(sum),
+    }
+}
+
+/// Divisor method: each unit goes to the largest `wᵢ / divisor(sᵢ)`.
+fn highest_averages(
+    total: u64,
+    weights: &[u64],
+    divisor: impl Fn(u64) -> u128,
+) -> Result<Vec<u64>, MathError> {
+    weight_sum(weights)?;
+    let mut parts = vec![0u64; weights.len()];
+    for _ in 0..total {
+        // wᵢ / dᵢ against wⱼ / dⱼ by cross-multiplication; divisors stay below 2^66
+        let quotient = |i: usize| (weights[i] as u128, divisor(parts[i]));
+        let cmp = |a: (u128, u128), b: (u128, u128)| {
+            let (hi_a, lo_a) = wide_mul(a.0, b.1);
+            let (hi_b, lo_b) = wide_mul(b.0, a.1);
+            (hi_a, lo_a).cmp(&(hi_b, lo_b))
+        };
+        let mut best = 0;
+        for i in 1..weights.len() {
+            if cmp(quotient(i), quotient(best)) == Ordering::Greater {
+                best = i;
+            }
+        }
+        parts[best] += 1;
+    }
+    Ok(parts)
+}
+
+/// Full product of a 64-bit and a 66-bit value as `(high, low)` 64-bit halves of `u128`s.
+fn wide_mul(a: u128, b: u128) -> (u128, u128) {
+    let (b_hi, b_lo) = (b >> 64, b & u64::MAX as u128);
+    let lo = a * b_lo;
+    let hi = a * b_hi + (lo >> 64);
+    (hi, lo & u64::MAX as u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VOTES: [u64; 4] = [100_000, 80_000, 30_000, 20_000];
+
+    #[test]
+    fn test_largest_remainder() {
+        assert_eq!(apportion(10, &[5, 3, 2]), Ok(vec![5, 3, 2]));
+        assert_eq!(apportion(10, &[1, 1, 1]), Ok(vec![4, 3, 3]));
+        assert_eq!(apportion(8, &VOTES), Ok(vec![3, 3, 1, 1]));
+        assert_eq!(apportion(0, &[3, 4]), Ok(vec![0, 0]));
+        assert_eq!(apportion(7, &[0, 5, 0]), Ok(vec![0, 7, 0]));
+        assert_eq!(apportion(10, &[3, 2, 2]), Ok(vec![4, 3, 3]));
+        // quotas 3.86, 2.57, 2.57: rounding each would hand out 10
+        assert_eq!(apportion(9, &[3, 2, 2]), Ok(vec![4, 3, 2]));
+    }
+
+    #[test]
+    fn test_alabama_paradox() {
+        // Hamilton's method can take a unit away when the total grows
+        let weights = [6, 6, 2];
+        assert_eq!(apportion(10, &weights), Ok(vec![4, 4, 2]));
+        assert_eq!(apportion(11, &weights), Ok(vec![5, 5, 1]));
+    }
+
+    #[test]
+    fn test_highest_averages() {
+        assert_eq!(apportion_dhondt(8, &VOTES), Ok(vec![4, 3, 1, 0]));
+        assert_eq!(apportion_sainte_lague(8, &VOTES), Ok(vec![3, 3, 1, 1]));
+        // ties go to the earlier index
+        assert_eq!(apportion_dhondt(3, &[1, 1]), Ok(vec![2, 1]));
+        assert_eq!(apportion_sainte_lague(1, &[0, 0, 4]), Ok(vec![0, 0, 1]));
+        // exact comparison where the weights are beyond f64 precision
+        let big = u64::MAX - 1;
+        assert_eq!(apportion_dhondt(3, &[big, big - 1]), Ok(vec![2, 1]));
+        assert_eq!(apportion_dhondt(4, &[big, big - 1]), Ok(vec![2, 2]));
+    }
+
+    #[test]
+    fn test_sums_to_total() {
+        let weights = [17, 0, 256, 3, 99, 41];
+        for total in 0..60 {
+            for parts in [
+                apportion(total, &weights),
+                apportion_dhondt(total, &weights),
+                apportion_sainte_lague(total, &weights),
+            ] {
+                assert_eq!(parts.unwrap().iter().sum::<u64>(), total);
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(apportion(5, &[]), Err(MathError::EmptyInput));
+        assert_eq!(apportion(5, &[0, 0]), Err(MathError::DivisionByZero));
+        assert_eq!(apportion_dhondt(5, &[0]), Err(MathError::DivisionByZero));
+        assert_eq!(apportion_sainte_lague(5, &[]), Err(MathError::EmptyInput));
+    }
+}
@@ -0,0 +1,438 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::factorization::Factorization;
+use crate::errors::MathError;
+
+/// Euler's totient `φ(n)`, the number of `1 <= k <= n` coprime to `n`:
+/// - `euler_phi(36) = 12`
+/// - `euler_phi(1) = 1`
+///
+/// `MathError::PositifIntegerRequired` if `n < 1`.
+pub fn euler_phi(n: i64) -> Result<i64, MathError> {
+    Ok(factor(n)?.totient() as i64)
+}
+
+/// Number of divisors `τ(n)`:
+/// - `divisor_count(36) = 9`
+///
+/// `MathError::PositifIntegerRequired` if `n < 1`.
+pub fn divisor_count(n: i64) -> Result<i64, MathError> {
+    Ok(factor(n)?.divisor_count() as i64)
+}
+
+/// Sum of divisors `σ(n)`:
+/// - `divisor_sum(36) = 91`
+///
+/// `MathError::PositifIntegerRequired` if `n < 1`, `MathError::Overflow` if the sum
+/// exceeds `i64::MAX` (see `divisors::sigma` for a `u128` result).
+pub fn divisor_sum(n: i64) -> Result<i64, MathError> {
+    i64::try_from(factor(n)?.sigma(1)?).map_err(|_| MathError::Overflow)
+}
+
+/// Möbius function `μ(n)`:
+/// - `mobius(30) = -1`, `mobius(36) = 0`, `mobius(1) = 1`
+///
+/// `MathError::PositifIntegerRequired` if `n < 1`.
+pub fn mobius(n: i64) -> Result<i8, MathError> {
+    Ok(factor(n)?.mobius())
+}
+
+/// `φ(n)` for every `0 <= n <= limit`, with `0` at index 0:
+/// - `euler_phi_sieve(6) = [0, 1, 1, 2, 2, 4, 2]`
+///
+/// Each value follows from `φ(n / p)` for the smallest prime factor `p` of `n`, so the
+/// whole table costs one sieve instead of a factorization per entry.
+pub fn euler_phi_sieve(limit: usize) -> Vec<i64> {
+    let spf = smallest_prime_factors(limit);
+    let mut phi = vec![0i64; limit + 1];
+    if limit >= 1 {
+        phi[1] = 1;
+    }
+    for n in 2..=limit {
+        let p = spf[n];
+        let m = n / p;
+        phi[n] = phi[m] * if m.is_multiple_of(p) { p } else { p - 1 } as i64;
+    }
+    phi
+}
+
+/// `μ(n)` for every `0 <= n <= limit`, with `0` at index 0:
+/// - `mobius_sieve(6) = [0, 1, -1, -1, 0, -1, 1]`
+///
+/// Same recurrence on the smallest prime factor as `euler_phi_sieve`.
+pub fn mobius_sieve(limit: usize) -> Vec<i8> {
+    let spf = smallest_prime_factors(limit);
+    let mut mu = vec![0i8; limit + 1];
+    if limit >= 1 {
+        mu[1] = 1;
+    }
+    for n in 2..=limit {
+        let p = spf[n];
+        let m = n / p;
+        mu[n] = if m.is_multiple_of(p) { 0 } else { -mu[m] };
+    }
+    mu
+}
+
+/// `τ(n)` for every `0 <= n <= limit`, with `0` at index 0:
+/// - `divisor_count_sieve(6) = [0, 1, 2, 2, 3, 2, 4]`
+///
+/// Every `d` is added to its multiples, O(limit log limit) in total.
+pub fn divisor_count_sieve(limit: usize) -> Vec<i64> {
+    let mut count = vec![0i64; limit + 1];
+    for d in 1..=limit {
+        for multiple in (d..=limit).step_by(d) {
+            count[multiple] += 1;
+        }
+    }
+    count
+}
+
+/// `σ(n)` for every `0 <= n <= limit`, with `0` at index 0:
+/// - `divisor_sum_sieve(6) = [0, 1, 3, 4, 7, 6, 12]`
+///
+/// Same O(limit log limit) pass over multiples as `divisor_count_sieve`.
+pub fn divisor_sum_sieve(limit: usize) -> Vec<i64> {
+    let mut sum = vec![0i64; limit + 1];
+    for d in 1..=limit {
+        for multiple in (d..=limit).step_by(d) {
+            sum[multiple] += d as i64;
+        }
+    }
+    sum
+}
+
+/// `true` when `n` is a product of exactly `k` primes counted with multiplicity,
+/// `Ω(n) = k`:
+/// - `is_k_almost_prime(12, 3) = true`, `12 = 2·2·3`
+/// - `is_k_almost_prime(1, 0) = true`
+///
+/// `MathError::PositifIntegerRequired` if `n < 1`.
+pub fn is_k_almost_prime(n: i64, k: u32) -> Result<bool, MathError> {
+    let omega: u32 = factor(n)?.prime_powers().iter().map(|&(_, e)| e).sum();
+    Ok(omega == k)
+}
+
+/// `true` for a product of two primes, `is_k_almost_prime(n, 2)`:
+/// - `is_semiprime(15) = true`, `is_semiprime(49) = true`, `is_semiprime(30) = false`
+///
+/// `MathError::PositifIntegerRequired` if `n < 1`.
+pub fn is_semiprime(n: i64) -> Result<bool, MathError> {
+    is_k_almost_prime(n, 2)
+}
+
+/// `Ω(n)`, the number of prime factors with multiplicity, for every `0 <= n <= limit`,
+/// with `0` at index 0:
+/// - `big_omega_sieve(8) = [0, 0, 1, 1, 2, 1, 2, 1, 3]`
+///
+/// `Ω(n) = Ω(n / p) + 1` for the smallest prime factor `p`, as in `euler_phi_sieve`.
+pub fn big_omega_sieve(limit: usize) -> Vec<u32> {
+    let spf = smallest_prime_factors(limit);
+    let mut omega = vec![0; limit + 1];
+    for n in 2..=limit {
+        omega[n] = omega[n / spf[n]] + 1;
+    }
+    omega
+}
+
+/// The `k`-almost-primes `<= limit`, ascending:
+/// - `almost_primes(2, 30) = [4, 6, 9, 10, 14, 15, 21, 22, 25, 26]`
+///
+/// One `big_omega_sieve`; `k = 1` lists the primes and `k = 0` only 1.
+pub fn almost_primes(k: u32, limit: usize) -> Vec<u64> {
+    big_omega_sieve(limit)
+        .into_iter()
+        .enumerate()
+        .skip(1)
+        .filter(|&(_, omega)| omega == k)
+        .map(|(n, _)| n as u64)
+        .collect()
+}
+
+/// Möbius inversion of a table: the `g` with `f(n) = Σ_{d | n} g(d)`, given `f(n)` at
+/// index `n` for `0 <= n < f.len()`, index 0 ignored and 0 in the result:
+/// - `mobius_inversion(&divisor_sum_sieve(6)) = [0, 1, 2, 3, 4, 5, 6]`, as `σ = id ∗ 1`
+///
+/// `g(n) = Σ_{d | n} μ(n/d)·f(d)`, adding `f(d)` to the multiples `n = d·k` with
+/// `μ(k) != 0`: O(L log L) for a table of length `L`. `MathError::Overflow` if a value
+/// leaves `i64`.
+pub fn mobius_inversion(f: &[i64]) -> Result<Vec<i64>, MathError> {
+    let Some(limit) = f.len().checked_sub(1) else {
+        return Ok(Vec::new());
+    };
+    let mu = mobius_sieve(limit);
+    let mut g = vec![0i64; limit + 1];
+    for (d, &value) in f.iter().enumerate().skip(1) {
+        for (k, multiple) in (d..=limit).step_by(d).enumerate() {
+            g[multiple] = match mu[k + 1] {
+                0 => continue,
+                1 => g[multiple].checked_add(value),
+                _ => g[multiple].checked_sub(value),
+            }
+            .ok_or(MathError::Overflow)?;
+        }
+    }
+    Ok(g)
+}
+
+/// A single value `g(n) = Σ_{d | n} μ(n/d)·f(d)` of the Möbius inverse of `f`:
+/// - `mobius_inversion_at(12, |d| d) = 4`, `φ(12)`, as `id = φ ∗ 1`
+///
+/// Calls `f` once per `d` with `n/d` squarefree, `2^ω(n)` times after factoring `n`.
+/// `MathError::PositifIntegerRequired` if `n < 1`, `Overflow` if the sum leaves `i64`.
+pub fn mobius_inversion_at(n: i64, mut f: impl FnMut(i64) -> i64) -> Result<i64, MathError> {
+    let primes: Vec<i64> = factor(n)?
+        .prime_powers()
+        .iter()
+        .map(|&(p, _)| p as i64)
+        .collect();
+    let mut sum = 0i64;
+    for mask in 0..1u32 << primes.len() {
+        // a product of distinct primes of n divides n
+        let squarefree: i64 = (0..primes.len())
+            .filter(|&i| mask >> i & 1 == 1)
+            .map(|i| primes[i])
+            .product();
+        let value = f(n / squarefree);
+        sum = match mask.count_ones() % 2 {
+            0 => sum.checked_add(value),
+            _ => sum.checked_sub(value),
+        }
+        .ok_or(MathError::Overflow)?;
+    }
+    Ok(sum)
+}
+
+/// Inversion of the summatory form: the `G(x)` with `F(x) = Σ_{n <= x} G(⌊x/n⌋)` for
+/// every `x`, given `F`:
+/// - `F(x) = 1` gives the Mertens function, `summatory_mobius_inversion(1000, |_| 1) = 2`
+/// - `F(x) = x(x + 1)/2` gives `Σ_{k <= x} φ(k)`
+///
+/// `G(x) = Σ_{n <= x} μ(n)·F(⌊x/n⌋)`. `⌊x/n⌋` takes about `2√x` values, each on a run
+/// of `n` whose `μ(n)` sum is a difference of Mertens values, so `F` is called only
+/// that often, at arguments `1..=x`; the Mertens table costs one sieve of `x + 1`
+/// entries. `MathError::Overflow` if a value leaves `i64`.
+pub fn summatory_mobius_inversion(
+    x: usize,
+    mut f: impl FnMut(usize) -> i64,
+) -> Result<i64, MathError> {
+    let mut mertens = vec![0i64; x + 1];
+    for (n, &mu) in mobius_sieve(x).iter().enumerate().skip(1) {
+        mertens[n] = mertens[n - 1] + mu as i64;
+    }
+    let mut sum = 0i64;
+    let mut n = 1;
+    while n <= x {
+        let q = x / n;
+        let last = x / q;
+        sum = (mertens[last] - mertens[n - 1])
+            .checked_mul(f(q))
+            .and_then(|term| sum.checked_add(term))
+            .ok_or(MathError::Overflow)?;
+        n = last + 1;
+    }
+    Ok(sum)
+}
+
+fn factor(n: i64) -> Result<Factorization, MathError> {
+    if n < 1 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    Factorization::new(n as u128)
+}
+
+/// Smallest prime factor of every `2 <= n <= limit`; entries 0 and 1 are 0.
+fn smallest_prime_factors(limit: usize) -> Vec<usize> {
+    let mut spf = vec![0; limit + 1];
+    for p in 2..=limit {
+        if spf[p] != 0 {
+            continue;
+        }
+        for multiple in (p..=limit).step_by(p) {
+            if spf[multiple] == 0 {
+                spf[multiple] = p;
+            }
+        }
+    }
+    spf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_functions() {
+        assert_eq!(euler_phi(36), Ok(12));
+        assert_eq!(divisor_count(36), Ok(9));
+        assert_eq!(divisor_sum(36), Ok(91));
+        assert_eq!(mobius(36), Ok(0));
+        assert_eq!(mobius(30), Ok(-1));
+        assert_eq!(euler_phi(1), Ok(1));
+        assert_eq!(divisor_sum(1), Ok(1));
+        let p = 1_000_000_007;
+        assert_eq!(euler_phi(p), Ok(p - 1));
+        assert_eq!(divisor_sum(p), Ok(p + 1));
+        // σ(2^62) = 2^63 - 1 still fits, σ(3·2^61) does not
+        assert_eq!(divisor_sum(1 << 62), Ok(i64::MAX));
+        assert_eq!(divisor_sum(3 << 61), Err(MathError::Overflow));
+        for f in [euler_phi, divisor_count, divisor_sum] {
+            assert_eq!(f(0), Err(MathError::PositifIntegerRequired));
+            assert_eq!(f(-4), Err(MathError::PositifIntegerRequired));
+        }
+        assert_eq!(mobius(0), Err(MathError::PositifIntegerRequired));
+    }
+
+    #[test]
+    fn test_sieves_match_scalar() {
+        assert_eq!(euler_phi_sieve(6), vec![0, 1, 1, 2, 2, 4, 2]);
+        assert_eq!(mobius_sieve(6), vec![0, 1, -1, -1, 0, -1, 1]);
+        assert_eq!(divisor_count_sieve(6), vec![0, 1, 2, 2, 3, 2, 4]);
+        assert_eq!(divisor_sum_sieve(6), vec![0, 1, 3, 4, 7, 6, 12]);
+        assert_eq!(euler_phi_sieve(0), vec![0]);
+        assert_eq!(mobius_sieve(1), vec![0, 1]);
+
+        let limit = 3000;
+        let (phi, mu) = (euler_phi_sieve(limit), mobius_sieve(limit));
+        let (tau, sigma) = (divisor_count_sieve(limit), divisor_sum_sieve(limit));
+        for n in 1..=limit {
+            let m = n as i64;
+            assert_eq!(Ok(phi[n]), euler_phi(m), "φ({n})");
+            assert_eq!(Ok(mu[n]), mobius(m), "μ({n})");
+            assert_eq!(Ok(tau[n]), divisor_count(m), "τ({n})");
+            assert_eq!(Ok(sigma[n]), divisor_sum(m), "σ({n})");
+        }
+    }
+
+    #[test]
+    fn test_sieve_identities() {
+        // Σ_{d | n} φ(d) = n and Σ_{d | n} μ(d) = [n = 1]
+        let limit = 500;
+        let (phi, mu) = (euler_phi_sieve(limit), mobius_sieve(limit));
+        for n in 1..=limit {
+            let divisors = (1..=n).filter(|d| n.is_multiple_of(*d));
+            let (phi_sum, mu_sum) = divisors.fold((0, 0), |(a, b), d| (a + phi[d], b + mu[d]));
+            assert_eq!(phi_sum, n as i64);
+            assert_eq!(mu_sum, i8::from(n == 1));
+        }
+    }
+
+    #[test]
+    fn test_almost_primes() {
+        assert_eq!(is_k_almost_prime(12, 3), Ok(true));
+        assert_eq!(is_k_almost_prime(12, 2), Ok(false));
+        assert_eq!(is_k_almost_prime(1, 0), Ok(true));
+        assert_eq!(is_semiprime(15), Ok(true));
+        assert_eq!(is_semiprime(49), Ok(true));
+        assert_eq!(is_semiprime(30), Ok(false));
+        assert_eq!(is_semiprime(1_000_003 * 1_000_033), Ok(true));
+        assert_eq!(is_semiprime(0), Err(MathError::PositifIntegerRequired));
+
+        assert_eq!(big_omega_sieve(8), vec![0, 0, 1, 1, 2, 1, 2, 1, 3]);
+        assert_eq!(
+            almost_primes(2, 30),
+            vec![4, 6, 9, 10, 14, 15, 21, 22, 25, 26]
+        );
+        assert_eq!(almost_primes(0, 30), vec![1]);
+        assert_eq!(almost_primes(1, 20), vec![2, 3, 5, 7, 11, 13, 17, 19]);
+        assert_eq!(almost_primes(5, 50), vec![32, 48]);
+        assert_eq!(almost_primes(1, 0), vec![]);
+
+        let limit = 2000;
+        for k in 0..6 {
+            let listed = almost_primes(k, limit);
+            let brute: Vec<u64> = (1..=limit as i64)
+                .filter(|&n| is_k_almost_prime(n, k).unwrap())
+                .map(|n| n as u64)
+                .collect();
+            assert_eq!(listed, brute, "k = {k}");
+        }
+    }
+
+    #[test]
+    fn test_mobius_inversion() {
+        assert_eq!(
+            mobius_inversion(&divisor_sum_sieve(6)),
+            Ok(vec![0, 1, 2, 3, 4, 5, 6])
+        );
+        assert_eq!(mobius_inversion(&[]), Ok(vec![]));
+        assert_eq!(mobius_inversion(&[7]), Ok(vec![0]));
+        let limit = 2000;
+        // τ = 1 ∗ 1 and id = φ ∗ 1
+        let ones = mobius_inversion(&divisor_count_sieve(limit)).unwrap();
+        assert!(ones[1..].iter().all(|&g| g == 1));
+        let id: Vec<i64> = (0..=limit as i64).collect();
+        assert_eq!(mobius_inversion(&id), Ok(euler_phi_sieve(limit)));
+        // and back: summing g over divisors recovers f
+        let f: Vec<i64> = (0..=300).map(|n| (n * n) % 97 - 40).collect();
+        let g = mobius_inversion(&f).unwrap();
+        for (n, &value) in f.iter().enumerate().skip(1) {
+            let back: i64 = (1..=n).filter(|d| n % d == 0).map(|d| g[d]).sum();
+            assert_eq!(back, value);
+        }
+        assert_eq!(
+            mobius_inversion(&[0, i64::MAX, i64::MIN]),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_mobius_inversion_at() {
+        assert_eq!(mobius_inversion_at(12, |d| d), Ok(4));
+        let phi = euler_phi_sieve(500);
+        let id: Vec<i64> = (0..=500).collect();
+        let g = mobius_inversion(&id).unwrap();
+        for n in 1..=500 {
+            assert_eq!(mobius_inversion_at(n, |d| d), Ok(phi[n as usize]));
+            assert_eq!(
+                mobius_inversion_at(n, |d| id[d as usize]),
+                Ok(g[n as usize])
+            );
+        }
+        let p = 1_000_000_007;
+        assert_eq!(mobius_inversion_at(p, |d| d), Ok(p - 1));
+        assert_eq!(mobius_inversion_at(3 << 40, |d| d), Ok(1 << 40));
+        assert_eq!(mobius_inversion_at(1, |_| 5), Ok(5));
+        assert_eq!(
+            mobius_inversion_at(6, |d| if d == 6 { i64::MIN } else { i64::MAX }),
+            Err(MathError::Overflow)
+        );
+        assert_eq!(
+            mobius_inversion_at(0, |d| d),
+            Err(MathError::PositifIntegerRequired)
+        );
+    }
+
+    #[test]
+    fn test_summatory_mobius_inversion() {
+        // Mertens function
+        assert_eq!(summatory_mobius_inversion(1000, |_| 1), Ok(2));
+        assert_eq!(summatory_mobius_inversion(1_000_000, |_| 1), Ok(212));
+        assert_eq!(summatory_mobius_inversion(0, |_| 1), Ok(0));
+        // Σ_{n <= x} ⌊x/n⌋ counts pairs (n, m) with nm <= x, each once: G = 1
+        for x in 1..300 {
+            assert_eq!(summatory_mobius_inversion(x, |y| y as i64), Ok(1));
+        }
+        // x(x + 1)/2 = Σ_{n <= x} Φ(⌊x/n⌋) for Φ(y) = Σ_{k <= y} φ(k)
+        let phi = euler_phi_sieve(1000);
+        let mut totient_sum = 0;
+        for (x, &phi_x) in phi.iter().enumerate().skip(1) {
+            totient_sum += phi_x;
+            let triangle = |y: usize| (y * (y + 1) / 2) as i64;
+            assert_eq!(summatory_mobius_inversion(x, triangle), Ok(totient_sum));
+        }
+        // F called only on the distinct quotients
+        let mut calls = 0;
+        summatory_mobius_inversion(1_000_000, |_| {
+            calls += 1;
+            1
+        })
+        .unwrap();
+        assert!(calls <= 2000);
+        assert_eq!(
+            summatory_mobius_inversion(2, |y| if y == 1 { i64::MIN } else { 1 }),
+            Err(MathError::Overflow)
+        );
+    }
+}
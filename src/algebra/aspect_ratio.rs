@@ -0,0 +1,167 @@
+use alloc::vec::Vec;
+
+use crate::algebra::gcd::gcd_u128;
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Named aspect ratios `width:height` as they are usually written, so `16:10` is not
+/// reduced to `8:5`; cinema formats use hundredths (`1.85:1` is `185:100`).
+pub const STANDARD_RATIOS: [(u64, u64); 10] = [
+    (1, 1),
+    (5, 4),
+    (4, 3),
+    (3, 2),
+    (16, 10),
+    (16, 9),
+    (185, 100),
+    (2, 1),
+    (21, 9),
+    (239, 100),
+];
+
+/// Common display and video resolutions `(width, height)`, by width.
+pub const STANDARD_RESOLUTIONS: [(u64, u64); 24] = [
+    (320, 240),
+    (640, 360),
+    (640, 480),
+    (800, 600),
+    (1024, 768),
+    (1152, 864),
+    (1280, 720),
+    (1280, 800),
+    (1280, 1024),
+    (1440, 900),
+    (1600, 900),
+    (1600, 1200),
+    (1680, 1050),
+    (1920, 1080),
+    (1920, 1200),
+    (2048, 1536),
+    (2560, 1080),
+    (2560, 1440),
+    (2560, 1600),
+    (3440, 1440),
+    (3840, 1600),
+    (3840, 2160),
+    (5120, 2880),
+    (7680, 4320),
+];
+
+/// `w:h` in lowest terms:
+/// - `simplify_ratio(1920, 1080) = (16, 9)`
+/// - `simplify_ratio(1440, 900) = (8, 5)`
+///
+/// `MathError::PositifIntegerRequired` if either side is zero.
+pub fn simplify_ratio(w: u64, h: u64) -> Result<(u64, u64), MathError> {
+    if w == 0 || h == 0 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    let g = gcd_u128(w as u128, h as u128) as u64;
+    Ok((w / g, h / g))
+}
+
+/// Entries of `STANDARD_RESOLUTIONS` with exactly the aspect ratio `r` and a width of at
+/// most `max_width`:
+/// - `16/9` up to 2560 wide: `640×360, 1280×720, 1600×900, 1920×1080, 2560×1440`
+///
+/// Only exact matches count, so `21/9` finds nothing: the "21:9" monitors are really
+/// `64:27` (2560×1080) or `43:18` (3440×1440). `MathError::OutOfRange` unless `r > 0`.
+pub fn common_resolutions_for_ratio(
+    r: Rational,
+    max_width: u64,
+) -> Result<Vec<(u64, u64)>, MathError> {
+    if r.numer() <= 0 {
+        return Err(MathError::OutOfRange);
+    }
+    let (num, den) = (r.numer() as u128, r.denom() as u128);
+    Ok(STANDARD_RESOLUTIONS
+        .iter()
+        .copied()
+        .filter(|&(w, h)| w <= max_width && w as u128 * den == h as u128 * num)
+        .collect())
+}
+
+/// The entry of `STANDARD_RATIOS` closest to `w:h`, measured as `|log(w/h ÷ ratio)|` so
+/// that being 10% too wide or too narrow counts the same:
+/// - `nearest_standard_ratio(1366, 768) = (16, 9)`
+/// - `nearest_standard_ratio(3440, 1440) = (239, 100)`
+///
+/// `MathError::PositifIntegerRequired` if either side is zero.
+#[cfg(feature = "std")]
+pub fn nearest_standard_ratio(w: u64, h: u64) -> Result<(u64, u64), MathError> {
+    if w == 0 || h == 0 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    let target = (w as f64).ln() - (h as f64).ln();
+    let distance = |&(a, b): &(u64, u64)| ((a as f64).ln() - (b as f64).ln() - target).abs();
+    Ok(STANDARD_RATIOS
+        .iter()
+        .copied()
+        .min_by(|x, y| distance(x).total_cmp(&distance(y)))
+        .unwrap_or((1, 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_simplify_ratio() {
+        assert_eq!(simplify_ratio(1920, 1080), Ok((16, 9)));
+        assert_eq!(simplify_ratio(1440, 900), Ok((8, 5)));
+        assert_eq!(simplify_ratio(2560, 1080), Ok((64, 27)));
+        assert_eq!(simplify_ratio(7, 7), Ok((1, 1)));
+        assert_eq!(
+            simplify_ratio(0, 1080),
+            Err(MathError::PositifIntegerRequired)
+        );
+        // the tables are consistent with their reduced forms
+        for (w, h) in STANDARD_RESOLUTIONS {
+            let (a, b) = simplify_ratio(w, h).unwrap();
+            assert_eq!(w * b, h * a);
+        }
+    }
+
+    #[test]
+    fn test_common_resolutions_for_ratio() {
+        let wide = Rational::new(16, 9).unwrap();
+        assert_eq!(
+            common_resolutions_for_ratio(wide, 2560),
+            Ok(vec![
+                (640, 360),
+                (1280, 720),
+                (1600, 900),
+                (1920, 1080),
+                (2560, 1440)
+            ])
+        );
+        assert_eq!(
+            common_resolutions_for_ratio(Rational::new(4, 3).unwrap(), 1024),
+            Ok(vec![(320, 240), (640, 480), (800, 600), (1024, 768)])
+        );
+        assert_eq!(
+            common_resolutions_for_ratio(Rational::new(21, 9).unwrap(), 10_000),
+            Ok(vec![])
+        );
+        assert_eq!(
+            common_resolutions_for_ratio(Rational::new(-16, 9).unwrap(), 10_000),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_nearest_standard_ratio() {
+        assert_eq!(nearest_standard_ratio(1920, 1080), Ok((16, 9)));
+        assert_eq!(nearest_standard_ratio(1366, 768), Ok((16, 9)));
+        assert_eq!(nearest_standard_ratio(1680, 1050), Ok((16, 10)));
+        assert_eq!(nearest_standard_ratio(1280, 1024), Ok((5, 4)));
+        assert_eq!(nearest_standard_ratio(3440, 1440), Ok((239, 100)));
+        assert_eq!(nearest_standard_ratio(1080, 1920), Ok((1, 1)));
+        assert_eq!(
+            nearest_standard_ratio(1920, 0),
+            Err(MathError::PositifIntegerRequired)
+        );
+    }
+}
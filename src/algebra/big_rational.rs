@@ -0,0 +1,195 @@
+use core::cmp::Ordering;
+use core::fmt;
+
+use crate::algebra::bigu::BigU;
+use crate::algebra::polynomial::{Field, Ring};
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Arbitrary-precision fraction, the fallback when `Rational` arithmetic overflows.
+///
+/// Sign and magnitude are kept apart: `numer / denom` with `denom > 0` in lowest terms,
+/// and zero is never negative, so derived equality is value equality. Only
+/// `checked_div` by zero can fail.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigRational {
+    negative: bool,
+    numer: BigU,
+    denom: BigU,
+}
+
+impl BigRational {
+    /// Reduced `±numer / denom`; `DivisionByZero` if `denom` is zero.
+    pub fn new(negative: bool, numer: BigU, denom: BigU) -> Result<Self, MathError> {
+        if denom.is_zero() {
+            return Err(MathError::DivisionByZero);
+        }
+        let g = numer.gcd(&denom);
+        Ok(BigRational {
+            negative: negative && !numer.is_zero(),
+            numer: numer.div_rem(&g)?.0,
+            denom: denom.div_rem(&g)?.0,
+        })
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Magnitude of the numerator.
+    pub fn numer(&self) -> &BigU {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &BigU {
+        &self.denom
+    }
+
+    /// The value as a `Rational`, or `None` if it does not fit.
+    pub fn to_rational(&self) -> Option<Rational> {
+        let numer = i64::try_from(self.numer.to_u128()?).ok()?;
+        let denom = i64::try_from(self.denom.to_u128()?).ok()?;
+        Rational::new(if self.negative { -numer } else { numer }, denom).ok()
+    }
+
+    pub fn neg(&self) -> BigRational {
+        BigRational {
+            negative: !self.negative && !self.numer.is_zero(),
+            ..self.clone()
+        }
+    }
+
+    /// `1 / self`; `DivisionByZero` for zero.
+    pub fn recip(&self) -> Result<BigRational, MathError> {
+        if self.numer.is_zero() {
+            return Err(MathError::DivisionByZero);
+        }
+        Ok(BigRational {
+            negative: self.negative,
+            numer: self.denom.clone(),
+            denom: self.numer.clone(),
+        })
+    }
+}
+
+impl From<Rational> for BigRational {
+    fn from(r: Rational) -> BigRational {
+        BigRational {
+            negative: r.numer() < 0,
+            numer: BigU::from_u64(r.numer().unsigned_abs()),
+            denom: BigU::from_u64(r.denom() as u64),
+        }
+    }
+}
+
+impl Ring for BigRational {
+    fn zero() -> Self {
+        BigRational::from(Rational::ZERO)
+    }
+
+    fn one() -> Self {
+        BigRational::from(Rational::ONE)
+    }
+
+    fn checked_add(&self, other: &Self) -> Result<Self, MathError> {
+        let a = &self.numer * &other.denom;
+        let b = &other.numer * &self.denom;
+        let denom = &self.denom * &other.denom;
+        if self.negative == other.negative {
+            return BigRational::new(self.negative, &a + &b, denom);
+        }
+        // opposite signs: subtract the smaller magnitude from the larger
+        match a.cmp(&b) {
+            Ordering::Less => BigRational::new(other.negative, &b - &a, denom),
+            _ => BigRational::new(self.negative, &a - &b, denom),
+        }
+    }
+
+    fn checked_sub(&self, other: &Self) -> Result<Self, MathError> {
+        self.checked_add(&other.neg())
+    }
+
+    fn checked_mul(&self, other: &Self) -> Result<Self, MathError> {
+        BigRational::new(
+            self.negative != other.negative,
+            &self.numer * &other.numer,
+            &self.denom * &other.denom,
+        )
+    }
+
+    fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+}
+
+impl Field for BigRational {
+    fn checked_div(&self, other: &Self) -> Result<Self, MathError> {
+        self.checked_mul(&other.recip()?)
+    }
+}
+
+impl fmt::Display for BigRational {
+    /// `-7/3`, or just the numerator for integers.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        if self.denom == BigU::one() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn big(n: i64, d: i64) -> BigRational {
+        BigRational::from(Rational::new(n, d).unwrap())
+    }
+
+    #[test]
+    fn test_arithmetic_matches_rational() {
+        let values = [(1, 2), (-3, 4), (5, 6), (0, 1), (-7, 3), (9, 1)];
+        for &(a, b) in &values {
+            for &(c, d) in &values {
+                let (x, y) = (Rational::new(a, b).unwrap(), Rational::new(c, d).unwrap());
+                let (bx, by) = (big(a, b), big(c, d));
+                assert_eq!(
+                    bx.checked_add(&by).unwrap().to_rational(),
+                    x.checked_add(&y).ok()
+                );
+                assert_eq!(
+                    bx.checked_sub(&by).unwrap().to_rational(),
+                    x.checked_sub(&y).ok()
+                );
+                assert_eq!(
+                    bx.checked_mul(&by).unwrap().to_rational(),
+                    x.checked_mul(&y).ok()
+                );
+                assert_eq!(
+                    bx.checked_div(&by).ok().and_then(|z| z.to_rational()),
+                    x.checked_div(&y).ok()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_beyond_i64() {
+        let max = big(i64::MAX, 1);
+        let sum = max.checked_add(&max).unwrap();
+        assert_eq!(sum.to_rational(), None);
+        assert_eq!(sum.to_string(), "18446744073709551614");
+        assert_eq!(sum.checked_sub(&max), Ok(max.clone()));
+        assert_eq!(
+            big(-1, 3).checked_mul(&sum).unwrap().to_string(),
+            "-18446744073709551614/3"
+        );
+        assert_eq!(big(0, 1).neg(), big(0, 1));
+        assert_eq!(big(0, 1).recip(), Err(MathError::DivisionByZero));
+    }
+}
@@ -0,0 +1,915 @@
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, Mul, Rem, Sub};
+use core::str::FromStr;
+
+use crate::algebra::integer::Integer;
+use crate::algebra::product_tree::TreeInt;
+use crate::errors::MathError;
+
+/// Operand size (in limbs) above which multiplication switches to Karatsuba.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Operand size (in bits) below which `gcd` and the half-gcd take plain Euclidean steps.
+const HGCD_THRESHOLD: u64 = 64 * 16;
+
+/// Minimal arbitrary-precision unsigned integer.
+///
+/// Stored as little-endian `u64` limbs with no trailing zero limb (zero is the
+/// empty vector), so derived equality is value equality.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigU {
+    limbs: Vec<u64>,
+}
+
+impl BigU {
+    pub fn zero() -> Self {
+        BigU { limbs: Vec::new() }
+    }
+
+    pub fn one() -> Self {
+        BigU::from_u64(1)
+    }
+
+    pub fn from_u64(n: u64) -> Self {
+        BigU::from_limbs(vec![n])
+    }
+
+    pub fn from_u128(n: u128) -> Self {
+        BigU::from_limbs(vec![n as u64, (n >> 64) as u64])
+    }
+
+    /// Builds a value from little-endian limbs.
+    pub fn from_limbs(mut limbs: Vec<u64>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigU { limbs }
+    }
+
+    /// Little-endian limbs, empty for zero.
+    pub fn limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().is_some_and(|limb| limb & 1 == 1)
+    }
+
+    /// Returns the value if it fits in a `u128`.
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.limbs.as_slice() {
+            [] => Some(0),
+            [lo] => Some(*lo as u128),
+            [lo, hi] => Some(((*hi as u128) << 64) | *lo as u128),
+            _ => None,
+        }
+    }
+
+    /// Number of significant bits (0 for zero).
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            None => 0,
+            Some(top) => self.limbs.len() as u64 * 64 - top.leading_zeros() as u64,
+        }
+    }
+
+    /// Value of bit `i` (bit 0 is the least significant).
+    pub fn bit(&self, i: u64) -> bool {
+        self.limbs
+            .get((i / 64) as usize)
+            .is_some_and(|limb| (limb >> (i % 64)) & 1 == 1)
+    }
+
+    /// `self * 2^shift`
+    pub fn shl_bits(&self, shift: u64) -> BigU {
+        if self.is_zero() {
+            return BigU::zero();
+        }
+        let mut limbs = vec![0; (shift / 64) as usize];
+        limbs.extend(shl_limbs(&self.limbs, (shift % 64) as u32));
+        BigU::from_limbs(limbs)
+    }
+
+    /// `self / 2^shift`
+    pub fn shr_bits(&self, shift: u64) -> BigU {
+        let skip = (shift / 64) as usize;
+        if skip >= self.limbs.len() {
+            return BigU::zero();
+        }
+        BigU::from_limbs(shr_limbs(&self.limbs[skip..], (shift % 64) as u32))
+    }
+
+    /// `self - other`, or `None` if the result would be negative.
+    pub fn checked_sub(&self, other: &BigU) -> Option<BigU> {
+        if *self < *other {
+            return None;
+        }
+        let mut limbs = self.limbs.clone();
+        sub_assign_limbs(&mut limbs, &other.limbs);
+        Some(BigU::from_limbs(limbs))
+    }
+
+    /// Quotient and remainder (Knuth's algorithm D).
+    pub fn div_rem(&self, divisor: &BigU) -> Result<(BigU, BigU), MathError> {
+        if divisor.is_zero() {
+            return Err(MathError::DivisionByZero);
+        }
+        if *self < *divisor {
+            return Ok((BigU::zero(), self.clone()));
+        }
+        if let [d] = divisor.limbs.as_slice() {
+            let (q, r) = div_rem_limb(&self.limbs, *d);
+            return Ok((BigU::from_limbs(q), BigU::from_u64(r)));
+        }
+        let (q, r) = div_rem_knuth(&self.limbs, &divisor.limbs);
+        Ok((BigU::from_limbs(q), BigU::from_limbs(r)))
+    }
+
+    /// `self^exp`
+    pub fn pow(&self, mut exp: u32) -> BigU {
+        let mut base = self.clone();
+        let mut result = BigU::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Square-and-multiply: `self^exp mod modulus`.
+    pub fn mod_pow(&self, exp: &BigU, modulus: &BigU) -> Result<BigU, MathError> {
+        if modulus.is_zero() {
+            return Err(MathError::DivisionByZero);
+        }
+        let mut result = BigU::one().div_rem(modulus)?.1;
+        let base = self.div_rem(modulus)?.1;
+        for i in (0..exp.bits()).rev() {
+            result = (&result * &result).div_rem(modulus)?.1;
+            if exp.bit(i) {
+                result = (&result * &base).div_rem(modulus)?.1;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Greatest common divisor, with `gcd(0, 0) = 0`:
+    /// - `BigU::from_u64(12).gcd(&BigU::from_u64(18)) = 6`
+    ///
+    /// Schönhage's half-gcd in Möller's formulation: the leading halves of the operands
+    /// determine the first half of the Euclidean quotients, so a recursive call on the
+    /// top bits yields a 2×2 matrix that cuts both operands to about half their size with
+    /// a few multiplications. With Karatsuba products that is O(n^1.59 log n) for
+    /// `n`-bit operands instead of Euclid's O(n²), which matters from a few thousand
+    /// bits on; smaller operands take Euclidean steps.
+    pub fn gcd(&self, other: &BigU) -> BigU {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        loop {
+            if a < b {
+                core::mem::swap(&mut a, &mut b);
+            }
+            if b.bits() < HGCD_THRESHOLD {
+                while !b.is_zero() {
+                    let r = &a % &b;
+                    (a, b) = (b, r);
+                }
+                return a;
+            }
+            match half_gcd(&a, &b) {
+                Some((_, x, y)) => (a, b) = (x, y),
+                None => (a, b) = (&a % &b, b),
+            }
+        }
+    }
+}
+
+impl From<u64> for BigU {
+    fn from(n: u64) -> Self {
+        BigU::from_u64(n)
+    }
+}
+
+impl From<u128> for BigU {
+    fn from(n: u128) -> Self {
+        BigU::from_u128(n)
+    }
+}
+
+impl Ord for BigU {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigU {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigU {
+    type Output = BigU;
+
+    fn add(self, other: &BigU) -> BigU {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut limbs = long.limbs.clone();
+        add_assign_limbs(&mut limbs, &short.limbs);
+        BigU::from_limbs(limbs)
+    }
+}
+
+impl Sub for &BigU {
+    type Output = BigU;
+
+    /// Panics on underflow, like the primitive unsigned types; see `checked_sub`.
+    fn sub(self, other: &BigU) -> BigU {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul for &BigU {
+    type Output = BigU;
+
+    fn mul(self, other: &BigU) -> BigU {
+        BigU::from_limbs(mul_limbs(&self.limbs, &other.limbs))
+    }
+}
+
+impl Rem for &BigU {
+    type Output = BigU;
+
+    /// Panics on a zero modulus, like the primitive types; see `div_rem`.
+    fn rem(self, modulus: &BigU) -> BigU {
+        self.div_rem(modulus)
+            .expect("attempt to calculate the remainder with a divisor of zero")
+            .1
+    }
+}
+
+macro_rules! forward_owned_ops {
+    ($($op:ident :: $method:ident),*) => {
+        $(
+            impl $op for BigU {
+                type Output = BigU;
+
+                fn $method(self, other: BigU) -> BigU {
+                    (&self).$method(&other)
+                }
+            }
+        )*
+    };
+}
+
+forward_owned_ops!(Add::add, Sub::sub, Mul::mul, Rem::rem);
+
+impl fmt::Display for BigU {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000; // 10^19
+
+        if self.is_zero() {
+            return f.pad_integral(true, "", "0");
+        }
+        let mut chunks = Vec::new();
+        let mut limbs = self.limbs.clone();
+        while !limbs.is_empty() {
+            let (q, r) = div_rem_limb(&limbs, CHUNK);
+            chunks.push(r);
+            limbs = BigU::from_limbs(q).limbs;
+        }
+        let mut digits = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:019}", chunk));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+impl FromStr for BigU {
+    type Err = MathError;
+
+    /// Parses a non-empty string of decimal digits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(MathError::OutOfRange);
+        }
+        let ten = BigU::from_u64(10);
+        let mut value = BigU::zero();
+        for b in s.bytes() {
+            value = &(&value * &ten) + &BigU::from_u64((b - b'0') as u64);
+        }
+        Ok(value)
+    }
+}
+
+impl Integer for BigU {
+    fn zero() -> Self {
+        BigU::zero()
+    }
+
+    fn one() -> Self {
+        BigU::one()
+    }
+
+    fn int_mul(&self, other: &Self) -> Result<Self, MathError> {
+        Ok(self * other)
+    }
+
+    fn int_div_rem(&self, divisor: &Self) -> Result<(Self, Self), MathError> {
+        self.div_rem(divisor)
+    }
+
+    fn int_abs(&self) -> Result<Self, MathError> {
+        Ok(self.clone())
+    }
+
+    fn int_neg(&self) -> Result<Self, MathError> {
+        match self.is_zero() {
+            true => Ok(BigU::zero()),
+            false => Err(MathError::Overflow),
+        }
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        BigU::to_u128(self)
+    }
+
+    fn abs_to_u128(&self) -> Option<u128> {
+        BigU::to_u128(self)
+    }
+
+    fn try_from_u128(value: u128) -> Option<Self> {
+        Some(BigU::from_u128(value))
+    }
+}
+
+impl TreeInt for BigU {
+    fn tree_mul(&self, other: &Self) -> Result<Self, MathError> {
+        Ok(self * other)
+    }
+
+    fn tree_rem(&self, modulus: &Self) -> Result<Self, MathError> {
+        Ok(self.div_rem(modulus)?.1)
+    }
+}
+
+/// `acc += other` in place; `acc` must be at least as long as `other`.
+fn add_assign_limbs(acc: &mut Vec<u64>, other: &[u64]) {
+    let mut carry = 0u64;
+    for (i, limb) in acc.iter_mut().enumerate() {
+        let addend = other.get(i).copied().unwrap_or(0);
+        if i >= other.len() && carry == 0 {
+            break;
+        }
+        let (sum, c1) = limb.overflowing_add(addend);
+        let (sum, c2) = sum.overflowing_add(carry);
+        *limb = sum;
+        carry = (c1 || c2) as u64;
+    }
+    if carry > 0 {
+        acc.push(carry);
+    }
+}
+
+/// `acc -= other` in place; requires `acc >= other`.
+fn sub_assign_limbs(acc: &mut [u64], other: &[u64]) {
+    let mut borrow = 0u64;
+    for (i, limb) in acc.iter_mut().enumerate() {
+        let subtrahend = other.get(i).copied().unwrap_or(0);
+        if i >= other.len() && borrow == 0 {
+            break;
+        }
+        let (diff, b1) = limb.overflowing_sub(subtrahend);
+        let (diff, b2) = diff.overflowing_sub(borrow);
+        *limb = diff;
+        borrow = (b1 || b2) as u64;
+    }
+}
+
+fn mul_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+    mul_karatsuba(a, b)
+}
+
+fn mul_schoolbook(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut result = vec![0u64; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u128 * y as u128 + result[i + j] as u128 + carry;
+            result[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        result[i + b.len()] = carry as u64;
+    }
+    result
+}
+
+/// a * b = z2·B^2m + z1·B^m + z0 with z1 = (a0 + a1)(b0 + b1) - z0 - z2.
+fn mul_karatsuba(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let m = a.len().max(b.len()) / 2;
+    let (a0, a1) = a.split_at(m.min(a.len()));
+    let (b0, b1) = b.split_at(m.min(b.len()));
+
+    let z0 = BigU::from_limbs(mul_limbs(a0, b0));
+    let z2 = BigU::from_limbs(mul_limbs(a1, b1));
+    let a_sum = &BigU::from_limbs(a0.to_vec()) + &BigU::from_limbs(a1.to_vec());
+    let b_sum = &BigU::from_limbs(b0.to_vec()) + &BigU::from_limbs(b1.to_vec());
+    let z1 = &(&(&a_sum * &b_sum) - &z0) - &z2;
+
+    let result = &(&z2.shl_bits(128 * m as u64) + &z1.shl_bits(64 * m as u64)) + &z0;
+    result.limbs
+}
+
+/// Shifts left by `shift < 64` bits; the result has one extra (possibly zero) limb.
+fn shl_limbs(limbs: &[u64], shift: u32) -> Vec<u64> {
+    let mut result = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0u64;
+    for &limb in limbs {
+        if shift == 0 {
+            result.push(limb);
+        } else {
+            result.push((limb << shift) | carry);
+            carry = limb >> (64 - shift);
+        }
+    }
+    result.push(carry);
+    result
+}
+
+/// Shifts right by `shift < 64` bits.
+fn shr_limbs(limbs: &[u64], shift: u32) -> Vec<u64> {
+    if shift == 0 {
+        return limbs.to_vec();
+    }
+    (0..limbs.len())
+        .map(|i| {
+            let high = limbs.get(i + 1).map_or(0, |next| next << (64 - shift));
+            (limbs[i] >> shift) | high
+        })
+        .collect()
+}
+
+/// Division by a single nonzero limb.
+fn div_rem_limb(limbs: &[u64], divisor: u64) -> (Vec<u64>, u64) {
+    let mut quotient = vec![0u64; limbs.len()];
+    let mut rem = 0u128;
+    for i in (0..limbs.len()).rev() {
+        let cur = (rem << 64) | limbs[i] as u128;
+        quotient[i] = (cur / divisor as u128) as u64;
+        rem = cur % divisor as u128;
+    }
+    (quotient, rem as u64)
+}
+
+/// Knuth, TAOCP vol. 2, 4.3.1, algorithm D. Requires `v.len() >= 2` and `u >= v`.
+fn div_rem_knuth(u: &[u64], v: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    const BASE: u128 = 1 << 64;
+
+    let n = v.len();
+    let m = u.len() - n;
+
+    // normalize so the divisor's top bit is set
+    let shift = v[n - 1].leading_zeros();
+    let mut v = shl_limbs(v, shift);
+    v.truncate(n);
+    let mut u = shl_limbs(u, shift);
+
+    let mut q = vec![0u64; m + 1];
+    for j in (0..=m).rev() {
+        let top = ((u[j + n] as u128) << 64) | u[j + n - 1] as u128;
+        let mut qhat = top / v[n - 1] as u128;
+        let mut rhat = top % v[n - 1] as u128;
+        while qhat >= BASE || qhat * v[n - 2] as u128 > ((rhat << 64) | u[j + n - 2] as u128) {
+            qhat -= 1;
+            rhat += v[n - 1] as u128;
+            if rhat >= BASE {
+                break;
+            }
+        }
+
+        // u[j..=j+n] -= qhat * v
+        let mut borrow: i128 = 0;
+        let mut carry: u128 = 0;
+        for i in 0..n {
+            let p = qhat * v[i] as u128 + carry;
+            carry = p >> 64;
+            let t = u[i + j] as i128 - (p as u64) as i128 + borrow;
+            u[i + j] = t as u64;
+            borrow = t >> 64;
+        }
+        let t = u[j + n] as i128 - carry as i128 + borrow;
+        u[j + n] = t as u64;
+
+        if t < 0 {
+            // qhat was one too large: add v back
+            qhat -= 1;
+            let mut carry = 0u128;
+            for i in 0..n {
+                let s = u[i + j] as u128 + v[i] as u128 + carry;
+                u[i + j] = s as u64;
+                carry = s >> 64;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u64);
+        }
+        q[j] = qhat as u64;
+    }
+
+    let remainder = shr_limbs(&u[..n], shift);
+    (q, remainder)
+}
+
+/// Unimodular `[[m00, m01], [m10, m11]]` with `(a, b) = M·(a', b')`, a product of steps
+/// `a' = a - q·b` and `b' = b - q·a`, so the entries are nonnegative and the determinant
+/// is 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HgcdMatrix {
+    m: [[BigU; 2]; 2],
+}
+
+impl HgcdMatrix {
+    fn identity() -> Self {
+        HgcdMatrix {
+            m: [[BigU::one(), BigU::zero()], [BigU::zero(), BigU::one()]],
+        }
+    }
+
+    /// Records `x' = x - q·y` for `x` the operand at `reduced` (0 for `a`, 1 for `b`):
+    /// the other column gains `q` times this one.
+    fn push_step(&mut self, reduced: usize, q: &BigU) {
+        for row in &mut self.m {
+            row[1 - reduced] = &row[1 - reduced] + &(q * &row[reduced]);
+        }
+    }
+
+    fn mul(&self, other: &HgcdMatrix) -> HgcdMatrix {
+        let entry = |i: usize, j: usize| {
+            &(&self.m[i][0] * &other.m[0][j]) + &(&self.m[i][1] * &other.m[1][j])
+        };
+        HgcdMatrix {
+            m: [[entry(0, 0), entry(0, 1)], [entry(1, 0), entry(1, 1)]],
+        }
+    }
+
+    /// `M⁻¹·(a, b) = (m11·a - m01·b, m00·b - m10·a)`, `None` if either is negative.
+    fn apply_inverse(&self, a: &BigU, b: &BigU) -> Option<(BigU, BigU)> {
+        let [[m00, m01], [m10, m11]] = &self.m;
+        let x = (m11 * a).checked_sub(&(m01 * b))?;
+        let y = (m00 * b).checked_sub(&(m10 * a))?;
+        Some((x, y))
+    }
+}
+
+/// One Euclidean step on the larger operand that keeps both at `2^s` or above:
+/// `x - q·y` for the largest such `q`. `false`, with nothing changed, once
+/// `|a - b| < 2^s`, the point where the half-gcd stops.
+fn hgcd_step(a: &mut BigU, b: &mut BigU, s: u64, matrix: &mut HgcdMatrix) -> bool {
+    let reduced = usize::from(*a < *b);
+    let (x, y) = if reduced == 0 { (a, b) } else { (b, a) };
+    let Some(d) = x.checked_sub(y) else {
+        return false;
+    };
+    if d.bits() <= s || y.bits() <= s {
+        return false;
+    }
+    let Ok((mut q, mut r)) = d.div_rem(y) else {
+        return false;
+    };
+    q = &q + &BigU::one();
+    if r.bits() <= s {
+        q = &q - &BigU::one();
+        r = &r + y;
+    }
+    *x = r;
+    matrix.push_step(reduced, &q);
+    true
+}
+
+/// Half-gcd of `a, b` with `n = max(bits)`: the matrix of the Euclidean steps that bring
+/// both operands down to about `n/2` bits, and the reduced pair, `None` if no step
+/// applies. With `s = n/2 + 1`, it stops at the first pair with both values at least
+/// `2^s` and their difference below `2^s`; by Möller's lemma a matrix that does that for
+/// the top bits of `a, b` is also valid for `a, b` themselves, at a threshold raised by
+/// the number of bits dropped. Hence two recursive calls on top halves (from `n` to
+/// `3n/4` bits, then to `n/2`), glued by single steps.
+fn half_gcd(a: &BigU, b: &BigU) -> Option<(HgcdMatrix, BigU, BigU)> {
+    let n = a.bits().max(b.bits());
+    let s = n / 2 + 1;
+    if a.bits().min(b.bits()) <= s {
+        return None;
+    }
+    let (mut a, mut b) = (a.clone(), b.clone());
+    let mut matrix = HgcdMatrix::identity();
+    let mut progress = false;
+    if n >= HGCD_THRESHOLD {
+        let p = n / 2;
+        if let Some((m1, _, _)) = half_gcd(&a.shr_bits(p), &b.shr_bits(p))
+            && let Some((x, y)) = m1.apply_inverse(&a, &b)
+        {
+            (a, b, matrix, progress) = (x, y, m1, true);
+        }
+        while a.bits().max(b.bits()) > 3 * n / 4 + 1 {
+            if !hgcd_step(&mut a, &mut b, s, &mut matrix) {
+                return progress.then_some((matrix, a, b));
+            }
+            progress = true;
+        }
+        let n = a.bits().max(b.bits());
+        if n > s + 2 {
+            // the top bits of the operands reduced at their own threshold land on `s + 1`
+            let p = 2 * s - n + 1;
+            if let Some((m2, _, _)) = half_gcd(&a.shr_bits(p), &b.shr_bits(p))
+                && let Some((x, y)) = m2.apply_inverse(&a, &b)
+            {
+                (a, b, matrix, progress) = (x, y, matrix.mul(&m2), true);
+            }
+        }
+    }
+    while hgcd_step(&mut a, &mut b, s, &mut matrix) {
+        progress = true;
+    }
+    progress.then_some((matrix, a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigU {
+        s.parse().unwrap()
+    }
+
+    /// Deterministic pseudo-random value with `len` limbs.
+    fn pseudo_random(len: usize, seed: u64) -> BigU {
+        let mut state = seed;
+        let limbs = (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                state ^ (state >> 29)
+            })
+            .collect();
+        BigU::from_limbs(limbs)
+    }
+
+    #[test]
+    fn test_normalization_and_conversion() {
+        assert_eq!(BigU::from_limbs(vec![5, 0, 0]), BigU::from_u64(5));
+        assert!(BigU::from_u64(0).is_zero());
+        assert_eq!(BigU::from_u128(u128::MAX).to_u128(), Some(u128::MAX));
+        assert_eq!(BigU::from_u128(u128::MAX).bits(), 128);
+        assert_eq!(BigU::from_limbs(vec![0, 0, 1]).to_u128(), None);
+    }
+
+    #[test]
+    fn test_display_and_parse() {
+        let n = big("340282366920938463463374607431768211456"); // 2^128
+        assert_eq!(n, BigU::one().shl_bits(128));
+        assert_eq!(n.to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(BigU::zero().to_string(), "0");
+        assert_eq!(
+            big("10000000000000000000").to_string(),
+            "10000000000000000000"
+        );
+        assert_eq!("12a".parse::<BigU>(), Err(MathError::OutOfRange));
+        assert_eq!("".parse::<BigU>(), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_add_sub() {
+        let a = BigU::from_u128(u128::MAX);
+        let sum = &a + &BigU::one();
+        assert_eq!(sum, BigU::one().shl_bits(128));
+        assert_eq!(&sum - &BigU::one(), a);
+        assert_eq!(BigU::one().checked_sub(&a), None);
+    }
+
+    #[test]
+    fn test_mul_small() {
+        let a = BigU::from_u64(u64::MAX);
+        assert_eq!(
+            (&a * &a).to_u128(),
+            Some(u64::MAX as u128 * u64::MAX as u128)
+        );
+        assert_eq!(&a * &BigU::zero(), BigU::zero());
+    }
+
+    #[test]
+    fn test_karatsuba_matches_schoolbook() {
+        for (len_a, len_b) in [(40, 40), (64, 33), (100, 70), (129, 128)] {
+            let a = pseudo_random(len_a, len_a as u64);
+            let b = pseudo_random(len_b, 7 * len_b as u64);
+            let fast = &a * &b;
+            let slow = BigU::from_limbs(mul_schoolbook(&a.limbs, &b.limbs));
+            assert_eq!(fast, slow, "{} x {} limbs", len_a, len_b);
+        }
+    }
+
+    #[test]
+    fn test_div_rem_identity() {
+        for (len_u, len_v) in [(2, 1), (4, 2), (10, 3), (50, 20), (80, 79)] {
+            let u = pseudo_random(len_u, 11 * len_u as u64);
+            let v = pseudo_random(len_v, 13 * len_v as u64);
+            let (q, r) = u.div_rem(&v).unwrap();
+            assert!(r < v);
+            assert_eq!(&(&q * &v) + &r, u);
+        }
+    }
+
+    #[test]
+    fn test_div_rem_edge_cases() {
+        let a = big("123456789012345678901234567890");
+        assert_eq!(a.div_rem(&BigU::zero()), Err(MathError::DivisionByZero));
+        assert_eq!(
+            BigU::from_u64(3).div_rem(&a),
+            Ok((BigU::zero(), BigU::from_u64(3)))
+        );
+        assert_eq!(a.div_rem(&a), Ok((BigU::one(), BigU::zero())));
+        let (q, r) = a.div_rem(&big("1000000000000000000000")).unwrap();
+        assert_eq!(q, big("123456789"));
+        assert_eq!(r, big("12345678901234567890"));
+    }
+
+    #[test]
+    fn test_pow_and_shifts() {
+        assert_eq!(BigU::from_u64(2).pow(200), BigU::one().shl_bits(200));
+        assert_eq!(
+            BigU::one().shl_bits(200).shr_bits(190),
+            BigU::from_u64(1024)
+        );
+        assert_eq!(BigU::from_u64(5).shr_bits(64), BigU::zero());
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        let m = BigU::from_u64(1_000_000_007);
+        let r = BigU::from_u64(2).mod_pow(&BigU::from_u64(1_000_000_006), &m);
+        assert_eq!(r, Ok(BigU::one()));
+        assert_eq!(
+            BigU::from_u64(3).mod_pow(&BigU::zero(), &BigU::one()),
+            Ok(BigU::zero())
+        );
+    }
+
+    /// Lucas-Lehmer: M_p = 2^p - 1 is prime iff s_(p-2) = 0, with s_0 = 4, s_k = s² - 2.
+    fn lucas_lehmer(p: u64) -> bool {
+        let m = &BigU::one().shl_bits(p) - &BigU::one();
+        let two = BigU::from_u64(2);
+        let mut s = BigU::from_u64(4);
+        for _ in 0..p - 2 {
+            s = &(&(&s * &s) + &m) - &two;
+            s = &s % &m;
+        }
+        s.is_zero()
+    }
+
+    #[test]
+    fn test_lucas_lehmer() {
+        assert!(lucas_lehmer(61));
+        assert!(lucas_lehmer(127));
+        assert!(lucas_lehmer(521));
+        assert!(!lucas_lehmer(67));
+        assert!(!lucas_lehmer(523));
+    }
+
+    #[test]
+    fn test_pepin() {
+        // F_n = 2^(2^n) + 1 is prime iff 3^((F_n - 1)/2) ≡ -1 (mod F_n)
+        let pepin = |n: u32| {
+            let f = &BigU::one().shl_bits(1 << n) + &BigU::one();
+            let exp = BigU::one().shl_bits((1 << n) - 1);
+            BigU::from_u64(3).mod_pow(&exp, &f).unwrap() == &f - &BigU::one()
+        };
+        assert!(pepin(4));
+        assert!(!pepin(5));
+        assert!(!pepin(7));
+    }
+
+    #[test]
+    fn test_product_tree_with_bigu() {
+        use crate::algebra::product_tree::{product_tree, remainder_tree};
+
+        let moduli: Vec<BigU> = [u64::MAX, u64::MAX - 58, u64::MAX - 82]
+            .into_iter()
+            .map(BigU::from_u64)
+            .collect();
+        let tree = product_tree(&moduli).unwrap();
+        let n = BigU::from_u64(7).pow(100);
+        let expected: Vec<BigU> = moduli.iter().map(|m| &n % m).collect();
+        assert_eq!(remainder_tree(&n, &tree), Ok(expected));
+    }
+
+    fn euclid(a: &BigU, b: &BigU) -> BigU {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        while !b.is_zero() {
+            let r = &a % &b;
+            (a, b) = (b, r);
+        }
+        a
+    }
+
+    #[test]
+    fn test_gcd_matches_euclid() {
+        for (len_a, len_b, len_g) in [
+            (1, 1, 1),
+            (20, 3, 2),
+            (40, 40, 1),
+            (70, 64, 9),
+            (200, 190, 30),
+        ] {
+            for seed in 0..4 {
+                let g = pseudo_random(len_g, seed + 99);
+                let a = &g * &pseudo_random(len_a, 17 * seed + len_a as u64);
+                let b = &g * &pseudo_random(len_b, 31 * seed + 1);
+                assert_eq!(a.gcd(&b), euclid(&a, &b), "{len_a} {len_b} {seed}");
+                assert_eq!(b.gcd(&a), a.gcd(&b));
+            }
+        }
+        let a = pseudo_random(100, 5);
+        assert_eq!(a.gcd(&BigU::zero()), a);
+        assert_eq!(BigU::zero().gcd(&a), a);
+        assert_eq!(a.gcd(&a), a);
+        assert_eq!(BigU::zero().gcd(&BigU::zero()), BigU::zero());
+    }
+
+    #[test]
+    fn test_gcd_fibonacci() {
+        // gcd(F_m, F_n) = F_gcd(m, n); consecutive terms are Euclid's worst case
+        let mut fib = vec![BigU::zero(), BigU::one()];
+        for i in 2..=6000 {
+            fib.push(&fib[i - 1] + &fib[i - 2]);
+        }
+        assert_eq!(fib[6000].gcd(&fib[5999]), BigU::one());
+        assert_eq!(fib[6000].gcd(&fib[4500]), fib[1500]);
+        assert_eq!(fib[5929].gcd(&fib[3773]), fib[539]);
+    }
+
+    #[test]
+    fn test_half_gcd_reduces() {
+        for seed in 0..8 {
+            let a = pseudo_random(48, seed);
+            let b = pseudo_random(48 - seed as usize % 3, seed + 100);
+            let (matrix, x, y) = half_gcd(&a, &b).unwrap();
+            let [[m00, m01], [m10, m11]] = &matrix.m;
+            assert_eq!(&(m00 * &x) + &(m01 * &y), a);
+            assert_eq!(&(m10 * &x) + &(m11 * &y), b);
+            // stops with both above 2^s and their difference below
+            let s = a.bits().max(b.bits()) / 2 + 1;
+            assert!(x.bits() > s && y.bits() > s);
+            let diff = if x > y { &x - &y } else { &y - &x };
+            assert!(diff.bits() <= s);
+        }
+        let small = BigU::one().shl_bits(10);
+        assert_eq!(half_gcd(&pseudo_random(48, 1), &small), None);
+    }
+
+    #[test]
+    fn test_generic_gcd_with_bigu() {
+        use crate::algebra::gcd::gcd_generic;
+        use crate::algebra::lcm::lcm_generic;
+        use crate::algebra::prime_nbr::is_prime_generic;
+
+        // 2^127 - 1 times two different cofactors, past u128 once multiplied
+        let m127 = BigU::one().shl_bits(127) - BigU::one();
+        let a = &m127 * &BigU::from_u64(6);
+        let b = &m127 * &BigU::from_u64(10);
+        assert_eq!(
+            gcd_generic(a.clone(), b.clone()),
+            Ok(&m127 * &BigU::from_u64(2))
+        );
+        assert_eq!(lcm_generic(a, b), Ok(&m127 * &BigU::from_u64(30)));
+        assert_eq!(
+            gcd_generic(m127, BigU::zero()),
+            Err(MathError::DivisionByZero)
+        );
+        assert_eq!(is_prime_generic(BigU::from_u64(1_000_000_007)), Ok(true));
+        assert_eq!(
+            is_prime_generic(BigU::one().shl_bits(64)),
+            Err(MathError::OutOfRange)
+        );
+    }
+}
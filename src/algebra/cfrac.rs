@@ -0,0 +1,201 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::continued_fraction::sqrt_continued_fraction;
+use crate::algebra::gcd::gcd_u128;
+use crate::algebra::gf2::BitMatrix;
+use crate::algebra::prime_nbr::{miller_rabin_u128, mul_mod_u128, pow_mod_u128};
+use crate::errors::MathError;
+use crate::sequences::sequence::{Primes, Sequence};
+
+const PRIME_MR_ROUNDS: u32 = 20;
+/// Relations collected beyond the factor base size before each elimination attempt.
+const EXTRA_RELATIONS: usize = 8;
+/// Surplus relations after which a multiplier is abandoned.
+const MAX_SURPLUS: usize = 64;
+/// Squarefree multipliers `k`, tried in order when `√(k·n)` gives no factor.
+const MULTIPLIERS: [u128; 8] = [1, 3, 5, 7, 11, 13, 2, 6];
+
+/// A smooth relation `x² ≡ ±∏ pᵢ^eᵢ (mod n)`; `exponents[0]` is the exponent of -1.
+struct Relation {
+    x: u128,
+    exponents: Vec<u32>,
+}
+
+/// Continued fraction factorization (Morrison-Brillhart, 1975).
+///
+/// Returns a non-trivial factor of the composite `n`. The convergents `A_{k-1}/B_{k-1}`
+/// of `√n` satisfy `A_{k-1}² ≡ (-1)^k q_k (mod n)` with `q_k < 2√n`, so the `q_k` are
+/// far more often smooth than random residues. Relations whose `q_k` factor over the
+/// first `factor_base_size` primes with `(n/p) ≠ -1` are combined by Gaussian
+/// elimination over GF(2) into a congruence of squares `X² ≡ Y² (mod n)`, and
+/// `gcd(X - Y, n)` is tried for each dependency.
+///
+/// When `√n` has a short period (e.g. `n = m² + 1`) the expansion of `√(k·n)` is used
+/// instead, for a few small multipliers `k`; each gets at most `max_terms` partial
+/// quotients. Errors:
+/// - `OutOfRange` if `n < 4`, `n` is prime, or `n >= 2^122`
+/// - `PositifIntegerRequired` if `factor_base_size == 0`
+/// - `NoSolution` if no multiplier yields a useful congruence
+///
+/// Sits between trial division and sieving methods: fine up to ~30 digits.
+pub fn cfrac(n: u128, factor_base_size: usize, max_terms: usize) -> Result<u128, MathError> {
+    if n < 4 || miller_rabin_u128(n, PRIME_MR_ROUNDS)?.is_prime() || n >= 1 << 122 {
+        return Err(MathError::OutOfRange);
+    }
+    if factor_base_size == 0 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    if n.is_multiple_of(2) {
+        return Ok(2);
+    }
+    let root = n.isqrt();
+    if root * root == n {
+        return Ok(root);
+    }
+
+    for k in MULTIPLIERS {
+        if let Some(factor) = cfrac_with_multiplier(n, k, factor_base_size, max_terms)? {
+            return Ok(factor);
+        }
+    }
+    Err(MathError::NoSolution)
+}
+
+/// One CFRAC run on the expansion of `√(k·n)`; `None` if it gives no factor of `n`.
+fn cfrac_with_multiplier(
+    n: u128,
+    k: u128,
+    factor_base_size: usize,
+    max_terms: usize,
+) -> Result<Option<u128>, MathError> {
+    let kn = k * n;
+    let root = kn.isqrt();
+    if root * root == kn {
+        return Ok(None);
+    }
+
+    // primes p with kn a square mod p: only they can divide some q_k
+    let mut factor_base = Vec::with_capacity(factor_base_size);
+    for p in Primes.iter() {
+        if factor_base.len() == factor_base_size {
+            break;
+        }
+        if n.is_multiple_of(p) {
+            return Ok(Some(p));
+        }
+        if p == 2 || k.is_multiple_of(p) || pow_mod_u128(kn % p, (p - 1) / 2, p) == 1 {
+            factor_base.push(p as u64);
+        }
+    }
+
+    let columns = factor_base.len() + 1;
+    let mut target = columns + EXTRA_RELATIONS;
+    let mut relations = Vec::new();
+    // A_{i-2} and A_{i-1} mod n, starting from A_{-1} = 1 and A_0 = a₀
+    let (mut a_prev, mut a_cur) = (1, root % n);
+
+    for (i, term) in sqrt_continued_fraction(kn)?
+        .enumerate()
+        .skip(1)
+        .take(max_terms)
+    {
+        // q_i < 2√(kn) < 2^64
+        if let Some(mut exponents) = factor_over(term.q as u64, &factor_base) {
+            exponents[0] = (i % 2) as u32;
+            relations.push(Relation {
+                x: a_cur,
+                exponents,
+            });
+            if relations.len() >= target {
+                if let Some(factor) = combine(n, &factor_base, &relations) {
+                    return Ok(Some(factor));
+                }
+                if relations.len() >= columns + MAX_SURPLUS {
+                    return Ok(None);
+                }
+                target += EXTRA_RELATIONS;
+            }
+        }
+        let next = (mul_mod_u128(term.a % n, a_cur, n) + a_prev) % n;
+        (a_prev, a_cur) = (a_cur, next);
+    }
+
+    Ok(combine(n, &factor_base, &relations))
+}
+
+/// Exponent vector of `q` over the factor base (slot 0 left for the sign), if `q` is smooth.
+fn factor_over(mut q: u64, factor_base: &[u64]) -> Option<Vec<u32>> {
+    let mut exponents = vec![0; factor_base.len() + 1];
+    for (i, &p) in factor_base.iter().enumerate() {
+        while q.is_multiple_of(p) {
+            q /= p;
+            exponents[i + 1] += 1;
+        }
+    }
+    (q == 1).then_some(exponents)
+}
+
+/// Tries every GF(2) dependency among the relations for a congruence of squares.
+fn combine(n: u128, factor_base: &[u64], relations: &[Relation]) -> Option<u128> {
+    let mut matrix = BitMatrix::new(relations.len(), factor_base.len() + 1);
+    for (r, relation) in relations.iter().enumerate() {
+        for (c, &e) in relation.exponents.iter().enumerate() {
+            matrix.set(r, c, e % 2 == 1);
+        }
+    }
+
+    matrix.left_kernel().into_iter().find_map(|dependency| {
+        let mut x = 1;
+        let mut exponents = vec![0u128; factor_base.len()];
+        for &r in &dependency {
+            x = mul_mod_u128(x, relations[r].x, n);
+            for (sum, &e) in exponents.iter_mut().zip(&relations[r].exponents[1..]) {
+                *sum += e as u128;
+            }
+        }
+        // the sign exponent is even, so the product of the q_k is the square Y²
+        let y = factor_base.iter().zip(&exponents).fold(1, |y, (&p, &e)| {
+            mul_mod_u128(y, pow_mod_u128(p as u128, e / 2, n), n)
+        });
+
+        let factor = gcd_u128(if x >= y { x - y } else { x + n - y }, n);
+        (factor > 1 && factor < n).then_some(factor)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_semiprimes() {
+        for (n, p, q) in [
+            (8051, 83, 97),
+            (10_403, 101, 103),
+            (1_000_730_021, 10_007, 100_003),
+        ] {
+            let factor = cfrac(n, 20, 10_000).unwrap();
+            assert!(factor == p || factor == q, "{n}: got {factor}");
+        }
+    }
+
+    #[test]
+    fn test_sixth_fermat_number() {
+        // F6 = 2^64 + 1 = 274177 · 67280421310721
+        let n = (1u128 << 64) + 1;
+        let factor = cfrac(n, 200, 1_000_000).unwrap();
+        assert_eq!(n % factor, 0);
+        assert!(factor == 274_177 || factor == 67_280_421_310_721);
+    }
+
+    #[test]
+    fn test_trivial_cases_and_errors() {
+        assert_eq!(cfrac(1_000_006, 10, 100), Ok(2));
+        assert_eq!(cfrac(10_201, 10, 100), Ok(101));
+        assert_eq!(cfrac(3 * 1_000_003, 10, 100), Ok(3));
+        assert_eq!(cfrac(1_000_003, 10, 100), Err(MathError::OutOfRange));
+        assert_eq!(cfrac(3, 10, 100), Err(MathError::OutOfRange));
+        assert_eq!(cfrac(8051, 0, 100), Err(MathError::PositifIntegerRequired));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::algebra::prime_nbr::sieve;
+
+/// Chebyshev's first function `θ(x) = Σ ln p` over the primes `p <= x`:
+/// - `chebyshev_theta(10) = ln(2·3·5·7) = ln 210`
+///
+/// The prime number theorem is equivalent to `θ(x) ~ x`. Primes come from `sieve`, so
+/// time and memory are O(x). Each logarithm is within an ulp and the terms are added
+/// with Neumaier's compensated summation, which keeps the relative error near `1e-15`
+/// instead of growing with the number of primes.
+pub fn chebyshev_theta(x: usize) -> f64 {
+    let mut sum = CompensatedSum::default();
+    for (p, _) in sieve(x).iter().enumerate().filter(|&(_, &prime)| prime) {
+        sum.add((p as f64).ln());
+    }
+    sum.value()
+}
+
+/// Chebyshev's second function `ψ(x) = Σ ln p` over the prime powers `p^k <= x`, which
+/// is also `ln lcm(1, 2, ..., x)`:
+/// - `chebyshev_psi(10) = ln 2520`
+///
+/// Each prime contributes `⌊log_p x⌋ · ln p`, found by exact integer powers. Same cost
+/// and precision as `chebyshev_theta`.
+pub fn chebyshev_psi(x: usize) -> f64 {
+    let mut sum = CompensatedSum::default();
+    for (p, _) in sieve(x).iter().enumerate().filter(|&(_, &prime)| prime) {
+        let mut k = 1;
+        let mut power = p;
+        while let Some(next) = power.checked_mul(p).filter(|&next| next <= x) {
+            power = next;
+            k += 1;
+        }
+        sum.add(k as f64 * (p as f64).ln());
+    }
+    sum.value()
+}
+
+/// Neumaier's variant of Kahan summation: the rounding error of every addition is
+/// carried separately and added back at the end.
+#[derive(Default)]
+struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    fn add(&mut self, term: f64) {
+        let total = self.sum + term;
+        self.compensation += if self.sum.abs() >= term.abs() {
+            (self.sum - total) + term
+        } else {
+            (term - total) + self.sum
+        };
+        self.sum = total;
+    }
+
+    fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lcm_upto(x: u128) -> u128 {
+        (1..=x).fold(1, |l, k| {
+            let (mut a, mut b) = (l, k);
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            l / a * k
+        })
+    }
+
+    #[test]
+    fn test_small_values() {
+        assert_eq!(chebyshev_theta(0), 0.0);
+        assert_eq!(chebyshev_theta(1), 0.0);
+        assert_eq!(chebyshev_psi(1), 0.0);
+        assert!((chebyshev_theta(10) - 210f64.ln()).abs() < 1e-14);
+        assert!((chebyshev_psi(10) - 2520f64.ln()).abs() < 1e-14);
+        // ψ(x) = ln lcm(1..x), checked while the lcm fits in u128
+        for x in 2..=80 {
+            let exact = (lcm_upto(x) as f64).ln();
+            assert!((chebyshev_psi(x as usize) - exact).abs() < 1e-12, "x = {x}");
+        }
+    }
+
+    #[test]
+    fn test_large_values() {
+        // reference values computed with 30 significant digits
+        let theta = chebyshev_theta(1_000_000);
+        assert!((theta - 998_484.175_025_634_3).abs() < 1e-8);
+        let psi = chebyshev_psi(1_000_000);
+        assert!((psi - 999_586.597_495_633).abs() < 1e-8);
+        // ψ - θ is dominated by θ(√x)
+        assert!(psi - theta > chebyshev_theta(1000));
+    }
+}
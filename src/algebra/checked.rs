@@ -0,0 +1,113 @@
+use crate::errors::{ComputeError, MathError};
+
+/// `Σ aᵢ·bᵢ` in `i64`:
+/// - `checked_dot(&[1, 2, 3], &[4, 5, 6]) = 32`
+///
+/// Accumulates left to right; `ComputeError::OverflowAt(i)` names the first index whose
+/// product or running sum leaves `i64`, even if later terms would bring the total back.
+/// `DimensionMismatch` if the slices differ in length; an empty dot product is 0.
+pub fn checked_dot(a: &[i64], b: &[i64]) -> Result<i64, MathError> {
+    if a.len() != b.len() {
+        return Err(MathError::DimensionMismatch);
+    }
+    accumulate(a.iter().zip(b).map(|(&x, &y)| (x, y)))
+}
+
+/// `Σ wᵢ·vᵢ` over `(weight, value)` pairs, with the overflow rule of `checked_dot`:
+/// - `checked_weighted_sum(&[(2, 10), (-1, 7), (3, 1)]) = 16`
+pub fn checked_weighted_sum(terms: &[(i64, i64)]) -> Result<i64, MathError> {
+    accumulate(terms.iter().copied())
+}
+
+/// `Σ coeffs[i]·x^i` by Horner's rule, coefficients from the constant term upwards as in
+/// `Polynomial::new`:
+/// - `horner_checked(&[1, 0, 3], 2) = 13`
+///
+/// Evaluated from the leading coefficient down, so `ComputeError::OverflowAt(i)` is the
+/// index of the coefficient being folded in when `acc·x + coeffs[i]` leaves `i64`. An
+/// empty slice evaluates to 0.
+pub fn horner_checked(coeffs: &[i64], x: i64) -> Result<i64, MathError> {
+    coeffs
+        .iter()
+        .enumerate()
+        .rev()
+        .try_fold(0i64, |acc, (i, &c)| {
+            acc.checked_mul(x)
+                .and_then(|v| v.checked_add(c))
+                .ok_or(MathError::Compute(ComputeError::OverflowAt(i)))
+        })
+}
+
+fn accumulate(terms: impl Iterator<Item = (i64, i64)>) -> Result<i64, MathError> {
+    terms.enumerate().try_fold(0i64, |acc, (i, (x, y))| {
+        x.checked_mul(y)
+            .and_then(|p| acc.checked_add(p))
+            .ok_or(MathError::Compute(ComputeError::OverflowAt(i)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_dot() {
+        assert_eq!(checked_dot(&[1, 2, 3], &[4, 5, 6]), Ok(32));
+        assert_eq!(checked_dot(&[], &[]), Ok(0));
+        assert_eq!(checked_dot(&[-3, 7], &[5, -2]), Ok(-29));
+        assert_eq!(
+            checked_dot(&[1, 2], &[1]),
+            Err(MathError::DimensionMismatch)
+        );
+        // the product of index 1 overflows
+        assert_eq!(
+            checked_dot(&[1, i64::MAX, 0], &[1, 2, 0]),
+            Err(MathError::Compute(ComputeError::OverflowAt(1)))
+        );
+        // the running sum overflows at index 2, although index 3 would cancel it
+        assert_eq!(
+            checked_dot(&[i64::MAX, 0, 1, -1], &[1, 5, 1, 1]),
+            Err(MathError::Compute(ComputeError::OverflowAt(2)))
+        );
+        assert_eq!(checked_dot(&[i64::MIN, 1], &[1, 0]), Ok(i64::MIN));
+    }
+
+    #[test]
+    fn test_checked_weighted_sum() {
+        assert_eq!(checked_weighted_sum(&[(2, 10), (-1, 7), (3, 1)]), Ok(16));
+        assert_eq!(checked_weighted_sum(&[]), Ok(0));
+        assert_eq!(
+            checked_weighted_sum(&[(1, 1), (-1, i64::MIN)]),
+            Err(MathError::Compute(ComputeError::OverflowAt(1)))
+        );
+    }
+
+    #[test]
+    fn test_horner_checked() {
+        assert_eq!(horner_checked(&[1, 0, 3], 2), Ok(13));
+        assert_eq!(horner_checked(&[], 5), Ok(0));
+        assert_eq!(horner_checked(&[7], i64::MAX), Ok(7));
+        // 2^62 fits, 2^63 does not
+        let mut coeffs = [0i64; 64];
+        coeffs[62] = 1;
+        assert_eq!(horner_checked(&coeffs[..63], 2), Ok(1 << 62));
+        coeffs[62] = 0;
+        coeffs[63] = 1;
+        assert_eq!(
+            horner_checked(&coeffs, 2),
+            Err(MathError::Compute(ComputeError::OverflowAt(0)))
+        );
+        // -2^63 is reachable, one less overflows while adding the constant term
+        coeffs[63] = -1;
+        assert_eq!(horner_checked(&coeffs, 2), Ok(i64::MIN));
+        coeffs[0] = -1;
+        assert_eq!(
+            horner_checked(&coeffs, 2),
+            Err(MathError::Compute(ComputeError::OverflowAt(0)))
+        );
+        for x in -5i64..=5 {
+            let expected = 4 - 3 * x + 2 * x * x * x;
+            assert_eq!(horner_checked(&[4, -3, 0, 2], x), Ok(expected));
+        }
+    }
+}
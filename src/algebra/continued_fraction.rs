@@ -0,0 +1,446 @@
+use alloc::vec::Vec;
+
+use crate::algebra::interval::RationalInterval;
+use crate::algebra::polynomial::Polynomial;
+use crate::algebra::rational::Rational;
+use crate::errors::{ComputeError, MathError};
+
+/// One step of the continued fraction of `√N`.
+///
+/// The k-th complete quotient is `(√N + p) / q` and `a = ⌊(√N + p) / q⌋`. The
+/// convergent numerators `A_k` satisfy `A_{k-1}² - N·B_{k-1}² = (-1)^k q_k`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqrtCfTerm {
+    pub a: u128,
+    pub p: u128,
+    pub q: u128,
+}
+
+/// Continued fraction expansion of `√N` for a non-square `N`, as an endless iterator.
+///
+/// Uses exact integer recurrences (`p`, `q` stay below `2√N`):
+/// - `√7 = [2; 1, 1, 1, 4, 1, 1, 1, 4, ...]`
+#[derive(Debug, Clone)]
+pub struct SqrtContinuedFraction {
+    n: u128,
+    a0: u128,
+    next: SqrtCfTerm,
+}
+
+/// Expansion of `√n`; `MathError::OutOfRange` if `n` is a perfect square.
+///
+/// `n` must be below `2^126` so that the recurrences cannot overflow.
+pub fn sqrt_continued_fraction(n: u128) -> Result<SqrtContinuedFraction, MathError> {
+    let a0 = n.isqrt();
+    if a0 * a0 == n || n >= 1 << 126 {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(SqrtContinuedFraction {
+        n,
+        a0,
+        next: SqrtCfTerm { a: a0, p: 0, q: 1 },
+    })
+}
+
+impl Iterator for SqrtContinuedFraction {
+    type Item = SqrtCfTerm;
+
+    fn next(&mut self) -> Option<SqrtCfTerm> {
+        let term = self.next;
+        let p = term.a * term.q - term.p;
+        let q = (self.n - p * p) / term.q;
+        let a = (self.a0 + p) / q;
+        self.next = SqrtCfTerm { a, p, q };
+        Some(term)
+    }
+}
+
+/// Period length of the continued fraction of `√n` (it repeats after `a₀`).
+pub fn sqrt_cf_period(n: u128) -> Result<usize, MathError> {
+    let expansion = sqrt_continued_fraction(n)?;
+    let a0 = expansion.a0;
+    // the period ends with the first partial quotient equal to 2·a₀
+    Ok(expansion.skip(1).position(|t| t.a == 2 * a0).unwrap_or(0) + 1)
+}
+
+/// The partial quotients shared by every number in `x`, at most `terms` of them:
+/// - `cf_of_interval([355/113, 22/7], 5) = [3, 7]`
+/// - `cf_of_interval([22/7, 22/7], 5) = [3, 7]`
+///
+/// While `⌊lo⌋ = ⌊hi⌋`, that is the next quotient `a` of everything in between, and the
+/// interval moves on to `1/(x - a)`. Stops at the first quotient the endpoints disagree
+/// on, when the interval reaches 0 (the expansion may end there), or when an endpoint
+/// leaves `i64`, so every quotient returned is certified.
+pub fn cf_of_interval(x: &RationalInterval, terms: usize) -> Vec<i64> {
+    let mut x = *x;
+    let mut quotients = Vec::new();
+    while quotients.len() < terms {
+        let a = floor(&x.lo());
+        if floor(&x.hi()) != a {
+            break;
+        }
+        quotients.push(a);
+        // `recip` fails exactly when the fractional part can be 0
+        match x
+            .checked_sub(&Rational::from_integer(a).into())
+            .and_then(|frac| frac.recip())
+        {
+            Ok(next) => x = next,
+            Err(_) => break,
+        }
+    }
+    quotients
+}
+
+/// The first `terms` partial quotients of `e = [2; 1, 2, 1, 1, 4, 1, 1, 6, ...]`.
+///
+/// Euler's pattern `[2; 1, 2, 1, 1, 4, ..., 1, 2k, 1, ...]` is a theorem, so unlike
+/// `cf_pi` there is no precision limit and every term is exact.
+pub fn cf_e(terms: usize) -> Vec<i64> {
+    (0..terms)
+        .map(|i| match i {
+            0 => 2,
+            i if i % 3 == 2 => 2 * (i as i64 + 1) / 3,
+            _ => 1,
+        })
+        .collect()
+}
+
+/// The first `terms` partial quotients of `π = [3; 7, 15, 1, 292, 1, 1, ...]`, each one
+/// certified.
+///
+/// Machin's `π = 16·atan(1/5) - 4·atan(1/239)` is summed in `RationalInterval`, each
+/// series bounded by its first omitted term, and expanded with `cf_of_interval`. The
+/// `i64` endpoints pin π down to about `10^-17`, which settles the first 17 quotients:
+/// asking for more errors with `ComputeError::OverflowAt(17)`, the index of the first
+/// quotient the enclosure cannot settle.
+pub fn cf_pi(terms: usize) -> Result<Vec<i64>, MathError> {
+    let sixteen = RationalInterval::point(Rational::from_integer(16));
+    let four = RationalInterval::point(Rational::from_integer(4));
+    let pi = sixteen
+        .checked_mul(&atan_of_inverse(5)?)?
+        .checked_sub(&four.checked_mul(&atan_of_inverse(239)?)?)?;
+    let quotients = cf_of_interval(&pi, terms);
+    match quotients.len() {
+        len if len < terms => Err(MathError::Compute(ComputeError::OverflowAt(len))),
+        _ => Ok(quotients),
+    }
+}
+
+/// Up to `terms` partial quotients of the real root of `poly` in `root_bracket`:
+/// - `x² - 2` on `[1, 2]`: `[1; 2, 2, 2, ...]`
+/// - `x³ - 2` on `[1, 2]`: `[1; 3, 1, 5, 1, 1, 4, 1, 1, 8, ...]`
+///
+/// Lagrange's method, exact in `i128`: with `a = ⌊x⌋` found by bisection on the sign of
+/// `poly` at the integers of the bracket, the remaining expansion is that of the root
+/// `y > 1` of `y^d·poly(a + 1/y)`, an integer polynomial again, with the bracket mapped
+/// along. The coefficients stay bounded for quadratic irrationals, so any number of
+/// terms comes out; for higher degrees they grow with the convergents, and the
+/// expansion stops early, every returned term still exact, once they would leave
+/// `i128`. A rational root gives its finite expansion. If the bracket holds several
+/// roots, the terms are those of one of them. Errors with `OutOfRange` for a constant
+/// polynomial and `NoSolution` if `poly` has the same nonzero sign at both ends.
+pub fn cf_algebraic(
+    poly: &Polynomial<i64>,
+    root_bracket: RationalInterval,
+    terms: usize,
+) -> Result<Vec<i64>, MathError> {
+    if poly.degree().is_none_or(|d| d == 0) {
+        return Err(MathError::OutOfRange);
+    }
+    let mut q: Vec<i128> = poly.coeffs().iter().map(|&c| c as i128).collect();
+    let (mut lo, hi) = (root_bracket.lo(), root_bracket.hi());
+    match (sign_at(&q, &lo)?, sign_at(&q, &hi)?) {
+        (0, _) => return Ok(cf_of_interval(&lo.into(), terms)),
+        (_, 0) => return Ok(cf_of_interval(&hi.into(), terms)),
+        (s, t) if s == t => return Err(MathError::NoSolution),
+        _ => {}
+    }
+    let mut hi = Some(hi);
+    let mut quotients = Vec::new();
+    while quotients.len() < terms {
+        let Ok((a, lo_pt, hi_pt)) = root_floor(&q, &lo, hi) else {
+            break;
+        };
+        quotients.push(a);
+        let (Some(lo_pt), Some(hi_pt)) = (lo_pt, hi_pt) else {
+            // the root is the integer `a`
+            break;
+        };
+        // x in (lo_pt, hi_pt) ⊂ [a, a + 1] becomes y = 1/(x - a) in (1/(hi_pt - a), ...)
+        let shifted = |r: Rational| r.checked_sub(&Rational::from_integer(a));
+        let next = (|| {
+            let lo = shifted(hi_pt)?.recip()?;
+            let hi = match shifted(lo_pt)? {
+                d if d.is_zero() => None,
+                d => Some(d.recip()?),
+            };
+            let mut q = taylor_shift(&q, a)?;
+            q.reverse();
+            Ok::<_, MathError>((q, lo, hi))
+        })();
+        match next {
+            Ok(next) => (q, lo, hi) = next,
+            Err(_) => break,
+        }
+    }
+    Ok(quotients)
+}
+
+fn floor(x: &Rational) -> i64 {
+    x.numer().div_euclid(x.denom())
+}
+
+/// `atan(1/n)`, enclosing the alternating series tail by its first omitted term, which
+/// is below `1/i64::MAX` once the term denominators stop fitting.
+fn atan_of_inverse(n: i64) -> Result<RationalInterval, MathError> {
+    let mut sum = RationalInterval::point(Rational::ZERO);
+    for j in 0u32.. {
+        let Some(denom) = n
+            .checked_pow(2 * j + 1)
+            .and_then(|p| p.checked_mul(2 * j as i64 + 1))
+        else {
+            break;
+        };
+        let term = RationalInterval::point(Rational::new(1, denom)?);
+        sum = match j % 2 {
+            0 => sum.checked_add(&term)?,
+            _ => sum.checked_sub(&term)?,
+        };
+    }
+    let tail = Rational::new(1, i64::MAX)?;
+    sum.checked_add(&RationalInterval::new(tail.checked_neg()?, tail)?)
+}
+
+/// Sign of `Σ q[i]·x^i`, from `Σ q[i]·n^i·d^(deg-i)` for `x = n/d`.
+fn sign_at(q: &[i128], x: &Rational) -> Result<i8, MathError> {
+    let (n, d) = (x.numer() as i128, x.denom() as i128);
+    let mut acc = *q.last().ok_or(MathError::EmptyInput)?;
+    let mut d_power = 1i128;
+    for &c in q.iter().rev().skip(1) {
+        d_power = d_power.checked_mul(d).ok_or(MathError::Overflow)?;
+        acc = acc
+            .checked_mul(n)
+            .and_then(|acc| acc.checked_add(c.checked_mul(d_power)?))
+            .ok_or(MathError::Overflow)?;
+    }
+    Ok(acc.signum() as i8)
+}
+
+/// `⌊x⌋` and the two points of the sign change around `x`, from `root_floor`.
+type RootFloor = (i64, Option<Rational>, Option<Rational>);
+
+/// `⌊x⌋` for a root `x` of `q` between `lo` and `hi` (`None` for +∞), across which `q`
+/// changes sign, with the two points of the last sign change bisected to; `(a, None,
+/// None)` if `q` vanishes at the integer `a`.
+fn root_floor(q: &[i128], lo: &Rational, hi: Option<Rational>) -> Result<RootFloor, MathError> {
+    let s_lo = sign_at(q, lo)?;
+    let (mut lo_pt, mut hi_pt) = (*lo, hi);
+    // the integers strictly between lo and hi, searched for the sign change
+    let mut left = floor(lo).checked_add(1).ok_or(MathError::Overflow)?;
+    let mut right = match hi {
+        Some(hi) => -floor(&hi.checked_neg()?) - 1,
+        None => {
+            let mut m = left.max(1);
+            loop {
+                match sign_at(q, &Rational::from_integer(m))? {
+                    0 => return Ok((m, None, None)),
+                    s if s != s_lo => break,
+                    _ => {}
+                }
+                (lo_pt, left) = (Rational::from_integer(m), m + 1);
+                m = m.checked_mul(2).ok_or(MathError::Overflow)?;
+            }
+            hi_pt = Some(Rational::from_integer(m));
+            m - 1
+        }
+    };
+    while left <= right {
+        let mid = left + (right - left) / 2;
+        match sign_at(q, &Rational::from_integer(mid))? {
+            0 => return Ok((mid, None, None)),
+            s if s == s_lo => (lo_pt, left) = (Rational::from_integer(mid), mid + 1),
+            _ => (hi_pt, right) = (Some(Rational::from_integer(mid)), mid - 1),
+        }
+    }
+    Ok((floor(&lo_pt), Some(lo_pt), hi_pt))
+}
+
+/// Coefficients of `q(a + t)`, by repeated synthetic division.
+fn taylor_shift(q: &[i128], a: i64) -> Result<Vec<i128>, MathError> {
+    let mut c = q.to_vec();
+    let n = c.len() - 1;
+    for i in 0..n {
+        for j in (i..n).rev() {
+            c[j] = (a as i128)
+                .checked_mul(c[j + 1])
+                .and_then(|t| c[j].checked_add(t))
+                .ok_or(MathError::Overflow)?;
+        }
+    }
+    Ok(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_sqrt_7() {
+        let quotients: Vec<u128> = sqrt_continued_fraction(7)
+            .unwrap()
+            .take(9)
+            .map(|t| t.a)
+            .collect();
+        assert_eq!(quotients, vec![2, 1, 1, 1, 4, 1, 1, 1, 4]);
+    }
+
+    #[test]
+    fn test_sqrt_2_and_periods() {
+        let quotients: Vec<u128> = sqrt_continued_fraction(2)
+            .unwrap()
+            .take(5)
+            .map(|t| t.a)
+            .collect();
+        assert_eq!(quotients, vec![1, 2, 2, 2, 2]);
+        assert_eq!(sqrt_cf_period(2), Ok(1));
+        assert_eq!(sqrt_cf_period(7), Ok(4));
+        assert_eq!(sqrt_cf_period(13), Ok(5));
+        assert_eq!(sqrt_cf_period(94), Ok(16));
+    }
+
+    #[test]
+    fn test_perfect_square_rejected() {
+        assert!(sqrt_continued_fraction(49).is_err());
+        assert_eq!(sqrt_cf_period(0), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_convergent_identity() {
+        // A_{k-1}² - N·B_{k-1}² = (-1)^k q_k
+        let n = 1_000_003i128;
+        let terms: Vec<SqrtCfTerm> = sqrt_continued_fraction(n as u128)
+            .unwrap()
+            .take(12)
+            .collect();
+        let (mut a_prev, mut a) = (1i128, terms[0].a as i128);
+        let (mut b_prev, mut b) = (0i128, 1i128);
+        for (k, term) in terms.iter().enumerate().skip(1) {
+            let sign = if k % 2 == 0 { 1 } else { -1 };
+            assert_eq!(a * a - n * b * b, sign * term.q as i128);
+            (a_prev, a) = (a, term.a as i128 * a + a_prev);
+            (b_prev, b) = (b, term.a as i128 * b + b_prev);
+        }
+    }
+
+    fn bracket(lo: i64, hi: i64) -> RationalInterval {
+        RationalInterval::new(Rational::from_integer(lo), Rational::from_integer(hi)).unwrap()
+    }
+
+    #[test]
+    fn test_cf_of_interval() {
+        let r = |n, d| Rational::new(n, d).unwrap();
+        let both = RationalInterval::new(r(355, 113), r(22, 7)).unwrap();
+        assert_eq!(cf_of_interval(&both, 5), vec![3, 7]);
+        assert_eq!(cf_of_interval(&r(22, 7).into(), 5), vec![3, 7]);
+        assert_eq!(cf_of_interval(&r(-7, 2).into(), 5), vec![-4, 2]);
+        assert_eq!(cf_of_interval(&bracket(2, 3), 5), vec![]);
+        assert_eq!(cf_of_interval(&r(355, 113).into(), 2), vec![3, 7]);
+    }
+
+    #[test]
+    fn test_cf_e() {
+        assert_eq!(cf_e(10), vec![2, 1, 2, 1, 1, 4, 1, 1, 6, 1]);
+        assert_eq!(cf_e(0), vec![]);
+        // the pattern against an enclosure of Σ 1/k!, tail below 1/i64::MAX past 20!
+        let mut e = RationalInterval::point(Rational::ZERO);
+        let mut factorial = 1i64;
+        for k in 0..=20 {
+            factorial *= k.max(1);
+            e = e
+                .checked_add(&Rational::new(1, factorial).unwrap().into())
+                .unwrap();
+        }
+        let tail = RationalInterval::new(Rational::ZERO, Rational::new(1, i64::MAX).unwrap());
+        let e = e.checked_add(&tail.unwrap()).unwrap();
+        let certified = cf_of_interval(&e, 40);
+        assert!(certified.len() >= 15);
+        assert_eq!(certified, cf_e(certified.len()));
+    }
+
+    #[test]
+    fn test_cf_pi() {
+        let expected = [3, 7, 15, 1, 292, 1, 1, 1, 2, 1, 3, 1, 14, 2, 1, 1, 2];
+        assert_eq!(cf_pi(5).unwrap(), expected[..5]);
+        assert_eq!(cf_pi(17).unwrap(), expected);
+        assert_eq!(cf_pi(0), Ok(Vec::new()));
+        // past what the enclosure settles
+        let past = Err(MathError::Compute(ComputeError::OverflowAt(17)));
+        assert_eq!(cf_pi(18), past);
+        assert_eq!(cf_pi(100), past);
+    }
+
+    #[test]
+    fn test_cf_algebraic() {
+        let poly = |c: Vec<i64>| Polynomial::new(c);
+        let sqrt2 = cf_algebraic(&poly(vec![-2, 0, 1]), bracket(1, 2), 200).unwrap();
+        assert_eq!(sqrt2.len(), 200);
+        assert!(sqrt2[0] == 1 && sqrt2[1..].iter().all(|&a| a == 2));
+        assert_eq!(
+            cf_algebraic(&poly(vec![-2, 0, 1]), bracket(-2, -1), 5),
+            Ok(vec![-2, 1, 1, 2, 2])
+        );
+        // a narrow bracket works as well as the integer one
+        let narrow = RationalInterval::new(
+            Rational::new(141, 100).unwrap(),
+            Rational::new(142, 100).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            cf_algebraic(&poly(vec![-2, 0, 1]), narrow, 30),
+            cf_algebraic(&poly(vec![-2, 0, 1]), bracket(1, 2), 30)
+        );
+        // golden ratio
+        assert_eq!(
+            cf_algebraic(&poly(vec![-1, -1, 1]), bracket(1, 2), 50),
+            Ok(vec![1; 50])
+        );
+        // cube root of 2 (OEIS A002945), until the coefficients leave i128
+        let cbrt2 = cf_algebraic(&poly(vec![-2, 0, 0, 1]), bracket(1, 2), 500).unwrap();
+        assert_eq!(
+            cbrt2[..20],
+            [
+                1, 3, 1, 5, 1, 1, 4, 1, 1, 8, 1, 14, 1, 10, 2, 1, 4, 12, 2, 3
+            ]
+        );
+        assert_eq!(cbrt2[35], 534);
+        assert!(cbrt2.len() > 40 && cbrt2.len() < 500);
+        // plastic number, root of x³ - x - 1 (OEIS A072117)
+        assert_eq!(
+            cf_algebraic(&poly(vec![-1, -1, 0, 1]), bracket(0, 5), 13),
+            Ok(vec![1, 3, 12, 1, 1, 3, 2, 3, 2, 4, 2, 141, 80])
+        );
+    }
+
+    #[test]
+    fn test_cf_algebraic_rational_roots() {
+        let poly = |c: Vec<i64>| Polynomial::new(c);
+        assert_eq!(
+            cf_algebraic(&poly(vec![-3, 2]), bracket(0, 5), 10),
+            Ok(vec![1, 2])
+        );
+        // (x - 1)(x - 2)(x - 3) with a root at the bisection point, or on the bracket
+        let cubic = poly(vec![-6, 11, -6, 1]);
+        assert_eq!(cf_algebraic(&cubic, bracket(0, 10), 10), Ok(vec![2]));
+        assert_eq!(cf_algebraic(&cubic, bracket(3, 10), 10), Ok(vec![3]));
+        assert_eq!(
+            cf_algebraic(&cubic, bracket(4, 10), 10),
+            Err(MathError::NoSolution)
+        );
+        assert_eq!(
+            cf_algebraic(&poly(vec![5]), bracket(0, 1), 10),
+            Err(MathError::OutOfRange)
+        );
+    }
+}
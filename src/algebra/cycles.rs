@@ -0,0 +1,390 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::modular::crt;
+use crate::errors::MathError;
+use crate::sequences::period::brent;
+
+/// When a set of periodic events all happen at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleAlignment {
+    /// First common time.
+    pub first: u64,
+    /// Gap between common times, the lcm of the periods.
+    pub interval: u64,
+}
+
+impl CycleAlignment {
+    /// The `k`-th common time (from 0), or `None` past `u64::MAX`.
+    pub fn nth(&self, k: u64) -> Option<u64> {
+        self.interval.checked_mul(k)?.checked_add(self.first)
+    }
+}
+
+/// First time all cycles coincide, where cycle `i` fires at `offsets[i] + j·periods[i]`
+/// for `j >= 0`:
+/// - periods `[4, 6]`, offsets `[1, 3]`: first at 9, then every 12
+/// - periods `[4, 6]`, offsets `[0, 1]`: never, the parities disagree
+///
+/// The times are solved by CRT and the result is moved up to the latest offset, so no
+/// cycle is counted before it starts. Errors with `EmptyInput` for no cycles,
+/// `DimensionMismatch` if the slices differ in length, `DivisionByZero` for a zero
+/// period, `NoSolution` if the cycles never coincide and `Overflow` if the lcm or the
+/// first time exceeds `u64`.
+pub fn align_cycles(periods: &[u64], offsets: &[u64]) -> Result<CycleAlignment, MathError> {
+    if periods.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    if periods.len() != offsets.len() {
+        return Err(MathError::DimensionMismatch);
+    }
+    let congruences: Vec<(u64, u64)> = offsets
+        .iter()
+        .copied()
+        .zip(periods.iter().copied())
+        .collect();
+    let (x, interval) = crt(&congruences)?;
+    let start = offsets.iter().copied().max().unwrap_or(0);
+    let first = if x >= start {
+        x
+    } else {
+        (start - x)
+            .div_ceil(interval)
+            .checked_mul(interval)
+            .and_then(|shift| shift.checked_add(x))
+            .ok_or(MathError::Overflow)?
+    };
+    Ok(CycleAlignment { first, interval })
+}
+
+/// Where the orbit `x0, f(x0), f(f(x0)), …` of a self-map starts repeating, its `ρ` shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrbitCycle<T> {
+    /// Steps `μ` before the orbit enters the cycle.
+    pub tail: u64,
+    /// Length `λ` of the cycle.
+    pub length: u64,
+    /// First value on the cycle, `f^μ(x0)`.
+    pub entry: T,
+}
+
+/// Tail and cycle of the orbit of `x0` under `f`, by Brent's algorithm:
+/// - `x ↦ x² + 1 mod 255` from 3: `3, 10, 101, 2, 5, 26, 167, 95, 101, …`, tail 2,
+///   cycle of length 6 entered at 101
+///
+/// The same search as `detect_period_mod`, without a modulus or a step limit. Holds two
+/// values at a time and calls `f` `O(μ + λ)` times, fewer than Floyd's version, which
+/// moves both pointers at every step; this is the loop Pollard rho runs with
+/// `x² + c mod n`. The orbit of a map on a finite set always cycles, after at most as
+/// many steps as the set has elements; on an infinite one that never repeats this does
+/// not return.
+pub fn find_cycle<T: Clone + PartialEq>(f: impl FnMut(&T) -> T, x0: T) -> OrbitCycle<T> {
+    let Some((period, entry)) = brent(x0, f, usize::MAX) else {
+        unreachable!("no orbit runs for usize::MAX steps");
+    };
+    OrbitCycle {
+        tail: period.preperiod as u64,
+        length: period.period as u64,
+        entry,
+    }
+}
+
+/// `find_cycle` for the Pollard rho map `x ↦ x² + c mod n` from `x0 mod n`:
+/// - `quadratic_map_cycle(1, 3, 255)`: tail 2, length 6, entry 101
+///
+/// `μ + λ <= n`, and about `√(πn/8)` each for a random-looking map; rho finds a factor
+/// `p` of `n` once the orbit mod `p` cycles, after `O(√p)` steps. Products go through
+/// `u128`, so any `n` works. `MathError::DivisionByZero` if `n == 0`.
+pub fn quadratic_map_cycle(c: u64, x0: u64, n: u64) -> Result<OrbitCycle<u64>, MathError> {
+    if n == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let (c, n) = (c as u128, n as u128);
+    Ok(find_cycle(
+        |&x| ((x as u128 * x as u128 + c) % n) as u64,
+        x0 % n as u64,
+    ))
+}
+
+/// Shape of the functional graph `x → f(x) mod n` on `0..n`, indexed by starting point.
+///
+/// Each component is a single cycle with trees hanging off it, so the components are
+/// counted by the cycles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionalGraph {
+    /// Steps from `x` until its orbit reaches a cycle, 0 on a cycle.
+    pub tail: Vec<u64>,
+    /// Length of the cycle that the orbit of `x` ends in.
+    pub cycle_length: Vec<u64>,
+    /// Component of `x`, numbered from 0 in order of the smallest point.
+    pub component: Vec<u64>,
+    /// Number of components, that is of distinct cycles.
+    pub components: u64,
+}
+
+impl FunctionalGraph {
+    /// Number of points on a cycle, those with tail 0.
+    pub fn cyclic_points(&self) -> u64 {
+        self.tail.iter().filter(|&&t| t == 0).count() as u64
+    }
+
+    /// Longest tail, the most steps any point takes to reach a cycle.
+    pub fn max_tail(&self) -> u64 {
+        self.tail.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// Tails, cycles and components of `x → f(x) mod n` for every `x` in `0..n`:
+/// - `x ↦ x² mod 10`: 0, 1, 5 and 6 are fixed points that every other point reaches
+///   within two steps, as `3 → 9 → 1`, so 4 components
+///
+/// Walks each orbit until it meets a point already known, then fills in the path
+/// backwards, so `f` is called once per point and the whole is O(n) time and memory,
+/// where running `find_cycle` from each start would be O(n·(μ + λ)). `f` only needs to
+/// be defined on `0..n`; its values are reduced mod `n`. `MathError::OutOfRange` if
+/// `n == 0`, `Overflow` if `n` does not fit in `usize`.
+pub fn functional_graph_stats(
+    mut f: impl FnMut(u64) -> u64,
+    n: u64,
+) -> Result<FunctionalGraph, MathError> {
+    if n == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    let size = usize::try_from(n).map_err(|_| MathError::Overflow)?;
+    // position on the current path while it is walked, then NONE once done
+    const NONE: usize = usize::MAX;
+    let mut on_path = vec![NONE; size];
+    let mut done = vec![false; size];
+    let mut graph = FunctionalGraph {
+        tail: vec![0; size],
+        cycle_length: vec![0; size],
+        component: vec![0; size],
+        components: 0,
+    };
+    let mut path = Vec::new();
+    for start in 0..size {
+        if done[start] {
+            continue;
+        }
+        let mut x = start;
+        while !done[x] && on_path[x] == NONE {
+            on_path[x] = path.len();
+            path.push(x);
+            x = (f(x as u64) % n) as usize;
+        }
+        let mut rest = path.len();
+        if !done[x] {
+            // the walk closed a new cycle
+            let first = on_path[x];
+            let length = (path.len() - first) as u64;
+            for &y in &path[first..] {
+                graph.cycle_length[y] = length;
+                graph.component[y] = graph.components;
+                done[y] = true;
+            }
+            graph.components += 1;
+            rest = first;
+            x = path[first];
+        }
+        for &y in path[..rest].iter().rev() {
+            graph.tail[y] = graph.tail[x] + 1;
+            graph.cycle_length[y] = graph.cycle_length[x];
+            graph.component[y] = graph.component[x];
+            done[y] = true;
+            x = y;
+        }
+        for y in path.drain(..) {
+            on_path[y] = NONE;
+        }
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+
+    #[test]
+    fn test_align_cycles() {
+        let aligned = align_cycles(&[4, 6], &[1, 3]).unwrap();
+        assert_eq!(
+            aligned,
+            CycleAlignment {
+                first: 9,
+                interval: 12
+            }
+        );
+        assert_eq!(aligned.nth(2), Some(33));
+        // coprime periods: the classic planetary alignment puzzle
+        assert_eq!(
+            align_cycles(&[3, 5, 7], &[2, 3, 2]),
+            Ok(CycleAlignment {
+                first: 23,
+                interval: 105
+            })
+        );
+        assert_eq!(
+            align_cycles(&[7], &[3]),
+            Ok(CycleAlignment {
+                first: 3,
+                interval: 7
+            })
+        );
+    }
+
+    #[test]
+    fn test_align_cycles_late_start() {
+        // 1 ≡ 11 (mod 10) but the second cycle only starts at 11
+        assert_eq!(
+            align_cycles(&[5, 10], &[1, 11]),
+            Ok(CycleAlignment {
+                first: 11,
+                interval: 10
+            })
+        );
+        // first candidate 2 is before both starts; the next is 2 + 3·6
+        assert_eq!(
+            align_cycles(&[2, 3], &[20, 14]),
+            Ok(CycleAlignment {
+                first: 20,
+                interval: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_align_cycles_errors() {
+        assert_eq!(align_cycles(&[4, 6], &[0, 1]), Err(MathError::NoSolution));
+        assert_eq!(align_cycles(&[], &[]), Err(MathError::EmptyInput));
+        assert_eq!(
+            align_cycles(&[4, 6], &[0]),
+            Err(MathError::DimensionMismatch)
+        );
+        assert_eq!(align_cycles(&[0], &[0]), Err(MathError::DivisionByZero));
+        assert_eq!(
+            align_cycles(&[1 << 40, (1 << 40) - 1], &[0, 0]),
+            Err(MathError::Overflow)
+        );
+        assert_eq!(
+            align_cycles(&[u64::MAX], &[u64::MAX]).map(|a| a.nth(1)),
+            Ok(None)
+        );
+    }
+
+    /// Tail and length by recording the step of every value.
+    fn brute_force_cycle(f: impl Fn(u64) -> u64, x0: u64) -> (u64, u64) {
+        let mut seen = BTreeMap::from([(x0, 0)]);
+        let mut x = x0;
+        for step in 1.. {
+            x = f(x);
+            if let Some(&first) = seen.get(&x) {
+                return (first, step - first);
+            }
+            seen.insert(x, step);
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let orbit = quadratic_map_cycle(1, 3, 255).unwrap();
+        assert_eq!(
+            orbit,
+            OrbitCycle {
+                tail: 2,
+                length: 6,
+                entry: 101
+            }
+        );
+        // a pure cycle and a fixed point
+        assert_eq!(find_cycle(|&x: &u64| (x + 1) % 10, 4).length, 10);
+        assert_eq!(find_cycle(|&x: &u64| (x + 1) % 10, 4).tail, 0);
+        assert_eq!(
+            find_cycle(|&x: &u64| x / 2, 1000),
+            OrbitCycle {
+                tail: 10,
+                length: 1,
+                entry: 0
+            }
+        );
+        // any type with equality
+        let strings = find_cycle(|s: &String| s.chars().rev().collect(), "ab".into());
+        assert_eq!((strings.tail, strings.length), (0, 2));
+
+        for n in [1, 2, 97, 255, 1000, 4096, 10_007] {
+            for c in [0, 1, 2, n - 1] {
+                for x0 in [0, 2, 5] {
+                    let orbit = quadratic_map_cycle(c, x0, n).unwrap();
+                    let f = |x: u64| (x * x + c) % n;
+                    assert_eq!(
+                        (orbit.tail, orbit.length),
+                        brute_force_cycle(f, x0 % n),
+                        "c = {c}, x0 = {x0}, n = {n}"
+                    );
+                    let entry = (0..orbit.tail).fold(x0 % n, |x, _| f(x));
+                    assert_eq!(orbit.entry, entry);
+                }
+            }
+        }
+        // 1 is a fixed point, whatever the size of n
+        assert_eq!(quadratic_map_cycle(0, 1, u64::MAX).map(|o| o.length), Ok(1));
+        assert_eq!(quadratic_map_cycle(1, 0, 0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_functional_graph_stats() {
+        let graph = functional_graph_stats(|x| x * x, 10).unwrap();
+        assert_eq!(graph.tail, [0, 0, 2, 2, 1, 0, 0, 2, 2, 1]);
+        assert_eq!(graph.cycle_length, [1; 10]);
+        assert_eq!(graph.component, [0, 1, 2, 1, 2, 3, 2, 1, 2, 1]);
+        assert_eq!(graph.components, 4);
+        assert_eq!((graph.cyclic_points(), graph.max_tail()), (4, 2));
+        // x² + 1 mod 255 has a cycle of length 6
+        let graph = functional_graph_stats(|x| x * x + 1, 255).unwrap();
+        assert_eq!((graph.tail[3], graph.cycle_length[3]), (2, 6));
+
+        // a permutation is all cycles
+        let shift = functional_graph_stats(|x| x + 3, 12).unwrap();
+        assert_eq!(shift.components, 3);
+        assert!(shift.tail.iter().all(|&t| t == 0));
+        assert!(shift.cycle_length.iter().all(|&l| l == 4));
+        // a constant map is one fixed point with everything one step away
+        let constant = functional_graph_stats(|_| 7, 9).unwrap();
+        assert_eq!((constant.components, constant.max_tail()), (1, 1));
+        assert_eq!(
+            functional_graph_stats(|x| x, 1).map(|g| g.components),
+            Ok(1)
+        );
+        assert_eq!(functional_graph_stats(|x| x, 0), Err(MathError::OutOfRange));
+
+        // agrees with find_cycle from every start
+        for n in [1, 2, 64, 97, 255, 1000, 2021] {
+            for c in [0, 1, 3] {
+                let f = |x: u64| (x * x + c) % n;
+                let graph = functional_graph_stats(f, n).unwrap();
+                let mut entries = BTreeMap::new();
+                for x in 0..n {
+                    let orbit = quadratic_map_cycle(c, x, n).unwrap();
+                    let i = x as usize;
+                    assert_eq!(
+                        (graph.tail[i], graph.cycle_length[i]),
+                        (orbit.tail, orbit.length)
+                    );
+                    // the smallest point of a cycle names it
+                    let cycle_min = (0..orbit.length)
+                        .scan(orbit.entry, |y, _| {
+                            *y = f(*y);
+                            Some(*y)
+                        })
+                        .min()
+                        .unwrap();
+                    let id = *entries.entry(cycle_min).or_insert(graph.component[i]);
+                    assert_eq!(graph.component[i], id);
+                }
+                assert_eq!(entries.len() as u64, graph.components);
+            }
+        }
+    }
+}
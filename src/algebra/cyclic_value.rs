@@ -0,0 +1,170 @@
+use core::fmt;
+use core::ops::{Add, Sub};
+
+use crate::errors::MathError;
+
+/// A position on a cycle of `modulus` steps, `0 <= value < modulus`: a compass bearing in
+/// degrees, an hour on a clock, a slot of a ring buffer.
+///
+/// Adding or subtracting a signed step count wraps around, and `distance` gives the
+/// short way round between two positions, so `350° → 10°` is `+20`, not `-340`. Angles
+/// with fractions work in smaller units, tenths of a degree on a cycle of 3600.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CyclicValue {
+    value: u64,
+    modulus: u64,
+}
+
+impl CyclicValue {
+    /// `value` reduced into `0..modulus`, negative values counting back from 0:
+    /// - `CyclicValue::new(-90, 360) = 270 (mod 360)`
+    ///
+    /// `MathError::DivisionByZero` if `modulus == 0`.
+    pub fn new(value: i64, modulus: u64) -> Result<Self, MathError> {
+        if modulus == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        Ok(CyclicValue {
+            value: (value as i128).rem_euclid(modulus as i128) as u64,
+            modulus,
+        })
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Steps forward from `self` to `other`, in `0..modulus`:
+    /// - `10 → 350 (mod 360)` is 340
+    ///
+    /// `MathError::DimensionMismatch` if the moduli differ.
+    pub fn forward_distance(&self, other: &CyclicValue) -> Result<u64, MathError> {
+        if self.modulus != other.modulus {
+            return Err(MathError::DimensionMismatch);
+        }
+        Ok(if other.value >= self.value {
+            other.value - self.value
+        } else {
+            self.modulus - (self.value - other.value)
+        })
+    }
+
+    /// Shortest signed step count from `self` to `other`, positive going forward:
+    /// - `350 → 10 (mod 360)` is `+20`
+    /// - `10 → 350 (mod 360)` is `-20`
+    ///
+    /// In `(-m/2, m/2]`, so halfway round on an even cycle counts as forward:
+    /// `0 → 6 (mod 12)` is `+6`. Always fits in an `i64`. `MathError::DimensionMismatch`
+    /// if the moduli differ.
+    pub fn distance(&self, other: &CyclicValue) -> Result<i64, MathError> {
+        let forward = self.forward_distance(other)?;
+        Ok(if forward <= self.modulus / 2 {
+            forward as i64
+        } else {
+            -((self.modulus - forward) as i64)
+        })
+    }
+}
+
+/// `steps` forward, wrapping around; negative steps go backward.
+impl Add<i64> for CyclicValue {
+    type Output = CyclicValue;
+
+    fn add(self, steps: i64) -> CyclicValue {
+        let m = self.modulus as i128;
+        CyclicValue {
+            value: (self.value as i128 + (steps as i128).rem_euclid(m)).rem_euclid(m) as u64,
+            modulus: self.modulus,
+        }
+    }
+}
+
+/// `steps` backward, wrapping around.
+impl Sub<i64> for CyclicValue {
+    type Output = CyclicValue;
+
+    fn sub(self, steps: i64) -> CyclicValue {
+        let m = self.modulus as i128;
+        CyclicValue {
+            value: (self.value as i128 - (steps as i128).rem_euclid(m)).rem_euclid(m) as u64,
+            modulus: self.modulus,
+        }
+    }
+}
+
+/// `value (mod modulus)`.
+impl fmt::Display for CyclicValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value, self.modulus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn cv(value: i64, modulus: u64) -> CyclicValue {
+        CyclicValue::new(value, modulus).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(cv(-90, 360).value(), 270);
+        assert_eq!(cv(725, 360).value(), 5);
+        assert_eq!(cv(i64::MIN, u64::MAX).value(), u64::MAX - (1 << 63));
+        assert_eq!(cv(-1, u64::MAX).value(), u64::MAX - 1);
+        assert_eq!(cv(5, 1).value(), 0);
+        assert_eq!(CyclicValue::new(5, 0), Err(MathError::DivisionByZero));
+        assert_eq!(cv(-90, 360).to_string(), "270 (mod 360)");
+    }
+
+    #[test]
+    fn test_add_sub() {
+        assert_eq!(cv(350, 360) + 20, cv(10, 360));
+        assert_eq!(cv(10, 360) - 20, cv(350, 360));
+        assert_eq!(cv(10, 360) + -20, cv(350, 360));
+        assert_eq!(cv(11, 12) + 25, cv(0, 12));
+        assert_eq!(cv(3, 12) - i64::MIN, cv(11, 12)); // 2^63 ≡ 8 (mod 12)
+        assert_eq!((cv(-1, u64::MAX) - 1).value(), u64::MAX - 2);
+        assert_eq!((cv(0, u64::MAX) - 1) + 1, cv(0, u64::MAX));
+        // 2·(2^63 - 1) = 2^64 - 2 ≡ -1
+        assert_eq!(
+            (cv(0, u64::MAX) + i64::MAX + i64::MAX).value(),
+            u64::MAX - 1
+        );
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(cv(350, 360).distance(&cv(10, 360)), Ok(20));
+        assert_eq!(cv(10, 360).distance(&cv(350, 360)), Ok(-20));
+        assert_eq!(cv(10, 360).forward_distance(&cv(350, 360)), Ok(340));
+        assert_eq!(cv(0, 12).distance(&cv(6, 12)), Ok(6));
+        assert_eq!(cv(6, 12).distance(&cv(0, 12)), Ok(6));
+        assert_eq!(cv(0, 7).distance(&cv(4, 7)), Ok(-3));
+        assert_eq!(cv(4, 7).distance(&cv(4, 7)), Ok(0));
+        assert_eq!(cv(0, u64::MAX).distance(&(cv(0, u64::MAX) - 1)), Ok(-1));
+        assert_eq!(
+            cv(0, u64::MAX).distance(&cv(i64::MAX, u64::MAX)),
+            Ok(i64::MAX)
+        );
+        assert_eq!(
+            cv(0, 12).distance(&cv(0, 24)),
+            Err(MathError::DimensionMismatch)
+        );
+        // a step and its distance agree
+        for m in [1, 2, 7, 12, 360] {
+            for start in 0..m as i64 {
+                for steps in -(m as i64 - 1) / 2..=m as i64 / 2 {
+                    let a = cv(start, m);
+                    assert_eq!(a.distance(&(a + steps)), Ok(steps), "m = {m}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,484 @@
+use core::ops::RangeInclusive;
+
+use alloc::vec::Vec;
+
+use crate::algebra::gcd::{extended_gcd, gcd_many};
+use crate::errors::MathError;
+
+/// Every integer solution of `a·x + b·y = c`, as the one-parameter family
+/// `x = x0 + k·step_x`, `y = y0 - k·step_y` for `k ∈ Z`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiophantineSolution {
+    /// Particular solution with the smallest non-negative `x` (or `y = 0` when `x` is
+    /// fixed because `b == 0`).
+    pub x0: i64,
+    pub y0: i64,
+    /// `b / g` with `g = gcd(a, b)`.
+    pub step_x: i64,
+    /// `a / g`.
+    pub step_y: i64,
+}
+
+impl DiophantineSolution {
+    /// The solution at parameter `k`, or `None` if it leaves `i64`.
+    pub fn nth(&self, k: i64) -> Option<(i64, i64)> {
+        let x = self.step_x.checked_mul(k)?.checked_add(self.x0)?;
+        let y = self.y0.checked_sub(self.step_y.checked_mul(k)?)?;
+        Some((x, y))
+    }
+
+    /// The solutions with `x` in `x_range` and `y` in `y_range`, by increasing `k`:
+    /// - `3x + 5y = 22` with `x, y >= 0` only has `(4, 2)`
+    ///
+    /// The bounds on `k` are solved for directly, so nothing outside the box is visited.
+    pub fn solutions_in(
+        &self,
+        x_range: RangeInclusive<i64>,
+        y_range: RangeInclusive<i64>,
+    ) -> Solutions {
+        let x_bounds = parameter_bounds(self.x0, self.step_x as i128, &x_range);
+        let y_bounds = parameter_bounds(self.y0, -(self.step_y as i128), &y_range);
+        let (next, last) = match (x_bounds, y_bounds) {
+            (Some((x_lo, x_hi)), Some((y_lo, y_hi))) => (x_lo.max(y_lo), x_hi.min(y_hi)),
+            _ => (1, 0),
+        };
+        Solutions {
+            solution: *self,
+            next,
+            last,
+        }
+    }
+}
+
+/// Iterator returned by `DiophantineSolution::solutions_in`.
+#[derive(Debug, Clone)]
+pub struct Solutions {
+    solution: DiophantineSolution,
+    next: i128,
+    last: i128,
+}
+
+impl Iterator for Solutions {
+    type Item = (i64, i64);
+
+    fn next(&mut self) -> Option<(i64, i64)> {
+        if self.next > self.last {
+            return None;
+        }
+        let k = self.next;
+        self.next += 1;
+        // k is within the ranges' bounds, so both coordinates fit in i64
+        let s = &self.solution;
+        Some((
+            (s.x0 as i128 + k * s.step_x as i128) as i64,
+            (s.y0 as i128 - k * s.step_y as i128) as i64,
+        ))
+    }
+}
+
+/// All integer solutions of `a·x + b·y = c`:
+/// - `solve_linear_diophantine(3, 5, 7)`: `x = 4 + 5k`, `y = -1 - 3k`
+/// - `solve_linear_diophantine(4, 6, 5)`: none, `gcd(4, 6) = 2` does not divide 5
+///
+/// A solution exists exactly when `g = gcd(a, b)` divides `c`; the Bézout coefficients
+/// of `extended_gcd` scaled by `c / g` give one, which is then shifted to the smallest
+/// non-negative `x`. Errors with `NoSolution` if `g ∤ c` (or `a = b = 0 ≠ c`),
+/// `OutOfRange` for `0·x + 0·y = 0`, whose solutions are not a one-parameter family,
+/// and `Overflow` if the particular solution does not fit in `i64`.
+pub fn solve_linear_diophantine(a: i64, b: i64, c: i64) -> Result<DiophantineSolution, MathError> {
+    if a == 0 && b == 0 {
+        return Err(if c == 0 {
+            MathError::OutOfRange
+        } else {
+            MathError::NoSolution
+        });
+    }
+    let (g, u, v) = if b != 0 {
+        extended_gcd(a, b)?
+    } else {
+        let (g, v, u) = extended_gcd(b, a)?;
+        (g, u, v)
+    };
+    if c % g != 0 {
+        return Err(MathError::NoSolution);
+    }
+    let (a, b, c, g) = (a as i128, b as i128, c as i128, g as i128);
+    let (step_x, step_y) = (b / g, a / g);
+    let (mut x0, mut y0) = (u as i128 * (c / g), v as i128 * (c / g));
+    if step_x != 0 {
+        let k = (x0 - x0.rem_euclid(step_x.abs())) / step_x;
+        (x0, y0) = (x0 - k * step_x, y0 + k * step_y);
+    } else {
+        y0 = 0;
+    }
+    let narrow = |v: i128| i64::try_from(v).map_err(|_| MathError::Overflow);
+    Ok(DiophantineSolution {
+        x0: narrow(x0)?,
+        y0: narrow(y0)?,
+        step_x: narrow(step_x)?,
+        step_y: narrow(step_y)?,
+    })
+}
+
+/// Maximizes `p·x + q·y` over the integer points satisfying every `a·x + b·y <= c`,
+/// for `objective = (p, q)` and `constraints` as `(a, b, c)`; returns the optimum and
+/// every point reaching it, by increasing `k` along the optimal line:
+/// - `ilp2(&[(2, 2, 7), (-1, 0, 0), (0, -1, 0)], (1, 1))`: `(3, [(0, 3), (1, 2), (2, 1),
+///   (3, 0)])`, where the relaxation would reach `3.5`
+/// - `ilp2(&[(1, 0, 3), (-1, 0, -3), (0, 1, 1), (0, -1, 1)], (1, 1))`: `(4, [(3, 1)])`
+///
+/// Negate the objective to minimize, and write an equality as two inequalities. The
+/// optimum of the relaxation sits at a vertex of the feasible polygon, which bounds the
+/// objective; from there the lines `p·x + q·y = v` are tried downwards through the
+/// multiples of `gcd(p, q)`, each one solved with `solve_linear_diophantine`, until one
+/// has a lattice point inside every constraint. Meant for small programs: the lines
+/// tried can number up to the objective's range over the polygon.
+///
+/// Errors with `OutOfRange` if the feasible region is unbounded, or the objective is
+/// `(0, 0)`, `NoSolution` if no integer point is feasible, and `Overflow` if the
+/// arithmetic on the polygon leaves `i128` or the optimum `i64`.
+pub fn ilp2(
+    constraints: &[(i64, i64, i64)],
+    objective: (i64, i64),
+) -> Result<(i64, Vec<(i64, i64)>), MathError> {
+    let (p, q) = objective;
+    if p == 0 && q == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    // 0 <= c holds everywhere or nowhere
+    if constraints
+        .iter()
+        .any(|&(a, b, c)| a == 0 && b == 0 && c < 0)
+    {
+        return Err(MathError::NoSolution);
+    }
+    let lines: Vec<(i128, i128, i128)> = constraints
+        .iter()
+        .filter(|&&(a, b, _)| a != 0 || b != 0)
+        .map(|&(a, b, c)| (a as i128, b as i128, c as i128))
+        .collect();
+    if is_unbounded(&lines)? {
+        return Err(MathError::OutOfRange);
+    }
+
+    // the objective's range over the vertices, rounded inwards to integers
+    let (p, q) = (p as i128, q as i128);
+    let mut range: Option<(i128, i128)> = None;
+    for (i, &(a1, b1, c1)) in lines.iter().enumerate() {
+        for &(a2, b2, c2) in &lines[i + 1..] {
+            // Cramer's rule, with the determinant made positive
+            let det = mul(a1, b2)?.checked_sub(mul(a2, b1)?);
+            let x = mul(c1, b2)?.checked_sub(mul(c2, b1)?);
+            let y = mul(a1, c2)?.checked_sub(mul(a2, c1)?);
+            let (Some(det), Some(x), Some(y)) = (det, x, y) else {
+                return Err(MathError::Overflow);
+            };
+            if det == 0 {
+                continue;
+            }
+            let sign = det.signum();
+            let (det, x, y) = (det * sign, x * sign, y * sign);
+            if !satisfies_all(&lines, x, y, det)? {
+                continue;
+            }
+            let value = mul(p, x)?
+                .checked_add(mul(q, y)?)
+                .ok_or(MathError::Overflow)?;
+            let (lo, hi) = (-(-value).div_euclid(det), value.div_euclid(det));
+            range = Some(range.map_or((lo, hi), |(l, h)| (l.min(lo), h.max(hi))));
+        }
+    }
+    // a bounded region without vertices is empty
+    let Some((lo, hi)) = range else {
+        return Err(MathError::NoSolution);
+    };
+
+    let g = gcd_many(&[p, q])?;
+    let mut v = hi.div_euclid(g) * g;
+    while v >= lo {
+        let value = i64::try_from(v).map_err(|_| MathError::Overflow)?;
+        // g divides v, so the line always has lattice points
+        let line = solve_linear_diophantine(objective.0, objective.1, value)?;
+        if let Some((k_lo, k_hi)) = feasible_parameters(&line, &lines)? {
+            let points = (k_lo..=k_hi)
+                .map(|k| i64::try_from(k).ok().and_then(|k| line.nth(k)))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(MathError::Overflow)?;
+            return Ok((value, points));
+        }
+        v -= g;
+    }
+    Err(MathError::NoSolution)
+}
+
+/// Whether some direction `d != 0` has `a·d_x + b·d_y <= 0` for every line, so the
+/// region, if not empty, contains a half-line. Such a cone, if not `{0}`, contains a
+/// direction along one of the lines or, when it is a half-plane, the inner normal
+/// `-(a, b)` of its edge; with no lines at all, it is the whole plane.
+fn is_unbounded(lines: &[(i128, i128, i128)]) -> Result<bool, MathError> {
+    if lines.is_empty() {
+        return Ok(true);
+    }
+    for &(a, b, _) in lines {
+        for (dx, dy) in [(b, -a), (-b, a), (-a, -b)] {
+            let mut inside = true;
+            for &(a, b, _) in lines {
+                let dot = mul(a, dx)?
+                    .checked_add(mul(b, dy)?)
+                    .ok_or(MathError::Overflow)?;
+                inside &= dot <= 0;
+            }
+            if inside {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Whether the point `(x / det, y / det)`, `det > 0`, satisfies every constraint.
+fn satisfies_all(
+    lines: &[(i128, i128, i128)],
+    x: i128,
+    y: i128,
+    det: i128,
+) -> Result<bool, MathError> {
+    for &(a, b, c) in lines {
+        let lhs = mul(a, x)?
+            .checked_add(mul(b, y)?)
+            .ok_or(MathError::Overflow)?;
+        if lhs > mul(c, det)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// The parameters `k` for which `line.nth(k)` satisfies every constraint, as an
+/// inclusive interval, `None` if there are none. Each constraint reads `t·k <= r`.
+fn feasible_parameters(
+    line: &DiophantineSolution,
+    lines: &[(i128, i128, i128)],
+) -> Result<Option<(i128, i128)>, MathError> {
+    let (x0, y0) = (line.x0 as i128, line.y0 as i128);
+    let (sx, sy) = (line.step_x as i128, line.step_y as i128);
+    let (mut lo, mut hi) = (i128::MIN, i128::MAX);
+    for &(a, b, c) in lines {
+        let t = mul(a, sx)?.checked_sub(mul(b, sy)?);
+        let r = mul(a, x0)?
+            .checked_add(mul(b, y0)?)
+            .and_then(|s| c.checked_sub(s));
+        let (Some(t), Some(r)) = (t, r) else {
+            return Err(MathError::Overflow);
+        };
+        match t.signum() {
+            0 if r < 0 => return Ok(None),
+            0 => {}
+            1 => hi = hi.min(r.div_euclid(t)),
+            _ => lo = lo.max(-r.div_euclid(-t)),
+        }
+    }
+    Ok((lo <= hi).then_some((lo, hi)))
+}
+
+fn mul(x: i128, y: i128) -> Result<i128, MathError> {
+    x.checked_mul(y).ok_or(MathError::Overflow)
+}
+
+/// The parameters `k` with `base + k·step` in `range`, as an inclusive interval
+/// (unbounded when `step == 0` and `base` is in range), `None` if there are none.
+fn parameter_bounds(base: i64, step: i128, range: &RangeInclusive<i64>) -> Option<(i128, i128)> {
+    let base = base as i128;
+    let (lo, hi) = (*range.start() as i128, *range.end() as i128);
+    if lo > hi {
+        return None;
+    }
+    if step == 0 {
+        return (lo..=hi).contains(&base).then_some((i128::MIN, i128::MAX));
+    }
+    // with s = |step|: lo <= base + j·s <= hi for j = ±k
+    let s = step.abs();
+    let (j_lo, j_hi) = (-(base - lo).div_euclid(s), (hi - base).div_euclid(s));
+    if j_lo > j_hi {
+        return None;
+    }
+    Some(if step > 0 {
+        (j_lo, j_hi)
+    } else {
+        (-j_hi, -j_lo)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_solve_linear_diophantine() {
+        assert_eq!(
+            solve_linear_diophantine(3, 5, 7),
+            Ok(DiophantineSolution {
+                x0: 4,
+                y0: -1,
+                step_x: 5,
+                step_y: 3
+            })
+        );
+        assert_eq!(
+            solve_linear_diophantine(4, 6, 5),
+            Err(MathError::NoSolution)
+        );
+        assert_eq!(
+            solve_linear_diophantine(0, 0, 0),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            solve_linear_diophantine(0, 0, 3),
+            Err(MathError::NoSolution)
+        );
+        // one coefficient zero: the other variable is fixed
+        let s = solve_linear_diophantine(0, -4, 12).unwrap();
+        assert_eq!((s.x0, s.y0, s.step_x, s.step_y), (0, -3, -1, 0));
+        let s = solve_linear_diophantine(6, 0, -18).unwrap();
+        assert_eq!((s.x0, s.y0, s.step_x, s.step_y), (-3, 0, 0, 1));
+        assert_eq!(
+            solve_linear_diophantine(6, 0, 4),
+            Err(MathError::NoSolution)
+        );
+    }
+
+    #[test]
+    fn test_family_is_complete() {
+        for a in -12i64..=12 {
+            for b in -12i64..=12 {
+                for c in -30i64..=30 {
+                    let brute: Vec<(i64, i64)> = (-20..=20)
+                        .flat_map(|x| (-20..=20).map(move |y| (x, y)))
+                        .filter(|&(x, y)| a * x + b * y == c)
+                        .collect();
+                    match solve_linear_diophantine(a, b, c) {
+                        Ok(s) => {
+                            let found: Vec<(i64, i64)> =
+                                s.solutions_in(-20..=20, -20..=20).collect();
+                            let mut sorted = found.clone();
+                            sorted.sort_unstable();
+                            assert_eq!(sorted, brute, "{a}x + {b}y = {c}");
+                            assert!(found.iter().all(|&(x, y)| a * x + b * y == c));
+                        }
+                        Err(MathError::NoSolution) => assert!(brute.is_empty()),
+                        Err(e) => assert_eq!((a, b, c, e), (0, 0, 0, MathError::OutOfRange)),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solutions_in() {
+        let s = solve_linear_diophantine(3, 5, 22).unwrap();
+        let non_negative: Vec<_> = s.solutions_in(0..=i64::MAX, 0..=i64::MAX).collect();
+        assert_eq!(non_negative, vec![(4, 2)]);
+        assert_eq!(s.nth(2), Some((14, -4)));
+        assert_eq!(s.nth(i64::MAX), None);
+        assert_eq!(s.solutions_in(5..=8, 0..=100).count(), 0);
+        // the whole i64 box stays finite and in range
+        let s = solve_linear_diophantine(i64::MAX, i64::MAX - 1, 1).unwrap();
+        let all: Vec<_> = s
+            .solutions_in(i64::MIN..=i64::MAX, i64::MIN..=i64::MAX)
+            .collect();
+        assert_eq!(
+            all,
+            vec![(2 - i64::MAX, i64::MAX - 1), (1, -1), (i64::MAX, i64::MIN)]
+        );
+    }
+
+    #[test]
+    fn test_large_coefficients() {
+        let (a, b) = (1_000_000_007i64, 998_244_353i64);
+        let c = 1_i64 << 62;
+        let s = solve_linear_diophantine(a, b, c).unwrap();
+        assert!((0..b).contains(&s.x0));
+        assert_eq!(
+            a as i128 * s.x0 as i128 + b as i128 * s.y0 as i128,
+            c as i128
+        );
+    }
+
+    #[test]
+    fn test_ilp2() {
+        let triangle = [(2, 2, 7), (-1, 0, 0), (0, -1, 0)];
+        assert_eq!(
+            ilp2(&triangle, (1, 1)),
+            Ok((3, vec![(0, 3), (1, 2), (2, 1), (3, 0)]))
+        );
+        assert_eq!(ilp2(&triangle, (-1, -1)), Ok((0, vec![(0, 0)])));
+        let segment = [(1, 0, 3), (-1, 0, -3), (0, 1, 1), (0, -1, 1)];
+        assert_eq!(ilp2(&segment, (1, 1)), Ok((4, vec![(3, 1)])));
+        // the relaxation's optimum (1.5, 2) has no lattice point near it
+        let thin = [(2, 0, 3), (-2, 0, -3), (0, 1, 5), (0, -1, 5)];
+        assert_eq!(ilp2(&thin, (0, 1)), Err(MathError::NoSolution));
+        assert_eq!(ilp2(&triangle, (0, 0)), Err(MathError::OutOfRange));
+        assert_eq!(ilp2(&[], (1, 0)), Err(MathError::OutOfRange));
+        // bounded in the objective's direction, but a strip
+        assert_eq!(
+            ilp2(&[(1, 0, 5), (-1, 0, 0)], (1, 0)),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            ilp2(&[(1, 1, 1), (-1, -1, -2), (-1, 0, 0), (0, -1, 0)], (1, 2)),
+            Err(MathError::NoSolution)
+        );
+        assert_eq!(
+            ilp2(&[(0, 0, -1), (1, 0, 1)], (1, 0)),
+            Err(MathError::NoSolution)
+        );
+        // a classic: the relaxation's optimum (1.5, 2.5) is far from the integer one
+        let knap = [(-1, 1, 1), (3, 2, 12), (2, 3, 12), (-1, 0, 0), (0, -1, 0)];
+        assert_eq!(ilp2(&knap, (0, 1)), Ok((2, vec![(1, 2), (2, 2)])));
+    }
+
+    #[test]
+    fn test_ilp2_against_brute_force() {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = |m: i64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % (2 * m as u64 + 1)) as i64 - m
+        };
+        for _ in 0..300 {
+            // a box keeps every program bounded, and the rest cut through it
+            let mut constraints = vec![(1, 0, 8), (-1, 0, 8), (0, 1, 8), (0, -1, 8)];
+            for _ in 0..(next(2) + 3) {
+                constraints.push((next(7), next(7), next(20)));
+            }
+            let objective = (next(5), next(5));
+            let feasible: Vec<(i64, i64)> = (-8..=8)
+                .flat_map(|x| (-8..=8).map(move |y| (x, y)))
+                .filter(|&(x, y)| constraints.iter().all(|&(a, b, c)| a * x + b * y <= c))
+                .collect();
+            let result = ilp2(&constraints, objective);
+            if objective == (0, 0) {
+                assert_eq!(result, Err(MathError::OutOfRange));
+                continue;
+            }
+            let value = |&(x, y): &(i64, i64)| objective.0 * x + objective.1 * y;
+            match feasible.iter().map(value).max() {
+                None => assert_eq!(result, Err(MathError::NoSolution)),
+                Some(best) => {
+                    let (found, mut points) = result.unwrap();
+                    let mut expected: Vec<_> = feasible
+                        .iter()
+                        .filter(|p| value(p) == best)
+                        .copied()
+                        .collect();
+                    points.sort();
+                    expected.sort();
+                    assert_eq!((found, points), (best, expected), "{constraints:?}");
+                }
+            }
+        }
+    }
+}
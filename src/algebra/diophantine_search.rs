@@ -0,0 +1,478 @@
+use alloc::collections::{BTreeMap, BinaryHeap};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+use crate::algebra::gcd::gcd_u64;
+use crate::errors::MathError;
+
+/// Cuboid whose three face diagonals are integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EulerBrick {
+    /// `a < b < c`.
+    pub edges: [u64; 3],
+    /// Diagonals of the `ab`, `ac` and `bc` faces.
+    pub face_diagonals: [u64; 3],
+}
+
+/// A number with several representations as a sum of two positive cubes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxicabNumber {
+    pub value: u128,
+    /// `(a, b)` with `a³ + b³ = value` and `a <= b`, by increasing `a`.
+    pub ways: Vec<(u64, u64)>,
+}
+
+/// Every Euler brick with edges `a < b < c <= max_edge`, ordered by edges:
+/// - `euler_bricks(250)`: only Halcke's `(44, 117, 240)`, with face diagonals
+///   `(125, 244, 267)`
+///
+/// Pythagorean triples with both legs `<= max_edge` are generated once from Euclid's
+/// formula and filed by leg; a brick is then two partners `b < c` of the same leg `a`
+/// that are partners of each other, so only pairs that already make two square faces
+/// are ever tested. O(max_edge log max_edge) time and memory for the triples instead of
+/// the O(max_edge³) of trying every box.
+pub fn euler_bricks(max_edge: usize) -> Vec<EulerBrick> {
+    let partners = pythagorean_partners(max_edge);
+    let diagonal = |x: usize, y: u64| {
+        partners[x]
+            .binary_search_by_key(&y, |&(leg, _)| leg)
+            .ok()
+            .map(|i| partners[x][i].1)
+    };
+    let mut bricks = Vec::new();
+    for (a, legs) in partners.iter().enumerate() {
+        let larger = &legs[legs.partition_point(|&(leg, _)| leg <= a as u64)..];
+        for (i, &(b, ab)) in larger.iter().enumerate() {
+            for &(c, ac) in &larger[i + 1..] {
+                if let Some(bc) = diagonal(b as usize, c) {
+                    bricks.push(EulerBrick {
+                        edges: [a as u64, b, c],
+                        face_diagonals: [ab, ac, bc],
+                    });
+                }
+            }
+        }
+    }
+    bricks
+}
+
+/// `partners[x]`: every `(y, h)` with `x² + y² = h²`, `1 <= y <= max_leg`, by `y`.
+fn pythagorean_partners(max_leg: usize) -> Vec<Vec<(u64, u64)>> {
+    let mut partners = vec![Vec::new(); max_leg + 1];
+    let n = max_leg as u64;
+    // primitive legs m² - n'², 2mn' with m > n', coprime, of opposite parity
+    for m in 2..=n / 2 {
+        let smallest = (m * m).saturating_sub(n).isqrt();
+        for k in (smallest.max(1)..m).filter(|k| (m - k) % 2 == 1) {
+            let (p, q) = (m * m - k * k, 2 * m * k);
+            if q > n {
+                break;
+            }
+            if p > n || gcd_u64(m, k) != 1 {
+                continue;
+            }
+            let h = m * m + k * k;
+            for scale in 1..=n / p.max(q) {
+                let (x, y) = (scale * p, scale * q);
+                partners[x as usize].push((y, scale * h));
+                partners[y as usize].push((x, scale * h));
+            }
+        }
+    }
+    for legs in &mut partners {
+        legs.sort_unstable();
+    }
+    partners
+}
+
+/// Every way to write `n = a³ + b³` with `1 <= a <= b`, by increasing `a`:
+/// - `sums_of_two_cubes(1729) = [(1, 12), (9, 10)]`
+/// - `sums_of_two_cubes(1000) = []`
+///
+/// Two pointers walking the cubes from both ends, O(n^(1/3)); any `n` up to
+/// `u128::MAX`, a sum of cubes past it counting as too large.
+pub fn sums_of_two_cubes(n: u128) -> Vec<(u64, u64)> {
+    let mut ways = Vec::new();
+    let (mut a, mut b) = (1u64, cube_root(n));
+    while a <= b {
+        let sum = cube(a).checked_add(cube(b));
+        if sum == Some(n) {
+            ways.push((a, b));
+        }
+        if sum.is_some_and(|sum| sum <= n) {
+            a += 1;
+        } else {
+            b -= 1;
+        }
+    }
+    ways
+}
+
+/// Every `n <= limit` that is a sum of two positive cubes in at least `ways` ways,
+/// ascending, each with its representations as in `sums_of_two_cubes`:
+/// - `taxicab_numbers(20_000, 2)`: `1729 = 1³ + 12³ = 9³ + 10³` and
+///   `4104 = 2³ + 16³ = 9³ + 15³`, then `13832`
+/// - the first result for `ways = 3` is `Ta(3) = 87539319`
+///
+/// The sums `a³ + b³` come out of a min-heap holding one candidate `b` per `a`, so they
+/// are produced in increasing order and equal sums are adjacent: O(S log limit^(1/3))
+/// for the S sums below `limit`, with memory for only `limit^(1/3)` of them.
+/// `MathError::OutOfRange` if `ways == 0`.
+pub fn taxicab_numbers(limit: u128, ways: usize) -> Result<Vec<TaxicabNumber>, MathError> {
+    if ways == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    let mut heap: BinaryHeap<Reverse<(u128, u64, u64)>> = (1..)
+        .map(|a| (2 * cube(a), a))
+        .take_while(|&(sum, _)| sum <= limit)
+        .map(|(sum, a)| Reverse((sum, a, a)))
+        .collect();
+    let mut found = Vec::new();
+    let mut run = TaxicabNumber {
+        value: 0,
+        ways: Vec::new(),
+    };
+    while let Some(Reverse((sum, a, b))) = heap.pop() {
+        if sum != run.value {
+            let next = TaxicabNumber {
+                value: sum,
+                ways: Vec::new(),
+            };
+            let done = core::mem::replace(&mut run, next);
+            if done.ways.len() >= ways {
+                found.push(done);
+            }
+        }
+        run.ways.push((a, b));
+        let next = cube(a) + cube(b + 1);
+        if next <= limit {
+            heap.push(Reverse((next, a, b + 1)));
+        }
+    }
+    if run.ways.len() >= ways {
+        found.push(run);
+    }
+    Ok(found)
+}
+
+/// Every way to write `n = x₁^exp + ... + x_k^exp` with `1 <= x₁ <= ... <= x_k`, in
+/// lexicographic order:
+/// - `representations_as_sum_of_k_powers(1729, 2, 3) = [[1, 12], [9, 10]]`
+/// - `representations_as_sum_of_k_powers(25, 2, 2) = [[3, 4]]`, not `[0, 5]`
+/// - `representations_as_sum_of_k_powers(7, 3, 2) = []`
+///
+/// Meet in the middle: every non-decreasing `⌊k/2⌋`-tuple is filed by its power sum,
+/// then each `⌈k/2⌉`-tuple looks up the sum that completes it to `n`, so the cost is
+/// about `B^⌈k/2⌉` tuples for the `B = ⌊n^(1/exp)⌋` bases rather than `B^(k-1)`.
+/// Tuples are pruned as soon as their smallest completion exceeds `n`, and every power
+/// and sum is checked, so no input overflows. `MathError::OutOfRange` if `k == 0` or
+/// `exp == 0`.
+pub fn representations_as_sum_of_k_powers(
+    n: u128,
+    k: usize,
+    exp: u32,
+) -> Result<Vec<Vec<u64>>, MathError> {
+    if k == 0 || exp == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    // powers[i] = (i + 1)^exp, every power <= n
+    let powers: Vec<u128> = (1u64..)
+        .map_while(|x| (x as u128).checked_pow(exp).filter(|&p| p <= n))
+        .collect();
+    let (half, rest) = (k / 2, k - k / 2);
+
+    let mut left: BTreeMap<u128, Vec<Vec<u64>>> = BTreeMap::new();
+    power_tuples(&powers, half, n, &mut |sum, tuple| {
+        left.entry(sum).or_default().push(tuple.to_vec());
+    });
+    let mut found = Vec::new();
+    power_tuples(&powers, rest, n, &mut |sum, tuple| {
+        let Some(halves) = left.get(&(n - sum)) else {
+            return;
+        };
+        // each representation once: the left half ends where the right half starts
+        for first in halves.iter().filter(|h| h.last() <= tuple.first()) {
+            let mut representation = first.clone();
+            representation.extend_from_slice(tuple);
+            found.push(representation);
+        }
+    });
+    found.sort_unstable();
+    Ok(found)
+}
+
+/// Calls `visit` with every non-decreasing `len`-tuple of bases, as `(Σ powers, tuple)`,
+/// whose sum is at most `limit`.
+fn power_tuples(powers: &[u128], len: usize, limit: u128, visit: &mut impl FnMut(u128, &[u64])) {
+    fn extend(
+        powers: &[u128],
+        start: usize,
+        left: usize,
+        sum: u128,
+        limit: u128,
+        tuple: &mut Vec<u64>,
+        visit: &mut impl FnMut(u128, &[u64]),
+    ) {
+        if left == 0 {
+            visit(sum, tuple);
+            return;
+        }
+        for (i, &power) in powers.iter().enumerate().skip(start) {
+            // the remaining bases are all at least i + 1
+            let smallest = power
+                .checked_mul(left as u128)
+                .and_then(|p| p.checked_add(sum));
+            if smallest.is_none_or(|s| s > limit) {
+                break;
+            }
+            tuple.push(i as u64 + 1);
+            extend(powers, i, left - 1, sum + power, limit, tuple, visit);
+            tuple.pop();
+        }
+    }
+    extend(
+        powers,
+        0,
+        len,
+        0,
+        limit,
+        &mut Vec::with_capacity(len),
+        visit,
+    );
+}
+
+/// `⌊∛u128::MAX⌋`, the largest `x` that `cube` takes.
+const MAX_CUBE_ROOT: u64 = 6_981_463_658_331;
+
+/// `x³` for `x <= MAX_CUBE_ROOT`.
+fn cube(x: u64) -> u128 {
+    let x = x as u128;
+    x * x * x
+}
+
+/// `⌊n^(1/3)⌋`, by bisection below `2^(⌊log2 n⌋ / 3 + 1)`, capped past `MAX_CUBE_ROOT`.
+fn cube_root(n: u128) -> u64 {
+    if n < 2 {
+        return n as u64;
+    }
+    let hi = (1u64 << (n.ilog2() / 3 + 1)).min(MAX_CUBE_ROOT + 1);
+    let (mut lo, mut hi) = (1u64, hi);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if cube(mid) <= n {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_square(n: u64) -> bool {
+        n.isqrt() * n.isqrt() == n
+    }
+
+    #[test]
+    fn test_euler_bricks() {
+        assert_eq!(
+            euler_bricks(250),
+            vec![EulerBrick {
+                edges: [44, 117, 240],
+                face_diagonals: [125, 244, 267],
+            }]
+        );
+        assert_eq!(euler_bricks(0), vec![]);
+        assert_eq!(euler_bricks(239), vec![]);
+        let edges: Vec<[u64; 3]> = euler_bricks(1000).iter().map(|b| b.edges).collect();
+        assert_eq!(
+            edges,
+            vec![
+                [44, 117, 240],
+                [85, 132, 720],
+                [88, 234, 480],
+                [132, 351, 720],
+                [140, 480, 693],
+                [160, 231, 792],
+                [176, 468, 960],
+                [240, 252, 275],
+                [480, 504, 550],
+                [720, 756, 825],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_euler_bricks_against_brute_force() {
+        let n = 500u64;
+        let mut brute = Vec::new();
+        for a in 1..=n {
+            for b in a + 1..=n {
+                if !is_square(a * a + b * b) {
+                    continue;
+                }
+                for c in b + 1..=n {
+                    if is_square(a * a + c * c) && is_square(b * b + c * c) {
+                        brute.push([a, b, c]);
+                    }
+                }
+            }
+        }
+        let found: Vec<[u64; 3]> = euler_bricks(n as usize).iter().map(|b| b.edges).collect();
+        assert_eq!(found, brute);
+        for brick in euler_bricks(2000) {
+            let [a, b, c] = brick.edges;
+            let [ab, ac, bc] = brick.face_diagonals;
+            assert_eq!(a * a + b * b, ab * ab);
+            assert_eq!(a * a + c * c, ac * ac);
+            assert_eq!(b * b + c * c, bc * bc);
+        }
+    }
+
+    #[test]
+    fn test_sums_of_two_cubes() {
+        assert_eq!(sums_of_two_cubes(1729), vec![(1, 12), (9, 10)]);
+        assert_eq!(sums_of_two_cubes(2), vec![(1, 1)]);
+        assert_eq!(sums_of_two_cubes(1000), vec![]);
+        assert_eq!(sums_of_two_cubes(0), vec![]);
+        assert_eq!(
+            sums_of_two_cubes(87_539_319),
+            vec![(167, 436), (228, 423), (255, 414)]
+        );
+        // Ta(4), with cubes past u32
+        assert_eq!(
+            sums_of_two_cubes(6_963_472_309_248),
+            vec![(2421, 19083), (5436, 18948), (10200, 18072), (13322, 16630)]
+        );
+    }
+
+    #[test]
+    fn test_cube_root_near_u128_max() {
+        let top = cube(MAX_CUBE_ROOT);
+        assert_eq!(cube_root(u128::MAX), MAX_CUBE_ROOT);
+        assert_eq!(cube_root(top), MAX_CUBE_ROOT);
+        assert_eq!(cube_root(top - 1), MAX_CUBE_ROOT - 1);
+        assert_eq!(cube_root(u128::MAX / 2), 5_541_191_377_756);
+        for n in [1u128 << 126, 1 << 127, u128::MAX - 1] {
+            let r = cube_root(n);
+            assert!(cube(r) <= n && (r == MAX_CUBE_ROOT || cube(r + 1) > n));
+        }
+    }
+
+    #[test]
+    fn test_taxicab_numbers() {
+        let taxicab = |value, ways: &[(u64, u64)]| TaxicabNumber {
+            value,
+            ways: ways.to_vec(),
+        };
+        assert_eq!(
+            taxicab_numbers(20_000, 2),
+            Ok(vec![
+                taxicab(1729, &[(1, 12), (9, 10)]),
+                taxicab(4104, &[(2, 16), (9, 15)]),
+                taxicab(13832, &[(2, 24), (18, 20)]),
+            ])
+        );
+        let ta3 = taxicab_numbers(87_539_319, 3).unwrap();
+        assert_eq!(
+            ta3,
+            vec![taxicab(87_539_319, &sums_of_two_cubes(87_539_319))]
+        );
+        // ways = 1 lists every sum of two cubes
+        let all = taxicab_numbers(100, 1).unwrap();
+        let sums: Vec<u128> = all.iter().map(|t| t.value).collect();
+        assert_eq!(sums, vec![2, 9, 16, 28, 35, 54, 65, 72, 91]);
+        for t in taxicab_numbers(1_000_000, 2).unwrap() {
+            assert_eq!(t.ways, sums_of_two_cubes(t.value));
+        }
+        assert_eq!(taxicab_numbers(1728, 2), Ok(vec![]));
+        assert_eq!(taxicab_numbers(100, 0), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_representations_as_sum_of_k_powers() {
+        assert_eq!(
+            representations_as_sum_of_k_powers(1729, 2, 3),
+            Ok(vec![vec![1, 12], vec![9, 10]])
+        );
+        assert_eq!(
+            representations_as_sum_of_k_powers(25, 2, 2),
+            Ok(vec![vec![3, 4]])
+        );
+        assert_eq!(representations_as_sum_of_k_powers(7, 3, 2), Ok(vec![]));
+        // 7 needs four squares
+        assert_eq!(
+            representations_as_sum_of_k_powers(7, 4, 2),
+            Ok(vec![vec![1, 1, 1, 2]])
+        );
+        assert_eq!(
+            representations_as_sum_of_k_powers(8, 1, 3),
+            Ok(vec![vec![2]])
+        );
+        // 635318657 = 59⁴ + 158⁴ = 133⁴ + 134⁴, Euler's smallest equal sums of fourth powers
+        assert_eq!(
+            representations_as_sum_of_k_powers(635_318_657, 2, 4),
+            Ok(vec![vec![59, 158], vec![133, 134]])
+        );
+        // agrees with the two-pointer search for cubes
+        for n in (1..5000u128).step_by(7) {
+            let ways: Vec<Vec<u64>> = sums_of_two_cubes(n)
+                .into_iter()
+                .map(|(a, b)| vec![a, b])
+                .collect();
+            assert_eq!(representations_as_sum_of_k_powers(n, 2, 3), Ok(ways));
+        }
+        assert_eq!(
+            representations_as_sum_of_k_powers(10, 0, 2),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            representations_as_sum_of_k_powers(10, 2, 0),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_representations_against_brute_force() {
+        // r(n) for five squares, counted with order and sign removed
+        for n in 0..=120u128 {
+            let mut brute = Vec::new();
+            for a in 1..=10u64 {
+                for b in a..=10 {
+                    for c in b..=10 {
+                        for d in c..=10 {
+                            for e in d..=10 {
+                                let sum: u64 = [a, b, c, d, e].iter().map(|x| x * x).sum();
+                                if sum as u128 == n {
+                                    brute.push(vec![a, b, c, d, e]);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            assert_eq!(
+                representations_as_sum_of_k_powers(n, 5, 2),
+                Ok(brute),
+                "{n}"
+            );
+        }
+        // huge exponents and n near u128::MAX do not overflow
+        assert_eq!(
+            representations_as_sum_of_k_powers(u128::MAX, 3, 100),
+            Ok(vec![])
+        );
+        assert_eq!(
+            representations_as_sum_of_k_powers(1 << 127, 2, 127),
+            Ok(vec![])
+        );
+        assert_eq!(
+            representations_as_sum_of_k_powers(2, 2, 127),
+            Ok(vec![vec![1, 1]])
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::f64::consts::TAU;
+
+use crate::algebra::divisors::trial_factors;
+use crate::algebra::gcd::gcd_u64;
+use crate::algebra::modular::{crt, mod_pow, mul_mod};
+use crate::errors::MathError;
+
+/// Largest modulus accepted by `DirichletGroup::new`; the group keeps a table of size `n`.
+pub const MAX_MODULUS: u64 = 1 << 20;
+
+/// The unit group `(Z/nZ)*` split into cyclic factors, with a discrete log table.
+///
+/// Each odd prime power `p^e` in `n` contributes a primitive root, `4` contributes `-1`
+/// and `2^e` with `e >= 3` contributes `-1` and `5`; every generator is lifted to mod `n`
+/// by CRT. A unit is then `∏ gᵢ^aᵢ` for a unique exponent vector `(aᵢ)`, and its
+/// characters are indexed the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirichletGroup {
+    modulus: u64,
+    order: u64,
+    generators: Vec<(u64, u64)>,
+    logs: Vec<Option<Vec<u64>>>,
+}
+
+/// A Dirichlet character mod `n`, as `χ(a) = ζ^k` with `ζ = e^(2πi/φ(n))`.
+///
+/// Every character value is a `φ(n)`-th root of unity, so the table stores the exponent
+/// `k` for each unit and `None` for residues sharing a factor with `n`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirichletCharacter {
+    modulus: u64,
+    phi: u64,
+    table: Vec<Option<u64>>,
+}
+
+impl DirichletGroup {
+    /// Errors with `OutOfRange` unless `1 <= n <= MAX_MODULUS`.
+    pub fn new(n: u64) -> Result<Self, MathError> {
+        if n == 0 || n > MAX_MODULUS {
+            return Err(MathError::OutOfRange);
+        }
+
+        let mut generators = Vec::new();
+        for (p, e) in trial_factors(n as u128) {
+            let (p, e) = (p as u64, e);
+            let q = p.pow(e);
+            let local: Vec<(u64, u64)> = match (p, e) {
+                (2, 1) => vec![],
+                (2, 2) => vec![(3, 2)],
+                (2, _) => vec![(q - 1, 2), (5, q / 4)],
+                _ => vec![(primitive_root_prime_power(p, e)?, q / p * (p - 1))],
+            };
+            for (g, order) in local {
+                // g mod q, 1 mod n/q
+                generators.push((crt(&[(g, q), (1, n / q)])?.0, order));
+            }
+        }
+
+        // walk every exponent vector, one generator at a time
+        let mut units = vec![(1 % n, Vec::new())];
+        for &(g, order) in &generators {
+            let mut next = Vec::with_capacity(units.len() * order as usize);
+            for (x, exps) in &units {
+                let mut y = *x;
+                for a in 0..order {
+                    let mut e = exps.clone();
+                    e.push(a);
+                    next.push((y, e));
+                    y = mul_mod(y, g, n);
+                }
+            }
+            units = next;
+        }
+
+        let order = units.len() as u64;
+        let mut logs = vec![None; n as usize];
+        for (x, exps) in units {
+            logs[x as usize] = Some(exps);
+        }
+        Ok(DirichletGroup {
+            modulus: n,
+            order,
+            generators,
+            logs,
+        })
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// `φ(n)`, the number of units and of characters.
+    pub fn order(&self) -> u64 {
+        self.order
+    }
+
+    /// The generators as `(g, order of g)`.
+    pub fn generators(&self) -> &[(u64, u64)] {
+        &self.generators
+    }
+
+    /// The character sending the `i`-th generator to `e^(2πi·index[i]/ord(gᵢ))`.
+    ///
+    /// The all-zero index is the principal character. Errors with `DimensionMismatch`
+    /// unless there is one index per generator and `OutOfRange` if `index[i] >= ord(gᵢ)`.
+    pub fn character(&self, index: &[u64]) -> Result<DirichletCharacter, MathError> {
+        if index.len() != self.generators.len() {
+            return Err(MathError::DimensionMismatch);
+        }
+        if index
+            .iter()
+            .zip(&self.generators)
+            .any(|(&j, &(_, order))| j >= order)
+        {
+            return Err(MathError::OutOfRange);
+        }
+        // gᵢ ↦ ζ^(jᵢ·φ/ord(gᵢ))
+        let steps: Vec<u64> = index
+            .iter()
+            .zip(&self.generators)
+            .map(|(&j, &(_, order))| j * (self.order / order))
+            .collect();
+        let table = self
+            .logs
+            .iter()
+            .map(|log| {
+                log.as_ref().map(|exps| {
+                    exps.iter().zip(&steps).fold(0, |acc, (&a, &s)| {
+                        (acc + mul_mod(a, s, self.order)) % self.order
+                    })
+                })
+            })
+            .collect();
+        Ok(DirichletCharacter {
+            modulus: self.modulus,
+            phi: self.order,
+            table,
+        })
+    }
+
+    /// All `φ(n)` characters, the principal one first.
+    pub fn characters(&self) -> Vec<DirichletCharacter> {
+        let mut indices = vec![Vec::new()];
+        for &(_, order) in &self.generators {
+            indices = indices
+                .iter()
+                .flat_map(|idx: &Vec<u64>| {
+                    (0..order).map(move |j| {
+                        let mut next = idx.clone();
+                        next.push(j);
+                        next
+                    })
+                })
+                .collect();
+        }
+        indices
+            .iter()
+            .filter_map(|idx| self.character(idx).ok())
+            .collect()
+    }
+}
+
+impl DirichletCharacter {
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// `k` with `χ(a) = e^(2πik/φ(n))`, or `None` when `gcd(a, n) > 1`.
+    pub fn exponent(&self, a: u64) -> Option<u64> {
+        self.table[(a % self.modulus) as usize]
+    }
+
+    /// `χ(a)` as `(re, im)`; `(0, 0)` when `gcd(a, n) > 1`.
+    #[cfg(feature = "std")]
+    pub fn eval(&self, a: u64) -> (f64, f64) {
+        match self.exponent(a) {
+            Some(k) => root_of_unity(k, self.phi),
+            None => (0.0, 0.0),
+        }
+    }
+
+    /// Smallest `m >= 1` with `χ^m` principal.
+    pub fn order(&self) -> u64 {
+        let g = self
+            .table
+            .iter()
+            .flatten()
+            .fold(self.phi, |g, &k| gcd_u64(g, k));
+        self.phi / g
+    }
+
+    pub fn is_principal(&self) -> bool {
+        self.order() == 1
+    }
+
+    /// Real-valued, i.e. of order at most 2.
+    pub fn is_real(&self) -> bool {
+        self.order() <= 2
+    }
+}
+
+/// Gauss sum `g(χ) = Σ χ(a)·e^(2πia/n)` over `a mod n`, as `(re, im)`.
+///
+/// For a primitive character `|g(χ)|² = n`; for the Legendre symbol mod an odd prime `p`,
+/// `g(χ) = √p` if `p ≡ 1 (mod 4)` and `i√p` if `p ≡ 3 (mod 4)`.
+#[cfg(feature = "std")]
+pub fn gauss_sum(chi: &DirichletCharacter) -> (f64, f64) {
+    let n = chi.modulus;
+    (0..n).fold((0.0, 0.0), |acc, a| {
+        let Some(k) = chi.exponent(a) else {
+            return acc;
+        };
+        let value = complex_mul(root_of_unity(k, chi.phi), root_of_unity(a, n));
+        (acc.0 + value.0, acc.1 + value.1)
+    })
+}
+
+/// Jacobi sum `J(χ, ψ) = Σ χ(a)·ψ(1 - a)` over `a mod n`, as `(re, im)`.
+///
+/// Mod a prime `p`, `|J(χ, ψ)| = √p` whenever `χ`, `ψ` and `χψ` are all non-principal.
+/// Errors with `DimensionMismatch` if the moduli differ.
+#[cfg(feature = "std")]
+pub fn jacobi_sum(
+    chi: &DirichletCharacter,
+    psi: &DirichletCharacter,
+) -> Result<(f64, f64), MathError> {
+    if chi.modulus != psi.modulus {
+        return Err(MathError::DimensionMismatch);
+    }
+    let n = chi.modulus;
+    Ok((0..n).fold((0.0, 0.0), |acc, a| {
+        let value = complex_mul(chi.eval(a), psi.eval((n + 1 - a) % n));
+        (acc.0 + value.0, acc.1 + value.1)
+    }))
+}
+
+/// Primitive root mod `p^e` for an odd prime `p`: a primitive root `g` mod `p`, replaced
+/// by `g + p` in the rare case `g^(p-1) ≡ 1 (mod p²)`.
+fn primitive_root_prime_power(p: u64, e: u32) -> Result<u64, MathError> {
+    let prime_factors: Vec<u64> = trial_factors((p - 1) as u128)
+        .into_iter()
+        .map(|(q, _)| q as u64)
+        .collect();
+    let mut g = 2 % p;
+    while g < p {
+        let mut is_generator = true;
+        for &q in &prime_factors {
+            if mod_pow(g, (p - 1) / q, p)? == 1 {
+                is_generator = false;
+                break;
+            }
+        }
+        if is_generator {
+            break;
+        }
+        g += 1;
+    }
+    if e > 1 && mod_pow(g, p - 1, p * p)? == 1 {
+        g += p;
+    }
+    Ok(g)
+}
+
+/// `e^(2πik/m)` as `(re, im)`.
+#[cfg(feature = "std")]
+fn root_of_unity(k: u64, m: u64) -> (f64, f64) {
+    let angle = TAU * (k % m) as f64 / m as f64;
+    (angle.cos(), angle.sin())
+}
+
+#[cfg(feature = "std")]
+fn complex_mul(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    const EPS: f64 = 1e-9;
+
+    #[cfg(feature = "std")]
+    fn close(a: (f64, f64), b: (f64, f64)) -> bool {
+        (a.0 - b.0).abs() < EPS && (a.1 - b.1).abs() < EPS
+    }
+
+    #[cfg(feature = "std")]
+    fn norm2(z: (f64, f64)) -> f64 {
+        z.0 * z.0 + z.1 * z.1
+    }
+
+    #[test]
+    fn test_group_structure() {
+        let g = DirichletGroup::new(13).unwrap();
+        assert_eq!(g.order(), 12);
+        assert_eq!(g.generators(), &[(2, 12)]);
+
+        // (Z/24Z)* = C2 × C2 × C2
+        let g = DirichletGroup::new(24).unwrap();
+        assert_eq!(g.order(), 8);
+        assert_eq!(g.generators().len(), 3);
+        assert!(g.characters().iter().all(|chi| chi.is_real()));
+
+        assert_eq!(DirichletGroup::new(1).unwrap().characters().len(), 1);
+        assert_eq!(DirichletGroup::new(0), Err(MathError::OutOfRange));
+        assert_eq!(
+            DirichletGroup::new(MAX_MODULUS + 1),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_characters_are_multiplicative() {
+        for n in [9, 20, 32, 45] {
+            let group = DirichletGroup::new(n).unwrap();
+            let chars = group.characters();
+            assert_eq!(chars.len() as u64, group.order());
+            assert!(chars[0].is_principal());
+            for chi in &chars {
+                for a in 0..n {
+                    for b in 0..n {
+                        let lhs = chi.eval(a * b);
+                        let rhs = complex_mul(chi.eval(a), chi.eval(b));
+                        assert!(close(lhs, rhs), "n = {n}, a = {a}, b = {b}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_orthogonality() {
+        let group = DirichletGroup::new(21).unwrap();
+        for chi in group.characters() {
+            let sum = (0..21).fold((0.0, 0.0), |acc, a| {
+                let v = chi.eval(a);
+                (acc.0 + v.0, acc.1 + v.1)
+            });
+            let expected = if chi.is_principal() { 12.0 } else { 0.0 };
+            assert!(close(sum, (expected, 0.0)));
+        }
+    }
+
+    #[test]
+    fn test_character_errors() {
+        let group = DirichletGroup::new(15).unwrap();
+        assert_eq!(group.character(&[0]), Err(MathError::DimensionMismatch));
+        assert_eq!(group.character(&[0, 4]), Err(MathError::OutOfRange));
+        let chi = group.character(&[1, 0]).unwrap();
+        assert_eq!(chi.exponent(5), None);
+        assert_eq!(chi.order(), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_quadratic_gauss_sums() {
+        for (p, expected) in [(13u64, (13f64.sqrt(), 0.0)), (11, (0.0, 11f64.sqrt()))] {
+            let group = DirichletGroup::new(p).unwrap();
+            let legendre = group.character(&[(p - 1) / 2]).unwrap();
+            assert!(legendre.is_real() && !legendre.is_principal());
+            assert!(close(gauss_sum(&legendre), expected));
+        }
+        // the principal character mod p gives μ(p) = -1
+        let principal = DirichletGroup::new(7).unwrap().character(&[0]).unwrap();
+        assert!(close(gauss_sum(&principal), (-1.0, 0.0)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gauss_and_jacobi_norms() {
+        let p = 31;
+        let chars = DirichletGroup::new(p).unwrap().characters();
+        for chi in &chars[1..] {
+            assert!((norm2(gauss_sum(chi)) - p as f64).abs() < 1e-6);
+        }
+        // χ, ψ and χψ non-principal ⇒ |J(χ, ψ)|² = p
+        let (chi, psi) = (&chars[3], &chars[7]);
+        assert!((norm2(jacobi_sum(chi, psi).unwrap()) - p as f64).abs() < 1e-6);
+        // J(χ, χ̄) = -χ(-1) for non-principal χ
+        let (chi, chi_bar) = (&chars[5], &chars[25]);
+        let expected = chi.eval(p - 1);
+        assert!(close(
+            jacobi_sum(chi, chi_bar).unwrap(),
+            (-expected.0, -expected.1)
+        ));
+
+        let other = DirichletGroup::new(29).unwrap().character(&[1]).unwrap();
+        assert_eq!(jacobi_sum(chi, &other), Err(MathError::DimensionMismatch));
+    }
+}
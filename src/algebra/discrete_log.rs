@@ -0,0 +1,358 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::Cell;
+
+use crate::algebra::gcd::gcd_u64;
+use crate::algebra::modular::mod_pow;
+use crate::algebra::prime_nbr::is_prime_u64;
+use crate::errors::MathError;
+use crate::outcome::{Outcome, measure};
+
+/// Baby-step giant-step over any group given by its operation and identity.
+///
+/// Finds the smallest `x` in `0..=order_bound` with `g^x = h`, or `None`. Baby steps
+/// store `h·g^j` for `0 <= j < m`, giant steps compute `(g^m)^i`, and a match
+/// `h·g^j = g^(i·m)` gives `x = i·m - j`. Only `op` is called, but that last step
+/// cancels `g^j`, so `g` must be invertible: in a monoid a match can be spurious and
+/// the answer has to be checked. Costs O(√order_bound) group operations and memory.
+///
+/// Works for (Z/nZ)* (see `discrete_log_mod`), elliptic-curve point groups, or any
+/// user-supplied group whose elements are `Ord + Clone`.
+pub fn baby_step_giant_step<T, F>(g: &T, h: &T, order_bound: u64, identity: T, op: F) -> Option<u64>
+where
+    T: Ord + Clone,
+    F: Fn(&T, &T) -> T,
+{
+    bsgs_from(g, h, order_bound, identity.clone(), identity, op)
+}
+
+/// Smallest `x <= order_bound` with `start·g^x = h`: the giant steps are
+/// `start·(g^m)^i`, everything else as in `baby_step_giant_step`.
+fn bsgs_from<T, F>(g: &T, h: &T, order_bound: u64, identity: T, start: T, op: F) -> Option<u64>
+where
+    T: Ord + Clone,
+    F: Fn(&T, &T) -> T,
+{
+    if *h == start {
+        return Some(0);
+    }
+    let m = order_bound.isqrt() + 1;
+
+    // value -> largest j with h·g^j = value, so the first hit gives the smallest x
+    let mut baby = BTreeMap::new();
+    let mut current = h.clone();
+    for j in 0..m {
+        baby.insert(current.clone(), j);
+        current = op(&current, g);
+    }
+
+    let mut g_m = identity.clone();
+    for _ in 0..m {
+        g_m = op(&g_m, g);
+    }
+
+    let mut giant = start;
+    for i in 1..=m {
+        giant = op(&giant, &g_m);
+        if let Some(&j) = baby.get(&giant) {
+            let x = i * m - j;
+            return (x <= order_bound).then_some(x);
+        }
+    }
+    None
+}
+
+/// Smallest `x >= 0` with `g^x ≡ h (mod n)`, or `None` if there is none.
+///
+/// - `discrete_log_mod(3, 13, 17) = Some(4)` since `3^4 = 81 ≡ 13`
+/// - `discrete_log_mod(2, 8, 12) = Some(3)`, `g` not a unit
+///
+/// A unit `g` goes straight to `baby_step_giant_step`. Otherwise the powers of `g` only
+/// cycle after a tail shorter than `log₂ n`, where `g` cannot be cancelled: the first
+/// `TAIL` powers are compared directly, the giant steps then start from `g^TAIL` on the
+/// cycle, and the answer is confirmed with `mod_pow`. `MathError::DivisionByZero` if `n == 0`.
+pub fn discrete_log_mod(g: u64, h: u64, n: u64) -> Result<Option<u64>, MathError> {
+    if n == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let mul = |a: &u64, b: &u64| ((*a as u128 * *b as u128) % n as u128) as u64;
+    Ok(log_mod(g % n, h % n, n, mul))
+}
+
+/// `discrete_log_mod` with its cost: method `"baby-step giant-step"`, one iteration per
+/// multiplication mod `n`. Errors as `discrete_log_mod`.
+pub fn discrete_log_mod_outcome(g: u64, h: u64, n: u64) -> Result<Outcome<Option<u64>>, MathError> {
+    if n == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    measure(|| {
+        let steps = Cell::new(0);
+        let mul = |a: &u64, b: &u64| {
+            steps.set(steps.get() + 1);
+            ((*a as u128 * *b as u128) % n as u128) as u64
+        };
+        let x = log_mod(g % n, h % n, n, mul);
+        Ok(Outcome::new(x, "baby-step giant-step", steps.get()))
+    })
+}
+
+/// Exponents compared directly for a non-unit base, past the longest tail of its powers:
+/// `g^x mod n` is on its cycle once `x` reaches every exponent in the factorization of
+/// `n`, all below 64.
+const TAIL: u64 = 64;
+
+/// `discrete_log_mod` on reduced `g`, `h`, with `mul` the multiplication mod `n`.
+fn log_mod(g: u64, h: u64, n: u64, mul: impl Fn(&u64, &u64) -> u64) -> Option<u64> {
+    if gcd_u64(g, n) == 1 {
+        return baby_step_giant_step(&g, &h, n, 1 % n, mul);
+    }
+    let mut power = 1 % n;
+    for x in 0..TAIL {
+        if power == h {
+            return Some(x);
+        }
+        power = mul(&power, &g);
+    }
+    // from g^TAIL on, multiplying by g permutes the cycle, so a match
+    // h·g^j = g^TAIL·g^(i·m) is genuine once h is on it; off the cycle h is no power at
+    // all, which the check rejects
+    let x = bsgs_from(&g, &h, n, 1 % n, power, mul)? + TAIL;
+    (mod_pow(g, x, n) == Ok(h)).then_some(x)
+}
+
+/// Every discrete log to base `g` modulo a small prime `p`, precomputed once so that
+/// each query is a table lookup:
+/// - `DlogTable::new(3, 17)?.log(13) = Some(4)`
+///
+/// Built by walking the powers of `g` in O(p) time, with two `u32` tables: one indexed by
+/// residue, one by exponent (for `pow`). Lookups only read the tables, so a `DlogTable`
+/// is `Send + Sync` and can be shared across threads behind an `Arc`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DlogTable {
+    p: u64,
+    g: u64,
+    /// `logs[h]` is the smallest `x` with `g^x ≡ h`, `u32::MAX` if `h` is not a power.
+    logs: Vec<u32>,
+    /// `powers[x] = g^x` for `0 <= x < order`.
+    powers: Vec<u32>,
+}
+
+impl DlogTable {
+    /// Errors with `OutOfRange` unless `p` is a prime below `2^32` and `g` is nonzero
+    /// mod `p`.
+    pub fn new(g: u64, p: u64) -> Result<Self, MathError> {
+        if p > u32::MAX as u64 || !is_prime_u64(p) || g.is_multiple_of(p) {
+            return Err(MathError::OutOfRange);
+        }
+        let g = g % p;
+        let mut logs = vec![u32::MAX; p as usize];
+        let mut powers = Vec::new();
+        let mut x = 1u64;
+        // the powers of a unit come back to 1 after exactly `order` steps
+        while logs[x as usize] == u32::MAX {
+            logs[x as usize] = powers.len() as u32;
+            powers.push(x as u32);
+            x = x * g % p;
+        }
+        Ok(DlogTable { p, g, logs, powers })
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.p
+    }
+
+    pub fn generator(&self) -> u64 {
+        self.g
+    }
+
+    /// Multiplicative order of `g`, `p - 1` exactly when `g` is a primitive root.
+    pub fn order(&self) -> u64 {
+        self.powers.len() as u64
+    }
+
+    /// Smallest `x >= 0` with `g^x ≡ h (mod p)`, or `None` if `h` is not a power of `g`.
+    pub fn log(&self, h: u64) -> Option<u64> {
+        let x = self.logs[(h % self.p) as usize];
+        (x != u32::MAX).then_some(x as u64)
+    }
+
+    /// `g^x mod p`, from the same walk.
+    pub fn pow(&self, x: u64) -> u64 {
+        self.powers[(x % self.order()) as usize] as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discrete_log_mod_small() {
+        assert_eq!(discrete_log_mod(3, 13, 17), Ok(Some(4)));
+        assert_eq!(discrete_log_mod(3, 1, 17), Ok(Some(0)));
+        assert_eq!(discrete_log_mod(2, 3, 7), Ok(None)); // 2 generates {1, 2, 4}
+        assert_eq!(discrete_log_mod(2, 3, 0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_discrete_log_mod_is_smallest() {
+        let p = 1009;
+        for h in 1..p {
+            let x = discrete_log_mod(11, h, p).unwrap();
+            // 11 is a primitive root mod 1009
+            let x = x.expect("every residue is a power of a primitive root");
+            assert_eq!(mod_pow(11, x, p), Ok(h));
+            assert!(x < p - 1);
+        }
+    }
+
+    #[test]
+    fn test_discrete_log_mod_large_prime() {
+        let p = 1_000_000_007;
+        let h = mod_pow(5, 123_456_789, p).unwrap();
+        let x = discrete_log_mod(5, h, p).unwrap().unwrap();
+        assert_eq!(mod_pow(5, x, p), Ok(h));
+    }
+
+    #[test]
+    fn test_discrete_log_composite_modulus() {
+        // 2^x mod 15 cycles through 1, 2, 4, 8
+        assert_eq!(discrete_log_mod(2, 8, 15), Ok(Some(3)));
+        assert_eq!(discrete_log_mod(2, 7, 15), Ok(None));
+    }
+
+    #[test]
+    fn test_discrete_log_non_unit_base() {
+        assert_eq!(discrete_log_mod(2, 8, 12), Ok(Some(3)));
+        assert_eq!(discrete_log_mod(2, 4, 8), Ok(Some(2)));
+        assert_eq!(discrete_log_mod(6, 0, 12), Ok(Some(2)));
+        assert_eq!(discrete_log_mod(6, 3, 12), Ok(None));
+        assert_eq!(discrete_log_mod(6, 0, 36), Ok(Some(2)));
+        assert_eq!(discrete_log_mod(2, 0, 1 << 40), Ok(Some(40)));
+        assert_eq!(discrete_log_mod(0, 0, 10), Ok(Some(1)));
+        // against the powers written out, tail and cycle alike
+        for n in 1..60u64 {
+            for g in 0..n {
+                let mut first = alloc::collections::BTreeMap::new();
+                let mut power = 1 % n;
+                for x in 0..2 * n + 64 {
+                    first.entry(power).or_insert(x);
+                    power = power * g % n;
+                }
+                for h in 0..n {
+                    assert_eq!(
+                        discrete_log_mod(g, h, n),
+                        Ok(first.get(&h).copied()),
+                        "{g}^x = {h} mod {n}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_discrete_log_non_unit_long_cycle() {
+        // cycles far longer than TAIL², after a tail of one step or of 12
+        for (g, n) in [(2, 2018), (6, 4096 * 10_007)] {
+            let mut first = alloc::collections::BTreeMap::new();
+            let mut power = 1 % n;
+            for x in 0..2 * 10_007 + 64 {
+                first.entry(power).or_insert(x);
+                power = power * g % n;
+            }
+            for (&h, &x) in first.iter().step_by(7) {
+                assert_eq!(
+                    discrete_log_mod(g, h, n),
+                    Ok(Some(x)),
+                    "{g}^x = {h} mod {n}"
+                );
+            }
+            assert_eq!(discrete_log_mod(g, 3, n), Ok(None));
+        }
+    }
+
+    #[test]
+    fn test_discrete_log_mod_outcome() {
+        let p = 1_000_000_007;
+        let h = mod_pow(5, 123_456_789, p).unwrap();
+        let outcome = discrete_log_mod_outcome(5, h, p).unwrap();
+        assert_eq!(*outcome, discrete_log_mod(5, h, p).unwrap());
+        assert_eq!(outcome.method, "baby-step giant-step");
+        // m = isqrt(p) + 1 baby steps and as many to build g^m, then the giant steps
+        let m = p.isqrt() + 1;
+        assert!(outcome.iterations > 2 * m && outcome.iterations <= 3 * m);
+        assert_eq!(outcome.duration.is_some(), cfg!(feature = "std"));
+        assert_eq!(discrete_log_mod_outcome(3, 1, 17).unwrap().iterations, 0);
+        assert_eq!(
+            discrete_log_mod_outcome(2, 3, 0),
+            Err(MathError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_generic_additive_group() {
+        // Z/100 under addition: smallest x with 7x ≡ 31 (mod 100) is 33
+        let add = |a: &u64, b: &u64| (a + b) % 100;
+        assert_eq!(baby_step_giant_step(&7, &31, 100, 0, add), Some(33));
+    }
+
+    #[test]
+    fn test_generic_permutation_group() {
+        // powers of a 5-cycle acting on 0..5
+        let compose = |a: &Vec<usize>, b: &Vec<usize>| b.iter().map(|&i| a[i]).collect();
+        let cycle = vec![1, 2, 3, 4, 0];
+        let identity: Vec<usize> = (0..5).collect();
+        let target = vec![3, 4, 0, 1, 2]; // cycle^3
+        assert_eq!(
+            baby_step_giant_step(&cycle, &target, 120, identity.clone(), compose),
+            Some(3)
+        );
+        let swap = vec![1, 0, 2, 3, 4];
+        assert_eq!(
+            baby_step_giant_step(&cycle, &swap, 120, identity, compose),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dlog_table() {
+        let table = DlogTable::new(3, 17).unwrap();
+        assert_eq!(table.log(13), Some(4));
+        assert_eq!(table.order(), 16);
+        assert_eq!((table.modulus(), table.generator()), (17, 3));
+        assert_eq!(table.log(0), None);
+        assert_eq!(table.log(13 + 17), Some(4));
+        assert_eq!(table.pow(4), 13);
+        assert_eq!(table.pow(20), 13);
+
+        // 9 ≡ 2 generates the squares mod 7: {1, 2, 4}
+        let table = DlogTable::new(9, 7).unwrap();
+        assert_eq!(table.order(), 3);
+        assert_eq!(table.log(4), Some(2));
+        assert_eq!(table.log(3), None);
+
+        assert_eq!(DlogTable::new(1, 2).map(|t| t.log(1)), Ok(Some(0)));
+        assert_eq!(DlogTable::new(3, 15), Err(MathError::OutOfRange));
+        assert_eq!(DlogTable::new(17, 17), Err(MathError::OutOfRange));
+        assert_eq!(DlogTable::new(2, 4_294_967_311), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_dlog_table_matches_bsgs() {
+        let p = 1009;
+        for g in [11, 4, 1008, 1] {
+            let table = DlogTable::new(g, p).unwrap();
+            for h in 0..p {
+                assert_eq!(
+                    Some(table.log(h)),
+                    discrete_log_mod(g, h, p).ok(),
+                    "{g}, {h}"
+                );
+            }
+            for x in 0..2 * p {
+                assert_eq!(Ok(table.pow(x)), mod_pow(g, x, p));
+            }
+        }
+    }
+}
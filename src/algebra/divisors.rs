@@ -0,0 +1,166 @@
+use alloc::vec::Vec;
+
+use crate::algebra::factorization::Factorization;
+use crate::errors::MathError;
+
+/// Sum of divisors σ(n), computed in `u128` so that results above `i64::MAX` are exact.
+///
+/// - `sigma(12) = 1 + 2 + 3 + 4 + 6 + 12 = 28`
+/// - `sigma(1) = 1`
+///
+/// Returns `MathError::PositifIntegerRequired` for `n < 1`.
+pub fn sigma(n: i64) -> Result<u128, MathError> {
+    if n < 1 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    sigma_u128(n as u128)
+}
+
+/// σ(n) for `u128` inputs; `MathError::Overflow` only if the sum exceeds `u128::MAX`.
+///
+/// Factorizes by trial division, so the cost grows with √p of the largest prime factor.
+pub fn sigma_u128(n: u128) -> Result<u128, MathError> {
+    Factorization::new(n)?.sigma(1)
+}
+
+/// One aliquot step: s(n) = σ(n) - n, the sum of proper divisors.
+pub fn aliquot_step(n: u128) -> Result<u128, MathError> {
+    Ok(sigma_u128(n)? - n)
+}
+
+/// Aliquot sequence n, s(n), s(s(n)), ... with at most `max_len` terms.
+///
+/// Stops after reaching 0, or just before a term would repeat
+/// (perfect, amicable and sociable cycles). Terms are `u128` because
+/// the sequence quickly leaves the `i64` range.
+pub fn aliquot_sequence(n: i64, max_len: usize) -> Result<Vec<u128>, MathError> {
+    if n < 1 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+
+    let mut sequence = Vec::new();
+    let mut current = n as u128;
+    while sequence.len() < max_len {
+        if sequence.contains(&current) {
+            break;
+        }
+        sequence.push(current);
+        if current == 0 {
+            break;
+        }
+        current = aliquot_step(current)?;
+    }
+    Ok(sequence)
+}
+
+/// `true` when σ(n) = 2n (6, 28, 496, 8128, ...).
+pub fn is_perfect(n: i64) -> Result<bool, MathError> {
+    let sum = sigma(n)?;
+    Ok(sum == 2 * n as u128)
+}
+
+/// Prime-power decomposition of n by trial division.
+pub(crate) fn trial_factors(n: u128) -> Vec<(u128, u32)> {
+    trial_factors_up_to(n, u128::MAX).0
+}
+
+/// Trial division by the candidates `p <= bound`: the prime powers found, the cofactor
+/// left, which is 1 unless the bound stopped the search before `p² > n`, and the number
+/// of candidates tried.
+pub(crate) fn trial_factors_up_to(mut n: u128, bound: u128) -> (Vec<(u128, u32)>, u128, u64) {
+    let mut factors = Vec::new();
+    let mut p: u128 = 2;
+    let mut tried = 0;
+    while p * p <= n {
+        if p > bound {
+            return (factors, n, tried);
+        }
+        tried += 1;
+        if n.is_multiple_of(p) {
+            let mut e = 0;
+            while n.is_multiple_of(p) {
+                n /= p;
+                e += 1;
+            }
+            factors.push((p, e));
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+    (factors, 1, tried)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_sigma_basic() {
+        assert_eq!(sigma(1), Ok(1));
+        assert_eq!(sigma(12), Ok(28));
+        assert_eq!(sigma(13), Ok(14));
+        assert_eq!(sigma(360), Ok(1170));
+    }
+
+    #[test]
+    fn test_sigma_invalid_input() {
+        assert_eq!(sigma(0), Err(MathError::PositifIntegerRequired));
+        assert_eq!(sigma(-6), Err(MathError::PositifIntegerRequired));
+        assert_eq!(sigma_u128(0), Err(MathError::PositifIntegerRequired));
+    }
+
+    #[test]
+    fn test_sigma_escalates_past_i64() {
+        // 2^62 * 3: σ = (2^63 - 1) * 4 > i64::MAX
+        let n = (1i64 << 62) / 2 * 3;
+        let expected = ((1u128 << 62) - 1) * 4;
+        assert_eq!(sigma(n), Ok(expected));
+        assert!(expected > i64::MAX as u128);
+    }
+
+    #[test]
+    fn test_sigma_u128_overflow() {
+        // 2^126 * 3: σ = (2^127 - 1) * 4
+        assert_eq!(sigma_u128(3 << 126), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_aliquot_sequence_terminates() {
+        assert_eq!(
+            aliquot_sequence(12, 20),
+            Ok(vec![12, 16, 15, 9, 4, 3, 1, 0])
+        );
+        assert_eq!(aliquot_sequence(1, 20), Ok(vec![1, 0]));
+    }
+
+    #[test]
+    fn test_aliquot_sequence_cycles() {
+        assert_eq!(aliquot_sequence(6, 20), Ok(vec![6]));
+        assert_eq!(aliquot_sequence(220, 20), Ok(vec![220, 284]));
+        assert_eq!(aliquot_sequence(95, 20), Ok(vec![95, 25, 6]));
+    }
+
+    #[test]
+    fn test_aliquot_sequence_max_len() {
+        assert_eq!(aliquot_sequence(12, 3), Ok(vec![12, 16, 15]));
+        assert_eq!(aliquot_sequence(12, 0), Ok(vec![]));
+        assert_eq!(
+            aliquot_sequence(0, 5),
+            Err(MathError::PositifIntegerRequired)
+        );
+    }
+
+    #[test]
+    fn test_is_perfect() {
+        for n in [6, 28, 496, 8128, 33_550_336] {
+            assert_eq!(is_perfect(n), Ok(true), "{} is perfect", n);
+        }
+        for n in [1, 12, 220, 1000] {
+            assert_eq!(is_perfect(n), Ok(false), "{} is not perfect", n);
+        }
+        assert_eq!(is_perfect(-6), Err(MathError::PositifIntegerRequired));
+    }
+}
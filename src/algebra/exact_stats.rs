@@ -0,0 +1,353 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "internal-bigint")]
+use crate::algebra::big_rational::BigRational;
+#[cfg(feature = "internal-bigint")]
+use crate::algebra::bigu::BigU;
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// The most frequent values of a sample, all of them when several tie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Modes {
+    /// Ascending.
+    pub values: Vec<i64>,
+    /// How often each of them occurs.
+    pub count: usize,
+}
+
+impl Modes {
+    /// `true` when a single value is the most frequent.
+    pub fn is_unique(&self) -> bool {
+        self.values.len() == 1
+    }
+}
+
+/// Arithmetic mean as a reduced fraction:
+/// - `mean(&[1, 2, 3, 5]) = 11/4`
+///
+/// The sum is taken in `i128`, so only a mean that is itself outside `i64` after
+/// reduction fails, with `Overflow`. `EmptyInput` for an empty slice.
+pub fn mean(values: &[i64]) -> Result<Rational, MathError> {
+    if values.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    let sum: i128 = values.iter().map(|&v| v as i128).sum();
+    Rational::from_i128(sum, values.len() as i128)
+}
+
+/// Middle value, or the exact midpoint of the two middle values for an even count:
+/// - `median(&[7, 1, 3]) = 3`
+/// - `median(&[4, 1, 2, 8]) = 3`
+/// - `median(&[1, 2]) = 3/2`
+///
+/// Sorts a copy, O(n log n). `EmptyInput` for an empty slice.
+pub fn median(values: &[i64]) -> Result<Rational, MathError> {
+    if values.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let upper = sorted.len() / 2;
+    if sorted.len() % 2 == 1 {
+        return Ok(Rational::from_integer(sorted[upper]));
+    }
+    Rational::from_i128(sorted[upper - 1] as i128 + sorted[upper] as i128, 2)
+}
+
+/// Every most frequent value with its count:
+/// - `mode(&[3, 1, 3, 2])`: `[3]`, twice
+/// - `mode(&[5, 1, 5, 1, 2])`: `[1, 5]`, a tie, twice each
+///
+/// Sorts a copy and counts runs, O(n log n). `EmptyInput` for an empty slice.
+pub fn mode(values: &[i64]) -> Result<Modes, MathError> {
+    if values.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let mut modes = Modes {
+        values: Vec::new(),
+        count: 0,
+    };
+    for run in sorted.chunk_by(|a, b| a == b) {
+        if run.len() > modes.count {
+            modes.values.clear();
+            modes.count = run.len();
+        }
+        if run.len() == modes.count {
+            modes.values.push(run[0]);
+        }
+    }
+    Ok(modes)
+}
+
+/// Population variance `Σ(xᵢ - x̄)² / n` as a reduced fraction:
+/// - `variance_exact(&[1, 2, 3, 4]) = 5/4`
+///
+/// Same accumulation and errors as `covariance_exact(values, values)`.
+pub fn variance_exact(values: &[i64]) -> Result<Rational, MathError> {
+    covariance_exact(values, values)
+}
+
+/// Sample variance `Σ(xᵢ - x̄)² / (n - 1)`:
+/// - `sample_variance_exact(&[1, 2, 3, 4]) = 5/3`
+///
+/// `EmptyInput` for fewer than two values, otherwise as `variance_exact`.
+pub fn sample_variance_exact(values: &[i64]) -> Result<Rational, MathError> {
+    sample_covariance_exact(values, values)
+}
+
+/// Population covariance `Σ(xᵢ - x̄)(yᵢ - ȳ) / n` as a reduced fraction:
+/// - `covariance_exact(&[1, 2, 3], &[6, 4, 5]) = -1/3`
+///
+/// Computed as `(n·Σxy - Σx·Σy) / n²` in checked `i128`, after shifting each sample by
+/// its midrange, which leaves the result unchanged but keeps every deviation below
+/// `2^63` in magnitude. Intermediate sums then only overflow for results far outside
+/// `i64` or for billions of values, and either way the error is `Overflow`, never a
+/// rounded value. `EmptyInput` for empty samples, `DimensionMismatch` if the lengths
+/// differ.
+pub fn covariance_exact(x: &[i64], y: &[i64]) -> Result<Rational, MathError> {
+    let n = x.len() as i128;
+    Rational::from_i128(co_moment(x, y)?, n * n)
+}
+
+/// Sample covariance `Σ(xᵢ - x̄)(yᵢ - ȳ) / (n - 1)`:
+/// - `sample_covariance_exact(&[1, 2, 3], &[6, 4, 5]) = -1/2`
+///
+/// `EmptyInput` for fewer than two pairs, otherwise as `covariance_exact`.
+pub fn sample_covariance_exact(x: &[i64], y: &[i64]) -> Result<Rational, MathError> {
+    if x.len() < 2 {
+        return Err(if x.len() == y.len() {
+            MathError::EmptyInput
+        } else {
+            MathError::DimensionMismatch
+        });
+    }
+    let n = x.len() as i128;
+    Rational::from_i128(co_moment(x, y)?, n * (n - 1))
+}
+
+/// Upper tail `P(X >= k)` of a binomial variable with `n` trials of success probability
+/// `p`, as an exact fraction:
+/// - `binomial_tail_exact(10, 8, 1/2) = 7/128`
+/// - `binomial_tail_exact(3, 2, 1/3) = 7/27`
+///
+/// With `p = a/b` and `c = b - a`, the tail is `Σ_{i>=k} C(n, i)·aⁱ·c^(n-i) / bⁿ`. The
+/// numerator is summed in big integers by Horner's rule in `a` from `i = n` down,
+/// updating the binomial and the power of `c` one step at a time, and reduced once at
+/// the end: O(n) big multiplications of numbers up to `n·log₂ b` bits. The lower tail
+/// `P(X < k)` is `1 -` this. `0` when `k > n` and `1` when `k == 0`.
+/// `MathError::OutOfRange` unless `0 <= p <= 1`.
+#[cfg(feature = "internal-bigint")]
+pub fn binomial_tail_exact(n: u64, k: u64, p: Rational) -> Result<BigRational, MathError> {
+    if p.numer() < 0 || p.numer() > p.denom() {
+        return Err(MathError::OutOfRange);
+    }
+    if k > n {
+        return Ok(BigRational::from(Rational::ZERO));
+    }
+    let a = BigU::from_u64(p.numer() as u64);
+    let c = BigU::from_u64((p.denom() - p.numer()) as u64);
+    let b = BigU::from_u64(p.denom() as u64);
+
+    let mut binomial = BigU::one();
+    let mut c_power = BigU::one();
+    let mut sum = BigU::zero();
+    for i in (k..=n).rev() {
+        sum = &(&sum * &a) + &(&binomial * &c_power);
+        if i > k {
+            // C(n, i - 1) = C(n, i)·i / (n - i + 1), exactly
+            binomial = (&binomial * &BigU::from_u64(i))
+                .div_rem(&BigU::from_u64(n - i + 1))?
+                .0;
+            c_power = &c_power * &c;
+        }
+    }
+    let mut denom = BigU::one();
+    for _ in 0..n {
+        denom = &denom * &b;
+    }
+    for _ in 0..k {
+        sum = &sum * &a;
+    }
+    BigRational::new(false, sum, denom)
+}
+
+/// `n·Σxy - Σx·Σy`, which is `n²` times the population covariance.
+fn co_moment(x: &[i64], y: &[i64]) -> Result<i128, MathError> {
+    if x.len() != y.len() {
+        return Err(MathError::DimensionMismatch);
+    }
+    if x.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    let (dx, dy) = (deviations(x), deviations(y));
+    let (mut sum_x, mut sum_y, mut sum_xy) = (0i128, 0i128, 0i128);
+    for (a, b) in dx.zip(dy) {
+        sum_x = sum_x.checked_add(a).ok_or(MathError::Overflow)?;
+        sum_y = sum_y.checked_add(b).ok_or(MathError::Overflow)?;
+        sum_xy = sum_xy.checked_add(a * b).ok_or(MathError::Overflow)?;
+    }
+    (x.len() as i128)
+        .checked_mul(sum_xy)
+        .zip(sum_x.checked_mul(sum_y))
+        .and_then(|(nxy, xy)| nxy.checked_sub(xy))
+        .ok_or(MathError::Overflow)
+}
+
+/// `xᵢ - c` for the midrange `c = ⌊(min + max) / 2⌋`, each at most `2^63` in magnitude.
+fn deviations(values: &[i64]) -> impl Iterator<Item = i128> + '_ {
+    let (min, max) = values
+        .iter()
+        .fold((i64::MAX, i64::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+    let center = (min as i128 + max as i128).div_euclid(2);
+    values.iter().map(move |&v| v as i128 - center)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn r(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    #[test]
+    fn test_mean() {
+        assert_eq!(mean(&[1, 2, 3, 5]), Ok(r(11, 4)));
+        assert_eq!(mean(&[-4, 4, 6]), Ok(r(2, 1)));
+        assert_eq!(mean(&[-1, -2]), Ok(r(-3, 2)));
+        // the sum overflows i64, the mean does not
+        assert_eq!(mean(&[i64::MAX, i64::MAX, i64::MAX]), Ok(r(i64::MAX, 1)));
+        assert_eq!(mean(&[i64::MIN, i64::MAX]), Ok(r(-1, 2)));
+        assert_eq!(mean(&[]), Err(MathError::EmptyInput));
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&[7, 1, 3]), Ok(r(3, 1)));
+        assert_eq!(median(&[4, 1, 2, 8]), Ok(r(3, 1)));
+        assert_eq!(median(&[1, 2]), Ok(r(3, 2)));
+        assert_eq!(median(&[-3, -2]), Ok(r(-5, 2)));
+        assert_eq!(median(&[i64::MAX, i64::MAX]), Ok(r(i64::MAX, 1)));
+        assert_eq!(median(&[i64::MIN, i64::MAX]), Ok(r(-1, 2)));
+        assert_eq!(median(&[42]), Ok(r(42, 1)));
+        assert_eq!(median(&[]), Err(MathError::EmptyInput));
+    }
+
+    #[test]
+    fn test_mode() {
+        let m = mode(&[3, 1, 3, 2]).unwrap();
+        assert_eq!((m.values.clone(), m.count), (vec![3], 2));
+        assert!(m.is_unique());
+        let m = mode(&[5, 1, 5, 1, 2]).unwrap();
+        assert_eq!((m.values.clone(), m.count), (vec![1, 5], 2));
+        assert!(!m.is_unique());
+        // every value once: all of them tie
+        let m = mode(&[4, -2, 9]).unwrap();
+        assert_eq!((m.values, m.count), (vec![-2, 4, 9], 1));
+        assert_eq!(mode(&[]), Err(MathError::EmptyInput));
+    }
+
+    #[test]
+    fn test_variance_exact() {
+        assert_eq!(variance_exact(&[1, 2, 3, 4]), Ok(r(5, 4)));
+        assert_eq!(sample_variance_exact(&[1, 2, 3, 4]), Ok(r(5, 3)));
+        assert_eq!(variance_exact(&[7]), Ok(Rational::ZERO));
+        assert_eq!(variance_exact(&[-5, -5, -5]), Ok(Rational::ZERO));
+        // far from zero, where Σx² alone would not fit in i128
+        let big = [i64::MAX, i64::MAX - 1, i64::MAX - 2];
+        assert_eq!(variance_exact(&big), Ok(r(2, 3)));
+        assert_eq!(sample_variance_exact(&big), Ok(r(1, 1)));
+        assert_eq!(variance_exact(&[i64::MIN, i64::MIN + 2]), Ok(r(1, 1)));
+        // (2^64 - 1)² / 4 does not fit in i64
+        assert_eq!(
+            variance_exact(&[i64::MIN, i64::MAX]),
+            Err(MathError::Overflow)
+        );
+        assert_eq!(variance_exact(&[]), Err(MathError::EmptyInput));
+        assert_eq!(sample_variance_exact(&[3]), Err(MathError::EmptyInput));
+    }
+
+    #[test]
+    fn test_covariance_exact() {
+        assert_eq!(covariance_exact(&[1, 2, 3], &[6, 4, 5]), Ok(r(-1, 3)));
+        assert_eq!(
+            sample_covariance_exact(&[1, 2, 3], &[6, 4, 5]),
+            Ok(r(-1, 2))
+        );
+        assert_eq!(covariance_exact(&[1, 2, 3], &[2, 4, 6]), Ok(r(4, 3)));
+        assert_eq!(
+            covariance_exact(&[1, 2], &[1]),
+            Err(MathError::DimensionMismatch)
+        );
+        assert_eq!(
+            sample_covariance_exact(&[1], &[1, 2]),
+            Err(MathError::DimensionMismatch)
+        );
+        assert_eq!(covariance_exact(&[], &[]), Err(MathError::EmptyInput));
+        // against the two-pass definition with rational arithmetic
+        let x = [3, -7, 12, 0, 5, 5, -1];
+        let y = [10, 2, -4, 8, 8, 1, 0];
+        let (mx, my) = (mean(&x).unwrap(), mean(&y).unwrap());
+        let mut sum = Rational::ZERO;
+        for (&a, &b) in x.iter().zip(&y) {
+            let term =
+                ((Rational::from(a) - mx).unwrap() * (Rational::from(b) - my).unwrap()).unwrap();
+            sum = (sum + term).unwrap();
+        }
+        let n = Rational::from(x.len() as i64);
+        assert_eq!(covariance_exact(&x, &y), sum / n);
+        assert_eq!(
+            sample_covariance_exact(&x, &y),
+            sum / (n - Rational::ONE).unwrap()
+        );
+    }
+
+    #[cfg(feature = "internal-bigint")]
+    #[test]
+    fn test_binomial_tail_exact() {
+        use crate::algebra::polynomial::Ring;
+
+        let tail = |n, k, p| binomial_tail_exact(n, k, p).unwrap().to_rational().unwrap();
+        assert_eq!(tail(10, 8, r(1, 2)), r(7, 128));
+        assert_eq!(tail(3, 2, r(1, 3)), r(7, 27));
+        assert_eq!(tail(5, 0, r(2, 7)), Rational::ONE);
+        assert_eq!(tail(5, 6, r(2, 7)), Rational::ZERO);
+        assert_eq!(tail(5, 1, r(0, 1)), Rational::ZERO);
+        assert_eq!(tail(5, 5, r(1, 1)), Rational::ONE);
+        assert_eq!(tail(0, 0, r(1, 2)), Rational::ONE);
+        for p in [r(-1, 2), r(3, 2)] {
+            assert_eq!(binomial_tail_exact(4, 1, p), Err(MathError::OutOfRange));
+        }
+
+        // consecutive tails differ by the probability mass C(n, k)·pᵏ·q^(n-k)
+        let (n, p) = (120, r(3, 11));
+        let q = BigRational::from((Rational::ONE - p).unwrap());
+        let p = BigRational::from(p);
+        let power = |x: &BigRational, e: u64| {
+            (0..e).fold(BigRational::one(), |acc, _| acc.checked_mul(x).unwrap())
+        };
+        let mut binomial = BigRational::one();
+        for k in 0..=n {
+            let mass = binomial
+                .checked_mul(&power(&p, k))
+                .and_then(|m| m.checked_mul(&power(&q, n - k)))
+                .unwrap();
+            let difference = binomial_tail_exact(n, k, r(3, 11))
+                .unwrap()
+                .checked_sub(&binomial_tail_exact(n, k + 1, r(3, 11)).unwrap())
+                .unwrap();
+            assert_eq!(difference, mass, "k = {k}");
+            binomial = binomial
+                .checked_mul(&BigRational::from(r((n - k) as i64, k as i64 + 1)))
+                .unwrap();
+        }
+        // far beyond i64: the denominator is 11^120
+        let far = binomial_tail_exact(n, 100, r(3, 11)).unwrap();
+        assert!(far.to_rational().is_none());
+        assert_eq!(far.denom(), &BigU::from_u64(11).pow(120));
+    }
+}
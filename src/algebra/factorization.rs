@@ -0,0 +1,676 @@
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::divisors::{trial_factors, trial_factors_up_to};
+use crate::algebra::gcd::gcd_u128;
+use crate::algebra::prime_nbr::{PrimalityConfig, Verdict, miller_rabin_u128};
+use crate::errors::MathError;
+use crate::outcome::{Outcome, measure};
+
+/// Prime-power decomposition `n = Π pᵢ^eᵢ` of a positive integer, primes ascending.
+///
+/// Every multiplicative function below reads the stored exponents, so one factorization
+/// answers all of them; the free functions of this module accept either a number or a
+/// `Factorization` through `Factorize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Factorization {
+    n: u128,
+    factors: Vec<(u128, u32)>,
+}
+
+/// Factors of `n` found within a trial-division budget, the rest kept as an opaque
+/// cofactor.
+///
+/// `n = Π pᵢ^eᵢ · cofactor`, where the listed primes are all below the budget and the
+/// cofactor has no prime factor up to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialFactorization {
+    n: u128,
+    factors: Vec<(u128, u32)>,
+    cofactor: u128,
+    cofactor_verdict: Option<Verdict>,
+}
+
+impl PartialFactorization {
+    /// Trial division by the candidates up to `trial_bound`:
+    /// - `new(2^3 · 3 · 1000003 · 1000033, 1000)`: `[(2, 3), (3, 1)]`, cofactor
+    ///   `1000003 · 1000033`, `Composite`
+    ///
+    /// Stops early, and the factorization is complete, once the candidates pass the
+    /// square root of what is left. Otherwise the cofactor is classified by
+    /// `PrimalityConfig::default()`: `Prime` or `Composite` up to `u64::MAX`,
+    /// `ProbablePrime` or `Composite` beyond. `MathError::PositifIntegerRequired` if
+    /// `n == 0`.
+    pub fn new(n: u128, trial_bound: u128) -> Result<Self, MathError> {
+        if n == 0 {
+            return Err(MathError::PositifIntegerRequired);
+        }
+        let (factors, cofactor, _) = trial_factors_up_to(n, trial_bound);
+        let cofactor_verdict = if cofactor == 1 {
+            None
+        } else {
+            Some(PrimalityConfig::default().check(cofactor)?.verdict)
+        };
+        Ok(PartialFactorization {
+            n,
+            factors,
+            cofactor,
+            cofactor_verdict,
+        })
+    }
+
+    /// The number being factored.
+    pub fn value(&self) -> u128 {
+        self.n
+    }
+
+    /// The `(p, e)` pairs found, primes ascending.
+    pub fn prime_powers(&self) -> &[(u128, u32)] {
+        &self.factors
+    }
+
+    /// What is left of `n` once the found prime powers are divided out, 1 when complete.
+    pub fn cofactor(&self) -> u128 {
+        self.cofactor
+    }
+
+    /// Primality of the cofactor, `None` when it is 1.
+    pub fn cofactor_verdict(&self) -> Option<Verdict> {
+        self.cofactor_verdict
+    }
+
+    /// `true` when no cofactor is left.
+    pub fn is_complete(&self) -> bool {
+        self.cofactor == 1
+    }
+
+    /// The full `Factorization`, with a (probable) prime cofactor counted as a prime;
+    /// `None` while a composite cofactor is left.
+    pub fn to_factorization(&self) -> Option<Factorization> {
+        let mut factors = self.factors.clone();
+        match self.cofactor_verdict {
+            None => {}
+            Some(Verdict::Composite) => return None,
+            Some(_) => factors.push((self.cofactor, 1)),
+        }
+        Some(Factorization { n: self.n, factors })
+    }
+}
+
+/// Anything that can produce the factorization of a positive integer.
+///
+/// Integers are factored by trial division on each call; a `Factorization` is borrowed
+/// as is, so reusing one skips that cost.
+pub trait Factorize {
+    fn factorize(&self) -> Result<Cow<'_, Factorization>, MathError>;
+}
+
+macro_rules! impl_factorize {
+    ($($t:ty),*) => {
+        $(
+            impl Factorize for $t {
+                fn factorize(&self) -> Result<Cow<'_, Factorization>, MathError> {
+                    Factorization::new(*self as u128).map(Cow::Owned)
+                }
+            }
+        )*
+    };
+}
+
+impl_factorize!(u32, u64, u128);
+
+impl Factorize for Factorization {
+    fn factorize(&self) -> Result<Cow<'_, Factorization>, MathError> {
+        Ok(Cow::Borrowed(self))
+    }
+}
+
+impl<T: Factorize + ?Sized> Factorize for &T {
+    fn factorize(&self) -> Result<Cow<'_, Factorization>, MathError> {
+        (**self).factorize()
+    }
+}
+
+impl Factorization {
+    /// Factors `n` by trial division, so the cost grows with the square root of its
+    /// second largest prime factor.
+    ///
+    /// `MathError::PositifIntegerRequired` if `n == 0`.
+    pub fn new(n: u128) -> Result<Self, MathError> {
+        if n == 0 {
+            return Err(MathError::PositifIntegerRequired);
+        }
+        Ok(Factorization {
+            n,
+            factors: trial_factors(n),
+        })
+    }
+
+    /// Builds the factorization from known `(p, e)` pairs in any order, without
+    /// dividing anything:
+    /// - `from_prime_powers(&[(3, 1), (2, 2)])` is `12`
+    ///
+    /// Each `p` is checked by Miller-Rabin with 32 rounds. `MathError::OutOfRange` if a
+    /// base is not prime or an exponent is zero, `DuplicateInput` if a prime repeats,
+    /// `Overflow` if the product exceeds `u128`.
+    pub fn from_prime_powers(factors: &[(u128, u32)]) -> Result<Self, MathError> {
+        let mut factors = factors.to_vec();
+        factors.sort_unstable();
+        if factors.windows(2).any(|w| w[0].0 == w[1].0) {
+            return Err(MathError::DuplicateInput);
+        }
+        let mut n: u128 = 1;
+        for &(p, e) in &factors {
+            if e == 0 || p < 2 || !miller_rabin_u128(p, 32)?.is_prime() {
+                return Err(MathError::OutOfRange);
+            }
+            let power = p.checked_pow(e).ok_or(MathError::Overflow)?;
+            n = n.checked_mul(power).ok_or(MathError::Overflow)?;
+        }
+        Ok(Factorization { n, factors })
+    }
+
+    /// The factored number.
+    pub fn value(&self) -> u128 {
+        self.n
+    }
+
+    /// The `(p, e)` pairs, primes ascending; empty for `1`.
+    pub fn prime_powers(&self) -> &[(u128, u32)] {
+        &self.factors
+    }
+
+    /// Euler's totient `φ(n) = Π p^(e-1)·(p - 1)`, the number of units mod `n`:
+    /// - `φ(12) = 4`
+    pub fn totient(&self) -> u128 {
+        self.factors
+            .iter()
+            .map(|&(p, e)| p.pow(e - 1) * (p - 1))
+            .product()
+    }
+
+    /// Carmichael's function `λ(n)`, the exponent of `(Z/nZ)*`: the lcm of
+    /// `λ(p^e) = p^(e-1)·(p - 1)`, except `λ(2^e) = 2^(e-2)` for `e >= 3`:
+    /// - `λ(8) = 2`, while `φ(8) = 4`
+    /// - `λ(561) = 80`
+    ///
+    /// `λ(n)` divides `φ(n)`, so nothing overflows.
+    pub fn carmichael(&self) -> u128 {
+        self.cyclic_orders()
+            .into_iter()
+            .fold(1, |l, x| l / gcd_u128(l, x) * x)
+    }
+
+    /// Invariant factors `d₁ | d₂ | ... | d_k` of `(Z/nZ)* ≅ C_d₁ × ... × C_d_k`,
+    /// ascending, with `Π dᵢ = φ(n)` and `d_k = λ(n)`:
+    /// - `(Z/15Z)* = [2, 4]`
+    /// - `(Z/24Z)* = [2, 2, 2]`
+    /// - `(Z/9Z)* = [6]`, and empty for the trivial groups mod 1 and 2
+    ///
+    /// The group is cyclic exactly when at most one factor remains, that is for `1, 2, 4,
+    /// p^e, 2p^e`; then any element of order `λ(n)` generates it (`DirichletGroup` lists
+    /// explicit generators). Each `p^e` contributes a cyclic group of order
+    /// `p^(e-1)·(p - 1)`, or `C₂ × C_2^(e-2)` for `2^e` with `e >= 3`; those orders are
+    /// split into prime powers by trial division and the largest powers of every prime
+    /// are multiplied together, then the next largest, and so on.
+    pub fn unit_group_structure(&self) -> Vec<u128> {
+        // exponents of each prime across the cyclic components, largest first
+        let mut primes: Vec<(u128, Vec<u32>)> = Vec::new();
+        for order in self.cyclic_orders() {
+            for (q, a) in trial_factors(order) {
+                match primes.iter_mut().find(|(p, _)| *p == q) {
+                    Some((_, exps)) => exps.push(a),
+                    None => primes.push((q, vec![a])),
+                }
+            }
+        }
+        for (_, exps) in &mut primes {
+            exps.sort_unstable_by(|a, b| b.cmp(a));
+        }
+        let k = primes.iter().map(|(_, exps)| exps.len()).max().unwrap_or(0);
+        let mut factors: Vec<u128> = (0..k)
+            .map(|i| {
+                primes
+                    .iter()
+                    .filter_map(|(q, exps)| exps.get(i).map(|&a| q.pow(a)))
+                    .product()
+            })
+            .collect();
+        factors.reverse();
+        factors
+    }
+
+    /// Orders of the cyclic groups `(Z/p^eZ)*` splits into, `1` for the trivial ones.
+    fn cyclic_orders(&self) -> Vec<u128> {
+        let mut orders = Vec::with_capacity(self.factors.len() + 1);
+        for &(p, e) in &self.factors {
+            if p == 2 && e >= 3 {
+                orders.extend([2, 1 << (e - 2)]);
+            } else {
+                orders.push(p.pow(e - 1) * (p - 1));
+            }
+        }
+        orders
+    }
+
+    /// Möbius function: `0` if a square divides `n`, else `(-1)^k` for `k` primes:
+    /// - `μ(30) = -1`, `μ(12) = 0`, `μ(1) = 1`
+    pub fn mobius(&self) -> i8 {
+        if self.factors.iter().any(|&(_, e)| e > 1) {
+            0
+        } else if self.factors.len().is_multiple_of(2) {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Divisor function `σ_k(n) = Σ d^k` over the divisors `d` of `n`:
+    /// - `σ_1(12) = 28`, `σ_2(6) = 50`, `σ_0(12) = 6`
+    ///
+    /// `MathError::Overflow` if the sum exceeds `u128`.
+    pub fn sigma(&self, k: u32) -> Result<u128, MathError> {
+        let mut result: u128 = 1;
+        for &(p, e) in &self.factors {
+            // 1 + p^k + p^2k + ... + p^ek
+            let step = p.checked_pow(k).ok_or(MathError::Overflow)?;
+            let (mut term, mut power): (u128, u128) = (1, 1);
+            for _ in 0..e {
+                power = power.checked_mul(step).ok_or(MathError::Overflow)?;
+                term = term.checked_add(power).ok_or(MathError::Overflow)?;
+            }
+            result = result.checked_mul(term).ok_or(MathError::Overflow)?;
+        }
+        Ok(result)
+    }
+
+    /// Number of divisors `τ(n) = Π (e + 1)`:
+    /// - `τ(12) = 6`
+    pub fn divisor_count(&self) -> u128 {
+        self.factors.iter().map(|&(_, e)| e as u128 + 1).product()
+    }
+
+    /// Radical `rad(n) = Π p`, the largest squarefree divisor:
+    /// - `rad(72) = 6`
+    pub fn radical(&self) -> u128 {
+        self.factors.iter().map(|&(p, _)| p).product()
+    }
+
+    /// Whether `n = p^e` for a prime `p` and `e >= 1`; `1` is not a prime power.
+    pub fn is_prime_power(&self) -> bool {
+        self.factors.len() == 1
+    }
+
+    /// Smallest prime factor, `None` for `1`.
+    pub fn smallest_prime_factor(&self) -> Option<u128> {
+        self.factors.first().map(|&(p, _)| p)
+    }
+
+    /// Largest prime factor, `None` for `1`.
+    pub fn largest_prime_factor(&self) -> Option<u128> {
+        self.factors.last().map(|&(p, _)| p)
+    }
+}
+
+/// Prime-power decomposition of `n`, primes ascending:
+/// - `factorize(360) = [(2, 3), (3, 2), (5, 1)]`
+/// - `factorize(1) = []`
+///
+/// `factorize_u64` after checking the sign. `MathError::PositifIntegerRequired` if
+/// `n < 1`.
+pub fn factorize(n: i64) -> Result<Vec<(i64, u32)>, MathError> {
+    let n = u64::try_from(n).map_err(|_| MathError::PositifIntegerRequired)?;
+    // every factor of a positive i64 fits back in i64
+    Ok(factorize_u64(n)?
+        .into_iter()
+        .map(|(p, e)| (p as i64, e))
+        .collect())
+}
+
+/// Prime-power decomposition of a `u64`, primes ascending, the core that `factorize`
+/// wraps:
+/// - `factorize_u64(2^64 - 1) = [(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), ...]`
+///
+/// Trial division, as in `Factorization::new`. `MathError::PositifIntegerRequired` if
+/// `n == 0`.
+pub fn factorize_u64(n: u64) -> Result<Vec<(u64, u32)>, MathError> {
+    if n == 0 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    Ok(trial_factors(n as u128)
+        .into_iter()
+        .map(|(p, e)| (p as u64, e))
+        .collect())
+}
+
+/// `factorize_u64` with its cost: method `"trial division"`, one iteration per candidate
+/// divisor tried. Errors as `factorize_u64`.
+pub fn factorize_u64_outcome(n: u64) -> Result<Outcome<Vec<(u64, u32)>>, MathError> {
+    if n == 0 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    measure(|| {
+        let (factors, _, tried) = trial_factors_up_to(n as u128, u128::MAX);
+        let factors = factors.into_iter().map(|(p, e)| (p as u64, e)).collect();
+        Ok(Outcome::new(factors, "trial division", tried))
+    })
+}
+
+/// `factorize` limited to the trial divisors up to `trial_bound`, see
+/// `PartialFactorization::new`:
+/// - `factorize_bounded(360 · 1000003, 100)` finds `[(2, 3), (3, 2), (5, 1)]` and leaves
+///   the prime `1000003`
+///
+/// `MathError::PositifIntegerRequired` if `n < 1`.
+pub fn factorize_bounded(n: i64, trial_bound: u64) -> Result<PartialFactorization, MathError> {
+    if n < 1 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    PartialFactorization::new(n as u128, trial_bound as u128)
+}
+
+/// `φ(n)`, see `Factorization::totient`. Errors as `Factorize::factorize`.
+pub fn totient(n: impl Factorize) -> Result<u128, MathError> {
+    Ok(n.factorize()?.totient())
+}
+
+/// `λ(n)`, see `Factorization::carmichael`.
+pub fn carmichael(n: impl Factorize) -> Result<u128, MathError> {
+    Ok(n.factorize()?.carmichael())
+}
+
+/// Invariant factors of `(Z/nZ)*`, see `Factorization::unit_group_structure`.
+pub fn unit_group_structure(n: impl Factorize) -> Result<Vec<u128>, MathError> {
+    Ok(n.factorize()?.unit_group_structure())
+}
+
+/// `μ(n)`, see `Factorization::mobius`.
+pub fn mobius(n: impl Factorize) -> Result<i8, MathError> {
+    Ok(n.factorize()?.mobius())
+}
+
+/// `σ_k(n)`, see `Factorization::sigma`.
+pub fn sigma_k(n: impl Factorize, k: u32) -> Result<u128, MathError> {
+    n.factorize()?.sigma(k)
+}
+
+/// `τ(n)`, see `Factorization::divisor_count`.
+pub fn divisor_count(n: impl Factorize) -> Result<u128, MathError> {
+    Ok(n.factorize()?.divisor_count())
+}
+
+/// `rad(n)`, see `Factorization::radical`.
+pub fn radical(n: impl Factorize) -> Result<u128, MathError> {
+    Ok(n.factorize()?.radical())
+}
+
+/// See `Factorization::is_prime_power`.
+pub fn is_prime_power(n: impl Factorize) -> Result<bool, MathError> {
+    Ok(n.factorize()?.is_prime_power())
+}
+
+/// See `Factorization::smallest_prime_factor`.
+pub fn smallest_prime_factor(n: impl Factorize) -> Result<Option<u128>, MathError> {
+    Ok(n.factorize()?.smallest_prime_factor())
+}
+
+/// See `Factorization::largest_prime_factor`.
+pub fn largest_prime_factor(n: impl Factorize) -> Result<Option<u128>, MathError> {
+    Ok(n.factorize()?.largest_prime_factor())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::gcd::gcd_u64;
+
+    #[test]
+    fn test_against_brute_force() {
+        for n in 1..=400u64 {
+            let f = Factorization::new(n as u128).unwrap();
+            let divisors: Vec<u64> = (1..=n).filter(|&d| n.is_multiple_of(d)).collect();
+            let primes: Vec<u64> = divisors
+                .iter()
+                .copied()
+                .filter(|&d| d > 1 && (2..d).all(|q| !d.is_multiple_of(q)))
+                .collect();
+            let units: Vec<u64> = (1..=n).filter(|&a| gcd_u64(a, n) == 1).collect();
+            assert_eq!(f.totient(), units.len() as u128, "φ({n})");
+            // the exponent of the unit group is the lcm of the element orders
+            let exponent = units.iter().fold(1, |l, &a| {
+                let mut order = 1;
+                let mut x = a % n;
+                while x != 1 % n {
+                    x = x * a % n;
+                    order += 1;
+                }
+                l / gcd_u64(l, order) * order
+            });
+            assert_eq!(f.carmichael(), exponent as u128, "λ({n})");
+            assert_eq!(f.divisor_count(), divisors.len() as u128);
+            for k in 0..4 {
+                let sum: u128 = divisors.iter().map(|&d| (d as u128).pow(k)).sum();
+                assert_eq!(f.sigma(k), Ok(sum), "σ_{k}({n})");
+            }
+            assert_eq!(f.radical(), primes.iter().product::<u64>() as u128);
+            let squarefree = (2..=n).all(|d| !n.is_multiple_of(d * d));
+            let mobius = match (squarefree, primes.len() % 2) {
+                (false, _) => 0,
+                (true, 0) => 1,
+                (true, _) => -1,
+            };
+            assert_eq!(f.mobius(), mobius, "μ({n})");
+            assert_eq!(
+                f.smallest_prime_factor(),
+                primes.first().map(|&p| p as u128)
+            );
+            assert_eq!(f.largest_prime_factor(), primes.last().map(|&p| p as u128));
+            let prime_power = primes.len() == 1;
+            assert_eq!(f.is_prime_power(), prime_power, "{n}");
+        }
+    }
+
+    #[test]
+    fn test_unit_group_structure() {
+        assert_eq!(unit_group_structure(15u64), Ok(vec![2, 4]));
+        assert_eq!(unit_group_structure(24u64), Ok(vec![2, 2, 2]));
+        assert_eq!(unit_group_structure(9u64), Ok(vec![6]));
+        assert_eq!(unit_group_structure(1u64), Ok(vec![]));
+        assert_eq!(unit_group_structure(2u64), Ok(vec![]));
+        // C₂ × C₁₆ from 2^6 and C₆ × C₁₀ ≅ C₂ × C₃₀ from 7 · 11
+        assert_eq!(unit_group_structure(64u64), Ok(vec![2, 16]));
+        assert_eq!(unit_group_structure(77u64), Ok(vec![2, 30]));
+        assert_eq!(
+            unit_group_structure(0u64),
+            Err(MathError::PositifIntegerRequired)
+        );
+    }
+
+    #[test]
+    fn test_unit_group_structure_invariants() {
+        for n in 1..=2000u64 {
+            let f = Factorization::new(n as u128).unwrap();
+            let factors = f.unit_group_structure();
+            assert!(factors.iter().all(|&d| d > 1));
+            assert!(factors.windows(2).all(|w| w[1].is_multiple_of(w[0])));
+            assert_eq!(factors.iter().product::<u128>(), f.totient());
+            assert_eq!(factors.last().copied().unwrap_or(1), f.carmichael());
+            // cyclic exactly for 1, 2, 4, p^e and 2p^e
+            let odd = n >> n.trailing_zeros();
+            let cyclic = n <= 4 || (n.trailing_zeros() <= 1 && is_prime_power(odd).unwrap());
+            assert_eq!(factors.len() <= 1, cyclic, "n = {n}");
+            // solutions of a² ≡ 1 number 2^(count of even invariant factors)
+            let involutions = (1..=n)
+                .filter(|&a| gcd_u64(a, n) == 1 && a * a % n == 1 % n)
+                .count();
+            let even = factors.iter().filter(|&&d| d.is_multiple_of(2)).count();
+            assert_eq!(involutions, 1 << even, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert_eq!(factorize(360), Ok(vec![(2, 3), (3, 2), (5, 1)]));
+        assert_eq!(factorize(1), Ok(vec![]));
+        assert_eq!(factorize(97), Ok(vec![(97, 1)]));
+        // 2^63 - 1 = 7² · 73 · 127 · 337 · 92737 · 649657
+        assert_eq!(
+            factorize(i64::MAX),
+            Ok(vec![
+                (7, 2),
+                (73, 1),
+                (127, 1),
+                (337, 1),
+                (92737, 1),
+                (649657, 1)
+            ])
+        );
+        for n in 1..2000i64 {
+            let product: i64 = factorize(n)
+                .unwrap()
+                .iter()
+                .map(|&(p, e)| p.pow(e))
+                .product();
+            assert_eq!(product, n);
+        }
+        assert_eq!(factorize(0), Err(MathError::PositifIntegerRequired));
+        assert_eq!(factorize_u64(0), Err(MathError::PositifIntegerRequired));
+        assert_eq!(
+            factorize_u64(u64::MAX),
+            Ok(vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6700417, 1)
+            ])
+        );
+        assert_eq!(factorize(-12), Err(MathError::PositifIntegerRequired));
+        // 2 and 3 leave 13, and 5² > 13 ends the search after two candidates
+        let outcome = factorize_u64_outcome(2 * 3 * 3 * 13).unwrap();
+        assert_eq!(outcome.value, vec![(2, 1), (3, 2), (13, 1)]);
+        assert_eq!((outcome.method, outcome.iterations), ("trial division", 2));
+        assert_eq!(
+            *factorize_u64_outcome(u64::MAX).unwrap(),
+            factorize_u64(u64::MAX).unwrap()
+        );
+        assert_eq!(
+            factorize_u64_outcome(0),
+            Err(MathError::PositifIntegerRequired)
+        );
+    }
+
+    #[test]
+    fn test_partial_factorization() {
+        let n = 8 * 3 * 1_000_003 * 1_000_033;
+        let partial = PartialFactorization::new(n, 1000).unwrap();
+        assert_eq!(partial.value(), n);
+        assert_eq!(partial.prime_powers(), &[(2, 3), (3, 1)]);
+        assert_eq!(partial.cofactor(), 1_000_003 * 1_000_033);
+        assert_eq!(partial.cofactor_verdict(), Some(Verdict::Composite));
+        assert!(!partial.is_complete());
+        assert_eq!(partial.to_factorization(), None);
+
+        // a larger budget finishes the job
+        let full = PartialFactorization::new(n, 1_000_003).unwrap();
+        assert!(full.is_complete());
+        assert_eq!(full.cofactor_verdict(), None);
+        assert_eq!(full.to_factorization(), Factorization::new(n).ok());
+
+        let partial = factorize_bounded(360 * 1_000_003, 100).unwrap();
+        assert_eq!(partial.prime_powers(), &[(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(partial.cofactor(), 1_000_003);
+        assert_eq!(partial.cofactor_verdict(), Some(Verdict::Prime));
+        assert_eq!(
+            partial.to_factorization(),
+            Factorization::from_prime_powers(&[(2, 3), (3, 2), (5, 1), (1_000_003, 1)]).ok()
+        );
+        assert_eq!(
+            factorize_bounded(0, 10),
+            Err(MathError::PositifIntegerRequired)
+        );
+        assert_eq!(factorize_bounded(1, 0).map(|p| p.is_complete()), Ok(true));
+    }
+
+    #[test]
+    fn test_partial_factorization_past_u64() {
+        // 6 · (2^89 - 1): the Mersenne cofactor is only a probable prime
+        let m89 = (1u128 << 89) - 1;
+        let partial = PartialFactorization::new(6 * m89, 1 << 16).unwrap();
+        assert_eq!(partial.prime_powers(), &[(2, 1), (3, 1)]);
+        assert_eq!(partial.cofactor(), m89);
+        assert_eq!(partial.cofactor_verdict(), Some(Verdict::ProbablePrime));
+        assert_eq!(
+            partial
+                .to_factorization()
+                .map(|f| f.prime_powers().to_vec()),
+            Some(vec![(2, 1), (3, 1), (m89, 1)])
+        );
+        // a budget of 0 or 1 divides nothing
+        let untouched = PartialFactorization::new(1 << 100, 1).unwrap();
+        assert_eq!(untouched.prime_powers(), &[]);
+        assert_eq!(untouched.cofactor_verdict(), Some(Verdict::Composite));
+    }
+
+    #[test]
+    fn test_known_values() {
+        let f = Factorization::new(72).unwrap();
+        assert_eq!(f.prime_powers(), &[(2, 3), (3, 2)]);
+        assert_eq!(f.value(), 72);
+        assert_eq!(f.radical(), 6);
+        assert_eq!(f.smallest_prime_factor(), Some(2));
+        assert_eq!(f.largest_prime_factor(), Some(3));
+        assert!(!f.is_prime_power());
+
+        assert_eq!(carmichael(561u64), Ok(80));
+        assert_eq!(mobius(30u64), Ok(-1));
+        assert_eq!(mobius(1u64), Ok(1));
+        assert_eq!(sigma_k(6u64, 2), Ok(50));
+        assert_eq!(is_prime_power(1u64 << 40), Ok(true));
+        assert_eq!(is_prime_power(1u64), Ok(false));
+        assert_eq!(largest_prime_factor(1u64), Ok(None));
+        assert_eq!(totient(0u64), Err(MathError::PositifIntegerRequired));
+        assert_eq!(sigma_k(3u128 << 126, 1), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_reuse_factorization() {
+        // a prime too large for trial division, supplied directly
+        let p = (1u128 << 89) - 1;
+        let f = Factorization::from_prime_powers(&[(p, 1), (3, 2)]).unwrap();
+        assert_eq!(f.value(), 9 * p);
+        assert_eq!(f.prime_powers(), &[(3, 2), (p, 1)]);
+        assert_eq!(totient(&f), Ok(6 * (p - 1)));
+        // 6 divides p - 1 = 2(2^88 - 1)
+        assert_eq!(carmichael(&f), Ok(p - 1));
+        assert_eq!(divisor_count(&f), Ok(6));
+        assert_eq!(radical(&f), Ok(3 * p));
+        assert_eq!(mobius(&f), Ok(0));
+        assert_eq!(smallest_prime_factor(&f), Ok(Some(3)));
+        assert_eq!(largest_prime_factor(f), Ok(Some(p)));
+
+        assert_eq!(
+            Factorization::from_prime_powers(&[(4, 1)]),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            Factorization::from_prime_powers(&[(3, 0)]),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            Factorization::from_prime_powers(&[(3, 1), (3, 2)]),
+            Err(MathError::DuplicateInput)
+        );
+        assert_eq!(
+            Factorization::from_prime_powers(&[(2, 128)]),
+            Err(MathError::Overflow)
+        );
+        assert_eq!(
+            Factorization::from_prime_powers(&[]).map(|f| f.value()),
+            Ok(1)
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use alloc::vec::Vec;
+
+use crate::algebra::gcd::gcd_u128;
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Common step of measured values, as found by `gcd_of_floats`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatGcd {
+    /// The exact rational GCD of the lifted values.
+    pub step: Rational,
+    /// `kᵢ` with `valuesᵢ ≈ kᵢ·step`, signs included; 0 for values lifted to 0.
+    pub multiples: Vec<i64>,
+    /// Largest `|valuesᵢ - kᵢ·step|`, never above the tolerance.
+    pub max_error: f64,
+    /// `1 - 2·max_error / step`, clamped to `[0, 1]`: 1 for an exact fit, near 0 when
+    /// the residuals are as large as those of values placed at random on the grid.
+    pub confidence: f64,
+}
+
+/// The largest step `s` such that every value is within `tolerance` of a multiple of `s`,
+/// among steps read off the simplest fractions:
+/// - `gcd_of_floats(&[0.3, 0.5, 0.7], 1e-9)`: step `1/10`, multiples `[3, 5, 7]`
+/// - `gcd_of_floats(&[2.0004, 3.4996, 5.0003], 1e-3)`: step `1/2`, multiples
+///   `[4, 7, 10]`, confidence ≈ 0.998
+///
+/// Each value is lifted to the rational of smallest denominator within `tolerance`
+/// (the continued fraction of the interval around it), and the step is the exact GCD
+/// of those rationals, `gcd(numerators) / lcm(denominators)`. A tolerance near the
+/// noise level works best: too tight and noisy values lift to large denominators and
+/// a tiny step, which `confidence` flags. Errors with `EmptyInput` for no values,
+/// `OutOfRange` if the tolerance is not positive and finite or a value is not finite,
+/// `NoSolution` if every value lifts to 0, and `Overflow` if the step leaves `Rational`.
+pub fn gcd_of_floats(values: &[f64], tolerance: f64) -> Result<FloatGcd, MathError> {
+    if values.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    if !tolerance.is_finite() || tolerance <= 0.0 || values.iter().any(|v| !v.is_finite()) {
+        return Err(MathError::OutOfRange);
+    }
+    let lifted = values
+        .iter()
+        .map(|&v| {
+            let (p, q) = simplest_between((v.abs() - tolerance).max(0.0), v.abs() + tolerance)?;
+            Ok((if v < 0.0 { -p } else { p }, q))
+        })
+        .collect::<Result<Vec<(i128, i128)>, MathError>>()?;
+
+    // gcd of reduced fractions: gcd of the numerators over lcm of the denominators
+    let (mut numer, mut denom) = (0u128, 1u128);
+    for &(p, q) in lifted.iter().filter(|(p, _)| *p != 0) {
+        numer = gcd_u128(numer, p.unsigned_abs());
+        let q = q as u128;
+        denom = (denom / gcd_u128(denom, q))
+            .checked_mul(q)
+            .ok_or(MathError::Overflow)?;
+    }
+    if numer == 0 {
+        return Err(MathError::NoSolution);
+    }
+    let to_i64 = |x: u128| i64::try_from(x).map_err(|_| MathError::Overflow);
+    let step = Rational::new(to_i64(numer)?, to_i64(denom)?)?;
+
+    // p/q = k·numer/denom  <=>  k = p·(denom/q)/numer
+    let multiples = lifted
+        .iter()
+        .map(|&(p, q)| {
+            let k = p
+                .checked_mul((denom / q as u128) as i128)
+                .ok_or(MathError::Overflow)?
+                / numer as i128;
+            i64::try_from(k).map_err(|_| MathError::Overflow)
+        })
+        .collect::<Result<Vec<i64>, MathError>>()?;
+    let step_f64 = numer as f64 / denom as f64;
+    let max_error = values
+        .iter()
+        .zip(&multiples)
+        .map(|(&v, &k)| (v - k as f64 * step_f64).abs())
+        .fold(0.0, f64::max);
+    Ok(FloatGcd {
+        step,
+        multiples,
+        max_error,
+        confidence: (1.0 - 2.0 * max_error / step_f64).clamp(0.0, 1.0),
+    })
+}
+
+/// The fraction `p/q` of smallest denominator in `[lo, hi]`, for `0 <= lo <= hi`.
+///
+/// Peels off common continued fraction terms of the two ends, keeping the convergent
+/// as the matrix `(a b; c d)` so that the value is `(a·x + b) / (c·x + d)` for the
+/// remaining tail `x`, and stops at the first integer inside the interval.
+fn simplest_between(mut lo: f64, mut hi: f64) -> Result<(i128, i128), MathError> {
+    let (mut a, mut b, mut c, mut d) = (1i128, 0i128, 0i128, 1i128);
+    // 2^62 bounds each term, so 128 terms cover any f64 interval before overflow does
+    for _ in 0..128 {
+        let ceil = lo.ceil();
+        if ceil > (1u64 << 62) as f64 {
+            return Err(MathError::Overflow);
+        }
+        if ceil <= hi {
+            let x = ceil as i128;
+            let p = a.checked_mul(x).and_then(|ax| ax.checked_add(b));
+            let q = c.checked_mul(x).and_then(|cx| cx.checked_add(d));
+            return p.zip(q).ok_or(MathError::Overflow);
+        }
+        // lo and hi share the integer part n: value = n + 1/x with 1/x in (lo - n, hi - n)
+        let n = lo.floor();
+        let term = n as i128;
+        (a, b, c, d) = (
+            a.checked_mul(term)
+                .and_then(|an| an.checked_add(b))
+                .ok_or(MathError::Overflow)?,
+            a,
+            c.checked_mul(term)
+                .and_then(|cn| cn.checked_add(d))
+                .ok_or(MathError::Overflow)?,
+            c,
+        );
+        (lo, hi) = (1.0 / (hi - n), 1.0 / (lo - n));
+    }
+    Err(MathError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn r(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    #[test]
+    fn test_gcd_of_floats_exact() {
+        let found = gcd_of_floats(&[0.3, 0.5, 0.7], 1e-9).unwrap();
+        assert_eq!(found.step, r(1, 10));
+        assert_eq!(found.multiples, vec![3, 5, 7]);
+        assert!(found.max_error < 1e-12);
+        assert!(found.confidence > 0.999_999);
+
+        let found = gcd_of_floats(&[-1.5, 2.25, 0.0], 1e-9).unwrap();
+        assert_eq!(found.step, r(3, 4));
+        assert_eq!(found.multiples, vec![-2, 3, 0]);
+
+        let found = gcd_of_floats(&[12.0, 18.0, 30.0], 1e-6).unwrap();
+        assert_eq!(found.step, r(6, 1));
+        assert_eq!(found.multiples, vec![2, 3, 5]);
+    }
+
+    #[test]
+    fn test_gcd_of_floats_noisy() {
+        let found = gcd_of_floats(&[2.0004, 3.4996, 5.0003], 1e-3).unwrap();
+        assert_eq!(found.step, r(1, 2));
+        assert_eq!(found.multiples, vec![4, 7, 10]);
+        assert!((found.max_error - 4e-4).abs() < 1e-9);
+        assert!((found.confidence - 0.9984).abs() < 1e-6);
+
+        // a period of 1/3 s measured to a millisecond
+        let samples = [0.6667, 1.3331, 2.3335, 3.0002];
+        let found = gcd_of_floats(&samples, 1e-3).unwrap();
+        assert_eq!(found.step, r(1, 3));
+        assert_eq!(found.multiples, vec![2, 4, 7, 9]);
+
+        // too tight a tolerance: the noise becomes structure, and confidence drops
+        let found = gcd_of_floats(&[2.0004, 3.4996, 5.0003], 1e-6).unwrap();
+        assert!(found.step < r(1, 1000));
+        // 1/3 and 1/2 fit loosely, and their step of 1/6 is coarse next to the error
+        let loose = gcd_of_floats(&[0.31, 0.52], 0.05).unwrap();
+        assert_eq!(loose.step, r(1, 6));
+        assert_eq!(loose.multiples, vec![2, 3]);
+        assert!(loose.confidence < 0.9);
+    }
+
+    #[test]
+    fn test_simplest_between() {
+        assert_eq!(simplest_between(0.3, 0.4), Ok((1, 3)));
+        assert_eq!(simplest_between(2.0, 2.0), Ok((2, 1)));
+        assert_eq!(simplest_between(0.0, 0.1), Ok((0, 1)));
+        let pi = core::f64::consts::PI;
+        assert_eq!(simplest_between(pi - 3e-7, pi + 3e-7), Ok((355, 113)));
+        assert_eq!(simplest_between(0.618, 0.6181), Ok((89, 144)));
+    }
+
+    #[test]
+    fn test_gcd_of_floats_errors() {
+        assert_eq!(gcd_of_floats(&[], 0.1), Err(MathError::EmptyInput));
+        assert_eq!(gcd_of_floats(&[1.0], 0.0), Err(MathError::OutOfRange));
+        assert_eq!(gcd_of_floats(&[1.0], f64::NAN), Err(MathError::OutOfRange));
+        assert_eq!(
+            gcd_of_floats(&[1.0, f64::INFINITY], 0.1),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            gcd_of_floats(&[0.01, -0.02], 0.1),
+            Err(MathError::NoSolution)
+        );
+        assert_eq!(
+            gcd_of_floats(&[1.0, 1e-30], 1e-40),
+            Err(MathError::Overflow)
+        );
+    }
+}
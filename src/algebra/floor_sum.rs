@@ -0,0 +1,428 @@
+use crate::algebra::gcd::gcd_u128;
+use crate::errors::MathError;
+
+/// `Σ ⌊(a·i + b) / m⌋` for `0 <= i < n`, the lattice points under a line:
+/// - `floor_sum(4, 10, 6, 3) = 0 + 0 + 1 + 2 = 3`
+///
+/// `a` and `b` are first reduced mod `m`, then the roles of the slope and the modulus swap
+/// as in Euclid's algorithm, so the cost is O(log m) whatever `n`. Negative `a` and `b`
+/// are fine (floors round toward negative infinity). Errors with `DivisionByZero` if
+/// `m == 0` and `Overflow` if the sum leaves `i128`.
+pub fn floor_sum(n: u64, m: u64, a: i64, b: i64) -> Result<i128, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    // Σ i = n(n - 1)/2 < 2^127
+    let triangle = (n as u128 * n.saturating_sub(1) as u128 / 2) as i128;
+    let (n, m, a, b) = (n as i128, m as i128, a as i128, b as i128);
+    let whole = a
+        .div_euclid(m)
+        .checked_mul(triangle)
+        .zip(b.div_euclid(m).checked_mul(n))
+        .and_then(|(x, y)| x.checked_add(y))
+        .ok_or(MathError::Overflow)?;
+    let rest = floor_sum_reduced(
+        n as u128,
+        m as u128,
+        a.rem_euclid(m) as u128,
+        b.rem_euclid(m) as u128,
+    )?;
+    i128::try_from(rest)
+        .ok()
+        .and_then(|rest| whole.checked_add(rest))
+        .ok_or(MathError::Overflow)
+}
+
+/// A Beatty sequence `⌊n·α⌋`, `n >= 1`, for a positive quadratic irrational
+/// `α = (p + q√d) / r`.
+///
+/// Terms, counts and membership are exact: `⌊n·q√d⌋` is an integer square root, never a
+/// float. Rayleigh's theorem says the sequences of `α` and `β` partition the positive
+/// integers exactly when `1/α + 1/β = 1`; `complement` builds that `β` and
+/// `is_complementary` tests the identity. The form is kept in lowest terms but `d` is
+/// not made squarefree, so `√8` and `2√2` give sequences that compare unequal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeattySequence {
+    alpha: Surd,
+    recip: Surd,
+}
+
+impl BeattySequence {
+    /// The sequence of `α = (p + q√d) / r`:
+    /// - `BeattySequence::new(1, 1, 5, 2)`: the golden ratio, terms 1, 3, 4, 6, 8, 9, ...
+    ///   (lower Wythoff sequence)
+    ///
+    /// Errors with `DivisionByZero` if `r == 0`, `OutOfRange` if `α` is rational
+    /// (`q == 0` or `d` a perfect square) or not positive, and `Overflow` if `1/α` does
+    /// not fit the same form in `i128`.
+    pub fn new(p: i64, q: i64, d: u64, r: i64) -> Result<Self, MathError> {
+        if r == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        if q == 0 || d.isqrt() * d.isqrt() == d {
+            return Err(MathError::OutOfRange);
+        }
+        let alpha = Surd::new(p as i128, q as i128, d as u128, r as i128);
+        if !alpha.is_positive()? {
+            return Err(MathError::OutOfRange);
+        }
+        Ok(BeattySequence {
+            alpha,
+            recip: alpha.recip()?,
+        })
+    }
+
+    /// `⌊n·α⌋`:
+    /// - `BeattySequence::new(0, 1, 2, 1)?.term(10) = ⌊10√2⌋ = 14`
+    ///
+    /// `MathError::Overflow` once `(n·q)²·d` leaves `u128`.
+    pub fn term(&self, n: u64) -> Result<u128, MathError> {
+        self.alpha.floor_mul(n as i128).map(|t| t as u128)
+    }
+
+    /// How many terms, `n >= 1`, are at most `k`:
+    /// - `BeattySequence::new(1, 1, 5, 2)?.count_up_to(10) = 6`
+    ///
+    /// `⌊n·α⌋ <= k` exactly when `n < (k + 1)/α`, so this is `⌊(k + 1)/α⌋`, one term
+    /// of the reciprocal's sequence. `MathError::Overflow` as for `term`.
+    pub fn count_up_to(&self, k: u64) -> Result<u128, MathError> {
+        self.recip.floor_mul(k as i128 + 1).map(|c| c as u128)
+    }
+
+    /// Whether `k = ⌊n·α⌋` for some `n >= 1`:
+    /// - `BeattySequence::new(1, 1, 5, 2)?.contains(5) = false`, 5 is upper Wythoff
+    ///
+    /// Compares two counts; no term is searched for. `MathError::Overflow` as for `term`.
+    pub fn contains(&self, k: u64) -> Result<bool, MathError> {
+        let below = match k {
+            0 => 0,
+            _ => self.count_up_to(k - 1)?,
+        };
+        Ok(self.count_up_to(k)? > below)
+    }
+
+    /// The sequence of `β = α / (α - 1)`, which together with this one covers every
+    /// positive integer exactly once:
+    /// - `BeattySequence::new(0, 1, 2, 1)?.complement()`: `2 + √2`, terms 3, 6, 10, 13, ...
+    ///
+    /// Errors with `OutOfRange` if `α <= 1`, where no complement exists, and `Overflow`
+    /// if `β` does not fit in `i128`.
+    pub fn complement(&self) -> Result<BeattySequence, MathError> {
+        let shifted = Surd::new(
+            self.alpha.p - self.alpha.r,
+            self.alpha.q,
+            self.alpha.d,
+            self.alpha.r,
+        );
+        if !shifted.is_positive()? {
+            return Err(MathError::OutOfRange);
+        }
+        // 1/β = 1 - 1/α
+        let recip = Surd::new(
+            self.recip.r - self.recip.p,
+            -self.recip.q,
+            self.recip.d,
+            self.recip.r,
+        );
+        Ok(BeattySequence {
+            alpha: recip.recip()?,
+            recip,
+        })
+    }
+
+    /// Whether the two sequences partition the positive integers, by Rayleigh's
+    /// criterion `1/α + 1/β = 1`:
+    /// - `φ` and `φ² = (3 + √5)/2` are complementary (Wythoff's pairs)
+    ///
+    /// The rational parts of the reciprocals must add to 1 and the irrational parts
+    /// cancel, which holds across different `d` whenever `q₁²d₁·r₂² = q₂²d₂·r₁²` with
+    /// opposite signs. `MathError::Overflow` if those products leave `u128`.
+    pub fn is_complementary(&self, other: &BeattySequence) -> Result<bool, MathError> {
+        let (x, y) = (&self.recip, &other.recip);
+        let rational =
+            x.p.checked_mul(y.r)
+                .zip(y.p.checked_mul(x.r))
+                .and_then(|(a, b)| a.checked_add(b))
+                .zip(x.r.checked_mul(y.r))
+                .ok_or(MathError::Overflow)?;
+        if rational.0 != rational.1 || (x.q > 0) == (y.q > 0) {
+            return Ok(false);
+        }
+        let radical = |s: &Surd, other_r: i128| {
+            s.q.unsigned_abs()
+                .checked_mul(s.q.unsigned_abs())
+                .and_then(|v| v.checked_mul(s.d))
+                .and_then(|v| v.checked_mul(other_r.unsigned_abs()))
+                .and_then(|v| v.checked_mul(other_r.unsigned_abs()))
+                .ok_or(MathError::Overflow)
+        };
+        Ok(radical(x, y.r)? == radical(y, x.r)?)
+    }
+}
+
+/// `(p + q√d) / r` with `q != 0`, `d` not a square, `r > 0` and `gcd(p, q, r) = 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Surd {
+    p: i128,
+    q: i128,
+    d: u128,
+    r: i128,
+}
+
+impl Surd {
+    fn new(p: i128, q: i128, d: u128, r: i128) -> Self {
+        let (p, q, r) = if r < 0 { (-p, -q, -r) } else { (p, q, r) };
+        let g = gcd_u128(
+            gcd_u128(p.unsigned_abs(), q.unsigned_abs()),
+            r.unsigned_abs(),
+        ) as i128;
+        Surd {
+            p: p / g,
+            q: q / g,
+            d,
+            r: r / g,
+        }
+    }
+
+    fn is_positive(&self) -> Result<bool, MathError> {
+        match (self.p >= 0, self.q > 0) {
+            (true, true) => Ok(true),
+            (false, false) => Ok(false),
+            // the sign of whichever of |p| and |q|√d is larger
+            (p_positive, _) => {
+                Ok((self.p.unsigned_abs().pow(2) > self.q_squared_d()?) == p_positive)
+            }
+        }
+    }
+
+    /// `r / (p + q√d) = r·(p - q√d) / (p² - q²d)`, never dividing by zero as `d` is not a
+    /// square.
+    fn recip(&self) -> Result<Surd, MathError> {
+        let norm = self
+            .p
+            .checked_mul(self.p)
+            .zip(i128::try_from(self.q_squared_d()?).ok())
+            .map(|(p2, q2d)| p2 - q2d)
+            .ok_or(MathError::Overflow)?;
+        let p = self.r.checked_mul(self.p).ok_or(MathError::Overflow)?;
+        let q = self.r.checked_mul(-self.q).ok_or(MathError::Overflow)?;
+        Ok(Surd::new(p, q, self.d, norm))
+    }
+
+    /// `⌊n·(p + q√d) / r⌋ = ⌊(n·p + ⌊n·q√d⌋) / r⌋`, since `r` is a positive integer.
+    fn floor_mul(&self, n: i128) -> Result<i128, MathError> {
+        let t = n.checked_mul(self.q).ok_or(MathError::Overflow)?;
+        let root = match t {
+            0 => 0,
+            _ => {
+                let square = t
+                    .unsigned_abs()
+                    .checked_mul(t.unsigned_abs())
+                    .and_then(|v| v.checked_mul(self.d))
+                    .ok_or(MathError::Overflow)?;
+                // never a perfect square, so ⌊-x⌋ = -⌊x⌋ - 1
+                let root = square.isqrt() as i128;
+                if t > 0 { root } else { -root - 1 }
+            }
+        };
+        n.checked_mul(self.p)
+            .and_then(|np| np.checked_add(root))
+            .map(|numer| numer.div_euclid(self.r))
+            .ok_or(MathError::Overflow)
+    }
+
+    fn q_squared_d(&self) -> Result<u128, MathError> {
+        self.q
+            .unsigned_abs()
+            .checked_mul(self.q.unsigned_abs())
+            .and_then(|v| v.checked_mul(self.d))
+            .ok_or(MathError::Overflow)
+    }
+}
+
+/// `floor_sum` with `0 <= a, b < m`: each round adds the whole parts, then counts the
+/// same lattice points by columns instead of rows, swapping `a` and `m`.
+fn floor_sum_reduced(
+    mut n: u128,
+    mut m: u128,
+    mut a: u128,
+    mut b: u128,
+) -> Result<u128, MathError> {
+    let mut sum = 0u128;
+    loop {
+        if a >= m {
+            let add = (n * n.saturating_sub(1) / 2).checked_mul(a / m);
+            sum = add
+                .and_then(|add| sum.checked_add(add))
+                .ok_or(MathError::Overflow)?;
+            a %= m;
+        }
+        if b >= m {
+            let add = n.checked_mul(b / m);
+            sum = add
+                .and_then(|add| sum.checked_add(add))
+                .ok_or(MathError::Overflow)?;
+            b %= m;
+        }
+        // a, b < m and n stays below 2^64·m / previous m, so this fits
+        let y_max = a * n + b;
+        if y_max < m {
+            return Ok(sum);
+        }
+        n = y_max / m;
+        b = y_max % m;
+        core::mem::swap(&mut m, &mut a);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn brute(n: u64, m: u64, a: i64, b: i64) -> i128 {
+        (0..n as i128)
+            .map(|i| (a as i128 * i + b as i128).div_euclid(m as i128))
+            .sum()
+    }
+
+    #[test]
+    fn test_floor_sum() {
+        assert_eq!(floor_sum(4, 10, 6, 3), Ok(3));
+        assert_eq!(floor_sum(0, 7, 5, 3), Ok(0));
+        for n in 0..20 {
+            for m in 1..12 {
+                for a in -15..15 {
+                    for b in -15..15 {
+                        assert_eq!(
+                            floor_sum(n, m, a, b),
+                            Ok(brute(n, m, a, b)),
+                            "{n} {m} {a} {b}"
+                        );
+                    }
+                }
+            }
+        }
+        assert_eq!(floor_sum(3, 0, 1, 1), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_floor_sum_large() {
+        // Σ ⌊a·i/m⌋ over a full period is (a - 1)(m - 1)/2 when gcd(a, m) = 1
+        let (m, a) = (1_000_000_007u64, 123_456_789i64);
+        assert_eq!(
+            floor_sum(m, m, a, 0),
+            Ok((a as i128 - 1) * (m as i128 - 1) / 2)
+        );
+        let (m, a) = ((1u64 << 61) - 1, (1i64 << 40) + 15);
+        assert_eq!(
+            floor_sum(m, m, a, 0),
+            Ok((a as i128 - 1) * (m as i128 - 1) / 2)
+        );
+        // Σ_{i < n} i = n(n - 1)/2 at the top of the range
+        let triangle = (u64::MAX as u128 * (u64::MAX - 1) as u128 / 2) as i128;
+        assert_eq!(floor_sum(u64::MAX, 1, 1, 0), Ok(triangle));
+        assert_eq!(floor_sum(u64::MAX, 1, -1, 0), Ok(-triangle));
+        assert_eq!(
+            floor_sum(u64::MAX, 1, i64::MAX, i64::MAX),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_beatty_terms() {
+        let phi = BeattySequence::new(1, 1, 5, 2).unwrap();
+        let lower: Vec<u128> = (1..=8).map(|n| phi.term(n).unwrap()).collect();
+        assert_eq!(lower, [1, 3, 4, 6, 8, 9, 11, 12]);
+        let sqrt2 = BeattySequence::new(0, 1, 2, 1).unwrap();
+        assert_eq!(sqrt2.term(10), Ok(14));
+        for n in 0..2000u64 {
+            assert_eq!(sqrt2.term(n), Ok((n as f64 * 2f64.sqrt()) as u128));
+            let negative = BeattySequence::new(7, -2, 3, 3).unwrap();
+            let value = (7.0 - 2.0 * 3f64.sqrt()) / 3.0;
+            assert_eq!(negative.term(n), Ok((n as f64 * value) as u128));
+        }
+        // ⌊2^62·√2⌋ = ⌊√(2^125)⌋
+        assert_eq!(sqrt2.term(1 << 62), Ok((1u128 << 125).isqrt()));
+        assert_eq!(sqrt2.term(u64::MAX), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_beatty_new_errors() {
+        assert_eq!(
+            BeattySequence::new(1, 1, 5, 0),
+            Err(MathError::DivisionByZero)
+        );
+        assert_eq!(BeattySequence::new(1, 1, 4, 1), Err(MathError::OutOfRange));
+        assert_eq!(BeattySequence::new(1, 0, 5, 1), Err(MathError::OutOfRange));
+        assert_eq!(BeattySequence::new(-3, 1, 5, 1), Err(MathError::OutOfRange));
+        assert_eq!(
+            BeattySequence::new(3, -1, 5, -1),
+            Err(MathError::OutOfRange)
+        );
+        assert!(BeattySequence::new(-2, 1, 5, 1).is_ok());
+        // same value, normalized
+        assert_eq!(
+            BeattySequence::new(2, 2, 5, 4),
+            BeattySequence::new(-1, -1, 5, -2)
+        );
+    }
+
+    #[test]
+    fn test_beatty_partition() {
+        let phi = BeattySequence::new(1, 1, 5, 2).unwrap();
+        let phi2 = phi.complement().unwrap();
+        assert_eq!(phi2, BeattySequence::new(3, 1, 5, 2).unwrap());
+        assert_eq!(phi.is_complementary(&phi2), Ok(true));
+        assert_eq!(phi2.is_complementary(&phi), Ok(true));
+        assert_eq!(phi.is_complementary(&phi), Ok(false));
+        assert_eq!(phi.count_up_to(10), Ok(6));
+        assert_eq!(phi.contains(5), Ok(false));
+
+        let sqrt2 = BeattySequence::new(0, 1, 2, 1).unwrap();
+        let other = sqrt2.complement().unwrap();
+        assert_eq!(other, BeattySequence::new(2, 1, 2, 1).unwrap());
+        // √8 and 2√2 give the same sequence and are still recognized
+        let sqrt8 = BeattySequence::new(4, 1, 8, 2).unwrap();
+        assert_eq!(sqrt2.is_complementary(&sqrt8), Ok(true));
+        assert_ne!(sqrt8, other);
+
+        for (a, b) in [(phi, phi2), (sqrt2, other)] {
+            let limit = 5000u64;
+            let mut seen = alloc::vec![0u8; limit as usize + 1];
+            for seq in [a, b] {
+                for n in 1.. {
+                    let t = seq.term(n).unwrap() as u64;
+                    if t > limit {
+                        break;
+                    }
+                    seen[t as usize] += 1;
+                }
+            }
+            assert!(seen[1..].iter().all(|&c| c == 1));
+            for k in 0..=limit {
+                assert_eq!(
+                    a.contains(k),
+                    Ok(k > 0 && seen[k as usize] == 1 && !b.contains(k).unwrap())
+                );
+                assert_eq!(
+                    a.count_up_to(k).unwrap() + b.count_up_to(k).unwrap(),
+                    k as u128
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_beatty_below_one() {
+        // α = √2/2: every nonnegative integer appears, some twice
+        let half = BeattySequence::new(0, 1, 2, 2).unwrap();
+        assert_eq!(half.term(3), Ok(2));
+        assert_eq!(half.contains(0), Ok(true));
+        assert!((0..100).all(|k| half.contains(k) == Ok(true)));
+        assert_eq!(half.count_up_to(0), Ok(1));
+        assert_eq!(half.complement(), Err(MathError::OutOfRange));
+        let one_half = BeattySequence::new(1, 1, 5, 2).unwrap();
+        assert_eq!(half.is_complementary(&one_half), Ok(false));
+    }
+}
@@ -1,17 +1,35 @@
+use crate::algebra::integer::Integer;
+use crate::algebra::modular;
 use crate::errors::MathError;
 
-/// Computes the greatest common divisor using Euclid's algorithm.
+/// Computes the greatest common divisor.
 ///
-/// The sign of the result follows the sign of the first parameter:
+/// The sign of the result follows the sign of the first parameter, or of the second
+/// when the first is 0:
 /// - `gcd(48, 88) = 8`
 /// - `gcd(48, -88) = 8`  
 /// - `gcd(-48, 88) = -8`
 /// - `gcd(-48, -88) = -8`
-pub fn gcd(a: i64, b: i64) -> Result<i64, MathError> {
+/// - `gcd(0, -5) = -5`
+///
+/// `gcd_i64` with the sign restored. Errors with `DivisionByZero` if `b == 0`, and
+/// `Overflow` for the gcd `2^63` with a non-negative sign. A `const fn`; `gcd_generic`
+/// takes any `Integer` but cannot be const, as it goes through trait methods.
+pub const fn gcd(a: i64, b: i64) -> Result<i64, MathError> {
     if b == 0 {
         return Err(MathError::DivisionByZero);
     }
-    gcd_with_quotient(a, b).map(|(gcd, _)| gcd)
+    let g = gcd_i64(a, b);
+    let negative = if a == 0 { b < 0 } else { a < 0 };
+    if negative {
+        // -2^63 is the one magnitude that only fits negated
+        match 0i64.checked_sub_unsigned(g) {
+            Some(n) => Ok(n),
+            None => Err(MathError::Overflow),
+        }
+    } else {
+        narrow_i64(g)
+    }
 }
 
 /// This result is always positive (absolute value):
@@ -19,38 +37,288 @@ pub fn gcd(a: i64, b: i64) -> Result<i64, MathError> {
 /// - `gcd_abs(48, -88) = 8`
 /// - `gcd_abs(-48, 88) = 8`
 /// - `gcd_abs(-48, -88) = 8`
-pub fn gcd_abs(a: i64, b: i64) -> Result<i64, MathError> {
+///
+/// `gcd_i64` narrowed to `i64`: `DivisionByZero` if `b == 0`, like `gcd`, and
+/// `Overflow` if the gcd is `2^63`. A `const fn`, unlike `gcd_abs_generic`.
+pub const fn gcd_abs(a: i64, b: i64) -> Result<i64, MathError> {
     if b == 0 {
         return Err(MathError::DivisionByZero);
     }
-    gcd_with_quotient(a, b).map(|(gcd, _)| gcd.abs())
+    narrow_i64(gcd_i64(a, b))
 }
 
-/// Computes GCD using the Euclidean algorithm recursively.
-/// Returns (gcd, last_quotient) where gcd has the same sign as the first parameter.
-fn gcd_with_quotient(a: i64, b: i64) -> Result<(i64, i64), MathError> {
-    let r = a % b;
-    let q = a / b; // it's the same than "q = (a - r) / b"
-
-    if r == 0 {
-        Ok((b, q))
+const fn narrow_i64(g: u64) -> Result<i64, MathError> {
+    if g > i64::MAX as u64 {
+        Err(MathError::Overflow)
     } else {
-        gcd_with_quotient(b, r)
+        Ok(g as i64)
     }
 }
 
-/// Safe for cryptographic use - never exposes intermediate quotients
-pub fn gcd_secure(a: i64, b: i64) -> Result<i64, MathError> {
+/// `gcd` for any `Integer`:
+/// - `gcd_generic(48u64, 88) = 8`
+/// - `gcd_generic(-48i128, 88) = -8`
+///
+/// Magnitudes up to `u64` go through `gcd_u64` and up to `u128` through `gcd_u128`;
+/// only wider values, and a gcd of `MIN` whose magnitude has no positive `T`, fall back
+/// to Euclid's remainders. Trait methods cannot be called in a `const` context, so
+/// compile-time code should use the `const fn` `gcd_u128` instead. `DivisionByZero` if
+/// `b` is zero.
+pub fn gcd_generic<T: Integer>(a: T, b: T) -> Result<T, MathError> {
+    if b.is_zero() {
+        return Err(MathError::DivisionByZero);
+    }
+    let negative = match a.is_zero() {
+        true => b.is_negative(),
+        false => a.is_negative(),
+    };
+    match magnitude_gcd::<T>(&a, &b) {
+        Some(g) if negative => g.int_neg(),
+        Some(g) => Ok(g),
+        None => euclid(a, b),
+    }
+}
+
+/// `gcd_abs` for any `Integer`:
+/// - `gcd_abs_generic(-48i8, 88) = 8`
+///
+/// `DivisionByZero` if `b` is zero and `Overflow` if the gcd is `MIN` of a signed type.
+pub fn gcd_abs_generic<T: Integer>(a: T, b: T) -> Result<T, MathError> {
+    if b.is_zero() {
+        return Err(MathError::DivisionByZero);
+    }
+    match magnitude_gcd::<T>(&a, &b) {
+        Some(g) => Ok(g),
+        None => euclid(a, b)?.int_abs(),
+    }
+}
+
+/// `gcd(|a|, |b|)` by the binary gcd, `None` if a magnitude leaves `u128` or the gcd
+/// does not fit in `T`.
+fn magnitude_gcd<T: Integer>(a: &T, b: &T) -> Option<T> {
+    let (a, b) = a.abs_to_u128().zip(b.abs_to_u128())?;
+    let g = match (u64::try_from(a), u64::try_from(b)) {
+        (Ok(a), Ok(b)) => u128::from(gcd_u64(a, b)),
+        _ => gcd_u128(a, b),
+    };
+    T::try_from_u128(g)
+}
+
+/// Non-negative gcd of a whole list, zeros ignored:
+/// - `gcd_many(&[12, -18, 30]) = 6`
+/// - `gcd_many(&[0, 0]) = 0`
+///
+/// Stops as soon as the running gcd reaches 1. Errors with `EmptyInput` for an empty
+/// list and `Overflow` only if the gcd itself is `MIN` of a signed type.
+pub fn gcd_many<T: Integer>(values: &[T]) -> Result<T, MathError> {
+    if values.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    // signed until the end, so that a MIN operand only fails if it is the answer
+    let mut g = T::zero();
+    for v in values.iter().filter(|v| !v.is_zero()) {
+        g = if g.is_zero() {
+            v.clone()
+        } else {
+            gcd_generic(v.clone(), g)?
+        };
+        if g.int_abs() == Ok(T::one()) {
+            break;
+        }
+    }
+    g.int_abs()
+}
+
+/// Extended Euclidean algorithm: `(g, x, y)` with `a*x + b*y = g` and `g = gcd_abs(a, b)`:
+/// - `extended_gcd(240, 46) = (2, -9, 47)`
+/// - `extended_gcd(-3, 7) = (1, 2, 1)`
+///
+/// The quotients that `gcd` never computes drive the Bézout coefficients here,
+/// so `|x| <= |b| / g` and `|y| <= |a| / g`. Errors with `DivisionByZero` if `b == 0`
+/// (like `gcd`) and `Overflow` only when `i64::MIN` pushes `g` or a coefficient out of
+/// range.
+pub fn extended_gcd(a: i64, b: i64) -> Result<(i64, i64, i64), MathError> {
     if b == 0 {
         return Err(MathError::DivisionByZero);
     }
-    let r = a % b;
-    let a = a / b;
+    // invariant: old_r = a*old_x + b*old_y and r = a*x + b*y
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+    // every value stays within 2^64 in magnitude, so the checks below never fail
+    let step = |old: i128, new: i128, q: i128| {
+        q.checked_mul(new)
+            .and_then(|t| old.checked_sub(t))
+            .ok_or(MathError::Overflow)
+    };
+    while r != 0 {
+        let q = old_r.checked_div(r).ok_or(MathError::Overflow)?;
+        (old_r, r) = (r, step(old_r, r, q)?);
+        (old_x, x) = (x, step(old_x, x, q)?);
+        (old_y, y) = (y, step(old_y, y, q)?);
+    }
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (step(0, old_r, 1)?, step(0, old_x, 1)?, step(0, old_y, 1)?);
+    }
+    let narrow = |v: i128| i64::try_from(v).map_err(|_| MathError::Overflow);
+    Ok((narrow(old_r)?, narrow(old_x)?, narrow(old_y)?))
+}
 
-    if r == 0 {
-        Ok(b.abs())
-    } else {
-        gcd_secure(b, r)
+/// Inverse of `a` modulo `m`, in `[0, m)`, for signed operands:
+/// - `mod_inverse_i64(3, 11) = 4`, since `3·4 = 12 ≡ 1`
+/// - `mod_inverse_i64(-3, 11) = 7`
+///
+/// Reduces `a` into `[0, m)` and defers to `modular::mod_inverse`. Errors with
+/// `PositifIntegerRequired` if `m < 1` and `NoInverse` if `gcd(a, m) ≠ 1`.
+pub fn mod_inverse_i64(a: i64, m: i64) -> Result<i64, MathError> {
+    if m < 1 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    let inverse = modular::mod_inverse(a.rem_euclid(m).unsigned_abs(), m.unsigned_abs())?;
+    // below m, so within i64
+    i64::try_from(inverse).map_err(|_| MathError::Overflow)
+}
+
+/// Computes GCD using the Euclidean algorithm iteratively.
+/// The gcd has the same sign as the first parameter.
+fn euclid<T: Integer>(mut a: T, mut b: T) -> Result<T, MathError> {
+    loop {
+        let r = a.int_rem(&b)?;
+
+        if r.is_zero() {
+            return Ok(b);
+        }
+        a = b;
+        b = r;
+    }
+}
+
+/// Non-negative gcd in constant time, for secret operands:
+/// - `gcd_secure(-48, 88) = 8`
+/// - `gcd_secure(0, 5) = 5`
+///
+/// Runs `gcd_constant_time` on the magnitudes, so the sequence of operations depends
+/// neither on the values nor on how many Euclidean steps they would take. Only the
+/// public precondition `b != 0` is branched on: `DivisionByZero` otherwise, like
+/// `gcd`. `Overflow` for `gcd_secure(i64::MIN, i64::MIN)`, whose gcd is `2^63`.
+pub fn gcd_secure(a: i64, b: i64) -> Result<i64, MathError> {
+    gcd_secure_generic(a, b)
+}
+
+/// `gcd_secure` for any `Integer`:
+/// - `gcd_secure_generic(u128::MAX, u128::MAX) = u128::MAX`
+///
+/// Errors with `OutOfRange` for a magnitude beyond `u128` and `Overflow` if the gcd
+/// does not fit in `T`.
+pub fn gcd_secure_generic<T: Integer>(a: T, b: T) -> Result<T, MathError> {
+    if b.is_zero() {
+        return Err(MathError::DivisionByZero);
+    }
+    let (a, b) = a
+        .abs_to_u128()
+        .zip(b.abs_to_u128())
+        .ok_or(MathError::OutOfRange)?;
+    T::try_from_u128(gcd_constant_time(a, b)).ok_or(MathError::Overflow)
+}
+
+/// Binary gcd with a fixed number of branch-free iterations; requires `b != 0`.
+///
+/// Keeps `b` odd and each step replaces an odd `a` by `|a - b|` (moving the smaller one
+/// into `b`) then halves `a`. That lowers `bits(a) + bits(b)` by at least one, so
+/// `2·128` steps always bring `a` to 0, and running them all hides when it happened.
+/// Conditional moves are done with all-ones or all-zeros masks instead of branches.
+fn gcd_constant_time(mut a: u128, mut b: u128) -> u128 {
+    let shift = (a | b).trailing_zeros();
+    a >>= shift;
+    b >>= shift;
+    // one of them is now odd: make it b
+    let swap = 0u128.wrapping_sub(!b & 1);
+    let t = (a ^ b) & swap;
+    a ^= t;
+    b ^= t;
+
+    for _ in 0..2 * u128::BITS {
+        let odd = 0u128.wrapping_sub(a & 1);
+        let (diff, borrow) = a.overflowing_sub(b);
+        let swap = odd & 0u128.wrapping_sub(borrow as u128);
+        b ^= (a ^ b) & swap;
+        // (x ^ m) - m negates x when m is all ones: |a - b| on a borrow
+        let abs_diff = (diff ^ swap).wrapping_sub(swap);
+        a ^= (a ^ abs_diff) & odd;
+        a >>= 1;
+    }
+    b << shift
+}
+
+/// Binary gcd of `u64` operands, the core that `gcd_i64` wraps:
+/// - `gcd_u64(48, 88) = 8`
+/// - `gcd_u64(0, 0) = 0`
+///
+/// Same loop as `gcd_u128` on native 64-bit words; a `const fn`.
+pub const fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    if a == 0 {
+        return b;
+    }
+    if b == 0 {
+        return a;
+    }
+
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+
+    loop {
+        b >>= b.trailing_zeros();
+        if a > b {
+            core::mem::swap(&mut a, &mut b);
+        }
+        // a <= b after the swap
+        b = b.wrapping_sub(a);
+        if b == 0 {
+            return a << shift;
+        }
+    }
+}
+
+/// Non-negative gcd of signed operands, as a `u64`:
+/// - `gcd_i64(-48, 88) = 8`
+/// - `gcd_i64(i64::MIN, 0) = 2^63`
+///
+/// Takes the magnitudes and calls `gcd_u64`, so unlike `gcd_abs` there is no `MIN` case
+/// to reject and no error at all.
+pub const fn gcd_i64(a: i64, b: i64) -> u64 {
+    gcd_u64(a.unsigned_abs(), b.unsigned_abs())
+}
+
+/// Binary (Stein) gcd for unsigned 128-bit operands.
+///
+/// Uses only shifts and subtractions, avoiding the slow 128-bit hardware division:
+/// - `gcd_u128(48, 88) = 8`
+/// - `gcd_u128(0, 5) = 5`
+/// - `gcd_u128(0, 0) = 0`
+///
+/// A `const fn`, usable in constants and array lengths.
+pub const fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    if a == 0 {
+        return b;
+    }
+    if b == 0 {
+        return a;
+    }
+
+    // common power of two
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+
+    loop {
+        b >>= b.trailing_zeros();
+        if a > b {
+            core::mem::swap(&mut a, &mut b);
+        }
+        // a <= b after the swap
+        b = b.wrapping_sub(a);
+        if b == 0 {
+            return a << shift;
+        }
     }
 }
 
@@ -79,12 +347,42 @@ mod tests {
         assert_eq!(gcd_abs(-48, -88).unwrap(), 8);
     }
 
+    #[test]
+    fn test_min_and_minus_one() {
+        assert_eq!(gcd(i64::MIN, -1), Ok(-1));
+        assert_eq!(gcd(-1, i64::MIN), Ok(-1));
+        assert_eq!(gcd(i64::MIN, i64::MIN), Ok(i64::MIN));
+        assert_eq!(gcd_abs(i64::MIN, -1), Ok(1));
+        assert_eq!(gcd_abs(i64::MIN, i64::MIN), Err(MathError::Overflow));
+        assert_eq!(gcd_generic(i8::MIN, -1), Ok(-1));
+        assert_eq!(gcd_many(&[i32::MIN, -1]), Ok(1));
+        assert_eq!(extended_gcd(i64::MIN, -1), Ok((1, 0, -1)));
+        assert_eq!(extended_gcd(i64::MIN, i64::MIN), Err(MathError::Overflow));
+        assert_eq!(gcd_u64(u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(gcd_u128(u128::MAX, u128::MAX), u128::MAX);
+    }
+
     #[test]
     fn test_division_by_zero() {
         assert!(matches!(gcd(5, 0), Err(MathError::DivisionByZero)));
         assert!(matches!(gcd_abs(5, 0), Err(MathError::DivisionByZero)));
     }
 
+    #[test]
+    fn test_zero_first_operand() {
+        assert_eq!(gcd(0, -5), Ok(-5));
+        assert_eq!(gcd(0, i64::MIN), Ok(i64::MIN));
+        assert_eq!(gcd_abs(0, i64::MIN), Err(MathError::Overflow));
+        for b in (-20i64..=20).filter(|&b| b != 0) {
+            assert_eq!(gcd_abs(0, b), Ok(b.abs()));
+            assert_eq!(gcd_secure(0, b), gcd_abs(0, b));
+            assert_eq!(extended_gcd(0, b).map(|(g, _, _)| g), gcd_abs(0, b));
+            assert_eq!(gcd(b, 0), Err(MathError::DivisionByZero));
+            assert_eq!(gcd_generic(b, 0), Err(MathError::DivisionByZero));
+            assert_eq!(gcd_abs_generic(b, 0), Err(MathError::DivisionByZero));
+        }
+    }
+
     #[test]
     fn test_edge_cases() {
         assert_eq!(gcd(0, 5).unwrap(), 5);
@@ -109,6 +407,7 @@ mod tests {
     #[test]
     fn test_gcd_secure_division_by_zero() {
         assert!(matches!(gcd_secure(5, 0), Err(MathError::DivisionByZero)));
+        assert_eq!(gcd_secure_generic(0u128, 0), Err(MathError::DivisionByZero));
     }
 
     #[test]
@@ -143,4 +442,184 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_gcd_secure_randomized() {
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = || {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..5000 {
+            let (x, y) = (next(), next());
+            // shared factors make the gcds non-trivial
+            let common = (next() % 1000 + 1) as i64;
+            let a = (x as i64 >> (x % 60)).wrapping_mul(common);
+            let b = (y as i64 >> (y % 60)).wrapping_mul(common);
+            if b == 0 {
+                continue;
+            }
+            assert_eq!(gcd_secure(a, b), gcd_abs(a, b), "gcd_secure({a}, {b})");
+            let (a, b) = ((x as u128) << 64 | y as u128, (y as u128 | 1) << 17);
+            assert_eq!(gcd_secure_generic(a, b), Ok(gcd_u128(a, b)));
+        }
+    }
+
+    #[test]
+    fn test_gcd_secure_extremes() {
+        assert_eq!(gcd_secure_generic(u128::MAX, u128::MAX), Ok(u128::MAX));
+        assert_eq!(gcd_secure_generic(1u128 << 127, 1u128 << 100), Ok(1 << 100));
+        assert_eq!(gcd_secure(i64::MIN, 6), Ok(2));
+        assert_eq!(gcd_secure(i64::MIN, i64::MIN), Err(MathError::Overflow));
+        assert_eq!(gcd_secure_generic(i8::MIN, -64), Ok(64));
+    }
+
+    #[test]
+    fn test_gcd_generic_types() {
+        assert_eq!(gcd_generic(48u64, 88), Ok(8));
+        assert_eq!(gcd_generic(u64::MAX, u64::MAX / 5), Ok(u64::MAX / 5));
+        assert_eq!(gcd_generic(-48i128, 88), Ok(-8));
+        assert_eq!(gcd_abs_generic(-48i8, 88), Ok(8));
+        assert_eq!(gcd_secure_generic(-48i16, -88), Ok(8));
+        assert_eq!(gcd_generic(5usize, 0), Err(MathError::DivisionByZero));
+        let cases = [
+            (48, 88),
+            (-48, 88),
+            (i64::MIN, -1),
+            (i64::MIN, 6),
+            (0, -5),
+            (0, i64::MIN),
+            (i64::MIN, 0),
+            (0, 0),
+        ];
+        for (a, b) in cases {
+            assert_eq!(gcd_generic(a, b), gcd(a, b));
+            assert_eq!(gcd_abs_generic(a, b), gcd_abs(a, b));
+        }
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        assert_eq!(extended_gcd(240, 46), Ok((2, -9, 47)));
+        assert_eq!(extended_gcd(-3, 7), Ok((1, 2, 1)));
+        assert_eq!(extended_gcd(0, 5), Ok((5, 0, 1)));
+        assert_eq!(extended_gcd(5, 0), Err(MathError::DivisionByZero));
+        for (a, b) in [(48, 88), (-48, 88), (48, -88), (-48, -88), (17, 13), (1, 1)] {
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+            assert_eq!(g, gcd_abs(a, b).unwrap());
+            assert_eq!(a * x + b * y, g, "extended_gcd({a}, {b})");
+        }
+        let (g, x, y) = extended_gcd(i64::MAX, i64::MAX - 1).unwrap();
+        assert_eq!((g, x, y), (1, 1, -1));
+        assert_eq!(extended_gcd(i64::MIN, i64::MIN), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_mod_inverse_i64() {
+        assert_eq!(mod_inverse_i64(3, 11), Ok(4));
+        assert_eq!(mod_inverse_i64(-3, 11), Ok(7));
+        assert_eq!(mod_inverse_i64(1, 1), Ok(0));
+        // RSA textbook example: e = 17, φ = 3120
+        assert_eq!(mod_inverse_i64(17, 3120), Ok(2753));
+        for a in 1..97 {
+            let inv = mod_inverse_i64(a, 97).unwrap();
+            assert_eq!(a * inv % 97, 1);
+        }
+        assert_eq!(mod_inverse_i64(i64::MAX - 1, i64::MAX), Ok(i64::MAX - 1));
+        assert_eq!(mod_inverse_i64(6, 9), Err(MathError::NoInverse));
+        assert_eq!(mod_inverse_i64(0, 7), Err(MathError::NoInverse));
+        assert_eq!(
+            mod_inverse_i64(3, 0),
+            Err(MathError::PositifIntegerRequired)
+        );
+    }
+
+    #[test]
+    fn test_gcd_u128() {
+        assert_eq!(gcd_u128(48, 88), 8);
+        assert_eq!(gcd_u128(88, 48), 8);
+        assert_eq!(gcd_u128(0, 5), 5);
+        assert_eq!(gcd_u128(5, 0), 5);
+        assert_eq!(gcd_u128(0, 0), 0);
+        assert_eq!(gcd_u128(17, 13), 1);
+        assert_eq!(gcd_u128(1 << 100, 1 << 90), 1 << 90);
+        assert_eq!(gcd_u128(u128::MAX, u128::MAX), u128::MAX);
+    }
+
+    #[test]
+    fn test_gcd_u64_and_i64() {
+        assert_eq!(gcd_u64(48, 88), 8);
+        assert_eq!(gcd_u64(0, 5), 5);
+        assert_eq!(gcd_u64(0, 0), 0);
+        assert_eq!(gcd_u64(u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(gcd_i64(-48, 88), 8);
+        assert_eq!(gcd_i64(-48, -88), 8);
+        assert_eq!(gcd_i64(i64::MIN, 0), 1 << 63);
+        assert_eq!(gcd_i64(i64::MIN, i64::MIN), 1 << 63);
+        for a in -60i64..=60 {
+            for b in -60i64..=60 {
+                assert_eq!(
+                    gcd_i64(a, b) as u128,
+                    gcd_u128(a.unsigned_abs() as u128, b.unsigned_abs() as u128)
+                );
+            }
+        }
+        const G: u64 = gcd_i64(-12, 18);
+        assert_eq!(G, 6);
+    }
+
+    #[test]
+    fn test_gcd_u128_in_const() {
+        const G: u128 = gcd_u128(1 << 100, 3 << 90);
+        const TABLE: [u8; gcd_u128(48, 88) as usize] = [0; 8];
+        assert_eq!(G, 1 << 90);
+        assert_eq!(TABLE.len(), 8);
+        const SIGNED: Result<i64, MathError> = gcd(-48, 88);
+        const ABS: Result<i64, MathError> = gcd_abs(i64::MIN, i64::MIN);
+        assert_eq!(SIGNED, Ok(-8));
+        assert_eq!(ABS, Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_gcd_u128_same_as_gcd_abs() {
+        let test_cases = [
+            (48, 88),
+            (17, 13),
+            (100, 30),
+            (7, 3),
+            (1_234_567, 2_345_678),
+        ];
+
+        for (a, b) in test_cases {
+            assert_eq!(
+                gcd_u128(a as u128, b as u128),
+                gcd_abs(a, b).unwrap() as u128
+            );
+        }
+    }
+
+    #[test]
+    fn test_gcd_u128_large_operands() {
+        // (2^61 - 1) * (2^31 - 1) and (2^61 - 1) * (2^17 - 1)
+        let m61 = (1u128 << 61) - 1;
+        let a = m61 * ((1 << 31) - 1);
+        let b = m61 * ((1 << 17) - 1);
+        assert_eq!(gcd_u128(a, b), m61);
+    }
+
+    #[test]
+    fn test_gcd_many() {
+        assert_eq!(gcd_many(&[12, -18, 30]), Ok(6));
+        assert_eq!(gcd_many(&[-7]), Ok(7));
+        assert_eq!(gcd_many(&[0, 15, 0, 25]), Ok(5));
+        assert_eq!(gcd_many(&[0, 0]), Ok(0));
+        assert_eq!(gcd_many(&[4u8, 6, 9, 200]), Ok(1));
+        // i64::MIN = -2^63 is fine as long as it is not the gcd
+        assert_eq!(gcd_many(&[i64::MIN, 6]), Ok(2));
+        assert_eq!(gcd_many(&[i64::MIN, 0]), Err(MathError::Overflow));
+        assert_eq!(gcd_many::<i64>(&[]), Err(MathError::EmptyInput));
+    }
 }
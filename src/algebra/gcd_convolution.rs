@@ -0,0 +1,174 @@
+use alloc::vec::Vec;
+
+use crate::algebra::sieve::primes_up_to;
+use crate::errors::MathError;
+
+/// `c[k] = Σ_{gcd(i, j) = k} a[i]·b[j]` over `1 <= i, j <= n`, for tables indexed
+/// `1..=n` at indices `1..=n` (index 0 ignored and 0 in the result):
+/// - `gcd_convolution(&[0, 1, 1, 1, 1], &[0, 1, 1, 1, 1]) = [0, 11, 3, 1, 1]`, the pairs
+///   in `1..=4` by their gcd
+///
+/// Sums over multiples turn it into a pointwise product, `Σ_{k | m} c[m] = A[k]·B[k]` with
+/// `A[k] = Σ_{k | m} a[m]`, which Möbius inversion over multiples undoes. Each transform
+/// runs prime by prime, O(n log log n). `MathError::DimensionMismatch` if the lengths
+/// differ, `Overflow` if an intermediate sum leaves `i128` or a result `i64`.
+pub fn gcd_convolution(a: &[i64], b: &[i64]) -> Result<Vec<i64>, MathError> {
+    convolve(a, b, Lattice::Multiples)
+}
+
+/// `c[k] = Σ_{lcm(i, j) = k} a[i]·b[j]` over `1 <= i, j <= n`, for `1 <= k <= n`; pairs
+/// whose lcm exceeds `n` are left out:
+/// - `lcm_convolution(&[0, 1, 1, 1, 1], &[0, 1, 1, 1, 1]) = [0, 1, 3, 3, 5]`
+///
+/// The same scheme over divisors: `Σ_{m | k} c[m] = A[k]·B[k]` with `A[k] = Σ_{m | k} a[m]`.
+/// Same errors as `gcd_convolution`.
+pub fn lcm_convolution(a: &[i64], b: &[i64]) -> Result<Vec<i64>, MathError> {
+    convolve(a, b, Lattice::Divisors)
+}
+
+/// Which way the sums run: over the multiples of an index, or over its divisors.
+#[derive(Debug, Clone, Copy)]
+enum Lattice {
+    Multiples,
+    Divisors,
+}
+
+fn convolve(a: &[i64], b: &[i64], lattice: Lattice) -> Result<Vec<i64>, MathError> {
+    if a.len() != b.len() {
+        return Err(MathError::DimensionMismatch);
+    }
+    let Some(n) = a.len().checked_sub(1) else {
+        return Ok(Vec::new());
+    };
+    let primes = primes_up_to(n);
+    let mut a: Vec<i128> = a.iter().map(|&x| x as i128).collect();
+    let mut b: Vec<i128> = b.iter().map(|&x| x as i128).collect();
+    a[0] = 0;
+    b[0] = 0;
+    zeta(&mut a, &primes, lattice)?;
+    zeta(&mut b, &primes, lattice)?;
+    let mut c = a
+        .iter()
+        .zip(&b)
+        .map(|(x, y)| x.checked_mul(*y).ok_or(MathError::Overflow))
+        .collect::<Result<Vec<_>, _>>()?;
+    mobius(&mut c, &primes, lattice)?;
+    c.into_iter()
+        .map(|x| i64::try_from(x).map_err(|_| MathError::Overflow))
+        .collect()
+}
+
+/// In place, `v[k]` becomes the sum of `v` over the multiples or the divisors of `k`,
+/// one prime at a time: after prime `p`, `v[k]` sums over `k·p^e` (or `k/p^e`), so each
+/// pass walks from the indices whose sums are already complete.
+fn zeta(v: &mut [i128], primes: &[u64], lattice: Lattice) -> Result<(), MathError> {
+    let n = v.len() - 1;
+    for &p in primes {
+        let p = p as usize;
+        match lattice {
+            Lattice::Multiples => {
+                for k in (1..=n / p).rev() {
+                    v[k] = v[k].checked_add(v[k * p]).ok_or(MathError::Overflow)?;
+                }
+            }
+            Lattice::Divisors => {
+                for k in 1..=n / p {
+                    v[k * p] = v[k * p].checked_add(v[k]).ok_or(MathError::Overflow)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Inverse of `zeta`, running each prime's pass backwards.
+fn mobius(v: &mut [i128], primes: &[u64], lattice: Lattice) -> Result<(), MathError> {
+    let n = v.len() - 1;
+    for &p in primes {
+        let p = p as usize;
+        match lattice {
+            Lattice::Multiples => {
+                for k in 1..=n / p {
+                    v[k] = v[k].checked_sub(v[k * p]).ok_or(MathError::Overflow)?;
+                }
+            }
+            Lattice::Divisors => {
+                for k in (1..=n / p).rev() {
+                    v[k * p] = v[k * p].checked_sub(v[k]).ok_or(MathError::Overflow)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::gcd::gcd_u128;
+    use alloc::vec;
+
+    fn naive(a: &[i64], b: &[i64], combine: impl Fn(usize, usize) -> usize) -> Vec<i64> {
+        let n = a.len() - 1;
+        let mut c = vec![0; n + 1];
+        for (i, x) in a.iter().enumerate().skip(1) {
+            for (j, y) in b.iter().enumerate().skip(1) {
+                let k = combine(i, j);
+                if k <= n {
+                    c[k] += x * y;
+                }
+            }
+        }
+        c
+    }
+
+    fn gcd(i: usize, j: usize) -> usize {
+        gcd_u128(i as u128, j as u128) as usize
+    }
+
+    fn table(n: usize, seed: i64) -> Vec<i64> {
+        (0..=n as i64).map(|i| (i * seed + 7) % 23 - 11).collect()
+    }
+
+    #[test]
+    fn test_gcd_convolution() {
+        let ones = [0, 1, 1, 1, 1];
+        assert_eq!(gcd_convolution(&ones, &ones), Ok(vec![0, 11, 3, 1, 1]));
+        assert_eq!(gcd_convolution(&[], &[]), Ok(vec![]));
+        assert_eq!(gcd_convolution(&[5], &[3]), Ok(vec![0]));
+        assert_eq!(
+            gcd_convolution(&[0, 1], &[0, 1, 2]),
+            Err(MathError::DimensionMismatch)
+        );
+        for n in [1, 2, 12, 30, 97, 210] {
+            let (a, b) = (table(n, 5), table(n, 13));
+            assert_eq!(gcd_convolution(&a, &b), Ok(naive(&a, &b, gcd)));
+        }
+        // coprime pairs in 1..=n: 2·Σ φ(k) - 1
+        let ones = vec![1; 101];
+        let c = gcd_convolution(&ones, &ones).unwrap();
+        assert_eq!(c[1], 2 * 3044 - 1);
+        let big = [0, i64::MAX, i64::MAX];
+        assert_eq!(gcd_convolution(&big, &big), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_lcm_convolution() {
+        let ones = [0, 1, 1, 1, 1];
+        assert_eq!(lcm_convolution(&ones, &ones), Ok(vec![0, 1, 3, 3, 5]));
+        assert_eq!(
+            lcm_convolution(&[0, 1, 2], &[0, 1]),
+            Err(MathError::DimensionMismatch)
+        );
+        let lcm = |i, j| i / gcd(i, j) * j;
+        for n in [1, 2, 12, 30, 97, 210] {
+            let (a, b) = (table(n, 3), table(n, 17));
+            assert_eq!(lcm_convolution(&a, &b), Ok(naive(&a, &b, lcm)));
+        }
+        // the ordered pairs with lcm(i, j) = k number τ(k²)
+        let ones = vec![1; 61];
+        let c = lcm_convolution(&ones, &ones).unwrap();
+        assert_eq!(c[60], 45); // τ(3600) = 5·3·3
+        assert_eq!(c[1], 1);
+    }
+}
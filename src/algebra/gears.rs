@@ -0,0 +1,219 @@
+use alloc::vec::Vec;
+
+use crate::algebra::gcd::gcd_u128;
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Largest number of gear trains `approximate_ratio_with_factors` will try.
+pub const MAX_GEAR_TRAINS: u128 = 1 << 24;
+
+/// A gear train and the ratio it achieves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GearTrain {
+    /// `(driver, driven)` tooth counts, one pair per stage.
+    pub stages: Vec<(u64, u64)>,
+    /// Product of `driven / driver` over the stages, in lowest terms.
+    pub ratio: Rational,
+    /// Relative error `ratio / target - 1`.
+    pub error: f64,
+}
+
+/// Gear train of at most `stages` stages whose ratio `Π driven/driver` is closest to
+/// `target`, with every gear taken from `available_teeth` (each size may be reused):
+/// - teeth `[20, 30, 45]`, target `9/4` gives `(20, 45)` in one stage
+/// - teeth `[20, 24, 30, 36, 40, 45, 50, 60, 72, 80]`, target `355/113 ≈ π` gives
+///   `25/8` in two stages, 0.53% short
+///
+/// Closeness is relative, `|log(ratio / target)|`, and ties go to fewer stages. The
+/// search tries every multiset of distinct stage ratios, so its size grows like
+/// `teeth^(2·stages)`; it errors with `OutOfRange` past `MAX_GEAR_TRAINS`, as well as
+/// for `stages == 0`, a non-positive `target` or a zero tooth count. `EmptyInput` if no
+/// teeth are given, `Overflow` if the best ratio does not fit in a `Rational`.
+pub fn approximate_ratio_with_factors(
+    target: Rational,
+    available_teeth: &[u64],
+    stages: usize,
+) -> Result<GearTrain, MathError> {
+    if available_teeth.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    if stages == 0 || target.numer() <= 0 || available_teeth.contains(&0) {
+        return Err(MathError::OutOfRange);
+    }
+
+    // one representative pair per reduced stage ratio
+    let mut pairs: Vec<(u64, u64)> = Vec::new();
+    let mut seen: Vec<(u64, u64)> = Vec::new();
+    for &driver in available_teeth {
+        for &driven in available_teeth {
+            let g = gcd_u128(driver as u128, driven as u128) as u64;
+            let reduced = (driver / g, driven / g);
+            if !seen.contains(&reduced) {
+                seen.push(reduced);
+                pairs.push((driver, driven));
+            }
+        }
+    }
+    if train_count(pairs.len() as u128, stages as u128) > MAX_GEAR_TRAINS {
+        return Err(MathError::OutOfRange);
+    }
+
+    let factors: Vec<f64> = pairs
+        .iter()
+        .map(|&(driver, driven)| driven as f64 / driver as f64)
+        .collect();
+    let goal = target.numer() as f64 / target.denom() as f64;
+    let mut search = Search {
+        factors: &factors,
+        goal,
+        chosen: Vec::with_capacity(stages),
+        best: (f64::INFINITY, Vec::new()),
+    };
+    // fewest stages first, so a later train must be strictly better to win
+    for count in 1..=stages {
+        search.extend(0, count, 1.0);
+    }
+
+    let chosen: Vec<(u64, u64)> = search.best.1.iter().map(|&i| pairs[i]).collect();
+    let ratio = train_ratio(&chosen)?;
+    let error = (ratio.numer() as i128 * target.denom() as i128) as f64
+        / (ratio.denom() as i128 * target.numer() as i128) as f64
+        - 1.0;
+    Ok(GearTrain {
+        stages: chosen,
+        ratio,
+        error,
+    })
+}
+
+struct Search<'a> {
+    factors: &'a [f64],
+    goal: f64,
+    chosen: Vec<usize>,
+    best: (f64, Vec<usize>),
+}
+
+impl Search<'_> {
+    /// Tries every non-decreasing choice of `remaining` more stage indices from `from`.
+    ///
+    /// `max(ratio / goal, goal / ratio)` orders the trains as `|log(ratio / goal)|` does,
+    /// without a logarithm.
+    fn extend(&mut self, from: usize, remaining: usize, ratio: f64) {
+        if remaining == 0 {
+            let distance = (ratio / self.goal).max(self.goal / ratio);
+            // tolerance so that rounding does not prefer a longer exact train
+            if distance < self.best.0 * (1.0 - 1e-12) {
+                self.best = (distance, self.chosen.clone());
+            }
+            return;
+        }
+        for i in from..self.factors.len() {
+            self.chosen.push(i);
+            self.extend(i, remaining - 1, ratio * self.factors[i]);
+            self.chosen.pop();
+        }
+    }
+}
+
+/// Number of multisets of 1 to `stages` elements from `n` kinds, saturating.
+fn train_count(n: u128, stages: u128) -> u128 {
+    // C(n + s - 1, s), built incrementally over s
+    let mut term = 1u128;
+    let mut total = 0u128;
+    for s in 1..=stages {
+        term = term.saturating_mul(n + s - 1) / s;
+        total = total.saturating_add(term);
+    }
+    total
+}
+
+fn train_ratio(stages: &[(u64, u64)]) -> Result<Rational, MathError> {
+    let (mut numer, mut denom) = (1u128, 1u128);
+    for &(driver, driven) in stages {
+        numer = numer
+            .checked_mul(driven as u128)
+            .ok_or(MathError::Overflow)?;
+        denom = denom
+            .checked_mul(driver as u128)
+            .ok_or(MathError::Overflow)?;
+        let g = gcd_u128(numer, denom);
+        (numer, denom) = (numer / g, denom / g);
+    }
+    let narrow = |x: u128| i64::try_from(x).map_err(|_| MathError::Overflow);
+    Rational::new(narrow(numer)?, narrow(denom)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn q(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    #[test]
+    fn test_exact_ratio() {
+        let train = approximate_ratio_with_factors(q(9, 4), &[20, 30, 45], 2).unwrap();
+        assert_eq!(train.stages, vec![(20, 45)]);
+        assert_eq!((train.ratio, train.error), (q(9, 4), 0.0));
+
+        // 100/7 needs two stages, e.g. 100/14 · 40/20
+        let train = approximate_ratio_with_factors(q(100, 7), &[14, 20, 40, 100], 3).unwrap();
+        assert_eq!(train.ratio, q(100, 7));
+        assert_eq!(train.stages.len(), 2);
+        assert_eq!(train_ratio(&train.stages), Ok(q(100, 7)));
+    }
+
+    #[test]
+    fn test_approximate_ratio() {
+        let target = q(355, 113);
+        let teeth = [20, 24, 30, 36, 40, 45, 50, 60, 72, 80];
+        let one = approximate_ratio_with_factors(target, &teeth, 1).unwrap();
+        assert_eq!(one.stages, vec![(20, 60)]);
+        let two = approximate_ratio_with_factors(target, &teeth, 2).unwrap();
+        assert_eq!((two.ratio, two.stages.len()), (q(25, 8), 2));
+        assert!((two.error + 0.005_281_690_140_845_07).abs() < 1e-15);
+
+        // brute force over every two-stage train agrees
+        let distance = |r: Rational| {
+            let x = r.numer() as f64 / r.denom() as f64 / (355.0 / 113.0);
+            x.max(1.0 / x)
+        };
+        for &a in &teeth {
+            for &b in &teeth {
+                for &c in &teeth {
+                    for &d in &teeth {
+                        let r = train_ratio(&[(a, b), (c, d)]).unwrap();
+                        assert!(distance(two.ratio) <= distance(r) * (1.0 + 1e-12));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_bounds_and_errors() {
+        let teeth: Vec<u64> = (10..60).collect();
+        assert_eq!(
+            approximate_ratio_with_factors(q(3, 1), &teeth, 4),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            approximate_ratio_with_factors(q(3, 1), &[], 1),
+            Err(MathError::EmptyInput)
+        );
+        assert_eq!(
+            approximate_ratio_with_factors(q(3, 1), &[10, 20], 0),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            approximate_ratio_with_factors(q(-3, 1), &[10, 20], 1),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            approximate_ratio_with_factors(q(3, 1), &[0, 20], 1),
+            Err(MathError::OutOfRange)
+        );
+    }
+}
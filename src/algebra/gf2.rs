@@ -0,0 +1,329 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::errors::MathError;
+
+/// Dense matrix over GF(2), one bit per entry, rows packed into `u64` words.
+///
+/// Addition is XOR and multiplication is AND, so elimination needs no inverses:
+/// useful for parity-check codes, Lights Out style puzzles, and the relation
+/// matrices of factoring methods such as CFRAC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    rows: Vec<Vec<u64>>,
+    cols: usize,
+}
+
+impl BitMatrix {
+    /// All-zero `rows × cols` matrix.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        BitMatrix {
+            rows: vec![vec![0; cols.div_ceil(64)]; rows],
+            cols,
+        }
+    }
+
+    /// Matrix from rows of booleans; `DimensionMismatch` if the rows are ragged.
+    pub fn from_rows(rows: &[Vec<bool>]) -> Result<Self, MathError> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != cols) {
+            return Err(MathError::DimensionMismatch);
+        }
+        let mut matrix = BitMatrix::new(rows.len(), cols);
+        for (r, row) in rows.iter().enumerate() {
+            for (c, &value) in row.iter().enumerate() {
+                matrix.set(r, c, value);
+            }
+        }
+        Ok(matrix)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Entry `(row, col)`; panics if out of bounds, like `Matrix::get`.
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.check_bounds(row, col);
+        bit(&self.rows[row], col)
+    }
+
+    /// Sets entry `(row, col)`; panics if out of bounds, so the padding bits past `cols`
+    /// in the last word, which `rank` and `kernel_basis` assume are 0, stay 0.
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        self.check_bounds(row, col);
+        let word = &mut self.rows[row][col / 64];
+        if value {
+            *word |= 1 << (col % 64);
+        } else {
+            *word &= !(1 << (col % 64));
+        }
+    }
+
+    fn check_bounds(&self, row: usize, col: usize) {
+        assert!(
+            row < self.rows.len() && col < self.cols,
+            "bit matrix index out of bounds"
+        );
+    }
+
+    /// Product `M·x`, one parity per row; `DimensionMismatch` if `x.len() != cols`.
+    ///
+    /// - rows `110, 011` times `101` give `11`
+    pub fn mul_vec(&self, x: &[bool]) -> Result<Vec<bool>, MathError> {
+        if x.len() != self.cols {
+            return Err(MathError::DimensionMismatch);
+        }
+        let mut packed = vec![0u64; self.cols.div_ceil(64)];
+        for (c, &value) in x.iter().enumerate() {
+            packed[c / 64] |= (value as u64) << (c % 64);
+        }
+        Ok(self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&packed)
+                    .fold(0, |acc, (r, x)| acc ^ (r & x).count_ones())
+                    & 1
+                    == 1
+            })
+            .collect())
+    }
+
+    /// Rank over GF(2).
+    pub fn rank(&self) -> usize {
+        echelon(self.rows.clone(), self.cols).1.len()
+    }
+
+    /// Basis of the null space `{x : M·x = 0}`, one vector of length `cols` per free column.
+    ///
+    /// - rows `110, 011` give the single kernel vector `111`
+    pub fn kernel_basis(&self) -> Vec<Vec<bool>> {
+        let (reduced, pivots) = echelon(self.rows.clone(), self.cols);
+        (0..self.cols)
+            .filter(|c| !pivots.contains(c))
+            .map(|free| {
+                let mut x = vec![false; self.cols];
+                x[free] = true;
+                for (row, &pivot) in reduced.iter().zip(&pivots) {
+                    x[pivot] = bit(row, free);
+                }
+                x
+            })
+            .collect()
+    }
+
+    /// One solution of `M·x = b`, with every free variable set to 0.
+    ///
+    /// Adding any combination of `kernel_basis` vectors gives the other solutions.
+    /// Errors with `DimensionMismatch` if `b.len() != rows` and `NoSolution` if the
+    /// system is inconsistent.
+    pub fn solve(&self, b: &[bool]) -> Result<Vec<bool>, MathError> {
+        if b.len() != self.rows.len() {
+            return Err(MathError::DimensionMismatch);
+        }
+        // augmented matrix [M | b], eliminating on the first `cols` columns only
+        let augmented = self
+            .rows
+            .iter()
+            .zip(b)
+            .map(|(row, &value)| {
+                let mut row = row.clone();
+                row.resize((self.cols + 1).div_ceil(64), 0);
+                row[self.cols / 64] |= (value as u64) << (self.cols % 64);
+                row
+            })
+            .collect();
+        let (reduced, pivots) = echelon(augmented, self.cols);
+
+        if reduced[pivots.len()..]
+            .iter()
+            .any(|row| bit(row, self.cols))
+        {
+            return Err(MathError::NoSolution);
+        }
+        let mut x = vec![false; self.cols];
+        for (row, &pivot) in reduced.iter().zip(&pivots) {
+            x[pivot] = bit(row, self.cols);
+        }
+        Ok(x)
+    }
+
+    /// Sets of row indices whose rows XOR to zero, spanning all such dependencies.
+    ///
+    /// Gaussian elimination where every row carries the set of original rows it
+    /// was combined from; a row reduced to zero yields one dependency.
+    pub fn left_kernel(&self) -> Vec<Vec<usize>> {
+        let n = self.rows.len();
+        let mut rows = self.rows.clone();
+        let mut history: Vec<Vec<u64>> = (0..n)
+            .map(|i| {
+                let mut h = vec![0u64; n.div_ceil(64)];
+                h[i / 64] |= 1 << (i % 64);
+                h
+            })
+            .collect();
+
+        let mut used = vec![false; n];
+        for col in 0..self.cols {
+            let (word, bit) = (col / 64, 1u64 << (col % 64));
+            let Some(pivot) = (0..n).find(|&r| !used[r] && rows[r][word] & bit != 0) else {
+                continue;
+            };
+            used[pivot] = true;
+            let (pivot_row, pivot_history) = (rows[pivot].clone(), history[pivot].clone());
+            for r in 0..n {
+                if r != pivot && rows[r][word] & bit != 0 {
+                    xor_into(&mut rows[r], &pivot_row);
+                    xor_into(&mut history[r], &pivot_history);
+                }
+            }
+        }
+
+        (0..n)
+            .filter(|&r| !used[r])
+            .map(|r| {
+                (0..n)
+                    .filter(|&i| history[r][i / 64] >> (i % 64) & 1 == 1)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Reduced row echelon form on the first `cols` columns; returns the rows and pivot columns.
+///
+/// The `i`-th returned row holds the pivot `pivots[i]`; rows past `pivots.len()` are
+/// zero on those columns.
+fn echelon(mut rows: Vec<Vec<u64>>, cols: usize) -> (Vec<Vec<u64>>, Vec<usize>) {
+    let mut pivots = Vec::new();
+    for col in 0..cols {
+        let top = pivots.len();
+        let Some(pivot) = (top..rows.len()).find(|&r| bit(&rows[r], col)) else {
+            continue;
+        };
+        rows.swap(top, pivot);
+        let pivot_row = rows[top].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r != top && bit(row, col) {
+                xor_into(row, &pivot_row);
+            }
+        }
+        pivots.push(col);
+    }
+    (rows, pivots)
+}
+
+fn bit(row: &[u64], col: usize) -> bool {
+    row[col / 64] >> (col % 64) & 1 == 1
+}
+
+fn xor_into(target: &mut [u64], source: &[u64]) {
+    for (t, s) in target.iter_mut().zip(source) {
+        *t ^= s;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_vec() {
+        let m = BitMatrix::from_rows(&[vec![true, true, false], vec![false, true, true]]).unwrap();
+        assert_eq!(m.mul_vec(&[true, false, true]), Ok(vec![true, true]));
+        assert_eq!(m.mul_vec(&[true, true, true]), Ok(vec![false, false]));
+        assert_eq!(m.mul_vec(&[true]), Err(MathError::DimensionMismatch));
+    }
+
+    #[test]
+    fn test_left_kernel_finds_dependencies() {
+        // rows: 110, 011, 101 -> r0 ^ r1 ^ r2 = 0
+        let mut m = BitMatrix::new(3, 3);
+        for (r, c) in [(0, 0), (0, 1), (1, 1), (1, 2), (2, 0), (2, 2)] {
+            m.set(r, c, true);
+        }
+        assert_eq!(m.left_kernel(), vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn test_left_kernel_independent_rows() {
+        let mut m = BitMatrix::new(2, 130);
+        m.set(0, 129, true);
+        m.set(1, 64, true);
+        assert!(m.left_kernel().is_empty());
+        m.set(1, 64, false);
+        assert_eq!(m.left_kernel(), vec![vec![1]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit matrix index out of bounds")]
+    fn test_set_in_padding_panics() {
+        // column 3 is still inside the first word, but past `cols`
+        BitMatrix::new(2, 3).set(0, 3, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit matrix index out of bounds")]
+    fn test_get_out_of_bounds_panics() {
+        BitMatrix::new(2, 3).get(2, 0);
+    }
+
+    fn apply(m: &BitMatrix, x: &[bool]) -> Vec<bool> {
+        (0..m.rows())
+            .map(|r| (0..m.cols()).fold(false, |acc, c| acc ^ (m.get(r, c) & x[c])))
+            .collect()
+    }
+
+    fn bits(s: &str) -> Vec<bool> {
+        s.bytes().map(|b| b == b'1').collect()
+    }
+
+    #[test]
+    fn test_rank_and_kernel() {
+        let m = BitMatrix::from_rows(&[bits("110"), bits("011"), bits("101")]).unwrap();
+        assert_eq!(m.rank(), 2);
+        assert_eq!(m.kernel_basis(), vec![bits("111")]);
+
+        // Hamming(7,4) parity checks: rank 3, 4-dimensional code as kernel
+        let h = BitMatrix::from_rows(&[bits("1010101"), bits("0110011"), bits("0001111")]).unwrap();
+        assert_eq!(h.rank(), 3);
+        let basis = h.kernel_basis();
+        assert_eq!(basis.len(), 4);
+        for x in &basis {
+            assert_eq!(apply(&h, x), vec![false; 3]);
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        // Lights Out on a 2×2 board: pressing a cell toggles its row and column
+        let m = BitMatrix::from_rows(&[bits("1110"), bits("1101"), bits("1011"), bits("0111")])
+            .unwrap();
+        assert_eq!(m.rank(), 4);
+        let target = bits("1000");
+        let x = m.solve(&target).unwrap();
+        assert_eq!(apply(&m, &x), target);
+
+        let wide = BitMatrix::from_rows(&[bits("1100"), bits("0110")]).unwrap();
+        let x = wide.solve(&bits("11")).unwrap();
+        assert_eq!(x, bits("0100"));
+        assert_eq!(apply(&wide, &x), bits("11"));
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        let m = BitMatrix::from_rows(&[bits("11"), bits("11")]).unwrap();
+        assert_eq!(m.solve(&bits("10")), Err(MathError::NoSolution));
+        assert_eq!(m.solve(&bits("1")), Err(MathError::DimensionMismatch));
+        assert_eq!(
+            BitMatrix::from_rows(&[bits("1"), bits("10")]),
+            Err(MathError::DimensionMismatch)
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use crate::algebra::factorization::carmichael;
+use crate::algebra::mod_expression::pow_mod_huge_exponent;
+use crate::algebra::modular::mod_pow;
+use crate::errors::MathError;
+
+/// The hyperoperation `H_level(a, b)`: successor, addition, multiplication,
+/// exponentiation, tetration, and so on, each level iterating the one below:
+/// - `hyperop(2, 3, 4) = 12`, `hyperop(3, 3, 4) = 81`
+/// - `hyperop(4, 3, 3) = 3^3^3 = 7625597484987`
+/// - `hyperop(5, 2, 3) = 2↑↑4 = 65536`
+///
+/// `H_0(a, b) = b + 1`, `H_n(a, 0)` is `a`, `0` and `1` for `n = 1, 2` and `n >= 3`, and
+/// `H_n(a, b) = H_{n-1}(a, H_n(a, b - 1))`; `0^0 = 1`. Values that fit are found in a
+/// few steps whatever `level` and `b`, since the chain overflows fast: every level from
+/// 6 on overflows unless `a <= 1`, `b <= 1` or `a = b = 2`. `MathError::Overflow` if the
+/// value exceeds `u128`; `hyperop_mod` still reduces it.
+pub fn hyperop(level: u32, a: u64, b: u64) -> Result<u128, MathError> {
+    exact(level, a as u128, b as u128).ok_or(MathError::Overflow)
+}
+
+/// The Ackermann–Péter function, `A(m, n) = H_m(2, n + 3) - 3`:
+/// - `ackermann(2, 3) = 9`, `ackermann(3, 3) = 61`
+/// - `ackermann(4, 1) = 65533`
+///
+/// `MathError::Overflow` if the value exceeds `u128`, from `ackermann(4, 2) = 2^65536 - 3`.
+pub fn ackermann(m: u32, n: u64) -> Result<u128, MathError> {
+    exact(m, 2, n as u128 + 3)
+        .map(|h| h - 3)
+        .ok_or(MathError::Overflow)
+}
+
+/// `H_level(a, b) mod m`, however large the exact value:
+/// - `hyperop_mod(4, 3, 10^18, 1000) = 387`, the last digits of any tall tower of 3s
+/// - `hyperop_mod(6, 3, 3, 10^10) = 2464195387`, the last digits of Graham's `g₁`
+///
+/// Levels up to 3 are direct. From tetration on, `a↑↑h mod k = a^(a↑↑(h - 1)) mod k`
+/// needs the exponent only modulo `λ(k)`, then `λ(λ(k))`, as for power towers in
+/// `simplify_mod_expression`, and the chain reaches 1 within about `2·log₂ m` steps; a
+/// value of level 5 or more that does not fit is a tower of `a` too tall for its height
+/// to matter. `MathError::DivisionByZero` if `m == 0`.
+pub fn hyperop_mod(level: u32, a: u64, b: u64, m: u64) -> Result<u64, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    match level {
+        // exact for every u64 input
+        0..=2 => hyperop(level, a, b).map(|x| (x % m as u128) as u64),
+        3 => mod_pow(a, b, m),
+        _ => modular(level, a, Count::Exact(b as u128), m).map(|r| r.residue),
+    }
+}
+
+/// `H_level(a, b)` when it fits in `u128`.
+fn exact(level: u32, a: u128, b: u128) -> Option<u128> {
+    match level {
+        0 => b.checked_add(1),
+        1 => a.checked_add(b),
+        2 => a.checked_mul(b),
+        _ if b == 0 => Some(1),
+        _ if b == 1 || a == 1 => Some(a),
+        // 0↑↑b alternates 1, 0, 1, ..., and so does every level above it
+        _ if a == 0 => Some(u128::from(level > 3 && b.is_multiple_of(2))),
+        3 => a.checked_pow(u32::try_from(b).ok()?),
+        _ if a == 2 && b == 2 => Some(4),
+        // a, b >= 2 and not both 2: H_6(a, b) >= H_6(2, 3) = 2↑↑65536 already
+        4 | 5 => (0..b).try_fold(1, |x, _| exact(level - 1, a, x)),
+        _ => None,
+    }
+}
+
+/// A count that is either known or too large to matter.
+#[derive(Debug, Clone, Copy)]
+enum Count {
+    Exact(u128),
+    Huge,
+}
+
+/// A value modulo some `k`, with the value itself when it fits.
+#[derive(Debug, Clone, Copy)]
+struct Residue {
+    residue: u64,
+    exact: Option<u128>,
+}
+
+/// `H_level(a, b) mod k` for `level >= 4`.
+fn modular(level: u32, a: u64, b: Count, k: u64) -> Result<Residue, MathError> {
+    let Count::Exact(b) = b else {
+        // H_n(a, b) = H_{n-1}(a, H_n(a, b - 1)) with both counts huge, down to a tower
+        return tower(a, Count::Huge, k);
+    };
+    if let Some(x) = exact(level, a as u128, b) {
+        return Ok(Residue {
+            residue: (x % k as u128) as u64,
+            exact: Some(x),
+        });
+    }
+    if level == 4 {
+        return tower(a, Count::Exact(b), k);
+    }
+    // b >= 1, as H_n(a, 0) fits
+    let inner = exact(level, a as u128, b - 1).map_or(Count::Huge, Count::Exact);
+    modular(level - 1, a, inner, k)
+}
+
+/// `a↑↑h mod k`, with `a >= 2` whenever `h` is `Huge`.
+fn tower(a: u64, h: Count, k: u64) -> Result<Residue, MathError> {
+    let below = match h {
+        Count::Exact(h) => match exact(4, a as u128, h) {
+            Some(x) => {
+                return Ok(Residue {
+                    residue: (x % k as u128) as u64,
+                    exact: Some(x),
+                });
+            }
+            // a >= 2 and h >= 1 here, as a↑↑0 and towers of 0 or 1 fit
+            None => Count::Exact(h - 1),
+        },
+        Count::Huge => Count::Huge,
+    };
+    if k == 1 {
+        return Ok(Residue {
+            residue: 0,
+            exact: None,
+        });
+    }
+    let lambda = carmichael(k)? as u64;
+    let e = tower(a, below, lambda)?;
+    let residue = match e.exact.and_then(|x| u64::try_from(x).ok()) {
+        Some(x) => mod_pow(a, x, k)?,
+        None => pow_mod_huge_exponent(a % k, e.residue, lambda, k)?.0,
+    };
+    Ok(Residue {
+        residue,
+        exact: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::mod_expression::simplify_mod_expression;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_hyperop() {
+        let levels: Vec<_> = (0..5).map(|n| hyperop(n, 3, 3).unwrap()).collect();
+        assert_eq!(levels, [4, 6, 9, 27, 7625597484987]);
+        assert_eq!(hyperop(2, 3, 4), Ok(12));
+        assert_eq!(hyperop(3, 3, 4), Ok(81));
+        assert_eq!(hyperop(4, 2, 4), Ok(65536));
+        assert_eq!(hyperop(5, 2, 3), Ok(65536));
+        assert_eq!(hyperop(4, 2, 5), Err(MathError::Overflow));
+        assert_eq!(hyperop(3, 2, 127), Ok(1 << 127));
+        assert_eq!(hyperop(3, 2, 128), Err(MathError::Overflow));
+        assert_eq!(
+            hyperop(2, u64::MAX, u64::MAX),
+            Ok(u64::MAX as u128 * u64::MAX as u128)
+        );
+        for level in [2, 3, 4, 5, 6, 100, u32::MAX] {
+            assert_eq!(hyperop(level, 2, 2), Ok(4));
+            assert_eq!(hyperop(level, 7, 1), Ok(7));
+            assert_eq!(hyperop(level.max(3), 1, u64::MAX), Ok(1));
+        }
+        assert_eq!(hyperop(u32::MAX, 2, 3), Err(MathError::Overflow));
+        assert_eq!(hyperop(6, 3, 2), Err(MathError::Overflow));
+        // 0^0 = 1, and towers of 0 alternate
+        assert_eq!(hyperop(3, 0, 0), Ok(1));
+        assert_eq!(hyperop(3, 0, 5), Ok(0));
+        assert_eq!(hyperop(4, 0, 3), Ok(0));
+        assert_eq!(hyperop(4, 0, 4), Ok(1));
+        assert_eq!(hyperop(7, 0, u64::MAX), Ok(0));
+        // the recursion itself, on what fits
+        for level in 3..7 {
+            for a in 0..5 {
+                for b in 1..5 {
+                    if let Ok(h) = hyperop(level, a, b) {
+                        let inner = hyperop(level, a, b - 1).unwrap();
+                        assert_eq!(exact(level - 1, a as u128, inner), Some(h));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ackermann() {
+        assert_eq!(ackermann(0, 0), Ok(1));
+        assert_eq!(ackermann(1, 2), Ok(4));
+        assert_eq!(ackermann(2, 3), Ok(9));
+        assert_eq!(ackermann(3, 3), Ok(61));
+        assert_eq!(ackermann(4, 0), Ok(13));
+        assert_eq!(ackermann(4, 1), Ok(65533));
+        assert_eq!(ackermann(5, 0), Ok(65533));
+        assert_eq!(ackermann(4, 2), Err(MathError::Overflow));
+        assert_eq!(ackermann(0, u64::MAX), Ok(u64::MAX as u128 + 1));
+        // A(m, n) = A(m - 1, A(m, n - 1))
+        for m in 1..4 {
+            for n in 1..8 {
+                let inner = ackermann(m, n - 1).unwrap() as u64;
+                assert_eq!(ackermann(m, n), ackermann(m - 1, inner));
+            }
+        }
+    }
+
+    #[test]
+    fn test_hyperop_mod() {
+        assert_eq!(hyperop_mod(4, 3, 1_000_000_000_000_000_000, 1000), Ok(387));
+        assert_eq!(hyperop_mod(6, 3, 3, 10_000_000_000), Ok(2464195387));
+        assert_eq!(hyperop_mod(3, 2, 10, 1000), Ok(24));
+        assert_eq!(hyperop_mod(2, u64::MAX, u64::MAX, 1 << 63), Ok(1));
+        assert_eq!(hyperop_mod(4, 2, 3, 0), Err(MathError::DivisionByZero));
+        assert_eq!(hyperop_mod(u32::MAX, 5, 5, 1), Ok(0));
+
+        // against the exact values
+        for level in 0..7 {
+            for a in 0..6 {
+                for b in 0..6 {
+                    let Ok(h) = hyperop(level, a, b) else {
+                        continue;
+                    };
+                    for m in 1..40 {
+                        assert_eq!(hyperop_mod(level, a, b, m), Ok((h % m as u128) as u64));
+                    }
+                }
+            }
+        }
+
+        // against towers written out for simplify_mod_expression
+        for a in 2..8u64 {
+            for height in 1..7 {
+                let tower: Vec<String> = (0..height).map(|_| alloc::format!("{a}")).collect();
+                let tower = tower.join("^");
+                for m in [2, 7, 100, 1000, 1 << 32, 999_999_999_989, u64::MAX] {
+                    assert_eq!(
+                        hyperop_mod(4, a, height, m),
+                        simplify_mod_expression(&tower, m),
+                        "{tower} mod {m}"
+                    );
+                }
+            }
+        }
+
+        // 2↑↑↑4 = 2↑↑65536 = 2↑↑(2↑↑↑3), and a tower that tall is as good as any taller
+        for m in [1000, 1 << 40, 999_999_999_989] {
+            let level5 = hyperop_mod(5, 2, 4, m).unwrap();
+            assert_eq!(hyperop_mod(4, 2, 65536, m), Ok(level5));
+            assert_eq!(hyperop_mod(4, 2, 1000, m), Ok(level5));
+            assert_eq!(hyperop_mod(7, 2, 3, m), Ok(level5));
+        }
+    }
+}
@@ -0,0 +1,418 @@
+use alloc::vec::Vec;
+
+use crate::algebra::matrix::Matrix;
+use crate::algebra::modular::{mod_inverse, mul_mod};
+use crate::algebra::prime_nbr::miller_rabin;
+use crate::algebra::ratio::normalize_ratio;
+use crate::errors::MathError;
+use crate::outcome::{Outcome, measure};
+
+/// Primes for `det_exact`, the largest below 2^62 first.
+const DET_PRIME_START: i64 = (1 << 62) - 1;
+
+/// Basis of the integer null space `{x ∈ Zⁿ : A·x = 0}` of an m×n matrix.
+///
+/// Computed by bringing `[Aᵀ | Iₙ]` to Hermite (row echelon) form with unimodular
+/// row operations (Euclid on each column); the identity part of every row whose
+/// `Aᵀ` part vanished is a kernel vector, and together they span the whole
+/// integer kernel. Intermediate values use checked `i128` arithmetic.
+///
+/// Errors with `EmptyInput` for a matrix without rows or columns, `DimensionMismatch`
+/// for ragged rows and `Overflow` if a basis entry does not fit in `i64`.
+pub fn kernel_basis(matrix: &[Vec<i64>]) -> Result<Vec<Vec<i64>>, MathError> {
+    let cols = matrix.first().map_or(0, Vec::len);
+    if cols == 0 {
+        return Err(MathError::EmptyInput);
+    }
+    if matrix.iter().any(|row| row.len() != cols) {
+        return Err(MathError::DimensionMismatch);
+    }
+    let rows = matrix.len();
+
+    // one augmented row per variable: [column j of A | e_j]
+    let mut aug: Vec<Vec<i128>> = (0..cols)
+        .map(|j| {
+            let mut row: Vec<i128> = matrix.iter().map(|r| r[j] as i128).collect();
+            row.extend((0..cols).map(|k| (k == j) as i128));
+            row
+        })
+        .collect();
+
+    let mut pivot_row = 0;
+    for c in 0..rows {
+        if pivot_row == cols {
+            break;
+        }
+        // smallest nonzero entry in column c at or below pivot_row
+        while let Some(p) = (pivot_row..cols)
+            .filter(|&r| aug[r][c] != 0)
+            .min_by_key(|&r| aug[r][c].unsigned_abs())
+        {
+            aug.swap(pivot_row, p);
+            let (done, below) = aug.split_at_mut(pivot_row + 1);
+            let pivot = &done[pivot_row];
+
+            let mut reduced = true;
+            for row in below.iter_mut().filter(|row| row[c] != 0) {
+                let q = row[c] / pivot[c];
+                for (x, &y) in row.iter_mut().zip(pivot) {
+                    let delta = q.checked_mul(y).ok_or(MathError::Overflow)?;
+                    *x = x.checked_sub(delta).ok_or(MathError::Overflow)?;
+                }
+                reduced &= row[c] == 0;
+            }
+            if reduced {
+                pivot_row += 1;
+                break;
+            }
+        }
+    }
+
+    aug[pivot_row..]
+        .iter()
+        .map(|row| {
+            row[rows..]
+                .iter()
+                .map(|&x| i64::try_from(x).map_err(|_| MathError::Overflow))
+                .collect()
+        })
+        .collect()
+}
+
+/// Smallest strictly positive integer vector `x` with `A·x = 0`.
+///
+/// Balancing a chemical equation is the typical use: one row per element, one
+/// column per species, products entered with negative counts. For
+/// `H₂ + O₂ → H₂O` (rows H, O):
+/// - `balance_ratios(&[vec![2, 0, -2], vec![0, 2, -1]]) = [2, 1, 2]`
+///
+/// Errors with `NoSolution` unless the null space is one-dimensional and spanned
+/// by a vector whose entries are all nonzero with the same sign.
+pub fn balance_ratios(matrix: &[Vec<i64>]) -> Result<Vec<i64>, MathError> {
+    let basis = kernel_basis(matrix)?;
+    let [mut x] = <[Vec<i64>; 1]>::try_from(basis).map_err(|_| MathError::NoSolution)?;
+
+    normalize_ratio(&mut x)?;
+    if x.iter().all(|&v| v < 0) {
+        x.iter_mut().for_each(|v| *v = -*v);
+    }
+    if x.iter().all(|&v| v > 0) {
+        Ok(x)
+    } else {
+        Err(MathError::NoSolution)
+    }
+}
+
+/// Exact determinant of a square integer matrix by fraction-free (Bareiss) elimination.
+///
+/// O(n³) operations on `i128` values that grow like the minors of the matrix. Errors
+/// with `DimensionMismatch` for a non-square matrix and `Overflow` if a minor leaves
+/// the `i128` range.
+pub fn det_bareiss(matrix: &Matrix<i64>) -> Result<i128, MathError> {
+    if !matrix.is_square() {
+        return Err(MathError::DimensionMismatch);
+    }
+    let n = matrix.rows();
+    let mut m: Vec<Vec<i128>> = (0..n)
+        .map(|i| matrix.row(i).iter().map(|&x| x as i128).collect())
+        .collect();
+    let (mut sign, mut prev) = (1, 1i128);
+    for k in 0..n {
+        let Some(p) = (k..n).find(|&r| m[r][k] != 0) else {
+            return Ok(0);
+        };
+        if p != k {
+            m.swap(p, k);
+            sign = -sign;
+        }
+        for i in k + 1..n {
+            for j in k + 1..n {
+                let x = m[i][j]
+                    .checked_mul(m[k][k])
+                    .and_then(|x| x.checked_sub(m[i][k].checked_mul(m[k][j])?))
+                    .ok_or(MathError::Overflow)?;
+                m[i][j] = x / prev;
+            }
+        }
+        prev = m[k][k];
+    }
+    Ok(sign * prev)
+}
+
+/// Exact determinant from determinants modulo several primes, same contract as
+/// `det_bareiss`.
+///
+/// Hadamard's inequality `|det A| <= ∏ ‖rowᵢ‖` tells how many primes `p < 2^62` are
+/// needed for their product to exceed `2·|det A|`; each `det A mod p` costs O(n³)
+/// word operations, and the residues are combined by CRT in mixed radix with
+/// symmetric digits, so no intermediate exceeds the final value. Unlike Bareiss,
+/// the cost does not depend on how large the minors get, which pays off for large
+/// matrices. Errors with `Overflow` if the determinant itself does not fit in `i128`.
+pub fn det_exact(matrix: &Matrix<i64>) -> Result<i128, MathError> {
+    det_multimodular(matrix).map(|(det, _)| det)
+}
+
+/// `det_exact` with its cost: method `"multi-modular"`, one iteration per prime whose
+/// determinant residue was computed (0 for a zero row). Errors as `det_exact`.
+pub fn det_exact_outcome(matrix: &Matrix<i64>) -> Result<Outcome<i128>, MathError> {
+    measure(|| {
+        let (det, primes) = det_multimodular(matrix)?;
+        Ok(Outcome::new(det, "multi-modular", primes))
+    })
+}
+
+/// The determinant and the number of primes used.
+fn det_multimodular(matrix: &Matrix<i64>) -> Result<(i128, u64), MathError> {
+    if !matrix.is_square() {
+        return Err(MathError::DimensionMismatch);
+    }
+    let n = matrix.rows();
+    // twice log2 of the Hadamard bound with a margin, from `|row|² <= n·max²` in whole bits
+    let mut log_bound = 2 * 2;
+    for i in 0..n {
+        let max = matrix
+            .row(i)
+            .iter()
+            .map(|x| x.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        if max == 0 {
+            return Ok((0, 0));
+        }
+        log_bound += n.ilog2() + 1 + 2 * (max.ilog2() + 1);
+    }
+
+    // x ≡ det (mod M), |x| <= M/2; `modulus` is None once M leaves i128
+    let (mut x, mut modulus) = (0i128, Some(1i128));
+    let (mut covered, mut primes) = (0, 0);
+    let mut candidate = DET_PRIME_START;
+    while covered < log_bound {
+        primes += 1;
+        while !miller_rabin(candidate, 32)?.is_prime() {
+            candidate -= 2;
+        }
+        let p = candidate as u64;
+        candidate -= 2;
+
+        let r = det_mod_prime(matrix, p)?;
+        // M mod p and x mod p, then the next digit t = (r - x)/M mod p, symmetric
+        let m_mod = modulus.map_or(0, |m| m.rem_euclid(p as i128) as u64);
+        let x_mod = x.rem_euclid(p as i128) as u64;
+        let diff = (r + p - x_mod) % p;
+        if diff != 0 {
+            let m = modulus.ok_or(MathError::Overflow)?;
+            let t = mul_mod(diff, mod_inverse(m_mod, p)?, p);
+            let t = if t > p / 2 {
+                t as i128 - p as i128
+            } else {
+                t as i128
+            };
+            x = m
+                .checked_mul(t)
+                .and_then(|d| x.checked_add(d))
+                .ok_or(MathError::Overflow)?;
+        }
+        modulus = modulus.and_then(|m| m.checked_mul(p as i128));
+        covered += 2 * p.ilog2();
+    }
+    Ok((x, primes))
+}
+
+/// `det A mod p` by Gaussian elimination over GF(p).
+fn det_mod_prime(matrix: &Matrix<i64>, p: u64) -> Result<u64, MathError> {
+    let n = matrix.rows();
+    let mut m: Vec<Vec<u64>> = (0..n)
+        .map(|i| {
+            matrix
+                .row(i)
+                .iter()
+                .map(|&x| (x as i128).rem_euclid(p as i128) as u64)
+                .collect()
+        })
+        .collect();
+    let mut det = 1;
+    for k in 0..n {
+        let Some(piv) = (k..n).find(|&r| m[r][k] != 0) else {
+            return Ok(0);
+        };
+        if piv != k {
+            m.swap(piv, k);
+            det = p - det;
+        }
+        det = mul_mod(det, m[k][k], p);
+        let inv = mod_inverse(m[k][k], p)?;
+        let (done, below) = m.split_at_mut(k + 1);
+        let pivot_row = &done[k];
+        for row in below.iter_mut() {
+            let factor = mul_mod(row[k], inv, p);
+            for (x, &y) in row[k..].iter_mut().zip(&pivot_row[k..]) {
+                *x = (*x + p - mul_mod(factor, y, p)) % p;
+            }
+        }
+    }
+    Ok(det % p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn apply(matrix: &[Vec<i64>], x: &[i64]) -> Vec<i64> {
+        matrix
+            .iter()
+            .map(|row| row.iter().zip(x).map(|(a, b)| a * b).sum())
+            .collect()
+    }
+
+    #[test]
+    fn test_kernel_basis_is_in_kernel() {
+        let matrix = vec![vec![1, 2, 3, 4], vec![2, 4, 6, 9]];
+        let basis = kernel_basis(&matrix).unwrap();
+        assert_eq!(basis.len(), 2);
+        for v in &basis {
+            assert_eq!(apply(&matrix, v), vec![0, 0]);
+        }
+    }
+
+    #[test]
+    fn test_kernel_basis_full_rank() {
+        let matrix = vec![vec![2, 1], vec![1, 3]];
+        assert_eq!(kernel_basis(&matrix), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_kernel_basis_is_primitive() {
+        // kernel of [6, 10, 15] over Z needs integer (not rational) generators
+        let basis = kernel_basis(&[vec![6, 10, 15]]).unwrap();
+        assert_eq!(basis.len(), 2);
+        for v in &basis {
+            assert_eq!(apply(&[vec![6, 10, 15]], v), vec![0]);
+        }
+    }
+
+    #[test]
+    fn test_kernel_basis_errors() {
+        assert_eq!(kernel_basis(&[]), Err(MathError::EmptyInput));
+        assert_eq!(kernel_basis(&[vec![]]), Err(MathError::EmptyInput));
+        assert_eq!(
+            kernel_basis(&[vec![1, 2], vec![1]]),
+            Err(MathError::DimensionMismatch)
+        );
+    }
+
+    fn int_matrix(rows: &[&[i64]]) -> Matrix<i64> {
+        Matrix::new(rows.iter().map(|row| row.to_vec()).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_determinants_agree() {
+        let cases = [
+            int_matrix(&[&[1, 2], &[3, 4]]),
+            int_matrix(&[&[0, 1, 2], &[1, 0, 3], &[4, -3, 8]]),
+            int_matrix(&[&[1, 2, 3], &[2, 4, 6], &[1, 0, 1]]),
+            int_matrix(&[&[7]]),
+            int_matrix(&[&[0, 0], &[0, 5]]),
+        ];
+        let expected = [-2, -2, 0, 7, 0];
+        for (m, det) in cases.iter().zip(expected) {
+            assert_eq!(det_bareiss(m), Ok(det));
+            assert_eq!(det_exact(m), Ok(det));
+        }
+        let wide = int_matrix(&[&[1, 2, 3]]);
+        assert_eq!(det_exact(&wide), Err(MathError::DimensionMismatch));
+        assert_eq!(det_exact_outcome(&wide), Err(MathError::DimensionMismatch));
+        assert_eq!(det_bareiss(&wide), Err(MathError::DimensionMismatch));
+    }
+
+    #[test]
+    fn test_det_exact_large_entries() {
+        // pseudo-random 12×12 with entries up to 100: the Hadamard bound (about 2^101)
+        // needs two primes, while the Bareiss intermediates overflow
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let rows: Vec<Vec<i64>> = (0..12)
+            .map(|_| {
+                (0..12)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        (state % 201) as i64 - 100
+                    })
+                    .collect()
+            })
+            .collect();
+        let m = Matrix::new(rows).unwrap();
+        assert_eq!(det_bareiss(&m), Err(MathError::Overflow));
+        let det = det_exact(&m).unwrap();
+        assert!(det.unsigned_abs() > 1 << 64);
+        let outcome = det_exact_outcome(&m).unwrap();
+        assert_eq!(
+            (outcome.value, outcome.method, outcome.iterations),
+            (det, "multi-modular", 2)
+        );
+        // checked against primes unrelated to the ones used in the reconstruction
+        for p in [1_000_003u64, 998_244_353, 4_294_967_291] {
+            assert_eq!(
+                det.rem_euclid(p as i128) as u64,
+                det_mod_prime(&m, p).unwrap()
+            );
+        }
+        // the top-left 6×6 block is within reach of Bareiss
+        let block = Matrix::new((0..6).map(|i| m.row(i)[..6].to_vec()).collect()).unwrap();
+        assert_eq!(det_exact(&block), Ok(det_bareiss(&block).unwrap()));
+
+        // (2^63 - 1)³ does not fit in i128: reported, not wrapped
+        let huge = int_matrix(&[&[i64::MAX, 0, 0], &[0, i64::MAX, 0], &[0, 0, i64::MAX]]);
+        assert_eq!(det_exact(&huge), Err(MathError::Overflow));
+        // a large Hadamard bound with a small determinant still succeeds
+        let small = int_matrix(&[&[i64::MAX, i64::MAX - 1], &[i64::MAX - 1, i64::MAX - 2]]);
+        assert_eq!(det_exact(&small), Ok(-1));
+    }
+
+    #[test]
+    fn test_balance_water() {
+        // H2 + O2 -> H2O
+        let matrix = vec![vec![2, 0, -2], vec![0, 2, -1]];
+        assert_eq!(balance_ratios(&matrix), Ok(vec![2, 1, 2]));
+    }
+
+    #[test]
+    fn test_balance_propane_combustion() {
+        // C3H8 + O2 -> CO2 + H2O
+        let matrix = vec![vec![3, 0, -1, 0], vec![8, 0, 0, -2], vec![0, 2, -2, -1]];
+        assert_eq!(balance_ratios(&matrix), Ok(vec![1, 5, 3, 4]));
+    }
+
+    #[test]
+    fn test_balance_permanganate() {
+        // KMnO4 + HCl -> KCl + MnCl2 + H2O + Cl2
+        let matrix = vec![
+            vec![1, 0, -1, 0, 0, 0],   // K
+            vec![1, 0, 0, -1, 0, 0],   // Mn
+            vec![4, 0, 0, 0, -1, 0],   // O
+            vec![0, 1, 0, 0, -2, 0],   // H
+            vec![0, 1, -1, -2, 0, -2], // Cl
+        ];
+        assert_eq!(balance_ratios(&matrix), Ok(vec![2, 16, 2, 2, 8, 5]));
+    }
+
+    #[test]
+    fn test_balance_no_solution() {
+        // only the trivial solution
+        assert_eq!(
+            balance_ratios(&[vec![1, 0], vec![0, 1]]),
+            Err(MathError::NoSolution)
+        );
+        // kernel spanned by (1, -1, 0): no positive vector
+        assert_eq!(
+            balance_ratios(&[vec![1, 1, 0], vec![0, 0, 1]]),
+            Err(MathError::NoSolution)
+        );
+        // two-dimensional kernel: no unique balance
+        assert_eq!(
+            balance_ratios(&[vec![1, 1, -1, -1]]),
+            Err(MathError::NoSolution)
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use crate::errors::MathError;
+
+/// Integer type accepted by the generic arithmetic (`gcd_generic`, `lcm_generic`, `is_prime_generic`, ...).
+///
+/// Implemented for every native signed and unsigned type with overflow checks; a
+/// big-integer type can implement it to reuse the same algorithms. Division truncates
+/// toward zero like the `/` and `%` operators.
+pub trait Integer: Clone + PartialOrd {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn int_mul(&self, other: &Self) -> Result<Self, MathError>;
+    /// `(self / divisor, self % divisor)`; `DivisionByZero` for a zero divisor and
+    /// `Overflow` for `MIN / -1`.
+    fn int_div_rem(&self, divisor: &Self) -> Result<(Self, Self), MathError>;
+    /// `self % divisor`, with the sign of `self`; `DivisionByZero` for a zero divisor.
+    /// Unlike `int_div_rem`, defined at `MIN % -1`, which is 0.
+    fn int_rem(&self, divisor: &Self) -> Result<Self, MathError> {
+        self.int_div_rem(divisor).map(|(_, r)| r)
+    }
+    /// `|self|`; `Overflow` for `MIN`.
+    fn int_abs(&self) -> Result<Self, MathError>;
+    /// `-self`; `Overflow` for `MIN` and for a nonzero unsigned value.
+    fn int_neg(&self) -> Result<Self, MathError>;
+    /// The value as `u128`, `None` if negative or too large.
+    fn to_u128(&self) -> Option<u128>;
+    /// `|self|` as `u128`, `None` if too large; unlike `int_abs`, defined at `MIN`.
+    fn abs_to_u128(&self) -> Option<u128>;
+    /// `value` as `Self`, `None` if it does not fit.
+    fn try_from_u128(value: u128) -> Option<Self>;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        *self < Self::zero()
+    }
+}
+
+macro_rules! impl_integer {
+    ($abs:ident; $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn int_mul(&self, other: &Self) -> Result<Self, MathError> {
+                    self.checked_mul(*other).ok_or(MathError::Overflow)
+                }
+
+                fn int_div_rem(&self, divisor: &Self) -> Result<(Self, Self), MathError> {
+                    if *divisor == 0 {
+                        return Err(MathError::DivisionByZero);
+                    }
+                    let q = self.checked_div(*divisor).ok_or(MathError::Overflow)?;
+                    Ok((q, self.checked_rem(*divisor).ok_or(MathError::Overflow)?))
+                }
+
+                fn int_rem(&self, divisor: &Self) -> Result<Self, MathError> {
+                    if *divisor == 0 {
+                        return Err(MathError::DivisionByZero);
+                    }
+                    // with a nonzero divisor, only MIN % -1 has no checked_rem, and it is 0
+                    Ok(self.checked_rem(*divisor).unwrap_or(0))
+                }
+
+                fn int_abs(&self) -> Result<Self, MathError> {
+                    impl_integer!(@$abs self)
+                }
+
+                fn int_neg(&self) -> Result<Self, MathError> {
+                    self.checked_neg().ok_or(MathError::Overflow)
+                }
+
+                fn to_u128(&self) -> Option<u128> {
+                    u128::try_from(*self).ok()
+                }
+
+                fn abs_to_u128(&self) -> Option<u128> {
+                    Some(impl_integer!(@abs_u128 $abs self))
+                }
+
+                fn try_from_u128(value: u128) -> Option<Self> {
+                    Self::try_from(value).ok()
+                }
+            }
+        )*
+    };
+    (@signed $x:ident) => {
+        $x.checked_abs().ok_or(MathError::Overflow)
+    };
+    (@unsigned $x:ident) => {
+        Ok(*$x)
+    };
+    (@abs_u128 signed $x:ident) => {
+        $x.unsigned_abs() as u128
+    };
+    (@abs_u128 unsigned $x:ident) => {
+        *$x as u128
+    };
+}
+
+impl_integer!(signed; i8, i16, i32, i64, i128, isize);
+impl_integer!(unsigned; u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_div_rem_truncates() {
+        assert_eq!(7i64.int_div_rem(&2), Ok((3, 1)));
+        assert_eq!((-7i64).int_div_rem(&2), Ok((-3, -1)));
+        assert_eq!(7i32.int_div_rem(&-2), Ok((-3, 1)));
+        assert_eq!(u128::MAX.int_div_rem(&u128::MAX), Ok((1, 0)));
+        assert_eq!(5u8.int_div_rem(&0), Err(MathError::DivisionByZero));
+        assert_eq!(i64::MIN.int_div_rem(&-1), Err(MathError::Overflow));
+        assert_eq!(i64::MIN.int_rem(&-1), Ok(0));
+        assert_eq!((-7i64).int_rem(&2), Ok(-1));
+        assert_eq!(i8::MIN.int_rem(&i8::MAX), Ok(-1));
+        assert_eq!(3u16.int_rem(&0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_checked_operations() {
+        assert_eq!(i16::MIN.int_abs(), Err(MathError::Overflow));
+        assert_eq!((-5i128).int_abs(), Ok(5));
+        assert_eq!(200u8.int_abs(), Ok(200));
+        assert_eq!((-5i8).int_neg(), Ok(5));
+        assert_eq!(i32::MIN.int_neg(), Err(MathError::Overflow));
+        assert_eq!(0u64.int_neg(), Ok(0));
+        assert_eq!(3u64.int_neg(), Err(MathError::Overflow));
+        assert_eq!(16u8.int_mul(&16), Err(MathError::Overflow));
+        assert_eq!((-3i64).to_u128(), None);
+        assert_eq!(usize::MAX.to_u128(), Some(usize::MAX as u128));
+        assert_eq!(i64::MIN.abs_to_u128(), Some(1 << 63));
+        assert_eq!(u128::MAX.abs_to_u128(), Some(u128::MAX));
+        assert_eq!(i8::try_from_u128(127), Some(127));
+        assert_eq!(i8::try_from_u128(128), None);
+        assert!((-1i8).is_negative() && !0u32.is_negative());
+        assert!(0i64.is_zero() && !u64::one().is_zero());
+    }
+}
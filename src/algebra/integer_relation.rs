@@ -0,0 +1,377 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::errors::MathError;
+
+/// Scale of the weighted column: the values are rounded to integers of about this size.
+const SCALE: f64 = 1e12;
+
+/// Largest `|Σ aᵢ·xᵢ|` accepted, relative to `max |aᵢ·xᵢ|`.
+const RELATIVE_TOLERANCE: f64 = 1e-9;
+
+/// Lovász constant of the reduction.
+const DELTA: f64 = 0.99;
+
+/// A nonzero integer vector `a` with `Σ aᵢ·valuesᵢ ≈ 0` and every `|aᵢ| <= max_coeff`, or
+/// `None` if LLL finds none:
+/// - `find_integer_relation(&[φ², φ, 1], 10) = Some([1, -1, -1])`
+/// - `find_integer_relation(&[π/4, atan(1/5), atan(1/239)], 10) = Some([1, -4, 1])`
+///   (Machin)
+/// - `find_integer_relation(&[1, ∛2, ∛4, 2], 10) = Some([2, 0, 0, -1])`, hence `2 - x³`
+///   for `x = ∛2` when the values are its powers
+///
+/// LLL reduces the lattice spanned by the rows `(eᵢ, round(C·valuesᵢ))` with `C` about
+/// `10^12 / max |valuesᵢ|`: a small relation gives a short vector whose last entry is
+/// within rounding of 0, while any other vector there is long. Every reduced row is
+/// checked against the unscaled values, and the one with the smallest coefficients
+/// wins, its first nonzero coefficient made positive. A relation must hold to `10^-9`
+/// relative to its largest term; that is far above `f64` rounding but, with `n` values,
+/// chance near-relations reach it once `max_coeff^(n-1)` nears `10^9`, so a hit there
+/// is a conjecture to confirm at higher precision. Floating-point Gram-Schmidt and
+/// O(n⁴) work per pass suit a few dozen values. Errors with `OutOfRange` for fewer than
+/// two values, a non-finite value or `max_coeff == 0`.
+pub fn find_integer_relation(
+    values: &[f64],
+    max_coeff: u64,
+) -> Result<Option<Vec<i64>>, MathError> {
+    let n = values.len();
+    if n < 2 || max_coeff == 0 || values.iter().any(|v| !v.is_finite()) {
+        return Err(MathError::OutOfRange);
+    }
+    let largest = values.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+    if largest == 0.0 {
+        return Ok(Some(relation_from(&unit(n, 0))));
+    }
+    let scale = SCALE / largest;
+    let mut basis: Vec<Vec<i128>> = (0..n)
+        .map(|i| {
+            let mut row = unit(n, i);
+            row.push((values[i] * scale).round() as i128);
+            row
+        })
+        .collect();
+    lll(&mut basis)?;
+
+    let holds = |a: &[i128]| {
+        let terms: Vec<f64> = a.iter().zip(values).map(|(&c, &v)| c as f64 * v).collect();
+        let sum: f64 = terms.iter().sum();
+        let biggest = terms.iter().fold(0.0f64, |m, t| m.max(t.abs()));
+        sum.abs() <= RELATIVE_TOLERANCE * biggest
+    };
+    Ok(basis
+        .iter()
+        .map(|row| &row[..n])
+        .filter(|a| a.iter().all(|c| c.unsigned_abs() <= max_coeff as u128) && holds(a))
+        .min_by_key(|a| a.iter().map(|c| c.unsigned_abs()).max())
+        .map(relation_from))
+}
+
+/// Largest `max_den` accepted by `simultaneous_approximation`.
+const MAX_DENOMINATOR: u64 = 1 << 32;
+
+/// A denominator `q <= max_den` making `q·x` and `q·y` both nearly integers, with those
+/// integers: `(q, p_x, p_y)` minimizing `max(|q·x - p_x|, |q·y - p_y|)` over the candidates
+/// below, the smallest `q` among ties:
+/// - `simultaneous_approximation(√2, √3, 100) = (41, 58, 71)`, errors `0.017` and
+///   `0.014`, where Dirichlet promises `100^(-1/2)`
+/// - `simultaneous_approximation(0.5, 0.25, 10) = (4, 2, 1)`, exact
+///
+/// With `x` and `y` the cycles of two periodic processes per cycle of a third, `q`
+/// cycles of the third bring both within that error of whole cycles.
+///
+/// LLL reduces the rows `(w, S·x, S·y)`, `(0, S, 0)` and `(0, 0, S)`, in which
+/// `q·row₀ - p_x·row₁ - p_y·row₂ = (q·w, S·(q·x - p_x), S·(q·y - p_y))`; the weight
+/// `w = S·max_den^(-3/2)` balances a `q` near `max_den` against errors near its
+/// Dirichlet bound. The candidates are the small combinations of the reduced rows plus
+/// the convergent denominators of `x` and of `y`, so the optimum is found in practice
+/// but not certified, and errors below about `max_den·2^-53` are beyond `f64`. Errors
+/// with `OutOfRange` for a non-finite value or `max_den` outside `1..=2^32`, `Overflow`
+/// if `p_x` or `p_y` leaves `i64`.
+pub fn simultaneous_approximation(
+    x: f64,
+    y: f64,
+    max_den: u64,
+) -> Result<(u64, i64, i64), MathError> {
+    if !x.is_finite() || !y.is_finite() || !(1..=MAX_DENOMINATOR).contains(&max_den) {
+        return Err(MathError::OutOfRange);
+    }
+    // only the fractional parts matter, and they keep q·x exact enough
+    let (fx, fy) = (x - x.floor(), y - y.floor());
+    let scale = 2f64.powi(52);
+    let w = ((scale / (max_den as f64).powf(1.5)).round() as i128).max(1);
+    let s = scale as i128;
+    let mut basis = vec![
+        vec![
+            w,
+            (fx * scale).round() as i128,
+            (fy * scale).round() as i128,
+        ],
+        vec![0, s, 0],
+        vec![0, 0, s],
+    ];
+    lll(&mut basis)?;
+
+    let mut candidates: Vec<u64> = Vec::new();
+    for c0 in -2i128..=2 {
+        for c1 in -2i128..=2 {
+            for c2 in -2i128..=2 {
+                let q = (c0 * basis[0][0] + c1 * basis[1][0] + c2 * basis[2][0]).unsigned_abs()
+                    / w as u128;
+                candidates.push(q.min(u64::MAX as u128) as u64);
+            }
+        }
+    }
+    candidates.extend(convergent_denominators(fx, max_den));
+    candidates.extend(convergent_denominators(fy, max_den));
+    candidates.push(1);
+
+    let error = |q: u64| {
+        let (ex, ey) = (q as f64 * fx, q as f64 * fy);
+        (ex - ex.round()).abs().max((ey - ey.round()).abs())
+    };
+    let q = candidates
+        .into_iter()
+        .filter(|q| (1..=max_den).contains(q))
+        .min_by(|&a, &b| error(a).total_cmp(&error(b)).then(a.cmp(&b)))
+        .unwrap_or(1);
+    let nearest = |v: f64, f: f64| {
+        let p = (q as f64 * f).round() as i128 + q as i128 * v.floor() as i128;
+        i64::try_from(p).map_err(|_| MathError::Overflow)
+    };
+    Ok((q, nearest(x, fx)?, nearest(y, fy)?))
+}
+
+/// The denominators up to `max_den` of the convergents of `f`, expanded in `f64`.
+fn convergent_denominators(f: f64, max_den: u64) -> Vec<u64> {
+    let (mut prev, mut current) = (0u64, 1u64);
+    let mut denominators = Vec::new();
+    let mut rest = f;
+    while rest.fract() != 0.0 {
+        rest = 1.0 / rest.fract();
+        let a = rest.floor();
+        if a >= max_den as f64 {
+            break;
+        }
+        let Some(next) = (a as u64)
+            .checked_mul(current)
+            .and_then(|t| t.checked_add(prev))
+        else {
+            break;
+        };
+        if next > max_den {
+            break;
+        }
+        (prev, current) = (current, next);
+        denominators.push(next);
+    }
+    denominators
+}
+
+fn unit(n: usize, i: usize) -> Vec<i128> {
+    (0..n).map(|j| (i == j) as i128).collect()
+}
+
+/// The coefficients as `i64`, first nonzero one positive; they are at most `max_coeff`.
+fn relation_from(a: &[i128]) -> Vec<i64> {
+    let sign = match a.iter().find(|&&c| c != 0) {
+        Some(&c) if c < 0 => -1,
+        _ => 1,
+    };
+    a.iter().map(|&c| (sign * c) as i64).collect()
+}
+
+/// Lenstra-Lenstra-Lovász reduction of the rows of `basis`, exact on the integer rows
+/// with the Gram-Schmidt data in `f64`. `Overflow` if a row leaves `i128`.
+fn lll(basis: &mut [Vec<i128>]) -> Result<(), MathError> {
+    let n = basis.len();
+    let (mut mu, mut norms) = gram_schmidt(basis);
+    let mut k = 1;
+    while k < n {
+        for j in (0..k).rev() {
+            let q = mu[k][j].round();
+            if q == 0.0 {
+                continue;
+            }
+            let qi = q as i128;
+            let (done, rest) = basis.split_at_mut(k);
+            for (x, &y) in rest[0].iter_mut().zip(&done[j]) {
+                *x = qi
+                    .checked_mul(y)
+                    .and_then(|t| x.checked_sub(t))
+                    .ok_or(MathError::Overflow)?;
+            }
+            let (above, below) = mu.split_at_mut(k);
+            for (x, &y) in below[0][..j].iter_mut().zip(&above[j][..j]) {
+                *x -= q * y;
+            }
+            below[0][j] -= q;
+        }
+        if norms[k] >= (DELTA - mu[k][k - 1] * mu[k][k - 1]) * norms[k - 1] {
+            k += 1;
+        } else {
+            basis.swap(k, k - 1);
+            (mu, norms) = gram_schmidt(basis);
+            k = (k - 1).max(1);
+        }
+    }
+    Ok(())
+}
+
+/// `μᵢⱼ = ⟨bᵢ, b*ⱼ⟩ / ⟨b*ⱼ, b*ⱼ⟩` and the squared norms `⟨b*ᵢ, b*ᵢ⟩`.
+fn gram_schmidt(basis: &[Vec<i128>]) -> (Vec<Vec<f64>>, Vec<f64>) {
+    let n = basis.len();
+    let dot = |x: &[f64], y: &[f64]| x.iter().zip(y).map(|(a, b)| a * b).sum::<f64>();
+    let rows: Vec<Vec<f64>> = basis
+        .iter()
+        .map(|row| row.iter().map(|&x| x as f64).collect())
+        .collect();
+    let mut ortho: Vec<Vec<f64>> = Vec::with_capacity(n);
+    let mut mu = vec![vec![0.0; n]; n];
+    let mut norms = Vec::with_capacity(n);
+    for i in 0..n {
+        let mut v = rows[i].clone();
+        for j in 0..i {
+            mu[i][j] = dot(&rows[i], &ortho[j]) / norms[j];
+            for (x, y) in v.iter_mut().zip(&ortho[j]) {
+                *x -= mu[i][j] * y;
+            }
+        }
+        norms.push(dot(&v, &v));
+        ortho.push(v);
+    }
+    (mu, norms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classical_relations() {
+        let phi = (1.0 + 5f64.sqrt()) / 2.0;
+        assert_eq!(
+            find_integer_relation(&[phi * phi, phi, 1.0], 10),
+            Ok(Some(vec![1, -1, -1]))
+        );
+        let machin = [
+            core::f64::consts::FRAC_PI_4,
+            (0.2f64).atan(),
+            (1.0f64 / 239.0).atan(),
+        ];
+        assert_eq!(find_integer_relation(&machin, 10), Ok(Some(vec![1, -4, 1])));
+        let logs = [2f64.ln(), 3f64.ln(), 6f64.ln(), 5f64.ln()];
+        assert_eq!(
+            find_integer_relation(&logs, 10),
+            Ok(Some(vec![1, 1, -1, 0]))
+        );
+    }
+
+    #[test]
+    fn test_minimal_polynomial() {
+        // powers of an algebraic number reveal its minimal polynomial
+        let x = 2f64.cbrt();
+        let powers: Vec<f64> = (0..4).map(|k| x.powi(k)).collect();
+        assert_eq!(
+            find_integer_relation(&powers, 10),
+            Ok(Some(vec![2, 0, 0, -1]))
+        );
+        let y = 2f64.sqrt() + 3f64.sqrt();
+        let powers: Vec<f64> = (0..5).map(|k| y.powi(k)).collect();
+        assert_eq!(
+            find_integer_relation(&powers, 20),
+            Ok(Some(vec![1, 0, -10, 0, 1]))
+        );
+    }
+
+    #[test]
+    fn test_no_relation() {
+        use core::f64::consts::{E, PI};
+        assert_eq!(find_integer_relation(&[PI, E, 1.0], 50), Ok(None));
+        assert_eq!(find_integer_relation(&[1.0, 2f64.sqrt()], 1000), Ok(None));
+        // the relation exists but exceeds the coefficient bound
+        assert_eq!(find_integer_relation(&[1.0, 0.25], 3), Ok(None));
+        assert_eq!(
+            find_integer_relation(&[1.0, 0.25], 4),
+            Ok(Some(vec![1, -4]))
+        );
+    }
+
+    #[test]
+    fn test_errors_and_degenerate_inputs() {
+        assert_eq!(
+            find_integer_relation(&[1.0], 10),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            find_integer_relation(&[1.0, f64::NAN], 10),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            find_integer_relation(&[1.0, 2.0], 0),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(find_integer_relation(&[0.0, 0.0], 5), Ok(Some(vec![1, 0])));
+        assert_eq!(find_integer_relation(&[3.0, 0.0], 5), Ok(Some(vec![0, 1])));
+    }
+
+    #[test]
+    fn test_simultaneous_approximation() {
+        let (r2, r3) = (2f64.sqrt(), 3f64.sqrt());
+        assert_eq!(simultaneous_approximation(r2, r3, 100), Ok((41, 58, 71)));
+        assert_eq!(simultaneous_approximation(0.5, 0.25, 10), Ok((4, 2, 1)));
+        assert_eq!(simultaneous_approximation(-0.5, 7.25, 10), Ok((4, -2, 29)));
+        assert_eq!(simultaneous_approximation(r2, 3.0, 1), Ok((1, 1, 3)));
+        assert_eq!(
+            simultaneous_approximation(1e30, 0.5, 10),
+            Err(MathError::Overflow)
+        );
+        // with y an integer, the best approximations of x itself: 355/113 for π
+        let pi = core::f64::consts::PI;
+        assert_eq!(simultaneous_approximation(pi, 0.0, 200), Ok((113, 355, 0)));
+        assert_eq!(
+            simultaneous_approximation(f64::NAN, 0.5, 10),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            simultaneous_approximation(0.5, 0.5, 0),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            simultaneous_approximation(0.5, 0.5, (1 << 32) + 1),
+            Err(MathError::OutOfRange)
+        );
+        // Dirichlet's bound holds at the top of the range
+        let (q, px, py) = simultaneous_approximation(r2, r3, 1 << 32).unwrap();
+        let bound = 1.0 / 65536.0;
+        assert!(q <= 1 << 32);
+        assert!((q as f64 * r2 - px as f64).abs() <= bound);
+        assert!((q as f64 * r3 - py as f64).abs() <= bound);
+    }
+
+    #[test]
+    fn test_simultaneous_approximation_against_brute_force() {
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        };
+        for max_den in [1, 10, 100, 1000, 10_000] {
+            for _ in 0..40 {
+                let (x, y) = (next(), next());
+                let error = |q: u64| {
+                    let (ex, ey) = (q as f64 * x, q as f64 * y);
+                    (ex - ex.round()).abs().max((ey - ey.round()).abs())
+                };
+                let best = (1..=max_den)
+                    .min_by(|&a, &b| error(a).total_cmp(&error(b)).then(a.cmp(&b)))
+                    .unwrap();
+                let (q, px, py) = simultaneous_approximation(x, y, max_den).unwrap();
+                assert_eq!(q, best, "{x} {y} {max_den}");
+                assert_eq!(px, (q as f64 * x).round() as i64);
+                assert_eq!(py, (q as f64 * y).round() as i64);
+            }
+        }
+    }
+}
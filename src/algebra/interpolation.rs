@@ -0,0 +1,160 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::modular::{mod_inverse, mul_mod};
+use crate::algebra::poly_mod::PolyMod;
+use crate::algebra::polynomial::{Field, Polynomial};
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Lagrange interpolation over any field: the unique polynomial of degree `< n`
+/// through `n` points with distinct `x`.
+///
+/// Builds `M(x) = ∏ (x - xᵢ)` once, then each basis polynomial `M(x) / (x - xᵢ)` by
+/// exact division and weights it by `yᵢ / ∏_{j≠i} (xᵢ - xⱼ)`; O(n²) field operations.
+///
+/// Errors with `EmptyInput` for no points and `DuplicateInput` if two points share an `x`.
+pub fn interpolate<T: Field>(points: &[(T, T)]) -> Result<Polynomial<T>, MathError> {
+    if points.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    for (i, (x, _)) in points.iter().enumerate() {
+        if points[..i].iter().any(|(other, _)| other == x) {
+            return Err(MathError::DuplicateInput);
+        }
+    }
+
+    let mut master = Polynomial::constant(T::one());
+    for (x, _) in points {
+        master = master.checked_mul(&Polynomial::linear_root(x)?)?;
+    }
+
+    let mut result = Polynomial::zero();
+    for (i, (xi, yi)) in points.iter().enumerate() {
+        let (basis, _) = master.div_rem(&Polynomial::linear_root(xi)?)?;
+        let mut denom = T::one();
+        for (j, (xj, _)) in points.iter().enumerate() {
+            if j != i {
+                denom = denom.checked_mul(&xi.checked_sub(xj)?)?;
+            }
+        }
+        result = result.checked_add(&basis.scale(&yi.checked_div(&denom)?)?)?;
+    }
+    Ok(result)
+}
+
+/// Exact interpolating polynomial with rational coefficients:
+/// - through `(0, 1), (1, 3), (2, 7)` it is `x² + x + 1`
+///
+/// See `interpolate` for the method and errors; coefficients may overflow `i64`
+/// (`MathError::Overflow`) for many points with large coordinates.
+pub fn interpolate_rational(
+    points: &[(Rational, Rational)],
+) -> Result<Polynomial<Rational>, MathError> {
+    interpolate(points)
+}
+
+/// Interpolating polynomial over GF(p) for points `(xᵢ, yᵢ)` taken mod `p`.
+///
+/// `p` must be prime. Errors with `OutOfRange` if `p < 2`, `EmptyInput` for no points
+/// and `DuplicateInput` if two `x` coincide mod `p`.
+pub fn interpolate_mod(points: &[(u64, u64)], p: u64) -> Result<PolyMod, MathError> {
+    if p < 2 {
+        return Err(MathError::OutOfRange);
+    }
+    if points.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    let xs: Vec<u64> = points.iter().map(|&(x, _)| x % p).collect();
+    for (i, x) in xs.iter().enumerate() {
+        if xs[..i].contains(x) {
+            return Err(MathError::DuplicateInput);
+        }
+    }
+
+    let linear = |x: u64| PolyMod::new(vec![(p - x) % p, 1], p);
+    let mut master = PolyMod::new(vec![1], p)?;
+    for &x in &xs {
+        master = master.mul(&linear(x)?)?;
+    }
+
+    let mut result = PolyMod::zero(p)?;
+    for (i, (&xi, &(_, yi))) in xs.iter().zip(points).enumerate() {
+        let (basis, _) = master.div_rem(&linear(xi)?)?;
+        let denom = xs
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .fold(1, |acc, (_, &xj)| {
+                let diff = if xi >= xj { xi - xj } else { p - (xj - xi) };
+                mul_mod(acc, diff, p)
+            });
+        let weight = mul_mod(yi % p, mod_inverse(denom, p)?, p);
+        result = result.add(&basis.scale(weight))?;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q(n: i64) -> Rational {
+        Rational::from_integer(n)
+    }
+
+    #[test]
+    fn test_interpolate_rational() {
+        let points = [(q(0), q(1)), (q(1), q(3)), (q(2), q(7))];
+        let poly = interpolate_rational(&points).unwrap();
+        assert_eq!(poly, Polynomial::new(vec![q(1), q(1), q(1)]));
+
+        // non-integer coefficients: through (0, 0), (2, 1) the line is x/2
+        let half = interpolate_rational(&[(q(0), q(0)), (q(2), q(1))]).unwrap();
+        assert_eq!(
+            half.coeffs(),
+            &[Rational::ZERO, Rational::new(1, 2).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_interpolation_reproduces_points() {
+        let points: Vec<(Rational, Rational)> = [(-3, 5), (-1, 0), (2, 2), (4, -7), (7, 1)]
+            .iter()
+            .map(|&(x, y)| (q(x), q(y)))
+            .collect();
+        let poly = interpolate_rational(&points).unwrap();
+        assert!(poly.degree() <= Some(4));
+        for (x, y) in &points {
+            assert_eq!(poly.eval(x).as_ref(), Ok(y));
+        }
+    }
+
+    #[test]
+    fn test_interpolate_mod() {
+        let p = 1_000_000_007;
+        let points = [(1, 5), (2, 11), (3, 19), (10, 7)];
+        let poly = interpolate_mod(&points, p).unwrap();
+        assert!(poly.degree() <= Some(3));
+        for &(x, y) in &points {
+            assert_eq!(poly.eval(x), y);
+        }
+        // x² + 1 over GF(5)
+        let poly = interpolate_mod(&[(0, 1), (1, 2), (2, 0)], 5).unwrap();
+        assert_eq!(poly.coeffs(), &[1, 0, 1]);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            interpolate_rational(&[(q(1), q(2)), (q(1), q(3))]),
+            Err(MathError::DuplicateInput)
+        );
+        assert_eq!(
+            interpolate_mod(&[(1, 2), (8, 3)], 7),
+            Err(MathError::DuplicateInput)
+        );
+        assert_eq!(interpolate_mod(&[], 7), Err(MathError::EmptyInput));
+        assert_eq!(interpolate_rational(&[]), Err(MathError::EmptyInput));
+    }
+}
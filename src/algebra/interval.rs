@@ -0,0 +1,326 @@
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Closed interval `[lo, hi]` of rationals, for certified enclosures.
+///
+/// Arithmetic is exact whenever the result endpoints fit in a `Rational`. When they do
+/// not, each endpoint is rounded outward to the closest continued-fraction convergent
+/// that fits, so the result still encloses every `x ∘ y` with `x`, `y` in the operands:
+/// - `[1, 2] + [1/3, 1/2] = [4/3, 5/2]`
+/// - `[-1, 2] · [3, 4] = [-4, 8]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RationalInterval {
+    lo: Rational,
+    hi: Rational,
+}
+
+impl RationalInterval {
+    /// Errors with `OutOfRange` if `lo > hi`.
+    pub fn new(lo: Rational, hi: Rational) -> Result<Self, MathError> {
+        if !le(&lo, &hi) {
+            return Err(MathError::OutOfRange);
+        }
+        Ok(RationalInterval { lo, hi })
+    }
+
+    /// The degenerate interval `[x, x]`.
+    pub fn point(x: Rational) -> Self {
+        RationalInterval { lo: x, hi: x }
+    }
+
+    pub fn lo(&self) -> Rational {
+        self.lo
+    }
+
+    pub fn hi(&self) -> Rational {
+        self.hi
+    }
+
+    pub fn is_point(&self) -> bool {
+        self.lo == self.hi
+    }
+
+    /// `hi - lo`, rounded up if it does not fit.
+    pub fn width(&self) -> Result<Rational, MathError> {
+        let (a, b) = (parts(&self.hi), parts(&self.lo));
+        round_outward(a.0 * b.1 - b.0 * a.1, a.1 * b.1, true)
+    }
+
+    pub fn contains(&self, x: &Rational) -> bool {
+        le(&self.lo, x) && le(x, &self.hi)
+    }
+
+    /// `other ⊆ self`.
+    pub fn contains_interval(&self, other: &RationalInterval) -> bool {
+        le(&self.lo, &other.lo) && le(&other.hi, &self.hi)
+    }
+
+    pub fn overlaps(&self, other: &RationalInterval) -> bool {
+        le(&self.lo, &other.hi) && le(&other.lo, &self.hi)
+    }
+
+    /// Smallest interval containing both.
+    pub fn hull(&self, other: &RationalInterval) -> RationalInterval {
+        RationalInterval {
+            lo: if le(&self.lo, &other.lo) {
+                self.lo
+            } else {
+                other.lo
+            },
+            hi: if le(&self.hi, &other.hi) {
+                other.hi
+            } else {
+                self.hi
+            },
+        }
+    }
+
+    /// `Overflow` only if an endpoint leaves the `i64` range.
+    pub fn checked_add(&self, other: &RationalInterval) -> Result<RationalInterval, MathError> {
+        let sum = |x: &Rational, y: &Rational, up| {
+            let (a, b) = (parts(x), parts(y));
+            round_outward(a.0 * b.1 + b.0 * a.1, a.1 * b.1, up)
+        };
+        Ok(RationalInterval {
+            lo: sum(&self.lo, &other.lo, false)?,
+            hi: sum(&self.hi, &other.hi, true)?,
+        })
+    }
+
+    pub fn checked_neg(&self) -> Result<RationalInterval, MathError> {
+        Ok(RationalInterval {
+            lo: self.hi.checked_neg()?,
+            hi: self.lo.checked_neg()?,
+        })
+    }
+
+    pub fn checked_sub(&self, other: &RationalInterval) -> Result<RationalInterval, MathError> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    /// Hull of the four endpoint products, each rounded outward.
+    pub fn checked_mul(&self, other: &RationalInterval) -> Result<RationalInterval, MathError> {
+        let product = |x: &Rational, y: &Rational, up: bool| {
+            let (a, b) = (parts(x), parts(y));
+            round_outward(a.0 * b.0, a.1 * b.1, up)
+        };
+        let mut lo = product(&self.lo, &other.lo, false)?;
+        let mut hi = product(&self.lo, &other.lo, true)?;
+        for (x, y) in [
+            (self.lo, other.hi),
+            (self.hi, other.lo),
+            (self.hi, other.hi),
+        ] {
+            let down = product(&x, &y, false)?;
+            if !le(&lo, &down) {
+                lo = down;
+            }
+            let up = product(&x, &y, true)?;
+            if !le(&up, &hi) {
+                hi = up;
+            }
+        }
+        Ok(RationalInterval { lo, hi })
+    }
+
+    /// `[1/hi, 1/lo]`; `DivisionByZero` if the interval contains 0.
+    pub fn recip(&self) -> Result<RationalInterval, MathError> {
+        if self.contains(&Rational::ZERO) {
+            return Err(MathError::DivisionByZero);
+        }
+        Ok(RationalInterval {
+            lo: self.hi.recip()?,
+            hi: self.lo.recip()?,
+        })
+    }
+
+    /// `DivisionByZero` if `other` contains 0.
+    pub fn checked_div(&self, other: &RationalInterval) -> Result<RationalInterval, MathError> {
+        self.checked_mul(&other.recip()?)
+    }
+}
+
+impl From<Rational> for RationalInterval {
+    fn from(x: Rational) -> RationalInterval {
+        RationalInterval::point(x)
+    }
+}
+
+/// `numer / denom`, or the closest bound on the requested side that fits in a `Rational`.
+///
+/// The convergents `pₖ/qₖ` of a continued fraction alternate around its value (even ones
+/// below, odd ones above), and the best one-sided approximations with a bounded
+/// denominator are the semiconvergents `(t·pₖ + pₖ₋₁) / (t·qₖ + qₖ₋₁)`, `t < aₖ₊₁`, which lie
+/// on the side of `pₖ₋₁/qₖ₋₁`. So the expansion runs until the next convergent no longer
+/// fits in `i64`, and the answer is the last fitting convergent on the right side or the
+/// largest fitting semiconvergent, whichever is closer. Errors with `Overflow` only if
+/// `⌊x⌋` or `⌈x⌉` does not fit. `denom` must be positive.
+fn round_outward(numer: i128, denom: i128, up: bool) -> Result<Rational, MathError> {
+    let narrow = |x: i128| i64::try_from(x).map_err(|_| MathError::Overflow);
+    let fits = |x: i128| i64::try_from(x).is_ok();
+    let a0 = numer.div_euclid(denom);
+    let (mut n, mut d) = (denom, numer.rem_euclid(denom));
+    let mut best = Rational::from_integer(narrow(if up && d != 0 { a0 + 1 } else { a0 })?);
+
+    // convergents k-1 and k, k even ⇔ below
+    let (mut p_prev, mut q_prev) = (1i128, 0i128);
+    let (mut p, mut q) = (a0, 1i128);
+    let mut below = true;
+    loop {
+        if d == 0 {
+            // the expansion ended: p/q is exact
+            return Rational::new(narrow(p)?, narrow(q)?);
+        }
+        if below != up {
+            best = Rational::new(narrow(p)?, narrow(q)?)?;
+        }
+        let a = n / d;
+        (n, d) = (d, n % d);
+        let next_p = a.checked_mul(p).and_then(|x| x.checked_add(p_prev));
+        let next_q = a.checked_mul(q).and_then(|x| x.checked_add(q_prev));
+        match (next_p, next_q) {
+            (Some(np), Some(nq)) if fits(np) && fits(nq) => {
+                (p_prev, q_prev, p, q) = (p, q, np, nq);
+                below = !below;
+            }
+            _ => {
+                // semiconvergents sit on the side of convergent k-1
+                if below == up {
+                    let limit = i64::MAX as i128;
+                    let t_q = (limit - q_prev) / q;
+                    let t_p = if p == 0 {
+                        a
+                    } else {
+                        (limit - p_prev.abs()) / p.abs()
+                    };
+                    let t = a.min(t_q).min(t_p);
+                    if t >= 1 {
+                        best = Rational::new(narrow(t * p + p_prev)?, narrow(t * q + q_prev)?)?;
+                    }
+                }
+                return Ok(best);
+            }
+        }
+    }
+}
+
+fn parts(x: &Rational) -> (i128, i128) {
+    (x.numer() as i128, x.denom() as i128)
+}
+
+fn le(x: &Rational, y: &Rational) -> bool {
+    let (a, b) = (parts(x), parts(y));
+    a.0 * b.1 <= b.0 * a.1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    fn iv(lo: (i64, i64), hi: (i64, i64)) -> RationalInterval {
+        RationalInterval::new(r(lo.0, lo.1), r(hi.0, hi.1)).unwrap()
+    }
+
+    #[test]
+    fn test_exact_arithmetic() {
+        let a = iv((1, 1), (2, 1));
+        let b = iv((1, 3), (1, 2));
+        assert_eq!(a.checked_add(&b), Ok(iv((4, 3), (5, 2))));
+        assert_eq!(a.checked_sub(&b), Ok(iv((1, 2), (5, 3))));
+        assert_eq!(
+            iv((-1, 1), (2, 1)).checked_mul(&iv((3, 1), (4, 1))),
+            Ok(iv((-4, 1), (8, 1)))
+        );
+        assert_eq!(a.checked_div(&b), Ok(iv((2, 1), (6, 1))));
+        assert_eq!(
+            a.checked_div(&iv((-1, 1), (1, 1))),
+            Err(MathError::DivisionByZero)
+        );
+        assert_eq!(b.width(), Ok(r(1, 6)));
+    }
+
+    #[test]
+    fn test_containment() {
+        let a = iv((-1, 2), (3, 4));
+        assert!(a.contains(&r(3, 4)) && a.contains(&Rational::ZERO));
+        assert!(!a.contains(&r(4, 5)));
+        assert!(a.contains_interval(&iv((0, 1), (1, 2))));
+        assert!(!a.contains_interval(&iv((0, 1), (1, 1))));
+        assert!(a.overlaps(&iv((3, 4), (2, 1))));
+        assert!(!a.overlaps(&iv((4, 5), (2, 1))));
+        assert_eq!(a.hull(&iv((1, 1), (2, 1))), iv((-1, 2), (2, 1)));
+        assert!(RationalInterval::point(r(1, 3)).is_point());
+        assert_eq!(
+            RationalInterval::new(r(1, 2), r(1, 3)),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_enclosure_of_sampled_points() {
+        let a = iv((-3, 2), (5, 7));
+        let b = iv((2, 9), (11, 4));
+        let samples = |i: &RationalInterval| {
+            let mid = i.lo().checked_add(&i.hi()).unwrap().checked_div(&r(2, 1));
+            [i.lo(), mid.unwrap(), i.hi()]
+        };
+        for x in samples(&a) {
+            for y in samples(&b) {
+                assert!(
+                    a.checked_add(&b)
+                        .unwrap()
+                        .contains(&x.checked_add(&y).unwrap())
+                );
+                assert!(
+                    a.checked_sub(&b)
+                        .unwrap()
+                        .contains(&x.checked_sub(&y).unwrap())
+                );
+                assert!(
+                    a.checked_mul(&b)
+                        .unwrap()
+                        .contains(&x.checked_mul(&y).unwrap())
+                );
+                assert!(
+                    a.checked_div(&b)
+                        .unwrap()
+                        .contains(&x.checked_div(&y).unwrap())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_outward_rounding() {
+        // 1/4_000_000_000 + 1/4_000_000_001 has a denominator beyond i64
+        let (p, q) = (4_000_000_000i64, 4_000_000_001i64);
+        let x = RationalInterval::point(r(1, p));
+        let y = RationalInterval::point(r(1, q));
+        assert_eq!(r(1, p).checked_add(&r(1, q)), Err(MathError::Overflow));
+        let sum = x.checked_add(&y).unwrap();
+        assert!(!sum.is_point());
+
+        // lo ≤ (p + q) / (p·q) ≤ hi, checked by cross-multiplication in i128
+        let (num, den) = ((p + q) as i128, p as i128 * q as i128);
+        let (lo, hi) = (parts(&sum.lo()), parts(&sum.hi()));
+        assert!(lo.0 * den <= num * lo.1);
+        assert!(num * hi.1 <= hi.0 * den);
+        // and the enclosure is tight
+        assert!(le(&sum.width().unwrap(), &r(1, 1 << 60)));
+
+        // the same enclosure mirrored for the negated sum
+        let neg = x.checked_neg().unwrap().checked_sub(&y).unwrap();
+        assert_eq!(neg, sum.checked_neg().unwrap());
+        assert_eq!(round_outward(-7, 3, false), Ok(r(-7, 3)));
+
+        let big = RationalInterval::point(Rational::from_integer(i64::MAX));
+        assert_eq!(
+            big.checked_add(&RationalInterval::point(Rational::ONE)),
+            Err(MathError::Overflow)
+        );
+    }
+}
@@ -1,31 +1,130 @@
-use crate::algebra::gcd::gcd_abs;
+use crate::algebra::gcd::{gcd_abs_generic, gcd_u64, gcd_u128};
+use crate::algebra::integer::Integer;
 use crate::errors::MathError;
 
+/// Least common multiple of non-negative operands:
+/// - `lcm(12, 18) = 36`
+/// - `lcm(0, n) = 0`
+///
+/// `lcm_i64` narrowed to `i64`: returns `MathError::PositifIntegerRequired` for a
+/// negative operand and `Overflow` if the result exceeds `i64::MAX`. A `const fn`;
+/// `lcm_generic` takes any `Integer` but cannot be const.
+pub const fn lcm(a: i64, b: i64) -> Result<i64, MathError> {
+    match lcm_i64(a, b) {
+        Ok(l) if l <= i64::MAX as u64 => Ok(l as i64),
+        Ok(_) => Err(MathError::Overflow),
+        Err(e) => Err(e),
+    }
+}
 
-// 
-pub fn lcm(a: i64, b: i64) -> Result<i64, MathError> {
-    if a < 0 || b < 0 {
+/// `lcm` for any `Integer`:
+/// - `lcm_generic(1u128 << 100, 3) = 3 << 100`
+///
+/// Operands up to `u64` go through `lcm_u64` and up to `u128` through `lcm_u128`, wider
+/// ones divide by `gcd_abs_generic`. `Overflow` if the result does not fit in `T`.
+pub fn lcm_generic<T: Integer>(a: T, b: T) -> Result<T, MathError> {
+    if a.is_negative() || b.is_negative() {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    if let Some((x, y)) = a.to_u128().zip(b.to_u128()) {
+        let l = match (u64::try_from(x), u64::try_from(y)) {
+            (Ok(x), Ok(y)) => u128::from(lcm_u64(x, y)?),
+            _ => lcm_u128(x, y)?,
+        };
+        return T::try_from_u128(l).ok_or(MathError::Overflow);
+    }
+
+    if a.is_zero() || b.is_zero() {
+        return Ok(T::zero());
+    }
+
+    let gcd_val = gcd_abs_generic(a.clone(), b.clone())?;
+
+    let (a_reduced, _) = a.int_div_rem(&gcd_val)?;
+
+    a_reduced.int_mul(&b)
+}
+
+/// Least common multiple of a whole list of non-negative operands:
+/// - `lcm_many(&[4, 6, 10]) = 60`
+/// - `lcm_many(&[3, 0, 5]) = 0`
+///
+/// Folds `lcm_generic`, which divides by the gcd before multiplying: every running value divides
+/// the final result, so `Overflow` is returned only when the result itself does not fit
+/// in `T`, unlike the naive `a·b / gcd(a, b)`. Errors with `EmptyInput` for an empty
+/// list and `PositifIntegerRequired` for a negative operand.
+pub fn lcm_many<T: Integer>(values: &[T]) -> Result<T, MathError> {
+    if values.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    if values.iter().any(Integer::is_negative) {
         return Err(MathError::PositifIntegerRequired);
     }
+    if values.iter().any(Integer::is_zero) {
+        return Ok(T::zero());
+    }
+    values
+        .iter()
+        .try_fold(T::one(), |acc, v| lcm_generic(acc, v.clone()))
+}
 
+/// Least common multiple of unsigned 128-bit operands, built on the binary `gcd_u128`.
+///
+/// `lcm_u128(0, n) = 0`; returns `MathError::Overflow` if the result exceeds `u128::MAX`.
+/// Unlike `lcm_generic`, a `const fn`.
+pub const fn lcm_u128(a: u128, b: u128) -> Result<u128, MathError> {
     if a == 0 || b == 0 {
         return Ok(0);
     }
 
-    let gcd_val = gcd_abs(a, b)?;
+    // the gcd of a nonzero a is nonzero
+    let Some(a_reduced) = a.checked_div(gcd_u128(a, b)) else {
+        return Err(MathError::DivisionByZero);
+    };
+
+    match a_reduced.checked_mul(b) {
+        Some(l) => Ok(l),
+        None => Err(MathError::Overflow),
+    }
+}
 
-    let a_reduced = a / gcd_val;
+/// Least common multiple of `u64` operands, the core that `lcm_i64` wraps:
+/// - `lcm_u64(12, 18) = 36`
+/// - `lcm_u64(0, n) = 0`
+///
+/// `MathError::Overflow` if the result exceeds `u64::MAX`; a `const fn`.
+pub const fn lcm_u64(a: u64, b: u64) -> Result<u64, MathError> {
+    if a == 0 || b == 0 {
+        return Ok(0);
+    }
 
+    let Some(a_reduced) = a.checked_div(gcd_u64(a, b)) else {
+        return Err(MathError::DivisionByZero);
+    };
     match a_reduced.checked_mul(b) {
-        Some(result) => Ok(result),
+        Some(l) => Ok(l),
         None => Err(MathError::Overflow),
     }
 }
 
+/// `lcm_u64` of non-negative `i64` operands:
+/// - `lcm_i64(4, 6) = 12`
+///
+/// Returns `MathError::PositifIntegerRequired` for a negative operand, like `lcm`, and
+/// `Overflow` past `u64::MAX` (not past `i64::MAX`).
+pub const fn lcm_i64(a: i64, b: i64) -> Result<u64, MathError> {
+    if a < 0 || b < 0 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    lcm_u64(a as u64, b as u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algebra::gcd::gcd_abs;
     use crate::errors::MathError;
+    use alloc::vec::Vec;
 
     // Tests de base - cas normaux
     #[test]
@@ -171,7 +270,7 @@ mod tests {
     fn test_lcm_specific_regressions() {
         // Cas qui ont pu poser problème dans le passé
         assert_eq!(lcm(60, 168), Ok(840));
-        assert_eq!(lcm(132, 88), Ok(1452));
+        assert_eq!(lcm(132, 88), Ok(264));
         assert_eq!(lcm(48, 72), Ok(144));
 
         // Test avec facteurs premiers multiples
@@ -182,6 +281,7 @@ mod tests {
     #[test]
     #[ignore = "test de performance - exécuter manuellement"]
     fn test_lcm_performance() {
+        use std::println;
         use std::time::Instant;
 
         let start = Instant::now();
@@ -259,4 +359,96 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_lcm_u128() {
+        assert_eq!(lcm_u128(12, 18), Ok(36));
+        assert_eq!(lcm_u128(0, 42), Ok(0));
+        assert_eq!(lcm_u128(42, 0), Ok(0));
+        assert_eq!(lcm_u128(1, u128::MAX), Ok(u128::MAX));
+        assert_eq!(lcm_u128(360, 504), Ok(2520));
+
+        // beyond i64: 2^61 - 1 and 2^31 - 1 are both prime
+        let m61 = (1u128 << 61) - 1;
+        let m31 = (1u128 << 31) - 1;
+        assert_eq!(lcm_u128(m61, m31), Ok(m61 * m31));
+    }
+
+    #[test]
+    fn test_lcm_u128_overflow() {
+        assert_eq!(lcm_u128(u128::MAX, u128::MAX - 1), Err(MathError::Overflow));
+        assert_eq!(lcm_u128(1 << 127, 3), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_lcm_u64_and_i64() {
+        assert_eq!(lcm_u64(12, 18), Ok(36));
+        assert_eq!(lcm_u64(0, 42), Ok(0));
+        assert_eq!(lcm_u64(1, u64::MAX), Ok(u64::MAX));
+        assert_eq!(lcm_u64(u64::MAX, u64::MAX - 1), Err(MathError::Overflow));
+        assert_eq!(lcm_i64(4, 6), Ok(12));
+        assert_eq!(lcm_i64(i64::MAX, 2), Ok(i64::MAX as u64 * 2));
+        assert_eq!(lcm_i64(-4, 6), Err(MathError::PositifIntegerRequired));
+        assert_eq!(lcm_i64(4, -6), Err(MathError::PositifIntegerRequired));
+        for a in 0i64..=50 {
+            for b in 0i64..=50 {
+                assert_eq!(lcm_i64(a, b).map(i64::try_from), Ok(Ok(lcm(a, b).unwrap())));
+            }
+        }
+    }
+
+    #[test]
+    fn test_lcm_u128_in_const() {
+        const L: Result<u128, MathError> = lcm_u128(360, 504);
+        assert_eq!(L, Ok(2520));
+        const SIGNED: Result<i64, MathError> = lcm(12, 18);
+        const NEGATIVE: Result<i64, MathError> = lcm(-12, 18);
+        assert_eq!(SIGNED, Ok(36));
+        assert_eq!(NEGATIVE, Err(MathError::PositifIntegerRequired));
+    }
+
+    #[test]
+    fn test_lcm_u128_same_as_lcm() {
+        for (a, b) in [(12, 18), (7, 11), (100, 150), (1_234_567, 2_345_678)] {
+            assert_eq!(
+                lcm_u128(a as u128, b as u128).unwrap(),
+                lcm(a, b).unwrap() as u128
+            );
+        }
+    }
+
+    #[test]
+    fn test_lcm_generic_types() {
+        assert_eq!(lcm_generic(12u64, 18), Ok(36));
+        assert_eq!(lcm_generic(1u128 << 100, 3), Ok(3 << 100));
+        assert_eq!(
+            lcm_generic(-4i128, 6),
+            Err(MathError::PositifIntegerRequired)
+        );
+        // 15 · 17 = 255 fits in u8, 16 · 17 does not
+        assert_eq!(lcm_generic(15u8, 17), Ok(255));
+        assert_eq!(lcm_generic(16u8, 17), Err(MathError::Overflow));
+        assert_eq!(lcm_generic(360i64, 504), lcm(360, 504));
+    }
+
+    #[test]
+    fn test_lcm_many() {
+        assert_eq!(lcm_many(&[4, 6, 10]), Ok(60));
+        assert_eq!(lcm_many(&[7]), Ok(7));
+        assert_eq!(lcm_many(&[3, 0, 5]), Ok(0));
+        assert_eq!(lcm_many(&(1..=20).collect::<Vec<i64>>()), Ok(232_792_560));
+        // lcm(1..=42) fits in i64 even though the product of the operands does not
+        assert_eq!(
+            lcm_many(&(1..=42).collect::<Vec<i64>>()),
+            Ok(219_060_189_739_591_200)
+        );
+        assert_eq!(
+            lcm_many(&(1..=43).collect::<Vec<i64>>()),
+            Err(MathError::Overflow)
+        );
+        assert_eq!(lcm_many(&[200u8, 100, 50]), Ok(200));
+        assert_eq!(lcm_many(&[4, -6]), Err(MathError::PositifIntegerRequired));
+        assert_eq!(lcm_many(&[0, -6]), Err(MathError::PositifIntegerRequired));
+        assert_eq!(lcm_many::<u32>(&[]), Err(MathError::EmptyInput));
+    }
 }
@@ -0,0 +1,369 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "internal-bigint")]
+use crate::algebra::big_rational::BigRational;
+use crate::algebra::polynomial::{Field, Ring};
+#[cfg(feature = "internal-bigint")]
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Dense matrix over a `Ring`, stored row-major; always at least 1×1.
+///
+/// Over a `Field` (such as `Rational`) it offers exact determinant, rank and inverse by
+/// Gauss-Jordan elimination. With `Rational` entries every step is checked, and with
+/// the `internal-bigint` feature the `*_escalating` methods redo an overflowing
+/// computation with `BigRational`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T: Ring> Matrix<T> {
+    /// Errors with `EmptyInput` without rows or columns and `DimensionMismatch` for
+    /// ragged rows.
+    pub fn new(rows: Vec<Vec<T>>) -> Result<Self, MathError> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if cols == 0 {
+            return Err(MathError::EmptyInput);
+        }
+        if rows.iter().any(|row| row.len() != cols) {
+            return Err(MathError::DimensionMismatch);
+        }
+        Ok(Matrix {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// The n×n identity; `EmptyInput` for `n == 0`.
+    pub fn identity(n: usize) -> Result<Self, MathError> {
+        if n == 0 {
+            return Err(MathError::EmptyInput);
+        }
+        let data = (0..n * n)
+            .map(|k| if k / n == k % n { T::one() } else { T::zero() })
+            .collect();
+        Ok(Matrix {
+            rows: n,
+            cols: n,
+            data,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// Entry `(i, j)`; panics if out of bounds, like slice indexing.
+    pub fn get(&self, i: usize, j: usize) -> &T {
+        assert!(i < self.rows && j < self.cols, "matrix index out of bounds");
+        &self.data[i * self.cols + j]
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn transpose(&self) -> Matrix<T> {
+        let data = (0..self.cols)
+            .flat_map(|j| (0..self.rows).map(move |i| (i, j)))
+            .map(|(i, j)| self.get(i, j).clone())
+            .collect();
+        Matrix {
+            rows: self.cols,
+            cols: self.rows,
+            data,
+        }
+    }
+
+    /// Errors with `DimensionMismatch` unless the shapes are equal.
+    pub fn checked_add(&self, other: &Matrix<T>) -> Result<Matrix<T>, MathError> {
+        if (self.rows, self.cols) != (other.rows, other.cols) {
+            return Err(MathError::DimensionMismatch);
+        }
+        let data = self
+            .data
+            .iter()
+            .zip(&other.data)
+            .map(|(a, b)| a.checked_add(b))
+            .collect::<Result<_, _>>()?;
+        Ok(Matrix { data, ..*self })
+    }
+
+    /// Errors with `DimensionMismatch` unless `self.cols() == other.rows()`.
+    pub fn checked_mul(&self, other: &Matrix<T>) -> Result<Matrix<T>, MathError> {
+        if self.cols != other.rows {
+            return Err(MathError::DimensionMismatch);
+        }
+        let mut data = Vec::with_capacity(self.rows * other.cols);
+        for i in 0..self.rows {
+            for j in 0..other.cols {
+                let entry = (0..self.cols).try_fold(T::zero(), |acc, k| {
+                    acc.checked_add(&self.get(i, k).checked_mul(other.get(k, j))?)
+                })?;
+                data.push(entry);
+            }
+        }
+        Ok(Matrix {
+            rows: self.rows,
+            cols: other.cols,
+            data,
+        })
+    }
+
+    /// Entry-wise conversion, e.g. `Rational` to `BigRational`.
+    pub fn map<U: Ring>(&self, f: impl Fn(&T) -> U) -> Matrix<U> {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Field> Matrix<T> {
+    /// Determinant by elimination; `DimensionMismatch` for a non-square matrix.
+    ///
+    /// - `det [[1, 2], [3, 4]] = -2`
+    pub fn determinant(&self) -> Result<T, MathError> {
+        if !self.is_square() {
+            return Err(MathError::DimensionMismatch);
+        }
+        let mut m = self.clone();
+        let (pivots, swaps) = m.reduce(None)?;
+        if pivots.len() < self.rows {
+            return Ok(T::zero());
+        }
+        // each pivot row was divided by its pivot, and each swap flipped the sign
+        let det = pivots
+            .iter()
+            .try_fold(T::one(), |acc, (_, scale)| acc.checked_mul(scale))?;
+        if swaps % 2 == 1 {
+            T::zero().checked_sub(&det)
+        } else {
+            Ok(det)
+        }
+    }
+
+    /// Number of linearly independent rows.
+    pub fn rank(&self) -> Result<usize, MathError> {
+        let mut m = self.clone();
+        Ok(m.reduce(None)?.0.len())
+    }
+
+    /// Exact inverse by Gauss-Jordan on `[A | I]`.
+    ///
+    /// Errors with `DimensionMismatch` for a non-square matrix and `NoSolution` if it is
+    /// singular.
+    pub fn inverse(&self) -> Result<Matrix<T>, MathError> {
+        if !self.is_square() {
+            return Err(MathError::DimensionMismatch);
+        }
+        let n = self.rows;
+        let mut inv = Matrix::identity(n)?;
+        let mut m = self.clone();
+        let (pivots, _) = m.reduce(Some(&mut inv))?;
+        if pivots.len() < n {
+            return Err(MathError::NoSolution);
+        }
+        Ok(inv)
+    }
+
+    /// Reduced row echelon form in place, mirroring every row operation on `companion`.
+    ///
+    /// Returns the pivots as `(column, value before scaling)` and the number of swaps.
+    fn reduce(
+        &mut self,
+        mut companion: Option<&mut Matrix<T>>,
+    ) -> Result<(Vec<(usize, T)>, usize), MathError> {
+        let mut pivots = Vec::new();
+        let mut swaps = 0;
+        for c in 0..self.cols {
+            let r = pivots.len();
+            if r == self.rows {
+                break;
+            }
+            let Some(p) = (r..self.rows).find(|&i| !self.get(i, c).is_zero()) else {
+                continue;
+            };
+            if p != r {
+                self.swap_rows(p, r);
+                if let Some(m) = companion.as_deref_mut() {
+                    m.swap_rows(p, r);
+                }
+                swaps += 1;
+            }
+            let pivot = self.get(r, c).clone();
+            self.scale_row(r, &pivot)?;
+            if let Some(m) = companion.as_deref_mut() {
+                m.scale_row(r, &pivot)?;
+            }
+            for i in (0..self.rows).filter(|&i| i != r) {
+                let factor = self.get(i, c).clone();
+                if factor.is_zero() {
+                    continue;
+                }
+                self.sub_row_multiple(i, r, &factor)?;
+                if let Some(m) = companion.as_deref_mut() {
+                    m.sub_row_multiple(i, r, &factor)?;
+                }
+            }
+            pivots.push((c, pivot));
+        }
+        Ok((pivots, swaps))
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for j in 0..self.cols {
+            self.data.swap(a * self.cols + j, b * self.cols + j);
+        }
+    }
+
+    /// Row `r` divided by `pivot`.
+    fn scale_row(&mut self, r: usize, pivot: &T) -> Result<(), MathError> {
+        for x in &mut self.data[r * self.cols..(r + 1) * self.cols] {
+            *x = x.checked_div(pivot)?;
+        }
+        Ok(())
+    }
+
+    /// Row `i` minus `factor` times row `r`.
+    fn sub_row_multiple(&mut self, i: usize, r: usize, factor: &T) -> Result<(), MathError> {
+        for j in 0..self.cols {
+            let delta = factor.checked_mul(&self.data[r * self.cols + j])?;
+            let x = &mut self.data[i * self.cols + j];
+            *x = x.checked_sub(&delta)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "internal-bigint")]
+impl Matrix<Rational> {
+    /// Determinant, recomputed with `BigRational` if `Rational` overflows.
+    pub fn determinant_escalating(&self) -> Result<BigRational, MathError> {
+        match self.determinant() {
+            Err(MathError::Overflow) => self.map(|&x| BigRational::from(x)).determinant(),
+            result => result.map(BigRational::from),
+        }
+    }
+
+    /// Inverse, recomputed with `BigRational` if `Rational` overflows.
+    pub fn inverse_escalating(&self) -> Result<Matrix<BigRational>, MathError> {
+        match self.inverse() {
+            Err(MathError::Overflow) => self.map(|&x| BigRational::from(x)).inverse(),
+            result => result.map(|inv| inv.map(|&x| BigRational::from(x))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::rational::Rational;
+    use alloc::vec;
+
+    fn q(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    fn ints(rows: &[&[i64]]) -> Matrix<Rational> {
+        Matrix::new(
+            rows.iter()
+                .map(|row| row.iter().map(|&x| Rational::from_integer(x)).collect())
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn hilbert(n: usize) -> Matrix<Rational> {
+        Matrix::new(
+            (0..n)
+                .map(|i| (0..n).map(|j| q(1, (i + j + 1) as i64)).collect())
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_construction_and_products() {
+        let a = ints(&[&[1, 2, 3], &[4, 5, 6]]);
+        assert_eq!((a.rows(), a.cols()), (2, 3));
+        assert_eq!(a.transpose().row(2), &[q(3, 1), q(6, 1)]);
+        let aat = a.checked_mul(&a.transpose()).unwrap();
+        assert_eq!(aat, ints(&[&[14, 32], &[32, 77]]));
+        assert_eq!(a.checked_mul(&a), Err(MathError::DimensionMismatch));
+        assert_eq!(a.checked_add(&a), Ok(ints(&[&[2, 4, 6], &[8, 10, 12]])));
+        assert_eq!(
+            Matrix::<Rational>::new(vec![vec![q(1, 1)], vec![]]),
+            Err(MathError::DimensionMismatch)
+        );
+        assert_eq!(Matrix::<Rational>::new(vec![]), Err(MathError::EmptyInput));
+    }
+
+    #[test]
+    fn test_determinant_and_rank() {
+        assert_eq!(ints(&[&[1, 2], &[3, 4]]).determinant(), Ok(q(-2, 1)));
+        assert_eq!(
+            ints(&[&[0, 1, 2], &[1, 0, 3], &[4, -3, 8]]).determinant(),
+            Ok(q(-2, 1))
+        );
+        let singular = ints(&[&[1, 2, 3], &[2, 4, 6], &[1, 0, 1]]);
+        assert_eq!(singular.determinant(), Ok(Rational::ZERO));
+        assert_eq!(singular.rank(), Ok(2));
+        assert_eq!(singular.inverse(), Err(MathError::NoSolution));
+        assert_eq!(ints(&[&[1, 2, 3]]).rank(), Ok(1));
+        assert_eq!(
+            ints(&[&[1, 2, 3]]).determinant(),
+            Err(MathError::DimensionMismatch)
+        );
+        // det H₄ = 1/6048000
+        assert_eq!(hilbert(4).determinant(), Ok(q(1, 6_048_000)));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let a = ints(&[&[2, 1, 0], &[1, 3, 1], &[0, 1, 4]]);
+        let inv = a.inverse().unwrap();
+        assert_eq!(a.checked_mul(&inv), Matrix::identity(3));
+        // H₃⁻¹ has integer entries
+        assert_eq!(
+            hilbert(3).inverse(),
+            Ok(ints(&[&[9, -36, 30], &[-36, 192, -180], &[30, -180, 180]]))
+        );
+    }
+
+    #[cfg(feature = "internal-bigint")]
+    #[test]
+    fn test_escalation_to_big_rationals() {
+        let h = hilbert(20);
+        assert_eq!(h.inverse(), Err(MathError::Overflow));
+        let inv = h.inverse_escalating().unwrap();
+        let big = h.map(|&x| BigRational::from(x));
+        assert_eq!(big.checked_mul(&inv), Matrix::identity(20));
+        // H₂₀⁻¹ is an integer matrix; its corner entry is 400
+        assert_eq!(inv.get(0, 0).to_rational(), Some(q(400, 1)));
+        let det = h.determinant_escalating().unwrap();
+        // det H · det H⁻¹ = 1
+        assert_eq!(
+            det.checked_mul(&inv.determinant().unwrap()),
+            Ok(BigRational::from(Rational::ONE))
+        );
+        // small matrices stay on the fast path
+        assert_eq!(
+            ints(&[&[1, 2], &[3, 4]]).determinant_escalating(),
+            Ok(BigRational::from(q(-2, 1)))
+        );
+    }
+}
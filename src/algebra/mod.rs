@@ -1,3 +1,112 @@
+// `checked`, `gcd`, `integer` and `lcm`, like `combinatorics::comb_mod`, deny outside
+// tests every arithmetic operator, index or unwrap that could panic
+#[cfg(feature = "std")]
+pub mod abc;
+pub mod algebraic_factors;
+pub mod apportion;
+pub mod arithmetic_fn;
+pub mod aspect_ratio;
+#[cfg(feature = "internal-bigint")]
+pub mod big_rational;
+#[cfg(feature = "internal-bigint")]
+pub mod bigu;
+pub mod cfrac;
+#[cfg(feature = "std")]
+pub mod chebyshev;
+#[cfg_attr(
+    not(test),
+    deny(
+        clippy::arithmetic_side_effects,
+        clippy::indexing_slicing,
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic
+    )
+)]
+pub mod checked;
+pub mod continued_fraction;
+pub mod cycles;
+pub mod cyclic_value;
+pub mod diophantine;
+pub mod diophantine_search;
+pub mod dirichlet;
+pub mod discrete_log;
+pub mod divisors;
+pub mod exact_stats;
+pub mod factorization;
+#[cfg(feature = "std")]
+pub mod float_gcd;
+pub mod floor_sum;
+#[cfg_attr(
+    not(test),
+    deny(
+        clippy::arithmetic_side_effects,
+        clippy::indexing_slicing,
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic
+    )
+)]
 pub mod gcd;
+pub mod gcd_convolution;
+pub mod gears;
+pub mod gf2;
+pub mod hyperop;
+pub mod int_linalg;
+#[cfg_attr(
+    not(test),
+    deny(
+        clippy::arithmetic_side_effects,
+        clippy::indexing_slicing,
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic
+    )
+)]
+pub mod integer;
+#[cfg(feature = "std")]
+pub mod integer_relation;
+pub mod interpolation;
+pub mod interval;
+#[cfg_attr(
+    not(test),
+    deny(
+        clippy::arithmetic_side_effects,
+        clippy::indexing_slicing,
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic
+    )
+)]
 pub mod lcm;
+pub mod matrix;
+pub mod mod_expression;
+pub mod mod_trace;
+pub mod modular;
+pub mod ntt;
+pub mod padic;
+pub mod partial_fractions;
+pub mod poly_mod;
+pub mod polynomial;
+pub mod power_series;
+pub mod prime_gaps;
 pub mod prime_nbr;
+pub mod product_tree;
+pub mod pythagorean;
+pub mod ramanujan;
+pub mod ratio;
+pub mod rational;
+pub mod rational_linalg;
+pub mod reciprocity;
+pub mod resultant;
+pub mod rns;
+pub mod round_robin;
+#[cfg(feature = "rand")]
+pub mod sampling;
+pub mod sieve;
+pub mod special_primes;
+pub mod squarefree;
+pub mod transforms;
+pub mod tuning;
+pub mod unit_group;
+pub mod universal_hash;
@@ -0,0 +1,492 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::algebra::factorization::carmichael;
+use crate::algebra::modular::{mod_inverse, mod_pow};
+use crate::errors::MathError;
+
+/// Exponents from this size on are past the largest prime exponent of any `u64`
+/// modulus, where `a^e mod k` only depends on `e mod λ(k)`.
+const STABLE_EXPONENT: u64 = 64;
+
+/// Value of an arithmetic expression modulo `m`:
+/// - `simplify_mod_expression("3^(10^100) + 2", 7) = 6`
+/// - `simplify_mod_expression("(5 - 8) * 4 / 3", 11) = 7`
+///
+/// Supports `+ - * / ^`, unary minus and parentheses on decimal literals of any length;
+/// `^` binds tighter than unary minus and associates to the right, and `/` multiplies
+/// by the modular inverse. Huge exponents are reduced modulo `λ(m)` (then `λ(λ(m))`
+/// for towers, and so on), which stays valid for bases sharing a factor with `m` once
+/// the exponent is large enough. Exponents must be non-negative integers known
+/// exactly or known to exceed `2^64`, so `-` and `/` inside an exponent have to give
+/// an exact result. Errors with `DivisionByZero` if `m == 0`, `EmptyInput` for a blank
+/// expression, `OutOfRange` for a syntax error or an unsupported exponent and
+/// `NoInverse` for a division by a non-unit.
+pub fn simplify_mod_expression(expr: &str, m: u64) -> Result<u64, MathError> {
+    evaluate(expr, m, None)
+}
+
+/// `simplify_mod_expression`, together with the reductions it performed, innermost
+/// first:
+/// - `"2^100"` mod 7 gives `2` and the step `"2^100 ≡ 2 (mod 7)"`
+///
+/// Exponents are reduced modulo `λ(m)` and the steps name that modulus, e.g.
+/// `"3^(10^100) ≡ 3^(4 + 10·6) ≡ 4 (mod 7)"` after `"4^100 ≡ 4 (mod λ(7) = 6)"`.
+pub fn simplify_mod_expression_steps(expr: &str, m: u64) -> Result<(u64, Vec<String>), MathError> {
+    let mut steps = Vec::new();
+    let value = evaluate(expr, m, Some(&mut steps))?;
+    Ok((value, steps))
+}
+
+fn evaluate(expr: &str, m: u64, steps: Option<&mut Vec<String>>) -> Result<u64, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let mut parser = Parser {
+        bytes: expr.as_bytes(),
+        pos: 0,
+    };
+    if parser.peek().is_none() {
+        return Err(MathError::EmptyInput);
+    }
+    let tree = parser.expr()?;
+    if parser.peek().is_some() {
+        return Err(MathError::OutOfRange);
+    }
+    let mut evaluator = Evaluator { steps };
+    Ok(evaluator.eval(&tree, m, &format!("mod {m}"))?.residue)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+impl Op {
+    fn symbol(self) -> char {
+        match self {
+            Op::Add => '+',
+            Op::Sub => '-',
+            Op::Mul => '*',
+            Op::Div => '/',
+            Op::Pow => '^',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Number(String),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// The expression as written, in parentheses unless it is a single literal.
+    fn operand(&self) -> String {
+        match self {
+            Expr::Number(digits) => digits.clone(),
+            Expr::Neg(inner) => format!("(-{})", inner.operand()),
+            Expr::Binary(op, left, right) => {
+                format!("({}{}{})", left.operand(), op.symbol(), right.operand())
+            }
+        }
+    }
+}
+
+/// Recursive descent over `expr := term (± term)*`, `term := unary ((*|/) unary)*`,
+/// `unary := -unary | power`, `power := atom (^ unary)?`.
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    /// Next non-blank byte, without consuming it.
+    fn peek(&mut self) -> Option<u8> {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expr(&mut self) -> Result<Expr, MathError> {
+        let mut left = self.term()?;
+        while let Some(op) = self.peek().and_then(|b| match b {
+            b'+' => Some(Op::Add),
+            b'-' => Some(Op::Sub),
+            _ => None,
+        }) {
+            self.pos += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, MathError> {
+        let mut left = self.unary()?;
+        while let Some(op) = self.peek().and_then(|b| match b {
+            b'*' => Some(Op::Mul),
+            b'/' => Some(Op::Div),
+            _ => None,
+        }) {
+            self.pos += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, MathError> {
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        let base = self.atom()?;
+        if self.peek() == Some(b'^') {
+            self.pos += 1;
+            return Ok(Expr::Binary(
+                Op::Pow,
+                Box::new(base),
+                Box::new(self.unary()?),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expr, MathError> {
+        match self.peek() {
+            Some(b'(') => {
+                self.pos += 1;
+                let inner = self.expr()?;
+                if self.peek() != Some(b')') {
+                    return Err(MathError::OutOfRange);
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            Some(b) if b.is_ascii_digit() => {
+                let start = self.pos;
+                while self.bytes.get(self.pos).is_some_and(u8::is_ascii_digit) {
+                    self.pos += 1;
+                }
+                let digits = String::from_utf8_lossy(&self.bytes[start..self.pos]);
+                Ok(Expr::Number(digits.into_owned()))
+            }
+            _ => Err(MathError::OutOfRange),
+        }
+    }
+}
+
+/// What is known about the integer an expression stands for, besides its residue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Size {
+    Exact(u64),
+    /// Non-negative and at least `2^64`.
+    Huge,
+    /// Negative, fractional (from `/`) or lost along the way.
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Value {
+    residue: u64,
+    size: Size,
+}
+
+struct Evaluator<'a> {
+    steps: Option<&'a mut Vec<String>>,
+}
+
+impl Evaluator<'_> {
+    /// `expr` modulo `k`; `label` names that modulus in the steps (`mod 7`,
+    /// `mod λ(7) = 6`).
+    fn eval(&mut self, expr: &Expr, k: u64, label: &str) -> Result<Value, MathError> {
+        match expr {
+            Expr::Number(digits) => {
+                let residue = digits.bytes().fold(0, |acc, b| {
+                    ((acc as u128 * 10 + (b - b'0') as u128) % k as u128) as u64
+                });
+                let size = digits.parse().map_or(Size::Huge, Size::Exact);
+                if size != Size::Exact(residue) {
+                    self.record(|| format!("{digits} ≡ {residue} ({label})"));
+                }
+                Ok(Value { residue, size })
+            }
+            Expr::Neg(inner) => {
+                let v = self.eval(inner, k, label)?;
+                let size = match v.size {
+                    Size::Exact(0) => Size::Exact(0),
+                    _ => Size::Unknown,
+                };
+                Ok(Value {
+                    residue: (k - v.residue) % k,
+                    size,
+                })
+            }
+            Expr::Binary(Op::Pow, base, exponent) => self.power(base, exponent, k, label),
+            Expr::Binary(op, left, right) => {
+                let (a, b) = (self.eval(left, k, label)?, self.eval(right, k, label)?);
+                let value = combine(*op, a, b, k)?;
+                let symbol = op.symbol();
+                self.record(|| {
+                    format!(
+                        "{} {symbol} {} ≡ {} ({label})",
+                        a.residue, b.residue, value.residue
+                    )
+                });
+                Ok(value)
+            }
+        }
+    }
+
+    /// `base^exponent mod k`, with the exponent itself evaluated modulo `λ(k)`.
+    fn power(
+        &mut self,
+        base: &Expr,
+        exponent: &Expr,
+        k: u64,
+        label: &str,
+    ) -> Result<Value, MathError> {
+        let a = self.eval(base, k, label)?;
+        let lambda = carmichael(k)? as u64;
+        let e = self.eval(exponent, lambda, &format!("mod λ({k}) = {lambda}"))?;
+        let residue = match e.size {
+            Size::Exact(n) => {
+                let residue = mod_pow(a.residue, n, k)?;
+                self.record(|| format!("{}^{n} ≡ {residue} ({label})", a.residue));
+                residue
+            }
+            Size::Huge => {
+                let (residue, extra) = pow_mod_huge_exponent(a.residue, e.residue, lambda, k)?;
+                self.record(|| {
+                    format!(
+                        "{a}^{e} ≡ {a}^({r} + {extra}·{lambda}) ≡ {residue} ({label})",
+                        a = a.residue,
+                        e = exponent.operand(),
+                        r = e.residue
+                    )
+                });
+                residue
+            }
+            Size::Unknown => return Err(MathError::OutOfRange),
+        };
+        let size = match (a.size, e.size) {
+            (_, Size::Exact(0)) => Size::Exact(1),
+            (Size::Exact(b @ (0 | 1)), _) => Size::Exact(b),
+            (Size::Exact(b), Size::Exact(n)) => u32::try_from(n)
+                .ok()
+                .and_then(|n| b.checked_pow(n))
+                .map_or(Size::Huge, Size::Exact),
+            (Size::Unknown, _) => Size::Unknown,
+            _ => Size::Huge,
+        };
+        Ok(Value { residue, size })
+    }
+
+    fn record(&mut self, step: impl FnOnce() -> String) {
+        if let Some(steps) = self.steps.as_mut() {
+            steps.push(step());
+        }
+    }
+}
+
+/// `a^e mod k` for an exponent `e >= 2^64` known only through `r = e mod λ`, `λ = λ(k)`,
+/// along with the number `c` of extra `λ` it took to make `r + c·λ` large enough.
+///
+/// `a^e ≡ a^(r + c·λ)` for any `r + c·λ ≡ e (mod λ)` past `STABLE_EXPONENT`, whether or
+/// not `a` is a unit.
+pub(crate) fn pow_mod_huge_exponent(
+    a: u64,
+    r: u64,
+    lambda: u64,
+    k: u64,
+) -> Result<(u64, u64), MathError> {
+    let mut reduced = r;
+    let mut extra = 0;
+    while reduced < STABLE_EXPONENT {
+        reduced = reduced.saturating_add(lambda);
+        extra += 1;
+    }
+    let a_lambda = mod_pow(a, lambda, k)?;
+    let residue =
+        (mod_pow(a, r, k)? as u128 * mod_pow(a_lambda, extra, k)? as u128 % k as u128) as u64;
+    Ok((residue, extra))
+}
+
+/// `a op b` modulo `k` for every operator but `^`.
+fn combine(op: Op, a: Value, b: Value, k: u64) -> Result<Value, MathError> {
+    let (x, y, k128) = (a.residue as u128, b.residue as u128, k as u128);
+    let value = |residue: u128, size| Value {
+        residue: (residue % k128) as u64,
+        size,
+    };
+    Ok(match (op, a.size, b.size) {
+        (Op::Add, Size::Exact(p), Size::Exact(q)) => value(x + y, exact_or_huge(p.checked_add(q))),
+        (Op::Add, Size::Unknown, _) | (Op::Add, _, Size::Unknown) => value(x + y, Size::Unknown),
+        (Op::Add, _, _) => value(x + y, Size::Huge),
+        (Op::Sub, Size::Exact(p), Size::Exact(q)) => value(
+            x + k128 - y,
+            p.checked_sub(q).map_or(Size::Unknown, Size::Exact),
+        ),
+        (Op::Sub, _, _) => value(x + k128 - y, Size::Unknown),
+        (Op::Mul, Size::Exact(0), _) | (Op::Mul, _, Size::Exact(0)) => value(0, Size::Exact(0)),
+        (Op::Mul, Size::Exact(p), Size::Exact(q)) => value(x * y, exact_or_huge(p.checked_mul(q))),
+        (Op::Mul, Size::Unknown, _) | (Op::Mul, _, Size::Unknown) => value(x * y, Size::Unknown),
+        (Op::Mul, _, _) => value(x * y, Size::Huge),
+        (Op::Div, _, Size::Exact(0)) => return Err(MathError::DivisionByZero),
+        (Op::Div, Size::Exact(p), Size::Exact(q)) if p.is_multiple_of(q) => {
+            value((p / q) as u128, Size::Exact(p / q))
+        }
+        (Op::Div, _, _) if k == 1 => value(0, Size::Unknown),
+        (Op::Div, _, _) => {
+            let inverse = mod_inverse(b.residue, k).map_err(|_| MathError::NoInverse)?;
+            value(x * inverse as u128, Size::Unknown)
+        }
+        (Op::Pow, _, _) => unreachable!("powers are evaluated by Evaluator::power"),
+    })
+}
+
+fn exact_or_huge(n: Option<u64>) -> Size {
+    n.map_or(Size::Huge, Size::Exact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_simplify_mod_expression() {
+        assert_eq!(simplify_mod_expression("3^(10^100) + 2", 7), Ok(6));
+        assert_eq!(simplify_mod_expression("(5 - 8) * 4 / 3", 11), Ok(7));
+        assert_eq!(simplify_mod_expression("1 + 2 * 3", 100), Ok(7));
+        assert_eq!(simplify_mod_expression("-2^2", 10), Ok(6));
+        assert_eq!(simplify_mod_expression("2^3^2", 1000), Ok(512));
+        assert_eq!(
+            simplify_mod_expression("12345678901234567890123", 97),
+            Ok((12345678901234567890123u128 % 97) as u64)
+        );
+        assert_eq!(simplify_mod_expression("17", 1), Ok(0));
+        assert_eq!(simplify_mod_expression("2^(6 / 2)", 7), Ok(1));
+    }
+
+    #[test]
+    fn test_huge_exponents_with_non_units() {
+        // 24 = 2^3·3 shares the factor 2 with the base: 2^e mod 24 is 8 for odd e >= 3
+        // and 16 for even e >= 4
+        assert_eq!(simplify_mod_expression("2^(2^100)", 24), Ok(16));
+        assert_eq!(simplify_mod_expression("6^(10^30)", 36), Ok(0));
+        assert_eq!(simplify_mod_expression("2^(10^30 + 1)", 24), Ok(8));
+        // towers: 3^(3^(3^3)) mod 1000 against the exact 3^3^3 = 7625597484987
+        assert_eq!(
+            simplify_mod_expression("3^3^3^3", 1000),
+            Ok(mod_pow(3, 7_625_597_484_987, 1000).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_against_direct_evaluation() {
+        for m in 1..60u64 {
+            for a in 0..12u64 {
+                for e in 0..80u64 {
+                    let expected = mod_pow(a, e, m).unwrap();
+                    let expr = format!("{a}^({e} + 0 * 10^40)");
+                    assert_eq!(
+                        simplify_mod_expression(&expr, m),
+                        Ok(expected),
+                        "{expr} mod {m}"
+                    );
+                    // the same exponent pushed past 2^64 by multiples of λ(m)
+                    let lambda = carmichael(m).unwrap();
+                    let expr = format!("{a}^({e} + {lambda} * 10^30)");
+                    // valid past the largest prime exponent of m < 60, 5 for 32
+                    if e >= 5 {
+                        assert_eq!(
+                            simplify_mod_expression(&expr, m),
+                            Ok(expected),
+                            "{expr} mod {m}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            simplify_mod_expression("1 + 1", 0),
+            Err(MathError::DivisionByZero)
+        );
+        assert_eq!(
+            simplify_mod_expression("   ", 7),
+            Err(MathError::EmptyInput)
+        );
+        assert_eq!(
+            simplify_mod_expression("2 +", 7),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            simplify_mod_expression("(2 + 3", 7),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            simplify_mod_expression("2 3", 7),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            simplify_mod_expression("x + 1", 7),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            simplify_mod_expression("2^-1", 7),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            simplify_mod_expression("2^(10^30 - 1)", 7),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            simplify_mod_expression("1 / 2", 8),
+            Err(MathError::NoInverse)
+        );
+        assert_eq!(
+            simplify_mod_expression("1 / 0", 7),
+            Err(MathError::DivisionByZero)
+        );
+        assert_eq!(
+            simplify_mod_expression("1 / 7", 7),
+            Err(MathError::NoInverse)
+        );
+    }
+
+    #[test]
+    fn test_steps() {
+        let (value, steps) = simplify_mod_expression_steps("2^100", 7).unwrap();
+        assert_eq!(value, 2);
+        assert_eq!(steps, vec!["100 ≡ 4 (mod λ(7) = 6)", "2^100 ≡ 2 (mod 7)"]);
+        let (value, steps) = simplify_mod_expression_steps("3^(10^100) + 2", 7).unwrap();
+        assert_eq!(value, 6);
+        assert_eq!(
+            steps,
+            vec![
+                "10 ≡ 4 (mod λ(7) = 6)",
+                "100 ≡ 0 (mod λ(6) = 2)",
+                "4^100 ≡ 4 (mod λ(7) = 6)",
+                "3^(10^100) ≡ 3^(4 + 10·6) ≡ 4 (mod 7)",
+                "4 + 2 ≡ 6 (mod 7)",
+            ]
+        );
+        assert_eq!(steps.last().map(String::as_str), Some("4 + 2 ≡ 6 (mod 7)"));
+    }
+}
@@ -0,0 +1,393 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+
+use crate::algebra::gcd::gcd_u64;
+use crate::algebra::modular::mod_inverse;
+use crate::errors::MathError;
+
+/// One bit of the exponent in `PowTrace`, least significant first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowStep {
+    pub bit: bool,
+    /// `base^(2^i) mod m`, the square reached at this bit.
+    pub square: u64,
+    /// The running product after this bit, multiplied by `square` when `bit` is set.
+    pub result: u64,
+}
+
+/// The square-and-multiply workings of `mod_pow`, printed as a table by `Display`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowTrace {
+    pub base: u64,
+    pub exp: u64,
+    pub modulus: u64,
+    pub steps: Vec<PowStep>,
+    pub value: u64,
+}
+
+/// One row of the extended Euclidean table, with `remainder = s·a + t·m`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EuclidRow {
+    pub remainder: u64,
+    /// `⌊previous remainder / remainder⌋`, `None` on the first and the final zero row.
+    pub quotient: Option<u64>,
+    pub s: i128,
+    pub t: i128,
+}
+
+/// The extended Euclidean table behind `mod_inverse`, printed by `Display`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EuclidTrace {
+    /// `a mod m`, the value actually inverted.
+    pub a: u64,
+    pub modulus: u64,
+    pub rows: Vec<EuclidRow>,
+    pub gcd: u64,
+    /// `None` when `gcd ≠ 1`, where the table shows why no inverse exists.
+    pub inverse: Option<u64>,
+}
+
+/// One congruence folded into the running solution of `CrtTrace`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrtStep {
+    pub residue: u64,
+    pub modulus: u64,
+    /// `g = gcd(M, n)` with the modulus `M` solved so far.
+    pub gcd: u64,
+    /// `t ≡ (r - x)/g · (M/g)⁻¹ (mod n/g)`, the lift in `x + M·t`.
+    pub lift: u64,
+    /// The solution and its modulus after this congruence.
+    pub value: u64,
+    pub combined_modulus: u64,
+}
+
+/// The recombination performed by `crt`, printed by `Display`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrtTrace {
+    pub steps: Vec<CrtStep>,
+    /// `(x, M)` as returned by `crt`.
+    pub value: (u64, u64),
+}
+
+/// `mod_pow` with its workings, one `PowStep` per bit of `exp`:
+/// - `mod_pow_trace(4, 13, 497)`: squares `4, 16, 256, 429`, results `4, 4, 30, 445`
+///
+/// `exp == 0` has no steps and the value `1 mod m`. Errors with `DivisionByZero` if
+/// `m == 0`.
+pub fn mod_pow_trace(base: u64, exp: u64, m: u64) -> Result<PowTrace, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % m as u128) as u64;
+    let (mut square, mut result) = (base % m, 1 % m);
+    let mut steps = Vec::new();
+    let mut rest = exp;
+    while rest > 0 {
+        if !steps.is_empty() {
+            square = mul(square, square);
+        }
+        let bit = rest & 1 == 1;
+        if bit {
+            result = mul(result, square);
+        }
+        steps.push(PowStep {
+            bit,
+            square,
+            result,
+        });
+        rest >>= 1;
+    }
+    Ok(PowTrace {
+        base,
+        exp,
+        modulus: m,
+        steps,
+        value: result,
+    })
+}
+
+/// The extended Euclidean table for `a⁻¹ mod m`, rows `m` and `a mod m` first:
+/// - `mod_inverse_trace(3, 11)`: remainders `11, 3, 2, 1, 0`, inverse `Some(4)`
+/// - `mod_inverse_trace(4, 6)`: gcd 2, inverse `None`
+///
+/// Unlike `mod_inverse`, a missing inverse is part of the answer rather than an error.
+/// Errors with `DivisionByZero` if `m == 0`.
+pub fn mod_inverse_trace(a: u64, m: u64) -> Result<EuclidTrace, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let a = a % m;
+    let mut rows = vec![
+        EuclidRow {
+            remainder: m,
+            quotient: None,
+            s: 0,
+            t: 1,
+        },
+        EuclidRow {
+            remainder: a,
+            quotient: None,
+            s: 1,
+            t: 0,
+        },
+    ];
+    while let [.., previous, last] = rows.as_mut_slice()
+        && last.remainder != 0
+    {
+        let q = previous.remainder / last.remainder;
+        last.quotient = Some(q);
+        let next = EuclidRow {
+            remainder: previous.remainder - q * last.remainder,
+            quotient: None,
+            s: previous.s - q as i128 * last.s,
+            t: previous.t - q as i128 * last.t,
+        };
+        rows.push(next);
+    }
+    let last_nonzero = &rows[rows.len() - 2];
+    let gcd = last_nonzero.remainder;
+    Ok(EuclidTrace {
+        a,
+        modulus: m,
+        inverse: (gcd == 1).then(|| last_nonzero.s.rem_euclid(m as i128) as u64),
+        gcd,
+        rows,
+    })
+}
+
+/// `crt` with its workings, one `CrtStep` per congruence `(rᵢ, mᵢ)`:
+/// - `crt_trace(&[(2, 3), (3, 5), (2, 7)])`: values `2, 8, 23`, moduli `3, 15, 105`
+///
+/// Errors like `crt`: `DivisionByZero` for a zero modulus, `NoSolution` for
+/// incompatible congruences and `Overflow` if the combined modulus exceeds `u64::MAX`.
+pub fn crt_trace(congruences: &[(u64, u64)]) -> Result<CrtTrace, MathError> {
+    let (mut x, mut m) = (0u64, 1u64);
+    let mut steps = Vec::with_capacity(congruences.len());
+    for &(r, n) in congruences {
+        if n == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        let g = gcd_u64(m, n);
+        let diff = (r % n) as i128 - (x % n) as i128;
+        if diff % g as i128 != 0 {
+            return Err(MathError::NoSolution);
+        }
+        let n_g = n / g;
+        let lift = ((diff / g as i128).rem_euclid(n_g as i128) as u128
+            * mod_inverse(m / g % n_g, n_g)? as u128
+            % n_g as u128) as u64;
+        let lcm = (m / g).checked_mul(n).ok_or(MathError::Overflow)?;
+        x = ((x as u128 + m as u128 * lift as u128) % lcm as u128) as u64;
+        m = lcm;
+        steps.push(CrtStep {
+            residue: r,
+            modulus: n,
+            gcd: g,
+            lift,
+            value: x,
+            combined_modulus: m,
+        });
+    }
+    Ok(CrtTrace {
+        steps,
+        value: (x, m),
+    })
+}
+
+impl fmt::Display for PowTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{}^{} mod {}, {} = 0b{:b}",
+            self.base, self.exp, self.modulus, self.exp, self.exp
+        )?;
+        let rows = self.steps.iter().enumerate().map(|(i, step)| {
+            vec![
+                i.to_string(),
+                (step.bit as u8).to_string(),
+                step.square.to_string(),
+                step.result.to_string(),
+            ]
+        });
+        let square = format!("{}^(2^i)", self.base);
+        write_table(f, &["i", "bit", &square, "result"], rows)?;
+        write!(
+            f,
+            "{}^{} ≡ {} (mod {})",
+            self.base, self.exp, self.value, self.modulus
+        )
+    }
+}
+
+impl fmt::Display for EuclidTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "r = s·{} + t·{}", self.a, self.modulus)?;
+        let rows = self.rows.iter().enumerate().map(|(i, row)| {
+            vec![
+                i.to_string(),
+                row.remainder.to_string(),
+                row.quotient.map_or(String::new(), |q| q.to_string()),
+                row.s.to_string(),
+                row.t.to_string(),
+            ]
+        });
+        write_table(f, &["i", "r", "q", "s", "t"], rows)?;
+        match self.inverse {
+            Some(inverse) => write!(
+                f,
+                "gcd = 1, {}⁻¹ ≡ {} (mod {})",
+                self.a, inverse, self.modulus
+            ),
+            None => write!(
+                f,
+                "gcd = {}, {} has no inverse mod {}",
+                self.gcd, self.a, self.modulus
+            ),
+        }
+    }
+}
+
+impl fmt::Display for CrtTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "x ← x + M·t, t ≡ (r - x)/g · (M/g)⁻¹ (mod n/g)")?;
+        let rows = self.steps.iter().enumerate().map(|(i, step)| {
+            vec![
+                i.to_string(),
+                step.residue.to_string(),
+                step.modulus.to_string(),
+                step.gcd.to_string(),
+                step.lift.to_string(),
+                step.value.to_string(),
+                step.combined_modulus.to_string(),
+            ]
+        });
+        write_table(f, &["i", "r", "n", "g", "t", "x", "M"], rows)?;
+        write!(f, "x ≡ {} (mod {})", self.value.0, self.value.1)
+    }
+}
+
+/// Right-aligned columns separated by two spaces, one line per row.
+fn write_table(
+    f: &mut fmt::Formatter,
+    header: &[&str],
+    rows: impl Iterator<Item = Vec<String>>,
+) -> fmt::Result {
+    let rows: Vec<Vec<String>> = rows.collect();
+    let widths: Vec<usize> = (0..header.len())
+        .map(|c| {
+            rows.iter()
+                .map(|row| row[c].chars().count())
+                .chain([header[c].chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let header = header.iter().map(|h| h.to_string()).collect();
+    for row in core::iter::once(&header).chain(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &w)| format!("{cell:>w$}"))
+            .collect();
+        writeln!(f, "{}", cells.join("  "))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::modular::{crt, mod_pow};
+
+    #[test]
+    fn test_mod_pow_trace() {
+        let trace = mod_pow_trace(4, 13, 497).unwrap();
+        let squares: Vec<u64> = trace.steps.iter().map(|s| s.square).collect();
+        let results: Vec<u64> = trace.steps.iter().map(|s| s.result).collect();
+        assert_eq!(
+            (squares, results),
+            (vec![4, 16, 256, 429], vec![4, 4, 30, 445])
+        );
+        assert_eq!(
+            trace.to_string(),
+            "4^13 mod 497, 13 = 0b1101\n\
+             i  bit  4^(2^i)  result\n\
+             0    1        4       4\n\
+             1    0       16       4\n\
+             2    1      256      30\n\
+             3    1      429     445\n\
+             4^13 ≡ 445 (mod 497)"
+        );
+        assert!(mod_pow_trace(5, 0, 7).unwrap().steps.is_empty());
+        assert_eq!(mod_pow_trace(5, 0, 1).unwrap().value, 0);
+        for (base, exp, m) in [(2, 100, 7), (u64::MAX, u64::MAX, u64::MAX - 1), (3, 1, 2)] {
+            assert_eq!(
+                Ok(mod_pow_trace(base, exp, m).unwrap().value),
+                mod_pow(base, exp, m)
+            );
+        }
+        assert_eq!(mod_pow_trace(2, 3, 0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_mod_inverse_trace() {
+        let trace = mod_inverse_trace(3, 11).unwrap();
+        assert_eq!(
+            trace.to_string(),
+            "r = s·3 + t·11\n\
+             i   r  q    s   t\n\
+             0  11       0   1\n\
+             1   3  3    1   0\n\
+             2   2  1   -3   1\n\
+             3   1  2    4  -1\n\
+             4   0     -11   3\n\
+             gcd = 1, 3⁻¹ ≡ 4 (mod 11)"
+        );
+        for row in &trace.rows {
+            assert_eq!(row.remainder as i128, row.s * 3 + row.t * 11);
+        }
+        let trace = mod_inverse_trace(4, 6).unwrap();
+        assert_eq!((trace.gcd, trace.inverse), (2, None));
+        assert!(
+            trace
+                .to_string()
+                .ends_with("gcd = 2, 4 has no inverse mod 6")
+        );
+        for (a, m) in [
+            (17, 3120),
+            (u64::MAX, u64::MAX - 1),
+            (0, 1),
+            (25, 10),
+            (1, 1),
+        ] {
+            let trace = mod_inverse_trace(a, m).unwrap();
+            assert_eq!(trace.inverse.ok_or(MathError::NoInverse), mod_inverse(a, m));
+        }
+        assert_eq!(mod_inverse_trace(3, 0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_crt_trace() {
+        let trace = crt_trace(&[(2, 3), (3, 5), (2, 7)]).unwrap();
+        assert_eq!(
+            trace.to_string(),
+            "x ← x + M·t, t ≡ (r - x)/g · (M/g)⁻¹ (mod n/g)\n\
+             i  r  n  g  t   x    M\n\
+             0  2  3  1  2   2    3\n\
+             1  3  5  1  2   8   15\n\
+             2  2  7  1  1  23  105\n\
+             x ≡ 23 (mod 105)"
+        );
+        for system in [&[(1, 4), (3, 6)][..], &[], &[(5, 9), (8, 12), (2, 5)]] {
+            assert_eq!(Ok(crt_trace(system).unwrap().value), crt(system));
+        }
+        assert_eq!(crt_trace(&[(1, 4), (2, 6)]), Err(MathError::NoSolution));
+        assert_eq!(crt_trace(&[(1, 0)]), Err(MathError::DivisionByZero));
+        assert_eq!(
+            crt_trace(&[(0, u64::MAX), (0, u64::MAX - 1)]),
+            Err(MathError::Overflow)
+        );
+    }
+}
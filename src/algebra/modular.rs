@@ -0,0 +1,1168 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(feature = "internal-bigint")]
+use crate::algebra::bigu::BigU;
+use crate::algebra::divisors::trial_factors;
+use crate::algebra::gcd::gcd_u64;
+use crate::algebra::prime_nbr::is_prime_u64;
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// `a · b mod m` for any 64-bit operands, through a `u128` product that cannot overflow:
+/// - `mod_mul(u64::MAX, u64::MAX, 1_000_000_007) = 114_944_269`
+///
+/// Errors with `DivisionByZero` if `m == 0`.
+pub const fn mod_mul(a: u64, b: u64, m: u64) -> Result<u64, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    Ok(mul_mod(a, b, m))
+}
+
+/// Square-and-multiply: `base^exp mod m`.
+///
+/// - `mod_pow(4, 13, 497) = 445`
+///
+/// Moduli below `2^32` keep every product in a `u64`; larger ones go through a
+/// `BarrettCtx`, which replaces the 128-bit division of each product by multiplications.
+/// `mod_pow(b, 0, 1) = 0`; errors with `DivisionByZero` if `m == 0`. A `const fn`, like
+/// `mod_mul`.
+pub const fn mod_pow(base: u64, mut exp: u64, m: u64) -> Result<u64, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let mut result = 1 % m;
+    let mut base = base % m;
+    if m <= 1 << 32 {
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base % m;
+            }
+            base = base * base % m;
+            exp >>= 1;
+        }
+        return Ok(result);
+    }
+    let ctx = BarrettCtx::for_modulus(m);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = ctx.mul(result, base);
+        }
+        base = ctx.mul(base, base);
+        exp >>= 1;
+    }
+    Ok(result)
+}
+
+/// Barrett reduction modulo a fixed `m`: with `μ = ⌊(2^128 - 1) / m⌋` precomputed, the
+/// quotient of `x < 2^128` by `m` is estimated as the high half of `x·μ`, at most 2 too
+/// small, so reducing costs four 64-bit multiplications and at most two subtractions
+/// instead of a 128-bit division:
+/// - `BarrettCtx::new(10^12)?.mul(10^11 + 3, 10^11 + 7) = 21`
+///
+/// Works for every modulus, even or odd.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrettCtx {
+    modulus: u64,
+    mu: u128,
+}
+
+impl BarrettCtx {
+    /// Errors with `DivisionByZero` if `modulus == 0`.
+    pub const fn new(modulus: u64) -> Result<Self, MathError> {
+        if modulus == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        Ok(BarrettCtx::for_modulus(modulus))
+    }
+
+    /// `modulus > 0`.
+    const fn for_modulus(modulus: u64) -> Self {
+        BarrettCtx {
+            modulus,
+            mu: u128::MAX / modulus as u128,
+        }
+    }
+
+    pub const fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// `x mod modulus`.
+    pub const fn reduce(&self, x: u128) -> u64 {
+        let m = self.modulus as u128;
+        let mut r = x - mul_high(x, self.mu) * m;
+        while r >= m {
+            r -= m;
+        }
+        r as u64
+    }
+
+    /// `a · b mod modulus`.
+    pub const fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+}
+
+/// High 128 bits of the 256-bit product `a · b`.
+const fn mul_high(a: u128, b: u128) -> u128 {
+    let (a0, a1) = (a as u64 as u128, a >> 64);
+    let (b0, b1) = (b as u64 as u128, b >> 64);
+    let (low, cross1, cross2) = (a0 * b0, a1 * b0, a0 * b1);
+    let carry = ((low >> 64) + (cross1 as u64 as u128) + (cross2 as u64 as u128)) >> 64;
+    a1 * b1 + (cross1 >> 64) + (cross2 >> 64) + carry
+}
+
+/// `Π bᵢ^eᵢ mod m` for the pairs `(bᵢ, eᵢ)`, sharing one chain of squarings:
+/// - `multi_pow_mod(&[(2, 10), (3, 5)], 1000) = 1024·243 mod 1000 = 832`
+///
+/// Interleaved 4-bit windows (Shamir's trick generalized): every base gets a table of its
+/// first 16 powers, then the exponents are read together from the top, 4 squarings per
+/// window for the whole product and one multiplication per base with a non-zero digit.
+/// About `64 + 16k` products for `k` bases rather than the `k·(64 + 64)` of separate
+/// `mod_pow` calls, which is what verification equations like `g^s·y^e` need. An empty
+/// product is `1 % m`; errors with `DivisionByZero` if `m == 0`.
+pub fn multi_pow_mod(bases_and_exps: &[(u64, u64)], m: u64) -> Result<u64, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    const WINDOW: u32 = 4;
+    let tables: Vec<[u64; 1 << WINDOW]> = bases_and_exps
+        .iter()
+        .map(|&(base, _)| {
+            let mut table = [1 % m; 1 << WINDOW];
+            for d in 1..table.len() {
+                table[d] = mul_mod(table[d - 1], base % m, m);
+            }
+            table
+        })
+        .collect();
+    let bits = bases_and_exps
+        .iter()
+        .map(|&(_, e)| u64::BITS - e.leading_zeros())
+        .max()
+        .unwrap_or(0);
+    let mut result = 1 % m;
+    for window in (0..bits.div_ceil(WINDOW)).rev() {
+        for _ in 0..WINDOW {
+            result = mul_mod(result, result, m);
+        }
+        for (table, &(_, exp)) in tables.iter().zip(bases_and_exps) {
+            let digit = (exp >> (window * WINDOW)) as usize & (table.len() - 1);
+            if digit != 0 {
+                result = mul_mod(result, table[digit], m);
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Bits of the exponent consumed per table lookup in `FixedBasePow`.
+const WINDOW_BITS: u32 = 8;
+
+/// `base^e mod m` for one fixed base and many exponents, from precomputed powers:
+/// - `FixedBasePow::new(4, 497)?.pow(13) = 445`, as `mod_pow(4, 13, 497)`
+///
+/// Row `i` of the table holds `base^(d·2^(8i))` for every byte `d`, so `pow` multiplies
+/// one entry per byte of the exponent: at most 8 modular products where `mod_pow`
+/// needs up to 64 squarings and 64 multiplications. The 8 × 256 table costs about as
+/// much as 20 `mod_pow` calls to build and takes 16 KiB, so it pays off for the repeated
+/// exponentiations of a Diffie-Hellman generator or a batch verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedBasePow {
+    base: u64,
+    modulus: u64,
+    table: Vec<u64>,
+}
+
+impl FixedBasePow {
+    /// Errors with `DivisionByZero` if `modulus == 0`.
+    pub fn new(base: u64, modulus: u64) -> Result<Self, MathError> {
+        if modulus == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        let width = 1 << WINDOW_BITS;
+        let rows = u64::BITS / WINDOW_BITS;
+        let mut table = Vec::with_capacity((rows * width) as usize);
+        // g = base^(2^(8i)) for the current row i
+        let mut g = base % modulus;
+        for _ in 0..rows {
+            let mut power = 1 % modulus;
+            for _ in 0..width {
+                table.push(power);
+                power = mul_mod(power, g, modulus);
+            }
+            g = power;
+        }
+        Ok(FixedBasePow {
+            base: base % modulus,
+            modulus,
+            table,
+        })
+    }
+
+    /// The base, reduced mod `modulus`.
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// `base^exp mod modulus`, with `pow(0) = 1 % modulus` as in `mod_pow`.
+    pub fn pow(&self, exp: u64) -> u64 {
+        let width = 1 << WINDOW_BITS;
+        let mut result = 1 % self.modulus;
+        let mut exp = exp;
+        let mut row = 0;
+        while exp != 0 {
+            let digit = (exp & (width as u64 - 1)) as usize;
+            if digit != 0 {
+                result = mul_mod(result, self.table[row * width + digit], self.modulus);
+            }
+            exp >>= WINDOW_BITS;
+            row += 1;
+        }
+        result
+    }
+}
+
+/// Inverse of `a` mod `m` by the extended Euclidean algorithm.
+///
+/// - `mod_inverse(3, 11) = 4` since `3·4 = 12 ≡ 1`
+///
+/// Errors with `DivisionByZero` if `m == 0` and `NoInverse` if `gcd(a, m) ≠ 1`;
+/// `gcd::mod_inverse_i64` is the same for signed operands.
+pub fn mod_inverse(a: u64, m: u64) -> Result<u64, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let (mut old_r, mut r) = (a as i128 % m as i128, m as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    if old_r != 1 && m != 1 {
+        return Err(MathError::NoInverse);
+    }
+    Ok(old_s.rem_euclid(m as i128) as u64)
+}
+
+/// `i⁻¹ mod p` for every `0 <= i <= n`, with a 0 in slot 0:
+/// - `inverse_table(4, 7) = [0, 1, 4, 5, 2]`
+///
+/// O(n) without a single extended Euclid, from `p = ⌊p/i⌋·i + (p mod i)`, which gives
+/// `i⁻¹ ≡ -⌊p/i⌋·(p mod i)⁻¹` with `p mod i < i` already in the table. `p` need not be
+/// prime, but every `1..=n` must be invertible, so `n` has to stay below the smallest
+/// prime factor of `p`. Errors with `DivisionByZero` if `p == 0` and `NoSolution` if
+/// some `i <= n` shares a factor with `p`.
+pub fn inverse_table(n: usize, p: u64) -> Result<Vec<u64>, MathError> {
+    if p == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let mut table = vec![0; n + 1];
+    for i in 1..=n {
+        let i = i as u64;
+        if i >= p || (i > 1 && p.is_multiple_of(i)) {
+            return Err(MathError::NoSolution);
+        }
+        table[i as usize] = match i {
+            1 => 1 % p,
+            _ => mul_mod(p - p / i, table[(p % i) as usize], p),
+        };
+    }
+    Ok(table)
+}
+
+/// Chinese remainder theorem for congruences `x ≡ rᵢ (mod mᵢ)` given as `(rᵢ, mᵢ)`.
+///
+/// Returns `(x, M)` with `M = lcm(mᵢ)` and `0 <= x < M`; moduli need not be coprime:
+/// - `crt(&[(2, 3), (3, 5), (2, 7)]) = (23, 105)`
+/// - `crt(&[(1, 4), (3, 6)]) = (9, 12)`
+///
+/// An empty system gives `(0, 1)`. Errors with `DivisionByZero` for a zero modulus,
+/// `NoSolution` for incompatible congruences and `Overflow` if `M` exceeds `u64::MAX`.
+pub fn crt(congruences: &[(u64, u64)]) -> Result<(u64, u64), MathError> {
+    let (mut x, mut m) = (0u64, 1u64);
+    for &(r, n) in congruences {
+        if n == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        // x + m·t ≡ r (mod n)  <=>  (m/g)·t ≡ (r - x)/g (mod n/g)
+        let g = gcd_u64(m, n);
+        let diff = (r % n) as i128 - (x % n) as i128;
+        if diff % g as i128 != 0 {
+            return Err(MathError::NoSolution);
+        }
+        let n_g = n / g;
+        let t = mul_mod(
+            (diff / g as i128).rem_euclid(n_g as i128) as u64,
+            mod_inverse(m / g % n_g, n_g)?,
+            n_g,
+        );
+        let lcm = (m / g).checked_mul(n).ok_or(MathError::Overflow)?;
+        x = ((x as u128 + m as u128 * t as u128) % lcm as u128) as u64;
+        m = lcm;
+    }
+    Ok((x, m))
+}
+
+/// The value below `M = ∏ mᵢ` with residues `rᵢ` modulo pairwise coprime `mᵢ`, given as
+/// `(rᵢ, mᵢ)`, in `u128`:
+/// - `crt_reconstruct(&[(2, 3), (3, 5), (2, 7)]) = 23`
+///
+/// Unlike `crt`, the moduli must be coprime, but their product may exceed `u64`. One-off
+/// form of `Garner::reconstruct`; build a `Garner` to share the precomputed inverses over
+/// many values. An empty system gives 0. Errors with `DivisionByZero` for a zero modulus,
+/// `NoSolution` if two moduli share a factor and `Overflow` if the value leaves `u128`.
+pub fn crt_reconstruct(residues: &[(u64, u64)]) -> Result<u128, MathError> {
+    let (values, moduli): (Vec<u64>, Vec<u64>) = residues.iter().copied().unzip();
+    Garner::new(&moduli)?.reconstruct(&values)
+}
+
+/// `crt_reconstruct` without a size limit, as a `BigU`.
+#[cfg(feature = "internal-bigint")]
+pub fn crt_reconstruct_big(residues: &[(u64, u64)]) -> Result<BigU, MathError> {
+    let (values, moduli): (Vec<u64>, Vec<u64>) = residues.iter().copied().unzip();
+    Garner::new(&moduli)?.reconstruct_big(&values)
+}
+
+/// Garner's algorithm for a fixed list of pairwise coprime moduli `m₀, m₁, ...`.
+///
+/// A value `x < M = ∏ mᵢ` is rebuilt in mixed radix, `x = t₀ + t₁·m₀ + t₂·m₀m₁ + ...`,
+/// where `tⱼ = (rⱼ - (t₀ + ... )) · (m₀···mⱼ₋₁)⁻¹ mod mⱼ`. The inverses are computed once
+/// in `new`, so each reconstruction costs O(k²) word multiplications for `k` moduli and
+/// never reduces anything modulo a number above `u64`. Suited to many small primes, as
+/// in multi-modular determinants and NTT products.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Garner {
+    moduli: Vec<u64>,
+    /// `(m₀···mⱼ₋₁)⁻¹ mod mⱼ`
+    inverses: Vec<u64>,
+}
+
+impl Garner {
+    /// Precomputes the inverses for `moduli`.
+    ///
+    /// Errors with `DivisionByZero` for a zero modulus and `NoSolution` if two moduli are
+    /// not coprime.
+    pub fn new(moduli: &[u64]) -> Result<Self, MathError> {
+        let mut inverses = Vec::with_capacity(moduli.len());
+        for (j, &m) in moduli.iter().enumerate() {
+            if m == 0 {
+                return Err(MathError::DivisionByZero);
+            }
+            let prefix = moduli[..j]
+                .iter()
+                .fold(1 % m, |acc, &earlier| mul_mod(acc, earlier % m, m));
+            // with m nonzero, the inverse only fails for moduli sharing a factor
+            let inverse = mod_inverse(prefix, m).map_err(|_| MathError::NoSolution)?;
+            inverses.push(inverse);
+        }
+        Ok(Garner {
+            moduli: moduli.to_vec(),
+            inverses,
+        })
+    }
+
+    pub fn moduli(&self) -> &[u64] {
+        &self.moduli
+    }
+
+    /// The `x < ∏ mᵢ` with `x ≡ residues[i] (mod mᵢ)`, residues listed in the order of
+    /// the moduli:
+    /// - `Garner::new(&[3, 5, 7])?.reconstruct(&[2, 3, 2]) = 23`
+    ///
+    /// Evaluated from the top digit down, so `MathError::Overflow` only when the value
+    /// itself leaves `u128`; `DimensionMismatch` for the wrong number of residues.
+    pub fn reconstruct(&self, residues: &[u64]) -> Result<u128, MathError> {
+        let digits = self.digits(residues, false)?;
+        digits
+            .iter()
+            .zip(&self.moduli)
+            .rev()
+            .try_fold(0u128, |x, (&t, &m)| {
+                x.checked_mul(m as u128)
+                    .and_then(|x| x.checked_add(t as u128))
+            })
+            .ok_or(MathError::Overflow)
+    }
+
+    /// The representative `x` with `|x| <= M/2`, for signed results:
+    /// - `Garner::new(&[3, 5, 7])?.reconstruct_symmetric(&[1, 0, 1]) = -20`
+    ///
+    /// Uses balanced digits `|tⱼ| <= mⱼ/2`, which sum to the symmetric representative
+    /// directly. Errors as `reconstruct`, with `Overflow` past `i128`.
+    pub fn reconstruct_symmetric(&self, residues: &[u64]) -> Result<i128, MathError> {
+        let digits = self.digits(residues, true)?;
+        digits
+            .iter()
+            .zip(&self.moduli)
+            .rev()
+            .try_fold(0i128, |x, (&t, &m)| {
+                x.checked_mul(m as i128).and_then(|x| x.checked_add(t))
+            })
+            .ok_or(MathError::Overflow)
+    }
+
+    /// `reconstruct` without a size limit. `DimensionMismatch` for the wrong number of
+    /// residues.
+    #[cfg(feature = "internal-bigint")]
+    pub fn reconstruct_big(&self, residues: &[u64]) -> Result<BigU, MathError> {
+        let digits = self.digits(residues, false)?;
+        Ok(digits
+            .iter()
+            .zip(&self.moduli)
+            .rev()
+            .fold(BigU::zero(), |x, (&t, &m)| {
+                &(&x * &BigU::from_u64(m)) + &BigU::from_u64(t as u64)
+            }))
+    }
+
+    /// Mixed-radix digits `tⱼ`, in `[0, mⱼ)` or balanced in `(-mⱼ/2, mⱼ/2]`.
+    fn digits(&self, residues: &[u64], balanced: bool) -> Result<Vec<i128>, MathError> {
+        if residues.len() != self.moduli.len() {
+            return Err(MathError::DimensionMismatch);
+        }
+        let mut digits: Vec<i128> = Vec::with_capacity(residues.len());
+        for (j, (&r, &m)) in residues.iter().zip(&self.moduli).enumerate() {
+            // t₀ + t₁·m₀ + ... mod m, by Horner from the top digit
+            let prefix = (0..j).rev().fold(0, |acc, i| {
+                let digit = digits[i].rem_euclid(m as i128) as u64;
+                (mul_mod(acc, self.moduli[i] % m, m) as u128 + digit as u128) as u64 % m
+            });
+            let t = mul_mod(sub_mod(r % m, prefix, m), self.inverses[j], m);
+            digits.push(if balanced && t > m / 2 {
+                t as i128 - m as i128
+            } else {
+                t as i128
+            });
+        }
+        Ok(digits)
+    }
+}
+
+/// The fraction `p/q` with `a ≡ p·q⁻¹ (mod m)` and `|p|, q <= √((m - 1) / 2)`, by
+/// Wang's algorithm, or `None` if there is none:
+/// - `rational_reconstruction(61, 101) = Some(2/5)`, since `5·61 = 305 ≡ 2`
+/// - `rational_reconstruction(857_145, 1_000_003) = Some(-3/7)`
+///
+/// The last step of modular (CRT-based) exact computations: once `m` is a product of
+/// enough primes, the residue of a rational result gives the result back. The extended
+/// Euclidean algorithm on `(m, a)` is stopped at the first remainder within the bound;
+/// two candidates `p/q`, `p'/q'` would have `0 < |p·q' - p'·q| < m`, so the answer is
+/// unique. Errors with `DivisionByZero` if `m == 0`.
+pub fn rational_reconstruction(a: u64, m: u64) -> Result<Option<Rational>, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let bound = ((m - 1) / 2).isqrt() as i128;
+    // r ≡ t·a (mod m) throughout
+    let (mut r0, mut r1) = (m as i128, (a % m) as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 > bound {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    if t1 == 0 || t1.abs() > bound || gcd_u64(r1 as u64, t1.unsigned_abs() as u64) != 1 {
+        return Ok(None);
+    }
+    // both below 2^32
+    Rational::new((t1.signum() * r1) as i64, t1.abs() as i64).map(Some)
+}
+
+/// A square root of `a` modulo the odd prime (or 2) `p`, by Tonelli-Shanks.
+///
+/// Returns `None` when `a` is a non-residue; the other root is `p - r`:
+/// - `sqrt_mod(10, 13)` is `Some(6)` or `Some(7)`, since `6² = 36 ≡ 10`
+///
+/// Errors with `OutOfRange` unless `p` is prime; `sqrt_mod_n` takes any modulus.
+pub fn sqrt_mod(a: u64, p: u64) -> Result<Option<u64>, MathError> {
+    if !is_prime_u64(p) {
+        return Err(MathError::OutOfRange);
+    }
+    let a = a % p;
+    if a == 0 || p == 2 {
+        return Ok(Some(a));
+    }
+    // Euler's criterion
+    if mod_pow(a, (p - 1) / 2, p)? != 1 {
+        return Ok(None);
+    }
+
+    // p - 1 = q·2^s with q odd, z a non-residue
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = least_nonresidue(p);
+
+    let mut m = s;
+    let mut c = mod_pow(z, q, p)?;
+    let mut t = mod_pow(a, q, p)?;
+    let mut r = mod_pow(a, q.div_ceil(2), p)?;
+    while t != 1 {
+        // least i with t^(2^i) = 1
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != 1 {
+            t2 = mul_mod(t2, t2, p);
+            i += 1;
+        }
+        let b = mod_pow(c, 1 << (m - i - 1), p)?;
+        m = i;
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        r = mul_mod(r, b, p);
+    }
+    Ok(Some(r))
+}
+
+/// Legendre symbol `(a/p)` for an odd prime `p`, by Euler's criterion `a^((p-1)/2)`.
+///
+/// - `legendre_symbol(10, 13) = 1`, `legendre_symbol(5, 13) = -1`, `legendre_symbol(26, 13) = 0`
+///
+/// `p` is assumed prime; errors with `OutOfRange` if `p` is even or below 3.
+pub fn legendre_symbol(a: u64, p: u64) -> Result<i8, MathError> {
+    if p < 3 || p.is_multiple_of(2) {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(match mod_pow(a, (p - 1) / 2, p)? {
+        0 => 0,
+        1 => 1,
+        _ => -1,
+    })
+}
+
+/// Jacobi symbol `(a/n)` for odd `n`, by reciprocity without factoring `n`.
+///
+/// Equals the Legendre symbol for prime `n`; for composite `n` a value of 1 does not
+/// imply that `a` is a square:
+/// - `jacobi_symbol(2, 15) = 1` although 2 is not a square mod 15
+///
+/// Errors with `OutOfRange` if `n` is even.
+pub fn jacobi_symbol(a: u64, n: u64) -> Result<i8, MathError> {
+    if n.is_multiple_of(2) {
+        return Err(MathError::OutOfRange);
+    }
+    let (mut a, mut n) = (a % n, n);
+    let mut sign = 1;
+    while a != 0 {
+        let twos = a.trailing_zeros();
+        a >>= twos;
+        // (2/n) = -1 iff n ≡ ±3 (mod 8)
+        if twos % 2 == 1 && matches!(n % 8, 3 | 5) {
+            sign = -sign;
+        }
+        // swap, flipping the sign when both are ≡ 3 (mod 4)
+        if a % 4 == 3 && n % 4 == 3 {
+            sign = -sign;
+        }
+        (a, n) = (n % a, a);
+    }
+    Ok(if n == 1 { sign } else { 0 })
+}
+
+/// Least quadratic non-residue modulo the odd prime `p`, the `z` Tonelli-Shanks needs:
+/// - `first_quadratic_nonresidue(7) = 3`, the squares being `1, 2, 4`
+/// - `first_quadratic_nonresidue(71) = 7`
+///
+/// Tries `2, 3, 4, ...` with `jacobi_symbol`, O(log p) each; the answer is always a
+/// prime and, under GRH, below `2·ln²p`, so a handful of tries in practice. Errors with
+/// `OutOfRange` unless `p` is an odd prime, as a square modulus has no non-residue
+/// among the symbols and the search would not end.
+pub fn first_quadratic_nonresidue(p: u64) -> Result<u64, MathError> {
+    if p < 3 || !is_prime_u64(p) {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(least_nonresidue(p))
+}
+
+/// Number of quadratic residues `x` with `(x/p) = 1` in `a..=b`, multiples of `p`
+/// excluded:
+/// - `count_residues_in_interval(13, 1, 12) = 6`, the squares `1, 3, 4, 9, 10, 12`
+/// - `count_residues_in_interval(13, 5, 11) = 2`
+///
+/// The character sum `Σ (x/p)` vanishes over any `p` consecutive integers, so every
+/// full period contributes `(p - 1)/2` and only the last `(b - a + 1) mod p` values
+/// are tested, each by `jacobi_symbol`: O(min(b - a, p) log p) whatever the width.
+/// Errors with `OutOfRange` unless `p` is an odd prime and `a <= b`.
+pub fn count_residues_in_interval(p: u64, a: u64, b: u64) -> Result<u64, MathError> {
+    if p < 3 || a > b || !is_prime_u64(p) {
+        return Err(MathError::OutOfRange);
+    }
+    let len = (b - a) as u128 + 1;
+    let (periods, rest) = (len / p as u128, (len % p as u128) as u64);
+    let mut count = periods as u64 * ((p - 1) / 2);
+    let mut x = (b - rest.saturating_sub(1)) % p;
+    for _ in 0..rest {
+        if jacobi_symbol(x, p)? == 1 {
+            count += 1;
+        }
+        x = if x + 1 == p { 0 } else { x + 1 };
+    }
+    Ok(count)
+}
+
+/// `first_quadratic_nonresidue` for a modulus already known to be an odd prime.
+fn least_nonresidue(p: u64) -> u64 {
+    (2..)
+        .find(|&z| matches!(jacobi_symbol(z, p), Ok(-1)))
+        .unwrap_or(p)
+}
+
+/// Every square root of `a` modulo an arbitrary `n`, sorted ascending.
+///
+/// Factorizes `n` by trial division, finds the roots modulo each prime power
+/// (Tonelli-Shanks then Hensel lifting for units, bit-by-bit lifting for powers of 2,
+/// and a reduction by `p^v` when `p` divides `a`), then combines them by CRT:
+/// - `sqrt_mod_n(1, 15) = [1, 4, 11, 14]`
+/// - `sqrt_mod_n(0, 8) = [0, 4]`
+///
+/// An empty vector means `a` is not a square mod `n`. Errors with `DivisionByZero` if `n == 0`.
+pub fn sqrt_mod_n(a: u64, n: u64) -> Result<Vec<u64>, MathError> {
+    if n == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+
+    let mut roots = vec![0u64];
+    let mut modulus = 1u64;
+    for (p, e) in trial_factors(n as u128) {
+        let p = p as u64;
+        let pe = p.pow(e);
+        let local = sqrt_mod_prime_power(a % pe, p, e)?;
+        let mut combined = Vec::with_capacity(roots.len() * local.len());
+        for &x in &roots {
+            for &y in &local {
+                combined.push(crt(&[(x, modulus), (y, pe)])?.0);
+            }
+        }
+        roots = combined;
+        modulus *= pe;
+    }
+    roots.sort_unstable();
+    Ok(roots)
+}
+
+/// All roots of `x² ≡ a (mod p^e)` for `a < p^e`.
+fn sqrt_mod_prime_power(a: u64, p: u64, e: u32) -> Result<Vec<u64>, MathError> {
+    let pe = p.pow(e);
+    if a == 0 {
+        // x ≡ 0 (mod p^⌈e/2⌉)
+        let step = p.pow(e.div_ceil(2));
+        return Ok((0..pe / step).map(|j| j * step).collect());
+    }
+
+    // a = p^v·b with b a unit; x must be p^(v/2)·y with y² ≡ b (mod p^(e-v))
+    let v = (0..)
+        .find(|&v| !(a / p.pow(v)).is_multiple_of(p))
+        .unwrap_or(0);
+    if v % 2 == 1 {
+        return Ok(Vec::new());
+    }
+    let m = e - v;
+    let scale = p.pow(v / 2);
+    let pm = p.pow(m);
+    let units = sqrt_unit_prime_power(a / p.pow(v), p, m)?;
+    Ok(units
+        .iter()
+        .flat_map(|&y| (0..scale).map(move |t| scale * (y + t * pm)))
+        .collect())
+}
+
+/// Roots of `x² ≡ b (mod p^m)` for `b` coprime to `p`.
+fn sqrt_unit_prime_power(b: u64, p: u64, m: u32) -> Result<Vec<u64>, MathError> {
+    if p == 2 {
+        // lift bit by bit: each root mod 2^k gives candidates x and x + 2^k
+        let mut roots = vec![1u64];
+        for k in 1..m {
+            let next = 1u64 << (k + 1);
+            let mut lifted: Vec<u64> = roots
+                .iter()
+                .flat_map(|&x| [x, x + (1 << k)])
+                .filter(|&x| mul_mod(x, x, next) == b % next)
+                .collect();
+            lifted.sort_unstable();
+            lifted.dedup();
+            roots = lifted;
+        }
+        return Ok(roots);
+    }
+
+    let Some(mut x) = sqrt_mod(b, p)? else {
+        return Ok(Vec::new());
+    };
+    // Hensel: x ← x - (x² - b)/(2x) mod p^(k+1)
+    let mut pk = p;
+    for _ in 1..m {
+        pk *= p;
+        let f = sub_mod(mul_mod(x, x, pk), b % pk, pk);
+        let step = mul_mod(f, mod_inverse(2 * x % pk, pk)?, pk);
+        x = sub_mod(x, step, pk);
+    }
+    Ok(vec![x, pk - x])
+}
+
+/// `a·b mod m` through `u128`, for `m != 0`; `mod_mul` without the check, shared with the
+/// modules that have already checked their modulus.
+pub(crate) const fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+/// `(a + b) mod m` through `u128`, for `m != 0`, shared like `mul_mod`.
+pub(crate) const fn add_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 + b as u128) % m as u128) as u64
+}
+
+/// `(a - b) mod m` for `a, b < m`.
+pub(crate) const fn sub_mod(a: u64, b: u64, m: u64) -> u64 {
+    if a >= b { a - b } else { m - (b - a) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_roots(a: u64, n: u64) -> Vec<u64> {
+        (0..n).filter(|&x| x * x % n == a % n).collect()
+    }
+
+    #[test]
+    fn test_mod_mul() {
+        assert_eq!(mod_mul(3, 5, 7), Ok(1));
+        assert_eq!(mod_mul(u64::MAX, u64::MAX, 1_000_000_007), Ok(114_944_269));
+        assert_eq!(mod_mul(u64::MAX - 1, 2, u64::MAX), Ok(u64::MAX - 2));
+        assert_eq!(mod_mul(12, 12, 1), Ok(0));
+        assert_eq!(mod_mul(2, 3, 0), Err(MathError::DivisionByZero));
+        assert_eq!(mod_pow(3, 1 << 40, 1_000_000_007), {
+            let mut x = 3;
+            for _ in 0..40 {
+                x = mod_mul(x, x, 1_000_000_007).unwrap();
+            }
+            Ok(x)
+        });
+    }
+
+    #[test]
+    fn test_mod_pow_and_inverse() {
+        assert_eq!(mod_pow(4, 13, 497), Ok(445));
+        assert_eq!(mod_pow(7, 0, 1), Ok(0));
+        assert_eq!(mod_pow(2, 10, 0), Err(MathError::DivisionByZero));
+        const POW: Result<u64, MathError> = mod_pow(4, 13, 497);
+        assert_eq!(POW, Ok(445));
+        assert_eq!(mod_inverse(3, 11), Ok(4));
+        assert_eq!(mod_inverse(6, 9), Err(MathError::NoInverse));
+    }
+
+    #[test]
+    fn test_inverse_table() {
+        assert_eq!(inverse_table(4, 7), Ok(vec![0, 1, 4, 5, 2]));
+        assert_eq!(inverse_table(0, 7), Ok(vec![0]));
+        const P: u64 = 1_000_000_007;
+        let table = inverse_table(100_000, P).unwrap();
+        for (i, &inv) in table.iter().enumerate().skip(1) {
+            assert_eq!(mul_mod(i as u64, inv, P), 1);
+        }
+        // composite moduli work below their smallest prime factor
+        assert_eq!(
+            inverse_table(10, 143).unwrap()[1..],
+            (1..=10)
+                .map(|i| mod_inverse(i, 143).unwrap())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(inverse_table(11, 143), Err(MathError::NoSolution));
+        assert_eq!(inverse_table(7, 7), Err(MathError::NoSolution));
+        assert_eq!(inverse_table(3, 0), Err(MathError::DivisionByZero));
+        assert_eq!(inverse_table(1, 2), Ok(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_barrett_ctx() {
+        let ctx = BarrettCtx::new(1_000_000_000_000).unwrap();
+        // 10^22 + 10^12 + 21
+        assert_eq!(ctx.mul(100_000_000_003, 100_000_000_007), 21);
+        assert_eq!(BarrettCtx::new(0), Err(MathError::DivisionByZero));
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for m in [
+            1,
+            2,
+            3,
+            1 << 32,
+            (1 << 32) + 2,
+            1 << 63,
+            u64::MAX - 1,
+            u64::MAX,
+        ] {
+            let ctx = BarrettCtx::new(m).unwrap();
+            assert_eq!(ctx.modulus(), m);
+            assert_eq!(ctx.reduce(u128::MAX), (u128::MAX % m as u128) as u64);
+            for _ in 0..500 {
+                let (a, b) = (next(), next());
+                assert_eq!(ctx.mul(a, b), mul_mod(a, b, m), "{a}·{b} mod {m}");
+            }
+        }
+        for _ in 0..2000 {
+            let m = next() >> (next() % 64) | 1 << 33;
+            let x = (next() as u128) << 64 | next() as u128;
+            assert_eq!(
+                BarrettCtx::new(m).unwrap().reduce(x),
+                (x % m as u128) as u64
+            );
+        }
+    }
+
+    #[test]
+    fn test_mod_pow_both_paths() {
+        // across the 2^32 switch to Barrett, even moduli included
+        for m in [
+            (1u64 << 32) - 1,
+            1 << 32,
+            (1 << 32) + 1,
+            (1 << 32) + 2,
+            u64::MAX - 1,
+        ] {
+            for (base, exp) in [
+                (3u64, 1_000_003u64),
+                (u64::MAX, 77),
+                (2, 64),
+                (12_345, u64::MAX),
+            ] {
+                let mut expected = 1 % m as u128;
+                let (mut b, mut e) = (base as u128 % m as u128, exp);
+                while e > 0 {
+                    if e & 1 == 1 {
+                        expected = expected * b % m as u128;
+                    }
+                    b = b * b % m as u128;
+                    e >>= 1;
+                }
+                assert_eq!(
+                    mod_pow(base, exp, m),
+                    Ok(expected as u64),
+                    "{base}^{exp} mod {m}"
+                );
+            }
+        }
+        const BIG: Result<u64, MathError> = mod_pow(2, 100, 1 << 40);
+        assert_eq!(BIG, Ok(0));
+    }
+
+    #[test]
+    fn test_fixed_base_pow() {
+        let g = FixedBasePow::new(4, 497).unwrap();
+        assert_eq!(g.pow(13), 445);
+        assert_eq!((g.base(), g.modulus()), (4, 497));
+        let p = 0xffff_ffff_0000_0001;
+        for base in [0, 1, 2, 7, u64::MAX] {
+            let g = FixedBasePow::new(base, p).unwrap();
+            let mut exp = 0x9e37_79b9_7f4a_7c15u64;
+            for _ in 0..200 {
+                assert_eq!(Ok(g.pow(exp)), mod_pow(base, exp, p), "{base}^{exp}");
+                exp = exp.rotate_left(7) ^ (exp >> 3);
+            }
+            for exp in [0, 1, 255, 256, u64::MAX] {
+                assert_eq!(Ok(g.pow(exp)), mod_pow(base, exp, p));
+            }
+        }
+        assert_eq!(FixedBasePow::new(5, 1).unwrap().pow(0), 0);
+        assert_eq!(FixedBasePow::new(5, 0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_multi_pow_mod() {
+        assert_eq!(multi_pow_mod(&[(2, 10), (3, 5)], 1000), Ok(832));
+        assert_eq!(multi_pow_mod(&[], 7), Ok(1));
+        assert_eq!(multi_pow_mod(&[], 1), Ok(0));
+        assert_eq!(multi_pow_mod(&[(5, 0), (9, 0)], 11), Ok(1));
+        assert_eq!(multi_pow_mod(&[(2, 3)], 0), Err(MathError::DivisionByZero));
+        let p = 0xffff_ffff_0000_0001;
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for k in 1..=5 {
+            for _ in 0..20 {
+                let pairs: Vec<(u64, u64)> =
+                    (0..k).map(|_| (next(), next() >> (next() % 64))).collect();
+                let expected = pairs
+                    .iter()
+                    .fold(1, |acc, &(b, e)| mul_mod(acc, mod_pow(b, e, p).unwrap(), p));
+                assert_eq!(multi_pow_mod(&pairs, p), Ok(expected), "{pairs:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Ok((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), Err(MathError::NoSolution));
+        assert_eq!(crt(&[]), Ok((0, 1)));
+        assert_eq!(
+            crt(&[(0, u64::MAX), (1, u64::MAX - 1)]),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_crt_reconstruct() {
+        assert_eq!(crt_reconstruct(&[(2, 3), (3, 5), (2, 7)]), Ok(23));
+        assert_eq!(crt_reconstruct(&[]), Ok(0));
+        // residues are reduced first
+        assert_eq!(crt_reconstruct(&[(5, 3), (8, 5)]), Ok(8));
+        for a in 0..60u64 {
+            let system = [(a % 4, 4), (a % 9, 9), (a % 5, 5), (a % 7, 7)];
+            assert_eq!(
+                crt_reconstruct(&system),
+                Ok(crt(&system).unwrap().0 as u128)
+            );
+        }
+        assert_eq!(
+            crt_reconstruct(&[(1, 6), (2, 9)]),
+            Err(MathError::NoSolution)
+        );
+        assert_eq!(crt_reconstruct(&[(1, 0)]), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_garner() {
+        let garner = Garner::new(&[3, 5, 7]).unwrap();
+        assert_eq!(garner.moduli(), &[3, 5, 7]);
+        assert_eq!(garner.reconstruct(&[2, 3, 2]), Ok(23));
+        assert_eq!(garner.reconstruct_symmetric(&[1, 0, 1]), Ok(-20));
+        for x in -52i128..=52 {
+            let residues: Vec<u64> = [3i128, 5, 7]
+                .iter()
+                .map(|&m| x.rem_euclid(m) as u64)
+                .collect();
+            assert_eq!(garner.reconstruct_symmetric(&residues), Ok(x));
+            assert_eq!(garner.reconstruct(&residues), Ok(x.rem_euclid(105) as u128));
+        }
+        assert_eq!(
+            garner.reconstruct(&[1, 2]),
+            Err(MathError::DimensionMismatch)
+        );
+
+        // a product of three primes near 2^62 exceeds u128, but small values still fit
+        let primes = [(1u64 << 62) - 57, (1 << 62) - 87, (1 << 62) - 117];
+        let garner = Garner::new(&primes).unwrap();
+        let x: u128 = (1 << 127) + 12_345_678_901_234_567;
+        let residues: Vec<u64> = primes.iter().map(|&p| (x % p as u128) as u64).collect();
+        assert_eq!(garner.reconstruct(&residues), Ok(x));
+        let y: i128 = -(1 << 120) - 987_654_321;
+        let residues: Vec<u64> = primes
+            .iter()
+            .map(|&p| y.rem_euclid(p as i128) as u64)
+            .collect();
+        assert_eq!(garner.reconstruct_symmetric(&residues), Ok(y));
+        let top: Vec<u64> = primes.iter().map(|&p| p - 1).collect();
+        assert_eq!(garner.reconstruct(&top), Err(MathError::Overflow));
+        assert_eq!(garner.reconstruct_symmetric(&top), Ok(-1));
+    }
+
+    #[cfg(feature = "internal-bigint")]
+    #[test]
+    fn test_crt_reconstruct_big() {
+        let primes = [
+            (1u64 << 62) - 57,
+            (1 << 62) - 87,
+            (1 << 62) - 117,
+            (1 << 61) - 1,
+        ];
+        let product = primes
+            .iter()
+            .fold(BigU::one(), |acc, &p| &acc * &BigU::from_u64(p));
+        let system: Vec<(u64, u64)> = primes.iter().map(|&p| (p - 1, p)).collect();
+        assert_eq!(
+            crt_reconstruct_big(&system),
+            Ok(product.checked_sub(&BigU::one()).unwrap())
+        );
+        assert_eq!(
+            crt_reconstruct_big(&[(2, 3), (3, 5), (2, 7)]),
+            Ok(BigU::from_u64(23))
+        );
+    }
+
+    #[test]
+    fn test_rational_reconstruction() {
+        let r = |p, q| Some(Rational::new(p, q).unwrap());
+        assert_eq!(rational_reconstruction(61, 101), Ok(r(2, 5)));
+        assert_eq!(rational_reconstruction(857_145, 1_000_003), Ok(r(-3, 7)));
+        assert_eq!(rational_reconstruction(0, 97), Ok(r(0, 1)));
+        assert_eq!(rational_reconstruction(5, 1), Ok(None));
+        assert_eq!(
+            rational_reconstruction(5, 0),
+            Err(MathError::DivisionByZero)
+        );
+        // a fraction recovered from its residues modulo three primes, through crt
+        let primes = [1_000_003u64, 1_000_033, 1_000_037];
+        let (p, q) = (-123_456_789i64, 987_654_321i64);
+        let residues: Vec<(u64, u64)> = primes
+            .iter()
+            .map(|&m| {
+                let p = p.rem_euclid(m as i64) as u64;
+                (mul_mod(p, mod_inverse(q as u64, m).unwrap(), m), m)
+            })
+            .collect();
+        let (a, m) = crt(&residues).unwrap();
+        assert_eq!(rational_reconstruction(a, m), Ok(r(p, q)));
+        // one prime is not enough to pin it down
+        assert_ne!(
+            rational_reconstruction(residues[0].0, primes[0]),
+            Ok(r(p, q))
+        );
+    }
+
+    #[test]
+    fn test_rational_reconstruction_against_brute_force() {
+        for m in [2u64, 10, 97, 101, 128, 1000] {
+            let bound = ((m - 1) / 2).isqrt() as i64;
+            for a in 0..m {
+                let matches: Vec<Rational> = (1..=bound)
+                    .flat_map(|q| (-bound..=bound).map(move |p| (p, q)))
+                    .filter(|&(p, q)| {
+                        gcd_u64(p.unsigned_abs(), q as u64) == 1
+                            && (p - a as i64 * q).rem_euclid(m as i64) == 0
+                    })
+                    .map(|(p, q)| Rational::new(p, q).unwrap())
+                    .collect();
+                assert!(matches.len() <= 1, "{a} mod {m}");
+                let found = rational_reconstruction(a, m).unwrap();
+                assert_eq!(found, matches.first().copied(), "{a} mod {m}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_mod_prime() {
+        // p ≡ 1 (mod 8) exercises the full Tonelli-Shanks loop
+        for p in [2, 3, 13, 17, 41, 1_000_000_007, 998_244_353] {
+            for a in [0, 1, 2, 3, 5, 10, 12_345] {
+                match sqrt_mod(a, p).unwrap() {
+                    Some(r) => assert_eq!(mul_mod(r, r, p), a % p),
+                    None => assert_ne!(mod_pow(a, (p - 1) / 2, p), Ok(1)),
+                }
+            }
+        }
+        assert_eq!(sqrt_mod(5, 13), Ok(None));
+        assert_eq!(sqrt_mod(1, 1), Err(MathError::OutOfRange));
+        // a square modulus has no non-residue to start from, and 2² ≡ 4 (mod 15)
+        assert_eq!(sqrt_mod(1, 9), Err(MathError::OutOfRange));
+        assert_eq!(sqrt_mod(4, 15), Err(MathError::OutOfRange));
+        assert_eq!(sqrt_mod(4, 0), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_legendre_and_jacobi() {
+        assert_eq!(legendre_symbol(10, 13), Ok(1));
+        assert_eq!(legendre_symbol(5, 13), Ok(-1));
+        assert_eq!(legendre_symbol(26, 13), Ok(0));
+        assert_eq!(legendre_symbol(1, 2), Err(MathError::OutOfRange));
+        for n in (1..200).step_by(2) {
+            let factors = trial_factors(n as u128);
+            for a in 0..n {
+                let expected = factors.iter().fold(1, |acc, &(p, e)| {
+                    acc * legendre_symbol(a, p as u64).unwrap().pow(e)
+                });
+                assert_eq!(jacobi_symbol(a, n), Ok(expected), "({a}/{n})");
+            }
+        }
+        assert_eq!(jacobi_symbol(2, 15), Ok(1));
+        assert_eq!(jacobi_symbol(3, 10), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_first_quadratic_nonresidue() {
+        assert_eq!(first_quadratic_nonresidue(7), Ok(3));
+        assert_eq!(first_quadratic_nonresidue(13), Ok(2));
+        assert_eq!(first_quadratic_nonresidue(71), Ok(7));
+        for p in (3..2_000).filter(|&p| is_prime_u64(p)) {
+            let squares: Vec<u64> = (1..p).map(|x| x * x % p).collect();
+            let expected = (2..p).find(|z| !squares.contains(z));
+            assert_eq!(first_quadratic_nonresidue(p).ok(), expected, "p = {p}");
+        }
+        // record holders: the first primes whose least non-residue is 43 and 47
+        assert_eq!(first_quadratic_nonresidue(366_791), Ok(43));
+        assert_eq!(first_quadratic_nonresidue(3_818_929), Ok(47));
+        assert_eq!(first_quadratic_nonresidue(u64::MAX - 58), Ok(2));
+        for n in [0, 1, 2, 9, 15, 25] {
+            assert_eq!(first_quadratic_nonresidue(n), Err(MathError::OutOfRange));
+        }
+    }
+
+    #[test]
+    fn test_count_residues_in_interval() {
+        assert_eq!(count_residues_in_interval(13, 1, 12), Ok(6));
+        assert_eq!(count_residues_in_interval(13, 5, 11), Ok(2));
+        assert_eq!(count_residues_in_interval(13, 13, 13), Ok(0));
+        for p in [3, 5, 7, 13, 31] {
+            for a in 0..70 {
+                for b in a..70 {
+                    let expected = (a..=b).filter(|&x| legendre_symbol(x, p) == Ok(1)).count();
+                    assert_eq!(
+                        count_residues_in_interval(p, a, b),
+                        Ok(expected as u64),
+                        "p = {p}, {a}..={b}"
+                    );
+                }
+            }
+        }
+        // whole periods cost nothing, whatever the width
+        let p = 1_000_000_007;
+        let k = 1_000_000_000;
+        assert_eq!(
+            count_residues_in_interval(p, 12, 12 + k * p - 1),
+            Ok(k * ((p - 1) / 2))
+        );
+        // 2^64 - 1 ≡ 0 (mod 3): the residues 1, 4, ..., 2^64 - 3
+        assert_eq!(count_residues_in_interval(3, 0, u64::MAX), Ok(u64::MAX / 3));
+        assert_eq!(
+            count_residues_in_interval(4, 0, 9),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            count_residues_in_interval(13, 9, 8),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_sqrt_mod_n_matches_brute_force() {
+        for n in 1..200 {
+            for a in 0..n {
+                assert_eq!(sqrt_mod_n(a, n).unwrap(), brute_roots(a, n), "{a} mod {n}");
+            }
+        }
+        assert_eq!(sqrt_mod_n(1, 15), Ok(vec![1, 4, 11, 14]));
+        assert_eq!(sqrt_mod_n(0, 8), Ok(vec![0, 4]));
+    }
+
+    #[test]
+    fn test_sqrt_mod_n_large_prime_powers() {
+        let n = 1_000_003u64 * 1_000_003;
+        let roots = sqrt_mod_n(mul_mod(12_345, 12_345, n), n).unwrap();
+        assert_eq!(roots, vec![12_345, n - 12_345]);
+        // 2 is not a residue mod 1_000_003 ≡ 3 (mod 8)
+        assert_eq!(sqrt_mod_n(2, n), Ok(vec![]));
+
+        // Rabin-style modulus: four roots
+        let n = 1_000_003 * 999_983;
+        let m = 123_456_789_u64;
+        let roots = sqrt_mod_n(mul_mod(m, m, n), n).unwrap();
+        assert_eq!(roots.len(), 4);
+        assert!(roots.contains(&m));
+        assert_eq!(sqrt_mod_n(4, 0), Err(MathError::DivisionByZero));
+    }
+}
@@ -0,0 +1,312 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::divisors::trial_factors;
+use crate::algebra::gcd::gcd_u128;
+use crate::algebra::modular::{mod_inverse, mod_pow, mul_mod};
+use crate::algebra::poly_mod::PolyMod;
+use crate::algebra::prime_nbr::is_prime_u64;
+use crate::algebra::product_tree::{product_tree, remainder_tree};
+use crate::errors::MathError;
+
+/// The primitive `n`-th root of unity used by every transform mod `p`: `g^((p-1)/n)`
+/// with `g` the smallest primitive root of `p`.
+/// - `root_of_unity(4, 17) = 13`, from `g = 3`
+///
+/// Fixing the choice lets `ntt` and `inverse_ntt` (and their users) agree on the
+/// evaluation points. Errors with `OutOfRange` unless `p` is prime and `n` divides
+/// `p - 1`.
+pub fn root_of_unity(n: usize, p: u64) -> Result<u64, MathError> {
+    nth_root_of_unity(n as u64, p)?.ok_or(MathError::OutOfRange)
+}
+
+/// A primitive `n`-th root of unity mod the prime `p`, the same one as `root_of_unity`,
+/// or `None` when there is none because `n` does not divide `p - 1`:
+/// - `nth_root_of_unity(4, 17) = Some(13)`
+/// - `nth_root_of_unity(3, 17) = None`
+///
+/// Errors with `OutOfRange` if `p` is not prime or `n == 0`.
+pub fn nth_root_of_unity(n: u64, p: u64) -> Result<Option<u64>, MathError> {
+    if !is_prime_u64(p) || n == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    if !(p - 1).is_multiple_of(n) {
+        return Ok(None);
+    }
+    mod_pow(primitive_root(p)?, (p - 1) / n, p).map(Some)
+}
+
+/// Every solution of `x^n ≡ 1 (mod p)` for the prime `p`, ascending:
+/// - `nth_roots_of_unity(4, 17) = [1, 4, 13, 16]`
+/// - `nth_roots_of_unity(6, 17) = [1, 16]`, only the square roots since `gcd(6, 16) = 2`
+///
+/// The unit group is cyclic of order `p - 1`, so these are the `d = gcd(n, p - 1)`
+/// powers of a primitive `d`-th root, found in O(d) after the primitive root. Errors
+/// with `OutOfRange` if `p` is not prime or `n == 0`.
+pub fn nth_roots_of_unity(n: u64, p: u64) -> Result<Vec<u64>, MathError> {
+    if !is_prime_u64(p) || n == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    let d = gcd_u128(n as u128, (p - 1) as u128) as u64;
+    let w = mod_pow(primitive_root(p)?, (p - 1) / d, p)?;
+    let mut roots: Vec<u64> = core::iter::successors(Some(1), |&x| Some(mul_mod(x, w, p)))
+        .take(d as usize)
+        .collect();
+    roots.sort_unstable();
+    Ok(roots)
+}
+
+/// Number-theoretic transform: `A[k] = Σ a[j]·ω^(jk) mod p` with
+/// `ω = root_of_unity(n, p)` and `n = values.len()`:
+/// - `ntt(&[1, 1, 0, 0], 17) = [2, 14, 0, 5]`, the values of `1 + x` at `1, 13, 16, 4`
+///
+/// Iterative radix-2 Cooley-Tukey, O(n log n). Errors with `EmptyInput` for no values
+/// and `OutOfRange` unless `n` is a power of two dividing `p - 1` with `p` prime.
+pub fn ntt(values: &[u64], p: u64) -> Result<Vec<u64>, MathError> {
+    transform(values, p, false)
+}
+
+/// Inverse of `ntt`: `a[j] = n⁻¹ · Σ A[k]·ω^(-jk) mod p`. Same errors as `ntt`.
+pub fn inverse_ntt(values: &[u64], p: u64) -> Result<Vec<u64>, MathError> {
+    let n = values.len();
+    let scale = mod_inverse(n as u64 % p, p)?;
+    Ok(transform(values, p, true)?
+        .into_iter()
+        .map(|a| mul_mod(a, scale, p))
+        .collect())
+}
+
+/// Values of `poly` at the `n`-th roots of unity `ω^0, ω^1, ..., ω^(n-1)`, where `n` is
+/// the smallest power of two above its degree and `ω = root_of_unity(n, p)`:
+/// - `1 + x` mod 17 gives `[2, 0]` at `ω = 16 = -1`
+///
+/// One `ntt` of the zero-padded coefficients. Errors with `OutOfRange` unless the
+/// modulus is prime and `n` divides `p - 1`.
+pub fn evaluate_at_roots(poly: &PolyMod) -> Result<Vec<u64>, MathError> {
+    let n = poly.coeffs().len().max(1).next_power_of_two();
+    let mut coeffs = poly.coeffs().to_vec();
+    coeffs.resize(n, 0);
+    ntt(&coeffs, poly.modulus())
+}
+
+/// The polynomial of degree below `n = values.len()` taking `values[k]` at `ω^k`, the
+/// inverse of `evaluate_at_roots`:
+/// - `interpolate_from_roots(&[2, 0], 17) = 1 + x`
+///
+/// One `inverse_ntt`; same errors as `ntt`.
+pub fn interpolate_from_roots(values: &[u64], p: u64) -> Result<PolyMod, MathError> {
+    PolyMod::new(inverse_ntt(values, p)?, p)
+}
+
+/// Values of `poly` at arbitrary `points`, in order.
+///
+/// The points become the leaves `x - xᵢ` of a `product_tree` and `remainder_tree`
+/// reduces `poly` down to its constant remainders `poly mod (x - xᵢ) = poly(xᵢ)`, so the
+/// work is shared between points instead of running Horner once per point. Errors with
+/// `EmptyInput` for no points.
+pub fn evaluate_at_points(poly: &PolyMod, points: &[u64]) -> Result<Vec<u64>, MathError> {
+    let p = poly.modulus();
+    let leaves = points
+        .iter()
+        .map(|&x| PolyMod::new(vec![p - x % p, 1], p))
+        .collect::<Result<Vec<PolyMod>, MathError>>()?;
+    let tree = product_tree(&leaves)?;
+    Ok(remainder_tree(poly, &tree)?
+        .iter()
+        .map(|r| r.coeffs().first().copied().unwrap_or(0))
+        .collect())
+}
+
+/// Shared body of `ntt` and `inverse_ntt`, the latter running with `ω⁻¹` and unscaled.
+fn transform(values: &[u64], p: u64, inverse: bool) -> Result<Vec<u64>, MathError> {
+    let n = values.len();
+    if n == 0 {
+        return Err(MathError::EmptyInput);
+    }
+    if !n.is_power_of_two() {
+        return Err(MathError::OutOfRange);
+    }
+    let mut root = root_of_unity(n, p)?;
+    if inverse {
+        root = mod_inverse(root, p)?;
+    }
+
+    let mut a: Vec<u64> = values.iter().map(|&v| v % p).collect();
+    let bits = n.trailing_zeros();
+    if bits > 0 {
+        for i in 0..n {
+            let j = i.reverse_bits() >> (usize::BITS - bits);
+            if i < j {
+                a.swap(i, j);
+            }
+        }
+    }
+
+    // butterflies on blocks of doubling length, each twiddled by a root of its order
+    let mut len = 2;
+    while len <= n {
+        let step = mod_pow(root, (n / len) as u64, p)?;
+        for block in a.chunks_mut(len) {
+            let (low, high) = block.split_at_mut(len / 2);
+            let mut w = 1;
+            for (u, v) in low.iter_mut().zip(high.iter_mut()) {
+                let t = mul_mod(*v, w, p);
+                (*u, *v) = ((*u + t) % p, (*u + p - t) % p);
+                w = mul_mod(w, step, p);
+            }
+        }
+        len <<= 1;
+    }
+    Ok(a)
+}
+
+/// Smallest primitive root of the prime `p`.
+fn primitive_root(p: u64) -> Result<u64, MathError> {
+    let prime_factors: Vec<u64> = trial_factors((p - 1) as u128)
+        .into_iter()
+        .map(|(q, _)| q as u64)
+        .collect();
+    for g in 1..p {
+        let mut is_generator = true;
+        for &q in &prime_factors {
+            if mod_pow(g, (p - 1) / q, p)? == 1 {
+                is_generator = false;
+                break;
+            }
+        }
+        if is_generator {
+            return Ok(g);
+        }
+    }
+    Err(MathError::NoSolution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: u64 = 998_244_353; // 119·2^23 + 1
+
+    fn naive_dft(values: &[u64], p: u64) -> Vec<u64> {
+        let n = values.len();
+        let w = root_of_unity(n, p).unwrap();
+        (0..n)
+            .map(|k| {
+                values.iter().enumerate().fold(0, |acc, (j, &a)| {
+                    let wjk = mod_pow(w, (j * k) as u64, p).unwrap();
+                    (acc + mul_mod(a, wjk, p)) % p
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        assert_eq!(root_of_unity(4, 17), Ok(13));
+        assert_eq!(root_of_unity(1, 17), Ok(1));
+        assert_eq!(root_of_unity(1, 2), Ok(1));
+        let w = root_of_unity(1 << 23, P).unwrap();
+        assert_eq!(mod_pow(w, 1 << 23, P), Ok(1));
+        assert_ne!(mod_pow(w, 1 << 22, P), Ok(1));
+        assert_eq!(root_of_unity(3, 17), Err(MathError::OutOfRange));
+        assert_eq!(root_of_unity(2, 15), Err(MathError::OutOfRange));
+        assert_eq!(root_of_unity(0, 17), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_nth_roots_of_unity() {
+        assert_eq!(nth_root_of_unity(4, 17), Ok(Some(13)));
+        assert_eq!(nth_root_of_unity(3, 17), Ok(None));
+        assert_eq!(nth_root_of_unity(16, 17), Ok(Some(3)));
+        assert_eq!(nth_root_of_unity(0, 17), Err(MathError::OutOfRange));
+        assert_eq!(nth_root_of_unity(2, 15), Err(MathError::OutOfRange));
+        assert_eq!(nth_roots_of_unity(4, 17), Ok(vec![1, 4, 13, 16]));
+        assert_eq!(nth_roots_of_unity(6, 17), Ok(vec![1, 16]));
+        assert_eq!(nth_roots_of_unity(5, 17), Ok(vec![1]));
+        assert_eq!(nth_roots_of_unity(3, 2), Ok(vec![1]));
+        assert_eq!(nth_roots_of_unity(1, 21), Err(MathError::OutOfRange));
+
+        // against brute force, and a primitive root has exact order n
+        for p in [13u64, 17, 31, 97] {
+            for n in 1..=2 * p {
+                let brute: Vec<u64> = (1..p).filter(|&x| mod_pow(x, n, p) == Ok(1)).collect();
+                assert_eq!(nth_roots_of_unity(n, p), Ok(brute), "n = {n}, p = {p}");
+                if let Ok(Some(w)) = nth_root_of_unity(n, p) {
+                    let order = (1..=n).find(|&k| mod_pow(w, k, p) == Ok(1));
+                    assert_eq!(order, Some(n), "n = {n}, p = {p}");
+                }
+            }
+        }
+        let roots = nth_roots_of_unity(1 << 10, P).unwrap();
+        assert_eq!(roots.len(), 1 << 10);
+        assert!(roots.iter().all(|&x| mod_pow(x, 1 << 10, P) == Ok(1)));
+    }
+
+    #[test]
+    fn test_ntt_matches_naive_dft() {
+        assert_eq!(ntt(&[1, 1, 0, 0], 17), Ok(vec![2, 14, 0, 5]));
+        for n in [1usize, 2, 4, 8, 16, 64] {
+            let values: Vec<u64> = (0..n as u64).map(|i| (i * i * 7 + 3) % P).collect();
+            let transformed = ntt(&values, P).unwrap();
+            assert_eq!(transformed, naive_dft(&values, P), "n = {n}");
+            assert_eq!(inverse_ntt(&transformed, P), Ok(values));
+        }
+        assert_eq!(ntt(&[], 17), Err(MathError::EmptyInput));
+        assert_eq!(ntt(&[1, 2, 3], 17), Err(MathError::OutOfRange));
+        assert_eq!(ntt(&[1; 32], 17), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_evaluate_and_interpolate_at_roots() {
+        let poly = PolyMod::new(vec![5, 0, 3, 1, 4], P).unwrap();
+        let values = evaluate_at_roots(&poly).unwrap();
+        assert_eq!(values.len(), 8);
+        let w = root_of_unity(8, P).unwrap();
+        for (k, &v) in values.iter().enumerate() {
+            assert_eq!(v, poly.eval(mod_pow(w, k as u64, P).unwrap()));
+        }
+        assert_eq!(interpolate_from_roots(&values, P), Ok(poly));
+
+        let line = PolyMod::new(vec![1, 1], 17).unwrap();
+        assert_eq!(evaluate_at_roots(&line), Ok(vec![2, 0]));
+        assert_eq!(interpolate_from_roots(&[2, 0], 17), Ok(line));
+        let zero = PolyMod::zero(17).unwrap();
+        assert_eq!(evaluate_at_roots(&zero), Ok(vec![0]));
+        // degree 4 needs 8 points, and 8 does not divide 10
+        let poly = PolyMod::new(vec![1, 2, 3, 4, 5], 11).unwrap();
+        assert_eq!(evaluate_at_roots(&poly), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_ntt_convolution() {
+        // pointwise products of transforms multiply polynomials
+        let a = PolyMod::new(vec![3, 1, 4, 1, 5], P).unwrap();
+        let b = PolyMod::new(vec![9, 2, 6], P).unwrap();
+        let pad = |poly: &PolyMod| {
+            let mut c = poly.coeffs().to_vec();
+            c.resize(8, 0);
+            ntt(&c, P).unwrap()
+        };
+        let product: Vec<u64> = pad(&a)
+            .iter()
+            .zip(pad(&b))
+            .map(|(&x, y)| mul_mod(x, y, P))
+            .collect();
+        assert_eq!(interpolate_from_roots(&product, P), a.mul(&b));
+    }
+
+    #[test]
+    fn test_evaluate_at_points() {
+        let poly = PolyMod::new(vec![7, 0, 2, 9, 1, 3], 101).unwrap();
+        let points: Vec<u64> = (0..37).map(|i| i * 13 + 5).collect();
+        let expected: Vec<u64> = points.iter().map(|&x| poly.eval(x)).collect();
+        assert_eq!(evaluate_at_points(&poly, &points), Ok(expected));
+        // more points than the degree, repeated points, and a composite modulus
+        let poly = PolyMod::new(vec![1, 1, 1], 12).unwrap();
+        assert_eq!(
+            evaluate_at_points(&poly, &[0, 1, 2, 2, 11]),
+            Ok(vec![1, 3, 7, 7, 1])
+        );
+        assert_eq!(evaluate_at_points(&poly, &[]), Err(MathError::EmptyInput));
+    }
+}
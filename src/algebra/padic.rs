@@ -0,0 +1,444 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::modular::{add_mod, mod_inverse, mul_mod, sqrt_mod, sub_mod};
+use crate::algebra::polynomial::Polynomial;
+use crate::algebra::prime_nbr::miller_rabin_u128;
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Truncated p-adic number `p^valuation · (d₀ + d₁p + ... + d_{n-1}p^(n-1))`, `n` the precision.
+///
+/// Precision is relative: every non-zero value keeps exactly `precision` digits from its
+/// leading one (`d₀ ≠ 0`), so `p^precision` must fit in a `u64`. Digits lost to
+/// cancellation in a subtraction are filled with zeros, as are digits shifted out by a
+/// product. Zero is exact and has valuation 0.
+///
+/// - `-1` in Z₇ to 3 digits is `6 + 6·7 + 6·7²`
+/// - `1/3` in Z₅ to 4 digits is `2 + 3·5 + 1·5² + 3·5³`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Padic {
+    p: u64,
+    precision: u32,
+    valuation: i32,
+    digits: Vec<u64>,
+}
+
+impl Padic {
+    /// Zero with the given prime and precision.
+    ///
+    /// Errors with `OutOfRange` if `p` is not prime, `PositifIntegerRequired` for a
+    /// precision of 0 and `Overflow` if `p^precision` does not fit in a `u64`.
+    pub fn zero(p: u64, precision: u32) -> Result<Self, MathError> {
+        check_params(p, precision)?;
+        Ok(Padic {
+            p,
+            precision,
+            valuation: 0,
+            digits: vec![0; precision as usize],
+        })
+    }
+
+    /// `n` as a p-adic number:
+    /// - `from_integer(50, 5, 3)` has valuation 2 and digits `[2, 0, 0]`
+    ///
+    /// Errors as `zero`.
+    pub fn from_integer(n: i64, p: u64, precision: u32) -> Result<Self, MathError> {
+        let pn = check_params(p, precision)?;
+        if n == 0 {
+            return Padic::zero(p, precision);
+        }
+        let mut n = n as i128;
+        let mut valuation = 0;
+        while n % p as i128 == 0 {
+            n /= p as i128;
+            valuation += 1;
+        }
+        from_parts(p, precision, valuation, n.rem_euclid(pn as i128) as u64)
+    }
+
+    /// `numer / denom` as a p-adic number; a denominator divisible by `p` gives a
+    /// negative valuation. Errors as `zero`.
+    pub fn from_rational(r: &Rational, p: u64, precision: u32) -> Result<Self, MathError> {
+        Padic::from_integer(r.numer(), p, precision)?.checked_div(&Padic::from_integer(
+            r.denom(),
+            p,
+            precision,
+        )?)
+    }
+
+    /// Root of `f` lifted from a simple root `approx` modulo `p`, by Hensel's lemma.
+    ///
+    /// Newton's iteration `x ← x - f(x)/f'(x)` doubles the number of correct digits at
+    /// each step:
+    /// - `x² + 1` from `approx = 2` gives the square root of `-1` in Z₅ starting `2 + 1·5`
+    ///
+    /// Errors with `NoSolution` unless `f(approx) ≡ 0` and `f'(approx) ≢ 0 (mod p)`,
+    /// otherwise as `zero`.
+    pub fn hensel_root(
+        f: &Polynomial<i64>,
+        approx: u64,
+        p: u64,
+        precision: u32,
+    ) -> Result<Self, MathError> {
+        let pn = check_params(p, precision)?;
+        let df = f.derivative()?;
+        let mut x = approx % p;
+        if eval_mod(f, x, p) != 0 || eval_mod(&df, x, p) == 0 {
+            return Err(MathError::NoSolution);
+        }
+        let mut k = 1;
+        while k < precision {
+            k = (2 * k).min(precision);
+            let pk = p.pow(k);
+            let step = mul_mod(
+                eval_mod(f, x, pk),
+                mod_inverse(eval_mod(&df, x, pk), pk)?,
+                pk,
+            );
+            x = sub_mod(x, step, pk);
+        }
+        from_parts(p, precision, 0, x % pn)
+    }
+
+    pub fn p(&self) -> u64 {
+        self.p
+    }
+
+    pub fn precision(&self) -> u32 {
+        self.precision
+    }
+
+    pub fn valuation(&self) -> i32 {
+        self.valuation
+    }
+
+    /// Base-`p` digits of the unit part, least significant first.
+    pub fn digits(&self) -> &[u64] {
+        &self.digits
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.iter().all(|&d| d == 0)
+    }
+
+    /// The value modulo `p^precision`, for a p-adic integer:
+    /// - `1/3` in Z₅ to 4 digits gives `417`, since `3·417 ≡ 1 (mod 625)`
+    ///
+    /// Errors with `OutOfRange` for a negative valuation.
+    pub fn to_residue(&self) -> Result<u64, MathError> {
+        if self.valuation < 0 {
+            return Err(MathError::OutOfRange);
+        }
+        let pn = self.p.pow(self.precision);
+        let shift = (self.valuation as u32).min(self.precision);
+        Ok(mul_mod(self.unit(), self.p.pow(shift), pn))
+    }
+
+    /// Errors with `DimensionMismatch` if `p` or the precision differ.
+    pub fn checked_add(&self, other: &Padic) -> Result<Padic, MathError> {
+        self.check_compatible(other)?;
+        if self.is_zero() {
+            return Ok(other.clone());
+        }
+        if other.is_zero() {
+            return Ok(self.clone());
+        }
+        let (low, high) = if self.valuation <= other.valuation {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let shift = (high.valuation - low.valuation) as u32;
+        if shift >= self.precision {
+            return Ok(low.clone());
+        }
+        let pn = self.p.pow(self.precision);
+        let aligned = mul_mod(high.unit(), self.p.pow(shift), pn);
+        from_parts(
+            self.p,
+            self.precision,
+            low.valuation as i64,
+            add_mod(low.unit(), aligned, pn),
+        )
+    }
+
+    pub fn checked_sub(&self, other: &Padic) -> Result<Padic, MathError> {
+        self.checked_add(&other.neg())
+    }
+
+    /// Errors with `DimensionMismatch` as `checked_add` and `Overflow` if the
+    /// valuation leaves the `i32` range.
+    pub fn checked_mul(&self, other: &Padic) -> Result<Padic, MathError> {
+        self.check_compatible(other)?;
+        if self.is_zero() || other.is_zero() {
+            return Padic::zero(self.p, self.precision);
+        }
+        let pn = self.p.pow(self.precision);
+        from_parts(
+            self.p,
+            self.precision,
+            self.valuation as i64 + other.valuation as i64,
+            mul_mod(self.unit(), other.unit(), pn),
+        )
+    }
+
+    /// `DivisionByZero` if `other` is zero.
+    pub fn checked_div(&self, other: &Padic) -> Result<Padic, MathError> {
+        self.checked_mul(&other.recip()?)
+    }
+
+    pub fn neg(&self) -> Padic {
+        let pn = self.p.pow(self.precision);
+        let mut result = self.clone();
+        result.digits = to_digits((pn - self.unit()) % pn, self.p, self.precision);
+        result
+    }
+
+    /// `1 / self`: the unit part is inverted mod `p^precision` and the valuation negated.
+    ///
+    /// Errors with `DivisionByZero` for zero.
+    pub fn recip(&self) -> Result<Padic, MathError> {
+        if self.is_zero() {
+            return Err(MathError::DivisionByZero);
+        }
+        let pn = self.p.pow(self.precision);
+        from_parts(
+            self.p,
+            self.precision,
+            -(self.valuation as i64),
+            mod_inverse(self.unit(), pn)?,
+        )
+    }
+
+    /// A square root, or `None` if `self` is not a p-adic square.
+    ///
+    /// The valuation must be even and the unit part a square mod `p` (mod 8 for
+    /// `p = 2`). For odd `p` the root mod `p` comes from Tonelli-Shanks and is
+    /// Hensel-lifted; of the two roots, the one with the smaller residue is returned.
+    /// For `p = 2` the root is only determined mod `2^(precision-1)`, so its last
+    /// digit is 0.
+    /// - `sqrt(2)` in Z₇ starts `3 + 1·7 + 2·7²`
+    pub fn sqrt(&self) -> Result<Option<Padic>, MathError> {
+        if self.is_zero() {
+            return Ok(Some(self.clone()));
+        }
+        if self.valuation % 2 != 0 {
+            return Ok(None);
+        }
+        let (p, precision, u) = (self.p, self.precision, self.unit());
+        let root = if p == 2 {
+            let low = 1 << precision.min(3);
+            if u % low != 1 % low {
+                return Ok(None);
+            }
+            // x² ≡ u (mod 2^k) gives x or x + 2^(k-1) as a root mod 2^(k+1)
+            let mut x = 1u64;
+            for k in 3..precision {
+                if mul_mod(x, x, 1 << (k + 1)) != u % (1 << (k + 1)) {
+                    x += 1 << (k - 1);
+                }
+            }
+            let half = 1 << (precision - 1).max(1);
+            (x % half).min(half - x % half)
+        } else {
+            let Some(mut x) = sqrt_mod(u % p, p)? else {
+                return Ok(None);
+            };
+            // x ← x - (x² - u)/(2x) mod p^k
+            let mut k = 1;
+            while k < precision {
+                k = (2 * k).min(precision);
+                let pk = p.pow(k);
+                let f = sub_mod(mul_mod(x, x, pk), u % pk, pk);
+                x = sub_mod(x, mul_mod(f, mod_inverse(2 * x % pk, pk)?, pk), pk);
+            }
+            let pn = p.pow(precision);
+            x.min(pn - x)
+        };
+        from_parts(p, precision, (self.valuation / 2) as i64, root).map(Some)
+    }
+
+    fn unit(&self) -> u64 {
+        self.digits.iter().rev().fold(0, |acc, &d| acc * self.p + d)
+    }
+
+    fn check_compatible(&self, other: &Padic) -> Result<(), MathError> {
+        if self.p != other.p || self.precision != other.precision {
+            return Err(MathError::DimensionMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Validates `p` and the precision, returning `p^precision`.
+fn check_params(p: u64, precision: u32) -> Result<u64, MathError> {
+    if p < 2 || !miller_rabin_u128(p as u128, 32)?.is_prime() {
+        return Err(MathError::OutOfRange);
+    }
+    if precision == 0 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    p.checked_pow(precision).ok_or(MathError::Overflow)
+}
+
+/// `p^valuation · unit` with the factors of `p` moved from `unit` to the valuation.
+fn from_parts(
+    p: u64,
+    precision: u32,
+    mut valuation: i64,
+    mut unit: u64,
+) -> Result<Padic, MathError> {
+    if unit == 0 {
+        return Padic::zero(p, precision);
+    }
+    while unit.is_multiple_of(p) {
+        unit /= p;
+        valuation += 1;
+    }
+    Ok(Padic {
+        p,
+        precision,
+        valuation: i32::try_from(valuation).map_err(|_| MathError::Overflow)?,
+        digits: to_digits(unit, p, precision),
+    })
+}
+
+fn to_digits(mut n: u64, p: u64, precision: u32) -> Vec<u64> {
+    (0..precision)
+        .map(|_| {
+            let d = n % p;
+            n /= p;
+            d
+        })
+        .collect()
+}
+
+/// `f(x) mod m` by Horner's rule.
+fn eval_mod(f: &Polynomial<i64>, x: u64, m: u64) -> u64 {
+    f.coeffs().iter().rev().fold(0, |acc, &c| {
+        add_mod(
+            mul_mod(acc, x, m),
+            (c as i128).rem_euclid(m as i128) as u64,
+            m,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(n: i64, p: u64, precision: u32) -> Padic {
+        Padic::from_integer(n, p, precision).unwrap()
+    }
+
+    #[test]
+    fn test_construction() {
+        assert_eq!(int(-1, 7, 3).digits(), &[6, 6, 6]);
+        let x = int(50, 5, 3);
+        assert_eq!((x.valuation(), x.digits()), (2, &[2, 0, 0][..]));
+        assert_eq!(x.to_residue(), Ok(50));
+
+        let third = Padic::from_rational(&Rational::new(1, 3).unwrap(), 5, 4).unwrap();
+        assert_eq!(third.digits(), &[2, 3, 1, 3]);
+        assert_eq!(third.to_residue(), Ok(417));
+
+        let fifth = Padic::from_rational(&Rational::new(1, 5).unwrap(), 5, 4).unwrap();
+        assert_eq!((fifth.valuation(), fifth.digits()), (-1, &[1, 0, 0, 0][..]));
+        assert_eq!(fifth.to_residue(), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert_eq!(Padic::zero(4, 3), Err(MathError::OutOfRange));
+        assert_eq!(Padic::zero(1, 3), Err(MathError::OutOfRange));
+        assert_eq!(Padic::zero(5, 0), Err(MathError::PositifIntegerRequired));
+        assert_eq!(Padic::zero(2, 64), Err(MathError::Overflow));
+        assert_eq!(
+            int(1, 5, 3).checked_add(&int(1, 7, 3)),
+            Err(MathError::DimensionMismatch)
+        );
+        assert_eq!(
+            int(1, 5, 3).checked_add(&int(1, 5, 4)),
+            Err(MathError::DimensionMismatch)
+        );
+        assert_eq!(
+            Padic::zero(5, 3).unwrap().recip(),
+            Err(MathError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let (p, n) = (5, 6);
+        assert_eq!(
+            int(17, p, n).checked_add(&int(-43, p, n)),
+            Ok(int(-26, p, n))
+        );
+        // 17 - 42 = -25 cancels two digits, which come back as zeros
+        let sum = int(17, p, n).checked_add(&int(-42, p, n)).unwrap();
+        assert_eq!(
+            (sum.valuation(), sum.digits()),
+            (2, &[4, 4, 4, 4, 0, 0][..])
+        );
+        assert_eq!(int(17, p, n).checked_sub(&int(17, p, n)), Padic::zero(p, n));
+        assert_eq!(
+            int(-12, p, n).checked_mul(&int(35, p, n)),
+            Ok(int(-420, p, n))
+        );
+        // cancellation: (1 + 5) - 1 = 5
+        assert_eq!(int(6, p, n).checked_sub(&int(1, p, n)), Ok(int(5, p, n)));
+
+        // 2/15 · 15/4 = 1/2
+        let a = Padic::from_rational(&Rational::new(2, 15).unwrap(), p, n).unwrap();
+        let b = Padic::from_rational(&Rational::new(15, 4).unwrap(), p, n).unwrap();
+        let half = Padic::from_rational(&Rational::new(1, 2).unwrap(), p, n).unwrap();
+        assert_eq!(a.checked_mul(&b), Ok(half.clone()));
+        assert_eq!(half.checked_add(&half), Ok(int(1, p, n)));
+        assert_eq!(a.checked_div(&a), Ok(int(1, p, n)));
+        assert_eq!(a.recip().unwrap().recip(), Ok(a));
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let two = int(2, 7, 5);
+        let root = two.sqrt().unwrap().unwrap();
+        assert_eq!(&root.digits()[..3], &[3, 1, 2]);
+        assert_eq!(root.checked_mul(&root), Ok(two));
+
+        assert_eq!(int(3, 7, 5).sqrt(), Ok(None));
+        assert_eq!(int(7, 7, 5).sqrt(), Ok(None));
+        let square = int(2 * 49, 7, 5);
+        let root = square.sqrt().unwrap().unwrap();
+        assert_eq!(root.valuation(), 1);
+        assert_eq!(root.checked_mul(&root), Ok(square));
+
+        // -7 ≡ 1 (mod 8) is a 2-adic square, 3 is not
+        let minus_seven = int(-7, 2, 20);
+        let root = minus_seven.sqrt().unwrap().unwrap();
+        assert_eq!(root.checked_mul(&root), Ok(minus_seven));
+        assert_eq!(int(3, 2, 20).sqrt(), Ok(None));
+        assert_eq!(int(1, 2, 1).sqrt(), Ok(Some(int(1, 2, 1))));
+    }
+
+    #[test]
+    fn test_hensel_root() {
+        // x² + 1 over Z₅
+        let f = Polynomial::new(vec![1, 0, 1]);
+        let i = Padic::hensel_root(&f, 2, 5, 8).unwrap();
+        assert_eq!(&i.digits()[..2], &[2, 1]);
+        let one = int(1, 5, 8);
+        assert!(
+            i.checked_mul(&i)
+                .unwrap()
+                .checked_add(&one)
+                .unwrap()
+                .is_zero()
+        );
+        assert_eq!(Padic::hensel_root(&f, 1, 5, 8), Err(MathError::NoSolution));
+        // x² has a double root at 0
+        let g = Polynomial::new(vec![0, 0, 1]);
+        assert_eq!(Padic::hensel_root(&g, 0, 5, 8), Err(MathError::NoSolution));
+    }
+}
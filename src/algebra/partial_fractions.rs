@@ -0,0 +1,184 @@
+use alloc::vec::Vec;
+
+use crate::algebra::polynomial::Polynomial;
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// One term `numerator / factor^power` with `deg numerator < deg factor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialFraction {
+    pub numerator: Polynomial<Rational>,
+    pub factor: Polynomial<Rational>,
+    pub power: u32,
+}
+
+/// `N / D = polynomial + Σ terms`, the polynomial part coming from `deg N >= deg D`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialFractions {
+    pub polynomial: Polynomial<Rational>,
+    pub terms: Vec<PartialFraction>,
+}
+
+/// Decomposes `num / ∏ fᵢ^eᵢ` for a denominator given by its factorization `(fᵢ, eᵢ)`.
+///
+/// Every `fᵢ` must be linear or an irreducible quadratic, and the factors pairwise
+/// coprime. For each `fᵢ^eᵢ` with cofactor `gᵢ = D / fᵢ^eᵢ`, the numerator is
+/// `Aᵢ = r·gᵢ⁻¹ mod fᵢ^eᵢ` (inverse from the extended Euclidean algorithm, `r` the
+/// remainder of `num` by `D`), and `Aᵢ` written in base `fᵢ` gives the terms of each power:
+/// - `1 / (x² - 1) = (1/2)/(x - 1) - (1/2)/(x + 1)`
+///
+/// Terms are listed factor by factor, by increasing power; zero numerators are kept.
+/// Errors with `PositifIntegerRequired` for an exponent of 0, `OutOfRange` for a factor
+/// of degree 0 or above 2 or a reducible quadratic, and `DuplicateInput` for factors
+/// sharing a root.
+pub fn partial_fractions(
+    num: &Polynomial<Rational>,
+    den_factors: &[(Polynomial<Rational>, u32)],
+) -> Result<PartialFractions, MathError> {
+    let mut powers = Vec::with_capacity(den_factors.len());
+    for (factor, e) in den_factors {
+        if *e == 0 {
+            return Err(MathError::PositifIntegerRequired);
+        }
+        check_factor(factor)?;
+        powers.push(factor.pow(*e)?);
+    }
+    for (i, (f, _)) in den_factors.iter().enumerate() {
+        for (g, _) in &den_factors[..i] {
+            if f.gcd(g)?.degree() != Some(0) {
+                return Err(MathError::DuplicateInput);
+            }
+        }
+    }
+
+    let den = powers
+        .iter()
+        .try_fold(Polynomial::constant(Rational::ONE), |acc, p| {
+            acc.checked_mul(p)
+        })?;
+    let (polynomial, rem) = num.div_rem(&den)?;
+
+    let mut terms = Vec::new();
+    for ((factor, e), power) in den_factors.iter().zip(&powers) {
+        let (cofactor, _) = den.div_rem(power)?;
+        // s·cofactor + t·power = 1, so s is the inverse of the cofactor mod power
+        let (_, s, _) = cofactor.extended_gcd(power)?;
+        let (_, mut a) = rem.checked_mul(&s)?.div_rem(power)?;
+
+        // a = c₁·f^(e-1) + ... + c_e, each cⱼ of degree < deg f
+        let mut digits = Vec::with_capacity(*e as usize);
+        for _ in 0..*e {
+            let (quot, digit) = a.div_rem(factor)?;
+            digits.push(digit);
+            a = quot;
+        }
+        for (k, numerator) in digits.into_iter().rev().enumerate() {
+            terms.push(PartialFraction {
+                numerator,
+                factor: factor.clone(),
+                power: k as u32 + 1,
+            });
+        }
+    }
+    Ok(PartialFractions { polynomial, terms })
+}
+
+/// Linear, or quadratic with a discriminant that is not a rational square.
+fn check_factor(factor: &Polynomial<Rational>) -> Result<(), MathError> {
+    match factor.degree() {
+        Some(1) => Ok(()),
+        Some(2) => {
+            let [c, b, a] = [0, 1, 2].map(|i| factor.coeffs()[i]);
+            let four_ac = Rational::from_integer(4).checked_mul(&a.checked_mul(&c)?)?;
+            let disc = b.checked_mul(&b)?.checked_sub(&four_ac)?;
+            if is_square(disc.numer()) && is_square(disc.denom()) {
+                Err(MathError::OutOfRange)
+            } else {
+                Ok(())
+            }
+        }
+        _ => Err(MathError::OutOfRange),
+    }
+}
+
+fn is_square(n: i64) -> bool {
+    n >= 0 && n.isqrt() * n.isqrt() == n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    fn poly(coeffs: &[i64]) -> Polynomial<Rational> {
+        Polynomial::new(coeffs.iter().map(|&c| Rational::from_integer(c)).collect())
+    }
+
+    /// polynomial·D + Σ numerator·D / factor^power, which must equal the numerator.
+    fn recombine(
+        pf: &PartialFractions,
+        den_factors: &[(Polynomial<Rational>, u32)],
+    ) -> Polynomial<Rational> {
+        let den = den_factors.iter().fold(poly(&[1]), |acc, (f, e)| {
+            acc.checked_mul(&f.pow(*e).unwrap()).unwrap()
+        });
+        let mut total = pf.polynomial.checked_mul(&den).unwrap();
+        for term in &pf.terms {
+            let (cofactor, rem) = den.div_rem(&term.factor.pow(term.power).unwrap()).unwrap();
+            assert!(rem.is_zero());
+            total = total
+                .checked_add(&term.numerator.checked_mul(&cofactor).unwrap())
+                .unwrap();
+        }
+        total
+    }
+
+    #[test]
+    fn test_distinct_linear_factors() {
+        // 1 / ((x - 1)(x + 1))
+        let factors = [(poly(&[-1, 1]), 1), (poly(&[1, 1]), 1)];
+        let pf = partial_fractions(&poly(&[1]), &factors).unwrap();
+        assert!(pf.polynomial.is_zero());
+        assert_eq!(pf.terms[0].numerator, Polynomial::constant(q(1, 2)));
+        assert_eq!(pf.terms[1].numerator, Polynomial::constant(q(-1, 2)));
+    }
+
+    #[test]
+    fn test_repeated_and_quadratic_factors() {
+        // (x⁴ + 3x + 5) / ((x - 1)²(x² + 1)) has a polynomial part of degree 0
+        let factors = [(poly(&[-1, 1]), 2), (poly(&[1, 0, 1]), 1)];
+        let num = poly(&[5, 3, 0, 0, 1]);
+        let pf = partial_fractions(&num, &factors).unwrap();
+        assert_eq!(pf.polynomial, poly(&[1]));
+        assert_eq!(pf.terms.len(), 3);
+        assert_eq!((pf.terms[0].power, pf.terms[1].power), (1, 2));
+        // coefficient of 1/(x - 1)² is N(1) / (1² + 1) = 9/2
+        assert_eq!(pf.terms[1].numerator, Polynomial::constant(q(9, 2)));
+        assert!(pf.terms[2].numerator.degree() <= Some(1));
+        assert_eq!(recombine(&pf, &factors), num);
+    }
+
+    #[test]
+    fn test_invalid_factors() {
+        let num = poly(&[1]);
+        assert_eq!(
+            partial_fractions(&num, &[(poly(&[-1, 0, 1]), 1)]),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            partial_fractions(&num, &[(poly(&[1, 0, 0, 1]), 1)]),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            partial_fractions(&num, &[(poly(&[-1, 1]), 1), (poly(&[-2, 2]), 1)]),
+            Err(MathError::DuplicateInput)
+        );
+        assert_eq!(
+            partial_fractions(&num, &[(poly(&[-1, 1]), 0)]),
+            Err(MathError::PositifIntegerRequired)
+        );
+    }
+}
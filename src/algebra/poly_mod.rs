@@ -0,0 +1,247 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::modular::{add_mod, mod_inverse, mul_mod};
+use crate::algebra::product_tree::TreeInt;
+use crate::errors::MathError;
+
+/// Polynomial with coefficients in Z/pZ, stored from the constant term upwards.
+///
+/// Coefficients are kept reduced and trailing zeros trimmed. Ring operations work
+/// for any modulus `p >= 2`; `div_rem` needs the divisor's leading coefficient to
+/// be invertible, which always holds for a prime `p`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolyMod {
+    coeffs: Vec<u64>,
+    modulus: u64,
+}
+
+impl PolyMod {
+    /// `coeffs[i]` (reduced mod `p`) is the coefficient of `x^i`; `OutOfRange` if `p < 2`.
+    pub fn new(coeffs: Vec<u64>, p: u64) -> Result<Self, MathError> {
+        if p < 2 {
+            return Err(MathError::OutOfRange);
+        }
+        Ok(PolyMod::reduced(
+            coeffs.into_iter().map(|c| c % p).collect(),
+            p,
+        ))
+    }
+
+    pub fn zero(p: u64) -> Result<Self, MathError> {
+        PolyMod::new(Vec::new(), p)
+    }
+
+    pub fn coeffs(&self) -> &[u64] {
+        &self.coeffs
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Value at `x` by Horner's rule.
+    pub fn eval(&self, x: u64) -> u64 {
+        let p = self.modulus;
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(0, |acc, &c| add_mod(mul_mod(acc, x % p, p), c, p))
+    }
+
+    /// `DimensionMismatch` if the moduli differ.
+    pub fn add(&self, other: &PolyMod) -> Result<PolyMod, MathError> {
+        let p = self.same_modulus(other)?;
+        Ok(self.zip_with(other, |a, b| add_mod(a, b, p)))
+    }
+
+    /// `DimensionMismatch` if the moduli differ.
+    pub fn sub(&self, other: &PolyMod) -> Result<PolyMod, MathError> {
+        let p = self.same_modulus(other)?;
+        Ok(self.zip_with(other, |a, b| add_mod(a, p - b, p)))
+    }
+
+    /// Schoolbook product; `DimensionMismatch` if the moduli differ.
+    pub fn mul(&self, other: &PolyMod) -> Result<PolyMod, MathError> {
+        let p = self.same_modulus(other)?;
+        if self.is_zero() || other.is_zero() {
+            return PolyMod::zero(p);
+        }
+        let mut coeffs = vec![0; self.coeffs.len() + other.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in other.coeffs.iter().enumerate() {
+                coeffs[i + j] = add_mod(coeffs[i + j], mul_mod(a, b, p), p);
+            }
+        }
+        Ok(PolyMod::reduced(coeffs, p))
+    }
+
+    /// Every coefficient multiplied by `c`.
+    pub fn scale(&self, c: u64) -> PolyMod {
+        let p = self.modulus;
+        let coeffs = self.coeffs.iter().map(|&a| mul_mod(a, c % p, p)).collect();
+        PolyMod::reduced(coeffs, p)
+    }
+
+    /// Euclidean division `self = q·divisor + r` with `deg r < deg divisor`.
+    ///
+    /// Errors with `DivisionByZero` for a zero divisor, `NoInverse` if its leading
+    /// coefficient is not invertible and `DimensionMismatch` if the moduli differ.
+    pub fn div_rem(&self, divisor: &PolyMod) -> Result<(PolyMod, PolyMod), MathError> {
+        let p = self.same_modulus(divisor)?;
+        let Some(&lead) = divisor.coeffs.last() else {
+            return Err(MathError::DivisionByZero);
+        };
+        let inv = mod_inverse(lead, p)?;
+        let d = divisor.coeffs.len();
+        if self.coeffs.len() < d {
+            return Ok((PolyMod::zero(p)?, self.clone()));
+        }
+
+        let mut rem = self.coeffs.clone();
+        let mut quot = vec![0; rem.len() - d + 1];
+        for i in (0..quot.len()).rev() {
+            let q = mul_mod(rem[i + d - 1], inv, p);
+            for (j, &c) in divisor.coeffs.iter().enumerate() {
+                rem[i + j] = add_mod(rem[i + j], p - mul_mod(q, c, p), p);
+            }
+            quot[i] = q;
+        }
+        rem.truncate(d - 1);
+        Ok((PolyMod::reduced(quot, p), PolyMod::reduced(rem, p)))
+    }
+
+    /// Same polynomial scaled to a leading coefficient of 1 (zero stays zero).
+    pub fn monic(&self) -> Result<PolyMod, MathError> {
+        match self.coeffs.last() {
+            None => Ok(self.clone()),
+            Some(&lead) => Ok(self.scale(mod_inverse(lead, self.modulus)?)),
+        }
+    }
+
+    /// Monic greatest common divisor by Euclid's algorithm (zero if both are zero).
+    ///
+    /// Same error cases as `div_rem`.
+    pub fn gcd(&self, other: &PolyMod) -> Result<PolyMod, MathError> {
+        self.same_modulus(other)?;
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let (_, r) = a.div_rem(&b)?;
+            (a, b) = (b, r);
+        }
+        a.monic()
+    }
+
+    /// Formal derivative; over GF(p) it vanishes for polynomials in `x^p`.
+    pub fn derivative(&self) -> PolyMod {
+        let p = self.modulus;
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| mul_mod(c, i as u64 % p, p))
+            .collect();
+        PolyMod::reduced(coeffs, p)
+    }
+
+    /// Builds from already reduced coefficients, trimming trailing zeros.
+    fn reduced(mut coeffs: Vec<u64>, p: u64) -> PolyMod {
+        while coeffs.last() == Some(&0) {
+            coeffs.pop();
+        }
+        PolyMod { coeffs, modulus: p }
+    }
+
+    fn same_modulus(&self, other: &PolyMod) -> Result<u64, MathError> {
+        if self.modulus != other.modulus {
+            return Err(MathError::DimensionMismatch);
+        }
+        Ok(self.modulus)
+    }
+
+    fn zip_with(&self, other: &PolyMod, op: impl Fn(u64, u64) -> u64) -> PolyMod {
+        let len = self.coeffs.len().max(other.coeffs.len());
+        let coeffs = (0..len)
+            .map(|i| {
+                let a = self.coeffs.get(i).copied().unwrap_or(0);
+                let b = other.coeffs.get(i).copied().unwrap_or(0);
+                op(a, b)
+            })
+            .collect();
+        PolyMod::reduced(coeffs, self.modulus)
+    }
+}
+
+impl TreeInt for PolyMod {
+    fn tree_mul(&self, other: &Self) -> Result<Self, MathError> {
+        self.mul(other)
+    }
+
+    fn tree_rem(&self, modulus: &Self) -> Result<Self, MathError> {
+        Ok(self.div_rem(modulus)?.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poly(coeffs: &[u64], p: u64) -> PolyMod {
+        PolyMod::new(coeffs.to_vec(), p).unwrap()
+    }
+
+    #[test]
+    fn test_reduction_and_eval() {
+        let f = poly(&[8, 7, 14], 7); // 1 + 0x + 0x² = 1
+        assert_eq!(f.coeffs(), &[1]);
+        assert_eq!(poly(&[1, 2, 3], 5).eval(4), (1 + 8 + 48) % 5);
+        assert_eq!(PolyMod::new(vec![1], 1), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_ring_operations() {
+        let p = 5;
+        let a = poly(&[1, 1], p); // x + 1
+        let b = poly(&[4, 1], p); // x - 1
+        assert_eq!(a.mul(&b), Ok(poly(&[4, 0, 1], p)));
+        assert_eq!(a.add(&b), Ok(poly(&[0, 2], p)));
+        assert_eq!(a.sub(&b), Ok(poly(&[2], p)));
+        assert_eq!(a.add(&poly(&[1], 7)), Err(MathError::DimensionMismatch));
+    }
+
+    #[test]
+    fn test_div_rem() {
+        // x³ + 2x + 1 over GF(7) divided by 3x + 1
+        let n = poly(&[1, 2, 0, 1], 7);
+        let d = poly(&[1, 3], 7);
+        let (q, r) = n.div_rem(&d).unwrap();
+        assert_eq!(q.mul(&d).unwrap().add(&r), Ok(n.clone()));
+        assert_eq!(r.degree(), Some(0));
+        assert_eq!(
+            n.div_rem(&PolyMod::zero(7).unwrap()),
+            Err(MathError::DivisionByZero)
+        );
+        assert_eq!(poly(&[2, 4], 7).monic(), Ok(poly(&[4, 1], 7)));
+    }
+
+    #[test]
+    fn test_gcd_and_derivative() {
+        // (x + 1)(x + 2) and (x + 1)(x + 3) over GF(5)
+        let a = poly(&[2, 3, 1], 5);
+        let b = poly(&[3, 4, 1], 5);
+        assert_eq!(a.gcd(&b), Ok(poly(&[1, 1], 5)));
+        assert_eq!(a.scale(3).gcd(&a), Ok(a.clone()));
+        assert_eq!(a.derivative(), poly(&[3, 2], 5));
+        // d/dx (x⁵ + 1) = 5x⁴ = 0 over GF(5)
+        assert!(poly(&[1, 0, 0, 0, 0, 1], 5).derivative().is_zero());
+    }
+}
@@ -0,0 +1,694 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::gcd::gcd_many;
+use crate::algebra::modular::Garner;
+use crate::algebra::ntt::{inverse_ntt, ntt};
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Shorter operand length below which `mul_auto` stays with the schoolbook product.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Shorter operand length from which `mul_auto` multiplies through the NTT.
+const NTT_THRESHOLD: usize = 512;
+
+/// NTT-friendly primes `c·2^k + 1` with `k >= 23`, combined by CRT as the coefficient
+/// bound requires; their product exceeds `2^64`, enough for any `i64` result.
+const NTT_PRIMES: [u64; 3] = [998_244_353, 469_762_049, 167_772_161];
+
+/// Coefficient ring with checked operations (overflow surfaces as `MathError`).
+pub trait Ring: Clone + PartialEq {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn checked_add(&self, other: &Self) -> Result<Self, MathError>;
+    fn checked_sub(&self, other: &Self) -> Result<Self, MathError>;
+    fn checked_mul(&self, other: &Self) -> Result<Self, MathError>;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+/// A `Ring` where every nonzero element is invertible.
+pub trait Field: Ring {
+    fn checked_div(&self, other: &Self) -> Result<Self, MathError>;
+}
+
+impl Ring for i64 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn checked_add(&self, other: &Self) -> Result<Self, MathError> {
+        i64::checked_add(*self, *other).ok_or(MathError::Overflow)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Result<Self, MathError> {
+        i64::checked_sub(*self, *other).ok_or(MathError::Overflow)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Result<Self, MathError> {
+        i64::checked_mul(*self, *other).ok_or(MathError::Overflow)
+    }
+}
+
+impl Ring for Rational {
+    fn zero() -> Self {
+        Rational::ZERO
+    }
+
+    fn one() -> Self {
+        Rational::ONE
+    }
+
+    fn checked_add(&self, other: &Self) -> Result<Self, MathError> {
+        Rational::checked_add(self, other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Result<Self, MathError> {
+        Rational::checked_sub(self, other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Result<Self, MathError> {
+        Rational::checked_mul(self, other)
+    }
+}
+
+impl Field for Rational {
+    fn checked_div(&self, other: &Self) -> Result<Self, MathError> {
+        Rational::checked_div(self, other)
+    }
+}
+
+/// Univariate polynomial, coefficients stored from the constant term upwards.
+///
+/// Trailing zero coefficients are trimmed, so the zero polynomial has no coefficients
+/// and `degree` is `None` for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial<T> {
+    coeffs: Vec<T>,
+}
+
+impl<T: Ring> Polynomial<T> {
+    /// `coeffs[i]` is the coefficient of `x^i`:
+    /// - `Polynomial::new(vec![1, 0, 3])` is `3x² + 1`
+    pub fn new(mut coeffs: Vec<T>) -> Self {
+        while coeffs.last().is_some_and(Ring::is_zero) {
+            coeffs.pop();
+        }
+        Polynomial { coeffs }
+    }
+
+    pub fn zero() -> Self {
+        Polynomial { coeffs: Vec::new() }
+    }
+
+    pub fn constant(c: T) -> Self {
+        Polynomial::new(vec![c])
+    }
+
+    /// `x - root`.
+    pub fn linear_root(root: &T) -> Result<Self, MathError> {
+        Ok(Polynomial::new(vec![
+            T::zero().checked_sub(root)?,
+            T::one(),
+        ]))
+    }
+
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Leading coefficient, `None` for the zero polynomial.
+    pub fn leading(&self) -> Option<&T> {
+        self.coeffs.last()
+    }
+
+    /// Value at `x` by Horner's rule.
+    pub fn eval(&self, x: &T) -> Result<T, MathError> {
+        self.coeffs
+            .iter()
+            .rev()
+            .try_fold(T::zero(), |acc, c| acc.checked_mul(x)?.checked_add(c))
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Result<Self, MathError> {
+        self.zip_with(other, T::checked_add)
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, MathError> {
+        self.zip_with(other, T::checked_sub)
+    }
+
+    /// Schoolbook product, O(deg·deg).
+    pub fn checked_mul(&self, other: &Self) -> Result<Self, MathError> {
+        if self.is_zero() || other.is_zero() {
+            return Ok(Polynomial::zero());
+        }
+        let mut coeffs = vec![T::zero(); self.coeffs.len() + other.coeffs.len() - 1];
+        for (i, a) in self.coeffs.iter().enumerate() {
+            for (j, b) in other.coeffs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j].checked_add(&a.checked_mul(b)?)?;
+            }
+        }
+        Ok(Polynomial::new(coeffs))
+    }
+
+    /// Every coefficient multiplied by `c`.
+    pub fn scale(&self, c: &T) -> Result<Self, MathError> {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .map(|a| a.checked_mul(c))
+            .collect::<Result<_, _>>()?;
+        Ok(Polynomial::new(coeffs))
+    }
+
+    /// `self^exp` by repeated squaring; `x^0 = 1`.
+    pub fn pow(&self, mut exp: u32) -> Result<Self, MathError> {
+        let mut result = Polynomial::constant(T::one());
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.checked_mul(&base)?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.checked_mul(&base)?;
+            }
+        }
+        Ok(result)
+    }
+
+    fn zip_with<F>(&self, other: &Self, op: F) -> Result<Self, MathError>
+    where
+        F: Fn(&T, &T) -> Result<T, MathError>,
+    {
+        let len = self.coeffs.len().max(other.coeffs.len());
+        let zero = T::zero();
+        let coeffs = (0..len)
+            .map(|i| {
+                let a = self.coeffs.get(i).unwrap_or(&zero);
+                let b = other.coeffs.get(i).unwrap_or(&zero);
+                op(a, b)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Polynomial::new(coeffs))
+    }
+}
+
+impl<T: Ring + From<i64>> Polynomial<T> {
+    /// Formal derivative `Σ i·aᵢ·x^(i-1)`.
+    pub fn derivative(&self) -> Result<Self, MathError> {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| c.checked_mul(&T::from(i as i64)))
+            .collect::<Result<_, _>>()?;
+        Ok(Polynomial::new(coeffs))
+    }
+}
+
+impl Polynomial<i64> {
+    /// Exact product, with the algorithm picked from the sizes of the operands:
+    /// - schoolbook when the shorter one has fewer than 32 coefficients,
+    /// - Karatsuba up to 512 coefficients,
+    /// - NTT beyond, modulo as many primes as the coefficient bound
+    ///   `max|aᵢ| · max|bⱼ| · min(len)` requires, recombined by CRT.
+    ///
+    /// Below that bound Karatsuba and the NTT are exact, so the result always equals
+    /// `checked_mul`'s. A bound past `i64` falls back to schoolbook in `i128`, which
+    /// unlike `checked_mul` tolerates intermediate sums that cancel out. Errors with
+    /// `Overflow` only if a coefficient of the product does not fit in `i64`.
+    pub fn mul_auto(&self, other: &Self) -> Result<Self, MathError> {
+        if self.is_zero() || other.is_zero() {
+            return Ok(Polynomial::zero());
+        }
+        let (a, b) = (&self.coeffs, &other.coeffs);
+        let shorter = a.len().min(b.len());
+        let max_abs = |c: &[i64]| c.iter().map(|x| x.unsigned_abs() as u128).max();
+        let bound = max_abs(a)
+            .zip(max_abs(b))
+            .and_then(|(x, y)| x.checked_mul(y)?.checked_mul(shorter as u128))
+            .filter(|&bound| bound <= i64::MAX as u128);
+
+        let coeffs = match bound {
+            None => mul_schoolbook_i128(a, b)?,
+            Some(_) if shorter < KARATSUBA_THRESHOLD => self.checked_mul(other)?.coeffs,
+            Some(bound) if shorter >= NTT_THRESHOLD && a.len() + b.len() - 1 <= 1 << 23 => {
+                mul_ntt_crt(a, b, bound)?
+            }
+            Some(_) => {
+                // the result fits in i64, so computing modulo 2^64 is exact
+                let n = a.len().max(b.len());
+                let (mut a, mut b) = (a.clone(), b.clone());
+                a.resize(n, 0);
+                b.resize(n, 0);
+                mul_karatsuba_wrapping(&a, &b)
+            }
+        };
+        Ok(Polynomial::new(coeffs))
+    }
+
+    /// Non-negative gcd of the coefficients, 0 for the zero polynomial:
+    /// - `content(6x² - 4x + 2) = 2`
+    ///
+    /// `Overflow` only if that gcd is `2^63`, i.e. every coefficient is `i64::MIN` or 0.
+    pub fn content(&self) -> Result<i64, MathError> {
+        if self.is_zero() {
+            return Ok(0);
+        }
+        gcd_many(&self.coeffs)
+    }
+
+    /// `self / content` with a positive leading coefficient (zero stays zero):
+    /// - `primitive_part(-6x² + 4x - 2) = 3x² - 2x + 1`
+    ///
+    /// `Overflow` as for `content`, or if a quotient is `-i64::MIN`.
+    pub fn primitive_part(&self) -> Result<Self, MathError> {
+        let Some(&lead) = self.leading() else {
+            return Ok(Polynomial::zero());
+        };
+        let content = if lead < 0 {
+            -self.content()?
+        } else {
+            self.content()?
+        };
+        let coeffs = self
+            .coeffs
+            .iter()
+            .map(|c| c.checked_div(content).ok_or(MathError::Overflow))
+            .collect::<Result<_, _>>()?;
+        Ok(Polynomial::new(coeffs))
+    }
+
+    /// Pseudo-division, the integer stand-in for `div_rem`: `lc^k · self = q·divisor + r`
+    /// with `lc` the leading coefficient of `divisor`, `deg r < deg divisor` and
+    /// `k = max(deg self - deg divisor + 1, 0)`:
+    /// - `x² + 1` by `2x + 1`: `4(x² + 1) = (2x - 1)(2x + 1) + 5`
+    ///
+    /// Errors with `DivisionByZero` for a zero divisor and `Overflow` if a coefficient
+    /// leaves `i64`.
+    pub fn pseudo_div_rem(&self, divisor: &Self) -> Result<(Self, Self), MathError> {
+        let Some(&lead) = divisor.leading() else {
+            return Err(MathError::DivisionByZero);
+        };
+        let d = divisor.coeffs.len();
+        if self.coeffs.len() < d {
+            return Ok((Polynomial::zero(), self.clone()));
+        }
+
+        // each step scales what is already there by lc, so that lc divides rem's top term
+        let mut rem = self.coeffs.clone();
+        let mut quot = vec![0i64; rem.len() - d + 1];
+        for i in (0..quot.len()).rev() {
+            let top = rem[i + d - 1];
+            for c in rem
+                .iter_mut()
+                .take(i + d - 1)
+                .chain(quot.iter_mut().skip(i + 1))
+            {
+                *c = Ring::checked_mul(c, &lead)?;
+            }
+            for (j, c) in divisor.coeffs.iter().enumerate().take(d - 1) {
+                rem[i + j] = Ring::checked_sub(&rem[i + j], &Ring::checked_mul(&top, c)?)?;
+            }
+            quot[i] = top;
+        }
+        rem.truncate(d - 1);
+        Ok((Polynomial::new(quot), Polynomial::new(rem)))
+    }
+
+    /// Greatest common divisor in `Z[x]`, with a positive leading coefficient (zero if
+    /// both inputs are zero):
+    /// - `gcd(2x² - 2, 4x² + 4x) = 2x + 2`
+    ///
+    /// The gcd of the contents times the last nonzero term of the primitive remainder
+    /// sequence, which divides out the content after every pseudo-division to keep the
+    /// coefficients small. Errors with `Overflow` if an intermediate coefficient still
+    /// leaves `i64`.
+    pub fn gcd(&self, other: &Self) -> Result<Self, MathError> {
+        if self.is_zero() && other.is_zero() {
+            return Ok(Polynomial::zero());
+        }
+        let content = gcd_many(&[self.content()?, other.content()?])?;
+        let (mut a, mut b) = (self.primitive_part()?, other.primitive_part()?);
+        if a.coeffs.len() < b.coeffs.len() {
+            (a, b) = (b, a);
+        }
+        while !b.is_zero() {
+            let (_, r) = a.pseudo_div_rem(&b)?;
+            (a, b) = (b, r.primitive_part()?);
+        }
+        a.scale(&content)
+    }
+}
+
+/// Schoolbook product accumulated in `i128`; `Overflow` if a final coefficient leaves
+/// `i64` (or a sum leaves `i128`).
+fn mul_schoolbook_i128(a: &[i64], b: &[i64]) -> Result<Vec<i64>, MathError> {
+    let mut acc = vec![0i128; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            acc[i + j] = acc[i + j]
+                .checked_add(x as i128 * y as i128)
+                .ok_or(MathError::Overflow)?;
+        }
+    }
+    acc.into_iter()
+        .map(|c| i64::try_from(c).map_err(|_| MathError::Overflow))
+        .collect()
+}
+
+/// Karatsuba on operands of equal length, in arithmetic modulo `2^64`.
+fn mul_karatsuba_wrapping(a: &[i64], b: &[i64]) -> Vec<i64> {
+    let n = a.len();
+    let mut result = vec![0i64; 2 * n - 1];
+    if n < KARATSUBA_THRESHOLD {
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                result[i + j] = result[i + j].wrapping_add(x.wrapping_mul(y));
+            }
+        }
+        return result;
+    }
+
+    // a·b = z2·x^2m + z1·x^m + z0 with z1 = (a0 + a1)(b0 + b1) - z0 - z2
+    let m = n / 2;
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+    let sum = |low: &[i64], high: &[i64]| -> Vec<i64> {
+        let mut s = high.to_vec();
+        for (x, &y) in s.iter_mut().zip(low) {
+            *x = x.wrapping_add(y);
+        }
+        s
+    };
+
+    let z0 = mul_karatsuba_wrapping(a0, b0);
+    let z2 = mul_karatsuba_wrapping(a1, b1);
+    let mut z1 = mul_karatsuba_wrapping(&sum(a0, a1), &sum(b0, b1));
+    for (i, c) in z1.iter_mut().enumerate() {
+        let low = z0.get(i).copied().unwrap_or(0);
+        *c = c.wrapping_sub(low).wrapping_sub(z2[i]);
+    }
+
+    for (i, &c) in z0.iter().enumerate() {
+        result[i] = result[i].wrapping_add(c);
+    }
+    for (i, &c) in z1.iter().enumerate() {
+        result[i + m] = result[i + m].wrapping_add(c);
+    }
+    for (i, &c) in z2.iter().enumerate() {
+        result[i + 2 * m] = result[i + 2 * m].wrapping_add(c);
+    }
+    result
+}
+
+/// Product through the NTT modulo enough of `NTT_PRIMES` that their product exceeds
+/// `2·bound`, recombined by `Garner` into the symmetric range.
+fn mul_ntt_crt(a: &[i64], b: &[i64], bound: u128) -> Result<Vec<i64>, MathError> {
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    let mut moduli = Vec::new();
+    let mut residues: Vec<Vec<u64>> = Vec::new();
+    let mut modulus: u128 = 1;
+    for &p in &NTT_PRIMES {
+        if modulus > 2 * bound {
+            break;
+        }
+        let transform = |c: &[i64]| {
+            let mut v: Vec<u64> = c.iter().map(|&x| x.rem_euclid(p as i64) as u64).collect();
+            v.resize(n, 0);
+            ntt(&v, p)
+        };
+        let (fa, fb) = (transform(a)?, transform(b)?);
+        let pointwise: Vec<u64> = fa
+            .iter()
+            .zip(&fb)
+            .map(|(&x, &y)| (x as u128 * y as u128 % p as u128) as u64)
+            .collect();
+        residues.push(inverse_ntt(&pointwise, p)?);
+        moduli.push(p);
+        modulus *= p as u128;
+    }
+
+    let garner = Garner::new(&moduli)?;
+    (0..len)
+        .map(|i| {
+            let column: Vec<u64> = residues.iter().map(|r| r[i]).collect();
+            // |c| <= bound < M/2, so the symmetric representative is the coefficient
+            Ok(garner.reconstruct_symmetric(&column)? as i64)
+        })
+        .collect()
+}
+
+impl<T: Field> Polynomial<T> {
+    /// Euclidean division `self = q·divisor + r` with `deg r < deg divisor`.
+    ///
+    /// Errors with `DivisionByZero` for a zero divisor.
+    pub fn div_rem(&self, divisor: &Self) -> Result<(Self, Self), MathError> {
+        let Some(lead) = divisor.leading() else {
+            return Err(MathError::DivisionByZero);
+        };
+        let d = divisor.coeffs.len();
+        if self.coeffs.len() < d {
+            return Ok((Polynomial::zero(), self.clone()));
+        }
+
+        let mut rem = self.coeffs.clone();
+        let mut quot = vec![T::zero(); rem.len() - d + 1];
+        for i in (0..quot.len()).rev() {
+            let q = rem[i + d - 1].checked_div(lead)?;
+            for (j, c) in divisor.coeffs.iter().enumerate() {
+                rem[i + j] = rem[i + j].checked_sub(&q.checked_mul(c)?)?;
+            }
+            quot[i] = q;
+        }
+        rem.truncate(d - 1);
+        Ok((Polynomial::new(quot), Polynomial::new(rem)))
+    }
+
+    /// Same polynomial scaled to a leading coefficient of 1 (zero stays zero).
+    pub fn monic(&self) -> Result<Self, MathError> {
+        match self.leading() {
+            None => Ok(Polynomial::zero()),
+            Some(lead) => self.scale(&T::one().checked_div(lead)?),
+        }
+    }
+
+    /// Monic gcd `g` with Bézout cofactors: `s·self + t·other = g`.
+    ///
+    /// `g` is zero only when both inputs are zero.
+    pub fn extended_gcd(&self, other: &Self) -> Result<(Self, Self, Self), MathError> {
+        let one = Polynomial::constant(T::one());
+        let (mut old_r, mut r) = (self.clone(), other.clone());
+        let (mut old_s, mut s) = (one.clone(), Polynomial::zero());
+        let (mut old_t, mut t) = (Polynomial::zero(), one);
+        while !r.is_zero() {
+            let (q, rem) = old_r.div_rem(&r)?;
+            let next_s = old_s.checked_sub(&q.checked_mul(&s)?)?;
+            let next_t = old_t.checked_sub(&q.checked_mul(&t)?)?;
+            (old_r, r) = (r, rem);
+            (old_s, s) = (s, next_s);
+            (old_t, t) = (t, next_t);
+        }
+
+        let Some(lead) = old_r.leading() else {
+            return Ok((old_r, old_s, old_t));
+        };
+        let inv = T::one().checked_div(lead)?;
+        Ok((old_r.scale(&inv)?, old_s.scale(&inv)?, old_t.scale(&inv)?))
+    }
+
+    /// Monic greatest common divisor (zero if both inputs are zero).
+    pub fn gcd(&self, other: &Self) -> Result<Self, MathError> {
+        Ok(self.extended_gcd(other)?.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    #[test]
+    fn test_trim_degree_eval() {
+        let p = Polynomial::new(vec![1i64, 0, 3, 0, 0]);
+        assert_eq!(p.coeffs(), &[1, 0, 3]);
+        assert_eq!(p.degree(), Some(2));
+        assert_eq!(p.eval(&2), Ok(13));
+        assert_eq!(Polynomial::<i64>::new(vec![0, 0]).degree(), None);
+    }
+
+    #[test]
+    fn test_ring_operations() {
+        let a = Polynomial::new(vec![1i64, 1]); // x + 1
+        let b = Polynomial::new(vec![-1i64, 1]); // x - 1
+        assert_eq!(a.checked_mul(&b), Ok(Polynomial::new(vec![-1, 0, 1])));
+        assert_eq!(a.checked_add(&b), Ok(Polynomial::new(vec![0, 2])));
+        assert_eq!(a.checked_sub(&a), Ok(Polynomial::zero()));
+        let big = Polynomial::constant(i64::MAX);
+        assert_eq!(big.checked_add(&big), Err(MathError::Overflow));
+    }
+
+    /// Deterministic pseudo-random coefficients in `[-amplitude, amplitude]`.
+    fn sample(len: usize, amplitude: i64, seed: u64) -> Polynomial<i64> {
+        let mut state = seed;
+        let coeffs = (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                ((state >> 33) as i64).rem_euclid(2 * amplitude + 1) - amplitude
+            })
+            .collect();
+        Polynomial::new(coeffs)
+    }
+
+    #[test]
+    fn test_mul_auto_matches_schoolbook() {
+        // each size lands in a different backend, with one or several NTT primes
+        for (len_a, len_b, amplitude) in [
+            (5, 7, 1_000),
+            (40, 45, 1 << 20),
+            (100, 300, 1 << 26),
+            (600, 700, 10),
+            (600, 513, 1 << 14),
+            (700, 600, 1 << 26),
+            (1000, 3, 1 << 30),
+        ] {
+            let a = sample(len_a, amplitude, len_a as u64);
+            let b = sample(len_b, amplitude, 7 * len_b as u64);
+            assert_eq!(
+                a.mul_auto(&b),
+                a.checked_mul(&b),
+                "{len_a}x{len_b}, |c| <= {amplitude}"
+            );
+        }
+        let x = Polynomial::new(vec![0i64, 1]);
+        assert_eq!(x.mul_auto(&Polynomial::zero()), Ok(Polynomial::zero()));
+    }
+
+    #[test]
+    fn test_mul_auto_exact_near_overflow() {
+        // partial sums overflow i64 but every coefficient of the product fits
+        let ones = Polynomial::new(vec![1i64, 1, 1]);
+        let b = Polynomial::new(vec![-i64::MAX, i64::MAX, i64::MAX, -i64::MAX]);
+        assert_eq!(ones.checked_mul(&b), Err(MathError::Overflow));
+        assert_eq!(
+            ones.mul_auto(&b),
+            Ok(Polynomial::new(vec![
+                -i64::MAX,
+                0,
+                i64::MAX,
+                i64::MAX,
+                0,
+                -i64::MAX
+            ]))
+        );
+        let big = Polynomial::new(vec![i64::MAX, i64::MAX]);
+        assert_eq!(
+            big.mul_auto(&Polynomial::constant(2)),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_div_rem_over_rationals() {
+        // (2x³ + 3x + 1) = (2x² - 2x + 5)(x + 1) - 4
+        let n = Polynomial::new(vec![q(1, 1), q(3, 1), q(0, 1), q(2, 1)]);
+        let d = Polynomial::new(vec![q(1, 1), q(1, 1)]);
+        let (quot, rem) = n.div_rem(&d).unwrap();
+        assert_eq!(quot, Polynomial::new(vec![q(5, 1), q(-2, 1), q(2, 1)]));
+        assert_eq!(rem, Polynomial::constant(q(-4, 1)));
+        assert_eq!(
+            n.div_rem(&Polynomial::zero()),
+            Err(MathError::DivisionByZero)
+        );
+        assert_eq!(
+            Polynomial::new(vec![q(1, 1), q(2, 1)]).monic(),
+            Ok(Polynomial::new(vec![q(1, 2), q(1, 1)]))
+        );
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        // (x - 1)(x + 2) and (x - 1)(x - 3) share x - 1
+        let a = Polynomial::new(vec![q(-2, 1), q(1, 1), q(1, 1)]);
+        let b = Polynomial::new(vec![q(3, 1), q(-4, 1), q(1, 1)]);
+        let (g, s, t) = a.extended_gcd(&b).unwrap();
+        assert_eq!(g, Polynomial::new(vec![q(-1, 1), q(1, 1)]));
+        let combo = s
+            .checked_mul(&a)
+            .unwrap()
+            .checked_add(&t.checked_mul(&b).unwrap());
+        assert_eq!(combo, Ok(g));
+        assert_eq!(a.pow(2).unwrap(), a.checked_mul(&a).unwrap());
+        assert_eq!(a.derivative(), Ok(Polynomial::new(vec![q(1, 1), q(2, 1)])));
+    }
+
+    #[test]
+    fn test_integer_pseudo_division_and_gcd() {
+        let p = |c: &[i64]| Polynomial::new(c.to_vec());
+        let f = p(&[2, -4, 6]);
+        assert_eq!(f.content(), Ok(2));
+        assert_eq!(f.scale(&-1).unwrap().primitive_part(), Ok(p(&[1, -2, 3])));
+        assert_eq!(Polynomial::<i64>::zero().content(), Ok(0));
+        assert_eq!(p(&[1, i64::MIN]).primitive_part(), Err(MathError::Overflow));
+
+        let (a, b) = (p(&[1, 0, 1]), p(&[1, 2]));
+        assert_eq!(a.pseudo_div_rem(&b), Ok((p(&[-1, 2]), p(&[5]))));
+        assert_eq!(b.pseudo_div_rem(&a), Ok((Polynomial::zero(), b.clone())));
+        assert_eq!(
+            a.pseudo_div_rem(&Polynomial::zero()),
+            Err(MathError::DivisionByZero)
+        );
+
+        assert_eq!(p(&[-2, 0, 2]).gcd(&p(&[0, 4, 4])), Ok(p(&[2, 2])));
+        assert_eq!(p(&[3, 1]).gcd(&p(&[5, 1])), Ok(p(&[1])));
+        assert_eq!(p(&[]).gcd(&p(&[-4, -6])), Ok(p(&[4, 6])));
+        assert_eq!(
+            Polynomial::<i64>::zero().gcd(&Polynomial::zero()),
+            Ok(Polynomial::zero())
+        );
+
+        // against the monic gcd over Q, for products sharing a known factor
+        let to_q =
+            |f: &Polynomial<i64>| Polynomial::new(f.coeffs().iter().map(|&c| q(c, 1)).collect());
+        for seed in 0..20 {
+            let common = sample(3, 5, seed).checked_add(&p(&[0, 0, 0, 1])).unwrap();
+            let a = common.checked_mul(&sample(4, 6, seed + 100)).unwrap();
+            let b = common.checked_mul(&sample(3, 6, seed + 200)).unwrap();
+            let g = a.gcd(&b).unwrap();
+            assert!(*g.leading().unwrap() > 0);
+            assert_eq!(to_q(&g).monic(), to_q(&a).gcd(&to_q(&b)));
+            let contents = [a.content().unwrap(), b.content().unwrap()];
+            assert_eq!(g.content(), gcd_many(&contents));
+
+            let (quot, rem) = a.pseudo_div_rem(&b).unwrap();
+            let k = (a.coeffs().len() + 1).saturating_sub(b.coeffs().len()) as u32;
+            let lc_k = b.leading().unwrap().pow(k);
+            let rebuilt = quot.checked_mul(&b).unwrap().checked_add(&rem).unwrap();
+            assert_eq!(a.scale(&lc_k), Ok(rebuilt));
+        }
+    }
+}
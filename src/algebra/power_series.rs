@@ -0,0 +1,273 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::modular::{mod_inverse, mul_mod, sqrt_mod};
+use crate::algebra::ntt::{inverse_ntt, ntt};
+use crate::errors::MathError;
+
+/// Product `a·b mod x^n` of power series over `Z/pZ`, given by their coefficients from
+/// the constant term upwards:
+/// - `series_mul(&[1, 1], &[1, 1], 3, 17) = [1, 2, 1]`
+///
+/// Every function of this module returns exactly `n` coefficients, ignores input ones
+/// past `n` and multiplies through `ntt`: `p` must be a prime with a power of two at
+/// least `2n` dividing `p - 1` (e.g. `998244353 = 119·2^23 + 1`), otherwise the error
+/// is `OutOfRange`.
+pub fn series_mul(a: &[u64], b: &[u64], n: usize, p: u64) -> Result<Vec<u64>, MathError> {
+    let (a, b) = (&a[..a.len().min(n)], &b[..b.len().min(n)]);
+    if a.is_empty() || b.is_empty() {
+        return Ok(vec![0; n]);
+    }
+    let size = (a.len() + b.len() - 1).next_power_of_two();
+    let transform = |c: &[u64]| {
+        let mut padded: Vec<u64> = c.iter().map(|&x| x % p).collect();
+        padded.resize(size, 0);
+        ntt(&padded, p)
+    };
+    let pointwise: Vec<u64> = transform(a)?
+        .iter()
+        .zip(transform(b)?)
+        .map(|(&x, y)| mul_mod(x, y, p))
+        .collect();
+    let mut product = inverse_ntt(&pointwise, p)?;
+    product.resize(n, 0);
+    Ok(product)
+}
+
+/// The series `g` with `a·g ≡ 1 mod x^n`:
+/// - `series_inverse(&[1, 16], 4, 17) = [1, 1, 1, 1]`, the geometric series `1/(1 - x)`
+///
+/// Newton iteration `g ← g·(2 - a·g)` doubles the precision each step, O(n log n) in
+/// total. Errors with `NoInverse` unless the constant term is a unit.
+pub fn series_inverse(a: &[u64], n: usize, p: u64) -> Result<Vec<u64>, MathError> {
+    let a0 = a.first().map_or(0, |&c| c % p);
+    if a0 == 0 {
+        return Err(MathError::NoInverse);
+    }
+    let mut g = vec![mod_inverse(a0, p)?];
+    let mut k = 1;
+    while k < n {
+        k = (2 * k).min(n);
+        let mut t = series_mul(a, &g, k, p)?;
+        for c in t.iter_mut() {
+            *c = (p - *c) % p;
+        }
+        t[0] = (t[0] + 2) % p;
+        g = series_mul(&g, &t, k, p)?;
+    }
+    g.resize(n, 0);
+    Ok(g)
+}
+
+/// Logarithm `∫ a'/a`, the series with zero constant term whose `exp` is `a`:
+/// - `series_log(&[1, 1], 4, 17) = [0, 1, -1/2, 1/3] = [0, 1, 8, 6]`
+///
+/// Errors with `OutOfRange` unless the constant term is 1 and `n <= p` (the integral
+/// divides by `1, ..., n - 1`).
+pub fn series_log(a: &[u64], n: usize, p: u64) -> Result<Vec<u64>, MathError> {
+    if a.first().map_or(0, |&c| c % p) != 1 || n as u64 > p {
+        return Err(MathError::OutOfRange);
+    }
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    let derivative: Vec<u64> = a
+        .iter()
+        .take(n)
+        .enumerate()
+        .skip(1)
+        .map(|(i, &c)| mul_mod(c % p, i as u64, p))
+        .collect();
+    let quotient = series_mul(&derivative, &series_inverse(a, n - 1, p)?, n - 1, p)?;
+    let mut log = vec![0; n];
+    for (i, &c) in quotient.iter().enumerate() {
+        log[i + 1] = mul_mod(c, mod_inverse(i as u64 + 1, p)?, p);
+    }
+    Ok(log)
+}
+
+/// Exponential `Σ aᵏ/k!`, the inverse of `series_log`:
+/// - `series_exp(&[0, 1], 4, 17) = [1, 1, 1/2, 1/6] = [1, 1, 9, 3]`
+///
+/// Newton iteration `g ← g·(1 + a - log g)`. Errors with `OutOfRange` unless the
+/// constant term is 0 and `n <= p`.
+pub fn series_exp(a: &[u64], n: usize, p: u64) -> Result<Vec<u64>, MathError> {
+    if a.first().is_some_and(|&c| c % p != 0) || n as u64 > p {
+        return Err(MathError::OutOfRange);
+    }
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    let mut g = vec![1];
+    let mut k = 1;
+    while k < n {
+        k = (2 * k).min(n);
+        let log = series_log(&g, k, p)?;
+        let mut t: Vec<u64> = (0..k)
+            .map(|i| {
+                let c = a.get(i).map_or(0, |&c| c % p);
+                (c + p - log.get(i).copied().unwrap_or(0)) % p
+            })
+            .collect();
+        t[0] = (t[0] + 1) % p;
+        g = series_mul(&g, &t, k, p)?;
+    }
+    g.resize(n, 0);
+    Ok(g)
+}
+
+/// A square root `g` with `g² ≡ a mod x^n`; the other one is `-g`:
+/// - `series_sqrt(&[4, 4, 1], 3, 17) = [2, 1, 0]` or its negation
+///
+/// Leading zeros must come in even number and the first nonzero coefficient must be a
+/// square mod `p`, otherwise there is no root and the error is `NoSolution`. Newton
+/// iteration `g ← (g + a/g)/2`, for odd `p`.
+pub fn series_sqrt(a: &[u64], n: usize, p: u64) -> Result<Vec<u64>, MathError> {
+    let Some(shift) = a.iter().take(n).position(|&c| c % p != 0) else {
+        return Ok(vec![0; n]);
+    };
+    if shift % 2 == 1 {
+        return Err(MathError::NoSolution);
+    }
+    let (b, n_root) = (&a[shift..], n - shift / 2);
+    let root = sqrt_mod(b[0], p)?.ok_or(MathError::NoSolution)?;
+    let half = mod_inverse(2, p)?;
+    let mut g = vec![root];
+    let mut k = 1;
+    while k < n_root {
+        k = (2 * k).min(n_root);
+        let quotient = series_mul(b, &series_inverse(&g, k, p)?, k, p)?;
+        g.resize(k, 0);
+        for (x, y) in g.iter_mut().zip(quotient) {
+            *x = mul_mod((*x + y) % p, half, p);
+        }
+    }
+    let mut result = vec![0; shift / 2];
+    result.extend_from_slice(&g[..n_root]);
+    Ok(result)
+}
+
+/// Partition numbers `p(0), ..., p(n - 1)` mod `p`, from Euler's generating function
+/// `Σ p(k) xᵏ = 1 / Π (1 - xᵏ)`:
+/// - `partition_numbers(8, 17) = [1, 1, 2, 3, 5, 7, 11, 15]`
+///
+/// The product is the sparse pentagonal series `Σ (-1)ʲ x^(j(3j-1)/2)` over all
+/// integers `j`, so a single `series_inverse` gives every value in O(n log n).
+pub fn partition_numbers(n: usize, p: u64) -> Result<Vec<u64>, MathError> {
+    let mut euler = vec![0; n.max(1)];
+    euler[0] = 1 % p;
+    for j in 1.. {
+        let sign = if j % 2 == 1 { p - 1 } else { 1 };
+        let first = j * (3 * j - 1) / 2;
+        if first >= n {
+            break;
+        }
+        euler[first] = sign;
+        let second = j * (3 * j + 1) / 2;
+        if second < n {
+            euler[second] = sign;
+        }
+    }
+    series_inverse(&euler, n, p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinatorics::partitions::partition_count;
+
+    const P: u64 = 998_244_353;
+
+    fn naive_mul(a: &[u64], b: &[u64], n: usize) -> Vec<u64> {
+        let mut c = vec![0; n];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                if i + j < n {
+                    c[i + j] = (c[i + j] + mul_mod(x, y, P)) % P;
+                }
+            }
+        }
+        c
+    }
+
+    fn sample(n: usize, seed: u64) -> Vec<u64> {
+        (0..n as u64)
+            .map(|i| (i * i * 31 + seed * 7 + 11) % P)
+            .collect()
+    }
+
+    #[test]
+    fn test_series_mul() {
+        assert_eq!(series_mul(&[1, 1], &[1, 1], 3, 17), Ok(vec![1, 2, 1]));
+        assert_eq!(series_mul(&[1, 1], &[1, 1], 1, 17), Ok(vec![1]));
+        assert_eq!(series_mul(&[], &[1, 1], 2, 17), Ok(vec![0, 0]));
+        let (a, b) = (sample(50, 1), sample(70, 2));
+        assert_eq!(series_mul(&a, &b, 90, P), Ok(naive_mul(&a, &b, 90)));
+        // 2^5 does not divide 10
+        assert_eq!(series_mul(&a, &b, 90, 11), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_series_inverse() {
+        assert_eq!(series_inverse(&[1, 16], 5, 17), Ok(vec![1; 5]));
+        for n in [1, 2, 7, 64, 100] {
+            let a = sample(n, 3);
+            let g = series_inverse(&a, n, P).unwrap();
+            let mut one = vec![0; n];
+            one[0] = 1;
+            assert_eq!(series_mul(&a, &g, n, P), Ok(one), "n = {n}");
+        }
+        assert_eq!(series_inverse(&[0, 1], 4, P), Err(MathError::NoInverse));
+        assert_eq!(series_inverse(&[17, 1], 4, 17), Err(MathError::NoInverse));
+    }
+
+    #[test]
+    fn test_log_and_exp() {
+        assert_eq!(series_log(&[1, 1], 4, 17), Ok(vec![0, 1, 8, 6]));
+        assert_eq!(series_exp(&[0, 1], 4, 17), Ok(vec![1, 1, 9, 3]));
+        for n in [1, 2, 5, 33, 128] {
+            let mut a = sample(n, 4);
+            a[0] = 0;
+            let e = series_exp(&a, n, P).unwrap();
+            assert_eq!(series_log(&e, n, P), Ok(a.clone()), "n = {n}");
+            // exp(a)·exp(-a) = 1
+            let neg: Vec<u64> = a.iter().map(|&c| (P - c) % P).collect();
+            let product = series_mul(&e, &series_exp(&neg, n, P).unwrap(), n, P).unwrap();
+            assert_eq!(product[0], 1);
+            assert!(product[1..].iter().all(|&c| c == 0));
+        }
+        assert_eq!(series_log(&[2, 1], 4, P), Err(MathError::OutOfRange));
+        assert_eq!(series_exp(&[1, 1], 4, P), Err(MathError::OutOfRange));
+        assert_eq!(series_exp(&[0, 1], 18, 17), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_series_sqrt() {
+        let root = series_sqrt(&[4, 4, 1], 3, 17).unwrap();
+        assert!(root == vec![2, 1, 0] || root == vec![15, 16, 0]);
+        for n in [1, 3, 16, 100] {
+            let a = sample(n, 5);
+            let square = series_mul(&a, &a, n, P).unwrap();
+            let g = series_sqrt(&square, n, P).unwrap();
+            assert_eq!(series_mul(&g, &g, n, P), Ok(square), "n = {n}");
+        }
+        // x²(1 + x) has the root x(1 + x/2 - x²/8 + ...)
+        let shifted = series_sqrt(&[0, 0, 1, 1], 4, P).unwrap();
+        assert_eq!(shifted[0], 0);
+        assert_eq!(series_mul(&shifted, &shifted, 4, P), Ok(vec![0, 0, 1, 1]));
+        assert_eq!(series_sqrt(&[0, 0], 3, P), Ok(vec![0, 0, 0]));
+        assert_eq!(series_sqrt(&[0, 1], 3, P), Err(MathError::NoSolution));
+        // 3 is not a square mod 17
+        assert_eq!(series_sqrt(&[3, 1], 3, 17), Err(MathError::NoSolution));
+    }
+
+    #[test]
+    fn test_partition_numbers() {
+        assert_eq!(partition_numbers(8, 17), Ok(vec![1, 1, 2, 3, 5, 7, 11, 15]));
+        let values = partition_numbers(500, P).unwrap();
+        for (k, &value) in values.iter().enumerate() {
+            assert_eq!(value, (partition_count(k).unwrap() % P as u128) as u64);
+        }
+        assert_eq!(partition_numbers(0, P), Ok(vec![]));
+    }
+}
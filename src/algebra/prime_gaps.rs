@@ -0,0 +1,179 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::prime_nbr::sieve;
+use crate::errors::MathError;
+
+/// Numbers sieved at a time: the working set stays in cache whatever the range.
+const SEGMENT: usize = 1 << 18;
+
+/// Two consecutive primes `start < end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimeGap {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl PrimeGap {
+    /// `end - start`.
+    pub fn size(&self) -> u64 {
+        self.end - self.start
+    }
+}
+
+/// Number of gaps of each size between consecutive primes of `lo..=hi`:
+/// - `gap_histogram(2, 30) = {1: 1, 2: 4, 4: 3, 6: 1}`
+///
+/// Only gaps with both primes inside the range count, so `π(hi) - π(lo - 1) - 1` in
+/// all, and none with fewer than two primes. Streams the primes out of a segmented
+/// sieve and keeps only the counts: O((hi - lo) log log hi) time, O(√hi) memory for
+/// the sieving primes plus a fixed segment, so ranges of width `10^12` need no more
+/// memory than small ones; about a megabyte up to `hi = 10^12`, but gigabytes near
+/// `2^64`. `MathError::OutOfRange` if `lo > hi`.
+pub fn gap_histogram(lo: u64, hi: u64) -> Result<BTreeMap<u64, u64>, MathError> {
+    let mut histogram = BTreeMap::new();
+    for_each_gap(lo, hi, |gap| *histogram.entry(gap.size()).or_insert(0) += 1)?;
+    Ok(histogram)
+}
+
+/// The largest gap between consecutive primes of `lo..=hi`, the first one on ties:
+/// - `max_gap_in_range(1, 1_000_000) = 492_113..492_227`, a gap of 114
+///
+/// `None` with fewer than two primes in the range. Same sieve and costs as
+/// `gap_histogram`. `MathError::OutOfRange` if `lo > hi`.
+pub fn max_gap_in_range(lo: u64, hi: u64) -> Result<Option<PrimeGap>, MathError> {
+    let mut best: Option<PrimeGap> = None;
+    for_each_gap(lo, hi, |gap| {
+        if best.is_none_or(|b| gap.size() > b.size()) {
+            best = Some(gap);
+        }
+    })?;
+    Ok(best)
+}
+
+fn for_each_gap(lo: u64, hi: u64, mut f: impl FnMut(PrimeGap)) -> Result<(), MathError> {
+    let mut previous = None;
+    for_each_prime(lo, hi, |p| {
+        if let Some(start) = previous {
+            f(PrimeGap { start, end: p });
+        }
+        previous = Some(p);
+    })
+}
+
+/// Calls `f` on every prime of `lo..=hi` in ascending order, a segment at a time.
+fn for_each_prime(lo: u64, hi: u64, mut f: impl FnMut(u64)) -> Result<(), MathError> {
+    if lo > hi {
+        return Err(MathError::OutOfRange);
+    }
+    let base: Vec<u64> = sieve(hi.isqrt() as usize)
+        .iter()
+        .enumerate()
+        .filter(|&(_, &prime)| prime)
+        .map(|(p, _)| p as u64)
+        .collect();
+    let mut composite = vec![false; SEGMENT];
+    let mut start = lo.max(2);
+    while start <= hi {
+        let end = hi.min(start.saturating_add(SEGMENT as u64 - 1));
+        let len = (end - start) as usize + 1;
+        composite[..len].fill(false);
+        for &p in base.iter().take_while(|&&p| p * p <= end) {
+            // the first multiple of p in the segment, not below p² so p itself survives
+            let first = (p * p).max(start.div_ceil(p).saturating_mul(p));
+            if first > end {
+                continue;
+            }
+            for i in ((first - start) as usize..len).step_by(p as usize) {
+                composite[i] = true;
+            }
+        }
+        for (i, _) in composite[..len].iter().enumerate().filter(|&(_, &c)| !c) {
+            f(start + i as u64);
+        }
+        match end.checked_add(1) {
+            Some(next) => start = next,
+            None => break,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::prime_nbr::is_prime_u64;
+    use crate::algebra::sieve::primes_up_to;
+
+    fn brute_force_gaps(lo: u64, hi: u64) -> Vec<PrimeGap> {
+        let primes: Vec<u64> = (lo..=hi).filter(|&n| is_prime_u64(n)).collect();
+        primes
+            .windows(2)
+            .map(|w| PrimeGap {
+                start: w[0],
+                end: w[1],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_gap_histogram() {
+        assert_eq!(
+            gap_histogram(2, 30),
+            Ok(BTreeMap::from([(1, 1), (2, 4), (4, 3), (6, 1)]))
+        );
+        // 25 primes below 100, 8 twin pairs
+        let below_100 = gap_histogram(0, 100).unwrap();
+        assert_eq!(below_100.values().sum::<u64>(), 24);
+        assert_eq!(below_100.get(&2), Some(&8));
+        assert_eq!(gap_histogram(24, 28), Ok(BTreeMap::new()));
+        assert_eq!(gap_histogram(7, 7), Ok(BTreeMap::new()));
+        assert_eq!(gap_histogram(8, 7), Err(MathError::OutOfRange));
+
+        // several segments
+        let primes = primes_up_to(2_000_000);
+        let total: u64 = gap_histogram(0, 2_000_000).unwrap().values().sum();
+        assert_eq!(total, primes.len() as u64 - 1);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        for (lo, hi) in [
+            (0, 1_000),
+            (1_000_000_000_000, 1_000_000_003_000),
+            (999_999_000_000, 1_000_001_000_000),
+        ] {
+            // the big ranges are checked on a window at each end
+            let windows = if hi - lo > 5_000 {
+                vec![(lo, lo + 2_000), (hi - 2_000, hi)]
+            } else {
+                vec![(lo, hi)]
+            };
+            for (a, b) in windows {
+                let expected = brute_force_gaps(a, b);
+                let mut histogram = BTreeMap::new();
+                for gap in &expected {
+                    *histogram.entry(gap.size()).or_insert(0) += 1;
+                }
+                assert_eq!(gap_histogram(a, b), Ok(histogram));
+                let largest = expected.iter().map(PrimeGap::size).max();
+                let first_largest = expected.iter().find(|g| Some(g.size()) == largest);
+                assert_eq!(max_gap_in_range(a, b), Ok(first_largest.copied()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_gap_in_range() {
+        let gap = max_gap_in_range(1, 1_000_000).unwrap().unwrap();
+        assert_eq!((gap.start, gap.end, gap.size()), (492_113, 492_227, 114));
+        assert_eq!(
+            max_gap_in_range(0, 10),
+            Ok(Some(PrimeGap { start: 3, end: 5 }))
+        );
+        assert_eq!(max_gap_in_range(90, 96), Ok(None));
+        assert_eq!(max_gap_in_range(2, 2), Ok(None));
+        assert_eq!(max_gap_in_range(3, 1), Err(MathError::OutOfRange));
+    }
+}
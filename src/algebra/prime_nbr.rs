@@ -1,22 +1,495 @@
-use std::i64::MAX;
+use alloc::vec;
+use alloc::vec::Vec;
 
+use crate::algebra::integer::Integer;
+use crate::algebra::modular::{mod_pow, mul_mod};
 use crate::errors::MathError;
 
-fn is_prime_number(a: i64) -> Result<Option<i64>, MathError> {
-    if a < 2 {
+/// What a primality test concluded about its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Proven prime (deterministic method).
+    Prime,
+    /// Passed every round of a probabilistic test.
+    ProbablePrime,
+    /// Proven composite (a witness was found).
+    Composite,
+}
+
+/// Algorithm that produced a `PrimalityResult`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrimalityMethod {
+    /// Miller-Rabin strong probable prime test.
+    MillerRabin,
+}
+
+/// Evidence backing a primality claim.
+///
+/// - `verdict`: the conclusion
+/// - `method`: the algorithm used
+/// - `rounds`: number of independent rounds run (1 for deterministic methods)
+/// - `error_bound`: upper bound on the probability that a `ProbablePrime` is composite
+///   (`0.0` when the verdict is proven)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrimalityResult {
+    pub verdict: Verdict,
+    pub method: PrimalityMethod,
+    pub rounds: u32,
+    pub error_bound: f64,
+}
+
+impl PrimalityResult {
+    /// `true` for `Prime` and `ProbablePrime`.
+    pub fn is_prime(&self) -> bool {
+        self.verdict != Verdict::Composite
+    }
+}
+
+impl From<PrimalityResult> for bool {
+    fn from(result: PrimalityResult) -> bool {
+        result.is_prime()
+    }
+}
+
+/// Error target of `PrimalityConfig::default()`: a composite is reported prime with
+/// probability below `2^-128`.
+pub const DEFAULT_ERROR_EXPONENT: u32 = 128;
+
+/// Primality testing against an error budget rather than a round count.
+///
+/// Declares "a composite may pass with probability below `2^-error_exponent`" and
+/// leaves the choice of test and rounds to `check`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimalityConfig {
+    error_exponent: u32,
+}
+
+impl PrimalityConfig {
+    pub fn new(error_exponent: u32) -> Self {
+        PrimalityConfig { error_exponent }
+    }
+
+    pub fn error_exponent(&self) -> u32 {
+        self.error_exponent
+    }
+
+    /// Miller-Rabin rounds run on inputs past `u64`, `required_mr_rounds(error_exponent)`.
+    pub fn rounds(&self) -> u32 {
+        required_mr_rounds(self.error_exponent)
+    }
+
+    /// Tests `n` within the budget, proving the answer whenever possible:
+    /// - up to `u64::MAX`, the deterministic witnesses of `is_prime` settle it with
+    ///   `error_bound = 0`, whatever the budget
+    /// - beyond, `miller_rabin_u128` runs `rounds()` rounds, so a `ProbablePrime` has
+    ///   `error_bound <= 2^-error_exponent`
+    ///
+    /// `MathError::OutOfRange` if `n < 2` or `n` does not fit in `u128`.
+    pub fn check<T: Integer>(&self, n: T) -> Result<PrimalityResult, MathError> {
+        let n = n
+            .to_u128()
+            .filter(|&n| n >= 2)
+            .ok_or(MathError::OutOfRange)?;
+        match u64::try_from(n) {
+            Ok(n) => Ok(PrimalityResult {
+                verdict: if is_prime_u64(n) {
+                    Verdict::Prime
+                } else {
+                    Verdict::Composite
+                },
+                method: PrimalityMethod::MillerRabin,
+                rounds: 1,
+                error_bound: 0.0,
+            }),
+            Err(_) => miller_rabin_u128(n, self.rounds()),
+        }
+    }
+}
+
+impl Default for PrimalityConfig {
+    fn default() -> Self {
+        PrimalityConfig::new(DEFAULT_ERROR_EXPONENT)
+    }
+}
+
+/// Miller-Rabin rounds needed to push the error probability below `2^-error_exponent`:
+/// - `required_mr_rounds(128) = 64`
+/// - `required_mr_rounds(5) = 3`
+///
+/// A composite passes one round with probability at most `1/4`, so this is
+/// `⌈error_exponent / 2⌉`, and never less than one round.
+pub fn required_mr_rounds(error_exponent: u32) -> u32 {
+    error_exponent.div_ceil(2).max(1)
+}
+
+/// Witnesses making Miller-Rabin deterministic: no composite below `3.3 · 10^24`, so
+/// none in 64 bits, is a strong pseudoprime to all of the first twelve primes.
+const DETERMINISTIC_WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Deterministic primality test for 64-bit integers:
+/// - `is_prime(1_000_000_007) = true`
+/// - `is_prime(3_825_123_056_546_413_051) = false`, a strong pseudoprime to the first
+///   eleven primes that only the witness 37 exposes
+///
+/// Runs Miller-Rabin for each of `DETERMINISTIC_WITNESSES`, so the answer is proven in
+/// O(log n) modular products. `MathError::OutOfRange` if `n < 2`.
+pub fn is_prime(n: i64) -> Result<bool, MathError> {
+    is_prime_generic(n)
+}
+
+/// `is_prime` for any `Integer` up to `u64::MAX`:
+/// - `is_prime_generic(251u8) = true`
+///
+/// `MathError::OutOfRange` if `n < 2` or `n` does not fit in `u64`.
+pub fn is_prime_generic<T: Integer>(n: T) -> Result<bool, MathError> {
+    match n.to_u128().map(u64::try_from) {
+        Some(Ok(n)) if n >= 2 => Ok(is_prime_u64(n)),
+        _ => Err(MathError::OutOfRange),
+    }
+}
+
+/// `Some((p, e))` when `n = p^e` for a prime `p` and `e >= 1`:
+/// - `as_prime_power(243) = Some((3, 5))`
+/// - `as_prime_power(97) = Some((97, 1))`
+/// - `as_prime_power(36) = None`
+///
+/// No factorization: the largest `e` for which `n` has an exact integer `e`-th root
+/// leaves a base that is not itself a perfect power, so `n` is a prime power exactly
+/// when that base passes `is_prime`. At most `log₂ n` integer roots and one
+/// deterministic Miller-Rabin.
+pub fn as_prime_power(n: u64) -> Option<(u64, u32)> {
+    if n < 2 {
+        return None;
+    }
+    if n.is_multiple_of(2) {
+        return n.is_power_of_two().then(|| (2, n.trailing_zeros()));
+    }
+    for e in (2..=n.ilog(3)).rev() {
+        let root = integer_root(n, e);
+        if root.pow(e) == n {
+            return is_prime_u64(root).then_some((root, e));
+        }
+    }
+    is_prime_u64(n).then_some((n, 1))
+}
+
+/// Miller-Rabin test running `rounds` rounds.
+///
+/// Bases are drawn from a deterministic pseudo-random stream seeded by `n`, so the
+/// same input always yields the same result. Each passed round divides the
+/// error bound by 4: `error_bound = 4^-rounds`.
+pub fn miller_rabin(n: i64, rounds: u32) -> Result<PrimalityResult, MathError> {
+    miller_rabin_generic(n, rounds)
+}
+
+/// `miller_rabin` for any `Integer`; values beyond `u64` are handed to
+/// `miller_rabin_u128`. `MathError::OutOfRange` if `n < 2` or `n` does not fit in `u128`.
+pub fn miller_rabin_generic<T: Integer>(n: T, rounds: u32) -> Result<PrimalityResult, MathError> {
+    let n = n
+        .to_u128()
+        .filter(|&n| n >= 2)
+        .ok_or(MathError::OutOfRange)?;
+    let Ok(n) = u64::try_from(n) else {
+        return miller_rabin_u128(n, rounds);
+    };
+    if rounds == 0 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+
+    let proven = |verdict| PrimalityResult {
+        verdict,
+        method: PrimalityMethod::MillerRabin,
+        rounds,
+        error_bound: 0.0,
+    };
+
+    if n < 4 {
+        return Ok(proven(Verdict::Prime));
+    }
+    if n.is_multiple_of(2) {
+        return Ok(proven(Verdict::Composite));
+    }
+
+    let (d, s) = mr_decomposition(n)?;
+
+    let mut seed = n;
+    let mut error_bound = 1.0;
+    for _ in 0..rounds {
+        // base in [2, n - 2]
+        let base = 2 + splitmix64(&mut seed) % (n - 3);
+        if !strong_probable_prime(n, d, s, base) {
+            return Ok(proven(Verdict::Composite));
+        }
+        error_bound *= 0.25;
+    }
+
+    Ok(PrimalityResult {
+        verdict: Verdict::ProbablePrime,
+        method: PrimalityMethod::MillerRabin,
+        rounds,
+        error_bound,
+    })
+}
+
+/// Miller-Rabin for `u128` inputs, with the same base stream and error bound as `miller_rabin`.
+///
+/// Modular products are computed by double-and-add, so each round costs
+/// O(log² n) additions; fine for occasional checks, slow for bulk sieving.
+pub fn miller_rabin_u128(n: u128, rounds: u32) -> Result<PrimalityResult, MathError> {
+    if n < 2 {
+        return Err(MathError::OutOfRange);
+    }
+    if rounds == 0 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+
+    let proven = |verdict| PrimalityResult {
+        verdict,
+        method: PrimalityMethod::MillerRabin,
+        rounds,
+        error_bound: 0.0,
+    };
+
+    if n < 4 {
+        return Ok(proven(Verdict::Prime));
+    }
+    if n.is_multiple_of(2) {
+        return Ok(proven(Verdict::Composite));
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    let mut seed = n as u64 ^ (n >> 64) as u64;
+    let mut error_bound = 1.0;
+    for _ in 0..rounds {
+        let random = ((splitmix64(&mut seed) as u128) << 64) | splitmix64(&mut seed) as u128;
+        let base = 2 + random % (n - 3);
+
+        let mut x = pow_mod_u128(base, d, n);
+        let mut passed = x == 1 || x == n - 1;
+        for _ in 1..s {
+            if passed {
+                break;
+            }
+            x = mul_mod_u128(x, x, n);
+            passed = x == n - 1;
+        }
+        if !passed {
+            return Ok(proven(Verdict::Composite));
+        }
+        error_bound *= 0.25;
+    }
+
+    Ok(PrimalityResult {
+        verdict: Verdict::ProbablePrime,
+        method: PrimalityMethod::MillerRabin,
+        rounds,
+        error_bound,
+    })
+}
+
+/// Smallest prime `>= n`, checked by the deterministic Miller-Rabin of `is_prime`:
+/// - `next_prime(90) = 97`
+/// - `next_prime(97) = 97`
+///
+/// `MathError::Overflow` past the largest 64-bit prime `2^64 - 59`.
+pub fn next_prime(n: u64) -> Result<u64, MathError> {
+    if n <= 2 {
+        return Ok(2);
+    }
+    let mut candidate = n | 1;
+    loop {
+        if is_prime_u64(candidate) {
+            return Ok(candidate);
+        }
+        candidate = candidate.checked_add(2).ok_or(MathError::Overflow)?;
+    }
+}
+
+/// The Miller-Rabin decomposition `n - 1 = d · 2^s` with `d` odd:
+/// - `mr_decomposition(2047) = (1023, 1)`
+/// - `mr_decomposition(561) = (35, 4)`
+///
+/// `MathError::OutOfRange` unless `n` is odd and at least 3.
+pub fn mr_decomposition(n: u64) -> Result<(u64, u32), MathError> {
+    if n < 3 || n.is_multiple_of(2) {
         return Err(MathError::OutOfRange);
     }
+    let s = (n - 1).trailing_zeros();
+    Ok(((n - 1) >> s, s))
+}
+
+/// One Miller-Rabin round: with `n - 1 = d · 2^s`, `n` passes for `base` if
+/// `base^d ≡ 1` or `base^(d·2^r) ≡ -1 (mod n)` for some `r < s`.
+///
+/// Every odd prime not dividing `base` passes; an odd composite that passes is a strong
+/// pseudoprime to `base`. `MathError::OutOfRange` unless `n` is odd and at least 3.
+pub fn is_strong_probable_prime(n: u64, base: u64) -> Result<bool, MathError> {
+    let (d, s) = mr_decomposition(n)?;
+    Ok(strong_probable_prime(n, d, s, base))
+}
+
+/// Odd composites up to `limit` that pass the Miller-Rabin round for `base`:
+/// - `strong_pseudoprimes(2, 10_000) = [2047, 3277, 4033, 4681, 8321]`
+///
+/// Compositeness comes from `sieve`, so memory is O(limit) and a composite dividing
+/// `base` is never listed (it fails the round). `MathError::OutOfRange` if `base < 2`.
+pub fn strong_pseudoprimes(base: u64, limit: usize) -> Result<Vec<u64>, MathError> {
+    if base < 2 {
+        return Err(MathError::OutOfRange);
+    }
+    let is_prime = sieve(limit);
+    Ok((9..=limit)
+        .step_by(2)
+        .filter(|&n| !is_prime[n])
+        .map(|n| n as u64)
+        .filter(|&n| {
+            let (d, s) = (
+                (n - 1) >> (n - 1).trailing_zeros(),
+                (n - 1).trailing_zeros(),
+            );
+            strong_probable_prime(n, d, s, base)
+        })
+        .collect())
+}
 
-    // factorial calcul ((a-1)!)
-    let mut factorial = 1;
-    for i in 1..a {
-        factorial = (factorial * i) % a;
+/// Sieve of Eratosthenes: entry `n` of the result tells whether `n` is prime, for
+/// `0 <= n <= limit`.
+pub fn sieve(limit: usize) -> Vec<bool> {
+    let mut is_prime = vec![true; limit + 1];
+    is_prime[0] = false;
+    if limit >= 1 {
+        is_prime[1] = false;
     }
-    // Wilson Theorem: p is prime nbr ioi ((p-1)! + 1) % p == 0;
-    if ((factorial + 1) % a) == 0 {
-        Ok(Some(a))
-    } else {
-        Ok(None)
+    let mut p = 2;
+    while p * p <= limit {
+        if is_prime[p] {
+            for multiple in (p * p..=limit).step_by(p) {
+                is_prime[multiple] = false;
+            }
+        }
+        p += 1;
+    }
+    is_prime
+}
+
+/// Deterministic Miller-Rabin for the full `u64` range, the core that `is_prime` wraps:
+/// - `is_prime_u64(2^64 - 59) = true`
+/// - `is_prime_u64(0) = is_prime_u64(1) = false`
+///
+/// Total, with no error: `is_prime` only adds the conversion from other integer types
+/// and the rejection of negative, out-of-range and `< 2` inputs.
+pub fn is_prime_u64(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    // also settles every n below 41, where a witness could be a multiple of n
+    for p in DETERMINISTIC_WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    DETERMINISTIC_WITNESSES
+        .iter()
+        .all(|&base| strong_probable_prime(n, d, s, base))
+}
+
+/// `⌊n^(1/k)⌋` for `k >= 2`, by bisection below `2^(⌊log2 n⌋ / k + 1)`.
+fn integer_root(n: u64, k: u32) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    let (mut lo, mut hi) = (1u64, 1u64 << (n.ilog2() / k + 1));
+    // lo^k <= n < hi^k
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if mid.checked_pow(k).is_some_and(|power| power <= n) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// One Miller-Rabin round for `n - 1 = d · 2^s`.
+fn strong_probable_prime(n: u64, d: u64, s: u32, base: u64) -> bool {
+    // n is odd and at least 3, so mod_pow cannot fail
+    let Ok(mut x) = mod_pow(base, d, n) else {
+        return false;
+    };
+    if x == 1 || x == n - 1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = mul_mod(x, x, n);
+        if x == n - 1 {
+            return true;
+        }
+    }
+    false
+}
+
+/// (a * b) mod m for `u128` by double-and-add; requires `a, b < m`.
+///
+/// A plain `u128` product when `m` fits in 64 bits, as it cannot overflow then.
+pub(crate) fn mul_mod_u128(mut a: u128, mut b: u128, m: u128) -> u128 {
+    if m <= u64::MAX as u128 {
+        return a * b % m;
+    }
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            // result + a without overflowing u128
+            result = if result >= m - a {
+                result - (m - a)
+            } else {
+                result + a
+            };
+        }
+        a = if a >= m - a { a - (m - a) } else { a + a };
+        b >>= 1;
+    }
+    result
+}
+
+pub(crate) fn pow_mod_u128(mut base: u128, mut exp: u128, m: u128) -> u128 {
+    let mut result = 1;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod_u128(result, base, m);
+        }
+        base = mul_mod_u128(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Uniform draw from `0..bound` out of `rng`'s uniform `u64`s, `bound >= 1`.
+///
+/// Rejection sampling: draws in the last `2^64 mod bound` values, the incomplete copy of
+/// `0..bound`, are thrown away, so no residue is favoured the way a plain `rng() % bound`
+/// favours the small ones. Fewer than two draws on average.
+pub(crate) fn uniform_below(bound: u64, rng: &mut impl FnMut() -> u64) -> u64 {
+    // 2^64 mod bound, computed as (2^64 - bound) mod bound
+    let excess = bound.wrapping_neg() % bound;
+    loop {
+        let x = rng();
+        if x <= u64::MAX - excess {
+            return x % bound;
+        }
     }
 }
 
@@ -24,20 +497,316 @@ fn is_prime_number(a: i64) -> Result<Option<i64>, MathError> {
 mod test {
     use super::*;
 
+    /// Slow reference based on Wilson's theorem `(p-1)! ≡ -1 (mod p)`, O(n) per call.
+    fn is_prime_wilson(n: i64) -> bool {
+        let n = n as u64;
+        let factorial = (1..n).fold(1 % n, |acc, i| mul_mod(acc, i, n));
+        n >= 2 && factorial == n - 1
+    }
+
+    #[test]
+    fn test_is_prime() {
+        for p in [2, 3, 37, 41, 47, 12967, 1_000_000_007, 9_999_999_967] {
+            assert_eq!(is_prime(p), Ok(true), "{p} is prime");
+        }
+        assert_eq!(is_prime(i64::MAX - 24), Ok(true));
+        for c in [4, 12, 1369, 158874, i64::MAX] {
+            assert_eq!(is_prime(c), Ok(false), "{c} is composite");
+        }
+        assert_eq!(is_prime(1), Err(MathError::OutOfRange));
+        assert_eq!(is_prime(-7), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_is_prime_generic_types() {
+        assert_eq!(is_prime_generic(u64::MAX - 58), Ok(true));
+        assert_eq!(is_prime_generic(251u8), Ok(true));
+        assert_eq!(is_prime_generic(65_535u16), Ok(false));
+        assert_eq!(is_prime_generic(1u128 << 64), Err(MathError::OutOfRange));
+        assert_eq!(is_prime_generic(-7i128), Err(MathError::OutOfRange));
+        // beyond u64, miller_rabin defers to the u128 implementation
+        let m89 = (1u128 << 89) - 1;
+        assert_eq!(miller_rabin_generic(m89, 8), miller_rabin_u128(m89, 8));
+        assert!(miller_rabin_generic(u64::MAX - 58, 8).unwrap().is_prime());
+        assert_eq!(miller_rabin_generic(-1i32, 8), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_as_prime_power() {
+        assert_eq!(as_prime_power(243), Some((3, 5)));
+        assert_eq!(as_prime_power(97), Some((97, 1)));
+        assert_eq!(as_prime_power(36), None);
+        assert_eq!(as_prime_power(0), None);
+        assert_eq!(as_prime_power(1), None);
+        for n in 2..20_000u64 {
+            let factors = crate::algebra::divisors::trial_factors(n as u128);
+            let expected = match factors[..] {
+                [(p, e)] => Some((p as u64, e)),
+                _ => None,
+            };
+            assert_eq!(as_prime_power(n), expected, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn test_as_prime_power_large() {
+        let m31 = (1u64 << 31) - 1;
+        assert_eq!(as_prime_power(1 << 63), Some((2, 63)));
+        assert_eq!(as_prime_power(3u64.pow(40)), Some((3, 40)));
+        assert_eq!(as_prime_power(m31 * m31), Some((m31, 2)));
+        assert_eq!(
+            as_prime_power(4_294_967_291u64.pow(2)),
+            Some((4_294_967_291, 2))
+        );
+        assert_eq!(as_prime_power(u64::MAX - 58), Some((u64::MAX - 58, 1)));
+        // perfect powers of composites and products of two large primes
+        assert_eq!(as_prime_power(6u64.pow(24)), None);
+        assert_eq!(as_prime_power(m31 * (m31 - 2)), None);
+        assert_eq!(as_prime_power(u64::MAX), None);
+        for k in 2..=64 {
+            let root = integer_root(u64::MAX, k);
+            assert!(root.checked_pow(k).is_some());
+            assert!((root + 1).checked_pow(k).is_none(), "k = {k}");
+        }
+    }
+
+    #[test]
+    fn test_is_prime_strong_pseudoprimes() {
+        // least strong pseudoprimes to the first 4, 8 and 11 prime bases (OEIS A014233)
+        for n in [
+            3_215_031_751u64,
+            341_550_071_728_321,
+            3_825_123_056_546_413_051,
+        ] {
+            assert!(is_strong_probable_prime(n, 2).unwrap());
+            assert_eq!(is_prime(n as i64), Ok(false), "{n}");
+        }
+        // full u64 range, including the largest 64-bit prime
+        assert!(is_prime_u64(u64::MAX - 58));
+        assert!(!is_prime_u64(u64::MAX));
+        assert!(!is_prime_u64(0));
+    }
+
+    #[test]
+    fn test_is_prime_agrees_with_references() {
+        for n in 2..2000 {
+            assert_eq!(is_prime(n), Ok(is_prime_wilson(n)), "n = {n}");
+        }
+        for (n, &prime) in sieve(100_000).iter().enumerate().skip(2) {
+            assert_eq!(is_prime(n as i64), Ok(prime), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn test_miller_rabin_primes() {
+        for p in [
+            2,
+            3,
+            5,
+            47,
+            12967,
+            111697,
+            1122157,
+            1_000_000_007,
+            4_294_967_291,
+        ] {
+            let result = miller_rabin(p, 10).unwrap();
+            assert!(bool::from(result), "{} should be prime", p);
+        }
+    }
+
+    #[test]
+    fn test_miller_rabin_composites() {
+        // 561 and 41041 are Carmichael numbers
+        for c in [4, 12, 561, 41041, 158874, 1_000_000_007 * 3] {
+            let result = miller_rabin(c, 10).unwrap();
+            assert_eq!(result.verdict, Verdict::Composite, "{} is composite", c);
+            assert_eq!(result.error_bound, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_miller_rabin_metadata() {
+        let result = miller_rabin(1_000_000_007, 5).unwrap();
+        assert_eq!(result.verdict, Verdict::ProbablePrime);
+        assert_eq!(result.method, PrimalityMethod::MillerRabin);
+        assert_eq!(result.rounds, 5);
+        assert_eq!(result.error_bound, 1.0 / 1024.0);
+
+        let small = miller_rabin(3, 5).unwrap();
+        assert_eq!(small.verdict, Verdict::Prime);
+        assert_eq!(small.error_bound, 0.0);
+    }
+
+    #[test]
+    fn test_miller_rabin_errors() {
+        assert_eq!(miller_rabin(1, 5), Err(MathError::OutOfRange));
+        assert_eq!(miller_rabin(-7, 5), Err(MathError::OutOfRange));
+        assert_eq!(miller_rabin(7, 0), Err(MathError::PositifIntegerRequired));
+    }
+
+    #[test]
+    fn test_agrees_with_wilson() {
+        for n in 2..2000 {
+            let wilson = is_prime_wilson(n);
+            assert_eq!(miller_rabin(n, 8).unwrap().is_prime(), wilson, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_miller_rabin_u128() {
+        // 2^89 - 1 and 2^127 - 1 are Mersenne primes
+        for p in [2u128, 3, 1_000_000_007, (1 << 89) - 1, (1 << 127) - 1] {
+            assert!(
+                miller_rabin_u128(p, 16).unwrap().is_prime(),
+                "{} is prime",
+                p
+            );
+        }
+        // 2^67 - 1 = 193707721 * 761838257287
+        for c in [4u128, 561, (1 << 67) - 1, ((1 << 61) - 1) * ((1 << 31) - 1)] {
+            let result = miller_rabin_u128(c, 16).unwrap();
+            assert_eq!(result.verdict, Verdict::Composite, "{} is composite", c);
+        }
+        assert_eq!(miller_rabin_u128(1, 4), Err(MathError::OutOfRange));
+        assert_eq!(
+            miller_rabin_u128(u128::MAX, 0),
+            Err(MathError::PositifIntegerRequired)
+        );
+    }
+
     #[test]
-    fn test_is_prime_nbr() {
-        assert_eq!(is_prime_number(47), Ok(Some(47)));
-        assert_eq!(is_prime_number(12967), Ok(Some(12967)));
-        assert_eq!(is_prime_number(111697), Ok(Some(111697)));
-        assert_eq!(is_prime_number(1122157), Ok(Some(1122157)));
+    fn test_mr_decomposition() {
+        assert_eq!(mr_decomposition(3), Ok((1, 1)));
+        assert_eq!(mr_decomposition(2047), Ok((1023, 1)));
+        assert_eq!(mr_decomposition(561), Ok((35, 4)));
+        assert_eq!(mr_decomposition(u64::MAX), Ok((u64::MAX >> 1, 1)));
+        assert_eq!(mr_decomposition(10), Err(MathError::OutOfRange));
+        assert_eq!(mr_decomposition(1), Err(MathError::OutOfRange));
+        assert_eq!(is_strong_probable_prime(2047, 2), Ok(true));
+        assert_eq!(is_strong_probable_prime(2047, 3), Ok(false));
+        assert_eq!(is_strong_probable_prime(u64::MAX - 58, 2), Ok(true));
+    }
+
+    #[test]
+    fn test_sieve() {
+        let is_prime = sieve(30);
+        let primes: Vec<usize> = (0..=30).filter(|&n| is_prime[n]).collect();
+        assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(sieve(0), vec![false]);
+        assert_eq!(sieve(1), vec![false, false]);
+        for (n, &prime) in sieve(2000).iter().enumerate().skip(2) {
+            assert_eq!(prime, miller_rabin(n as i64, 8).unwrap().is_prime());
+        }
+    }
+
+    #[test]
+    fn test_strong_pseudoprimes() {
+        // OEIS A001262 and A020229
+        assert_eq!(
+            strong_pseudoprimes(2, 100_000),
+            Ok(vec![
+                2047, 3277, 4033, 4681, 8321, 15841, 29341, 42799, 49141, 52633, 65281, 74665,
+                80581, 85489, 88357, 90751
+            ])
+        );
+        assert_eq!(
+            strong_pseudoprimes(3, 20_000),
+            Ok(vec![
+                121, 703, 1891, 3281, 8401, 8911, 10585, 12403, 16531, 18721, 19345
+            ])
+        );
+        // the first strong pseudoprime to both 2 and 3 is 1373653
+        let both: Vec<u64> = strong_pseudoprimes(2, 100_000)
+            .unwrap()
+            .into_iter()
+            .filter(|&n| is_strong_probable_prime(n, 3).unwrap())
+            .collect();
+        assert!(both.is_empty());
+        assert_eq!(strong_pseudoprimes(2, 8), Ok(vec![]));
+        assert_eq!(strong_pseudoprimes(1, 100), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_next_prime() {
+        assert_eq!(next_prime(0), Ok(2));
+        assert_eq!(next_prime(3), Ok(3));
+        assert_eq!(next_prime(4), Ok(5));
+        assert_eq!(next_prime(90), Ok(97));
+        assert_eq!(next_prime(1_000_000_000), Ok(1_000_000_007));
+        assert_eq!(next_prime(u64::MAX - 100), Ok(u64::MAX - 94));
+        assert_eq!(next_prime(u64::MAX - 58), Ok(u64::MAX - 58));
+        assert_eq!(next_prime(u64::MAX - 57), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_miller_rabin_u128_agrees_with_i64() {
+        for n in 2..3000i64 {
+            assert_eq!(
+                miller_rabin_u128(n as u128, 8).unwrap().is_prime(),
+                miller_rabin(n, 8).unwrap().is_prime()
+            );
+        }
+    }
+
+    #[test]
+    fn test_required_mr_rounds() {
+        assert_eq!(required_mr_rounds(128), 64);
+        assert_eq!(required_mr_rounds(5), 3);
+        assert_eq!(required_mr_rounds(1), 1);
+        assert_eq!(required_mr_rounds(0), 1);
+        for e in 0..200 {
+            let rounds = required_mr_rounds(e);
+            assert!(0.25f64.powi(rounds as i32) <= 0.5f64.powi(e as i32));
+            assert!(rounds == 1 || 0.25f64.powi(rounds as i32 - 1) > 0.5f64.powi(e as i32));
+        }
+    }
+
+    #[test]
+    fn test_primality_config() {
+        let config = PrimalityConfig::default();
+        assert_eq!(config.error_exponent(), DEFAULT_ERROR_EXPONENT);
+        assert_eq!(config.rounds(), 64);
+
+        // within u64 the answer is proven
+        let result = config.check(1_000_000_007u64).unwrap();
+        assert_eq!(result.verdict, Verdict::Prime);
+        assert_eq!(result.error_bound, 0.0);
+        let result = config.check(3_825_123_056_546_413_051u64).unwrap();
+        assert_eq!(result.verdict, Verdict::Composite);
+
+        // 2^89 - 1 is prime, (2^61 - 1)(2^31 - 1) is not
+        let m89 = (1u128 << 89) - 1;
+        let result = config.check(m89).unwrap();
+        assert_eq!(result.verdict, Verdict::ProbablePrime);
+        assert_eq!(result.rounds, 64);
+        assert!(result.error_bound <= 0.5f64.powi(128));
+        let result = PrimalityConfig::new(20).check(m89).unwrap();
+        assert_eq!(result.rounds, 10);
+        let composite = ((1u128 << 61) - 1) * ((1 << 31) - 1);
+        assert_eq!(config.check(composite).unwrap().verdict, Verdict::Composite);
 
-        assert_eq!(is_prime_number(12), Ok(None));
-        assert_eq!(is_prime_number(158874), Ok(None));
+        assert_eq!(config.check(1u8), Err(MathError::OutOfRange));
+        assert_eq!(config.check(-7i64), Err(MathError::OutOfRange));
     }
 
     #[test]
-    fn test_out_of_range() {
-        assert_eq!(is_prime_number(0), Err(MathError::OutOfRange));
-        assert_eq!(is_prime_number(1), Err(MathError::OutOfRange));
+    fn test_uniform_below() {
+        let draws = |values: Vec<u64>| {
+            let mut values = values.into_iter();
+            move || values.next().unwrap()
+        };
+        // 2^64 = 3·2^62 + 2^62: the top 2^62 draws are rejected
+        let bound = 3 << 62;
+        let mut rng = draws(vec![u64::MAX, 3 << 62, (3 << 62) - 1]);
+        assert_eq!(uniform_below(bound, &mut rng), (3 << 62) - 1);
+        let mut rng = draws(vec![u64::MAX, 7]);
+        assert_eq!(uniform_below(u64::MAX, &mut rng), 7);
+        let mut rng = draws(vec![u64::MAX]);
+        assert_eq!(uniform_below(1, &mut rng), 0);
+        assert_eq!(
+            uniform_below(1 << 63, &mut draws(vec![u64::MAX])),
+            (1 << 63) - 1
+        );
     }
 }
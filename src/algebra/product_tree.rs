@@ -0,0 +1,165 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::errors::MathError;
+
+/// Integer type usable as a product/remainder tree node.
+///
+/// Implemented for the native unsigned types with overflow checks;
+/// big-integer types can implement it to lift the size limit, and `PolyMod`
+/// implements it for trees of polynomials.
+pub trait TreeInt: Clone {
+    fn tree_mul(&self, other: &Self) -> Result<Self, MathError>;
+    fn tree_rem(&self, modulus: &Self) -> Result<Self, MathError>;
+}
+
+macro_rules! impl_tree_int {
+    ($($t:ty),*) => {
+        $(
+            impl TreeInt for $t {
+                fn tree_mul(&self, other: &Self) -> Result<Self, MathError> {
+                    self.checked_mul(*other).ok_or(MathError::Overflow)
+                }
+
+                fn tree_rem(&self, modulus: &Self) -> Result<Self, MathError> {
+                    self.checked_rem(*modulus).ok_or(MathError::DivisionByZero)
+                }
+            }
+        )*
+    };
+}
+
+impl_tree_int!(u32, u64, u128);
+
+/// Builds the product tree of `leaves`, bottom level first.
+///
+/// `tree[0]` is the leaves, each next level holds products of adjacent pairs
+/// (an odd node out is carried up unchanged), and the last level is `[∏ leaves]`:
+/// - `product_tree(&[2, 3, 5, 7]) = [[2, 3, 5, 7], [6, 35], [210]]`
+pub fn product_tree<T: TreeInt>(leaves: &[T]) -> Result<Vec<Vec<T>>, MathError> {
+    if leaves.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+
+    let mut tree = vec![leaves.to_vec()];
+    while let Some(level) = tree.last().filter(|level| level.len() > 1) {
+        let next = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => a.tree_mul(b),
+                [a] => Ok(a.clone()),
+                _ => unreachable!(),
+            })
+            .collect::<Result<Vec<T>, MathError>>()?;
+        tree.push(next);
+    }
+    Ok(tree)
+}
+
+/// Reduces `n` modulo every leaf of `tree`, walking down from the root.
+///
+/// Each node only reduces its parent's (already small) remainder, which is what
+/// makes batch reduction cheaper than `leaves.len()` independent reductions:
+/// - `remainder_tree(&100, &product_tree(&[3, 7, 11])?) = [1, 2, 1]`
+///
+/// Every level must hold half as many nodes as the one below it, rounded up,
+/// as [`product_tree`] builds it; any other shape is a `DimensionMismatch`.
+pub fn remainder_tree<T: TreeInt>(n: &T, tree: &[Vec<T>]) -> Result<Vec<T>, MathError> {
+    let Some(root) = tree.last() else {
+        return Err(MathError::EmptyInput);
+    };
+
+    let mut remainders = root
+        .iter()
+        .map(|node| n.tree_rem(node))
+        .collect::<Result<Vec<T>, MathError>>()?;
+
+    for level in tree.iter().rev().skip(1) {
+        if remainders.len() != level.len().div_ceil(2) {
+            return Err(MathError::DimensionMismatch);
+        }
+        remainders = level
+            .iter()
+            .enumerate()
+            .map(|(i, node)| remainders[i / 2].tree_rem(node))
+            .collect::<Result<Vec<T>, MathError>>()?;
+    }
+    Ok(remainders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_product_tree_shape() {
+        let tree = product_tree(&[2u64, 3, 5, 7]).unwrap();
+        assert_eq!(tree, vec![vec![2, 3, 5, 7], vec![6, 35], vec![210]]);
+    }
+
+    #[test]
+    fn test_product_tree_odd_length() {
+        let tree = product_tree(&[2u64, 3, 5, 7, 11]).unwrap();
+        assert_eq!(
+            tree,
+            vec![
+                vec![2, 3, 5, 7, 11],
+                vec![6, 35, 11],
+                vec![210, 11],
+                vec![2310]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_product_tree_single_and_empty() {
+        assert_eq!(product_tree(&[42u64]), Ok(vec![vec![42]]));
+        assert_eq!(product_tree::<u64>(&[]), Err(MathError::EmptyInput));
+    }
+
+    #[test]
+    fn test_product_tree_overflow() {
+        assert_eq!(product_tree(&[u64::MAX, 2]), Err(MathError::Overflow));
+        // the same leaves fit once lifted to u128
+        let tree = product_tree(&[u64::MAX as u128, 2]).unwrap();
+        assert_eq!(tree.last().unwrap()[0], u64::MAX as u128 * 2);
+    }
+
+    #[test]
+    fn test_remainder_tree() {
+        let tree = product_tree(&[3u64, 7, 11]).unwrap();
+        assert_eq!(remainder_tree(&100, &tree), Ok(vec![1, 2, 1]));
+    }
+
+    #[test]
+    fn test_remainder_tree_matches_direct_reduction() {
+        let moduli = [97u128, 101, 103, 107, 109, 113, 127];
+        let n = 123_456_789_012_345_678_901_234_567u128;
+        let tree = product_tree(&moduli).unwrap();
+        let expected: Vec<u128> = moduli.iter().map(|m| n % m).collect();
+        assert_eq!(remainder_tree(&n, &tree), Ok(expected));
+    }
+
+    #[test]
+    fn test_remainder_tree_errors() {
+        assert_eq!(remainder_tree::<u64>(&5, &[]), Err(MathError::EmptyInput));
+        let tree = product_tree(&[0u64, 3]).unwrap();
+        assert_eq!(remainder_tree(&5, &tree), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_remainder_tree_malformed() {
+        // a parent level too short for its children
+        let tree = vec![vec![3u64, 7, 11, 13], vec![21], vec![21]];
+        assert_eq!(
+            remainder_tree(&100, &tree),
+            Err(MathError::DimensionMismatch)
+        );
+        // a parent level too long for its children
+        let tree = vec![vec![3u64, 7], vec![3, 7], vec![21]];
+        assert_eq!(
+            remainder_tree(&100, &tree),
+            Err(MathError::DimensionMismatch)
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::errors::MathError;
+
+/// Pythagorean triple `a² + b² = c²`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Triple {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+}
+
+/// Which Berggren matrix leads from a parent to a child.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Branch {
+    A,
+    B,
+    C,
+}
+
+const BRANCHES: [Branch; 3] = [Branch::A, Branch::B, Branch::C];
+
+/// Root of the tree, `(3, 4, 5)`.
+pub const ROOT: Triple = Triple { a: 3, b: 4, c: 5 };
+
+impl Branch {
+    fn matrix(self) -> [[i128; 3]; 3] {
+        match self {
+            Branch::A => [[1, -2, 2], [2, -1, 2], [2, -2, 3]],
+            Branch::B => [[1, 2, 2], [2, 1, 2], [2, 2, 3]],
+            Branch::C => [[-1, 2, 2], [-2, 1, 2], [-2, 2, 3]],
+        }
+    }
+
+    /// `M⁻¹ = Q·Mᵀ·Q` with `Q = diag(1, 1, -1)`.
+    fn inverse_matrix(self) -> [[i128; 3]; 3] {
+        let m = self.matrix();
+        let sign = |i: usize| if i == 2 { -1 } else { 1 };
+        let mut inv = [[0; 3]; 3];
+        for (i, row) in inv.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                *entry = sign(i) * m[j][i] * sign(j);
+            }
+        }
+        inv
+    }
+}
+
+impl Triple {
+    /// `true` if `a² + b² = c²`, all sides are positive and `gcd(a, b) = 1`.
+    pub fn is_primitive(&self) -> bool {
+        let (a, b, c) = (self.a as u128, self.b as u128, self.c as u128);
+        let (mut x, mut y) = (self.a, self.b);
+        while y != 0 {
+            (x, y) = (y, x % y);
+        }
+        a > 0 && b > 0 && a * a + b * b == c * c && x == 1
+    }
+
+    /// Child along one Berggren branch; `MathError::Overflow` if a side exceeds `u64`.
+    pub fn child(&self, branch: Branch) -> Result<Triple, MathError> {
+        let [a, b, c] = apply(branch.matrix(), self);
+        let side = |x: i128| u64::try_from(x).map_err(|_| MathError::Overflow);
+        Ok(Triple {
+            a: side(a)?,
+            b: side(b)?,
+            c: side(c)?,
+        })
+    }
+
+    /// The three children, in A, B, C order.
+    pub fn children(&self) -> Result<[Triple; 3], MathError> {
+        Ok([
+            self.child(Branch::A)?,
+            self.child(Branch::B)?,
+            self.child(Branch::C)?,
+        ])
+    }
+
+    /// Parent triple and the branch leading back here, or `None` for the root.
+    ///
+    /// `MathError::OutOfRange` if `self` is not a primitive triple with legs in
+    /// the tree's orientation (`(4, 3, 5)` is not in the tree, `(3, 4, 5)` is).
+    pub fn parent(&self) -> Result<Option<(Triple, Branch)>, MathError> {
+        if !self.is_primitive() {
+            return Err(MathError::OutOfRange);
+        }
+        if *self == ROOT {
+            return Ok(None);
+        }
+        for branch in BRANCHES {
+            let [a, b, c] = apply(branch.inverse_matrix(), self);
+            if a > 0 && b > 0 && c > 0 {
+                let parent = Triple {
+                    a: a as u64,
+                    b: b as u64,
+                    c: c as u64,
+                };
+                return Ok(Some((parent, branch)));
+            }
+        }
+        Err(MathError::OutOfRange)
+    }
+
+    /// Branches from the root down to `self` (empty for the root).
+    pub fn path_from_root(&self) -> Result<Vec<Branch>, MathError> {
+        let mut path = Vec::new();
+        let mut current = *self;
+        while let Some((parent, branch)) = current.parent()? {
+            path.push(branch);
+            current = parent;
+        }
+        path.reverse();
+        Ok(path)
+    }
+}
+
+/// Every primitive triple within `depth` levels of `(3, 4, 5)`, in breadth-first order.
+///
+/// The Berggren/Barning tree reaches each primitive triple exactly once, so no gcd
+/// filtering is needed. Level `k` holds `3^k` triples:
+/// - `triple_tree(1) = [(3, 4, 5), (5, 12, 13), (21, 20, 29), (15, 8, 17)]`
+pub fn triple_tree(depth: u32) -> Result<Vec<Triple>, MathError> {
+    let mut triples = vec![ROOT];
+    let mut level_start = 0;
+    for _ in 0..depth {
+        let level_end = triples.len();
+        for i in level_start..level_end {
+            let children = triples[i].children()?;
+            triples.extend(children);
+        }
+        level_start = level_end;
+    }
+    Ok(triples)
+}
+
+fn apply(m: [[i128; 3]; 3], t: &Triple) -> [i128; 3] {
+    let v = [t.a as i128, t.b as i128, t.c as i128];
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(a: u64, b: u64, c: u64) -> Triple {
+        Triple { a, b, c }
+    }
+
+    #[test]
+    fn test_children_of_root() {
+        assert_eq!(
+            ROOT.children(),
+            Ok([t(5, 12, 13), t(21, 20, 29), t(15, 8, 17)])
+        );
+    }
+
+    #[test]
+    fn test_triple_tree_levels() {
+        assert_eq!(triple_tree(0), Ok(vec![ROOT]));
+        let tree = triple_tree(4).unwrap();
+        assert_eq!(tree.len(), 1 + 3 + 9 + 27 + 81);
+        assert!(tree.iter().all(Triple::is_primitive));
+
+        // no duplicates, even up to leg order
+        let mut keys: Vec<(u64, u64, u64)> = tree
+            .iter()
+            .map(|t| (t.a.min(t.b), t.a.max(t.b), t.c))
+            .collect();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), tree.len());
+    }
+
+    #[test]
+    fn test_tree_contains_all_small_primitive_triples() {
+        // primitive triples with c <= 100 (there are 16) appear by depth 5
+        let tree = triple_tree(5).unwrap();
+        let mut small: Vec<u64> = tree.iter().filter(|t| t.c <= 100).map(|t| t.c).collect();
+        small.sort();
+        assert_eq!(
+            small,
+            vec![
+                5, 13, 17, 25, 29, 37, 41, 53, 61, 65, 65, 73, 85, 85, 89, 97
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parent_navigation() {
+        assert_eq!(ROOT.parent(), Ok(None));
+        assert_eq!(t(5, 12, 13).parent(), Ok(Some((ROOT, Branch::A))));
+        assert_eq!(t(15, 8, 17).parent(), Ok(Some((ROOT, Branch::C))));
+
+        for triple in triple_tree(4).unwrap() {
+            for branch in BRANCHES {
+                let child = triple.child(branch).unwrap();
+                assert_eq!(child.parent(), Ok(Some((triple, branch))));
+            }
+        }
+    }
+
+    #[test]
+    fn test_path_from_root() {
+        let target = ROOT
+            .child(Branch::B)
+            .and_then(|t| t.child(Branch::C))
+            .and_then(|t| t.child(Branch::A))
+            .unwrap();
+        assert_eq!(
+            target.path_from_root(),
+            Ok(vec![Branch::B, Branch::C, Branch::A])
+        );
+        assert_eq!(ROOT.path_from_root(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parent_rejects_non_primitive() {
+        assert_eq!(t(6, 8, 10).parent(), Err(MathError::OutOfRange));
+        assert_eq!(t(3, 4, 6).parent(), Err(MathError::OutOfRange));
+        assert_eq!(t(4, 3, 5).parent(), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_child_overflow() {
+        let big = t(u64::MAX / 2, 1, u64::MAX / 2);
+        assert_eq!(big.child(Branch::B), Err(MathError::Overflow));
+    }
+}
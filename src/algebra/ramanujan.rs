@@ -0,0 +1,122 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::prime_nbr::{next_prime, sieve};
+use crate::errors::MathError;
+
+/// Ramanujan primes up to `n`: `R_k` is the least integer such that
+/// `π(x) - π(x/2) >= k` for every `x >= R_k`, and it is always prime:
+/// - `ramanujan_primes_upto(100) = [2, 11, 17, 29, 41, 47, 59, 67, 71, 97]`
+///
+/// Ramanujan introduced them to prove Bertrand's postulate (`R_1 = 2`). The count
+/// `π(x) - π(x/2)` is sieved up to `p_{3K}`, where `K = π(n) - π(n/2)` bounds the
+/// indices of the Ramanujan primes up to `n`; Laishram's theorem `R_k < p_{3k}`
+/// guarantees nothing beyond that point can change the result. O(n log log n).
+pub fn ramanujan_primes_upto(n: usize) -> Vec<u64> {
+    let counts = half_interval_counts(&sieve(n));
+    let k_max = counts[n];
+    if k_max == 0 {
+        return Vec::new();
+    }
+
+    // sieve far enough to hold the 3K-th prime
+    let mut limit = 2 * n.max(8);
+    let is_prime = loop {
+        let is_prime = sieve(limit);
+        if is_prime.iter().filter(|&&p| p).count() >= 3 * k_max {
+            break is_prime;
+        }
+        limit *= 2;
+    };
+    let counts = half_interval_counts(&is_prime);
+
+    // R_k - 1 is the last x with fewer than k primes in (x/2, x]
+    let mut last = vec![0usize; 3 * k_max + 1];
+    for (x, &c) in counts.iter().enumerate() {
+        if c < last.len() {
+            last[c] = x;
+        }
+    }
+    let mut latest = 0;
+    (1..=k_max)
+        .map(|k| {
+            latest = latest.max(last[k - 1]);
+            latest + 1
+        })
+        .filter(|&r| r <= n)
+        .map(|r| r as u64)
+        .collect()
+}
+
+/// The least prime in `(n, 2n]`, which Bertrand's postulate guarantees for `n >= 1`:
+/// - `bertrand_prime(10) = 11`
+/// - `bertrand_prime(24) = 29`
+///
+/// Found by `next_prime`. `MathError::OutOfRange` if `n == 0` and `Overflow` if the
+/// prime exceeds `u64`.
+pub fn bertrand_prime(n: u64) -> Result<u64, MathError> {
+    if n == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    next_prime(n.checked_add(1).ok_or(MathError::Overflow)?)
+}
+
+/// `π(x) - π(⌊x/2⌋)` for every `x` covered by the sieve.
+fn half_interval_counts(is_prime: &[bool]) -> Vec<usize> {
+    let mut pi = Vec::with_capacity(is_prime.len());
+    let mut count = 0;
+    for &p in is_prime {
+        count += p as usize;
+        pi.push(count);
+    }
+    (0..pi.len()).map(|x| pi[x] - pi[x / 2]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ramanujan_primes() {
+        // OEIS A104272
+        assert_eq!(
+            ramanujan_primes_upto(500),
+            vec![
+                2, 11, 17, 29, 41, 47, 59, 67, 71, 97, 101, 107, 127, 149, 151, 167, 179, 181, 227,
+                229, 233, 239, 241, 263, 269, 281, 307, 311, 347, 349, 367, 373, 401, 409, 419,
+                431, 433, 439, 461, 487, 491
+            ]
+        );
+        assert_eq!(ramanujan_primes_upto(10), vec![2]);
+        assert_eq!(ramanujan_primes_upto(11), vec![2, 11]);
+        assert_eq!(ramanujan_primes_upto(1), vec![]);
+        assert_eq!(ramanujan_primes_upto(0), vec![]);
+    }
+
+    #[test]
+    fn test_ramanujan_definition() {
+        let n = 5000;
+        let primes = ramanujan_primes_upto(n);
+        let is_prime = sieve(4 * n);
+        let counts = half_interval_counts(&is_prime);
+        for (k, &r) in primes.iter().enumerate() {
+            let r = r as usize;
+            assert!(is_prime[r]);
+            assert!(counts[r..].iter().all(|&c| c > k));
+            assert!(counts[r - 1] <= k);
+        }
+    }
+
+    #[test]
+    fn test_bertrand_prime() {
+        assert_eq!(bertrand_prime(1), Ok(2));
+        assert_eq!(bertrand_prime(10), Ok(11));
+        assert_eq!(bertrand_prime(24), Ok(29));
+        for n in 1..2000u64 {
+            let p = bertrand_prime(n).unwrap();
+            assert!(n < p && p <= 2 * n);
+        }
+        assert_eq!(bertrand_prime(0), Err(MathError::OutOfRange));
+        assert_eq!(bertrand_prime(u64::MAX - 10), Err(MathError::Overflow));
+    }
+}
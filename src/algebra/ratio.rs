@@ -0,0 +1,101 @@
+use alloc::vec::Vec;
+
+use crate::algebra::gcd::gcd_u128;
+use crate::errors::MathError;
+
+/// Divides every element by the collective gcd, in place, and returns that gcd.
+///
+/// Signs are preserved and zeros are allowed as long as one element is nonzero:
+/// - `[4, -6, 10]` becomes `[2, -3, 5]`, returns `2`
+/// - `[0, 12, 18]` becomes `[0, 2, 3]`, returns `6`
+///
+/// Errors with `EmptyInput` for an empty slice and `DivisionByZero` when all
+/// elements are zero.
+pub fn normalize_ratio(values: &mut [i64]) -> Result<i64, MathError> {
+    let factor = ratio_gcd(values)?;
+    for value in values.iter_mut() {
+        *value /= factor;
+    }
+    Ok(factor)
+}
+
+/// Non-mutating `normalize_ratio`: returns the reduced values and the factor removed.
+pub fn normalized_ratio(values: &[i64]) -> Result<(Vec<i64>, i64), MathError> {
+    let mut reduced = values.to_vec();
+    let factor = normalize_ratio(&mut reduced)?;
+    Ok((reduced, factor))
+}
+
+/// Positive gcd of all elements.
+fn ratio_gcd(values: &[i64]) -> Result<i64, MathError> {
+    if values.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    let g = values
+        .iter()
+        .fold(0u128, |g, v| gcd_u128(g, v.unsigned_abs() as u128));
+    if g == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    // only [i64::MIN, 0, ...] has a gcd of 2^63
+    i64::try_from(g).map_err(|_| MathError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_normalize_ratio() {
+        let mut values = [4, -6, 10];
+        assert_eq!(normalize_ratio(&mut values), Ok(2));
+        assert_eq!(values, [2, -3, 5]);
+
+        let mut values = [0, 12, 18];
+        assert_eq!(normalize_ratio(&mut values), Ok(6));
+        assert_eq!(values, [0, 2, 3]);
+    }
+
+    #[test]
+    fn test_normalize_ratio_already_reduced() {
+        let mut values = [16, 9];
+        assert_eq!(normalize_ratio(&mut values), Ok(1));
+        assert_eq!(values, [16, 9]);
+    }
+
+    #[test]
+    fn test_normalize_ratio_single_value() {
+        let mut values = [-42];
+        assert_eq!(normalize_ratio(&mut values), Ok(42));
+        assert_eq!(values, [-1]);
+    }
+
+    #[test]
+    fn test_normalize_ratio_errors() {
+        assert_eq!(normalize_ratio(&mut []), Err(MathError::EmptyInput));
+
+        let mut zeros = [0, 0, 0];
+        assert_eq!(normalize_ratio(&mut zeros), Err(MathError::DivisionByZero));
+        assert_eq!(zeros, [0, 0, 0]);
+
+        let mut min = [i64::MIN, 0];
+        assert_eq!(normalize_ratio(&mut min), Err(MathError::Overflow));
+        assert_eq!(min, [i64::MIN, 0]);
+    }
+
+    #[test]
+    fn test_normalize_ratio_extreme_values() {
+        let mut values = [i64::MIN, i64::MIN / 2];
+        assert_eq!(normalize_ratio(&mut values), Ok(1 << 62));
+        assert_eq!(values, [-2, -1]);
+    }
+
+    #[test]
+    fn test_normalized_ratio() {
+        let values = [1920, 1080];
+        assert_eq!(normalized_ratio(&values), Ok((vec![16, 9], 120)));
+        assert_eq!(values, [1920, 1080]);
+        assert_eq!(normalized_ratio(&[]), Err(MathError::EmptyInput));
+    }
+}
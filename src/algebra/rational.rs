@@ -0,0 +1,505 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Sub};
+
+use crate::algebra::gcd::gcd_u128;
+use crate::errors::MathError;
+
+/// How `Rational::round_to`, `Rational::round_to_max_denominator` and
+/// `Rational::to_fixed_point` resolve a value that falls between two representable
+/// results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Toward negative infinity.
+    Floor,
+    /// Toward positive infinity.
+    Ceil,
+    /// To the nearest, ties to the even neighbour (banker's rounding).
+    HalfEven,
+}
+
+/// Exact fraction `numer / denom`, always stored in lowest terms with `denom > 0`.
+///
+/// Arithmetic is checked: intermediate products are taken in `i128` and the reduced
+/// result must fit back in `i64`, otherwise `MathError::Overflow`. The `+ - * /`
+/// operators are the `checked_*` methods and return a `Result`, so `(a + b)?` reads
+/// like plain arithmetic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i64,
+    denom: i64,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numer: 0, denom: 1 };
+    pub const ONE: Rational = Rational { numer: 1, denom: 1 };
+
+    /// Reduced `numer / denom`:
+    /// - `Rational::new(6, -4) = -3/2`
+    ///
+    /// Errors with `DivisionByZero` if `denom == 0`.
+    pub fn new(numer: i64, denom: i64) -> Result<Self, MathError> {
+        Rational::from_i128(numer as i128, denom as i128)
+    }
+
+    pub fn from_integer(n: i64) -> Self {
+        Rational { numer: n, denom: 1 }
+    }
+
+    pub fn numer(&self) -> i64 {
+        self.numer
+    }
+
+    pub fn denom(&self) -> i64 {
+        self.denom
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1
+    }
+
+    pub fn checked_add(&self, other: &Rational) -> Result<Rational, MathError> {
+        let (a, b) = (self.numer as i128, self.denom as i128);
+        let (c, d) = (other.numer as i128, other.denom as i128);
+        Rational::from_i128(a * d + c * b, b * d)
+    }
+
+    pub fn checked_sub(&self, other: &Rational) -> Result<Rational, MathError> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    pub fn checked_mul(&self, other: &Rational) -> Result<Rational, MathError> {
+        Rational::from_i128(
+            self.numer as i128 * other.numer as i128,
+            self.denom as i128 * other.denom as i128,
+        )
+    }
+
+    /// `DivisionByZero` if `other` is zero.
+    pub fn checked_div(&self, other: &Rational) -> Result<Rational, MathError> {
+        Rational::from_i128(
+            self.numer as i128 * other.denom as i128,
+            self.denom as i128 * other.numer as i128,
+        )
+    }
+
+    /// `Overflow` only for `i64::MIN` numerators.
+    pub fn checked_neg(&self) -> Result<Rational, MathError> {
+        Ok(Rational {
+            numer: self.numer.checked_neg().ok_or(MathError::Overflow)?,
+            denom: self.denom,
+        })
+    }
+
+    /// `1 / self`; `DivisionByZero` for zero.
+    pub fn recip(&self) -> Result<Rational, MathError> {
+        Rational::from_i128(self.denom as i128, self.numer as i128)
+    }
+
+    /// Nearest multiple of `1 / denominator` in the given direction:
+    /// - `(7/3).round_to(4, Floor) = 9/4`
+    /// - `(5/8).round_to(4, HalfEven) = 1/2`, a tie going to the even `2/4`
+    ///
+    /// Errors with `DivisionByZero` if `denominator == 0`, `OutOfRange` if it is negative
+    /// and `Overflow` if the result does not fit.
+    pub fn round_to(&self, denominator: i64, mode: RoundingMode) -> Result<Rational, MathError> {
+        match denominator {
+            0 => Err(MathError::DivisionByZero),
+            d if d < 0 => Err(MathError::OutOfRange),
+            d => Rational::from_i128(self.scaled(d as i128, mode), d as i128),
+        }
+    }
+
+    /// Nearest fraction with denominator at most `max_denominator`, in the given
+    /// direction; `round_to` is the version with the denominator fixed:
+    /// - `(7/22).round_to_max_denominator(16, HalfEven) = 5/16`
+    /// - `(3/4).round_to_max_denominator(2, HalfEven) = 1`, a tie between `1/2` and `1`
+    ///   going to the smaller denominator
+    /// - `(314159/100000).round_to_max_denominator(100, Floor) = 311/99`
+    ///
+    /// The two candidates are the neighbours of `self` in the Farey sequence of order
+    /// `max_denominator`: the last continued-fraction convergent that fits and the
+    /// largest semiconvergent on the other side, so the cost is O(log denom) rather than
+    /// a scan of every denominator. `HalfEven` takes the nearer neighbour, and a tie the
+    /// one with the smaller denominator (the even one when both are integers). Errors with
+    /// `DivisionByZero` if `max_denominator == 0` and `OutOfRange` if it is negative.
+    pub fn round_to_max_denominator(
+        &self,
+        max_denominator: i64,
+        mode: RoundingMode,
+    ) -> Result<Rational, MathError> {
+        let n = match max_denominator {
+            0 => return Err(MathError::DivisionByZero),
+            n if n < 0 => return Err(MathError::OutOfRange),
+            n => n as u128,
+        };
+        if self.denom as u128 <= n {
+            return Ok(*self);
+        }
+        let (p, q) = (self.numer as i128, self.denom as i128);
+        // neighbours of the fractional part y, then shifted back by ⌊self⌋
+        let floor = p.div_euclid(q);
+        let y = (p - floor * q) as u128;
+        let (lower, upper) = farey_neighbours(y, q as u128, n);
+        let ((a, b), (c, d)) = (lower, upper);
+        let (num, den) = match mode {
+            RoundingMode::Floor => lower,
+            RoundingMode::Ceil => upper,
+            // the midpoint of a/b and c/d is (ad + bc) / 2bd, below 2^127 for b, d < 2^63
+            RoundingMode::HalfEven => match cmp_fractions(y, q as u128, a * d + b * c, 2 * b * d) {
+                Ordering::Less => lower,
+                Ordering::Greater => upper,
+                Ordering::Equal if b < d => lower,
+                Ordering::Equal if d < b => upper,
+                Ordering::Equal if (floor + a as i128) % 2 == 0 => lower,
+                Ordering::Equal => upper,
+            },
+        };
+        Rational::from_i128(num as i128 + floor * den as i128, den as i128)
+    }
+
+    /// The value in units of `10^-scale`, so `n` stands for `n / 10^scale`:
+    /// - `(2/3).to_fixed_point(4, HalfEven) = 6667`
+    /// - `(-2/3).to_fixed_point(2, Floor) = -67`
+    ///
+    /// Errors with `Overflow` if `10^scale` or the result leaves `i64`.
+    pub fn to_fixed_point(&self, scale: u32, mode: RoundingMode) -> Result<i64, MathError> {
+        let unit = 10i64.checked_pow(scale).ok_or(MathError::Overflow)?;
+        i64::try_from(self.scaled(unit as i128, mode)).map_err(|_| MathError::Overflow)
+    }
+
+    /// `self · factor` rounded to an integer; `factor > 0` keeps both factors below 2^63.
+    fn scaled(&self, factor: i128, mode: RoundingMode) -> i128 {
+        let (n, d) = (self.numer as i128 * factor, self.denom as i128);
+        let (floor, rem) = (n.div_euclid(d), n.rem_euclid(d));
+        let up = match mode {
+            RoundingMode::Floor => false,
+            RoundingMode::Ceil => rem != 0,
+            RoundingMode::HalfEven => match (2 * rem).cmp(&d) {
+                Ordering::Less => false,
+                Ordering::Greater => true,
+                Ordering::Equal => floor % 2 != 0,
+            },
+        };
+        floor + up as i128
+    }
+
+    /// Reduces an `i128` fraction and narrows it back to `i64`.
+    pub(crate) fn from_i128(numer: i128, denom: i128) -> Result<Rational, MathError> {
+        if denom == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        let g = gcd_u128(numer.unsigned_abs(), denom.unsigned_abs()) as i128;
+        let sign = denom.signum();
+        let narrow = |x: i128| i64::try_from(x).map_err(|_| MathError::Overflow);
+        Ok(Rational {
+            numer: narrow(sign * numer / g)?,
+            denom: narrow(sign * denom / g)?,
+        })
+    }
+}
+
+/// Neighbours `a/b < y < c/d` of `y = num / den` in the Farey sequence of order `n`, for
+/// `0 <= y < 1` in lowest terms with `den > n`.
+fn farey_neighbours(num: u128, den: u128, n: u128) -> ((u128, u128), (u128, u128)) {
+    // convergents p0/q0 and p1/q1, ending with the last whose denominator is <= n
+    let (mut p0, mut q0, mut p1, mut q1) = (0, 1, 1, 0);
+    let (mut x, mut y) = (num, den);
+    loop {
+        let a = x / y;
+        let q2 = q0 + a * q1;
+        if q2 > n {
+            break;
+        }
+        (p0, q0, p1, q1) = (p1, q1, p0 + a * p1, q2);
+        (x, y) = (y, x - a * y);
+    }
+    let k = (n - q0) / q1;
+    let semiconvergent = (p0 + k * p1, q0 + k * q1);
+    let convergent = (p1, q1);
+    if semiconvergent.0 * convergent.1 < convergent.0 * semiconvergent.1 {
+        (semiconvergent, convergent)
+    } else {
+        (convergent, semiconvergent)
+    }
+}
+
+/// Compares `a/b` and `c/d` through their continued fractions, so nothing is multiplied.
+fn cmp_fractions(mut a: u128, mut b: u128, mut c: u128, mut d: u128) -> Ordering {
+    loop {
+        let (q1, r1, q2, r2) = (a / b, a % b, c / d, c % d);
+        if q1 != q2 {
+            return q1.cmp(&q2);
+        }
+        if r1 == 0 || r2 == 0 {
+            return r1.cmp(&r2);
+        }
+        // r1/b < r2/d exactly when d/r2 < b/r1
+        (a, b, c, d) = (d, r2, b, r1);
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Rational {
+        Rational::from_integer(n)
+    }
+}
+
+macro_rules! forward_checked_ops {
+    ($($op:ident :: $method:ident => $checked:ident),*) => {
+        $(
+            impl $op for Rational {
+                type Output = Result<Rational, MathError>;
+
+                fn $method(self, other: Rational) -> Result<Rational, MathError> {
+                    self.$checked(&other)
+                }
+            }
+        )*
+    };
+}
+
+forward_checked_ops!(
+    Add::add => checked_add,
+    Sub::sub => checked_sub,
+    Mul::mul => checked_mul,
+    Div::div => checked_div
+);
+
+impl Ord for Rational {
+    /// Cross-multiplied in `i128`, which is exact since both denominators are positive.
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (self.numer as i128 * other.denom as i128).cmp(&(other.numer as i128 * self.denom as i128))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    /// `-7/3`, or just the numerator for integers.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn r(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    #[test]
+    fn test_normalization() {
+        assert_eq!(r(6, -4), r(-3, 2));
+        assert_eq!((r(6, -4).numer(), r(6, -4).denom()), (-3, 2));
+        assert_eq!(r(0, -7), Rational::ZERO);
+        assert_eq!(Rational::new(1, 0), Err(MathError::DivisionByZero));
+        assert_eq!(Rational::new(i64::MIN, -1), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(r(1, 2).checked_add(&r(1, 3)), Ok(r(5, 6)));
+        assert_eq!(r(1, 2).checked_sub(&r(1, 3)), Ok(r(1, 6)));
+        assert_eq!(r(2, 3).checked_mul(&r(9, 4)), Ok(r(3, 2)));
+        assert_eq!(r(2, 3).checked_div(&r(-4, 9)), Ok(r(-3, 2)));
+        assert_eq!(
+            r(2, 3).checked_div(&Rational::ZERO),
+            Err(MathError::DivisionByZero)
+        );
+        assert_eq!(r(-5, 7).recip(), Ok(r(-7, 5)));
+    }
+
+    #[test]
+    fn test_operators() -> Result<(), MathError> {
+        assert_eq!((r(1, 2) + r(1, 3))?, r(5, 6));
+        assert_eq!((r(1, 2) - r(1, 3))?, r(1, 6));
+        assert_eq!((r(2, 3) * r(9, 4))?, r(3, 2));
+        assert_eq!((r(2, 3) / r(-4, 9))?, r(-3, 2));
+        // 1/2 + 1/3 + 1/6 = 1
+        assert_eq!(((r(1, 2) + r(1, 3))? + r(1, 6))?, Rational::ONE);
+        assert_eq!(r(1, 2) / Rational::ZERO, Err(MathError::DivisionByZero));
+        let big = Rational::from_integer(i64::MAX);
+        assert_eq!(big + Rational::ONE, Err(MathError::Overflow));
+        assert_eq!(big - big, Ok(Rational::ZERO));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert!(r(2, 4) == r(1, 2) && r(2, 4) <= r(1, 2));
+        // cross products exceed i64
+        assert!(r(i64::MAX - 1, i64::MAX) < r(i64::MAX, i64::MAX - 1));
+        let mut values = vec![r(3, 4), r(-2, 3), r(0, 1), r(5, 8), r(7, 1)];
+        values.sort();
+        assert_eq!(values, vec![r(-2, 3), r(0, 1), r(5, 8), r(3, 4), r(7, 1)]);
+        assert_eq!(values.iter().max(), Some(&r(7, 1)));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(r(6, -4).to_string(), "-3/2");
+        assert_eq!(r(10, 5).to_string(), "2");
+        assert_eq!(Rational::ZERO.to_string(), "0");
+        assert_eq!(r(1, 3).to_string(), "1/3");
+    }
+
+    #[test]
+    fn test_overflow() {
+        let big = Rational::from_integer(i64::MAX);
+        assert_eq!(big.checked_add(&Rational::ONE), Err(MathError::Overflow));
+        assert_eq!(big.checked_mul(&r(2, 1)), Err(MathError::Overflow));
+        // reduction can bring an oversized intermediate back into range
+        assert_eq!(big.checked_mul(&r(2, 2)), Ok(big));
+    }
+
+    #[test]
+    fn test_round_to() {
+        use RoundingMode::*;
+        assert_eq!(r(7, 3).round_to(4, Floor), Ok(r(9, 4)));
+        assert_eq!(r(7, 3).round_to(4, Ceil), Ok(r(5, 2)));
+        assert_eq!(r(-7, 3).round_to(4, Floor), Ok(r(-5, 2)));
+        assert_eq!(r(-7, 3).round_to(4, Ceil), Ok(r(-9, 4)));
+        assert_eq!(r(7, 3).round_to(4, HalfEven), Ok(r(9, 4)));
+        // ties go to the even numerator
+        assert_eq!(r(5, 8).round_to(4, HalfEven), Ok(r(1, 2)));
+        assert_eq!(r(7, 8).round_to(4, HalfEven), Ok(r(1, 1)));
+        assert_eq!(r(-5, 2).round_to(1, HalfEven), Ok(r(-2, 1)));
+        assert_eq!(r(-7, 2).round_to(1, HalfEven), Ok(r(-4, 1)));
+        // already representable values are untouched in every mode
+        for mode in [Floor, Ceil, HalfEven] {
+            assert_eq!(r(3, 4).round_to(8, mode), Ok(r(3, 4)));
+        }
+        assert_eq!(r(1, 3).round_to(0, Floor), Err(MathError::DivisionByZero));
+        assert_eq!(r(1, 3).round_to(-2, Floor), Err(MathError::OutOfRange));
+        assert_eq!(
+            r(i64::MAX, 2).round_to(i64::MAX, Ceil),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_round_to_max_denominator() {
+        use RoundingMode::*;
+        assert_eq!(
+            r(7, 22).round_to_max_denominator(16, HalfEven),
+            Ok(r(5, 16))
+        );
+        // every denominator up to 16 counts, not only 16ths
+        assert_eq!(
+            r(3, 32).round_to_max_denominator(16, HalfEven),
+            Ok(r(1, 11))
+        );
+        assert_eq!(r(3, 32).round_to_max_denominator(16, Ceil), Ok(r(1, 10)));
+        assert_eq!(r(3, 4).round_to_max_denominator(2, HalfEven), Ok(r(1, 1)));
+        assert_eq!(r(3, 4).round_to_max_denominator(2, Floor), Ok(r(1, 2)));
+        assert_eq!(
+            r(314_159, 100_000).round_to_max_denominator(100, Floor),
+            Ok(r(311, 99))
+        );
+        assert_eq!(
+            r(314_159, 100_000).round_to_max_denominator(1000, HalfEven),
+            Ok(r(355, 113))
+        );
+        // integer neighbours tie to the even one, as in round_to
+        assert_eq!(r(5, 2).round_to_max_denominator(1, HalfEven), Ok(r(2, 1)));
+        assert_eq!(r(-5, 2).round_to_max_denominator(1, HalfEven), Ok(r(-2, 1)));
+        assert_eq!(r(7, 2).round_to_max_denominator(1, HalfEven), Ok(r(4, 1)));
+        assert_eq!(r(-1, 3).round_to_max_denominator(2, Floor), Ok(r(-1, 2)));
+        assert_eq!(r(3, 7).round_to_max_denominator(7, Floor), Ok(r(3, 7)));
+        // huge denominators: neither the search nor the tie test overflows
+        let x = r(i64::MAX - 1, i64::MAX);
+        assert_eq!(
+            x.round_to_max_denominator(i64::MAX - 1, Floor),
+            Ok(r(i64::MAX - 2, i64::MAX - 1))
+        );
+        assert_eq!(
+            x.round_to_max_denominator(i64::MAX - 1, Ceil),
+            Ok(Rational::ONE)
+        );
+        assert_eq!(
+            x.round_to_max_denominator(i64::MAX - 1, HalfEven),
+            Ok(r(i64::MAX - 2, i64::MAX - 1))
+        );
+        assert_eq!(
+            r(1, 3).round_to_max_denominator(0, Floor),
+            Err(MathError::DivisionByZero)
+        );
+        assert_eq!(
+            r(1, 3).round_to_max_denominator(-2, Floor),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            r(i64::MAX, 2).round_to_max_denominator(1, Ceil),
+            Ok(r(1 << 62, 1))
+        );
+    }
+
+    #[test]
+    fn test_round_to_max_denominator_against_brute_force() {
+        use RoundingMode::*;
+        for den in 1..=20 {
+            for num in -10..=10 {
+                let x = r(num, den);
+                for n in 1..=12i64 {
+                    let candidates: Vec<Rational> = (1..=n)
+                        .flat_map(|d| (-11 * d..=11 * d).map(move |k| r(k, d)))
+                        .collect();
+                    let floor = candidates.iter().filter(|&&c| c <= x).max().copied();
+                    let ceil = candidates.iter().filter(|&&c| c >= x).min().copied();
+                    assert_eq!(x.round_to_max_denominator(n, Floor).ok(), floor);
+                    assert_eq!(x.round_to_max_denominator(n, Ceil).ok(), ceil);
+                    let (lo, hi) = (floor.unwrap(), ceil.unwrap());
+                    let nearest = match (x - lo).unwrap().cmp(&(hi - x).unwrap()) {
+                        Ordering::Less => lo,
+                        Ordering::Greater => hi,
+                        Ordering::Equal => x.round_to_max_denominator(n, HalfEven).unwrap(),
+                    };
+                    assert_eq!(
+                        x.round_to_max_denominator(n, HalfEven),
+                        Ok(nearest),
+                        "{x} {n}"
+                    );
+                    assert!(nearest.denom() <= n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_fixed_point() {
+        use RoundingMode::*;
+        assert_eq!(r(2, 3).to_fixed_point(4, HalfEven), Ok(6667));
+        assert_eq!(r(2, 3).to_fixed_point(4, Floor), Ok(6666));
+        assert_eq!(r(-2, 3).to_fixed_point(2, Floor), Ok(-67));
+        assert_eq!(r(-2, 3).to_fixed_point(2, Ceil), Ok(-66));
+        // 0.125 and 0.375 are ties at two decimals
+        assert_eq!(r(1, 8).to_fixed_point(2, HalfEven), Ok(12));
+        assert_eq!(r(3, 8).to_fixed_point(2, HalfEven), Ok(38));
+        assert_eq!(r(5, 2).to_fixed_point(0, HalfEven), Ok(2));
+        assert_eq!(r(1, 3).to_fixed_point(19, Floor), Err(MathError::Overflow));
+        assert_eq!(
+            Rational::from_integer(i64::MAX).to_fixed_point(1, Floor),
+            Err(MathError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::gcd::gcd_u128;
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Outcome of `solve_rational_system`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RationalSolution {
+    /// Full column rank: the only solution.
+    Unique(Vec<Rational>),
+    /// Solutions `particular + Σ tᵢ·nullspace[i]`; the particular one sets every free
+    /// variable to 0 and `nullspace` has `n - rank` vectors.
+    Infinite {
+        particular: Vec<Rational>,
+        nullspace: Vec<Vec<Rational>>,
+        rank: usize,
+    },
+    /// `rank(A) < rank([A | b])`: no solution.
+    Inconsistent { rank: usize, augmented_rank: usize },
+}
+
+/// Exact solution of `A·x = b` over the rationals.
+///
+/// Each row of `[A | b]` is scaled to integers by the lcm of its denominators, then
+/// brought to echelon form by fraction-free (Bareiss) elimination: every intermediate
+/// entry is a minor of the scaled matrix, so the divisions are exact and entries grow
+/// only linearly in size, with no gcd reductions along the way. Back substitution is
+/// done in `Rational`:
+/// - `x + y = 3, x - y = 1` gives `Unique([2, 1])`
+/// - `x + y = 1, 2x + 2y = 3` gives `Inconsistent { rank: 1, augmented_rank: 2 }`
+///
+/// Errors with `EmptyInput` for a matrix without rows or columns, `DimensionMismatch`
+/// for ragged rows or `b.len() != rows` and `Overflow` if an intermediate leaves `i128`
+/// or a result leaves the `Rational` range.
+pub fn solve_rational_system(
+    a: &[Vec<Rational>],
+    b: &[Rational],
+) -> Result<RationalSolution, MathError> {
+    let cols = a.first().map_or(0, Vec::len);
+    if cols == 0 {
+        return Err(MathError::EmptyInput);
+    }
+    if a.iter().any(|row| row.len() != cols) || b.len() != a.len() {
+        return Err(MathError::DimensionMismatch);
+    }
+
+    let mut m = a
+        .iter()
+        .zip(b)
+        .map(|(row, &rhs)| integer_row(row.iter().copied().chain([rhs])))
+        .collect::<Result<Vec<_>, _>>()?;
+    let pivots = bareiss(&mut m, cols)?;
+    let rank = pivots.len();
+    // minors can outgrow i64 while the solution does not; the row content goes first
+    for row in &mut m {
+        let g = row
+            .iter()
+            .fold(0, |g, x| gcd_u128(g, x.unsigned_abs()))
+            .max(1) as i128;
+        row.iter_mut().for_each(|x| *x /= g);
+    }
+
+    if m[rank..].iter().any(|row| row[cols] != 0) {
+        return Ok(RationalSolution::Inconsistent {
+            rank,
+            augmented_rank: rank + 1,
+        });
+    }
+
+    let particular = back_substitute(&m, &pivots, cols, |row| row[cols], None)?;
+    if rank == cols {
+        return Ok(RationalSolution::Unique(particular));
+    }
+    let nullspace = (0..cols)
+        .filter(|c| !pivots.contains(c))
+        .map(|free| back_substitute(&m, &pivots, cols, |_| 0, Some(free)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(RationalSolution::Infinite {
+        particular,
+        nullspace,
+        rank,
+    })
+}
+
+/// The entries times the lcm of their denominators, as `i128`.
+fn integer_row(entries: impl Iterator<Item = Rational> + Clone) -> Result<Vec<i128>, MathError> {
+    let scale = entries.clone().try_fold(1u128, |l, x| {
+        let d = x.denom() as u128;
+        (l / gcd_u128(l, d))
+            .checked_mul(d)
+            .ok_or(MathError::Overflow)
+    })?;
+    let scale = i128::try_from(scale).map_err(|_| MathError::Overflow)?;
+    entries
+        .map(|x| {
+            (x.numer() as i128)
+                .checked_mul(scale / x.denom() as i128)
+                .ok_or(MathError::Overflow)
+        })
+        .collect()
+}
+
+/// Fraction-free row echelon form of the first `cols` columns; returns the pivot columns.
+///
+/// After the pivot in column `c` of row `r`, each lower entry becomes
+/// `(m[i][j]·m[r][c] - m[i][c]·m[r][j]) / previous pivot`, exact by Sylvester's identity.
+fn bareiss(m: &mut [Vec<i128>], cols: usize) -> Result<Vec<usize>, MathError> {
+    let rows = m.len();
+    let mut pivots = Vec::new();
+    let mut prev = 1i128;
+    for c in 0..cols {
+        let r = pivots.len();
+        if r == rows {
+            break;
+        }
+        let Some(p) = (r..rows).find(|&i| m[i][c] != 0) else {
+            continue;
+        };
+        m.swap(r, p);
+        let (top, below) = m.split_at_mut(r + 1);
+        let pivot_row = &top[r];
+        for row in below.iter_mut() {
+            let factor = row[c];
+            for j in c + 1..row.len() {
+                let x = row[j]
+                    .checked_mul(pivot_row[c])
+                    .and_then(|x| x.checked_sub(factor.checked_mul(pivot_row[j])?))
+                    .ok_or(MathError::Overflow)?;
+                row[j] = x / prev;
+            }
+            row[c] = 0;
+        }
+        prev = pivot_row[c];
+        pivots.push(c);
+    }
+    Ok(pivots)
+}
+
+/// Solves the echelon system from the bottom pivot up, with right-hand side `rhs(row)`.
+///
+/// Free variables are 0, except `free` which is set to 1 (for a nullspace vector).
+fn back_substitute(
+    m: &[Vec<i128>],
+    pivots: &[usize],
+    cols: usize,
+    rhs: impl Fn(&[i128]) -> i128,
+    free: Option<usize>,
+) -> Result<Vec<Rational>, MathError> {
+    let narrow = |x: i128| i64::try_from(x).map_err(|_| MathError::Overflow);
+    let mut x = vec![Rational::ZERO; cols];
+    if let Some(f) = free {
+        x[f] = Rational::ONE;
+    }
+    for (r, &c) in pivots.iter().enumerate().rev() {
+        let row = &m[r];
+        let mut acc = Rational::from_integer(narrow(rhs(row))?);
+        for j in c + 1..cols {
+            if row[j] != 0 && !x[j].is_zero() {
+                let term = Rational::from_integer(narrow(row[j])?).checked_mul(&x[j])?;
+                acc = acc.checked_sub(&term)?;
+            }
+        }
+        x[c] = acc.checked_div(&Rational::from_integer(narrow(row[c])?))?;
+    }
+    Ok(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    fn ints(rows: &[&[i64]]) -> Vec<Vec<Rational>> {
+        rows.iter()
+            .map(|row| row.iter().map(|&x| Rational::from_integer(x)).collect())
+            .collect()
+    }
+
+    fn apply(a: &[Vec<Rational>], x: &[Rational]) -> Vec<Rational> {
+        a.iter()
+            .map(|row| {
+                row.iter().zip(x).fold(Rational::ZERO, |acc, (a, b)| {
+                    acc.checked_add(&a.checked_mul(b).unwrap()).unwrap()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_unique_solution() {
+        let a = ints(&[&[1, 1], &[1, -1]]);
+        let b = [q(3, 1), q(1, 1)];
+        assert_eq!(
+            solve_rational_system(&a, &b),
+            Ok(RationalSolution::Unique(vec![q(2, 1), q(1, 1)]))
+        );
+
+        // fractional coefficients, zero in the leading position
+        let a = vec![
+            vec![q(0, 1), q(2, 3), q(1, 2)],
+            vec![q(1, 5), q(-1, 1), q(0, 1)],
+            vec![q(3, 1), q(1, 7), q(-2, 9)],
+        ];
+        let b = [q(1, 1), q(-1, 3), q(5, 2)];
+        let Ok(RationalSolution::Unique(x)) = solve_rational_system(&a, &b) else {
+            panic!("expected a unique solution");
+        };
+        assert_eq!(apply(&a, &x), b.to_vec());
+    }
+
+    #[test]
+    fn test_hilbert_matrix() {
+        // ill-conditioned in floating point, exact here
+        let n = 6;
+        let a: Vec<Vec<Rational>> = (0..n)
+            .map(|i| (0..n).map(|j| q(1, (i + j + 1) as i64)).collect())
+            .collect();
+        let x: Vec<Rational> = (1..=n as i64).map(Rational::from_integer).collect();
+        let b = apply(&a, &x);
+        assert_eq!(
+            solve_rational_system(&a, &b),
+            Ok(RationalSolution::Unique(x))
+        );
+    }
+
+    #[test]
+    fn test_underdetermined() {
+        let a = ints(&[&[1, 2, 3], &[2, 4, 7]]);
+        let b = [q(1, 1), q(3, 1)];
+        let Ok(RationalSolution::Infinite {
+            particular,
+            nullspace,
+            rank,
+        }) = solve_rational_system(&a, &b)
+        else {
+            panic!("expected infinitely many solutions");
+        };
+        assert_eq!(rank, 2);
+        assert_eq!(apply(&a, &particular), b.to_vec());
+        assert_eq!(nullspace, vec![vec![q(-2, 1), q(1, 1), q(0, 1)]]);
+    }
+
+    #[test]
+    fn test_inconsistent_and_errors() {
+        let a = ints(&[&[1, 1], &[2, 2]]);
+        assert_eq!(
+            solve_rational_system(&a, &[q(1, 1), q(3, 1)]),
+            Ok(RationalSolution::Inconsistent {
+                rank: 1,
+                augmented_rank: 2
+            })
+        );
+        assert_eq!(
+            solve_rational_system(&a, &[q(1, 1)]),
+            Err(MathError::DimensionMismatch)
+        );
+        assert_eq!(solve_rational_system(&[], &[]), Err(MathError::EmptyInput));
+        // overdetermined but consistent
+        let a = ints(&[&[1, 0], &[0, 1], &[1, 1]]);
+        assert_eq!(
+            solve_rational_system(&a, &[q(1, 2), q(1, 3), q(5, 6)]),
+            Ok(RationalSolution::Unique(vec![q(1, 2), q(1, 3)]))
+        );
+    }
+}
@@ -0,0 +1,232 @@
+#[cfg(feature = "std")]
+use core::f64::consts::TAU;
+
+use crate::algebra::modular::legendre_symbol;
+use crate::algebra::prime_nbr::miller_rabin_u128;
+use crate::errors::MathError;
+
+/// Both sides of quadratic reciprocity `(p/q)(q/p) = (-1)^((p-1)/2 · (q-1)/2)`.
+///
+/// The exponent is also counted geometrically, following Eisenstein's proof: the lattice
+/// points `(x, y)` with `1 <= x <= (p-1)/2`, `1 <= y <= (q-1)/2` split into
+/// `Σ ⌊kq/p⌋` below the diagonal `y = qx/p` and `Σ ⌊kp/q⌋` above it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReciprocityCheck {
+    pub p: u64,
+    pub q: u64,
+    /// `(p/q)`
+    pub p_over_q: i8,
+    /// `(q/p)`
+    pub q_over_p: i8,
+    /// `(p-1)/2 · (q-1)/2`
+    pub exponent: u64,
+    /// `Σ ⌊kq/p⌋` for `1 <= k <= (p-1)/2`
+    pub lattice_below: u64,
+    /// `Σ ⌊kp/q⌋` for `1 <= k <= (q-1)/2`
+    pub lattice_above: u64,
+}
+
+impl ReciprocityCheck {
+    /// Left side `(p/q)(q/p)`.
+    pub fn product(&self) -> i8 {
+        self.p_over_q * self.q_over_p
+    }
+
+    /// Right side `(-1)^exponent`.
+    pub fn predicted(&self) -> i8 {
+        sign_of(self.exponent)
+    }
+
+    /// The law and the lattice count both check out.
+    pub fn holds(&self) -> bool {
+        self.product() == self.predicted()
+            && self.lattice_below + self.lattice_above == self.exponent
+    }
+}
+
+/// A supplementary law: the Legendre symbol against its closed form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupplementCheck {
+    pub p: u64,
+    /// `(-1/p)` or `(2/p)`, from Euler's criterion
+    pub symbol: i8,
+    /// `(-1)^((p-1)/2)` or `(-1)^((p²-1)/8)`
+    pub predicted: i8,
+}
+
+impl SupplementCheck {
+    pub fn holds(&self) -> bool {
+        self.symbol == self.predicted
+    }
+}
+
+/// Computes both sides of quadratic reciprocity for distinct odd primes `p` and `q`:
+/// - `(3/7)(7/3) = (-1)(1) = -1 = (-1)^(1·3)`
+///
+/// The lattice sums take O(p + q) steps. Errors with `OutOfRange` unless `p` and `q`
+/// are distinct odd primes.
+pub fn verify_quadratic_reciprocity(p: u64, q: u64) -> Result<ReciprocityCheck, MathError> {
+    check_odd_prime(p)?;
+    check_odd_prime(q)?;
+    if p == q {
+        return Err(MathError::OutOfRange);
+    }
+    let lattice = |a: u64, b: u64| {
+        (1..=(a - 1) / 2)
+            .map(|k| (k as u128 * b as u128 / a as u128) as u64)
+            .sum()
+    };
+    Ok(ReciprocityCheck {
+        p,
+        q,
+        p_over_q: legendre_symbol(p, q)?,
+        q_over_p: legendre_symbol(q, p)?,
+        exponent: (p - 1) / 2 * ((q - 1) / 2),
+        lattice_below: lattice(p, q),
+        lattice_above: lattice(q, p),
+    })
+}
+
+/// First supplement `(-1/p) = (-1)^((p-1)/2)`: -1 is a square mod `p` iff `p ≡ 1 (mod 4)`.
+///
+/// Errors with `OutOfRange` unless `p` is an odd prime.
+pub fn first_supplement(p: u64) -> Result<SupplementCheck, MathError> {
+    check_odd_prime(p)?;
+    Ok(SupplementCheck {
+        p,
+        symbol: legendre_symbol(p - 1, p)?,
+        predicted: sign_of((p - 1) / 2),
+    })
+}
+
+/// Second supplement `(2/p) = (-1)^((p²-1)/8)`: 2 is a square mod `p` iff `p ≡ ±1 (mod 8)`.
+///
+/// Errors with `OutOfRange` unless `p` is an odd prime.
+pub fn second_supplement(p: u64) -> Result<SupplementCheck, MathError> {
+    check_odd_prime(p)?;
+    let p2 = p as u128 * p as u128;
+    Ok(SupplementCheck {
+        p,
+        symbol: legendre_symbol(2, p)?,
+        predicted: sign_of(((p2 - 1) / 8 % 2) as u64),
+    })
+}
+
+/// Gauss's lemma: `(a/p) = (-1)^μ` where `μ` counts the least positive residues of
+/// `a, 2a, ..., ((p-1)/2)·a` that exceed `p/2`.
+///
+/// Returns `(μ, (-1)^μ)`:
+/// - `gauss_lemma(5, 13) = (3, -1)`, from the residues `5, 10, 2, 7, 12, 4`
+///
+/// Errors with `OutOfRange` unless `p` is an odd prime not dividing `a`.
+pub fn gauss_lemma(a: u64, p: u64) -> Result<(u64, i8), MathError> {
+    check_odd_prime(p)?;
+    if a.is_multiple_of(p) {
+        return Err(MathError::OutOfRange);
+    }
+    let mu = (1..=(p - 1) / 2)
+        .filter(|&k| (k as u128 * a as u128 % p as u128) as u64 > p / 2)
+        .count() as u64;
+    Ok((mu, sign_of(mu)))
+}
+
+/// Quadratic Gauss sum `Σ e^(2πix²/p)` over `x mod p`, as `(re, im)`.
+///
+/// Gauss proved it equals `√p` when `p ≡ 1 (mod 4)` and `i√p` when `p ≡ 3 (mod 4)`,
+/// which gives another proof of reciprocity. O(p) terms. Errors with `OutOfRange`
+/// unless `p` is an odd prime.
+#[cfg(feature = "std")]
+pub fn quadratic_gauss_sum(p: u64) -> Result<(f64, f64), MathError> {
+    check_odd_prime(p)?;
+    Ok((0..p).fold((0.0, 0.0), |(re, im), x| {
+        let angle = TAU * (x as u128 * x as u128 % p as u128) as f64 / p as f64;
+        (re + angle.cos(), im + angle.sin())
+    }))
+}
+
+fn check_odd_prime(p: u64) -> Result<(), MathError> {
+    if p < 3 || p.is_multiple_of(2) || !miller_rabin_u128(p as u128, 32)?.is_prime() {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(())
+}
+
+fn sign_of(exponent: u64) -> i8 {
+    if exponent.is_multiple_of(2) { 1 } else { -1 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMES: [u64; 12] = [3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 1_000_003];
+
+    #[test]
+    fn test_reciprocity() {
+        let check = verify_quadratic_reciprocity(3, 7).unwrap();
+        assert_eq!((check.p_over_q, check.q_over_p), (-1, 1));
+        assert_eq!((check.exponent, check.predicted()), (3, -1));
+        assert_eq!((check.lattice_below, check.lattice_above), (2, 1));
+        assert!(check.holds());
+
+        for p in PRIMES {
+            for q in PRIMES {
+                if p != q {
+                    assert!(verify_quadratic_reciprocity(p, q).unwrap().holds());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_supplements() {
+        for p in PRIMES {
+            let first = first_supplement(p).unwrap();
+            assert!(first.holds());
+            assert_eq!(first.symbol == 1, p % 4 == 1);
+            let second = second_supplement(p).unwrap();
+            assert!(second.holds());
+            assert_eq!(second.symbol == 1, matches!(p % 8, 1 | 7));
+        }
+    }
+
+    #[test]
+    fn test_gauss_lemma() {
+        assert_eq!(gauss_lemma(5, 13), Ok((3, -1)));
+        for p in [7, 11, 13, 29] {
+            for a in 1..p {
+                assert_eq!(gauss_lemma(a, p).unwrap().1, legendre_symbol(a, p).unwrap());
+            }
+        }
+        assert_eq!(gauss_lemma(13, 13), Err(MathError::OutOfRange));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_quadratic_gauss_sum() {
+        for p in [5, 13, 7, 23] {
+            let (re, im) = quadratic_gauss_sum(p).unwrap();
+            let root = (p as f64).sqrt();
+            let expected = if p % 4 == 1 { (root, 0.0) } else { (0.0, root) };
+            assert!((re - expected.0).abs() < 1e-9 && (im - expected.1).abs() < 1e-9);
+        }
+        assert_eq!(quadratic_gauss_sum(15), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert_eq!(
+            verify_quadratic_reciprocity(5, 5),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            verify_quadratic_reciprocity(2, 5),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            verify_quadratic_reciprocity(9, 5),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(first_supplement(1), Err(MathError::OutOfRange));
+    }
+}
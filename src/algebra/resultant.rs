@@ -0,0 +1,251 @@
+use alloc::vec::Vec;
+
+use crate::algebra::gcd::gcd_u128;
+use crate::algebra::polynomial::Polynomial;
+use crate::errors::MathError;
+
+/// Resultant `Res(f, g)` of two integer polynomials: zero exactly when they share a
+/// complex root (or both leading coefficients vanish).
+///
+/// Subresultant pseudo-remainder sequence (Collins; Cohen, Algorithm 3.3.7). Every
+/// division in the sequence is exact and coefficients stay polynomial in the input
+/// size, unlike the plain Euclidean PRS whose coefficients explode exponentially.
+/// Intermediates use checked `i128`.
+///
+/// - `Res(x² + 1, x - 2) = 5`
+/// - `Res(f, 0) = 0`, and `Res(c, g) = c^deg g` for a constant `c`
+///
+/// Errors with `Overflow` if an intermediate or the result does not fit.
+pub fn resultant(f: &Polynomial<i64>, g: &Polynomial<i64>) -> Result<i64, MathError> {
+    let widen = |p: &Polynomial<i64>| p.coeffs().iter().map(|&c| c as i128).collect();
+    let result = resultant_i128(widen(f), widen(g))?;
+    i64::try_from(result).map_err(|_| MathError::Overflow)
+}
+
+/// Discriminant `(-1)^(n(n-1)/2) · Res(f, f') / aₙ` of a polynomial of degree `n >= 1`.
+///
+/// Zero exactly when `f` has a repeated root:
+/// - `disc(ax² + bx + c) = b² - 4ac`
+/// - `disc(x³ + px + q) = -4p³ - 27q²`
+///
+/// Errors with `OutOfRange` for constant polynomials and `Overflow` as for `resultant`.
+pub fn discriminant(f: &Polynomial<i64>) -> Result<i64, MathError> {
+    let n = match f.degree() {
+        Some(n) if n >= 1 => n,
+        _ => return Err(MathError::OutOfRange),
+    };
+    let lead = *f.leading().ok_or(MathError::OutOfRange)? as i128;
+    let res = resultant(f, &f.derivative()?)? as i128;
+    let sign = if (n * (n - 1) / 2) % 2 == 0 { 1 } else { -1 };
+    i64::try_from(sign * res / lead).map_err(|_| MathError::Overflow)
+}
+
+fn resultant_i128(mut a: Vec<i128>, mut b: Vec<i128>) -> Result<i128, MathError> {
+    if a.is_empty() || b.is_empty() {
+        return Ok(0);
+    }
+    let mut s = 1i128;
+    if a.len() < b.len() {
+        if (a.len() - 1) * (b.len() - 1) % 2 == 1 {
+            s = -1;
+        }
+        core::mem::swap(&mut a, &mut b);
+    }
+    if b.len() == 1 {
+        return checked_pow(b[0], a.len() as u32 - 1)?
+            .checked_mul(s)
+            .ok_or(MathError::Overflow);
+    }
+
+    // t = cont(A)^deg B · cont(B)^deg A, then work with primitive parts
+    let (ca, cb) = (content(&a), content(&b));
+    let t = checked_pow(ca, b.len() as u32 - 1)?
+        .checked_mul(checked_pow(cb, a.len() as u32 - 1)?)
+        .ok_or(MathError::Overflow)?;
+    a.iter_mut().for_each(|c| *c /= ca);
+    b.iter_mut().for_each(|c| *c /= cb);
+
+    let (mut g, mut h) = (1i128, 1i128);
+    loop {
+        let (deg_a, deg_b) = (a.len() - 1, b.len() - 1);
+        let delta = (deg_a - deg_b) as u32;
+        if deg_a % 2 == 1 && deg_b % 2 == 1 {
+            s = -s;
+        }
+        let r = pseudo_remainder(&a, &b)?;
+        a = b;
+        // B = R / (g·h^δ), an exact division
+        let divisor = g
+            .checked_mul(checked_pow(h, delta)?)
+            .ok_or(MathError::Overflow)?;
+        b = r.iter().map(|c| c / divisor).collect();
+        g = *a.last().ok_or(MathError::Overflow)?;
+        // h = g^δ / h^(δ-1)
+        h = if delta == 0 {
+            h
+        } else {
+            checked_pow(g, delta)? / checked_pow(h, delta - 1)?
+        };
+
+        match b.len() {
+            0 => return Ok(0),
+            1 => break,
+            _ => {}
+        }
+    }
+
+    // h = ℓ(B)^deg A / h^(deg A - 1)
+    let deg_a = a.len() as u32 - 1;
+    let h = checked_pow(b[0], deg_a)? / checked_pow(h, deg_a - 1)?;
+    s.checked_mul(t)
+        .and_then(|st| st.checked_mul(h))
+        .ok_or(MathError::Overflow)
+}
+
+/// `lc(b)^(deg a - deg b + 1) · a mod b`, computed without divisions; trimmed.
+fn pseudo_remainder(a: &[i128], b: &[i128]) -> Result<Vec<i128>, MathError> {
+    let mut r = a.to_vec();
+    let lead = *b.last().ok_or(MathError::DivisionByZero)?;
+    let mut steps = a.len() - b.len() + 1;
+    while r.len() >= b.len() {
+        let top = *r.last().ok_or(MathError::Overflow)?;
+        let shift = r.len() - b.len();
+        // r = lead·r - top·x^shift·b, which cancels the leading term
+        for (i, c) in r.iter_mut().enumerate() {
+            let sub = if i >= shift { b[i - shift] } else { 0 };
+            *c = c
+                .checked_mul(lead)
+                .and_then(|x| x.checked_sub(top.checked_mul(sub)?))
+                .ok_or(MathError::Overflow)?;
+        }
+        r.pop();
+        while r.last() == Some(&0) {
+            r.pop();
+        }
+        steps -= 1;
+    }
+    // remaining multiplications when the degree dropped by more than one per step
+    let scale = checked_pow(lead, steps as u32)?;
+    r.iter()
+        .map(|c| c.checked_mul(scale).ok_or(MathError::Overflow))
+        .collect()
+}
+
+/// Positive gcd of the coefficients (1 for an all-zero slice).
+fn content(coeffs: &[i128]) -> i128 {
+    let g = coeffs
+        .iter()
+        .fold(0u128, |g, c| gcd_u128(g, c.unsigned_abs()));
+    g.max(1) as i128
+}
+
+fn checked_pow(base: i128, exp: u32) -> Result<i128, MathError> {
+    base.checked_pow(exp).ok_or(MathError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn poly(coeffs: &[i64]) -> Polynomial<i64> {
+        Polynomial::new(coeffs.to_vec())
+    }
+
+    /// Determinant of the Sylvester matrix by fraction-free (Bareiss) elimination.
+    fn sylvester_resultant(f: &[i64], g: &[i64]) -> i128 {
+        let (m, n) = (f.len() - 1, g.len() - 1);
+        let size = m + n;
+        let mut rows: Vec<Vec<i128>> = Vec::new();
+        for i in 0..n {
+            let mut row = vec![0; size];
+            for (j, &c) in f.iter().rev().enumerate() {
+                row[i + j] = c as i128;
+            }
+            rows.push(row);
+        }
+        for i in 0..m {
+            let mut row = vec![0; size];
+            for (j, &c) in g.iter().rev().enumerate() {
+                row[i + j] = c as i128;
+            }
+            rows.push(row);
+        }
+
+        let (mut sign, mut prev) = (1, 1);
+        for k in 0..size {
+            let Some(p) = (k..size).find(|&r| rows[r][k] != 0) else {
+                return 0;
+            };
+            if p != k {
+                rows.swap(p, k);
+                sign = -sign;
+            }
+            for i in k + 1..size {
+                for j in k + 1..size {
+                    rows[i][j] = (rows[i][j] * rows[k][k] - rows[i][k] * rows[k][j]) / prev;
+                }
+            }
+            prev = rows[k][k];
+        }
+        sign * rows[size - 1][size - 1]
+    }
+
+    #[test]
+    fn test_small_resultants() {
+        assert_eq!(resultant(&poly(&[1, 0, 1]), &poly(&[-2, 1])), Ok(5));
+        assert_eq!(resultant(&poly(&[-1, 0, 1]), &poly(&[-1, 1])), Ok(0));
+        assert_eq!(resultant(&poly(&[3]), &poly(&[1, 2, 1])), Ok(9));
+        assert_eq!(resultant(&poly(&[1, 1]), &poly(&[])), Ok(0));
+    }
+
+    #[test]
+    fn test_matches_sylvester_determinant() {
+        let polys: [&[i64]; 6] = [
+            &[1, -3, 0, 2],
+            &[5, 0, -1, 4, 1],
+            &[-2, 7, 3],
+            &[6, 1, 0, 0, -3, 2],
+            &[4, 4, 1],
+            &[-1, 2, -2, 1, 3, -1],
+        ];
+        for f in polys {
+            for g in polys {
+                let expected = sylvester_resultant(f, g);
+                assert_eq!(
+                    resultant(&poly(f), &poly(g)).map(|r| r as i128),
+                    Ok(expected),
+                    "{f:?} {g:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_antisymmetry_sign() {
+        // Res(g, f) = (-1)^(deg f · deg g) Res(f, g)
+        let (f, g) = (poly(&[1, -3, 0, 2]), poly(&[-2, 7, 3]));
+        assert_eq!(resultant(&f, &g), resultant(&g, &f));
+        let h = poly(&[5, 0, -1, 4]);
+        assert_eq!(resultant(&f, &h), resultant(&h, &f).map(|r| -r));
+    }
+
+    #[test]
+    fn test_discriminant() {
+        assert_eq!(discriminant(&poly(&[3, 5, 2])), Ok(25 - 24));
+        assert_eq!(discriminant(&poly(&[1, 2, 1])), Ok(0));
+        // x³ + px + q with p = -2, q = 1: -4p³ - 27q² = 32 - 27
+        assert_eq!(discriminant(&poly(&[1, -2, 0, 1])), Ok(5));
+        assert_eq!(discriminant(&poly(&[7, 3])), Ok(1));
+        assert_eq!(discriminant(&poly(&[7])), Err(MathError::OutOfRange));
+        // (x - 1)²(x + 2) has a repeated root
+        assert_eq!(discriminant(&poly(&[2, -3, 0, 1])), Ok(0));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let f = poly(&[i64::MAX, 0, 0, 0, 1]);
+        let g = poly(&[1, 0, 0, i64::MAX]);
+        assert_eq!(resultant(&f, &g), Err(MathError::Overflow));
+    }
+}
@@ -0,0 +1,242 @@
+use alloc::vec::Vec;
+use core::ops::{Add, Mul, Sub};
+
+#[cfg(feature = "internal-bigint")]
+use crate::algebra::bigu::BigU;
+use crate::algebra::modular::Garner;
+use crate::errors::MathError;
+
+/// Pairwise coprime moduli `m₀, ..., mₖ₋₁` defining a residue number system.
+///
+/// An integer is held as its residues modulo each `mᵢ`, which pins it down modulo
+/// `M = ∏ mᵢ`. Addition, subtraction and multiplication act on each residue on its own,
+/// with no carries between them, so results are exact as long as the true value stays
+/// in `[0, M)`, or within `M/2` of zero for signed use; past that they wrap mod `M`.
+/// Converting back goes through `Garner`, whose inverses are precomputed here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RnsBasis {
+    garner: Garner,
+}
+
+impl RnsBasis {
+    /// Errors with `EmptyInput` for no moduli, `DivisionByZero` for a zero modulus and
+    /// `NoSolution` if two moduli share a factor.
+    pub fn new(moduli: &[u64]) -> Result<Self, MathError> {
+        if moduli.is_empty() {
+            return Err(MathError::EmptyInput);
+        }
+        Ok(RnsBasis {
+            garner: Garner::new(moduli)?,
+        })
+    }
+
+    pub fn moduli(&self) -> &[u64] {
+        self.garner.moduli()
+    }
+
+    /// `x` reduced modulo each modulus.
+    pub fn encode(&self, x: u128) -> RnsInt<'_> {
+        let residues = self
+            .moduli()
+            .iter()
+            .map(|&m| (x % m as u128) as u64)
+            .collect();
+        RnsInt {
+            basis: self,
+            residues,
+        }
+    }
+
+    /// `x` reduced modulo each modulus, negatives included; read back with `to_i128`.
+    pub fn encode_signed(&self, x: i128) -> RnsInt<'_> {
+        let residues = self
+            .moduli()
+            .iter()
+            .map(|&m| x.rem_euclid(m as i128) as u64)
+            .collect();
+        RnsInt {
+            basis: self,
+            residues,
+        }
+    }
+}
+
+/// An integer modulo `M` in the residue representation of an `RnsBasis`:
+/// - with moduli `[7, 11, 13]`, `100` is `[2, 1, 9]` and `100·9 = 900` is `[4, 9, 3]`
+///
+/// Built by `RnsBasis::encode`. The `+ - *` operators are the `checked_*` methods and
+/// return a `Result`, as for `Rational`; they only fail for operands from different
+/// bases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RnsInt<'a> {
+    basis: &'a RnsBasis,
+    residues: Vec<u64>,
+}
+
+impl<'a> RnsInt<'a> {
+    pub fn basis(&self) -> &'a RnsBasis {
+        self.basis
+    }
+
+    /// Residues in the order of `basis().moduli()`.
+    pub fn residues(&self) -> &[u64] {
+        &self.residues
+    }
+
+    pub fn checked_add(&self, other: &RnsInt<'a>) -> Result<RnsInt<'a>, MathError> {
+        self.zip_with(other, |a, b, m| {
+            ((a as u128 + b as u128) % m as u128) as u64
+        })
+    }
+
+    pub fn checked_sub(&self, other: &RnsInt<'a>) -> Result<RnsInt<'a>, MathError> {
+        self.zip_with(other, |a, b, m| if a >= b { a - b } else { m - (b - a) })
+    }
+
+    pub fn checked_mul(&self, other: &RnsInt<'a>) -> Result<RnsInt<'a>, MathError> {
+        self.zip_with(other, |a, b, m| {
+            ((a as u128 * b as u128) % m as u128) as u64
+        })
+    }
+
+    /// The value in `[0, M)`. `MathError::Overflow` if it does not fit in `u128`.
+    pub fn to_u128(&self) -> Result<u128, MathError> {
+        self.basis.garner.reconstruct(&self.residues)
+    }
+
+    /// The value in `[-M/2, M/2]`. `MathError::Overflow` if it does not fit in `i128`.
+    pub fn to_i128(&self) -> Result<i128, MathError> {
+        self.basis.garner.reconstruct_symmetric(&self.residues)
+    }
+
+    /// The value in `[0, M)` with no size limit.
+    #[cfg(feature = "internal-bigint")]
+    pub fn to_big(&self) -> BigU {
+        self.basis
+            .garner
+            .reconstruct_big(&self.residues)
+            .expect("one residue per modulus")
+    }
+
+    /// Applies `op(a, b, m)` channel by channel; `DimensionMismatch` across bases.
+    fn zip_with(
+        &self,
+        other: &RnsInt<'a>,
+        op: impl Fn(u64, u64, u64) -> u64,
+    ) -> Result<RnsInt<'a>, MathError> {
+        if !core::ptr::eq(self.basis, other.basis) && self.basis != other.basis {
+            return Err(MathError::DimensionMismatch);
+        }
+        let residues = self
+            .residues
+            .iter()
+            .zip(&other.residues)
+            .zip(self.basis.moduli())
+            .map(|((&a, &b), &m)| op(a, b, m))
+            .collect();
+        Ok(RnsInt {
+            basis: self.basis,
+            residues,
+        })
+    }
+}
+
+macro_rules! forward_checked_ops {
+    ($($op:ident :: $method:ident => $checked:ident),*) => {
+        $(
+            impl<'a> $op for &RnsInt<'a> {
+                type Output = Result<RnsInt<'a>, MathError>;
+
+                fn $method(self, other: &RnsInt<'a>) -> Result<RnsInt<'a>, MathError> {
+                    self.$checked(other)
+                }
+            }
+        )*
+    };
+}
+
+forward_checked_ops!(
+    Add::add => checked_add,
+    Sub::sub => checked_sub,
+    Mul::mul => checked_mul
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rns_small() {
+        let basis = RnsBasis::new(&[7, 11, 13]).unwrap();
+        let x = basis.encode(100);
+        assert_eq!(x.residues(), &[2, 1, 9]);
+        let y = (&x * &basis.encode(9)).unwrap();
+        assert_eq!(y.residues(), &[4, 9, 3]);
+        assert_eq!(y.to_u128(), Ok(900));
+        // 1001 = M: wraps
+        assert_eq!((&y + &basis.encode(200)).unwrap().to_u128(), Ok(99));
+        let negative = (&basis.encode(3) - &basis.encode(10)).unwrap();
+        assert_eq!(negative.to_i128(), Ok(-7));
+        assert_eq!(negative.to_u128(), Ok(994));
+        assert_eq!(basis.encode_signed(-7), negative);
+    }
+
+    #[test]
+    fn test_rns_matches_integers() {
+        // five primes near 2^60: M is about 2^300, far beyond the products below
+        let primes = [
+            (1u64 << 60) - 93,
+            (1 << 60) - 107,
+            (1 << 60) - 173,
+            (1 << 60) - 179,
+            (1 << 60) - 257,
+        ];
+        let basis = RnsBasis::new(&primes).unwrap();
+        let values: [i128; 5] = [0, 1, -1, 123_456_789_012_345, -(1 << 62) + 5];
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (basis.encode_signed(a), basis.encode_signed(b));
+                assert_eq!((&x + &y).unwrap().to_i128(), Ok(a + b));
+                assert_eq!((&x - &y).unwrap().to_i128(), Ok(a - b));
+                assert_eq!((&x * &y).unwrap().to_i128(), Ok(a * b));
+            }
+        }
+        // (2^63)^3 leaves u128 but not the basis
+        let big = basis.encode(1 << 63);
+        let cube = (&(&big * &big).unwrap() * &big).unwrap();
+        assert_eq!(cube.to_u128(), Err(MathError::Overflow));
+        assert_eq!((&cube - &cube).unwrap().to_u128(), Ok(0));
+    }
+
+    #[cfg(feature = "internal-bigint")]
+    #[test]
+    fn test_rns_to_big() {
+        let primes = [
+            (1u64 << 60) - 93,
+            (1 << 60) - 107,
+            (1 << 60) - 173,
+            (1 << 60) - 179,
+        ];
+        let basis = RnsBasis::new(&primes).unwrap();
+        let big = basis.encode(1 << 63);
+        let cube = (&(&big * &big).unwrap() * &big).unwrap();
+        assert_eq!(cube.to_big(), BigU::from_u64(1).shl_bits(189));
+    }
+
+    #[test]
+    fn test_rns_errors() {
+        assert_eq!(RnsBasis::new(&[]), Err(MathError::EmptyInput));
+        assert_eq!(RnsBasis::new(&[4, 6]), Err(MathError::NoSolution));
+        assert_eq!(RnsBasis::new(&[5, 0]), Err(MathError::DivisionByZero));
+        let (a, b) = (
+            RnsBasis::new(&[3, 5]).unwrap(),
+            RnsBasis::new(&[5, 7]).unwrap(),
+        );
+        let same = RnsBasis::new(&[3, 5]).unwrap();
+        assert_eq!(
+            &a.encode(1) + &b.encode(1),
+            Err(MathError::DimensionMismatch)
+        );
+        assert_eq!((&a.encode(1) + &same.encode(2)).unwrap().to_u128(), Ok(3));
+    }
+}
@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+
+use crate::errors::MathError;
+
+/// Single round-robin tournament for teams `0..n_teams` by the circle method: each round
+/// is a list of pairings `(a, b)` with `a < b`, and every two teams meet exactly once:
+/// - `round_robin_schedule(4) = [[(0, 3), (1, 2)], [(1, 3), (0, 2)], [(2, 3), (0, 1)]]`
+///
+/// With `n` even, team `n - 1` stays fixed and the others turn around a circle of
+/// `m = n - 1` places: in round `r` the fixed team plays `r`, and `r + k` plays `r - k`
+/// mod `m` for `k = 1..n/2`, since `(r + k) + (r - k) ≡ 2r` pairs off the residues
+/// symmetric about `r`. That makes `n - 1` rounds of `n/2` games. With `n` odd a phantom
+/// team is added and its games dropped, so team `r` sits out round `r`: `n` rounds of
+/// `(n - 1)/2` games. `MathError::OutOfRange` if `n_teams < 2`.
+pub fn round_robin_schedule(n_teams: u64) -> Result<Vec<Vec<(u64, u64)>>, MathError> {
+    if n_teams < 2 {
+        return Err(MathError::OutOfRange);
+    }
+    // circle size, odd; the fixed team is `m`, a phantom when `n_teams` is odd
+    let m = (n_teams - 1) | 1;
+    let pairing = |a: u64, b: u64| (a.min(b), a.max(b));
+    Ok((0..m)
+        .map(|r| {
+            let mut round = Vec::with_capacity((n_teams / 2) as usize);
+            if m < n_teams {
+                round.push((r, m));
+            }
+            round.extend((1..=m / 2).map(|k| pairing((r + k) % m, (r + m - k) % m)));
+            round
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::BTreeSet;
+    use alloc::vec;
+
+    #[test]
+    fn test_round_robin_schedule() {
+        assert_eq!(
+            round_robin_schedule(4),
+            Ok(vec![
+                vec![(0, 3), (1, 2)],
+                vec![(1, 3), (0, 2)],
+                vec![(2, 3), (0, 1)],
+            ])
+        );
+        assert_eq!(round_robin_schedule(2), Ok(vec![vec![(0, 1)]]));
+        // team r sits out round r
+        assert_eq!(
+            round_robin_schedule(3),
+            Ok(vec![vec![(1, 2)], vec![(0, 2)], vec![(0, 1)]])
+        );
+        assert_eq!(round_robin_schedule(1), Err(MathError::OutOfRange));
+        assert_eq!(round_robin_schedule(0), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_every_pair_meets_once() {
+        for n in 2..40u64 {
+            let schedule = round_robin_schedule(n).unwrap();
+            assert_eq!(
+                schedule.len() as u64,
+                if n.is_multiple_of(2) { n - 1 } else { n }
+            );
+            let mut games = BTreeSet::new();
+            for round in &schedule {
+                assert_eq!(round.len() as u64, n / 2);
+                let mut playing = BTreeSet::new();
+                for &(a, b) in round {
+                    assert!(a < b && b < n);
+                    assert!(playing.insert(a) && playing.insert(b), "n = {n}");
+                    assert!(games.insert((a, b)), "n = {n}");
+                }
+            }
+            assert_eq!(games.len() as u64, n * (n - 1) / 2);
+        }
+    }
+}
@@ -0,0 +1,380 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use crate::algebra::gcd::gcd_u64;
+use crate::algebra::prime_nbr::{is_prime_u64, sieve, uniform_below};
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Largest bound accepted by `random_smooth`, which sieves the primes up to it.
+pub const MAX_SMOOTHNESS_BOUND: u64 = 1 << 24;
+
+/// Random draws below this width are tried blindly before falling back to a scan.
+const PRIME_ATTEMPTS: u64 = 4096;
+
+/// A sampled integer together with its factorization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampledInt {
+    pub value: u64,
+    /// `(prime, exponent)` pairs, ascending, multiplying to `value`.
+    pub factors: Vec<(u64, u32)>,
+}
+
+/// Uniformly random prime with exactly `bits` bits, for `2 <= bits <= 64`:
+/// - `random_prime(8, rng)` is one of the 23 primes in `[128, 255]`
+///
+/// Draws from `rng` until the deterministic `is_prime_u64` accepts, about `0.7·bits`
+/// draws on average. `rng` supplies uniform `u64`s, as for `UniversalHash::new`.
+/// `MathError::OutOfRange` for other sizes.
+pub fn random_prime(bits: u32, mut rng: impl FnMut() -> u64) -> Result<u64, MathError> {
+    if !(2..=64).contains(&bits) {
+        return Err(MathError::OutOfRange);
+    }
+    let (lo, hi) = bit_range(bits);
+    // Bertrand's postulate puts a prime in every [2^(k-1), 2^k)
+    random_prime_between(lo, hi, &mut rng).ok_or(MathError::NoSolution)
+}
+
+/// Random `p·q` with exactly `bits` bits, `p < q` primes of about `bits/2` bits each,
+/// for `4 <= bits <= 64`:
+/// - `random_semiprime(64, rng)`: two 32-bit primes, a typical factoring test vector
+///
+/// `p` is a random prime of `bits/2` bits and `q` a random prime in the range that
+/// completes the bit length, so the sizes are balanced but `p·q` is not uniform over
+/// all semiprimes. `MathError::OutOfRange` for other sizes.
+pub fn random_semiprime(bits: u32, mut rng: impl FnMut() -> u64) -> Result<SampledInt, MathError> {
+    if !(4..=64).contains(&bits) {
+        return Err(MathError::OutOfRange);
+    }
+    let (lo, hi) = bit_range(bits);
+    loop {
+        let p = random_prime(bits / 2, &mut rng)?;
+        let Some(q) = random_prime_between(lo.div_ceil(p), hi / p, &mut rng) else {
+            continue;
+        };
+        if p != q {
+            let (p, q) = (p.min(q), p.max(q));
+            return Ok(SampledInt {
+                value: p * q,
+                factors: Vec::from([(p, 1), (q, 1)]),
+            });
+        }
+    }
+}
+
+/// Random squarefree integer with exactly `bits` bits, for `2 <= bits <= 64`:
+/// - `random_squarefree(16, rng)`: e.g. `2·7·3037 = 42518`
+///
+/// Built from the factorization up: random primes of random sizes are multiplied in,
+/// each distinct from the last, until a final prime lands the product in
+/// `[2^(bits-1), 2^bits)`. Known factors cost no factoring, even at 64 bits, but the
+/// result is not uniform over squarefree integers: small factor counts are favoured.
+/// `MathError::OutOfRange` for other sizes.
+pub fn random_squarefree(bits: u32, mut rng: impl FnMut() -> u64) -> Result<SampledInt, MathError> {
+    if !(2..=64).contains(&bits) {
+        return Err(MathError::OutOfRange);
+    }
+    let (lo, hi) = bit_range(bits);
+    'attempt: loop {
+        let (mut n, mut primes) = (1u64, Vec::new());
+        while n < lo {
+            // a prime in [need_lo, need_hi] finishes; a smaller one leaves room for more
+            let (need_lo, need_hi) = (lo.div_ceil(n), hi / n);
+            let finish = need_lo <= 2 || rng() & 1 == 0;
+            let p = if finish {
+                random_prime_between(need_lo, need_hi, &mut rng)
+            } else {
+                let size = 2 + uniform_below(u64::from(need_lo.ilog2()), &mut rng) as u32;
+                let (small_lo, small_hi) = bit_range(size);
+                random_prime_between(small_lo, small_hi.min(need_lo - 1), &mut rng)
+            };
+            match p {
+                Some(p) if !primes.contains(&p) => {
+                    n *= p;
+                    primes.push(p);
+                }
+                _ if finish => continue 'attempt,
+                _ => {}
+            }
+        }
+        primes.sort_unstable();
+        return Ok(SampledInt {
+            value: n,
+            factors: primes.into_iter().map(|p| (p, 1)).collect(),
+        });
+    }
+}
+
+/// Random `bound`-smooth integer (no prime factor above `bound`) with exactly `bits`
+/// bits, for `2 <= bits <= 64` and `2 <= bound <= MAX_SMOOTHNESS_BOUND`:
+/// - `random_smooth(32, 100, rng)`: e.g. `2^6·3·7·11·37·41·97 = 2175450816`
+///
+/// Sieves the primes up to `bound`, then multiplies in primes drawn uniformly from
+/// those that keep the product below `2^bits` until it reaches `2^(bits-1)`; 2 always
+/// fits, so no draw is wasted. Not uniform over smooth integers. `MathError::OutOfRange`
+/// for other parameters.
+pub fn random_smooth(
+    bits: u32,
+    bound: u64,
+    mut rng: impl FnMut() -> u64,
+) -> Result<SampledInt, MathError> {
+    if !(2..=64).contains(&bits) || !(2..=MAX_SMOOTHNESS_BOUND).contains(&bound) {
+        return Err(MathError::OutOfRange);
+    }
+    let (lo, hi) = bit_range(bits);
+    let limit = bound.min(hi) as usize;
+    let primes: Vec<u64> = sieve(limit)
+        .iter()
+        .enumerate()
+        .filter(|&(_, &is_prime)| is_prime)
+        .map(|(p, _)| p as u64)
+        .collect();
+    let mut n = 1u64;
+    let mut factors = BTreeMap::new();
+    while n < lo {
+        // n < 2^(bits-1) leaves room for at least a factor 2
+        let fitting = primes.partition_point(|&p| p <= hi / n);
+        let p = primes[uniform_below(fitting as u64, &mut rng) as usize];
+        n *= p;
+        *factors.entry(p).or_insert(0) += 1;
+    }
+    Ok(SampledInt {
+        value: n,
+        factors: factors.into_iter().collect(),
+    })
+}
+
+/// Uniformly random `(a, b)` with both in `range` and `gcd(a, b) = 1`:
+/// - `random_coprime_pair(1..=100, rng)`, e.g. `(39, 88)`
+///
+/// Rejection sampling: draws independent pairs until the gcd is 1, which is uniform
+/// over the coprime pairs and takes `π²/6 ≈ 1.64` draws on average over long ranges.
+/// A single-value range is coprime only as `(1, 1)`. Errors with `EmptyInput` for an
+/// empty range and `NoSolution` for a single value other than 1.
+pub fn random_coprime_pair(
+    range: RangeInclusive<u64>,
+    mut rng: impl FnMut() -> u64,
+) -> Result<(u64, u64), MathError> {
+    let (lo, hi) = (*range.start(), *range.end());
+    if lo > hi {
+        return Err(MathError::EmptyInput);
+    }
+    if lo == hi && lo != 1 {
+        return Err(MathError::NoSolution);
+    }
+    // any two consecutive values are coprime, so a wider range always succeeds
+    let mut draw = || match hi - lo {
+        u64::MAX => rng(),
+        span => lo + uniform_below(span + 1, &mut rng),
+    };
+    loop {
+        let (a, b) = (draw(), draw());
+        if gcd_u64(a, b) == 1 {
+            return Ok((a, b));
+        }
+    }
+}
+
+/// Uniformly random element of the Farey sequence `F_n`, the reduced fractions
+/// `p/q` in `[0, 1]` with `q <= max_denominator`:
+/// - `random_rational(4, rng)` is each of `0, 1/4, 1/3, 1/2, 2/3, 3/4, 1` with
+///   probability 1/7
+///
+/// Draws `(p, q)` uniformly from `[0, n] × [1, n]` and keeps it when `p <= q` and
+/// `gcd(p, q) = 1`: each Farey fraction is exactly one such pair in lowest terms, so
+/// the accepted fractions are uniform, and about 30% of draws are accepted. Errors with
+/// `OutOfRange` if `max_denominator < 1`.
+pub fn random_rational(
+    max_denominator: i64,
+    mut rng: impl FnMut() -> u64,
+) -> Result<Rational, MathError> {
+    if max_denominator < 1 {
+        return Err(MathError::OutOfRange);
+    }
+    let n = max_denominator as u64;
+    loop {
+        let (p, q) = (
+            uniform_below(n + 1, &mut rng),
+            1 + uniform_below(n, &mut rng),
+        );
+        if p <= q && gcd_u64(p, q) == 1 {
+            return Rational::new(p as i64, q as i64);
+        }
+    }
+}
+
+/// `[2^(bits-1), 2^bits - 1]` for `1 <= bits <= 64`.
+fn bit_range(bits: u32) -> (u64, u64) {
+    (1 << (bits - 1), u64::MAX >> (64 - bits))
+}
+
+/// A random prime in `[lo, hi]`, if there is one: uniform draws for wide ranges, a
+/// uniform pick among all primes for narrow ones.
+fn random_prime_between(lo: u64, hi: u64, rng: &mut impl FnMut() -> u64) -> Option<u64> {
+    let lo = lo.max(2);
+    if lo > hi {
+        return None;
+    }
+    let width = hi - lo;
+    if width < PRIME_ATTEMPTS {
+        let primes: Vec<u64> = (lo..=hi).filter(|&n| is_prime_u64(n)).collect();
+        return (!primes.is_empty())
+            .then(|| primes[uniform_below(primes.len() as u64, rng) as usize]);
+    }
+    // at least 1 in 45 numbers below 2^64 is prime, so the scan is practically never
+    // reached
+    (0..PRIME_ATTEMPTS)
+        .map(|_| lo + uniform_below(width + 1, rng))
+        .find(|&n| is_prime_u64(n))
+        .or_else(|| (lo..=hi).find(|&n| is_prime_u64(n)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::prime_nbr::splitmix64;
+
+    fn rng(seed: u64) -> impl FnMut() -> u64 {
+        let mut state = seed;
+        move || splitmix64(&mut state)
+    }
+
+    fn check(sample: &SampledInt, bits: u32) {
+        assert_eq!(sample.value.ilog2() + 1, bits, "{sample:?}");
+        let product = sample
+            .factors
+            .iter()
+            .fold(1u64, |acc, &(p, e)| acc * p.pow(e));
+        assert_eq!(product, sample.value);
+        assert!(sample.factors.iter().all(|&(p, _)| is_prime_u64(p)));
+        assert!(sample.factors.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_random_prime() {
+        let mut next = rng(1);
+        for bits in 2..=64 {
+            for _ in 0..20 {
+                let p = random_prime(bits, &mut next).unwrap();
+                assert!(is_prime_u64(p));
+                assert_eq!(p.ilog2() + 1, bits);
+            }
+        }
+        // both 2-bit primes come up
+        let small: Vec<u64> = (0..50)
+            .map(|_| random_prime(2, &mut next).unwrap())
+            .collect();
+        assert!(small.contains(&2) && small.contains(&3));
+        assert_eq!(random_prime(1, &mut next), Err(MathError::OutOfRange));
+        assert_eq!(random_prime(65, &mut next), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_random_semiprime() {
+        let mut next = rng(2);
+        for bits in 4..=64 {
+            for _ in 0..10 {
+                let s = random_semiprime(bits, &mut next).unwrap();
+                check(&s, bits);
+                assert_eq!(s.factors.len(), 2);
+                assert!(s.factors.iter().all(|&(_, e)| e == 1));
+            }
+        }
+        let s = random_semiprime(64, rng(3)).unwrap();
+        assert!(s.factors.iter().all(|&(p, _)| p.ilog2() + 1 >= 31));
+        assert_eq!(random_semiprime(3, rng(0)), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_random_squarefree() {
+        let mut next = rng(4);
+        let mut factor_counts = [0usize; 16];
+        for bits in 2..=64 {
+            for _ in 0..20 {
+                let s = random_squarefree(bits, &mut next).unwrap();
+                check(&s, bits);
+                assert!(s.factors.iter().all(|&(_, e)| e == 1));
+                factor_counts[s.factors.len()] += 1;
+            }
+        }
+        // primes and composites both occur
+        assert!(factor_counts[1] > 0 && factor_counts[2..].iter().sum::<usize>() > 0);
+        assert_eq!(random_squarefree(0, rng(0)), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_random_smooth() {
+        let mut next = rng(5);
+        for bits in 2..=64 {
+            for bound in [2, 3, 100, 65_537] {
+                let s = random_smooth(bits, bound, &mut next).unwrap();
+                check(&s, bits);
+                assert!(s.factors.iter().all(|&(p, _)| p <= bound));
+            }
+        }
+        assert_eq!(random_smooth(10, 2, rng(6)).unwrap().value, 512);
+        assert_eq!(random_smooth(10, 1, rng(0)), Err(MathError::OutOfRange));
+        assert_eq!(
+            random_smooth(10, MAX_SMOOTHNESS_BOUND + 1, rng(0)),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_random_coprime_pair() {
+        let mut next = rng(7);
+        for _ in 0..1000 {
+            let (a, b) = random_coprime_pair(10..=20, &mut next).unwrap();
+            assert!((10..=20).contains(&a) && (10..=20).contains(&b));
+            assert_eq!(gcd_u64(a, b), 1);
+        }
+        // every coprime pair in [2, 5]² comes up, and nothing else
+        let mut seen = BTreeMap::new();
+        for _ in 0..2000 {
+            *seen
+                .entry(random_coprime_pair(2..=5, &mut next).unwrap())
+                .or_insert(0) += 1;
+        }
+        assert_eq!(seen.len(), 10);
+        assert!(seen.values().all(|&count| count > 120));
+        let (a, b) = random_coprime_pair(0..=u64::MAX, &mut next).unwrap();
+        assert_eq!(gcd_u64(a, b), 1);
+        assert_eq!(random_coprime_pair(1..=1, &mut next), Ok((1, 1)));
+        assert_eq!(
+            random_coprime_pair(4..=4, &mut next),
+            Err(MathError::NoSolution)
+        );
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 5..=4;
+        assert_eq!(
+            random_coprime_pair(empty, &mut next),
+            Err(MathError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn test_random_rational() {
+        let mut next = rng(8);
+        let mut seen = BTreeMap::new();
+        for _ in 0..7000 {
+            *seen
+                .entry(random_rational(4, &mut next).unwrap())
+                .or_insert(0) += 1;
+        }
+        let farey: Vec<Rational> = [(0, 1), (1, 4), (1, 3), (1, 2), (2, 3), (3, 4), (1, 1)]
+            .iter()
+            .map(|&(p, q)| Rational::new(p, q).unwrap())
+            .collect();
+        assert_eq!(seen.keys().copied().collect::<Vec<_>>(), farey);
+        // 1000 expected each
+        assert!(seen.values().all(|&count| (850..1150).contains(&count)));
+        let r = random_rational(1_000_000, &mut next).unwrap();
+        assert!(r >= Rational::ZERO && r <= Rational::ONE && r.denom() <= 1_000_000);
+        assert_eq!(
+            random_rational(1, &mut next).map(|r| r.is_integer()),
+            Ok(true)
+        );
+        assert_eq!(random_rational(0, &mut next), Err(MathError::OutOfRange));
+    }
+}
@@ -0,0 +1,318 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::{fs, io, path::Path};
+
+use crate::algebra::prime_nbr::sieve;
+use crate::errors::MathError;
+
+/// First bytes of every `Sieve::to_bytes` snapshot, format version included.
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"EUSIEVE1";
+
+const SNAPSHOT_HEADER: usize = SNAPSHOT_MAGIC.len() + 8;
+
+/// Sieve of Eratosthenes up to a `limit`, kept so that primality queries and
+/// prime listings below it cost no further sieving.
+///
+/// Built in O(limit log log limit) time with one byte per integer. Queries take `&self`
+/// and never write to the table, so a `Sieve` is `Send + Sync`: build it once and share
+/// it between threads behind an `Arc` with no locking; only `extend_to` needs `&mut`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sieve {
+    is_prime: Vec<bool>,
+}
+
+impl Sieve {
+    pub fn new(limit: usize) -> Self {
+        Sieve {
+            is_prime: sieve(limit),
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.is_prime.len() - 1
+    }
+
+    /// Raises the limit to `new_limit` by sieving only `limit + 1..=new_limit`, crossing
+    /// out multiples of the primes up to `√new_limit` from the first one past the old
+    /// limit; does nothing if `new_limit` is not above the current limit.
+    ///
+    /// O((new_limit - limit) log log new_limit) plus O(√new_limit) for the primes, so
+    /// growing a sieve step by step costs about as much as building the final one.
+    pub fn extend_to(&mut self, new_limit: usize) {
+        let old_limit = self.limit();
+        if new_limit <= old_limit {
+            return;
+        }
+        self.is_prime.resize(new_limit + 1, true);
+        for n in old_limit + 1..2.min(new_limit + 1) {
+            self.is_prime[n] = false;
+        }
+        // a new p <= √new_limit has been crossed out by every smaller prime by the time
+        // it is reached
+        for p in (2..).take_while(|p| p * p <= new_limit) {
+            if !self.is_prime[p] {
+                continue;
+            }
+            let first = (p * p).max((old_limit / p + 1) * p);
+            for multiple in (first..=new_limit).step_by(p) {
+                self.is_prime[multiple] = false;
+            }
+        }
+    }
+
+    /// `MathError::OutOfRange` if `n` exceeds the limit.
+    pub fn is_prime(&self, n: usize) -> Result<bool, MathError> {
+        self.is_prime.get(n).copied().ok_or(MathError::OutOfRange)
+    }
+
+    /// Primes `<= limit`, ascending:
+    /// - `Sieve::new(100).primes_up_to(20) = [2, 3, 5, 7, 11, 13, 17, 19]`
+    ///
+    /// `MathError::OutOfRange` if `limit` exceeds the sieve's own.
+    pub fn primes_up_to(&self, limit: usize) -> Result<Vec<u64>, MathError> {
+        if limit > self.limit() {
+            return Err(MathError::OutOfRange);
+        }
+        Ok(self.primes().take_while(|&p| p <= limit as u64).collect())
+    }
+
+    /// Every prime up to the limit, ascending.
+    pub fn primes(&self) -> Primes<'_> {
+        Primes {
+            is_prime: &self.is_prime,
+            next: 2,
+        }
+    }
+
+    /// Compact snapshot: `SNAPSHOT_MAGIC`, the limit as a little-endian `u64`, then one
+    /// bit per integer `0..=limit`, least significant bit first.
+    ///
+    /// About `limit / 8` bytes, an eighth of the sieve in memory.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SNAPSHOT_HEADER + self.is_prime.len().div_ceil(8));
+        bytes.extend_from_slice(&SNAPSHOT_MAGIC);
+        bytes.extend_from_slice(&(self.limit() as u64).to_le_bytes());
+        for chunk in self.is_prime.chunks(8) {
+            let byte = chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |acc, (i, &prime)| acc | (u8::from(prime) << i));
+            bytes.push(byte);
+        }
+        bytes
+    }
+
+    /// Sieve back from `to_bytes`, without sieving again.
+    ///
+    /// `MathError::OutOfRange` for a wrong magic number, a length that does not match the
+    /// limit, a limit past `usize` or bits set past the limit.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MathError> {
+        let (header, bits) = bytes
+            .split_at_checked(SNAPSHOT_HEADER)
+            .ok_or(MathError::OutOfRange)?;
+        let (magic, limit) = header.split_at(SNAPSHOT_MAGIC.len());
+        if magic != SNAPSHOT_MAGIC {
+            return Err(MathError::OutOfRange);
+        }
+        let limit = u64::from_le_bytes(limit.try_into().map_err(|_| MathError::OutOfRange)?);
+        let len = usize::try_from(limit)
+            .ok()
+            .and_then(|l| l.checked_add(1))
+            .ok_or(MathError::OutOfRange)?;
+        if bits.len() != len.div_ceil(8) || bits.last().is_some_and(|&b| b >> ((len - 1) % 8) > 1) {
+            return Err(MathError::OutOfRange);
+        }
+        let is_prime = (0..len).map(|n| bits[n / 8] >> (n % 8) & 1 == 1).collect();
+        Ok(Sieve { is_prime })
+    }
+
+    /// Writes `to_bytes` to `path`.
+    #[cfg(feature = "std")]
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    /// Reads a snapshot written by `save`; a malformed file is an
+    /// `io::ErrorKind::InvalidData` error wrapping the `MathError` of `from_bytes`.
+    #[cfg(feature = "std")]
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Sieve::from_bytes(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Iterator returned by `Sieve::primes`.
+#[derive(Debug, Clone)]
+pub struct Primes<'a> {
+    is_prime: &'a [bool],
+    next: usize,
+}
+
+impl Iterator for Primes<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.next < self.is_prime.len() {
+            let n = self.next;
+            self.next += 1;
+            if self.is_prime[n] {
+                return Some(n as u64);
+            }
+        }
+        None
+    }
+}
+
+/// Primes `<= limit`, ascending, from a throwaway `Sieve`.
+pub fn primes_up_to(limit: usize) -> Vec<u64> {
+    Sieve::new(limit).primes().collect()
+}
+
+/// The `k`-th prime, counting from `nth_prime(1) = 2`:
+/// - `nth_prime(10) = 29`
+/// - `nth_prime(1000) = 7919`
+///
+/// Sieves up to Rosser's bound `p_k < k (ln k + ln ln k)` for `k >= 6`, so time and
+/// memory are O(k log k). `MathError::OutOfRange` if `k == 0`.
+pub fn nth_prime(k: usize) -> Result<u64, MathError> {
+    if k == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    let limit = if k < 6 {
+        11
+    } else {
+        // with b = ⌊log2 k⌋ + 1: ln k < 0.7·b and ln ln k < 0.7·(⌊log2 b⌋ + 1)
+        let bits = k.ilog2() as usize + 1;
+        k * (bits + bits.ilog2() as usize + 1) * 7 / 10 + 1
+    };
+    Sieve::new(limit)
+        .primes()
+        .nth(k - 1)
+        .ok_or(MathError::OutOfRange)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_sieve_queries() {
+        let sieve = Sieve::new(100);
+        assert_eq!(sieve.limit(), 100);
+        assert_eq!(sieve.is_prime(97), Ok(true));
+        assert_eq!(sieve.is_prime(91), Ok(false));
+        assert_eq!(sieve.is_prime(1), Ok(false));
+        assert_eq!(sieve.is_prime(101), Err(MathError::OutOfRange));
+        assert_eq!(sieve.primes_up_to(20), Ok(vec![2, 3, 5, 7, 11, 13, 17, 19]));
+        assert_eq!(sieve.primes_up_to(1), Ok(vec![]));
+        assert_eq!(sieve.primes_up_to(101), Err(MathError::OutOfRange));
+        assert_eq!(sieve.primes().count(), 25);
+        assert_eq!(sieve.primes().last(), Some(97));
+    }
+
+    #[test]
+    fn test_primes_up_to() {
+        assert_eq!(primes_up_to(0), vec![]);
+        assert_eq!(primes_up_to(2), vec![2]);
+        assert_eq!(primes_up_to(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        // π(10^6)
+        assert_eq!(primes_up_to(1_000_000).len(), 78_498);
+    }
+
+    #[test]
+    fn test_nth_prime() {
+        let first: Vec<u64> = (1..=10).map(|k| nth_prime(k).unwrap()).collect();
+        assert_eq!(first, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(nth_prime(1000), Ok(7919));
+        assert_eq!(nth_prime(1_000_000), Ok(15_485_863));
+        assert_eq!(nth_prime(0), Err(MathError::OutOfRange));
+        // the bound holds at every index of a long run
+        let primes = primes_up_to(200_000);
+        for (i, &p) in primes.iter().enumerate().step_by(97) {
+            assert_eq!(nth_prime(i + 1), Ok(p));
+        }
+    }
+
+    #[test]
+    fn test_extend_to() {
+        let mut sieve = Sieve::new(10);
+        sieve.extend_to(100);
+        assert_eq!(sieve, Sieve::new(100));
+        sieve.extend_to(50);
+        assert_eq!(sieve.limit(), 100);
+        // new primes below the new √limit have to cross out their own multiples
+        let mut sieve = Sieve::new(0);
+        for limit in [1, 2, 3, 4, 30, 31, 1000, 1001, 50_000] {
+            sieve.extend_to(limit);
+            assert_eq!(sieve, Sieve::new(limit), "limit = {limit}");
+        }
+        assert_eq!(sieve.primes().count(), 5133);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        for limit in [0, 1, 2, 7, 8, 9, 100, 10_007] {
+            let sieve = Sieve::new(limit);
+            let bytes = sieve.to_bytes();
+            assert_eq!(bytes.len(), 16 + (limit + 1).div_ceil(8));
+            assert_eq!(Sieve::from_bytes(&bytes), Ok(sieve), "limit = {limit}");
+        }
+        let bytes = Sieve::new(20).to_bytes();
+        // 2, 3, 5, 7 in the first byte
+        assert_eq!(bytes[16], 0b1010_1100);
+
+        assert_eq!(Sieve::from_bytes(&bytes[..10]), Err(MathError::OutOfRange));
+        assert_eq!(
+            Sieve::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(MathError::OutOfRange)
+        );
+        let mut corrupt = bytes.clone();
+        corrupt[0] ^= 1;
+        assert_eq!(Sieve::from_bytes(&corrupt), Err(MathError::OutOfRange));
+        // 21..=23 are past the limit
+        let mut corrupt = bytes.clone();
+        *corrupt.last_mut().unwrap() |= 0b1000_0000;
+        assert_eq!(Sieve::from_bytes(&corrupt), Err(MathError::OutOfRange));
+        let mut corrupt = bytes;
+        corrupt[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(Sieve::from_bytes(&corrupt), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        use alloc::sync::Arc;
+
+        let sieve = Arc::new(Sieve::new(100_000));
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let sieve = Arc::clone(&sieve);
+                std::thread::spawn(move || {
+                    (t * 25_000 + 1..=(t + 1) * 25_000)
+                        .filter(|&n| sieve.is_prime(n) == Ok(true))
+                        .count()
+                })
+            })
+            .collect();
+        let counts: Vec<usize> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(counts.iter().sum::<usize>(), 9592);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_save_and_load() {
+        let path =
+            std::env::temp_dir().join(std::format!("eucleia-sieve-{}.bin", std::process::id()));
+        let sieve = Sieve::new(100_000);
+        sieve.save(&path).unwrap();
+        let loaded = Sieve::load(&path).unwrap();
+        assert_eq!(loaded, sieve);
+        assert_eq!(loaded.primes().count(), 9592);
+
+        std::fs::write(&path, b"not a sieve").unwrap();
+        let err = Sieve::load(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        std::fs::remove_file(&path).unwrap();
+        assert!(Sieve::load(&path).is_err());
+    }
+}
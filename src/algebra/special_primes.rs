@@ -0,0 +1,410 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::prime_nbr::{miller_rabin_u128, mul_mod_u128, pow_mod_u128, sieve};
+use crate::errors::MathError;
+
+/// Largest `p` accepted by `is_wilson_prime`, keeping `p²` within `u64`.
+pub const MAX_WILSON_CANDIDATE: u64 = u32::MAX as u64;
+
+/// Largest `p` accepted by `is_wolstenholme_prime`, keeping `p⁴` within `u128`.
+pub const MAX_WOLSTENHOLME_CANDIDATE: u64 = u32::MAX as u64;
+
+/// Whether `p` is a Wilson prime: `(p-1)! ≡ -1 (mod p²)`, strengthening Wilson's
+/// theorem `(p-1)! ≡ -1 (mod p)`.
+///
+/// Only 5, 13 and 563 are known. Takes O(p) products mod `p²`; non-primes give `false`.
+/// `MathError::OutOfRange` past `MAX_WILSON_CANDIDATE`.
+pub fn is_wilson_prime(p: u64) -> Result<bool, MathError> {
+    if p > MAX_WILSON_CANDIDATE {
+        return Err(MathError::OutOfRange);
+    }
+    if !is_prime(p)? {
+        return Ok(false);
+    }
+    let m = p * p;
+    let factorial = (2..p).fold(1u64, |acc, k| (acc as u128 * k as u128 % m as u128) as u64);
+    Ok(factorial == m - 1)
+}
+
+/// Whether `p` is a Wolstenholme prime: `C(2p-1, p-1) ≡ 1 (mod p⁴)`, one power of `p`
+/// beyond Wolstenholme's theorem, which gives `mod p³` for every prime `p >= 5`.
+///
+/// Only 16843 and 2124679 are known. With `C(2p-1, p-1) = Π (p+k)/k` over
+/// `1 <= k < p` and the denominator prime to `p`, the test is `Π (p+k) ≡ Π k (mod p⁴)`,
+/// so no inverse is needed; O(p) products. Non-primes give `false`.
+/// `MathError::OutOfRange` past `MAX_WOLSTENHOLME_CANDIDATE`.
+pub fn is_wolstenholme_prime(p: u64) -> Result<bool, MathError> {
+    if p > MAX_WOLSTENHOLME_CANDIDATE {
+        return Err(MathError::OutOfRange);
+    }
+    if p < 5 || !is_prime(p)? {
+        return Ok(false);
+    }
+    let m = (p as u128).pow(4);
+    let (mut numer, mut denom) = (1u128, 1u128);
+    for k in 1..p as u128 {
+        numer = mul_mod_u128(numer, p as u128 + k, m);
+        denom = mul_mod_u128(denom, k, m);
+    }
+    Ok(numer == denom)
+}
+
+/// Wilson primes up to `limit`, testing each prime from the sieve: O(limit²/ln limit).
+///
+/// `MathError::OutOfRange` if `limit` exceeds `MAX_WILSON_CANDIDATE`.
+pub fn wilson_primes_upto(limit: u64) -> Result<Vec<u64>, MathError> {
+    search(limit, MAX_WILSON_CANDIDATE, is_wilson_prime)
+}
+
+/// Wolstenholme primes up to `limit`, testing each prime from the sieve:
+/// O(limit²/ln limit).
+///
+/// `MathError::OutOfRange` if `limit` exceeds `MAX_WOLSTENHOLME_CANDIDATE`.
+pub fn wolstenholme_primes_upto(limit: u64) -> Result<Vec<u64>, MathError> {
+    search(limit, MAX_WOLSTENHOLME_CANDIDATE, is_wolstenholme_prime)
+}
+
+/// Fermat quotient `q_p(a) = (a^(p-1) - 1) / p mod p`, an integer by Fermat's little
+/// theorem:
+/// - `fermat_quotient(2, 7) = 9 mod 7 = 2`, from `2⁶ - 1 = 63 = 7·9`
+/// - `fermat_quotient(2, 1093) = 0`, since 1093 is a Wieferich prime
+///
+/// Computed as `(a^(p-1) mod p²) - 1` divided by `p`. `MathError::OutOfRange` unless `p`
+/// is prime and does not divide `a`.
+pub fn fermat_quotient(a: u64, p: u64) -> Result<u64, MathError> {
+    if !is_prime(p)? || a.is_multiple_of(p) {
+        return Err(MathError::OutOfRange);
+    }
+    let m = p as u128 * p as u128;
+    let residue = pow_mod_u128(a as u128 % m, p as u128 - 1, m);
+    Ok(((residue + m - 1) % m / p as u128) as u64)
+}
+
+/// Whether `p` is a Wieferich prime to `base`: `base^(p-1) ≡ 1 (mod p²)`, that is
+/// `fermat_quotient(base, p) = 0`.
+///
+/// Base 2 has only 1093 and 3511 known. Non-primes and primes dividing `base` give
+/// `false`.
+pub fn is_wieferich(p: u64, base: u64) -> Result<bool, MathError> {
+    if p < 2 || base.is_multiple_of(p) {
+        return Ok(false);
+    }
+    // the congruence is rare, so it screens candidates before the primality test
+    let m = p as u128 * p as u128;
+    Ok(pow_mod_u128(base as u128 % m, p as u128 - 1, m) == 1 && is_prime(p)?)
+}
+
+/// Wieferich primes to `base` in `[lo, hi]`.
+///
+/// Every candidate is screened by the congruence and only survivors go through
+/// Miller-Rabin, so memory stays constant and a window far from 0 costs the same as one
+/// near it: O((hi - lo) log p) products.
+pub fn wieferich_primes_between(base: u64, lo: u64, hi: u64) -> Result<Vec<u64>, MathError> {
+    let mut found = Vec::new();
+    for p in lo..=hi {
+        if is_wieferich(p, base)? {
+            found.push(p);
+        }
+    }
+    Ok(found)
+}
+
+/// Which successor a Cunningham chain follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainKind {
+    /// `p -> 2p + 1`, chains of Sophie Germain primes.
+    First,
+    /// `p -> 2p - 1`.
+    Second,
+}
+
+impl ChainKind {
+    fn next(self, p: u128) -> Option<u128> {
+        match self {
+            ChainKind::First => p.checked_mul(2)?.checked_add(1),
+            ChainKind::Second => p.checked_mul(2)?.checked_sub(1),
+        }
+    }
+
+    fn previous(self, p: u128) -> Option<u128> {
+        let doubled = match self {
+            ChainKind::First => p.checked_sub(1)?,
+            ChainKind::Second => p + 1,
+        };
+        doubled.is_multiple_of(2).then_some(doubled / 2)
+    }
+}
+
+/// Whether `p` is a Sophie Germain prime: `p` and `2p + 1` are both prime:
+/// - `is_sophie_germain(11) = true`, `23` is prime
+/// - `is_sophie_germain(7) = false`, `15 = 3·5`
+pub fn is_sophie_germain(p: u64) -> Result<bool, MathError> {
+    Ok(is_prime(p)? && is_prime_wide(2 * p as u128 + 1)?)
+}
+
+/// Whether `p` is a safe prime: `p` and `(p - 1) / 2` are both prime, the Sophie Germain
+/// partner `p = 2q + 1` used for Diffie-Hellman groups:
+/// - `is_safe_prime(23) = true`, `is_safe_prime(13) = false`
+pub fn is_safe_prime(p: u64) -> Result<bool, MathError> {
+    Ok(!p.is_multiple_of(2) && is_prime(p / 2)? && is_prime(p)?)
+}
+
+/// The complete Cunningham chain of `kind` through `p`, from its first prime, which has
+/// no prime predecessor, to its last, whose successor is composite:
+/// - `cunningham_chain(5, ChainKind::First) = [2, 5, 11, 23, 47]`
+/// - `cunningham_chain(7, ChainKind::Second) = [7, 13]`
+///
+/// Empty if `p` is not prime. Members go through Miller-Rabin, so each one costs
+/// O(log p) products. `MathError::Overflow` if the chain runs past `u128`.
+pub fn cunningham_chain(p: u64, kind: ChainKind) -> Result<Vec<u128>, MathError> {
+    if !is_prime(p)? {
+        return Ok(Vec::new());
+    }
+    let mut first = p as u128;
+    while let Some(q) = kind.previous(first) {
+        if !is_prime_wide(q)? {
+            break;
+        }
+        first = q;
+    }
+    chain_from(first, kind, is_prime_wide)
+}
+
+/// Sophie Germain primes up to `limit`, ascending, from one sieve up to `2·limit + 1`:
+/// - `sophie_germain_primes_upto(50) = [2, 3, 5, 11, 23, 29, 41]`
+///
+/// O(limit log log limit) time and 2·limit bytes. `MathError::OutOfRange` if
+/// `2·limit + 1` does not fit in `usize`.
+pub fn sophie_germain_primes_upto(limit: u64) -> Result<Vec<u64>, MathError> {
+    let bound = usize::try_from(limit)
+        .ok()
+        .and_then(|l| l.checked_mul(2)?.checked_add(1))
+        .ok_or(MathError::OutOfRange)?;
+    let is_prime = sieve(bound);
+    Ok((2..=limit)
+        .filter(|&p| is_prime[p as usize] && is_prime[2 * p as usize + 1])
+        .collect())
+}
+
+/// Cunningham chains of `kind` with at least `min_length` primes whose first prime is
+/// at most `limit`, ordered by that first prime:
+/// - `cunningham_chains_upto(100, ChainKind::First, 4)` gives `[2, 5, 11, 23, 47]` and
+///   `[89, 179, 359, 719, 1439, 2879]`
+///
+/// Starts and members up to `limit` come from one sieve; members past it go through
+/// Miller-Rabin. `MathError::OutOfRange` if `limit` does not fit in `usize`.
+pub fn cunningham_chains_upto(
+    limit: u64,
+    kind: ChainKind,
+    min_length: usize,
+) -> Result<Vec<Vec<u128>>, MathError> {
+    let size = usize::try_from(limit).map_err(|_| MathError::OutOfRange)?;
+    let is_prime = sieve(size);
+    let sieved = |q: u128| -> Result<bool, MathError> {
+        match usize::try_from(q) {
+            Ok(i) if i <= size => Ok(is_prime[i]),
+            _ => is_prime_wide(q),
+        }
+    };
+    let mut chains = Vec::new();
+    for p in (2..=size).filter(|&p| is_prime[p]) {
+        let p = p as u128;
+        if kind.previous(p).is_some_and(|q| sieved(q).unwrap_or(false)) {
+            continue;
+        }
+        let chain = chain_from(p, kind, sieved)?;
+        if chain.len() >= min_length {
+            chains.push(chain);
+        }
+    }
+    Ok(chains)
+}
+
+/// Primes `first`, `next(first)`, ... up to the first composite successor.
+fn chain_from(
+    first: u128,
+    kind: ChainKind,
+    is_prime: impl Fn(u128) -> Result<bool, MathError>,
+) -> Result<Vec<u128>, MathError> {
+    let mut chain = vec![first];
+    loop {
+        let last = chain[chain.len() - 1];
+        let next = kind.next(last).ok_or(MathError::Overflow)?;
+        if !is_prime(next)? {
+            return Ok(chain);
+        }
+        chain.push(next);
+    }
+}
+
+fn search(
+    limit: u64,
+    max: u64,
+    test: impl Fn(u64) -> Result<bool, MathError>,
+) -> Result<Vec<u64>, MathError> {
+    if limit > max {
+        return Err(MathError::OutOfRange);
+    }
+    let mut found = Vec::new();
+    for (p, _) in sieve(limit as usize)
+        .iter()
+        .enumerate()
+        .filter(|&(_, &prime)| prime)
+    {
+        if test(p as u64)? {
+            found.push(p as u64);
+        }
+    }
+    Ok(found)
+}
+
+fn is_prime(p: u64) -> Result<bool, MathError> {
+    is_prime_wide(p as u128)
+}
+
+fn is_prime_wide(p: u128) -> Result<bool, MathError> {
+    Ok(p >= 2 && miller_rabin_u128(p, 32)?.is_prime())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wilson_primes() {
+        assert_eq!(wilson_primes_upto(1000), Ok(vec![5, 13, 563]));
+        assert_eq!(is_wilson_prime(563), Ok(true));
+        assert_eq!(is_wilson_prime(7), Ok(false));
+        assert_eq!(is_wilson_prime(25), Ok(false));
+        assert_eq!(is_wilson_prime(0), Ok(false));
+        assert_eq!(is_wilson_prime(1 << 32), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_wolstenholme_primes() {
+        assert_eq!(is_wolstenholme_prime(16843), Ok(true));
+        assert_eq!(wolstenholme_primes_upto(2000), Ok(vec![]));
+        // Wolstenholme's theorem mod p³ holds for every prime from 5 on
+        for p in [5u64, 7, 11, 13, 101] {
+            assert_eq!(is_wolstenholme_prime(p), Ok(false));
+            let m = (p as u128).pow(3);
+            let (numer, denom) =
+                (1..p as u128).fold((1, 1), |(a, b), k| (a * (p as u128 + k) % m, b * k % m));
+            assert_eq!(numer, denom, "p = {p}");
+        }
+        assert_eq!(is_wolstenholme_prime(3), Ok(false));
+        assert_eq!(
+            wolstenholme_primes_upto(u64::MAX),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_fermat_quotient() {
+        assert_eq!(fermat_quotient(2, 7), Ok(2));
+        assert_eq!(fermat_quotient(2, 1093), Ok(0));
+        assert_eq!(fermat_quotient(3, 11), Ok(0));
+        // against the exact quotient while a^(p-1) stays small
+        for p in [3u64, 5, 7, 11, 13] {
+            for a in (1..20u64).filter(|a| a % p != 0) {
+                let exact = ((a as u128).pow(p as u32 - 1) - 1) / p as u128;
+                assert_eq!(fermat_quotient(a, p), Ok((exact % p as u128) as u64));
+            }
+        }
+        // p² above 2^64
+        assert!(fermat_quotient(2, (1 << 61) - 1).is_ok());
+        assert_eq!(fermat_quotient(14, 7), Err(MathError::OutOfRange));
+        assert_eq!(fermat_quotient(2, 9), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_wieferich_primes() {
+        assert_eq!(wieferich_primes_between(2, 0, 5000), Ok(vec![1093, 3511]));
+        assert_eq!(
+            wieferich_primes_between(5, 0, 50_000),
+            Ok(vec![2, 20771, 40487])
+        );
+        assert_eq!(
+            wieferich_primes_between(3, 1000, 1_006_010),
+            Ok(vec![1_006_003])
+        );
+        assert_eq!(is_wieferich(11, 3), Ok(true));
+        assert_eq!(is_wieferich(1093, 3), Ok(false));
+        assert_eq!(is_wieferich(2, 4), Ok(false));
+        assert_eq!(is_wieferich(1, 2), Ok(false));
+    }
+
+    #[test]
+    fn test_wide_modulus() {
+        // p⁴ above 2^64 takes the double-and-add path
+        let p = 70_001u128;
+        let m = p.pow(4);
+        assert!(m > u64::MAX as u128);
+        assert_eq!(mul_mod_u128(m - 1, m - 1, m), 1);
+        assert_eq!(mul_mod_u128(p * p, p * p, m), 0);
+    }
+
+    #[test]
+    fn test_sophie_germain_and_safe_primes() {
+        assert_eq!(is_sophie_germain(11), Ok(true));
+        assert_eq!(is_sophie_germain(7), Ok(false));
+        assert_eq!(is_sophie_germain(15), Ok(false));
+        assert_eq!(is_safe_prime(23), Ok(true));
+        assert_eq!(is_safe_prime(13), Ok(false));
+        assert_eq!(is_safe_prime(2), Ok(false));
+        assert_eq!(is_safe_prime(3), Ok(false));
+        assert_eq!(
+            sophie_germain_primes_upto(50),
+            Ok(vec![2, 3, 5, 11, 23, 29, 41])
+        );
+        assert_eq!(sophie_germain_primes_upto(1), Ok(vec![]));
+        // 2p + 1 past u64
+        assert_eq!(is_sophie_germain(18_446_744_073_709_551_557), Ok(false));
+        let listed = sophie_germain_primes_upto(5000).unwrap();
+        let brute: Vec<u64> = (0..=5000)
+            .filter(|&p| is_sophie_germain(p).unwrap())
+            .collect();
+        assert_eq!(listed, brute);
+        assert!(listed.iter().all(|&p| is_safe_prime(2 * p + 1) == Ok(true)));
+    }
+
+    #[test]
+    fn test_cunningham_chains() {
+        assert_eq!(
+            cunningham_chain(5, ChainKind::First),
+            Ok(vec![2, 5, 11, 23, 47])
+        );
+        assert_eq!(
+            cunningham_chain(359, ChainKind::First),
+            Ok(vec![89, 179, 359, 719, 1439, 2879])
+        );
+        assert_eq!(cunningham_chain(7, ChainKind::Second), Ok(vec![7, 13]));
+        assert_eq!(cunningham_chain(3, ChainKind::Second), Ok(vec![2, 3, 5]));
+        assert_eq!(
+            cunningham_chain(1531, ChainKind::Second),
+            Ok(vec![1531, 3061, 6121, 12241, 24481])
+        );
+        assert_eq!(cunningham_chain(9, ChainKind::First), Ok(vec![]));
+
+        assert_eq!(
+            cunningham_chains_upto(100, ChainKind::First, 4),
+            Ok(vec![
+                vec![2, 5, 11, 23, 47],
+                vec![89, 179, 359, 719, 1439, 2879]
+            ])
+        );
+        // every chain found by the search is the one through its start
+        for kind in [ChainKind::First, ChainKind::Second] {
+            let chains = cunningham_chains_upto(3000, kind, 1).unwrap();
+            let starts: Vec<u64> = chains.iter().map(|c| c[0] as u64).collect();
+            for chain in &chains {
+                assert_eq!(cunningham_chain(chain[0] as u64, kind).as_ref(), Ok(chain));
+            }
+            // and every prime up to the limit lies on exactly one chain
+            let members = chains.iter().flatten().filter(|&&q| q <= 3000).count();
+            assert_eq!(members, sieve(3000).iter().filter(|&&p| p).count());
+            assert!(starts.is_sorted());
+        }
+    }
+}
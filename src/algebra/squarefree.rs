@@ -0,0 +1,189 @@
+use alloc::vec::Vec;
+
+use crate::algebra::poly_mod::PolyMod;
+use crate::algebra::polynomial::Polynomial;
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Square-free decomposition over Q by Yun's algorithm.
+///
+/// Returns monic, square-free, pairwise coprime `(aᵢ, i)` sorted by multiplicity, with
+/// `f = lc(f) · ∏ aᵢ^i`; only factors of positive degree are listed:
+/// - `x³ - x² - x + 1 = (x + 1)(x - 1)²` gives `[(x + 1, 1), (x - 1, 2)]`
+///
+/// With `b = f / gcd(f, f')` and `d = f' / gcd(f, f') - b'`, each step takes
+/// `aᵢ = gcd(b, d)` and divides it out, so only gcds of shrinking polynomials are
+/// needed. A constant `f` gives an empty list. Errors with `OutOfRange` for `f = 0`
+/// and `Overflow` if a coefficient leaves the `i64` range.
+pub fn squarefree_decomposition(
+    f: &Polynomial<Rational>,
+) -> Result<Vec<(Polynomial<Rational>, u32)>, MathError> {
+    if f.is_zero() {
+        return Err(MathError::OutOfRange);
+    }
+    let f = f.monic()?;
+    let df = f.derivative()?;
+    let a0 = f.gcd(&df)?;
+    let (mut b, _) = f.div_rem(&a0)?;
+    let (c, _) = df.div_rem(&a0)?;
+    let mut d = c.checked_sub(&b.derivative()?)?;
+
+    let mut factors = Vec::new();
+    let mut i = 1;
+    while b.degree() > Some(0) {
+        let a = b.gcd(&d)?;
+        (b, _) = b.div_rem(&a)?;
+        let (c, _) = d.div_rem(&a)?;
+        d = c.checked_sub(&b.derivative()?)?;
+        if a.degree() > Some(0) {
+            factors.push((a, i));
+        }
+        i += 1;
+    }
+    Ok(factors)
+}
+
+/// Square-free decomposition over GF(p), `p` prime, in the same `(aᵢ, i)` form.
+///
+/// Yun's step alone misses factors whose multiplicity is a multiple of `p`, since
+/// their derivative vanishes. The loop therefore peels off the multiplicities not
+/// divisible by `p`; what remains is a polynomial in `x^p`, whose `p`-th root
+/// (coefficients are fixed by Frobenius on GF(p)) is decomposed recursively and its
+/// multiplicities multiplied by `p`:
+/// - `x⁵ + 1 = (x + 1)⁵` over GF(5) gives `[(x + 1, 5)]`
+///
+/// Errors with `OutOfRange` for the zero polynomial.
+pub fn squarefree_decomposition_mod(f: &PolyMod) -> Result<Vec<(PolyMod, u32)>, MathError> {
+    if f.is_zero() {
+        return Err(MathError::OutOfRange);
+    }
+    let p = f.modulus();
+    let f = f.monic()?;
+    let mut c = f.gcd(&f.derivative())?;
+    let (mut w, _) = f.div_rem(&c)?;
+
+    let mut factors = Vec::new();
+    let mut i = 1;
+    while w.degree() > Some(0) {
+        let y = w.gcd(&c)?;
+        let (fac, _) = w.div_rem(&y)?;
+        if fac.degree() > Some(0) {
+            factors.push((fac, i));
+        }
+        (c, _) = c.div_rem(&y)?;
+        w = y;
+        i += 1;
+    }
+
+    if c.degree() > Some(0) {
+        let root_coeffs = c.coeffs().iter().step_by(p as usize).copied().collect();
+        let root = PolyMod::new(root_coeffs, p)?;
+        for (g, m) in squarefree_decomposition_mod(&root)? {
+            factors.push((g, m * p as u32));
+        }
+    }
+    factors.sort_by_key(|&(_, m)| m);
+    Ok(factors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn poly(coeffs: &[i64]) -> Polynomial<Rational> {
+        Polynomial::new(coeffs.iter().map(|&c| Rational::from_integer(c)).collect())
+    }
+
+    fn poly_mod(coeffs: &[u64], p: u64) -> PolyMod {
+        PolyMod::new(coeffs.to_vec(), p).unwrap()
+    }
+
+    #[test]
+    fn test_rational_decomposition() {
+        // x³ - x² - x + 1 = (x + 1)(x - 1)²
+        let factors = squarefree_decomposition(&poly(&[1, -1, -1, 1])).unwrap();
+        assert_eq!(factors, vec![(poly(&[1, 1]), 1), (poly(&[-1, 1]), 2)]);
+
+        // 2(x - 2)³(x² + 1)²x: leading coefficient dropped, factors monic
+        let f = poly(&[-2, 1])
+            .pow(3)
+            .unwrap()
+            .checked_mul(&poly(&[1, 0, 1]).pow(2).unwrap())
+            .unwrap()
+            .checked_mul(&poly(&[0, 2]))
+            .unwrap();
+        let factors = squarefree_decomposition(&f).unwrap();
+        assert_eq!(
+            factors,
+            vec![
+                (poly(&[0, 1]), 1),
+                (poly(&[1, 0, 1]), 2),
+                (poly(&[-2, 1]), 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_squarefree_and_constant_inputs() {
+        let f = poly(&[-6, 11, -6, 1]); // (x - 1)(x - 2)(x - 3)
+        assert_eq!(squarefree_decomposition(&f), Ok(vec![(f.clone(), 1)]));
+        assert_eq!(squarefree_decomposition(&poly(&[5])), Ok(vec![]));
+        assert_eq!(
+            squarefree_decomposition(&poly(&[])),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_mod_p_decomposition() {
+        // (x + 1)⁵ = x⁵ + 1 over GF(5)
+        let factors = squarefree_decomposition_mod(&poly_mod(&[1, 0, 0, 0, 0, 1], 5)).unwrap();
+        assert_eq!(factors, vec![(poly_mod(&[1, 1], 5), 5)]);
+
+        // x²·(x + 1)³·(x + 2)⁶ over GF(3): multiplicity 6 is a multiple of p
+        let p = 3;
+        let f = poly_mod(&[0, 1], p)
+            .mul(&poly_mod(&[0, 1], p))
+            .unwrap()
+            .mul(&poly_mod(&[1, 1], p).mul(&poly_mod(&[1, 1], p)).unwrap())
+            .unwrap()
+            .mul(&poly_mod(&[1, 1], p))
+            .unwrap();
+        let mut g = f.clone();
+        for _ in 0..6 {
+            g = g.mul(&poly_mod(&[2, 1], p)).unwrap();
+        }
+        let factors = squarefree_decomposition_mod(&g).unwrap();
+        assert_eq!(
+            factors,
+            vec![
+                (poly_mod(&[0, 1], p), 2),
+                (poly_mod(&[1, 1], p), 3),
+                (poly_mod(&[2, 1], p), 6)
+            ]
+        );
+        assert_eq!(
+            squarefree_decomposition_mod(&PolyMod::zero(7).unwrap()),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_mod_p_product_recovers_input() {
+        let p = 7;
+        // (x² + 1)(x + 3)² · 4
+        let f = poly_mod(&[1, 0, 1], p)
+            .mul(&poly_mod(&[3, 1], p).mul(&poly_mod(&[3, 1], p)).unwrap())
+            .unwrap()
+            .scale(4);
+        let factors = squarefree_decomposition_mod(&f).unwrap();
+        let mut product = poly_mod(&[4], p);
+        for (g, m) in &factors {
+            for _ in 0..*m {
+                product = product.mul(g).unwrap();
+            }
+        }
+        assert_eq!(product, f);
+    }
+}
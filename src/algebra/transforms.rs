@@ -0,0 +1,250 @@
+use alloc::vec::Vec;
+
+use crate::algebra::modular::mod_inverse;
+use crate::errors::MathError;
+
+/// Subset-sum (zeta) transform over the bitmasks indexing `values`, mod `m`:
+/// `A[S] = Σ_{T ⊆ S} a[T]`:
+/// - `subset_zeta(&[1, 2, 3, 4], 100) = [1, 3, 4, 10]`
+///
+/// The sum-over-subsets DP, one pass per bit, O(n log n) for `n = values.len()`.
+/// Errors with `EmptyInput` for no values, `OutOfRange` unless `n` is a power of two and
+/// `DivisionByZero` if `m == 0`.
+pub fn subset_zeta(values: &[u64], m: u64) -> Result<Vec<u64>, MathError> {
+    butterfly(values, m, |x, y| (x, add(y, x, m)))
+}
+
+/// Inverse of `subset_zeta`, `a[S] = Σ_{T ⊆ S} (-1)^|S \ T|·A[T]`. Same errors.
+pub fn subset_mobius(values: &[u64], m: u64) -> Result<Vec<u64>, MathError> {
+    butterfly(values, m, |x, y| (x, sub(y, x, m)))
+}
+
+/// Superset-sum transform, `A[S] = Σ_{T ⊇ S} a[T]`:
+/// - `superset_zeta(&[1, 2, 3, 4], 100) = [10, 6, 7, 4]`
+///
+/// Same errors as `subset_zeta`.
+pub fn superset_zeta(values: &[u64], m: u64) -> Result<Vec<u64>, MathError> {
+    butterfly(values, m, |x, y| (add(x, y, m), y))
+}
+
+/// Inverse of `superset_zeta`. Same errors.
+pub fn superset_mobius(values: &[u64], m: u64) -> Result<Vec<u64>, MathError> {
+    butterfly(values, m, |x, y| (sub(x, y, m), y))
+}
+
+/// Walsh–Hadamard transform, `A[S] = Σ_T (-1)^|S ∩ T|·a[T]`, the Fourier transform of
+/// the group of bitmasks under XOR:
+/// - `walsh_hadamard(&[1, 2, 3, 4], 100) = [10, 98, 96, 0]`, `-2 ≡ 98` and `-4 ≡ 96`
+///
+/// Same errors as `subset_zeta`.
+pub fn walsh_hadamard(values: &[u64], m: u64) -> Result<Vec<u64>, MathError> {
+    butterfly(values, m, |x, y| (add(x, y, m), sub(x, y, m)))
+}
+
+/// Inverse of `walsh_hadamard`: the same transform, scaled by `n⁻¹`. Errors as
+/// `subset_zeta`, and with `NoInverse` if `n` is not invertible mod `m`, so for an even
+/// `m` once `n >= 2`.
+pub fn inverse_walsh_hadamard(values: &[u64], m: u64) -> Result<Vec<u64>, MathError> {
+    let transformed = walsh_hadamard(values, m)?;
+    let scale = mod_inverse(values.len() as u64 % m, m).map_err(|_| MathError::NoInverse)?;
+    Ok(transformed
+        .into_iter()
+        .map(|x| (x as u128 * scale as u128 % m as u128) as u64)
+        .collect())
+}
+
+/// `c[S] = Σ_{T ∪ U = S} a[T]·b[U] mod m`:
+/// - `or_convolution(&[1, 1], &[1, 1], 100) = [1, 3]`
+///
+/// Pointwise product between `subset_zeta` and `subset_mobius`, O(n log n). Errors with
+/// `DimensionMismatch` if the lengths differ, otherwise as `subset_zeta`.
+pub fn or_convolution(a: &[u64], b: &[u64], m: u64) -> Result<Vec<u64>, MathError> {
+    convolve(a, b, m, subset_zeta, subset_mobius)
+}
+
+/// `c[S] = Σ_{T ∩ U = S} a[T]·b[U] mod m`:
+/// - `and_convolution(&[1, 1], &[1, 1], 100) = [3, 1]`
+///
+/// Through `superset_zeta`; same errors as `or_convolution`.
+pub fn and_convolution(a: &[u64], b: &[u64], m: u64) -> Result<Vec<u64>, MathError> {
+    convolve(a, b, m, superset_zeta, superset_mobius)
+}
+
+/// `c[S] = Σ_{T ⊕ U = S} a[T]·b[U] mod m`:
+/// - `xor_convolution(&[1, 2], &[3, 4], 101) = [11, 10]`
+///
+/// Through `walsh_hadamard`; errors as `or_convolution`, and with `NoInverse` as
+/// `inverse_walsh_hadamard`.
+pub fn xor_convolution(a: &[u64], b: &[u64], m: u64) -> Result<Vec<u64>, MathError> {
+    convolve(a, b, m, walsh_hadamard, inverse_walsh_hadamard)
+}
+
+type Transform = fn(&[u64], u64) -> Result<Vec<u64>, MathError>;
+
+fn convolve(
+    a: &[u64],
+    b: &[u64],
+    m: u64,
+    forward: Transform,
+    inverse: Transform,
+) -> Result<Vec<u64>, MathError> {
+    if a.len() != b.len() {
+        return Err(MathError::DimensionMismatch);
+    }
+    let product: Vec<u64> = forward(a, m)?
+        .into_iter()
+        .zip(forward(b, m)?)
+        .map(|(x, y)| (x as u128 * y as u128 % m as u128) as u64)
+        .collect();
+    inverse(&product, m)
+}
+
+/// Runs `step` on every pair `(a[j], a[j + h])` with bit `h` clear in `j`, bit by bit.
+fn butterfly(
+    values: &[u64],
+    m: u64,
+    step: impl Fn(u64, u64) -> (u64, u64),
+) -> Result<Vec<u64>, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let n = values.len();
+    if n == 0 {
+        return Err(MathError::EmptyInput);
+    }
+    if !n.is_power_of_two() {
+        return Err(MathError::OutOfRange);
+    }
+    let mut a: Vec<u64> = values.iter().map(|&v| v % m).collect();
+    let mut h = 1;
+    while h < n {
+        for block in (0..n).step_by(2 * h) {
+            for j in block..block + h {
+                (a[j], a[j + h]) = step(a[j], a[j + h]);
+            }
+        }
+        h *= 2;
+    }
+    Ok(a)
+}
+
+fn add(x: u64, y: u64, m: u64) -> u64 {
+    ((x as u128 + y as u128) % m as u128) as u64
+}
+
+fn sub(x: u64, y: u64, m: u64) -> u64 {
+    ((x as u128 + m as u128 - y as u128) % m as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const P: u64 = 998_244_353;
+
+    fn naive(a: &[u64], b: &[u64], m: u64, combine: impl Fn(usize, usize) -> usize) -> Vec<u64> {
+        let mut c = vec![0u128; a.len()];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                c[combine(i, j)] += x as u128 * y as u128 % m as u128;
+            }
+        }
+        c.into_iter().map(|x| (x % m as u128) as u64).collect()
+    }
+
+    fn table(n: usize, seed: u64) -> Vec<u64> {
+        (0..n as u64)
+            .map(|i| i.wrapping_mul(seed).wrapping_add(0x9e37_79b9_7f4a_7c15) >> 3)
+            .collect()
+    }
+
+    #[test]
+    fn test_transforms() {
+        assert_eq!(subset_zeta(&[1, 2, 3, 4], 100), Ok(vec![1, 3, 4, 10]));
+        assert_eq!(superset_zeta(&[1, 2, 3, 4], 100), Ok(vec![10, 6, 7, 4]));
+        assert_eq!(walsh_hadamard(&[1, 2, 3, 4], 100), Ok(vec![10, 98, 96, 0]));
+        assert_eq!(subset_zeta(&[7], 5), Ok(vec![2]));
+        assert_eq!(subset_zeta(&[], 5), Err(MathError::EmptyInput));
+        assert_eq!(subset_zeta(&[1, 2, 3], 5), Err(MathError::OutOfRange));
+        assert_eq!(subset_zeta(&[1, 2], 0), Err(MathError::DivisionByZero));
+        assert_eq!(
+            inverse_walsh_hadamard(&[1, 2], 100),
+            Err(MathError::NoInverse)
+        );
+        assert_eq!(inverse_walsh_hadamard(&[5], 100), Ok(vec![5]));
+
+        // sums over subsets, checked mask by mask
+        let a = table(64, 31);
+        let zeta = subset_zeta(&a, u64::MAX).unwrap();
+        for (s, &sum) in zeta.iter().enumerate() {
+            let direct = (0..64)
+                .filter(|t| t & s == *t)
+                .fold(0u128, |acc, t| (acc + a[t] as u128) % u64::MAX as u128);
+            assert_eq!(sum as u128, direct);
+        }
+
+        // each inverse undoes its transform
+        for m in [2, 7, P, u64::MAX] {
+            for n in [1, 2, 8, 256] {
+                let a: Vec<u64> = table(n, m).into_iter().map(|x| x % m).collect();
+                let pairs: [(Transform, Transform); 2] = [
+                    (subset_zeta, subset_mobius),
+                    (superset_zeta, superset_mobius),
+                ];
+                for (forward, inverse) in pairs {
+                    assert_eq!(inverse(&forward(&a, m).unwrap(), m), Ok(a.clone()));
+                }
+                if m % 2 == 1 {
+                    let transformed = walsh_hadamard(&a, m).unwrap();
+                    assert_eq!(inverse_walsh_hadamard(&transformed, m), Ok(a.clone()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_convolutions() {
+        assert_eq!(or_convolution(&[1, 1], &[1, 1], 100), Ok(vec![1, 3]));
+        assert_eq!(and_convolution(&[1, 1], &[1, 1], 100), Ok(vec![3, 1]));
+        assert_eq!(xor_convolution(&[1, 2], &[3, 4], 101), Ok(vec![11, 10]));
+        assert_eq!(
+            or_convolution(&[1, 2], &[1, 2, 3, 4], 100),
+            Err(MathError::DimensionMismatch)
+        );
+        assert_eq!(
+            xor_convolution(&[1, 2], &[3, 4], 1 << 40),
+            Err(MathError::NoInverse)
+        );
+        for m in [3, 1_000_003, P, u64::MAX] {
+            for n in [1, 4, 32, 128] {
+                let (a, b) = (table(n, 7), table(n, 13));
+                assert_eq!(
+                    or_convolution(&a, &b, m),
+                    Ok(naive(&a, &b, m, |i, j| i | j))
+                );
+                assert_eq!(
+                    and_convolution(&a, &b, m),
+                    Ok(naive(&a, &b, m, |i, j| i & j))
+                );
+                assert_eq!(
+                    xor_convolution(&a, &b, m),
+                    Ok(naive(&a, &b, m, |i, j| i ^ j))
+                );
+            }
+        }
+        // OR and AND need no division, so any modulus works
+        let (a, b) = (table(16, 3), table(16, 5));
+        for m in [1, 2, 1 << 63] {
+            assert_eq!(
+                or_convolution(&a, &b, m),
+                Ok(naive(&a, &b, m, |i, j| i | j))
+            );
+            assert_eq!(
+                and_convolution(&a, &b, m),
+                Ok(naive(&a, &b, m, |i, j| i & j))
+            );
+        }
+        assert_eq!(xor_convolution(&a, &b, 1), Ok(vec![0; 16]));
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(feature = "std")]
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Cents in an octave; a frequency ratio `r` spans `1200 · log₂ r` cents.
+pub const OCTAVE_CENTS: f64 = 1200.0;
+
+/// Nearest step of an equal temperament to a just interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TemperedApproximation {
+    /// Steps of `1200 / divisions` cents.
+    pub steps: i64,
+    /// Size of `steps` in cents.
+    pub cents: f64,
+    /// `cents` minus the size of the just interval: positive when the tempered
+    /// interval is wide.
+    pub error_cents: f64,
+}
+
+/// Size of the interval from `a` to `b` in cents, `1200 · log₂(b/a)`:
+/// - `cents_between(1, 2) = 1200`
+/// - `cents_between(2, 3) ≈ 701.955`, the just fifth
+///
+/// Errors with `OutOfRange` unless both ratios are positive.
+#[cfg(feature = "std")]
+pub fn cents_between(a: Rational, b: Rational) -> Result<f64, MathError> {
+    if a.numer() <= 0 || b.numer() <= 0 {
+        return Err(MathError::OutOfRange);
+    }
+    // b/a taken in i128 so the quotient cannot overflow before the logarithm
+    let numer = b.numer() as i128 * a.denom() as i128;
+    let denom = b.denom() as i128 * a.numer() as i128;
+    Ok(OCTAVE_CENTS * (numer as f64 / denom as f64).log2())
+}
+
+/// The frequency ratio with denominator at most `max_den` nearest to `cents`:
+/// - `closest_just_interval(700.0, 4) = 3/2`
+/// - `closest_just_interval(400.0, 4) = 5/4`
+/// - `closest_just_interval(400.0, 100) = 63/50`
+///
+/// The candidates are the last convergent and the best semiconvergent of the continued
+/// fraction of `2^(cents/1200)`; they bracket the target and each is the closest on its
+/// side, so the nearer one is optimal in cents as well. Errors with `OutOfRange` if
+/// `max_den == 0` or `cents` is not finite, and `Overflow` if the ratio exceeds `i64`.
+#[cfg(feature = "std")]
+pub fn closest_just_interval(cents: f64, max_den: u64) -> Result<Rational, MathError> {
+    if max_den == 0 || !cents.is_finite() {
+        return Err(MathError::OutOfRange);
+    }
+    let target = (cents / OCTAVE_CENTS).exp2();
+    if target >= i64::MAX as f64 {
+        return Err(MathError::Overflow);
+    }
+    let max_den = max_den.min(i64::MAX as u64) as u128;
+
+    // convergents h/k of the continued fraction, the previous one in (h0, k0)
+    let (mut h0, mut k0, mut h1, mut k1) = (0u128, 1u128, 1u128, 0u128);
+    let mut y = target;
+    let semiconvergent = loop {
+        let a = y.floor();
+        let next_k = (a as u128).checked_mul(k1).and_then(|x| x.checked_add(k0));
+        match next_k {
+            Some(k) if k <= max_den => {
+                (h0, h1) = (h1, a as u128 * h1 + h0);
+                (k0, k1) = (k1, k);
+            }
+            // the largest t with t·k1 + k0 in range
+            _ => {
+                let t = (max_den - k0) / k1;
+                break Some((t * h1 + h0, t * k1 + k0));
+            }
+        }
+        if y == a {
+            break None;
+        }
+        y = 1.0 / (y - a);
+    };
+
+    let error = |(h, k): (u128, u128)| {
+        let size = OCTAVE_CENTS * (h as f64 / k as f64).log2();
+        (size - cents).abs()
+    };
+    let best = [Some((h1, k1)), semiconvergent]
+        .into_iter()
+        .flatten()
+        .filter(|&(h, _)| h > 0)
+        .min_by(|&x, &y| error(x).total_cmp(&error(y)))
+        .unwrap_or((1, max_den));
+    let narrow = |x: u128| i64::try_from(x).map_err(|_| MathError::Overflow);
+    Rational::new(narrow(best.0)?, narrow(best.1)?)
+}
+
+/// Size in cents of `steps` steps of `divisions`-tone equal temperament.
+///
+/// `MathError::OutOfRange` if `divisions == 0`.
+pub fn tempered_cents(steps: i64, divisions: u32) -> Result<f64, MathError> {
+    if divisions == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(OCTAVE_CENTS * steps as f64 / divisions as f64)
+}
+
+/// Nearest step of `divisions`-tone equal temperament to the just interval `ratio`:
+/// - in 12-tone equal temperament `3/2` is 7 steps, 700 cents, about 1.955 cents flat
+/// - `5/4` is 4 steps, about 13.686 cents sharp
+///
+/// Errors with `OutOfRange` if `divisions == 0` or `ratio` is not positive.
+#[cfg(feature = "std")]
+pub fn tempered_approximation(
+    ratio: Rational,
+    divisions: u32,
+) -> Result<TemperedApproximation, MathError> {
+    let just = cents_between(Rational::ONE, ratio)?;
+    let step = tempered_cents(1, divisions)?;
+    let steps = (just / step).round() as i64;
+    let cents = tempered_cents(steps, divisions)?;
+    Ok(TemperedApproximation {
+        steps,
+        cents,
+        error_cents: cents - just,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    fn q(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_cents_between() {
+        assert_eq!(cents_between(q(1, 1), q(2, 1)), Ok(1200.0));
+        assert_eq!(cents_between(q(2, 1), q(1, 1)), Ok(-1200.0));
+        let fifth = cents_between(q(2, 1), q(3, 1)).unwrap();
+        assert!((fifth - 701.955_000_865_387_4).abs() < 1e-9);
+        // the syntonic comma 81/80 as the gap between a Pythagorean and a just third
+        let comma = cents_between(q(5, 4), q(81, 64)).unwrap();
+        assert!((comma - cents_between(q(80, 1), q(81, 1)).unwrap()).abs() < 1e-9);
+        assert_eq!(cents_between(q(0, 1), q(3, 2)), Err(MathError::OutOfRange));
+        assert_eq!(cents_between(q(1, 1), q(-3, 2)), Err(MathError::OutOfRange));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_closest_just_interval() {
+        assert_eq!(closest_just_interval(700.0, 4), Ok(q(3, 2)));
+        assert_eq!(closest_just_interval(400.0, 4), Ok(q(5, 4)));
+        assert_eq!(closest_just_interval(400.0, 100), Ok(q(63, 50)));
+        assert_eq!(closest_just_interval(1200.0, 1000), Ok(q(2, 1)));
+        assert_eq!(closest_just_interval(-700.0, 3), Ok(q(2, 3)));
+        // round trip through the exact size of a just interval
+        for ratio in [q(7, 4), q(16, 15), q(45, 32), q(15, 8)] {
+            let cents = cents_between(Rational::ONE, ratio).unwrap();
+            assert_eq!(closest_just_interval(cents, 64), Ok(ratio));
+        }
+        // nothing positive is closer than the smallest allowed ratio
+        assert_eq!(closest_just_interval(-20_000.0, 8), Ok(q(1, 8)));
+        assert_eq!(closest_just_interval(0.0, 0), Err(MathError::OutOfRange));
+        assert_eq!(
+            closest_just_interval(f64::NAN, 5),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(closest_just_interval(80_000.0, 5), Err(MathError::Overflow));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_closest_just_interval_is_optimal() {
+        for cents in [1.0, 111.0, 386.3, 498.0, 966.0, 1900.0] {
+            let best = closest_just_interval(cents, 30).unwrap();
+            let error = |r| (cents_between(Rational::ONE, r).unwrap() - cents).abs();
+            for k in 1..=30 {
+                for h in 1..=120 {
+                    assert!(error(best) <= error(q(h, k)) + 1e-9, "{cents} vs {h}/{k}");
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_tempered_approximation() {
+        let fifth = tempered_approximation(q(3, 2), 12).unwrap();
+        assert_eq!((fifth.steps, fifth.cents), (7, 700.0));
+        assert!((fifth.error_cents + 1.955).abs() < 1e-3);
+        let third = tempered_approximation(q(5, 4), 12).unwrap();
+        assert_eq!(third.steps, 4);
+        assert!((third.error_cents - 13.686).abs() < 1e-3);
+        // 31-tone equal temperament is known for its near-just thirds
+        assert!(
+            tempered_approximation(q(5, 4), 31)
+                .unwrap()
+                .error_cents
+                .abs()
+                < 1.0
+        );
+        assert_eq!(
+            tempered_approximation(q(3, 2), 0),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_tempered_cents() {
+        assert_eq!(tempered_cents(7, 12), Ok(700.0));
+        assert_eq!(tempered_cents(-12, 12), Ok(-1200.0));
+        assert_eq!(tempered_cents(1, 0), Err(MathError::OutOfRange));
+    }
+}
@@ -0,0 +1,232 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::factorization::Factorization;
+use crate::algebra::gcd::gcd_u128;
+use crate::algebra::modular::mod_pow;
+use crate::errors::MathError;
+
+/// Largest modulus accepted by the functions listing group elements; each keeps a
+/// membership table of size `n`.
+pub const MAX_MODULUS: u64 = 1 << 20;
+
+/// Multiplicative order of `a` in `(Z/nZ)*`, the least `k >= 1` with `a^k ≡ 1`:
+/// - `element_order(2, 7) = 3`
+/// - `element_order(3, 7) = 6`, so 3 generates `(Z/7Z)*`
+///
+/// Starts from `λ(n)`, which every order divides, and strips prime factors while the
+/// power stays 1, so it costs a factorization of `n` and O(log² n) products. Errors
+/// with `DivisionByZero` if `n == 0` and `NoInverse` if `a` is not a unit.
+pub fn element_order(a: u64, n: u64) -> Result<u64, MathError> {
+    check_unit(a, n)?;
+    let lambda = Factorization::new(n as u128)?.carmichael() as u64;
+    let mut order = lambda;
+    for &(q, _) in Factorization::new(lambda as u128)?.prime_powers() {
+        let q = q as u64;
+        while order.is_multiple_of(q) && mod_pow(a, order / q, n)? == 1 % n {
+            order /= q;
+        }
+    }
+    Ok(order)
+}
+
+/// The cyclic subgroup `⟨a⟩ = {1, a, a², ...}` of `(Z/nZ)*`, sorted ascending:
+/// - `subgroup_generated_by(2, 7) = [1, 2, 4]`
+///
+/// Its size is `element_order(a, n)`. Errors with `DivisionByZero` if `n == 0`,
+/// `NoInverse` if `a` is not a unit and `OutOfRange` past `MAX_MODULUS`.
+pub fn subgroup_generated_by(a: u64, n: u64) -> Result<Vec<u64>, MathError> {
+    check_unit(a, n)?;
+    if n > MAX_MODULUS {
+        return Err(MathError::OutOfRange);
+    }
+    let mut elements = vec![1 % n];
+    let mut power = a % n;
+    while power != 1 % n {
+        elements.push(power);
+        power = power * (a % n) % n;
+    }
+    elements.sort_unstable();
+    Ok(elements)
+}
+
+/// The cosets `aH` of a subgroup `H` of `(Z/nZ)*`, each sorted, ordered by their
+/// smallest element (so `H` itself comes first):
+/// - `cosets(&[1, 6], 7) = [[1, 6], [2, 5], [3, 4]]`
+///
+/// They partition the units into `index` classes of `|H|` elements each (Lagrange).
+/// Elements are reduced mod `n` and duplicates ignored. Errors with `EmptyInput` for no
+/// elements, `NoInverse` if one is not a unit, `OutOfRange` if they are not closed under
+/// multiplication or `n` exceeds `MAX_MODULUS`, and `DivisionByZero` if `n == 0`.
+pub fn cosets(subgroup: &[u64], n: u64) -> Result<Vec<Vec<u64>>, MathError> {
+    let subgroup = validate_subgroup(subgroup, n)?;
+    let mut covered = vec![false; n as usize];
+    let mut classes = Vec::new();
+    for a in 0..n {
+        if covered[a as usize] || gcd_u128(a as u128, n as u128) != 1 {
+            continue;
+        }
+        let mut coset: Vec<u64> = subgroup.iter().map(|&h| a * h % n).collect();
+        coset.sort_unstable();
+        for &x in &coset {
+            covered[x as usize] = true;
+        }
+        classes.push(coset);
+    }
+    Ok(classes)
+}
+
+/// Index `[(Z/nZ)* : H] = φ(n) / |H|`, the number of cosets:
+/// - `subgroup_index(&[1, 2, 4], 7) = 2`
+///
+/// Same validation and errors as `cosets`.
+pub fn subgroup_index(subgroup: &[u64], n: u64) -> Result<u64, MathError> {
+    let subgroup = validate_subgroup(subgroup, n)?;
+    Ok(Factorization::new(n as u128)?.totient() as u64 / subgroup.len() as u64)
+}
+
+/// `DivisionByZero` if `n == 0`, `NoInverse` unless `gcd(a, n) = 1`.
+fn check_unit(a: u64, n: u64) -> Result<(), MathError> {
+    if n == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    if gcd_u128(a as u128, n as u128) != 1 {
+        return Err(MathError::NoInverse);
+    }
+    Ok(())
+}
+
+/// The reduced, sorted, deduplicated elements, checked to form a subgroup.
+///
+/// The group generated by the elements is built one generator at a time: in an abelian
+/// group `⟨S, h⟩` is the union of the cosets `S·h^k` up to the first `h^k` in `S`, so
+/// this stays linear in its size. The set is a subgroup exactly when nothing was added.
+fn validate_subgroup(elements: &[u64], n: u64) -> Result<Vec<u64>, MathError> {
+    if elements.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    for &h in elements {
+        check_unit(h, n)?;
+    }
+    if n > MAX_MODULUS {
+        return Err(MathError::OutOfRange);
+    }
+    let mut set: Vec<u64> = elements.iter().map(|&h| h % n).collect();
+    set.sort_unstable();
+    set.dedup();
+
+    let mut member = vec![false; n as usize];
+    member[(1 % n) as usize] = true;
+    let mut generated = vec![1 % n];
+    for &h in &set {
+        let base = generated.clone();
+        let mut power = h;
+        while !member[power as usize] {
+            for &x in &base {
+                let y = x * power % n;
+                member[y as usize] = true;
+                generated.push(y);
+            }
+            power = power * h % n;
+        }
+    }
+    if generated.len() != set.len() {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn units(n: u64) -> Vec<u64> {
+        (0..n)
+            .filter(|&a| gcd_u128(a as u128, n as u128) == 1)
+            .collect()
+    }
+
+    #[test]
+    fn test_element_order() {
+        assert_eq!(element_order(2, 7), Ok(3));
+        assert_eq!(element_order(3, 7), Ok(6));
+        assert_eq!(element_order(1, 1), Ok(1));
+        assert_eq!(element_order(0, 1), Ok(1));
+        // 5 is a primitive root of the prime 10^9 + 7
+        assert_eq!(element_order(5, 1_000_000_007), Ok(1_000_000_006));
+        assert_eq!(element_order(4, 6), Err(MathError::NoInverse));
+        assert_eq!(element_order(3, 0), Err(MathError::DivisionByZero));
+        for n in 1..300u64 {
+            for a in units(n) {
+                let brute = (1..=n).find(|&k| mod_pow(a, k, n) == Ok(1 % n)).unwrap();
+                assert_eq!(element_order(a, n), Ok(brute), "ord_{n}({a})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_subgroup_generated_by() {
+        assert_eq!(subgroup_generated_by(2, 7), Ok(vec![1, 2, 4]));
+        assert_eq!(subgroup_generated_by(3, 7), Ok(units(7)));
+        assert_eq!(subgroup_generated_by(9, 7), Ok(vec![1, 2, 4]));
+        assert_eq!(subgroup_generated_by(5, 1), Ok(vec![0]));
+        assert_eq!(subgroup_generated_by(2, 8), Err(MathError::NoInverse));
+        assert_eq!(
+            subgroup_generated_by(3, MAX_MODULUS + 1),
+            Err(MathError::OutOfRange)
+        );
+        for n in 1..200u64 {
+            for a in units(n) {
+                let h = subgroup_generated_by(a, n).unwrap();
+                assert_eq!(h.len() as u64, element_order(a, n).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_cosets() {
+        assert_eq!(
+            cosets(&[1, 6], 7),
+            Ok(vec![vec![1, 6], vec![2, 5], vec![3, 4]])
+        );
+        assert_eq!(subgroup_index(&[1, 2, 4], 7), Ok(2));
+        // the trivial and the whole group
+        assert_eq!(cosets(&[1], 5).map(|c| c.len()), Ok(4));
+        assert_eq!(cosets(&units(15), 15), Ok(vec![units(15)]));
+        // duplicates and unreduced elements are accepted
+        assert_eq!(subgroup_index(&[8, 1, 1, 6], 7), Ok(3));
+        assert_eq!(cosets(&[1, 2], 7), Err(MathError::OutOfRange));
+        assert_eq!(cosets(&[1, 3], 9), Err(MathError::NoInverse));
+        assert_eq!(cosets(&[], 9), Err(MathError::EmptyInput));
+        assert_eq!(cosets(&[1], 0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_lagrange() {
+        for n in 1..120u64 {
+            let all = units(n);
+            for a in &all {
+                let h = subgroup_generated_by(*a, n).unwrap();
+                let classes = cosets(&h, n).unwrap();
+                assert_eq!(classes[0], h);
+                assert_eq!(classes.len() as u64, subgroup_index(&h, n).unwrap());
+                assert!(classes.iter().all(|c| c.len() == h.len()));
+                let mut union: Vec<u64> = classes.concat();
+                union.sort_unstable();
+                assert_eq!(union, all, "n = {n}, a = {a}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_cyclic_subgroup() {
+        // ⟨5, 7⟩ = {1, 5, 7, 11} in (Z/24Z)*, which has exponent 2
+        assert_eq!(
+            cosets(&[1, 5, 7, 11], 24),
+            Ok(vec![vec![1, 5, 7, 11], vec![13, 17, 19, 23]])
+        );
+        assert_eq!(subgroup_index(&[11, 7, 5, 1], 24), Ok(2));
+        assert_eq!(cosets(&[1, 5, 7], 24), Err(MathError::OutOfRange));
+        assert_eq!(cosets(&[5, 7, 11], 24), Err(MathError::OutOfRange));
+    }
+}
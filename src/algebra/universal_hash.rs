@@ -0,0 +1,159 @@
+use crate::algebra::prime_nbr::{is_prime_u64, next_prime, uniform_below};
+use crate::errors::MathError;
+
+/// A member `h(x) = ((a·x + b) mod p) mod m` of the Carter-Wegman family, with
+/// `1 <= a < p` and `0 <= b < p` drawn at random.
+///
+/// For keys `x ≠ y` below `p`, a random member collides with probability at most
+/// `1/m`. Proof: since `p` is prime, `(a, b) ↦ (ax + b, ay + b) mod p` is a bijection
+/// from the `p(p-1)` choices onto the pairs `(r, s)` with `r ≠ s`, so `(r, s)` is
+/// uniform among them. A collision needs `r ≡ s (mod m)`, and for each `r` at most
+/// `⌈p/m⌉ - 1 <= (p-1)/m` values `s ≠ r` qualify, giving at most `p(p-1)/m` colliding
+/// choices out of `p(p-1)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniversalHash {
+    p: u64,
+    m: u64,
+    a: u64,
+    b: u64,
+}
+
+impl UniversalHash {
+    /// Random member for the prime `p` and `m` buckets; `rng` supplies uniform `u64`s.
+    ///
+    /// `a` and `b` are drawn by rejection sampling, so they are exactly uniform as the
+    /// collision bound assumes. Errors with `OutOfRange` unless `p` is prime and
+    /// `1 <= m <= p`.
+    pub fn new(p: u64, m: u64, mut rng: impl FnMut() -> u64) -> Result<Self, MathError> {
+        if !is_prime_u64(p) || m == 0 || m > p {
+            return Err(MathError::OutOfRange);
+        }
+        Ok(UniversalHash {
+            p,
+            m,
+            a: 1 + uniform_below(p - 1, &mut rng),
+            b: uniform_below(p, &mut rng),
+        })
+    }
+
+    /// Random member for keys below `universe`, with `p` the smallest prime at least
+    /// `universe` and `m`.
+    ///
+    /// Errors with `OutOfRange` if `m == 0` and `Overflow` if no such 64-bit prime exists.
+    pub fn for_universe(
+        universe: u64,
+        m: u64,
+        rng: impl FnMut() -> u64,
+    ) -> Result<Self, MathError> {
+        if m == 0 {
+            return Err(MathError::OutOfRange);
+        }
+        UniversalHash::new(next_prime(universe.max(m))?, m, rng)
+    }
+
+    pub fn prime(&self) -> u64 {
+        self.p
+    }
+
+    /// Number of buckets `m`.
+    pub fn buckets(&self) -> u64 {
+        self.m
+    }
+
+    /// The chosen `(a, b)`.
+    pub fn coefficients(&self) -> (u64, u64) {
+        (self.a, self.b)
+    }
+
+    /// Bucket of `x`, in `0..m`.
+    ///
+    /// Keys are reduced mod `p` first, so the collision bound only holds for keys below `p`.
+    pub fn hash(&self, x: u64) -> u64 {
+        let p = self.p as u128;
+        ((self.a as u128 * (x as u128 % p) + self.b as u128) % p) as u64 % self.m
+    }
+
+    /// The guaranteed bound `1/m` on the collision probability of two distinct keys.
+    pub fn collision_bound(&self) -> f64 {
+        1.0 / self.m as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::prime_nbr::splitmix64;
+
+    fn rng(seed: u64) -> impl FnMut() -> u64 {
+        let mut state = seed;
+        move || splitmix64(&mut state)
+    }
+
+    #[test]
+    fn test_hash_values() {
+        let h = UniversalHash::new(101, 10, rng(7)).unwrap();
+        let (a, b) = h.coefficients();
+        assert!((1..101).contains(&a) && b < 101);
+        for x in 0..500 {
+            assert_eq!(h.hash(x), ((a * (x % 101) + b) % 101) % 10);
+        }
+        assert_eq!(h.collision_bound(), 0.1);
+
+        // full-width keys and primes do not overflow
+        let h = UniversalHash::new(u64::MAX - 58, 1 << 20, rng(1)).unwrap();
+        assert!(h.hash(u64::MAX) < 1 << 20);
+    }
+
+    #[test]
+    fn test_coefficients_are_rejection_sampled() {
+        // 2^64 ≡ 16 (mod 2^61 - 2) and 2^64 ≡ 8 (mod 2^61 - 1): u64::MAX lies in the
+        // incomplete last copy of both ranges and is drawn again
+        let p = (1 << 61) - 1;
+        let mut draws = [u64::MAX, 41, u64::MAX, 42].into_iter();
+        let h = UniversalHash::new(p, 8, || draws.next().unwrap()).unwrap();
+        assert_eq!(h.coefficients(), (42, 42));
+    }
+
+    #[test]
+    fn test_collision_bound_over_whole_family() {
+        // count colliding members exactly for every pair of keys
+        let (p, m) = (31u64, 4u64);
+        for x in 0..p {
+            for y in x + 1..p {
+                let collisions = (1..p)
+                    .flat_map(|a| (0..p).map(move |b| (a, b)))
+                    .filter(|&(a, b)| (a * x + b) % p % m == (a * y + b) % p % m)
+                    .count() as u64;
+                assert!(collisions * m <= p * (p - 1), "{x}, {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_for_universe() {
+        let h = UniversalHash::for_universe(1000, 64, rng(3)).unwrap();
+        assert_eq!((h.prime(), h.buckets()), (1009, 64));
+        // the prime must also cover the bucket count
+        let h = UniversalHash::for_universe(10, 100, rng(3)).unwrap();
+        assert_eq!(h.prime(), 101);
+        assert_eq!(
+            UniversalHash::for_universe(u64::MAX, 2, rng(3)),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert_eq!(
+            UniversalHash::new(100, 10, rng(0)),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(UniversalHash::new(7, 0, rng(0)), Err(MathError::OutOfRange));
+        assert_eq!(UniversalHash::new(7, 8, rng(0)), Err(MathError::OutOfRange));
+        assert_eq!(UniversalHash::new(1, 1, rng(0)), Err(MathError::OutOfRange));
+        assert_eq!(
+            UniversalHash::for_universe(10, 0, rng(0)),
+            Err(MathError::OutOfRange)
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use std::vec;
+use std::vec::Vec;
+
+use crate::algebra::gcd::{gcd, gcd_abs, gcd_secure};
+use crate::algebra::lcm::lcm;
+use crate::errors::MathError;
+
+/// Timing of one function over a fixed workload.
+///
+/// `calls = pairs.len() * iterations`; `errors` counts calls that returned `Err`
+/// (e.g. a zero divisor in the input pairs).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Throughput {
+    pub name: &'static str,
+    pub calls: usize,
+    pub errors: usize,
+    pub elapsed: Duration,
+}
+
+impl Throughput {
+    /// Average time per call in nanoseconds.
+    pub fn nanos_per_call(&self) -> f64 {
+        if self.calls == 0 {
+            return 0.0;
+        }
+        self.elapsed.as_nanos() as f64 / self.calls as f64
+    }
+
+    /// Calls per second (`f64::INFINITY` if the clock did not advance).
+    pub fn calls_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            return f64::INFINITY;
+        }
+        self.calls as f64 / secs
+    }
+}
+
+/// Runs `f` over every pair, `iterations` times, and reports the elapsed time.
+pub fn measure<F>(
+    name: &'static str,
+    mut f: F,
+    pairs: &[(i64, i64)],
+    iterations: usize,
+) -> Throughput
+where
+    F: FnMut(i64, i64) -> Result<i64, MathError>,
+{
+    let mut errors = 0;
+    let start = Instant::now();
+    for _ in 0..iterations {
+        for &(a, b) in pairs {
+            if black_box(f(black_box(a), black_box(b))).is_err() {
+                errors += 1;
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+
+    Throughput {
+        name,
+        calls: pairs.len() * iterations,
+        errors,
+        elapsed,
+    }
+}
+
+/// Measures every gcd variant on the same workload: `gcd`, `gcd_abs`, `gcd_secure`.
+pub fn measure_gcd_throughput(pairs: &[(i64, i64)], iterations: usize) -> Vec<Throughput> {
+    vec![
+        measure("gcd", gcd, pairs, iterations),
+        measure("gcd_abs", gcd_abs, pairs, iterations),
+        measure("gcd_secure", gcd_secure, pairs, iterations),
+    ]
+}
+
+/// Measures `lcm` on the given workload.
+pub fn measure_lcm_throughput(pairs: &[(i64, i64)], iterations: usize) -> Throughput {
+    measure("lcm", lcm, pairs, iterations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_counts_calls() {
+        let pairs = [(48, 88), (17, 13), (100, 30)];
+        let result = measure("gcd", gcd, &pairs, 4);
+        assert_eq!(result.name, "gcd");
+        assert_eq!(result.calls, 12);
+        assert_eq!(result.errors, 0);
+    }
+
+    #[test]
+    fn test_measure_counts_errors() {
+        let pairs = [(48, 0), (17, 13)];
+        let result = measure("gcd", gcd, &pairs, 3);
+        assert_eq!(result.calls, 6);
+        assert_eq!(result.errors, 3);
+    }
+
+    #[test]
+    fn test_measure_gcd_throughput_variants() {
+        let pairs = [(48, 88), (1_234_567, 2_345_678)];
+        let results = measure_gcd_throughput(&pairs, 2);
+        let names: Vec<_> = results.iter().map(|t| t.name).collect();
+        assert_eq!(names, ["gcd", "gcd_abs", "gcd_secure"]);
+        assert!(results.iter().all(|t| t.calls == 4 && t.errors == 0));
+    }
+
+    #[test]
+    fn test_measure_lcm_throughput() {
+        let result = measure_lcm_throughput(&[(12, 18), (-1, 2)], 1);
+        assert_eq!(result.calls, 2);
+        assert_eq!(result.errors, 1);
+    }
+
+    #[test]
+    fn test_empty_workload() {
+        let result = measure_lcm_throughput(&[], 10);
+        assert_eq!(result.calls, 0);
+        assert_eq!(result.nanos_per_call(), 0.0);
+    }
+}
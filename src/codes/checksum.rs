@@ -0,0 +1,567 @@
+use alloc::vec::Vec;
+
+use crate::algebra::prime_nbr::is_prime_u64;
+use crate::combinatorics::latin::is_latin_square;
+use crate::errors::MathError;
+
+/// Luhn check digit to append to `payload`, as used by payment cards and IMEIs:
+/// - `luhn_check_digit("7992739871") = '3'`
+///
+/// Doubling every second digit from the right, starting with the last digit of
+/// `payload`, catches every single-digit error and most swaps of adjacent digits.
+/// Spaces and hyphens are ignored. `EmptyInput` without digits, `OutOfRange` on any
+/// other character.
+pub fn luhn_check_digit(payload: &str) -> Result<char, MathError> {
+    let digits = parse(payload, false)?;
+    Ok(digit_char((10 - luhn_sum(&digits, true) % 10) % 10))
+}
+
+/// Whether `number`, check digit last, passes the Luhn test:
+/// - `is_valid_luhn("4539 1488 0343 6467") = true`
+///
+/// Spaces and hyphens are ignored; anything else, or fewer than two digits, fails.
+pub fn is_valid_luhn(number: &str) -> bool {
+    parse(number, false)
+        .is_ok_and(|digits| digits.len() >= 2 && luhn_sum(&digits, false).is_multiple_of(10))
+}
+
+/// ISBN-10 check character for the first 9 digits, `'X'` standing for 10:
+/// - `isbn10_check_digit("0-306-40615") = '2'`
+///
+/// Chosen so that `Σ (10 - i)·dᵢ ≡ 0 (mod 11)` over all ten positions; 11 being prime,
+/// every single-digit error and every transposition changes the sum. Spaces and
+/// hyphens are ignored. `OutOfRange` unless `payload` has exactly 9 digits and nothing
+/// else, `EmptyInput` if it has no digits at all.
+pub fn isbn10_check_digit(payload: &str) -> Result<char, MathError> {
+    let digits = parse(payload, false)?;
+    if digits.len() != 9 {
+        return Err(MathError::OutOfRange);
+    }
+    match (11 - isbn10_sum(&digits) % 11) % 11 {
+        10 => Ok('X'),
+        d => Ok(digit_char(d)),
+    }
+}
+
+/// Whether `isbn` is a valid ISBN-10, with `'X'` (or `'x'`) allowed as the last symbol:
+/// - `is_valid_isbn10("0-306-40615-2") = true`
+pub fn is_valid_isbn10(isbn: &str) -> bool {
+    parse(isbn, true).is_ok_and(|digits| {
+        digits.len() == 10 && !digits[..9].contains(&10) && isbn10_sum(&digits).is_multiple_of(11)
+    })
+}
+
+/// ISBN-13 (and EAN-13) check digit for the first 12 digits, weights alternating 1 and 3:
+/// - `isbn13_check_digit("978-0-306-40615") = '7'`
+///
+/// Spaces and hyphens are ignored. `OutOfRange` unless `payload` has exactly 12 digits
+/// and nothing else, `EmptyInput` if it has no digits at all.
+pub fn isbn13_check_digit(payload: &str) -> Result<char, MathError> {
+    let digits = parse(payload, false)?;
+    if digits.len() != 12 {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(digit_char((10 - isbn13_sum(&digits) % 10) % 10))
+}
+
+/// Whether `isbn` is a valid ISBN-13:
+/// - `is_valid_isbn13("978-0-306-40615-7") = true`
+pub fn is_valid_isbn13(isbn: &str) -> bool {
+    parse(isbn, false)
+        .is_ok_and(|digits| digits.len() == 13 && isbn13_sum(&digits).is_multiple_of(10))
+}
+
+/// The two IBAN check digits, `2..=98`, for a country code and a BBAN (the domestic
+/// account number):
+/// - `iban_check_digits("GB", "WEST12345698765432") = 82`, so `GB82WEST12345698765432`
+///
+/// ISO 7064 mod 97-10: with the check digits set to `00` and moved to the end along with
+/// the country, letters read as `A = 10, …, Z = 35` and the whole as one decimal number
+/// `N`, the check digits are `98 - N mod 97`. `N` is reduced a symbol at a time, so its
+/// length does not matter. Letters are case-insensitive and spaces ignored. `OutOfRange`
+/// unless `country` is two letters and the BBAN alphanumeric of at most 30 symbols.
+pub fn iban_check_digits(country: &str, bban: &str) -> Result<u8, MathError> {
+    let country = country.as_bytes();
+    if country.len() != 2 || !country.iter().all(u8::is_ascii_alphabetic) {
+        return Err(MathError::OutOfRange);
+    }
+    let bban: Vec<u8> = bban.bytes().filter(|&b| b != b' ').collect();
+    if bban.is_empty() || bban.len() > 30 || !bban.iter().all(u8::is_ascii_alphanumeric) {
+        return Err(MathError::OutOfRange);
+    }
+    let rearranged = bban.iter().chain(country).chain(b"00");
+    Ok(98 - mod97(rearranged) as u8)
+}
+
+/// Whether `iban` passes the mod 97 check, that is whether its rearranged value is
+/// `1 (mod 97)`:
+/// - `is_valid_iban("GB82 WEST 1234 5698 7654 32") = true`
+///
+/// Checks the format, two letters, two digits and up to 30 alphanumerics, but not the
+/// length each country prescribes. Letters are case-insensitive and spaces ignored.
+pub fn is_valid_iban(iban: &str) -> bool {
+    let iban: Vec<u8> = iban.bytes().filter(|&b| b != b' ').collect();
+    if !(5..=34).contains(&iban.len())
+        || !iban[..2].iter().all(u8::is_ascii_alphabetic)
+        || !iban[2..4].iter().all(u8::is_ascii_digit)
+        || !iban[4..].iter().all(u8::is_ascii_alphanumeric)
+    {
+        return false;
+    }
+    mod97(iban[4..].iter().chain(&iban[..4])) == 1
+}
+
+/// Verhoeff check digit to append to `payload`:
+/// - `verhoeff_check_digit("236") = '3'`
+///
+/// Works in the dihedral group D5 of the pentagon's symmetries, non-commutative so
+/// that `a·b ≠ b·a` catches swapped neighbours, with position `i` from the right
+/// (the check digit being 0) first sent through `σ^i`. Rotations `r^a` are the digits
+/// `a < 5` and reflections `r^a·s` the digits `a + 5`; the product comes from
+/// `s·r = r⁻¹·s` instead of a table. `σ` is Verhoeff's permutation
+/// `(0 1 5 8 9 4 2 7)(3 6)`, the one constant of the scheme. Catches every
+/// single-digit error and every adjacent transposition. Spaces and hyphens are
+/// ignored. `EmptyInput` without digits, `OutOfRange` on any other character.
+pub fn verhoeff_check_digit(payload: &str) -> Result<char, MathError> {
+    let digits = parse(payload, false)?;
+    Ok(digit_char(d5_inverse(verhoeff_product(&digits, 1))))
+}
+
+/// Whether `number`, check digit last, passes the Verhoeff test:
+/// - `is_valid_verhoeff("2363") = true`
+///
+/// Spaces and hyphens are ignored; anything else, or fewer than two digits, fails.
+pub fn is_valid_verhoeff(number: &str) -> bool {
+    parse(number, false).is_ok_and(|digits| digits.len() >= 2 && verhoeff_product(&digits, 0) == 0)
+}
+
+/// Operation table of a weakly totally anti-symmetric quasigroup on `0..order`, what
+/// Damm's algorithm folds a word through:
+/// - `x ∗ x = 0`, so a valid word ends on the interim value 0
+/// - `(c ∗ x) ∗ y = (c ∗ y) ∗ x` only when `x = y`, so swapped neighbours are caught,
+///   the check symbol included, since the fold starts from `c = 0`
+///
+/// Being a Latin square makes every single-symbol error show too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DammQuasigroup {
+    order: u64,
+    table: Vec<u64>,
+}
+
+impl DammQuasigroup {
+    /// The order-10 quasigroup of Damm's thesis, the one decimal check digits use.
+    ///
+    /// 10 is not a prime power, so there is no field to build it from as in
+    /// `over_prime_field`; the published table is taken as is, and `from_table`
+    /// accepts it.
+    pub fn decimal() -> Self {
+        DammQuasigroup {
+            order: 10,
+            table: DAMM_DECIMAL.iter().flatten().map(|&x| x as u64).collect(),
+        }
+    }
+
+    /// The quasigroup `x ∗ y = 2(x - y)` over GF(p) for an odd prime `p`:
+    /// - `over_prime_field(3)` has rows `[0, 1, 2], [2, 0, 1], [1, 2, 0]`
+    ///
+    /// `x ∗ x = 0` by construction, and `(c ∗ x) ∗ y - (c ∗ y) ∗ x = 2(y - x)` is
+    /// nonzero unless `x = y` when `p` is odd; any multiplier other than 0 and 1 would
+    /// do in place of 2. `MathError::OutOfRange` unless `p` is an odd prime.
+    pub fn over_prime_field(p: u64) -> Result<Self, MathError> {
+        if p < 3 || !is_prime_u64(p) {
+            return Err(MathError::OutOfRange);
+        }
+        let table = (0..p)
+            .flat_map(|x| (0..p).map(move |y| 2 * ((x + p - y) % p) % p))
+            .collect();
+        Ok(DammQuasigroup { order: p, table })
+    }
+
+    /// Checks a square table against the two properties above and the Latin property. `MathError::OutOfRange` if any fails, `EmptyInput` for an empty table.
+    pub fn from_table(rows: &[Vec<u64>]) -> Result<Self, MathError> {
+        let n = rows.len();
+        if n == 0 {
+            return Err(MathError::EmptyInput);
+        }
+        if !is_latin_square(rows) {
+            return Err(MathError::OutOfRange);
+        }
+        let q = DammQuasigroup {
+            order: n as u64,
+            table: rows.concat(),
+        };
+        let symbols = 0..q.order;
+        let anti_symmetric = symbols.clone().all(|x| {
+            q.op(x, x) == 0
+                && symbols.clone().filter(|&y| y != x).all(|y| {
+                    symbols
+                        .clone()
+                        .all(|c| q.op(q.op(c, x), y) != q.op(q.op(c, y), x))
+                })
+        });
+        if !anti_symmetric {
+            return Err(MathError::OutOfRange);
+        }
+        Ok(q)
+    }
+
+    /// Number of symbols.
+    pub fn order(&self) -> u64 {
+        self.order
+    }
+
+    /// `x ∗ y`, both below `order`.
+    pub fn op(&self, x: u64, y: u64) -> u64 {
+        self.table[(x * self.order + y) as usize]
+    }
+
+    /// The check symbol `c` with `payload` followed by `c` folding to 0, which is the
+    /// fold of `payload` itself since `x ∗ x = 0`. `MathError::OutOfRange` if a symbol
+    /// is not below `order`.
+    pub fn check_symbol(&self, payload: &[u64]) -> Result<u64, MathError> {
+        if payload.iter().any(|&x| x >= self.order) {
+            return Err(MathError::OutOfRange);
+        }
+        Ok(payload.iter().fold(0, |interim, &x| self.op(interim, x)))
+    }
+
+    /// Whether `word` folds to 0; `false` if a symbol is not below `order`.
+    pub fn is_valid(&self, word: &[u64]) -> bool {
+        self.check_symbol(word) == Ok(0)
+    }
+}
+
+/// Damm check digit to append to `payload`, with `DammQuasigroup::decimal()`:
+/// - `damm_check_digit("572") = '4'`
+///
+/// Catches every single-digit error and every adjacent transposition, check digit
+/// included. Spaces and hyphens are ignored. `EmptyInput` without digits,
+/// `OutOfRange` on any other character.
+pub fn damm_check_digit(payload: &str) -> Result<char, MathError> {
+    let digits = parse(payload, false)?;
+    Ok(digit_char(DammQuasigroup::decimal().check_symbol(&digits)?))
+}
+
+/// Whether `number`, check digit last, passes the decimal Damm test:
+/// - `is_valid_damm("5724") = true`
+///
+/// Spaces and hyphens are ignored; anything else, or fewer than two digits, fails.
+pub fn is_valid_damm(number: &str) -> bool {
+    parse(number, false)
+        .is_ok_and(|digits| digits.len() >= 2 && DammQuasigroup::decimal().is_valid(&digits))
+}
+
+/// Digit values of `s`, skipping spaces and hyphens, with `X` read as 10 when `allow_x`.
+fn parse(s: &str, allow_x: bool) -> Result<Vec<u64>, MathError> {
+    let digits: Vec<u64> = s
+        .chars()
+        .filter(|&c| c != ' ' && c != '-')
+        .map(|c| match c {
+            'X' | 'x' if allow_x => Ok(10),
+            _ => c.to_digit(10).map(u64::from).ok_or(MathError::OutOfRange),
+        })
+        .collect::<Result<_, _>>()?;
+    if digits.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    Ok(digits)
+}
+
+fn digit_char(d: u64) -> char {
+    char::from(b'0' + d as u8)
+}
+
+/// Luhn sum, doubling from the last digit when `double_last`, else from the one before.
+fn luhn_sum(digits: &[u64], double_last: bool) -> u64 {
+    digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if (i % 2 == 0) == double_last {
+                let doubled = 2 * d;
+                doubled / 10 + doubled % 10
+            } else {
+                d
+            }
+        })
+        .sum()
+}
+
+/// `Σ (10 - i)·dᵢ` for `i` from 0.
+fn isbn10_sum(digits: &[u64]) -> u64 {
+    digits.iter().zip((1..=10).rev()).map(|(&d, w)| w * d).sum()
+}
+
+fn isbn13_sum(digits: &[u64]) -> u64 {
+    digits
+        .iter()
+        .zip([1, 3].iter().cycle())
+        .map(|(&d, &w)| w * d)
+        .sum()
+}
+
+/// The alphanumeric string as a decimal number modulo 97, letters `A..Z` as `10..35`.
+fn mod97<'a>(symbols: impl Iterator<Item = &'a u8>) -> u64 {
+    symbols.fold(0, |rem, &b| {
+        let b = b.to_ascii_uppercase();
+        if b.is_ascii_digit() {
+            (rem * 10 + (b - b'0') as u64) % 97
+        } else {
+            (rem * 100 + (b - b'A' + 10) as u64) % 97
+        }
+    })
+}
+
+/// Verhoeff's `σ`, sending position `i` through `σ^i`.
+const VERHOEFF_SIGMA: [u64; 10] = [1, 5, 7, 6, 2, 8, 3, 0, 9, 4];
+
+/// Damm's thesis, p. 111: row `x`, column `y` holds `x ∗ y`.
+const DAMM_DECIMAL: [[u8; 10]; 10] = [
+    [0, 3, 1, 7, 5, 9, 8, 6, 4, 2],
+    [7, 0, 9, 2, 1, 5, 4, 8, 6, 3],
+    [4, 2, 0, 6, 8, 7, 1, 3, 5, 9],
+    [1, 7, 5, 0, 9, 8, 3, 4, 2, 6],
+    [6, 1, 2, 3, 0, 4, 5, 9, 7, 8],
+    [3, 6, 7, 4, 2, 0, 9, 5, 8, 1],
+    [5, 8, 6, 9, 7, 2, 0, 1, 3, 4],
+    [8, 9, 4, 5, 3, 6, 2, 0, 1, 7],
+    [9, 4, 3, 8, 6, 1, 7, 2, 0, 5],
+    [2, 5, 8, 1, 4, 3, 6, 7, 9, 0],
+];
+
+/// `r^a·s^e · r^b·s^f = r^(a ± b)·s^(e + f)` in D5, with `-` when `e = 1`.
+fn d5_mul(x: u64, y: u64) -> u64 {
+    let (a, e) = (x % 5, x / 5);
+    let (b, f) = (y % 5, y / 5);
+    let rotation = if e == 0 { (a + b) % 5 } else { (a + 5 - b) % 5 };
+    rotation + 5 * ((e + f) % 2)
+}
+
+/// Rotations invert to `r^-a`, reflections are their own inverses.
+fn d5_inverse(x: u64) -> u64 {
+    if x < 5 { (5 - x) % 5 } else { x }
+}
+
+/// `Π σ^(i + first)(dᵢ)` over the digits from the right.
+fn verhoeff_product(digits: &[u64], first: usize) -> u64 {
+    digits.iter().rev().enumerate().fold(0, |acc, (i, &d)| {
+        let permuted = (0..(i + first) % 8).fold(d, |x, _| VERHOEFF_SIGMA[x as usize]);
+        d5_mul(acc, permuted)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::prime_nbr::splitmix64;
+    use alloc::vec;
+
+    #[test]
+    fn test_luhn() {
+        assert_eq!(luhn_check_digit("7992739871"), Ok('3'));
+        assert_eq!(luhn_check_digit("0"), Ok('0'));
+        assert_eq!(luhn_check_digit("453914880343646"), Ok('7'));
+        assert!(is_valid_luhn("79927398713"));
+        assert!(is_valid_luhn("4539 1488 0343 6467"));
+        assert!(is_valid_luhn("490154203237518")); // an IMEI
+        assert!(!is_valid_luhn("79927398710"));
+        assert!(!is_valid_luhn("0"));
+        assert!(!is_valid_luhn("7992739871a"));
+        assert!(!is_valid_luhn(""));
+        assert_eq!(luhn_check_digit(" - "), Err(MathError::EmptyInput));
+        assert_eq!(luhn_check_digit("12a"), Err(MathError::OutOfRange));
+
+        // every single-digit error is caught
+        let valid = "79927398713";
+        for i in 0..valid.len() {
+            for d in b'0'..=b'9' {
+                let mut bytes = valid.as_bytes().to_vec();
+                if bytes[i] != d {
+                    bytes[i] = d;
+                    assert!(!is_valid_luhn(core::str::from_utf8(&bytes).unwrap()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_isbn10() {
+        assert_eq!(isbn10_check_digit("0-306-40615"), Ok('2'));
+        assert_eq!(isbn10_check_digit("080442957"), Ok('X'));
+        assert!(is_valid_isbn10("0-306-40615-2"));
+        assert!(is_valid_isbn10("0 8044 2957 X"));
+        assert!(is_valid_isbn10("080442957x"));
+        assert!(!is_valid_isbn10("0-306-40615-3"));
+        assert!(!is_valid_isbn10("0-306-46015-2")); // transposition
+        assert!(!is_valid_isbn10("X80442957X"));
+        assert!(!is_valid_isbn10("030640615"));
+        assert_eq!(isbn10_check_digit("0-306-4061"), Err(MathError::OutOfRange));
+        assert_eq!(isbn10_check_digit("03064061X"), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_isbn13() {
+        assert_eq!(isbn13_check_digit("978-0-306-40615"), Ok('7'));
+        assert_eq!(isbn13_check_digit("978316148410"), Ok('0'));
+        assert!(is_valid_isbn13("978-0-306-40615-7"));
+        assert!(is_valid_isbn13("9783161484100"));
+        assert!(!is_valid_isbn13("978-0-306-40615-8"));
+        assert!(!is_valid_isbn13("978-0-306-40615"));
+        assert_eq!(
+            isbn13_check_digit("97803064061"),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_iban() {
+        assert_eq!(iban_check_digits("GB", "WEST12345698765432"), Ok(82));
+        assert_eq!(iban_check_digits("DE", "370400440532013000"), Ok(89));
+        assert_eq!(
+            iban_check_digits("fr", "2004 1010 0505 0001 3M02 606"),
+            Ok(14)
+        );
+        assert!(is_valid_iban("GB82 WEST 1234 5698 7654 32"));
+        assert!(is_valid_iban("DE89370400440532013000"));
+        assert!(is_valid_iban("FR1420041010050500013M02606"));
+        assert!(is_valid_iban("gb82west12345698765432"));
+        assert!(!is_valid_iban("GB83WEST12345698765432"));
+        assert!(!is_valid_iban("GB82WEST12345698765423"));
+        assert!(!is_valid_iban("GB82WEST1234569876543!"));
+        assert!(!is_valid_iban("1B82WEST12345698765432"));
+        assert!(!is_valid_iban("GB8"));
+        assert!(!is_valid_iban(""));
+        assert_eq!(iban_check_digits("G", "123"), Err(MathError::OutOfRange));
+        assert_eq!(iban_check_digits("GB", ""), Err(MathError::OutOfRange));
+        assert_eq!(iban_check_digits("GB", "12-34"), Err(MathError::OutOfRange));
+
+        // generated check digits always validate
+        for bban in [
+            "0",
+            "ZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZ",
+            "000000000000000000000000000000",
+        ] {
+            let check = iban_check_digits("NL", bban).unwrap();
+            assert!((2..=98).contains(&check));
+            assert!(is_valid_iban(&alloc::format!("NL{check:02}{bban}")));
+        }
+    }
+
+    #[test]
+    fn test_d5() {
+        // associative, identity 0, inverses, and not commutative
+        for x in 0..10 {
+            assert_eq!(d5_mul(x, 0), x);
+            assert_eq!(d5_mul(x, d5_inverse(x)), 0);
+            for y in 0..10 {
+                for z in 0..10 {
+                    assert_eq!(d5_mul(d5_mul(x, y), z), d5_mul(x, d5_mul(y, z)));
+                }
+            }
+        }
+        assert_ne!(d5_mul(1, 5), d5_mul(5, 1));
+        // the table in Verhoeff's paper, row 6
+        let row: Vec<u64> = (0..10).map(|y| d5_mul(6, y)).collect();
+        assert_eq!(row, [6, 5, 9, 8, 7, 1, 0, 4, 3, 2]);
+    }
+
+    #[test]
+    fn test_verhoeff() {
+        assert_eq!(verhoeff_check_digit("236"), Ok('3'));
+        assert_eq!(verhoeff_check_digit("12345"), Ok('1'));
+        assert_eq!(verhoeff_check_digit("142857"), Ok('0'));
+        assert!(is_valid_verhoeff("2363"));
+        assert!(is_valid_verhoeff("123451"));
+        assert!(is_valid_verhoeff("1428570"));
+        assert!(!is_valid_verhoeff("2364"));
+        assert!(!is_valid_verhoeff("3263"));
+        assert!(!is_valid_verhoeff("0"));
+        assert_eq!(verhoeff_check_digit(""), Err(MathError::EmptyInput));
+        assert_catches_errors(verhoeff_check_digit, is_valid_verhoeff);
+    }
+
+    #[test]
+    fn test_damm() {
+        assert_eq!(damm_check_digit("572"), Ok('4'));
+        assert_eq!(damm_check_digit("0"), Ok('0'));
+        assert!(is_valid_damm("5724"));
+        assert!(is_valid_damm("112946"));
+        assert!(!is_valid_damm("5274"));
+        assert!(!is_valid_damm("5742"));
+        assert_eq!(damm_check_digit("57a"), Err(MathError::OutOfRange));
+        assert_catches_errors(damm_check_digit, is_valid_damm);
+
+        let rows: Vec<Vec<u64>> = DAMM_DECIMAL
+            .iter()
+            .map(|row| row.iter().map(|&x| x as u64).collect())
+            .collect();
+        assert_eq!(
+            DammQuasigroup::from_table(&rows),
+            Ok(DammQuasigroup::decimal())
+        );
+    }
+
+    #[test]
+    fn test_damm_quasigroup() {
+        let q = DammQuasigroup::over_prime_field(3).unwrap();
+        assert_eq!((q.op(1, 0), q.op(1, 2), q.op(2, 0)), (2, 1, 1));
+        for p in [3, 5, 7, 11, 13] {
+            let q = DammQuasigroup::over_prime_field(p).unwrap();
+            let rows: Vec<Vec<u64>> = (0..p)
+                .map(|x| (0..p).map(|y| q.op(x, y)).collect())
+                .collect();
+            assert_eq!(DammQuasigroup::from_table(&rows), Ok(q.clone()));
+            let check = q.check_symbol(&[1, p - 1, 2]).unwrap();
+            assert!(q.is_valid(&[1, p - 1, 2, check]));
+            assert!(!q.is_valid(&[p - 1, 1, 2, check]));
+        }
+        assert_eq!(q.check_symbol(&[3]), Err(MathError::OutOfRange));
+        assert!(!q.is_valid(&[3, 0]));
+        for p in [0, 1, 2, 9] {
+            assert_eq!(
+                DammQuasigroup::over_prime_field(p),
+                Err(MathError::OutOfRange)
+            );
+        }
+
+        // the cyclic group is Latin but (c + x) + y = (c + y) + x
+        let cyclic: Vec<Vec<u64>> = (0..5)
+            .map(|x| (0..5).map(|y| (x + y) % 5).collect())
+            .collect();
+        assert_eq!(
+            DammQuasigroup::from_table(&cyclic),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            DammQuasigroup::from_table(&[vec![0, 1], vec![0, 1]]),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(DammQuasigroup::from_table(&[]), Err(MathError::EmptyInput));
+    }
+
+    /// Every single-digit error and adjacent transposition of random numbers is caught.
+    fn assert_catches_errors(check: fn(&str) -> Result<char, MathError>, valid: fn(&str) -> bool) {
+        let mut state = 809u64;
+        for _ in 0..50 {
+            let payload: Vec<u8> = (0..8)
+                .map(|_| b'0' + (splitmix64(&mut state) % 10) as u8)
+                .collect();
+            let mut word = payload.clone();
+            word.push(check(core::str::from_utf8(&payload).unwrap()).unwrap() as u8);
+            assert!(valid(core::str::from_utf8(&word).unwrap()));
+            for i in 0..word.len() {
+                for d in b'0'..=b'9' {
+                    let mut wrong = word.clone();
+                    wrong[i] = d;
+                    assert_eq!(valid(core::str::from_utf8(&wrong).unwrap()), d == word[i]);
+                }
+                if i + 1 < word.len() && word[i] != word[i + 1] {
+                    let mut swapped = word.clone();
+                    swapped.swap(i, i + 1);
+                    assert!(!valid(core::str::from_utf8(&swapped).unwrap()));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::poly_mod::PolyMod;
+use crate::errors::MathError;
+
+/// Parameters of a CRC in the usual Rocksoft/Williams model.
+///
+/// - `width`: degree of the generator polynomial, between 8 and 64
+/// - `poly`: generator without its leading `x^width` term, most significant bit first
+/// - `init`: register value before the first byte
+/// - `reflect`: process bytes least significant bit first (input and output reflected)
+/// - `xor_out`: value XORed into the register at the end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrcParams {
+    pub width: u32,
+    pub poly: u64,
+    pub init: u64,
+    pub reflect: bool,
+    pub xor_out: u64,
+}
+
+/// CRC-8/SMBUS: `x^8 + x^2 + x + 1`.
+pub const CRC8: CrcParams = CrcParams {
+    width: 8,
+    poly: 0x07,
+    init: 0,
+    reflect: false,
+    xor_out: 0,
+};
+
+/// CRC-16/CCITT-FALSE (X.25 polynomial `x^16 + x^12 + x^5 + 1`, non-reflected).
+pub const CRC16_CCITT_FALSE: CrcParams = CrcParams {
+    width: 16,
+    poly: 0x1021,
+    init: 0xFFFF,
+    reflect: false,
+    xor_out: 0,
+};
+
+/// CRC-32 as used by Ethernet, zip and PNG.
+pub const CRC32: CrcParams = CrcParams {
+    width: 32,
+    poly: 0x04C1_1DB7,
+    init: 0xFFFF_FFFF,
+    reflect: true,
+    xor_out: 0xFFFF_FFFF,
+};
+
+/// Table-driven CRC: the remainder of the message polynomial times `x^width`
+/// modulo the generator, over GF(2).
+///
+/// The 256-entry table holds the remainder of every byte, divided once with
+/// `PolyMod` over GF(2), so each input byte costs one lookup, one shift and one XOR
+/// instead of eight polynomial steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crc {
+    params: CrcParams,
+    table: Vec<u64>,
+}
+
+impl Crc {
+    /// Builds the lookup table; `OutOfRange` if `width` is not in `8..=64` or a
+    /// parameter does not fit in `width` bits.
+    pub fn new(params: CrcParams) -> Result<Self, MathError> {
+        if !(8..=64).contains(&params.width) {
+            return Err(MathError::OutOfRange);
+        }
+        let mask = mask(params.width);
+        if (params.poly | params.init | params.xor_out) & !mask != 0 {
+            return Err(MathError::OutOfRange);
+        }
+
+        // generator x^width + poly
+        let mut generator = bits(params.poly, params.width as usize + 1);
+        generator[params.width as usize] = 1;
+        let generator = PolyMod::new(generator, 2)?;
+
+        // byte * x^width mod generator; the reflected table is the same division
+        // on the bit-reversed byte, read back reversed
+        let table = (0..256u64)
+            .map(|byte| {
+                let byte = if params.reflect {
+                    reflect(byte, 8)
+                } else {
+                    byte
+                };
+                let mut message = vec![0; params.width as usize];
+                message.extend(bits(byte, 8));
+                let (_, rem) = PolyMod::new(message, 2)?.div_rem(&generator)?;
+                let rem = rem
+                    .coeffs()
+                    .iter()
+                    .enumerate()
+                    .fold(0u64, |acc, (i, &c)| acc | c << i);
+                Ok(if params.reflect {
+                    reflect(rem, params.width)
+                } else {
+                    rem
+                })
+            })
+            .collect::<Result<Vec<u64>, MathError>>()?;
+
+        Ok(Crc { params, table })
+    }
+
+    pub fn params(&self) -> CrcParams {
+        self.params
+    }
+
+    /// CRC of `data`:
+    /// - `Crc::new(CRC32)?.checksum(b"123456789") = 0xCBF43926`
+    pub fn checksum(&self, data: &[u8]) -> u64 {
+        let CrcParams {
+            width,
+            init,
+            reflect: reflected,
+            xor_out,
+            ..
+        } = self.params;
+
+        let crc = if reflected {
+            data.iter().fold(reflect(init, width), |crc, &b| {
+                crc >> 8 ^ self.table[((crc ^ b as u64) & 0xFF) as usize]
+            })
+        } else {
+            data.iter().fold(init, |crc, &b| {
+                let index = ((crc >> (width - 8)) ^ b as u64) & 0xFF;
+                (crc << 8 & mask(width)) ^ self.table[index as usize]
+            })
+        };
+        crc ^ xor_out
+    }
+}
+
+fn mask(width: u32) -> u64 {
+    u64::MAX >> (64 - width)
+}
+
+/// The low `len` bits of `value` as GF(2) coefficients, constant term first.
+fn bits(value: u64, len: usize) -> Vec<u64> {
+    (0..len)
+        .map(|i| value.checked_shr(i as u32).unwrap_or(0) & 1)
+        .collect()
+}
+
+/// Reverses the low `width` bits of `value`.
+fn reflect(value: u64, width: u32) -> u64 {
+    value.reverse_bits() >> (64 - width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECK: &[u8] = b"123456789";
+
+    #[test]
+    fn test_standard_check_values() {
+        assert_eq!(Crc::new(CRC8).unwrap().checksum(CHECK), 0xF4);
+        assert_eq!(Crc::new(CRC16_CCITT_FALSE).unwrap().checksum(CHECK), 0x29B1);
+        assert_eq!(Crc::new(CRC32).unwrap().checksum(CHECK), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_custom_polynomial() {
+        // CRC-64/XZ and CRC-16/ARC (reflected 0x8005)
+        let crc64 = CrcParams {
+            width: 64,
+            poly: 0x42F0_E1EB_A9EA_3693,
+            init: u64::MAX,
+            reflect: true,
+            xor_out: u64::MAX,
+        };
+        assert_eq!(
+            Crc::new(crc64).unwrap().checksum(CHECK),
+            0x995D_C9BB_DF19_39FA
+        );
+        let arc = CrcParams {
+            width: 16,
+            poly: 0x8005,
+            init: 0,
+            reflect: true,
+            xor_out: 0,
+        };
+        assert_eq!(Crc::new(arc).unwrap().checksum(CHECK), 0xBB3D);
+        assert_eq!(Crc::new(arc).unwrap().checksum(b""), 0);
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        let narrow = CrcParams { width: 4, ..CRC8 };
+        assert_eq!(Crc::new(narrow), Err(MathError::OutOfRange));
+        let wide_poly = CrcParams {
+            poly: 0x1_0000,
+            ..CRC16_CCITT_FALSE
+        };
+        assert_eq!(Crc::new(wide_poly), Err(MathError::OutOfRange));
+    }
+}
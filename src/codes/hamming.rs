@@ -0,0 +1,178 @@
+use alloc::vec::Vec;
+
+use crate::algebra::gf2::BitMatrix;
+use crate::errors::MathError;
+
+/// Supported Hamming codes `(n, k)`: `n`-bit codewords carrying `k` data bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HammingCode {
+    H7_4,
+    H15_11,
+}
+
+impl HammingCode {
+    /// Codeword length `n = 2^r - 1`.
+    pub fn n(self) -> u32 {
+        match self {
+            HammingCode::H7_4 => 7,
+            HammingCode::H15_11 => 15,
+        }
+    }
+
+    /// Data bits `k = n - r`.
+    pub fn k(self) -> u32 {
+        match self {
+            HammingCode::H7_4 => 4,
+            HammingCode::H15_11 => 11,
+        }
+    }
+}
+
+/// Result of `hamming_decode`: the data bits and the flipped position, if any.
+///
+/// `corrected` is the 1-based codeword position that was repaired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HammingDecoded {
+    pub data: u16,
+    pub corrected: Option<u32>,
+}
+
+/// Encodes the low `k` bits of `data` into an `n`-bit codeword.
+///
+/// Codeword position `i` (1-based) is stored in bit `i - 1`. Parity bits sit at the
+/// powers of two and data bits fill the other positions, lowest data bit first;
+/// parity `2^j` makes the XOR of the positions of all set bits vanish on bit `j`:
+/// - `hamming_encode(HammingCode::H7_4, 0b1011) = 0b1010101`
+///
+/// Errors with `OutOfRange` if `data` does not fit in `k` bits.
+pub fn hamming_encode(code: HammingCode, data: u16) -> Result<u16, MathError> {
+    if data >> code.k() != 0 {
+        return Err(MathError::OutOfRange);
+    }
+
+    let mut word = 0u16;
+    for (i, position) in data_positions(code) {
+        word |= (data >> i & 1) << (position - 1);
+    }
+    let syndrome = syndrome(code, word)?;
+    for j in 0..code.n() - code.k() {
+        word |= ((syndrome >> j & 1) as u16) << ((1 << j) - 1);
+    }
+    Ok(word)
+}
+
+/// Decodes an `n`-bit codeword, correcting a single flipped bit.
+///
+/// The syndrome (XOR of the positions of all set bits, i.e. `H·x` for the parity-check
+/// matrix whose columns are `1..=n` in binary) is the position of the error, or 0.
+/// Two or more errors are miscorrected, as for any Hamming code.
+///
+/// Errors with `OutOfRange` if `word` does not fit in `n` bits.
+pub fn hamming_decode(code: HammingCode, word: u16) -> Result<HammingDecoded, MathError> {
+    if word >> code.n() != 0 {
+        return Err(MathError::OutOfRange);
+    }
+
+    let error = syndrome(code, word)?;
+    let word = if error == 0 {
+        word
+    } else {
+        word ^ 1 << (error - 1)
+    };
+
+    let mut data = 0u16;
+    for (i, position) in data_positions(code) {
+        data |= (word >> (position - 1) & 1) << i;
+    }
+    Ok(HammingDecoded {
+        data,
+        corrected: (error != 0).then_some(error),
+    })
+}
+
+/// `(data bit index, codeword position)` pairs: every position that is not a power of two.
+fn data_positions(code: HammingCode) -> impl Iterator<Item = (usize, u32)> {
+    (1..=code.n()).filter(|p| !p.is_power_of_two()).enumerate()
+}
+
+/// Parity-check matrix `H`: `r × n` over GF(2), column `p - 1` holding `p` in binary.
+fn parity_check(code: HammingCode) -> BitMatrix {
+    let r = (code.n() - code.k()) as usize;
+    let mut h = BitMatrix::new(r, code.n() as usize);
+    for p in 1..=code.n() {
+        for j in 0..r {
+            h.set(j, (p - 1) as usize, p >> j & 1 == 1);
+        }
+    }
+    h
+}
+
+/// `H·word`, read back as the integer whose bit `j` is row `j` of the product.
+fn syndrome(code: HammingCode, word: u16) -> Result<u32, MathError> {
+    let bits: Vec<bool> = (0..code.n()).map(|i| word >> i & 1 == 1).collect();
+    let syndrome = parity_check(code).mul_vec(&bits)?;
+    Ok(syndrome
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (j, &b)| acc | (b as u32) << j))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_known_codeword() {
+        // positions 1..7 = p1 p2 d1 p4 d2 d3 d4 with data 1011 (d1 = 1, d2 = 1, d3 = 0, d4 = 1)
+        assert_eq!(hamming_encode(HammingCode::H7_4, 0b1011), Ok(0b1010101));
+        assert_eq!(hamming_encode(HammingCode::H7_4, 0), Ok(0));
+        assert_eq!(
+            hamming_encode(HammingCode::H7_4, 0b10000),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_every_single_error_corrected() {
+        for code in [HammingCode::H7_4, HammingCode::H15_11] {
+            for data in 0..1u16 << code.k() {
+                let word = hamming_encode(code, data).unwrap();
+                let clean = hamming_decode(code, word).unwrap();
+                assert_eq!(
+                    clean,
+                    HammingDecoded {
+                        data,
+                        corrected: None
+                    }
+                );
+                for position in 1..=code.n() {
+                    let decoded = hamming_decode(code, word ^ 1 << (position - 1)).unwrap();
+                    assert_eq!(decoded.data, data);
+                    assert_eq!(decoded.corrected, Some(position));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parity_check_kills_codewords() {
+        let code = HammingCode::H15_11;
+        let h = parity_check(code);
+        assert_eq!((h.rows(), h.cols()), (4, 15));
+        for data in [0u16, 1, 0b101_0101_0101, 0x7FF] {
+            let word = hamming_encode(code, data).unwrap();
+            let bits: Vec<bool> = (0..15).map(|i| word >> i & 1 == 1).collect();
+            assert_eq!(h.mul_vec(&bits), Ok(vec![false; 4]));
+        }
+    }
+
+    #[test]
+    fn test_decode_rejects_wide_word() {
+        assert_eq!(
+            hamming_decode(HammingCode::H15_11, 1 << 15),
+            Err(MathError::OutOfRange)
+        );
+    }
+}
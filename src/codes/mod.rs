@@ -0,0 +1,3 @@
+pub mod checksum;
+pub mod crc;
+pub mod hamming;
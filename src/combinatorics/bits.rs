@@ -0,0 +1,191 @@
+use crate::errors::MathError;
+
+/// i-th reflected binary Gray code: `i ^ (i >> 1)`.
+pub fn gray(i: u64) -> u64 {
+    i ^ (i >> 1)
+}
+
+/// Inverse of `gray`: the position of `code` in the Gray sequence.
+pub fn gray_inverse(mut code: u64) -> u64 {
+    let mut i = code;
+    while code > 0 {
+        code >>= 1;
+        i ^= code;
+    }
+    i
+}
+
+/// All `2^k` k-bit Gray codes in order; consecutive codes differ in exactly one bit.
+///
+/// - `gray_code(3) = 0, 1, 3, 2, 6, 7, 5, 4`
+///
+/// `MathError::OutOfRange` for `k > 64`.
+pub fn gray_code(k: u32) -> Result<GrayCode, MathError> {
+    if k > 64 {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(GrayCode {
+        next: 0,
+        end: 1u128 << k,
+    })
+}
+
+/// Iterator returned by `gray_code`.
+#[derive(Debug, Clone)]
+pub struct GrayCode {
+    next: u128,
+    end: u128,
+}
+
+impl Iterator for GrayCode {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.next >= self.end {
+            return None;
+        }
+        let i = self.next as u64;
+        self.next += 1;
+        Some(gray(i))
+    }
+}
+
+/// Every submask of `mask`, from `mask` itself down to `0`.
+///
+/// - `subsets_of(0b101) = 0b101, 0b100, 0b001, 0b000`
+pub fn subsets_of(mask: u64) -> Submasks {
+    Submasks {
+        mask,
+        next: Some(mask),
+    }
+}
+
+/// Iterator returned by `subsets_of`.
+#[derive(Debug, Clone)]
+pub struct Submasks {
+    mask: u64,
+    next: Option<u64>,
+}
+
+impl Iterator for Submasks {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let current = self.next?;
+        self.next = (current != 0).then(|| (current - 1) & self.mask);
+        Some(current)
+    }
+}
+
+/// All n-bit masks with exactly `k` bits set, in increasing order (Gosper's hack).
+///
+/// - `k_subsets(4, 2) = 0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100`
+///
+/// `MathError::OutOfRange` for `n > 64` or `k > n`.
+pub fn k_subsets(n: u32, k: u32) -> Result<KSubsets, MathError> {
+    if n > 64 || k > n {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(KSubsets {
+        next: (1u128 << k) - 1,
+        end: 1u128 << n,
+    })
+}
+
+/// Iterator returned by `k_subsets`.
+#[derive(Debug, Clone)]
+pub struct KSubsets {
+    next: u128,
+    end: u128,
+}
+
+impl Iterator for KSubsets {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let current = self.next;
+        if current >= self.end {
+            return None;
+        }
+        if current == 0 {
+            // k = 0: the empty set only
+            self.next = self.end;
+        } else {
+            let low = current & current.wrapping_neg();
+            let ripple = current + low;
+            self.next = ripple | (((current ^ ripple) >> 2) / low);
+        }
+        Some(current as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_gray_code_sequence() {
+        let codes: Vec<u64> = gray_code(3).unwrap().collect();
+        assert_eq!(codes, vec![0, 1, 3, 2, 6, 7, 5, 4]);
+        assert_eq!(gray_code(0).unwrap().collect::<Vec<_>>(), vec![0]);
+        assert!(gray_code(65).is_err());
+    }
+
+    #[test]
+    fn test_gray_code_single_bit_steps() {
+        let codes: Vec<u64> = gray_code(10).unwrap().collect();
+        assert_eq!(codes.len(), 1024);
+        for pair in codes.windows(2) {
+            assert_eq!((pair[0] ^ pair[1]).count_ones(), 1);
+        }
+        for (i, &code) in codes.iter().enumerate() {
+            assert_eq!(gray_inverse(code), i as u64);
+        }
+    }
+
+    #[test]
+    fn test_gray_code_full_width() {
+        let mut codes = gray_code(64).unwrap();
+        assert_eq!(codes.next(), Some(0));
+        assert_eq!(gray_inverse(gray(u64::MAX)), u64::MAX);
+    }
+
+    #[test]
+    fn test_subsets_of() {
+        let subs: Vec<u64> = subsets_of(0b101).collect();
+        assert_eq!(subs, vec![0b101, 0b100, 0b001, 0]);
+        assert_eq!(subsets_of(0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(subsets_of(0b1011_0110).count(), 32);
+    }
+
+    #[test]
+    fn test_k_subsets() {
+        let masks: Vec<u64> = k_subsets(4, 2).unwrap().collect();
+        assert_eq!(masks, vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]);
+        assert_eq!(k_subsets(5, 0).unwrap().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(k_subsets(5, 5).unwrap().collect::<Vec<_>>(), vec![0b11111]);
+        assert!(k_subsets(3, 4).is_err());
+    }
+
+    #[test]
+    fn test_k_subsets_counts() {
+        // C(20, 7) = 77520
+        let masks: Vec<u64> = k_subsets(20, 7).unwrap().collect();
+        assert_eq!(masks.len(), 77520);
+        assert!(masks.iter().all(|m| m.count_ones() == 7 && *m < 1 << 20));
+        assert!(masks.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_k_subsets_full_width() {
+        let masks: Vec<u64> = k_subsets(64, 63).unwrap().collect();
+        assert_eq!(masks.len(), 64);
+        assert_eq!(masks.last(), Some(&(u64::MAX - 1)));
+        assert_eq!(
+            k_subsets(64, 64).unwrap().collect::<Vec<_>>(),
+            vec![u64::MAX]
+        );
+    }
+}
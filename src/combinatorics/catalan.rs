@@ -0,0 +1,235 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::errors::MathError;
+
+/// Binary tree shape with `n` internal nodes (there are `catalan(n)` of them).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinaryTree {
+    Leaf,
+    Node(Box<BinaryTree>, Box<BinaryTree>),
+}
+
+impl BinaryTree {
+    /// Number of internal nodes.
+    pub fn node_count(&self) -> usize {
+        match self {
+            BinaryTree::Leaf => 0,
+            BinaryTree::Node(left, right) => 1 + left.node_count() + right.node_count(),
+        }
+    }
+}
+
+/// Largest `n` whose Catalan number fits in `u128`.
+const MAX_N: usize = 69;
+
+/// n-th Catalan number `C(2n, n) / (n + 1)`; `MathError::Overflow` past `n = 69`.
+pub fn catalan(n: usize) -> Result<u128, MathError> {
+    Ok(ballot_table(n)?[2 * n][0])
+}
+
+/// Dyck path of semilength `n` with the given lexicographic rank.
+///
+/// Steps are `true` for up and `false` for down, with up ordered first, so rank 0
+/// is `n` ups followed by `n` downs:
+/// - `nth_dyck_path(2, 0) = [up, up, down, down]`
+/// - `nth_dyck_path(2, 1) = [up, down, up, down]`
+///
+/// `MathError::OutOfRange` unless `rank < catalan(n)`.
+pub fn nth_dyck_path(n: usize, mut rank: u128) -> Result<Vec<bool>, MathError> {
+    let ways = ballot_table(n)?;
+    if rank >= ways[2 * n][0] {
+        return Err(MathError::OutOfRange);
+    }
+
+    let mut path = Vec::with_capacity(2 * n);
+    let mut height = 0;
+    for remaining in (1..=2 * n).rev() {
+        // completions if we step up now
+        let up = if height < remaining - 1 {
+            ways[remaining - 1][height + 1]
+        } else {
+            0
+        };
+        if rank < up {
+            path.push(true);
+            height += 1;
+        } else {
+            rank -= up;
+            path.push(false);
+            height -= 1;
+        }
+    }
+    Ok(path)
+}
+
+/// Inverse of `nth_dyck_path`; `MathError::OutOfRange` if `path` is not a Dyck path.
+pub fn dyck_path_rank(path: &[bool]) -> Result<u128, MathError> {
+    if !path.len().is_multiple_of(2) {
+        return Err(MathError::OutOfRange);
+    }
+    let ways = ballot_table(path.len() / 2)?;
+
+    let mut rank = 0;
+    let mut height = 0usize;
+    for (i, &up) in path.iter().enumerate() {
+        let remaining = path.len() - i;
+        if up {
+            height += 1;
+        } else {
+            if height < remaining - 1 {
+                rank += ways[remaining - 1][height + 1];
+            }
+            height = height.checked_sub(1).ok_or(MathError::OutOfRange)?;
+        }
+    }
+    if height != 0 {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(rank)
+}
+
+/// Binary tree with `n` internal nodes and the given rank.
+///
+/// Uses the standard bijection `path = up · left · down · right` with the ranking of
+/// `nth_dyck_path`, so rank 0 is the left comb.
+pub fn nth_binary_tree(n: usize, rank: u128) -> Result<BinaryTree, MathError> {
+    let path = nth_dyck_path(n, rank)?;
+    let mut steps = path.into_iter();
+    Ok(tree_from_path(&mut steps))
+}
+
+fn tree_from_path(steps: &mut impl Iterator<Item = bool>) -> BinaryTree {
+    match steps.next() {
+        Some(true) => {
+            let left = tree_from_path(steps);
+            // the matching down step
+            let right = tree_from_path(steps);
+            BinaryTree::Node(Box::new(left), Box::new(right))
+        }
+        _ => BinaryTree::Leaf,
+    }
+}
+
+/// `ways[r][h]`: number of ways to finish a Dyck path with `r` steps left at height `h`.
+///
+/// Only the heights reachable after `2n - r` steps are filled, so every entry counts
+/// some of the Dyck paths and is at most `catalan(n)`. `MathError::Overflow` past
+/// `MAX_N`, before anything is allocated.
+fn ballot_table(n: usize) -> Result<Vec<Vec<u128>>, MathError> {
+    if n > MAX_N {
+        return Err(MathError::Overflow);
+    }
+    let len = 2 * n + 1;
+    let mut ways = vec![vec![0u128; len + 1]; len];
+    ways[0][0] = 1;
+    for r in 1..len {
+        for h in 0..=r.min(len - 1 - r) {
+            let up = ways[r - 1][h + 1];
+            let down = if h > 0 { ways[r - 1][h - 1] } else { 0 };
+            ways[r][h] = up.checked_add(down).ok_or(MathError::Overflow)?;
+        }
+    }
+    Ok(ways)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalan() {
+        let expected = [1, 1, 2, 5, 14, 42, 132, 429, 1430, 4862];
+        for (n, &c) in expected.iter().enumerate() {
+            assert_eq!(catalan(n), Ok(c));
+        }
+        assert!(catalan(64).is_ok());
+        assert_eq!(
+            catalan(MAX_N),
+            Ok(337_485_502_510_215_975_556_783_793_455_058_624_700)
+        );
+        assert_eq!(catalan(70), Err(MathError::Overflow));
+        // rejected before the table is allocated
+        assert_eq!(catalan(200_000), Err(MathError::Overflow));
+        assert_eq!(
+            dyck_path_rank(&vec![true; 400_000]),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_nth_dyck_path_small() {
+        assert_eq!(nth_dyck_path(2, 0), Ok(vec![true, true, false, false]));
+        assert_eq!(nth_dyck_path(2, 1), Ok(vec![true, false, true, false]));
+        assert_eq!(nth_dyck_path(2, 2), Err(MathError::OutOfRange));
+        assert_eq!(nth_dyck_path(0, 0), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_nth_dyck_path_enumerates_in_order() {
+        let n = 5;
+        let paths: Vec<Vec<bool>> = (0..catalan(n).unwrap())
+            .map(|r| nth_dyck_path(n, r).unwrap())
+            .collect();
+        // strictly decreasing under up > down, i.e. lexicographic with up first
+        for pair in paths.windows(2) {
+            assert!(pair[0] > pair[1]);
+        }
+        for (rank, path) in paths.iter().enumerate() {
+            assert_eq!(dyck_path_rank(path), Ok(rank as u128));
+        }
+    }
+
+    #[test]
+    fn test_dyck_path_rank_rejects_invalid() {
+        assert_eq!(dyck_path_rank(&[false, true]), Err(MathError::OutOfRange));
+        assert_eq!(dyck_path_rank(&[true, true]), Err(MathError::OutOfRange));
+        assert_eq!(dyck_path_rank(&[true]), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_large_rank() {
+        let last = catalan(30).unwrap() - 1;
+        let path = nth_dyck_path(30, last).unwrap();
+        assert_eq!(path, [true, false].repeat(30));
+        assert_eq!(dyck_path_rank(&path), Ok(last));
+        // every table entry stays below catalan(MAX_N)
+        let last = catalan(MAX_N).unwrap() - 1;
+        let path = nth_dyck_path(MAX_N, last).unwrap();
+        assert_eq!(path, [true, false].repeat(MAX_N));
+        assert_eq!(dyck_path_rank(&path), Ok(last));
+        let first = [vec![true; MAX_N], vec![false; MAX_N]].concat();
+        assert_eq!(nth_dyck_path(MAX_N, 0), Ok(first));
+    }
+
+    #[test]
+    fn test_nth_binary_tree() {
+        use BinaryTree::{Leaf, Node};
+
+        let leaf = || Box::new(Leaf);
+        assert_eq!(nth_binary_tree(0, 0), Ok(Leaf));
+        assert_eq!(
+            nth_binary_tree(2, 0),
+            Ok(Node(Box::new(Node(leaf(), leaf())), leaf()))
+        );
+        assert_eq!(
+            nth_binary_tree(2, 1),
+            Ok(Node(leaf(), Box::new(Node(leaf(), leaf()))))
+        );
+    }
+
+    #[test]
+    fn test_nth_binary_tree_all_distinct() {
+        let n = 6;
+        let trees: Vec<BinaryTree> = (0..catalan(n).unwrap())
+            .map(|r| nth_binary_tree(n, r).unwrap())
+            .collect();
+        assert!(trees.iter().all(|t| t.node_count() == n));
+        for i in 0..trees.len() {
+            for j in i + 1..trees.len() {
+                assert_ne!(trees[i], trees[j]);
+            }
+        }
+    }
+}
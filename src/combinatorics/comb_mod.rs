@@ -0,0 +1,333 @@
+use alloc::vec::Vec;
+
+use crate::algebra::modular::{add_mod, inverse_table, mod_inverse, mul_mod};
+use crate::errors::MathError;
+
+/// Factorial tables mod `p` for O(1) binomial queries.
+///
+/// Construction is O(max_n); afterwards `binomial`, `permutations` and the factorial
+/// lookups are a few multiplications each. `p` must be coprime to `max_n!`
+/// (e.g. a prime greater than `max_n`), so that every factorial is invertible.
+///
+/// The tables are never written after `new`, so a `CombMod` is `Send + Sync` and one
+/// instance can serve every thread from an `Arc` without a lock. No method panics:
+/// sizes and indices past `usize::MAX` give `OutOfRange`, and sums stay exact for
+/// moduli above `2^63`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombMod {
+    modulus: u64,
+    fact: Vec<u64>,
+    inv_fact: Vec<u64>,
+}
+
+impl CombMod {
+    /// Precomputes `k!` and `(k!)⁻¹ mod p` for `0 <= k <= max_n`.
+    ///
+    /// Errors with `OutOfRange` if `p < 2`, if `p` shares a factor with `max_n!` or if
+    /// the tables cannot be allocated.
+    pub fn new(max_n: usize, p: u64) -> Result<Self, MathError> {
+        if p < 2 {
+            return Err(MathError::OutOfRange);
+        }
+        let len = max_n.checked_add(1).ok_or(MathError::OutOfRange)?;
+        let (mut fact, mut inv_fact) = (Vec::new(), Vec::new());
+        fact.try_reserve_exact(len)
+            .and_then(|_| inv_fact.try_reserve_exact(len))
+            .map_err(|_| MathError::OutOfRange)?;
+
+        let mut running = 1;
+        fact.push(running);
+        for i in 1..=max_n {
+            running = mul_mod(running, i as u64, p);
+            fact.push(running);
+        }
+
+        // filled from (max_n!)⁻¹ down, then put in order
+        let mut inv = mod_inverse(running, p).map_err(|_| MathError::OutOfRange)?;
+        inv_fact.push(inv);
+        for i in (1..=max_n).rev() {
+            inv = mul_mod(inv, i as u64, p);
+            inv_fact.push(inv);
+        }
+        inv_fact.reverse();
+
+        Ok(CombMod {
+            modulus: p,
+            fact,
+            inv_fact,
+        })
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Largest `n` the tables cover.
+    pub fn max_n(&self) -> usize {
+        self.fact.len().saturating_sub(1)
+    }
+
+    /// `n! mod p`
+    pub fn factorial(&self, n: usize) -> Result<u64, MathError> {
+        self.fact.get(n).copied().ok_or(MathError::OutOfRange)
+    }
+
+    /// `(n!)⁻¹ mod p`
+    pub fn inverse_factorial(&self, n: usize) -> Result<u64, MathError> {
+        self.inv_fact.get(n).copied().ok_or(MathError::OutOfRange)
+    }
+
+    /// `C(n, k) mod p`, zero when `k > n`.
+    pub fn binomial(&self, n: usize, k: usize) -> Result<u64, MathError> {
+        let Some(rest) = n.checked_sub(k) else {
+            return Ok(0);
+        };
+        let p = self.modulus;
+        let top = self.factorial(n)?;
+        Ok(mul_mod(
+            mul_mod(top, self.inverse_factorial(k)?, p),
+            self.inverse_factorial(rest)?,
+            p,
+        ))
+    }
+
+    /// `P(n, k) = n! / (n - k)! mod p`, zero when `k > n`.
+    pub fn permutations(&self, n: usize, k: usize) -> Result<u64, MathError> {
+        let Some(rest) = n.checked_sub(k) else {
+            return Ok(0);
+        };
+        let top = self.factorial(n)?;
+        Ok(mul_mod(top, self.inverse_factorial(rest)?, self.modulus))
+    }
+
+    /// `n⁻¹ mod p = (n!)⁻¹·(n - 1)!`, for `1 <= n <= max_n`.
+    ///
+    /// Errors with `DivisionByZero` if `n == 0` and `OutOfRange` past `max_n`.
+    pub fn inverse(&self, n: usize) -> Result<u64, MathError> {
+        let Some(previous) = n.checked_sub(1) else {
+            return Err(MathError::DivisionByZero);
+        };
+        let inv = self.inverse_factorial(n)?;
+        Ok(mul_mod(inv, self.factorial(previous)?, self.modulus))
+    }
+
+    /// `Σ C(n, j)` for `0 <= j <= k`, mod p:
+    /// - `binomial_prefix_sum(5, 2) = 1 + 5 + 10 = 16`
+    ///
+    /// No closed form exists, so this is O(min(k, n)) table lookups; `2^n` once
+    /// `k >= n`.
+    pub fn binomial_prefix_sum(&self, n: usize, k: usize) -> Result<u64, MathError> {
+        (0..=k.min(n)).try_fold(0, |sum, j| {
+            Ok(add_mod(sum, self.binomial(n, j)?, self.modulus))
+        })
+    }
+
+    /// Hockey-stick identity down a column of Pascal's triangle,
+    /// `Σ C(i, r) for r <= i <= n = C(n + 1, r + 1)`:
+    /// - `hockey_stick(5, 2) = 1 + 3 + 6 + 10 = 20`
+    ///
+    /// O(1); zero when `r > n`. `MathError::OutOfRange` if `n + 1 > max_n`.
+    pub fn hockey_stick(&self, n: usize, r: usize) -> Result<u64, MathError> {
+        if r > n {
+            return Ok(0);
+        }
+        match (n.checked_add(1), r.checked_add(1)) {
+            (Some(n), Some(r)) => self.binomial(n, r),
+            _ => Err(MathError::OutOfRange),
+        }
+    }
+
+    /// Hockey-stick identity along a diagonal, `Σ C(m + i, i) for 0 <= i <= k =
+    /// C(m + k + 1, k)`:
+    /// - `hockey_stick_diagonal(2, 3) = 1 + 3 + 6 + 10 = 20`
+    ///
+    /// O(1). `MathError::OutOfRange` if `m + k + 1 > max_n`.
+    pub fn hockey_stick_diagonal(&self, m: usize, k: usize) -> Result<u64, MathError> {
+        let top = m
+            .checked_add(k)
+            .and_then(|s| s.checked_add(1))
+            .ok_or(MathError::OutOfRange)?;
+        self.binomial(top, k)
+    }
+}
+
+/// Harmonic number `H_n = Σ i⁻¹ mod p` for `1 <= i <= n`:
+/// - `sum_inverse_mod(3, 7) = 1 + 4 + 5 = 3`, as `H_3 = 11/6` and `11·6⁻¹ ≡ 3`
+///
+/// Reads the inverses off `modular::inverse_table`, O(n) in all. By Wolstenholme's
+/// theorem `H_(p-1) ≡ 0 (mod p²)` for primes `p >= 5`, so in particular
+/// `sum_inverse_mod(p - 1, p) = 0`. Errors as `inverse_table`: `DivisionByZero` if
+/// `p == 0`, `NoSolution` if some `i <= n` is not invertible.
+pub fn sum_inverse_mod(n: usize, p: u64) -> Result<u64, MathError> {
+    Ok(inverse_table(n, p)?
+        .into_iter()
+        .fold(0, |sum, inv| add_mod(sum, inv, p)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const P: u64 = 1_000_000_007;
+
+    #[test]
+    fn test_binomial_small() {
+        let c = CombMod::new(10, P).unwrap();
+        assert_eq!(c.binomial(5, 2), Ok(10));
+        assert_eq!(c.binomial(10, 5), Ok(252));
+        assert_eq!(c.binomial(10, 0), Ok(1));
+        assert_eq!(c.binomial(10, 10), Ok(1));
+        assert_eq!(c.binomial(3, 4), Ok(0));
+    }
+
+    #[test]
+    fn test_binomial_matches_pascal() {
+        let c = CombMod::new(60, P).unwrap();
+        let mut row = vec![1u64];
+        for n in 0..=60 {
+            for (k, &expected) in row.iter().enumerate() {
+                assert_eq!(c.binomial(n, k), Ok(expected), "C({}, {})", n, k);
+            }
+            let mut next = vec![1u64; n + 2];
+            for k in 1..=n {
+                next[k] = (row[k - 1] + row[k]) % P;
+            }
+            row = next;
+        }
+    }
+
+    #[test]
+    fn test_large_table() {
+        let c = CombMod::new(200_000, P).unwrap();
+        // C(200000, 100000) mod 1e9+7
+        assert_eq!(c.binomial(200_000, 100_000), Ok(879_467_333));
+        assert_eq!(c.max_n(), 200_000);
+    }
+
+    #[test]
+    fn test_factorials_and_permutations() {
+        let c = CombMod::new(20, P).unwrap();
+        assert_eq!(c.factorial(0), Ok(1));
+        assert_eq!(c.factorial(10), Ok(3_628_800));
+        assert_eq!(c.permutations(5, 2), Ok(20));
+        assert_eq!(c.permutations(5, 6), Ok(0));
+        let f = c.factorial(15).unwrap();
+        let inv = c.inverse_factorial(15).unwrap();
+        assert_eq!(mul_mod(f, inv, P), 1);
+    }
+
+    #[test]
+    fn test_composite_modulus_coprime_to_factorials() {
+        // 143 = 11 * 13 is coprime to 10!
+        let c = CombMod::new(10, 143).unwrap();
+        assert_eq!(c.binomial(10, 3), Ok(120));
+        assert_eq!(c.binomial(10, 5), Ok(252 % 143));
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        use alloc::sync::Arc;
+
+        let c = Arc::new(CombMod::new(1000, P).unwrap());
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let c = Arc::clone(&c);
+                std::thread::spawn(move || c.binomial(1000, 250 * t))
+            })
+            .collect();
+        let row: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(row[0], Ok(1));
+        assert_eq!(row[2], c.binomial(1000, 500));
+    }
+
+    #[test]
+    fn test_inverse_and_harmonic() {
+        let c = CombMod::new(1000, P).unwrap();
+        for n in 1..=1000 {
+            assert_eq!(mul_mod(c.inverse(n).unwrap(), n as u64, P), 1);
+        }
+        assert_eq!(c.inverse(0), Err(MathError::DivisionByZero));
+        assert_eq!(c.inverse(1001), Err(MathError::OutOfRange));
+
+        assert_eq!(sum_inverse_mod(3, 7), Ok(3));
+        assert_eq!(sum_inverse_mod(0, 7), Ok(0));
+        let harmonic = (1..=1000).fold(0, |sum, n| (sum + c.inverse(n).unwrap()) % P);
+        assert_eq!(sum_inverse_mod(1000, P), Ok(harmonic));
+        for p in [5, 7, 11, 13, 101, 7919] {
+            assert_eq!(sum_inverse_mod(p as usize - 1, p), Ok(0));
+        }
+        assert_eq!(sum_inverse_mod(7, 7), Err(MathError::NoSolution));
+        assert_eq!(sum_inverse_mod(2, 0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_binomial_sums() {
+        let c = CombMod::new(64, P).unwrap();
+        assert_eq!(c.binomial_prefix_sum(5, 2), Ok(16));
+        assert_eq!(c.binomial_prefix_sum(40, 40), Ok((1 << 40) % P));
+        assert_eq!(c.binomial_prefix_sum(10, 99), Ok(1024));
+        assert_eq!(c.hockey_stick(5, 2), Ok(20));
+        assert_eq!(c.hockey_stick_diagonal(2, 3), Ok(20));
+        assert_eq!(c.hockey_stick(2, 5), Ok(0));
+        for n in 0..63 {
+            for r in 0..63 {
+                let column = (r..=n).try_fold(0, |sum, i| {
+                    Ok::<_, MathError>((sum + c.binomial(i, r)?) % P)
+                });
+                assert_eq!(c.hockey_stick(n, r), column);
+                if n + r < 63 {
+                    let diagonal = (0..=r).try_fold(0, |sum, i| {
+                        Ok::<_, MathError>((sum + c.binomial(n + i, i)?) % P)
+                    });
+                    assert_eq!(c.hockey_stick_diagonal(n, r), diagonal);
+                }
+            }
+        }
+        assert_eq!(c.hockey_stick(64, 1), Err(MathError::OutOfRange));
+        assert_eq!(c.hockey_stick_diagonal(60, 4), Err(MathError::OutOfRange));
+        assert_eq!(c.binomial_prefix_sum(65, 1), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(CombMod::new(10, 1), Err(MathError::OutOfRange));
+        // 7 divides 10!
+        assert_eq!(CombMod::new(10, 7), Err(MathError::OutOfRange));
+        let c = CombMod::new(10, P).unwrap();
+        assert_eq!(c.binomial(11, 2), Err(MathError::OutOfRange));
+        assert_eq!(c.factorial(11), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_extreme_arguments_do_not_panic() {
+        assert_eq!(CombMod::new(usize::MAX, P), Err(MathError::OutOfRange));
+        let c = CombMod::new(10, P).unwrap();
+        assert_eq!(c.hockey_stick(usize::MAX, 3), Err(MathError::OutOfRange));
+        assert_eq!(
+            c.hockey_stick(usize::MAX, usize::MAX),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            c.hockey_stick_diagonal(usize::MAX, 1),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(c.binomial(usize::MAX, 3), Err(MathError::OutOfRange));
+        assert_eq!(c.binomial(3, usize::MAX), Ok(0));
+        assert_eq!(c.permutations(usize::MAX, 0), Err(MathError::OutOfRange));
+        assert_eq!(c.inverse(usize::MAX), Err(MathError::OutOfRange));
+        assert_eq!(
+            c.binomial_prefix_sum(usize::MAX, usize::MAX),
+            Err(MathError::OutOfRange)
+        );
+
+        // residues near 2^64 used to overflow the running sums
+        let big = u64::MAX - 58;
+        let c = CombMod::new(6, big).unwrap();
+        assert_eq!(
+            sum_inverse_mod(3, big),
+            Ok(mul_mod(11, c.inverse(6).unwrap(), big))
+        );
+        assert_eq!(c.binomial_prefix_sum(6, 6), Ok(64));
+    }
+}
@@ -0,0 +1,283 @@
+use alloc::vec::Vec;
+
+use crate::algebra::factorization::Factorization;
+use crate::errors::MathError;
+
+/// Alternating sum `t₀ - t₁ + t₂ - ...` with overflow checks.
+///
+/// The signed fold behind every inclusion-exclusion count: pass the sizes of the
+/// i-fold intersections summed over all choices of i properties.
+pub fn alternating_sum<I>(terms: I) -> Result<i128, MathError>
+where
+    I: IntoIterator<Item = i128>,
+{
+    terms
+        .into_iter()
+        .enumerate()
+        .try_fold(0i128, |acc, (i, term)| {
+            if i % 2 == 0 {
+                acc.checked_add(term)
+            } else {
+                acc.checked_sub(term)
+            }
+        })
+        .ok_or(MathError::Overflow)
+}
+
+/// Number of elements having none of `properties` properties.
+///
+/// `intersection(mask)` returns how many elements have every property in `mask`
+/// (`mask = 0` is the whole set); the result is `Σ (-1)^|S| intersection(S)` over all
+/// `2^properties` subsets. Limited to 63 properties.
+pub fn inclusion_exclusion<F>(properties: u32, mut intersection: F) -> Result<i128, MathError>
+where
+    F: FnMut(u64) -> i128,
+{
+    if properties > 63 {
+        return Err(MathError::OutOfRange);
+    }
+    (0..1u64 << properties).try_fold(0i128, |acc, mask| {
+        let size = intersection(mask);
+        let next = if mask.count_ones() % 2 == 0 {
+            acc.checked_add(size)
+        } else {
+            acc.checked_sub(size)
+        };
+        next.ok_or(MathError::Overflow)
+    })
+}
+
+/// Number of permutations of `n` elements with no fixed point, `D(n)`.
+///
+/// `D(0) = 1`, `D(1) = 0`, `D(n) = (n - 1)(D(n - 1) + D(n - 2))`:
+/// - `derangements(4) = 9`
+///
+/// `MathError::Overflow` past `D(34)`.
+pub fn derangements(n: u32) -> Result<u128, MathError> {
+    let (mut prev, mut cur) = (1u128, 0u128); // D(0), D(1)
+    if n == 0 {
+        return Ok(prev);
+    }
+    for i in 2..=n as u128 {
+        let sum = prev.checked_add(cur).ok_or(MathError::Overflow)?;
+        let next = (i - 1).checked_mul(sum).ok_or(MathError::Overflow)?;
+        (prev, cur) = (cur, next);
+    }
+    Ok(cur)
+}
+
+/// `D(n) mod m` by the same recurrence, for any `n`.
+pub fn derangements_mod(n: u64, m: u64) -> Result<u64, MathError> {
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let m128 = m as u128;
+    let (mut prev, mut cur) = (1 % m128, 0u128);
+    if n == 0 {
+        return Ok(prev as u64);
+    }
+    for i in 2..=n as u128 {
+        let next = ((i - 1) % m128) * ((prev + cur) % m128) % m128;
+        (prev, cur) = (cur, next);
+    }
+    Ok(cur as u64)
+}
+
+/// Number of surjections from an `n`-set onto a `k`-set, `k! · S(n, k)`.
+///
+/// Inclusion-exclusion over the missed targets: `Σ (-1)^i C(k, i) (k - i)^n`:
+/// - `surjections(4, 2) = 14`
+pub fn surjections(n: u32, k: u32) -> Result<u128, MathError> {
+    if k > n {
+        return Ok(0);
+    }
+    let mut binomial = 1i128;
+    let mut terms = Vec::with_capacity(k as usize + 1);
+    for i in 0..=k as i128 {
+        let power = (k as i128 - i).checked_pow(n).ok_or(MathError::Overflow)?;
+        terms.push(binomial.checked_mul(power).ok_or(MathError::Overflow)?);
+        // C(k, i + 1) = C(k, i) (k - i) / (i + 1)
+        binomial = binomial
+            .checked_mul(k as i128 - i)
+            .ok_or(MathError::Overflow)?
+            / (i + 1);
+    }
+    Ok(alternating_sum(terms)? as u128)
+}
+
+/// Number of `x` in `lo..=hi` with `gcd(x, n) = 1`:
+/// - `count_coprime_in_range(1, 30, 30) = 8`
+/// - `count_coprime_in_range(0, 10, 1) = 11`, 0 included as `gcd(0, 1) = 1`
+///
+/// Inclusion-exclusion over the distinct primes of `n`, at most 15 for a `u64`, so at
+/// most `2^15` terms whatever the range. `MathError::PositifIntegerRequired` if `n == 0`,
+/// `OutOfRange` if `lo > hi`.
+pub fn count_coprime_in_range(lo: u64, hi: u64, n: u64) -> Result<u64, MathError> {
+    if lo > hi {
+        return Err(MathError::OutOfRange);
+    }
+    coprime_count(lo, hi, &Factorization::new(n as u128)?)
+}
+
+/// Number of `x` in `lo..=hi` with `gcd(x, n) = g`:
+/// - `count_with_gcd(1, 100, 12, 4) = 17`, the `x = 4y` with `y` not a multiple of 3
+///
+/// `gcd(x, n) = g` exactly when `g | n`, `x = g·y` and `gcd(y, n/g) = 1`, so this counts
+/// coprimes to `n/g` over `⌈lo/g⌉..=⌊hi/g⌋`; 0 unless `g` divides `n`.
+/// `MathError::PositifIntegerRequired` if `n == 0`, `OutOfRange` if `lo > hi`.
+pub fn count_with_gcd(lo: u64, hi: u64, n: u64, g: u64) -> Result<u64, MathError> {
+    if lo > hi {
+        return Err(MathError::OutOfRange);
+    }
+    if n == 0 {
+        return Err(MathError::PositifIntegerRequired);
+    }
+    if g == 0 || !n.is_multiple_of(g) {
+        return Ok(0);
+    }
+    let (lo, hi) = (lo.div_ceil(g), hi / g);
+    if lo > hi {
+        return Ok(0);
+    }
+    coprime_count(lo, hi, &Factorization::new((n / g) as u128)?)
+}
+
+/// `x` in `lo..=hi` free of every prime of `n`, by `Σ μ(d)·#{multiples of d}` over the
+/// squarefree `d | n`; `lo <= hi`.
+fn coprime_count(lo: u64, hi: u64, n: &Factorization) -> Result<u64, MathError> {
+    let primes: Vec<u64> = n.prime_powers().iter().map(|&(p, _)| p as u64).collect();
+    // multiples of d in 0..=x number x/d + 1
+    let up_to = |x: u64, d: u64| (x / d) as i128 + 1;
+    let count = inclusion_exclusion(primes.len() as u32, |mask| {
+        // a product of distinct primes of n divides n, so fits
+        let d = (0..primes.len())
+            .filter(|&i| mask >> i & 1 == 1)
+            .map(|i| primes[i])
+            .product();
+        up_to(hi, d) - if lo == 0 { 0 } else { up_to(lo - 1, d) }
+    })?;
+    Ok(count as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derangements() {
+        let expected = [1, 0, 1, 2, 9, 44, 265, 1854, 14833, 133496, 1334961];
+        for (n, &d) in expected.iter().enumerate() {
+            assert_eq!(derangements(n as u32), Ok(d), "D({})", n);
+        }
+    }
+
+    #[test]
+    fn test_derangements_overflow() {
+        assert!(derangements(34).is_ok());
+        assert_eq!(derangements(35), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_derangements_mod() {
+        for n in 0..=30 {
+            let exact = derangements(n).unwrap();
+            assert_eq!(
+                derangements_mod(n as u64, 1_000_000_007),
+                Ok((exact % 1_000_000_007) as u64)
+            );
+        }
+        assert_eq!(derangements_mod(0, 1), Ok(0));
+        assert_eq!(derangements_mod(5, 0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_surjections() {
+        assert_eq!(surjections(4, 2), Ok(14));
+        assert_eq!(surjections(5, 3), Ok(150));
+        assert_eq!(surjections(6, 6), Ok(720));
+        assert_eq!(surjections(3, 4), Ok(0));
+        assert_eq!(surjections(0, 0), Ok(1));
+        assert_eq!(surjections(3, 0), Ok(0));
+    }
+
+    #[test]
+    fn test_alternating_sum() {
+        assert_eq!(alternating_sum([10, 4, 1]), Ok(7));
+        assert_eq!(alternating_sum([]), Ok(0));
+        assert_eq!(alternating_sum([i128::MIN, 1]), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_inclusion_exclusion_counts_coprimes() {
+        // integers in 1..=30 coprime to 30 = 2·3·5
+        let primes = [2i128, 3, 5];
+        let count = inclusion_exclusion(3, |mask| {
+            let product: i128 = (0..3)
+                .filter(|i| mask >> i & 1 == 1)
+                .map(|i| primes[i])
+                .product();
+            30 / product
+        });
+        assert_eq!(count, Ok(8));
+    }
+
+    #[test]
+    fn test_count_coprime_in_range() {
+        let naive = |lo: u64, hi: u64, n: u64| {
+            (lo..=hi)
+                .filter(|&x| crate::algebra::gcd::gcd_u128(x as u128, n as u128) == 1)
+                .count() as u64
+        };
+        assert_eq!(count_coprime_in_range(1, 30, 30), Ok(8));
+        assert_eq!(count_coprime_in_range(0, 10, 1), Ok(11));
+        assert_eq!(count_coprime_in_range(0, 0, 7), Ok(0));
+        assert_eq!(count_coprime_in_range(5, 4, 7), Err(MathError::OutOfRange));
+        assert_eq!(
+            count_coprime_in_range(1, 10, 0),
+            Err(MathError::PositifIntegerRequired)
+        );
+        for n in 1..60 {
+            for (lo, hi) in [(0, 0), (0, 59), (1, 100), (17, 17), (13, 240)] {
+                assert_eq!(count_coprime_in_range(lo, hi, n), Ok(naive(lo, hi, n)));
+            }
+        }
+        // the primorial 53# = 32589158477190044730 is past u64, 47# is not
+        let primorial: u64 = 614889782588491410;
+        assert_eq!(
+            count_coprime_in_range(1, primorial, primorial),
+            Ok(Factorization::new(primorial as u128).unwrap().totient() as u64)
+        );
+        assert_eq!(count_coprime_in_range(0, u64::MAX, 2), Ok(1 << 63));
+    }
+
+    #[test]
+    fn test_count_with_gcd() {
+        let naive = |lo: u64, hi: u64, n: u64, g: u64| {
+            (lo..=hi)
+                .filter(|&x| crate::algebra::gcd::gcd_u128(x as u128, n as u128) == g as u128)
+                .count() as u64
+        };
+        assert_eq!(count_with_gcd(1, 100, 12, 4), Ok(17));
+        assert_eq!(count_with_gcd(1, 100, 12, 5), Ok(0));
+        assert_eq!(count_with_gcd(1, 100, 12, 0), Ok(0));
+        assert_eq!(count_with_gcd(0, 0, 12, 12), Ok(1));
+        assert_eq!(count_with_gcd(5, 7, 12, 4), Ok(0));
+        assert_eq!(count_with_gcd(9, 3, 12, 4), Err(MathError::OutOfRange));
+        for n in 1..40 {
+            for g in 0..=n {
+                for (lo, hi) in [(0, 50), (7, 120), (12, 12)] {
+                    assert_eq!(count_with_gcd(lo, hi, n, g), Ok(naive(lo, hi, n, g)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_inclusion_exclusion_derangements() {
+        // permutations of 5 fixing none of the 5 points
+        let factorial = |n: u32| (1..=n as i128).product::<i128>();
+        let count = inclusion_exclusion(5, |mask| factorial(5 - mask.count_ones()));
+        assert_eq!(count, Ok(derangements(5).unwrap() as i128));
+        assert_eq!(inclusion_exclusion(64, |_| 0), Err(MathError::OutOfRange));
+    }
+}
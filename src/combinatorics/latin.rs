@@ -0,0 +1,142 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::algebra::modular::mod_mul;
+use crate::algebra::prime_nbr::is_prime_u64;
+use crate::errors::MathError;
+
+/// An `n × n` array of symbols `0..n`, rows first.
+pub type LatinSquare = Vec<Vec<u64>>;
+
+/// The addition table of `Z/nZ`, `L[i][j] = (i + j) mod n`:
+/// - `cyclic_latin_square(3) = [[0, 1, 2], [1, 2, 0], [2, 0, 1]]`
+///
+/// Every row is the previous one shifted left by one. `MathError::OutOfRange` if
+/// `n == 0`.
+pub fn cyclic_latin_square(n: u64) -> Result<LatinSquare, MathError> {
+    if n == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    Ok((0..n)
+        .map(|i| (0..n).map(|j| (i + j) % n).collect())
+        .collect())
+}
+
+/// The complete set of `p - 1` mutually orthogonal Latin squares of prime order `p`,
+/// `L_k[i][j] = k·i + j` over GF(p) for `k = 1..p`:
+/// - `mutually_orthogonal_latin_squares(3)`: `[[0, 1, 2], [1, 2, 0], [2, 0, 1]]` and
+///   `[[0, 1, 2], [2, 0, 1], [1, 2, 0]]`
+///
+/// Superimposing `L_k` and `L_l` gives every pair `(s, t)` once, because
+/// `k·i + j = s, l·i + j = t` has the unique solution `i = (s - t)/(k - l)` when
+/// `k ≠ l` in a field. `p - 1` is the most any order allows. `p²(p - 1)` entries, so
+/// meant for small orders. `MathError::OutOfRange` unless `p` is prime.
+pub fn mutually_orthogonal_latin_squares(p: u64) -> Result<Vec<LatinSquare>, MathError> {
+    if !is_prime_u64(p) {
+        return Err(MathError::OutOfRange);
+    }
+    (1..p)
+        .map(|k| {
+            (0..p)
+                .map(|i| {
+                    let row_shift = mod_mul(k, i, p)?;
+                    Ok((0..p).map(|j| (row_shift + j) % p).collect())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether `square` is `n × n` with each of `0..n` exactly once in every row and
+/// column:
+/// - `is_latin_square(&[vec![0, 1], vec![1, 0]]) = true`
+///
+/// The empty square counts as Latin.
+pub fn is_latin_square(square: &[Vec<u64>]) -> bool {
+    let n = square.len();
+    square
+        .iter()
+        .all(|row| is_permutation(n, row.iter().copied()))
+        && (0..n).all(|j| is_permutation(n, square.iter().map(|row| row[j])))
+}
+
+/// Whether `symbols` lists each of `0..n` exactly once.
+fn is_permutation(n: usize, mut symbols: impl Iterator<Item = u64>) -> bool {
+    let mut seen = BTreeSet::new();
+    symbols.all(|s| s < n as u64 && seen.insert(s)) && seen.len() == n
+}
+
+/// Whether two Latin squares of the same order are orthogonal, every pair of symbols
+/// `(a[i][j], b[i][j])` appearing exactly once:
+/// - the two squares of `mutually_orthogonal_latin_squares(3)` are
+///
+/// `false` for squares of different shapes.
+pub fn are_orthogonal(a: &[Vec<u64>], b: &[Vec<u64>]) -> bool {
+    let n = a.len();
+    if b.len() != n || a.iter().chain(b).any(|row| row.len() != n) {
+        return false;
+    }
+    let mut pairs = BTreeSet::new();
+    a.iter()
+        .zip(b)
+        .all(|(ra, rb)| ra.iter().zip(rb).all(|(&x, &y)| pairs.insert((x, y))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_cyclic_latin_square() {
+        assert_eq!(
+            cyclic_latin_square(3),
+            Ok(vec![vec![0, 1, 2], vec![1, 2, 0], vec![2, 0, 1]])
+        );
+        for n in 1..12 {
+            assert!(is_latin_square(&cyclic_latin_square(n).unwrap()));
+        }
+        assert_eq!(cyclic_latin_square(0), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_mutually_orthogonal_latin_squares() {
+        let squares = mutually_orthogonal_latin_squares(3).unwrap();
+        assert_eq!(
+            squares,
+            vec![
+                vec![vec![0, 1, 2], vec![1, 2, 0], vec![2, 0, 1]],
+                vec![vec![0, 1, 2], vec![2, 0, 1], vec![1, 2, 0]],
+            ]
+        );
+        for p in [2, 5, 7, 11] {
+            let squares = mutually_orthogonal_latin_squares(p).unwrap();
+            assert_eq!(squares.len() as u64, p - 1);
+            for (k, a) in squares.iter().enumerate() {
+                assert!(is_latin_square(a));
+                for b in &squares[k + 1..] {
+                    assert!(are_orthogonal(a, b));
+                }
+            }
+        }
+        for n in [0, 1, 4, 9] {
+            assert_eq!(
+                mutually_orthogonal_latin_squares(n),
+                Err(MathError::OutOfRange)
+            );
+        }
+    }
+
+    #[test]
+    fn test_checkers() {
+        assert!(is_latin_square(&[]));
+        assert!(is_latin_square(&[vec![0, 1], vec![1, 0]]));
+        assert!(!is_latin_square(&[vec![0, 1], vec![0, 1]]));
+        assert!(!is_latin_square(&[vec![0, 2], vec![2, 0]]));
+        assert!(!is_latin_square(&[vec![0, 1], vec![1]]));
+        let square = cyclic_latin_square(4).unwrap();
+        // pairs on the diagonal only
+        assert!(!are_orthogonal(&square, &square));
+        assert!(!are_orthogonal(&square, &square[..3]));
+    }
+}
@@ -0,0 +1,23 @@
+pub mod bits;
+pub mod catalan;
+// panic-free outside tests, as the gcd core in `algebra`
+#[cfg_attr(
+    not(test),
+    deny(
+        clippy::arithmetic_side_effects,
+        clippy::indexing_slicing,
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic
+    )
+)]
+pub mod comb_mod;
+pub mod inclusion_exclusion;
+pub mod latin;
+pub mod pairing;
+pub mod paley;
+pub mod partitions;
+pub mod sets;
+#[cfg(feature = "std")]
+pub mod stirling;
+pub mod valuation;
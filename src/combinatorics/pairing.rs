@@ -0,0 +1,165 @@
+use crate::errors::MathError;
+
+/// Szudzik's pairing of two `u64` into one `u128`, a bijection onto all of `u128`:
+/// - `pair_encode(0, 0) = 0`, `pair_encode(0, 1) = 1`, `pair_encode(1, 0) = 2`,
+///   `pair_encode(1, 1) = 3`
+/// - `pair_encode(u64::MAX, u64::MAX) = u128::MAX`
+///
+/// Every pair with `max(a, b) < m` lands below `m²`, so no value is wasted and none can
+/// overflow. Inverse of `pair_decode`.
+pub fn pair_encode(a: u64, b: u64) -> u128 {
+    let (a, b) = (a as u128, b as u128);
+    if a < b { b * b + a } else { a * a + a + b }
+}
+
+/// Inverse of `pair_encode`, defined for every `u128`:
+/// - `pair_decode(3) = (1, 1)`
+pub fn pair_decode(n: u128) -> (u64, u64) {
+    let (a, b) = szudzik_unpair(n);
+    // isqrt(n) < 2^64 and n - isqrt(n)² <= 2·isqrt(n), so both halves fit
+    (a as u64, b as u64)
+}
+
+/// Szudzik's pairing on `u128`, `b² + a` if `a < b` and `a² + a + b` otherwise:
+/// - `szudzik_pair(2, 3) = 11`
+///
+/// `MathError::Overflow` if the result exceeds `u128`, which starts once
+/// `max(a, b) >= 2^64`.
+pub fn szudzik_pair(a: u128, b: u128) -> Result<u128, MathError> {
+    if a < b {
+        b.checked_mul(b).and_then(|v| v.checked_add(a))
+    } else {
+        a.checked_mul(a)
+            .and_then(|v| v.checked_add(a))
+            .and_then(|v| v.checked_add(b))
+    }
+    .ok_or(MathError::Overflow)
+}
+
+/// Inverse of `szudzik_pair`: with `s = isqrt(n)` and `r = n - s²`, `(r, s)` if `r < s`,
+/// else `(s, r - s)`.
+/// - `szudzik_unpair(11) = (2, 3)`
+pub fn szudzik_unpair(n: u128) -> (u128, u128) {
+    let s = n.isqrt();
+    let r = n - s * s;
+    if r < s { (r, s) } else { (s, r - s) }
+}
+
+/// Cantor's pairing `(a + b)(a + b + 1)/2 + b`, enumerating the pairs diagonal by
+/// diagonal:
+/// - `cantor_pair(1, 0) = 1`, `cantor_pair(0, 1) = 2`
+/// - `cantor_pair(47, 32) = 3192`
+///
+/// `MathError::Overflow` if the result exceeds `u128`, which can happen from
+/// `a + b ≈ 2^64.5`, so some `u64` pairs do not fit either; `pair_encode` always does.
+pub fn cantor_pair(a: u128, b: u128) -> Result<u128, MathError> {
+    a.checked_add(b)
+        .and_then(triangle)
+        .and_then(|t| t.checked_add(b))
+        .ok_or(MathError::Overflow)
+}
+
+/// Inverse of `cantor_pair`, defined for every `u128`:
+/// - `cantor_unpair(3192) = (47, 32)`
+///
+/// Finds the diagonal `w` with `w(w + 1)/2 <= n < (w + 1)(w + 2)/2`, starting from
+/// `isqrt(2n)` and correcting by a step or two, since `8n + 1` overflows for large `n`.
+pub fn cantor_unpair(n: u128) -> (u128, u128) {
+    let mut w = match n.checked_mul(2) {
+        Some(double) => double.isqrt(),
+        None => 2 * (n / 2).isqrt() + 1,
+    };
+    while triangle(w).is_none_or(|t| t > n) {
+        w -= 1;
+    }
+    while triangle(w + 1).is_some_and(|t| t <= n) {
+        w += 1;
+    }
+    // the first loop left triangle(w) defined and at most n
+    let b = n - triangle(w).unwrap_or(n);
+    (w - b, b)
+}
+
+/// `w(w + 1)/2`, halving the even factor first so only a result past `u128` overflows.
+fn triangle(w: u128) -> Option<u128> {
+    if w.is_multiple_of(2) {
+        (w / 2).checked_mul(w + 1)
+    } else {
+        w.checked_mul(w.checked_add(1)? / 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pair_encode() {
+        assert_eq!(pair_encode(0, 0), 0);
+        assert_eq!(pair_encode(0, 1), 1);
+        assert_eq!(pair_encode(1, 0), 2);
+        assert_eq!(pair_encode(1, 1), 3);
+        assert_eq!(pair_encode(0, 2), 4);
+        assert_eq!(pair_encode(u64::MAX, u64::MAX), u128::MAX);
+        assert_eq!(pair_decode(u128::MAX), (u64::MAX, u64::MAX));
+        assert_eq!(pair_decode(3), (1, 1));
+
+        // the pairs below 100 fill 0..10000 exactly
+        let mut seen = alloc::vec![false; 10_000];
+        for a in 0..100 {
+            for b in 0..100 {
+                let n = pair_encode(a, b);
+                assert!(!seen[n as usize]);
+                seen[n as usize] = true;
+                assert_eq!(pair_decode(n), (a, b));
+            }
+        }
+        for (a, b) in [(u64::MAX, 0), (0, u64::MAX), (1 << 63, u64::MAX - 1)] {
+            assert_eq!(pair_decode(pair_encode(a, b)), (a, b));
+        }
+    }
+
+    #[test]
+    fn test_szudzik() {
+        assert_eq!(szudzik_pair(2, 3), Ok(11));
+        assert_eq!(szudzik_unpair(11), (2, 3));
+        assert_eq!(
+            szudzik_pair(u64::MAX as u128, u64::MAX as u128),
+            Ok(u128::MAX)
+        );
+        assert_eq!(szudzik_pair(1 << 64, 0), Err(MathError::Overflow));
+        assert_eq!(szudzik_pair(0, 1 << 64), Err(MathError::Overflow));
+        for n in (0..5000).chain(u128::MAX - 5000..=u128::MAX) {
+            let (a, b) = szudzik_unpair(n);
+            assert_eq!(szudzik_pair(a, b), Ok(n));
+        }
+    }
+
+    #[test]
+    fn test_cantor() {
+        assert_eq!(cantor_pair(0, 0), Ok(0));
+        assert_eq!(cantor_pair(1, 0), Ok(1));
+        assert_eq!(cantor_pair(0, 1), Ok(2));
+        assert_eq!(cantor_pair(47, 32), Ok(3192));
+        assert_eq!(cantor_unpair(3192), (47, 32));
+        // walks the diagonals in order
+        let mut n = 0;
+        for w in 0..60 {
+            for b in 0..=w {
+                assert_eq!(cantor_pair(w - b, b), Ok(n));
+                assert_eq!(cantor_unpair(n), (w - b, b));
+                n += 1;
+            }
+        }
+        for n in (0..5000).chain(u128::MAX - 5000..=u128::MAX) {
+            let (a, b) = cantor_unpair(n);
+            assert_eq!(cantor_pair(a, b), Ok(n));
+        }
+        assert_eq!(
+            cantor_pair(u64::MAX as u128, u64::MAX as u128),
+            Err(MathError::Overflow)
+        );
+        assert_eq!(cantor_pair(u128::MAX, 0), Err(MathError::Overflow));
+        assert_eq!(cantor_pair(0, u128::MAX), Err(MathError::Overflow));
+    }
+}
@@ -0,0 +1,142 @@
+use alloc::vec;
+
+use crate::algebra::gf2::BitMatrix;
+use crate::algebra::modular::first_quadratic_nonresidue;
+use crate::algebra::prime_nbr::is_prime_u64;
+use crate::errors::MathError;
+
+/// The Paley graph of a prime `p ≡ 1 (mod 4)`: vertices `0..p`, with `x ~ y` when
+/// `x - y` is a nonzero square mod `p`.
+///
+/// `-1` is a square for such `p`, so `x - y` and `y - x` agree and the graph is
+/// undirected. Adjacency rows are bitsets, `p²` bits in all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaleyGraph {
+    p: u64,
+    adjacency: BitMatrix,
+}
+
+/// The Paley graph on `p` vertices:
+/// - `paley_graph(5)` is the 5-cycle `0 - 1 - 2 - 3 - 4 - 0`, the squares mod 5 being
+///   1 and 4
+///
+/// Marks the `(p - 1)/2` squares `k² mod p` once, then fills every row as a shift of
+/// row 0, since `x ~ y` depends on `x - y` only. O(p²) time and bits.
+/// `MathError::OutOfRange` unless `p` is a prime `≡ 1 (mod 4)`, `Overflow` if `p` does
+/// not fit in `usize`.
+pub fn paley_graph(p: u64) -> Result<PaleyGraph, MathError> {
+    if p % 4 != 1 || !is_prime_u64(p) {
+        return Err(MathError::OutOfRange);
+    }
+    let n = usize::try_from(p).map_err(|_| MathError::Overflow)?;
+    let mut is_square = vec![false; n];
+    for k in 1..=p / 2 {
+        is_square[(k as u128 * k as u128 % p as u128) as usize] = true;
+    }
+    let mut adjacency = BitMatrix::new(n, n);
+    for x in 0..n {
+        for (d, _) in is_square.iter().enumerate().filter(|&(_, &s)| s) {
+            adjacency.set(x, (x + d) % n, true);
+        }
+    }
+    Ok(PaleyGraph { p, adjacency })
+}
+
+impl PaleyGraph {
+    /// Number of vertices, `p`.
+    pub fn order(&self) -> u64 {
+        self.p
+    }
+
+    /// Adjacency matrix; row `x` is the bitset of the neighbours of `x`.
+    pub fn adjacency(&self) -> &BitMatrix {
+        &self.adjacency
+    }
+
+    /// Whether `x` and `y` are joined; `false` if either is not a vertex.
+    pub fn is_adjacent(&self, x: u64, y: u64) -> bool {
+        x < self.p && y < self.p && self.adjacency.get(x as usize, y as usize)
+    }
+
+    /// Number of neighbours of `x`, `(p - 1)/2` for every vertex as the graph is regular;
+    /// 0 if `x` is not a vertex.
+    pub fn degree(&self, x: u64) -> u64 {
+        (0..self.p).filter(|&y| self.is_adjacent(x, y)).count() as u64
+    }
+
+    /// Whether `x ↦ n·x mod p`, with `n` the least non-residue, maps the graph onto its
+    /// complement:
+    /// - `true` for every Paley graph
+    ///
+    /// Multiplying by a non-residue swaps squares and non-squares, so `x - y` is a square
+    /// exactly when `n·x - n·y` is not. Checks every pair against the adjacency rows,
+    /// O(p²), so a certificate rather than an assumption.
+    pub fn is_self_complementary(&self) -> bool {
+        let Ok(n) = first_quadratic_nonresidue(self.p) else {
+            return false;
+        };
+        let image = |x: u64| (x as u128 * n as u128 % self.p as u128) as u64;
+        (0..self.p).all(|x| {
+            (0..self.p)
+                .filter(|&y| y != x)
+                .all(|y| self.is_adjacent(x, y) != self.is_adjacent(image(x), image(y)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::modular::legendre_symbol;
+
+    #[test]
+    fn test_paley_graph() {
+        let pentagon = paley_graph(5).unwrap();
+        for x in 0..5 {
+            assert!(pentagon.is_adjacent(x, (x + 1) % 5));
+            assert!(!pentagon.is_adjacent(x, (x + 2) % 5));
+            assert!(!pentagon.is_adjacent(x, x));
+        }
+        assert_eq!(pentagon.order(), 5);
+        assert!(!pentagon.is_adjacent(0, 5));
+
+        for p in [13, 17, 29, 37, 101] {
+            let graph = paley_graph(p).unwrap();
+            assert_eq!(graph.adjacency().rows() as u64, p);
+            for x in 0..p {
+                assert_eq!(graph.degree(x), (p - 1) / 2);
+                for y in 0..p {
+                    let square = x != y && legendre_symbol(x + p - y, p) == Ok(1);
+                    assert_eq!(graph.is_adjacent(x, y), square);
+                    assert_eq!(graph.is_adjacent(x, y), graph.is_adjacent(y, x));
+                }
+            }
+            assert!(graph.is_self_complementary());
+        }
+
+        for p in [0, 1, 2, 3, 7, 9, 25] {
+            assert_eq!(paley_graph(p), Err(MathError::OutOfRange));
+        }
+    }
+
+    #[test]
+    fn test_paley_17_has_no_4_clique() {
+        // P(17) has no clique of 4, the extremal graph for the Ramsey number R(4, 4) = 18
+        let graph = paley_graph(17).unwrap();
+        let joined = |v: &[u64]| {
+            v.iter()
+                .enumerate()
+                .all(|(i, &a)| v[i + 1..].iter().all(|&b| graph.is_adjacent(a, b)))
+        };
+        for a in 0..17 {
+            for b in a + 1..17 {
+                for c in b + 1..17 {
+                    for d in c + 1..17 {
+                        assert!(!joined(&[a, b, c, d]));
+                    }
+                }
+            }
+        }
+        assert!(joined(&[0, 1, 2]));
+    }
+}
@@ -0,0 +1,150 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::errors::MathError;
+
+/// Number of partitions p(n), by Euler's pentagonal number recurrence.
+///
+/// - `partition_count(5) = 7` (5, 4+1, 3+2, 3+1+1, 2+2+1, 2+1+1+1, 1+1+1+1+1)
+///
+/// Exact while p(n) fits in `i128` (n < 1900 or so); `MathError::Overflow` beyond that.
+pub fn partition_count(n: usize) -> Result<u128, MathError> {
+    let mut p = vec![0u128; n + 1]; // every entry kept below i128::MAX
+    p[0] = 1;
+    for m in 1..=n {
+        let mut sum: i128 = 0;
+        for k in 1.. {
+            // generalized pentagonal numbers k(3k - 1)/2 and k(3k + 1)/2
+            let first = k * (3 * k - 1) / 2;
+            if first > m {
+                break;
+            }
+            let second = k * (3 * k + 1) / 2;
+            let mut term = p[m - first] as i128;
+            if second <= m {
+                term = term
+                    .checked_add(p[m - second] as i128)
+                    .ok_or(MathError::Overflow)?;
+            }
+            sum = if k % 2 == 1 {
+                sum.checked_add(term)
+            } else {
+                sum.checked_sub(term)
+            }
+            .ok_or(MathError::Overflow)?;
+        }
+        p[m] = sum as u128;
+    }
+    Ok(p[n])
+}
+
+/// Number of partitions of `n` into distinct parts.
+///
+/// - `partition_count_distinct(6) = 4` (6, 5+1, 4+2, 3+2+1)
+pub fn partition_count_distinct(n: usize) -> Result<u128, MathError> {
+    // 0/1 knapsack: each part used at most once
+    let mut ways = vec![0u128; n + 1];
+    ways[0] = 1;
+    for part in 1..=n {
+        for total in (part..=n).rev() {
+            ways[total] = ways[total]
+                .checked_add(ways[total - part])
+                .ok_or(MathError::Overflow)?;
+        }
+    }
+    Ok(ways[n])
+}
+
+/// Number of partitions of `n` into odd parts (equal to the distinct count, by Euler).
+///
+/// - `partition_count_odd(6) = 4` (5+1, 3+3, 3+1+1+1, 1+1+1+1+1+1)
+pub fn partition_count_odd(n: usize) -> Result<u128, MathError> {
+    let odd_parts: Vec<usize> = (1..=n).step_by(2).collect();
+    count_with_parts(n, &odd_parts)
+}
+
+/// Number of partitions of `n` using only parts from `parts` (each any number of times).
+///
+/// Duplicate parts are counted once; a zero part is `MathError::OutOfRange`:
+/// - `partitions_with_parts_in(10, &[1, 2, 5]) = 10`
+pub fn partitions_with_parts_in(n: usize, parts: &[usize]) -> Result<u128, MathError> {
+    if parts.contains(&0) {
+        return Err(MathError::OutOfRange);
+    }
+    let mut unique = parts.to_vec();
+    unique.sort_unstable();
+    unique.dedup();
+    count_with_parts(n, &unique)
+}
+
+/// Unbounded knapsack over distinct positive parts.
+fn count_with_parts(n: usize, parts: &[usize]) -> Result<u128, MathError> {
+    let mut ways = vec![0u128; n + 1];
+    ways[0] = 1;
+    for &part in parts.iter().filter(|&&part| part <= n) {
+        for total in part..=n {
+            ways[total] = ways[total]
+                .checked_add(ways[total - part])
+                .ok_or(MathError::Overflow)?;
+        }
+    }
+    Ok(ways[n])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_count() {
+        let expected = [1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42];
+        for (n, &p) in expected.iter().enumerate() {
+            assert_eq!(partition_count(n), Ok(p), "p({})", n);
+        }
+        assert_eq!(partition_count(100), Ok(190_569_292));
+        assert_eq!(
+            partition_count(1000),
+            Ok(24_061_467_864_032_622_473_692_149_727_991)
+        );
+    }
+
+    #[test]
+    fn test_partition_count_overflow() {
+        assert_eq!(partition_count(2000), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_partition_count_distinct() {
+        let expected = [1, 1, 1, 2, 2, 3, 4, 5, 6, 8, 10];
+        for (n, &q) in expected.iter().enumerate() {
+            assert_eq!(partition_count_distinct(n), Ok(q), "q({})", n);
+        }
+        assert_eq!(partition_count_distinct(100), Ok(444_793));
+    }
+
+    #[test]
+    fn test_euler_distinct_equals_odd() {
+        for n in 0..200 {
+            assert_eq!(partition_count_distinct(n), partition_count_odd(n));
+        }
+    }
+
+    #[test]
+    fn test_partitions_with_parts_in() {
+        assert_eq!(partitions_with_parts_in(10, &[1, 2, 5]), Ok(10));
+        assert_eq!(partitions_with_parts_in(10, &[5, 2, 1, 2]), Ok(10));
+        assert_eq!(partitions_with_parts_in(7, &[2, 4]), Ok(0));
+        assert_eq!(partitions_with_parts_in(0, &[3]), Ok(1));
+        assert_eq!(partitions_with_parts_in(5, &[]), Ok(0));
+        assert_eq!(
+            partitions_with_parts_in(5, &[0, 1]),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_unrestricted_parts_give_partition_count() {
+        let all: Vec<usize> = (1..=60).collect();
+        assert_eq!(partitions_with_parts_in(60, &all), partition_count(60));
+    }
+}
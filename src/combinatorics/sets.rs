@@ -0,0 +1,117 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::algebra::prime_nbr::is_prime_u64;
+use crate::errors::MathError;
+
+/// Erdős–Turán Sidon set `{2pk + (k² mod p) : 0 <= k < p}` for an odd prime `p`:
+/// - `erdos_turan_sidon(3) = [0, 7, 13]`
+///
+/// `p` elements below `2p²`, so about `√(n/2)` up to `n`, near the `√n` maximum. Two
+/// equal sums `a + b = c + d` would agree both in the quotient by `2p`, giving
+/// `k + l`, and in the remainder, giving `k² + l²` mod `p`, and those two determine
+/// the pair `{k, l}` modulo a prime. Ascending. Errors with `OutOfRange` unless `p` is
+/// an odd prime and `Overflow` if `2p²` exceeds `u64::MAX`.
+pub fn erdos_turan_sidon(p: u64) -> Result<Vec<u64>, MathError> {
+    if p < 3 || !is_prime_u64(p) {
+        return Err(MathError::OutOfRange);
+    }
+    p.checked_mul(p)
+        .and_then(|square| square.checked_mul(2))
+        .ok_or(MathError::Overflow)?;
+    Ok((0..p)
+        .map(|k| 2 * p * k + (k as u128 * k as u128 % p as u128) as u64)
+        .collect())
+}
+
+/// Greedy sum-free set in `1..=n`: each `x` in turn joins unless `x = a + b` for members
+/// `a` and `b`, not necessarily distinct:
+/// - `greedy_sum_free(10) = [1, 3, 5, 7, 9]`
+///
+/// Starting from 1 this keeps the odd numbers, `⌈n/2⌉` of them, the largest possible
+/// size. O(n·k log k) for `k` members kept.
+pub fn greedy_sum_free(n: u64) -> Vec<u64> {
+    let mut members = Vec::new();
+    let mut taken = BTreeSet::new();
+    for x in 1..=n {
+        if !members.iter().any(|&a| x > a && taken.contains(&(x - a))) {
+            members.push(x);
+            taken.insert(x);
+        }
+    }
+    members
+}
+
+/// Whether all sums `a + b` with `a <= b` from `set` are distinct:
+/// - `is_sidon(&[1, 2, 5, 11]) = true`
+/// - `is_sidon(&[1, 2, 3, 4]) = false`, as `1 + 4 = 2 + 3`
+///
+/// A repeated element counts as a collision. O(k² log k) for `k` elements.
+pub fn is_sidon(set: &[u64]) -> bool {
+    let mut sums = BTreeSet::new();
+    set.iter().enumerate().all(|(i, &a)| {
+        set[..=i]
+            .iter()
+            .all(|&b| sums.insert(a as u128 + b as u128))
+    })
+}
+
+/// Whether no `a + b = c` holds with `a`, `b`, `c` in `set`, `a = b` allowed:
+/// - `is_sum_free(&[1, 3, 5, 7]) = true`
+/// - `is_sum_free(&[2, 3, 5]) = false`
+///
+/// Zero is never sum-free, as `0 + 0 = 0`. O(k² log k) for `k` elements.
+pub fn is_sum_free(set: &[u64]) -> bool {
+    let members: BTreeSet<u64> = set.iter().copied().collect();
+    set.iter().enumerate().all(|(i, &a)| {
+        set[i..]
+            .iter()
+            .all(|&b| a.checked_add(b).is_none_or(|c| !members.contains(&c)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_erdos_turan_sidon() {
+        assert_eq!(erdos_turan_sidon(3), Ok(vec![0, 7, 13]));
+        for p in [5, 7, 11, 101, 499] {
+            let set = erdos_turan_sidon(p).unwrap();
+            assert_eq!(set.len() as u64, p);
+            assert!(set.windows(2).all(|w| w[0] < w[1]));
+            assert!(*set.last().unwrap() < 2 * p * p);
+            assert!(is_sidon(&set), "p = {p}");
+        }
+        assert_eq!(erdos_turan_sidon(9), Err(MathError::OutOfRange));
+        assert_eq!(erdos_turan_sidon(2), Err(MathError::OutOfRange));
+        assert_eq!(erdos_turan_sidon(u64::MAX - 58), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_greedy_sum_free() {
+        assert_eq!(greedy_sum_free(10), vec![1, 3, 5, 7, 9]);
+        assert_eq!(greedy_sum_free(0), vec![]);
+        for n in 0..60 {
+            let set = greedy_sum_free(n);
+            assert_eq!(set.len() as u64, n.div_ceil(2));
+            assert!(is_sum_free(&set));
+        }
+    }
+
+    #[test]
+    fn test_checkers() {
+        assert!(is_sidon(&[1, 2, 5, 11]));
+        assert!(!is_sidon(&[1, 2, 3, 4]));
+        assert!(!is_sidon(&[4, 4]));
+        assert!(is_sidon(&[]));
+        assert!(is_sidon(&[u64::MAX, u64::MAX - 1]));
+        assert!(is_sum_free(&[1, 3, 5, 7]));
+        assert!(!is_sum_free(&[2, 3, 5]));
+        assert!(!is_sum_free(&[2, 4]));
+        assert!(!is_sum_free(&[0]));
+        assert!(is_sum_free(&[u64::MAX]));
+    }
+}
@@ -0,0 +1,118 @@
+use core::f64::consts::TAU;
+
+use crate::errors::MathError;
+
+/// Below this, `ln n!` is taken from the exact factorial instead of the series.
+const SERIES_START: u64 = 10;
+
+/// `ln n!` by Stirling's series with four correction terms:
+///
+/// `ln n! ≈ n ln n - n + ½ ln(2πn) + 1/(12n) - 1/(360n³) + 1/(1260n⁵) - 1/(1680n⁷)`
+///
+/// The series is enveloping, so the truncation error is below the first omitted term
+/// `1/(1188n⁹)`, under `1e-12` for `n >= 10`; smaller `n` use the exact factorial. What
+/// remains is `f64` rounding, a few ulps of the result:
+/// - `factorial_approx_ln(5) = ln 120 ≈ 4.787`
+/// - `factorial_approx_ln(1000) ≈ 5912.128`, where `1000!` has 2568 digits
+pub fn factorial_approx_ln(n: u64) -> f64 {
+    if n < SERIES_START {
+        return exact_factorial_ln(n);
+    }
+    let x = n as f64;
+    x * x.ln() - x + 0.5 * (TAU * x).ln() + correction(n)
+}
+
+/// `ln C(n, k)`, from Stirling's series for each of the three factorials.
+///
+/// The leading terms are combined as `k ln(n/k) - (n-k) ln(1 - k/n)`, so small `k` does
+/// not lose precision to cancellation between `ln n!` and `ln (n-k)!`. The truncation
+/// error is below `3e-12`, the sum of the three factorial bounds:
+/// - `binomial_approx_ln(10, 3) ≈ ln 120`
+/// - `binomial_approx_ln(10⁶, 5·10⁵) ≈ 693140.047`
+///
+/// `MathError::OutOfRange` if `k > n`.
+pub fn binomial_approx_ln(n: u64, k: u64) -> Result<f64, MathError> {
+    if k > n {
+        return Err(MathError::OutOfRange);
+    }
+    let k = k.min(n - k);
+    if k == 0 {
+        return Ok(0.0);
+    }
+    let m = n - k;
+    let (xn, xk, xm) = (n as f64, k as f64, m as f64);
+    let leading = xk * (xn / xk).ln() - xm * (-xk / xn).ln_1p() + 0.5 * (xn / (TAU * xk * xm)).ln();
+    Ok(leading + correction(n) - correction(k) - correction(m))
+}
+
+/// `ln n!` minus the leading terms `n ln n - n + ½ ln(2πn)`, for `n >= 1`.
+fn correction(n: u64) -> f64 {
+    let x = n as f64;
+    if n < SERIES_START {
+        return exact_factorial_ln(n) - (x * x.ln() - x + 0.5 * (TAU * x).ln());
+    }
+    let inv = 1.0 / x;
+    let inv2 = inv * inv;
+    // Horner form of 1/(12x) - 1/(360x³) + 1/(1260x⁵) - 1/(1680x⁷)
+    inv * (1.0 / 12.0 - inv2 * (1.0 / 360.0 - inv2 * (1.0 / 1260.0 - inv2 / 1680.0)))
+}
+
+fn exact_factorial_ln(n: u64) -> f64 {
+    ((2..=n).product::<u64>() as f64).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn factorial_ln(n: u64) -> f64 {
+        (2..=n).map(|k| (k as f64).ln()).sum()
+    }
+
+    fn binomial(n: u64, k: u64) -> u128 {
+        (0..k).fold(1u128, |acc, i| acc * (n - i) as u128 / (i + 1) as u128)
+    }
+
+    #[test]
+    fn test_factorial_approx_ln() {
+        assert_eq!(factorial_approx_ln(0), 0.0);
+        assert_eq!(factorial_approx_ln(1), 0.0);
+        assert!((factorial_approx_ln(5) - 120f64.ln()).abs() < 1e-15);
+        // 20! is exact in u64, 34! in u128
+        let f20 = (1..=20u64).product::<u64>() as f64;
+        assert!((factorial_approx_ln(20) - f20.ln()).abs() < 1e-13);
+        let f34 = (1..=34u128).product::<u128>() as f64;
+        assert!((factorial_approx_ln(34) - f34.ln()).abs() < 1e-13);
+        for n in [10, 50, 170, 1000] {
+            let exact = factorial_ln(n);
+            assert!((factorial_approx_ln(n) - exact).abs() < 1e-12 * exact);
+        }
+        // far past anything representable
+        assert!((factorial_approx_ln(1_000_000) - 12_815_518.384_658_169).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_binomial_approx_ln() {
+        assert_eq!(
+            binomial_approx_ln(10, 3).unwrap(),
+            binomial_approx_ln(10, 7).unwrap()
+        );
+        assert!((binomial_approx_ln(10, 3).unwrap() - 120f64.ln()).abs() < 1e-12);
+        for (n, k) in [(1, 1), (20, 1), (40, 20), (60, 7), (100, 50), (120, 3)] {
+            let exact = (binomial(n, k) as f64).ln();
+            assert!(
+                (binomial_approx_ln(n, k).unwrap() - exact).abs() < 1e-11,
+                "C({n}, {k})"
+            );
+        }
+        // small k against a huge n: ln C(n, 1) = ln n
+        let n = 1u64 << 60;
+        assert!((binomial_approx_ln(n, 1).unwrap() - (n as f64).ln()).abs() < 1e-9);
+        // central coefficient ~ 2^n / √(πn/2)
+        let n = 1_000_000u64;
+        let central = n as f64 * 2f64.ln() - 0.5 * (core::f64::consts::PI * n as f64 / 2.0).ln();
+        assert!((binomial_approx_ln(n, n / 2).unwrap() - central).abs() < 1e-6);
+        assert_eq!(binomial_approx_ln(5, 0), Ok(0.0));
+        assert_eq!(binomial_approx_ln(3, 4), Err(MathError::OutOfRange));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::algebra::divisors::trial_factors;
+use crate::algebra::prime_nbr::miller_rabin_u128;
+use crate::errors::MathError;
+
+/// Exponent of the prime `p` in `n!`, by Legendre's formula
+/// `v_p(n!) = ⌊n/p⌋ + ⌊n/p²⌋ + ⌊n/p³⌋ + ...`:
+/// - `factorial_valuation(10, 2) = 5 + 2 + 1 = 8`
+/// - `factorial_valuation(100, 5) = 20 + 4 = 24`
+///
+/// Equivalently `(n - s_p(n)) / (p - 1)` with `s_p` the base-`p` digit sum.
+/// `MathError::OutOfRange` unless `p` is prime.
+pub fn factorial_valuation(n: u64, p: u64) -> Result<u64, MathError> {
+    if p < 2 || !miller_rabin_u128(p as u128, 32)?.is_prime() {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(legendre(n, p))
+}
+
+/// Number of trailing zeros of `n!` written in `base`:
+/// - `trailing_zeros_of_factorial(100, 10) = 24`
+/// - `trailing_zeros_of_factorial(10, 9) = 2`
+///
+/// For `base = Π pᵢ^eᵢ` this is `min ⌊v_pᵢ(n!) / eᵢ⌋`; neither the largest prime nor
+/// the largest exponent alone decides it. `10!` has eight factors 2 and four factors 3,
+/// so it ends in 4 zeros in base `12 = 2²·3` but only 2 in base `9 = 3²` and in base
+/// `16 = 2⁴`. The base is factored by trial division. `MathError::OutOfRange` if
+/// `base < 2`.
+pub fn trailing_zeros_of_factorial(n: u64, base: u64) -> Result<u64, MathError> {
+    if base < 2 {
+        return Err(MathError::OutOfRange);
+    }
+    Ok(trial_factors(base as u128)
+        .into_iter()
+        .map(|(p, e)| legendre(n, p as u64) / e as u64)
+        .min()
+        .unwrap_or(0))
+}
+
+/// Exponent of the prime `p` in `C(n, k)`, by Kummer's theorem: it equals the number
+/// of carries when adding `k` and `n - k` in base `p`:
+/// - `binomial_valuation(10, 3, 2) = 3`, as `011 + 111` carries three times and
+///   `C(10, 3) = 120 = 2³·15`
+///
+/// O(log_p n), with no factorials involved. `MathError::OutOfRange` if `k > n` or `p`
+/// is not prime.
+pub fn binomial_valuation(n: u64, k: u64, p: u64) -> Result<u64, MathError> {
+    if k > n || p < 2 || !miller_rabin_u128(p as u128, 32)?.is_prime() {
+        return Err(MathError::OutOfRange);
+    }
+    carries(k, n - k, p)
+}
+
+/// Number of carries when adding `a` and `b` digit by digit in `base`:
+/// - `carries(9, 1, 10) = 1`
+/// - `carries(999, 1, 10) = 3`
+///
+/// `MathError::OutOfRange` if `base < 2`.
+pub fn carries(mut a: u64, mut b: u64, base: u64) -> Result<u64, MathError> {
+    if base < 2 {
+        return Err(MathError::OutOfRange);
+    }
+    let (mut count, mut carry) = (0, 0);
+    while a > 0 || b > 0 || carry > 0 {
+        // the digit sum is below 2·base, so it fits even for base near 2^64
+        let sum = (a % base) as u128 + (b % base) as u128 + carry as u128;
+        carry = (sum >= base as u128) as u64;
+        count += carry;
+        (a, b) = (a / base, b / base);
+    }
+    Ok(count)
+}
+
+/// `Σ ⌊n/p^k⌋`, dividing `n` down instead of raising `p` so nothing overflows.
+fn legendre(mut n: u64, p: u64) -> u64 {
+    let mut total = 0;
+    while n >= p {
+        n /= p;
+        total += n;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn factorial(n: u128) -> u128 {
+        (1..=n).product()
+    }
+
+    #[test]
+    fn test_factorial_valuation() {
+        assert_eq!(factorial_valuation(10, 2), Ok(8));
+        assert_eq!(factorial_valuation(100, 5), Ok(24));
+        assert_eq!(factorial_valuation(4, 5), Ok(0));
+        assert_eq!(factorial_valuation(0, 2), Ok(0));
+        // against the exact factorial while it fits in u128
+        for n in 0..=34u64 {
+            for p in [2u64, 3, 5, 7, 11, 31] {
+                let mut f = factorial(n as u128);
+                let mut v = 0;
+                while f.is_multiple_of(p as u128) {
+                    f /= p as u128;
+                    v += 1;
+                }
+                assert_eq!(factorial_valuation(n, p), Ok(v), "v_{p}({n}!)");
+            }
+        }
+        // digit-sum form at the top of the range
+        let n = u64::MAX;
+        assert_eq!(factorial_valuation(n, 2), Ok(n - n.count_ones() as u64));
+        assert_eq!(factorial_valuation(10, 4), Err(MathError::OutOfRange));
+        assert_eq!(factorial_valuation(10, 1), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_carries() {
+        assert_eq!(carries(9, 1, 10), Ok(1));
+        assert_eq!(carries(999, 1, 10), Ok(3));
+        assert_eq!(carries(123, 456, 10), Ok(0));
+        assert_eq!(carries(0b1011, 0b0111, 2), Ok(4));
+        assert_eq!(carries(u64::MAX - 1, u64::MAX - 1, u64::MAX), Ok(1));
+        assert_eq!(carries(u64::MAX, 1, 2), Ok(64));
+        assert_eq!(carries(5, 5, 1), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_binomial_valuation() {
+        assert_eq!(binomial_valuation(10, 3, 2), Ok(3));
+        // C(n, k) is odd iff k's bits are a subset of n's (Lucas)
+        assert_eq!(binomial_valuation(15, 6, 2), Ok(0));
+        // agrees with Legendre on n! / (k! (n-k)!)
+        for n in 0..200u64 {
+            for k in 0..=n {
+                for p in [2, 3, 5, 13] {
+                    let legendre = legendre(n, p) - legendre(k, p) - legendre(n - k, p);
+                    assert_eq!(binomial_valuation(n, k, p), Ok(legendre));
+                }
+            }
+        }
+        assert_eq!(binomial_valuation(3, 4, 2), Err(MathError::OutOfRange));
+        assert_eq!(binomial_valuation(10, 3, 6), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_trailing_zeros_of_factorial() {
+        assert_eq!(trailing_zeros_of_factorial(100, 10), Ok(24));
+        assert_eq!(trailing_zeros_of_factorial(10, 12), Ok(4));
+        assert_eq!(trailing_zeros_of_factorial(10, 9), Ok(2));
+        assert_eq!(trailing_zeros_of_factorial(10, 2), Ok(8));
+        assert_eq!(trailing_zeros_of_factorial(10, 16), Ok(2));
+        assert_eq!(trailing_zeros_of_factorial(10, 11), Ok(0));
+        for n in 0..=34u64 {
+            for base in [2u64, 6, 8, 10, 12, 18, 36, 60, 97] {
+                let mut f = factorial(n as u128);
+                let mut zeros = 0;
+                while f.is_multiple_of(base as u128) {
+                    f /= base as u128;
+                    zeros += 1;
+                }
+                assert_eq!(
+                    trailing_zeros_of_factorial(n, base),
+                    Ok(zeros),
+                    "{n}! in base {base}"
+                );
+            }
+        }
+        assert_eq!(
+            trailing_zeros_of_factorial(10, 1),
+            Err(MathError::OutOfRange)
+        );
+    }
+}
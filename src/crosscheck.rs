@@ -0,0 +1,318 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::bigu::BigU;
+use crate::algebra::gcd::gcd_u64;
+use crate::algebra::lcm::lcm_u64;
+use crate::algebra::modular::{crt_reconstruct, mod_mul, mod_pow};
+use crate::algebra::prime_nbr::{is_prime_u64, splitmix64};
+use crate::errors::MathError;
+
+/// A case where a fast routine and its `BigU` reference disagree.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Divergence {
+    pub check: &'static str,
+    /// Replays this case through `reproduce(check, seed)`.
+    pub seed: u64,
+    pub inputs: Vec<u64>,
+    /// Results widened to `u128`, booleans as 0 or 1.
+    pub fast: Result<u128, MathError>,
+    pub reference: Result<u128, MathError>,
+}
+
+/// Outcome of one check in `crosscheck`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CrosscheckReport {
+    pub check: &'static str,
+    pub cases: u64,
+    pub divergences: Vec<Divergence>,
+}
+
+impl CrosscheckReport {
+    pub fn passed(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+struct Case {
+    inputs: Vec<u64>,
+    fast: Result<u128, MathError>,
+    reference: Result<u128, MathError>,
+}
+
+type CaseFn = fn(&mut u64) -> Case;
+
+const CHECKS: [(&str, CaseFn); 6] = [
+    ("mod_mul", mod_mul_case),
+    ("mod_pow", mod_pow_case),
+    ("gcd_u64", gcd_case),
+    ("lcm_u64", lcm_case),
+    ("is_prime_u64", is_prime_case),
+    ("crt_reconstruct", crt_case),
+];
+
+/// Names of the checks run by `crosscheck`, in order.
+pub fn checks() -> impl Iterator<Item = &'static str> {
+    CHECKS.iter().map(|&(name, _)| name)
+}
+
+/// Runs `cases` random inputs through every fast `u64` routine in `checks()` and a slow
+/// schoolbook reference on `BigU`, collecting the disagreements:
+/// - `crosscheck(42, 1_000).iter().all(CrosscheckReport::passed) = true`
+///
+/// Inputs mix uniform 64-bit words, words with a random number of leading zeros, and
+/// edge values around `2^32` and `2^64`. Each case draws from its own seed, derived from
+/// `seed` by splitmix64 and recorded in any `Divergence`, so a failure found in a long
+/// run replays alone with `reproduce`. A differential test for maintainers and for
+/// new targets; the references are orders of magnitude slower than the code they check.
+pub fn crosscheck(seed: u64, cases: u64) -> Vec<CrosscheckReport> {
+    CHECKS
+        .iter()
+        .map(|&(check, case)| run(check, case, seed, cases))
+        .collect()
+}
+
+/// `crosscheck` restricted to one check. `MathError::OutOfRange` for an unknown name.
+pub fn crosscheck_one(check: &str, seed: u64, cases: u64) -> Result<CrosscheckReport, MathError> {
+    let &(check, case) = lookup(check)?;
+    Ok(run(check, case, seed, cases))
+}
+
+/// Replays the case drawn from a `Divergence::seed`, `None` if the two sides now agree.
+/// `MathError::OutOfRange` for an unknown check name.
+pub fn reproduce(check: &str, seed: u64) -> Result<Option<Divergence>, MathError> {
+    let &(check, case) = lookup(check)?;
+    Ok(diverges(check, case, seed))
+}
+
+fn lookup(check: &str) -> Result<&'static (&'static str, CaseFn), MathError> {
+    CHECKS
+        .iter()
+        .find(|&&(name, _)| name == check)
+        .ok_or(MathError::OutOfRange)
+}
+
+fn run(check: &'static str, case: CaseFn, seed: u64, cases: u64) -> CrosscheckReport {
+    let mut state = seed;
+    let divergences = (0..cases)
+        .filter_map(|_| diverges(check, case, splitmix64(&mut state)))
+        .collect();
+    CrosscheckReport {
+        check,
+        cases,
+        divergences,
+    }
+}
+
+fn diverges(check: &'static str, case: CaseFn, seed: u64) -> Option<Divergence> {
+    let mut state = seed;
+    let Case {
+        inputs,
+        fast,
+        reference,
+    } = case(&mut state);
+    (fast != reference).then_some(Divergence {
+        check,
+        seed,
+        inputs,
+        fast,
+        reference,
+    })
+}
+
+const EDGE_VALUES: [u64; 10] = [
+    0,
+    1,
+    2,
+    3,
+    (1 << 32) - 1,
+    1 << 32,
+    (1 << 32) + 1,
+    1 << 63,
+    u64::MAX - 1,
+    u64::MAX,
+];
+
+fn draw(state: &mut u64) -> u64 {
+    let choice = splitmix64(state);
+    match choice % 8 {
+        0 => EDGE_VALUES[(choice >> 3) as usize % EDGE_VALUES.len()],
+        1..=3 => splitmix64(state) >> ((choice >> 3) % 64),
+        _ => splitmix64(state),
+    }
+}
+
+fn big(n: u64) -> BigU {
+    BigU::from_u64(n)
+}
+
+fn narrow(value: &BigU, limit: u128) -> Result<u128, MathError> {
+    value
+        .to_u128()
+        .filter(|&v| v <= limit)
+        .ok_or(MathError::Overflow)
+}
+
+fn reference_gcd(a: u64, b: u64) -> BigU {
+    let (mut a, mut b) = (big(a), big(b));
+    while !b.is_zero() {
+        let r = &a % &b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+// strong probable prime test to the first 12 prime bases, exact below 3.3·10^24
+fn reference_is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(&p) = BASES.iter().find(|&&p| n.is_multiple_of(p)) {
+        return n == p;
+    }
+    let (one, n_big) = (big(1), big(n));
+    let minus_one = big(n - 1);
+    let s = (n - 1).trailing_zeros();
+    let d = big((n - 1) >> s);
+    BASES.iter().all(|&a| {
+        let mut x = big(a).mod_pow(&d, &n_big).unwrap_or_default();
+        if x == one || x == minus_one {
+            return true;
+        }
+        (1..s).any(|_| {
+            x = &(&x * &x) % &n_big;
+            x == minus_one
+        })
+    })
+}
+
+fn mod_mul_case(state: &mut u64) -> Case {
+    let (a, b, m) = (draw(state), draw(state), draw(state).max(1));
+    Case {
+        inputs: vec![a, b, m],
+        fast: mod_mul(a, b, m).map(u128::from),
+        reference: narrow(&(&(&big(a) * &big(b)) % &big(m)), u64::MAX as u128),
+    }
+}
+
+fn mod_pow_case(state: &mut u64) -> Case {
+    let (base, exp, m) = (draw(state), draw(state), draw(state).max(1));
+    Case {
+        inputs: vec![base, exp, m],
+        fast: mod_pow(base, exp, m).map(u128::from),
+        reference: big(base)
+            .mod_pow(&big(exp), &big(m))
+            .and_then(|r| narrow(&r, u64::MAX as u128)),
+    }
+}
+
+fn gcd_case(state: &mut u64) -> Case {
+    let (a, b) = (draw(state), draw(state));
+    Case {
+        inputs: vec![a, b],
+        fast: Ok(gcd_u64(a, b) as u128),
+        reference: narrow(&reference_gcd(a, b), u64::MAX as u128),
+    }
+}
+
+fn lcm_case(state: &mut u64) -> Case {
+    let (a, b) = (draw(state), draw(state));
+    let reference = match a == 0 || b == 0 {
+        true => Ok(0),
+        false => (&big(a) * &big(b))
+            .div_rem(&reference_gcd(a, b))
+            .and_then(|(l, _)| narrow(&l, u64::MAX as u128)),
+    };
+    Case {
+        inputs: vec![a, b],
+        fast: lcm_u64(a, b).map(u128::from),
+        reference,
+    }
+}
+
+fn is_prime_case(state: &mut u64) -> Case {
+    // odd inputs, and products of two small factors for the pseudoprime-shaped cases
+    let n = match splitmix64(state) % 4 {
+        0 => (draw(state) >> 32 | 1).wrapping_mul(draw(state) >> 32 | 1),
+        _ => draw(state) | 1,
+    };
+    Case {
+        inputs: vec![n],
+        fast: Ok(is_prime_u64(n) as u128),
+        reference: Ok(reference_is_prime(n) as u128),
+    }
+}
+
+fn crt_case(state: &mut u64) -> Case {
+    // picks the value first, so the reference never has to solve the system
+    let k = 1 + (splitmix64(state) % 3) as usize;
+    let moduli: Vec<u64> = (0..k).map(|_| draw(state).max(1)).collect();
+    let product = moduli.iter().fold(big(1), |acc, &m| &acc * &big(m));
+    let value = (0..k).fold(big(0), |acc, _| {
+        &(&(&acc * &big(1 << 32)) * &big(1 << 32)) + &big(splitmix64(state))
+    });
+    let value = &value % &product;
+    let residues: Vec<(u64, u64)> = moduli
+        .iter()
+        .map(|&m| ((&value % &big(m)).to_u128().unwrap_or_default() as u64, m))
+        .collect();
+    let coprime = moduli
+        .iter()
+        .enumerate()
+        .all(|(i, &m)| moduli[..i].iter().all(|&e| reference_gcd(m, e) == big(1)));
+    Case {
+        inputs: residues.iter().flat_map(|&(r, m)| [r, m]).collect(),
+        fast: crt_reconstruct(&residues),
+        reference: match coprime {
+            true => narrow(&value, u128::MAX),
+            false => Err(MathError::NoSolution),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crosscheck_passes() {
+        let reports = crosscheck(42, 1_000);
+        assert_eq!(reports.len(), CHECKS.len());
+        for report in &reports {
+            assert_eq!(report.cases, 1_000);
+            assert!(report.passed(), "{:?}", report.divergences.first());
+        }
+        assert!(checks().eq(reports.iter().map(|r| r.check)));
+    }
+
+    #[test]
+    fn test_cases_replay_from_seed() {
+        for &(_, case) in &CHECKS {
+            for seed in 0..50 {
+                let (first, second) = (case(&mut { seed }), case(&mut { seed }));
+                assert_eq!(first.inputs, second.inputs);
+                assert_eq!(first.fast, second.fast);
+            }
+        }
+        assert_eq!(reproduce("mod_pow", 7), Ok(None));
+    }
+
+    #[test]
+    fn test_reference_is_prime() {
+        let primes = [2u64, 3, 37, 41, 1_000_000_007, u64::MAX - 58];
+        let composites = [0u64, 1, 4, 561, 3_215_031_751, 3_825_123_056_546_413_051];
+        assert!(primes.iter().all(|&p| reference_is_prime(p)));
+        assert!(!composites.iter().any(|&c| reference_is_prime(c)));
+    }
+
+    #[test]
+    fn test_crosscheck_one() {
+        let report = crosscheck_one("crt_reconstruct", 1, 200).unwrap();
+        assert_eq!((report.check, report.cases), ("crt_reconstruct", 200));
+        assert!(report.passed());
+        assert_eq!(crosscheck_one("sqrt", 1, 10), Err(MathError::OutOfRange));
+        assert_eq!(reproduce("sqrt", 1), Err(MathError::OutOfRange));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::errors::MathError;
+
+/// k-th digit (1-indexed) of Champernowne's constant `0.123456789101112…`.
+///
+/// - `champernowne_digit(9) = 9`
+/// - `champernowne_digit(10) = 1` and `champernowne_digit(11) = 0` (from "10")
+///
+/// Skips whole blocks of equal-length numbers, so it runs in O(log k).
+pub fn champernowne_digit(k: u64) -> Result<u8, MathError> {
+    concatenated_digit(1, u64::MAX, k, 10)
+}
+
+/// k-th digit (1-indexed) of `start, start + 1, ..., end` written in `base`
+/// and concatenated.
+///
+/// - `concatenated_digit(8, 12, 3, 10) = 1` since "89101112" has '1' third
+/// - `concatenated_digit(0, 3, 5, 2) = 1` since "0 1 10 11" is "011011"
+///
+/// Errors with `OutOfRange` for a base outside `2..=256`, `start > end`, `k = 0`, or `k` past
+/// the last digit.
+pub fn concatenated_digit(start: u64, end: u64, k: u64, base: u32) -> Result<u8, MathError> {
+    if !(2..=256).contains(&base) || start > end || k == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    let base = base as u128;
+    let (start, end) = (start as u128, end as u128);
+    let mut remaining = k as u128 - 1; // 0-indexed within the rest of the string
+
+    let mut length = 1u32;
+    let mut low = 0u128; // smallest number with `length` digits
+    loop {
+        let high = low.max(1) * base - 1; // largest number with `length` digits
+        let block_start = start.max(low);
+        let block_end = end.min(high);
+        if block_start <= block_end {
+            let count = block_end - block_start + 1;
+            let block_digits = count * length as u128;
+            if remaining < block_digits {
+                let number = block_start + remaining / length as u128;
+                let position = remaining % length as u128; // from the most significant digit
+                let shift = length as u128 - 1 - position;
+                let digit = number / base.pow(shift as u32) % base;
+                return Ok(digit as u8);
+            }
+            remaining -= block_digits;
+        }
+        if high >= end {
+            return Err(MathError::OutOfRange);
+        }
+        low = high + 1;
+        length += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn naive(start: u64, end: u64, base: u32) -> Vec<u8> {
+        (start..=end)
+            .flat_map(|n| {
+                let mut digits = Vec::new();
+                let mut n = n;
+                loop {
+                    digits.push((n % base as u64) as u8);
+                    n /= base as u64;
+                    if n == 0 {
+                        break;
+                    }
+                }
+                digits.reverse();
+                digits
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_champernowne_first_digits() {
+        let expected = naive(1, 1000, 10);
+        for (i, &d) in expected.iter().enumerate() {
+            assert_eq!(champernowne_digit(i as u64 + 1), Ok(d), "digit {}", i + 1);
+        }
+    }
+
+    #[test]
+    fn test_champernowne_known_values() {
+        // Project Euler 40: d1 · d10 · d100 · ... · d1000000 = 210
+        let product: u32 = [1, 10, 100, 1_000, 10_000, 100_000, 1_000_000]
+            .iter()
+            .map(|&k| champernowne_digit(k).unwrap() as u32)
+            .product();
+        assert_eq!(product, 210);
+        assert_eq!(champernowne_digit(0), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_champernowne_huge_index() {
+        // the string of all 1..=9 digit numbers has 788_888_889 digits
+        assert_eq!(champernowne_digit(788_888_889), Ok(9));
+        assert_eq!(champernowne_digit(788_888_890), Ok(1)); // "1000000000"
+        assert!(champernowne_digit(u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_concatenated_digit_matches_naive() {
+        for (start, end, base) in [
+            (8, 12, 10),
+            (0, 3, 2),
+            (0, 200, 16),
+            (95, 1005, 10),
+            (7, 7, 3),
+        ] {
+            let expected = naive(start, end, base);
+            for (i, &d) in expected.iter().enumerate() {
+                assert_eq!(concatenated_digit(start, end, i as u64 + 1, base), Ok(d));
+            }
+            let past = expected.len() as u64 + 1;
+            assert_eq!(
+                concatenated_digit(start, end, past, base),
+                Err(MathError::OutOfRange)
+            );
+        }
+    }
+
+    #[test]
+    fn test_concatenated_digit_errors() {
+        assert_eq!(concatenated_digit(5, 4, 1, 10), Err(MathError::OutOfRange));
+        assert_eq!(concatenated_digit(1, 4, 1, 1), Err(MathError::OutOfRange));
+        assert_eq!(concatenated_digit(1, 4, 0, 10), Err(MathError::OutOfRange));
+    }
+}
@@ -0,0 +1,269 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::errors::MathError;
+
+/// A property of the digit string of an integer, read most significant digit first,
+/// without leading zeros; 0 is the single digit `0`.
+///
+/// `digit_dp` only ever keeps one count per state, so the number of distinct states
+/// reachable is what bounds the work, not the size of the limit.
+pub trait DigitMachine {
+    type State: Clone + Ord;
+
+    /// State before any digit.
+    fn start(&self) -> Self::State;
+
+    /// State after `digit`, or `None` if no number continuing this way qualifies.
+    fn step(&self, state: &Self::State, digit: u8) -> Option<Self::State>;
+
+    /// Whether a number whose digits end in `state` qualifies.
+    fn accept(&self, state: &Self::State) -> bool;
+}
+
+/// How many of `0, 1, ..., limit` written in `base` satisfy `machine`:
+/// - `digit_dp(100, 10, &NoAdjacentEqual) = 91`, all but 11, 22, ..., 99 and 100
+/// - `digit_dp(10^18, 10, &DigitSumDivisible::new(9)?) = 111111111111111112`
+///
+/// Runs over the digits of `limit` once, carrying a count per state for the numbers
+/// already below `limit`'s prefix, plus the single number equal to it: O(digits · base
+/// · states) steps. Errors with `OutOfRange` for a base outside `2..=256`, `Overflow` if
+/// the count is `2^128`, every `u128` qualifying.
+pub fn digit_dp<M: DigitMachine>(limit: u128, base: u32, machine: &M) -> Result<u128, MathError> {
+    if !(2..=256).contains(&base) {
+        return Err(MathError::OutOfRange);
+    }
+    let mut digits = Vec::new();
+    let mut n = limit;
+    while n > 0 {
+        digits.push((n % base as u128) as u8);
+        n /= base as u128;
+    }
+    digits.reverse();
+
+    let start = machine.start();
+    let mut tight = Some(start.clone());
+    // numbers below the limit's prefix, by state once their first nonzero digit is in
+    let mut below: BTreeMap<M::State, u128> = BTreeMap::new();
+    // whether the all-zero prefix, not yet a started number, is below the limit's prefix
+    let mut zeros_below = false;
+    for (i, &d) in digits.iter().enumerate() {
+        let mut next = BTreeMap::new();
+        let mut add = |state: Option<M::State>, count: u128| {
+            if let Some(state) = state {
+                *next.entry(state).or_insert(0) += count;
+            }
+        };
+        for (state, &count) in &below {
+            for digit in 0..base {
+                add(machine.step(state, digit as u8), count);
+            }
+        }
+        if zeros_below {
+            for digit in 1..base {
+                add(machine.step(&start, digit as u8), 1);
+            }
+        }
+        if let Some(state) = &tight {
+            // the leading digit is nonzero, so only 0 there leaves a number unstarted
+            for digit in (i == 0) as u8..d {
+                add(machine.step(state, digit), 1);
+            }
+        }
+        zeros_below |= i == 0;
+        tight = tight.and_then(|state| machine.step(&state, d));
+        below = next;
+    }
+
+    // the tight path is the limit itself unless that is 0, which is counted on its own
+    let tight = tight.filter(|_| limit > 0);
+    below
+        .into_iter()
+        .map(|(state, count)| (Some(state), count))
+        .chain([(tight, 1), (machine.step(&start, 0), 1)])
+        .filter(|(state, _)| state.as_ref().is_some_and(|s| machine.accept(s)))
+        .try_fold(0u128, |total, (_, count)| total.checked_add(count))
+        .ok_or(MathError::Overflow)
+}
+
+/// Numbers with no two equal adjacent digits, like 1210 but not 1221.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoAdjacentEqual;
+
+impl DigitMachine for NoAdjacentEqual {
+    /// The last digit, if any.
+    type State = Option<u8>;
+
+    fn start(&self) -> Option<u8> {
+        None
+    }
+
+    fn step(&self, last: &Option<u8>, digit: u8) -> Option<Option<u8>> {
+        (*last != Some(digit)).then_some(Some(digit))
+    }
+
+    fn accept(&self, _last: &Option<u8>) -> bool {
+        true
+    }
+}
+
+/// Numbers whose digit sum is a multiple of `k`.
+#[derive(Debug, Clone, Copy)]
+pub struct DigitSumDivisible {
+    k: u64,
+}
+
+impl DigitSumDivisible {
+    /// `MathError::DivisionByZero` if `k == 0`.
+    pub fn new(k: u64) -> Result<Self, MathError> {
+        if k == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+        Ok(DigitSumDivisible { k })
+    }
+}
+
+impl DigitMachine for DigitSumDivisible {
+    /// The digit sum so far, modulo `k`.
+    type State = u64;
+
+    fn start(&self) -> u64 {
+        0
+    }
+
+    fn step(&self, sum: &u64, digit: u8) -> Option<u64> {
+        Some(((*sum as u128 + digit as u128) % self.k as u128) as u64)
+    }
+
+    fn accept(&self, sum: &u64) -> bool {
+        *sum == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits_of(mut n: u128, base: u32) -> Vec<u8> {
+        let mut digits = Vec::new();
+        loop {
+            digits.push((n % base as u128) as u8);
+            n /= base as u128;
+            if n == 0 {
+                break;
+            }
+        }
+        digits.reverse();
+        digits
+    }
+
+    fn naive<M: DigitMachine>(limit: u128, base: u32, machine: &M) -> u128 {
+        (0..=limit)
+            .filter(|&n| {
+                digits_of(n, base)
+                    .into_iter()
+                    .try_fold(machine.start(), |s, d| machine.step(&s, d))
+                    .is_some_and(|s| machine.accept(&s))
+            })
+            .count() as u128
+    }
+
+    /// Numbers without the digit string `13`.
+    struct NoThirteen;
+
+    impl DigitMachine for NoThirteen {
+        type State = bool;
+
+        fn start(&self) -> bool {
+            false
+        }
+
+        fn step(&self, after_one: &bool, digit: u8) -> Option<bool> {
+            (!(*after_one && digit == 3)).then_some(digit == 1)
+        }
+
+        fn accept(&self, _: &bool) -> bool {
+            true
+        }
+    }
+
+    /// Numbers without a digit 0, so not 0 itself, which is fed its single digit.
+    struct NonZero;
+
+    impl DigitMachine for NonZero {
+        type State = ();
+
+        fn start(&self) {}
+
+        fn step(&self, _: &(), digit: u8) -> Option<()> {
+            (digit != 0).then_some(())
+        }
+
+        fn accept(&self, _: &()) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_digit_dp() {
+        assert_eq!(digit_dp(100, 10, &NoAdjacentEqual), Ok(91));
+        let nines = DigitSumDivisible::new(9).unwrap();
+        assert_eq!(
+            digit_dp(1_000_000_000_000_000_000, 10, &nines),
+            Ok(111111111111111112)
+        );
+        assert_eq!(digit_dp(0, 10, &nines), Ok(1));
+        assert_eq!(digit_dp(0, 10, &NonZero), Ok(0));
+        assert_eq!(digit_dp(9, 10, &NonZero), Ok(9));
+        assert_eq!(digit_dp(5, 1, &NonZero), Err(MathError::OutOfRange));
+        assert_eq!(digit_dp(5, 257, &NonZero), Err(MathError::OutOfRange));
+        assert_eq!(
+            DigitSumDivisible::new(0).err(),
+            Some(MathError::DivisionByZero)
+        );
+
+        let sevens = DigitSumDivisible::new(7).unwrap();
+        for base in [2, 3, 10, 16] {
+            for limit in (0..700).chain([1023, 1024, 4095, 9999]) {
+                assert_eq!(
+                    digit_dp(limit, base, &NoAdjacentEqual),
+                    Ok(naive(limit, base, &NoAdjacentEqual))
+                );
+                assert_eq!(
+                    digit_dp(limit, base, &sevens),
+                    Ok(naive(limit, base, &sevens))
+                );
+                assert_eq!(
+                    digit_dp(limit, base, &NoThirteen),
+                    Ok(naive(limit, base, &NoThirteen))
+                );
+                assert_eq!(
+                    digit_dp(limit, base, &NonZero),
+                    Ok(naive(limit, base, &NonZero))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_digit_dp_large() {
+        // 0, and 9^len numbers of each length below 10^18
+        let expected = 1 + (1..=18).map(|len| 9u128.pow(len)).sum::<u128>();
+        assert_eq!(
+            digit_dp(10u128.pow(18) - 1, 10, &NoAdjacentEqual),
+            Ok(expected)
+        );
+        // alternating binary strings: 0, then one per length
+        assert_eq!(digit_dp(u128::MAX, 2, &NoAdjacentEqual), Ok(129));
+        assert_eq!(
+            digit_dp(u128::MAX, 256, &NonZero),
+            Ok((1..=16).map(|len| 255u128.pow(len)).sum())
+        );
+        let everything = DigitSumDivisible::new(1).unwrap();
+        assert_eq!(digit_dp(u128::MAX - 1, 10, &everything), Ok(u128::MAX));
+        assert_eq!(
+            digit_dp(u128::MAX, 10, &everything),
+            Err(MathError::Overflow)
+        );
+    }
+}
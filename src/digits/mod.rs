@@ -0,0 +1,3 @@
+pub mod concatenation;
+pub mod digit_dp;
+pub mod repunit;
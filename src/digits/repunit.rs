@@ -0,0 +1,143 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "internal-bigint")]
+use crate::algebra::bigu::BigU;
+use crate::algebra::prime_nbr::miller_rabin_u128;
+use crate::errors::MathError;
+
+/// Miller-Rabin rounds used to screen repunits (error below 2^-64 each).
+const REPUNIT_MR_ROUNDS: u32 = 32;
+
+/// Repunit `R_k = 11…1` (k ones) in the given base: `(base^k - 1) / (base - 1)`.
+///
+/// - `repunit(4, 10) = 1111`
+/// - `repunit(4, 2) = 15`
+///
+/// `MathError::OutOfRange` for `base < 2`, `MathError::Overflow` past `u128`.
+pub fn repunit(k: u32, base: u64) -> Result<u128, MathError> {
+    if base < 2 {
+        return Err(MathError::OutOfRange);
+    }
+    (0..k).try_fold(0u128, |acc, _| {
+        acc.checked_mul(base as u128)
+            .and_then(|r| r.checked_add(1))
+            .ok_or(MathError::Overflow)
+    })
+}
+
+/// `R_k` in the given base as an arbitrary-precision integer.
+#[cfg(feature = "internal-bigint")]
+pub fn repunit_big(k: u32, base: u64) -> Result<BigU, MathError> {
+    if base < 2 {
+        return Err(MathError::OutOfRange);
+    }
+    let base = BigU::from_u64(base);
+    let one = BigU::one();
+    Ok((0..k).fold(BigU::zero(), |acc, _| &(&acc * &base) + &one))
+}
+
+/// `true` if every digit of `n` in `base` is the same (single digits included).
+///
+/// - `is_repdigit(777, 10) = true`
+/// - `is_repdigit(0b1111, 2) = true`
+pub fn is_repdigit(mut n: u128, base: u64) -> Result<bool, MathError> {
+    if base < 2 {
+        return Err(MathError::OutOfRange);
+    }
+    let base = base as u128;
+    let digit = n % base;
+    while n > 0 {
+        if n % base != digit {
+            return Ok(false);
+        }
+        n /= base;
+    }
+    Ok(true)
+}
+
+/// Exponents `k <= limit` for which `R_k` in `base` is a (probable) prime.
+///
+/// Only prime `k` are tested, since `R_ab` is divisible by `R_a`. In base 10 the
+/// first exponents are 2, 19, 23; `MathError::Overflow` once a tested `R_k`
+/// exceeds `u128` (k > 39 in base 10).
+pub fn repunit_prime_exponents_upto(limit: u32, base: u64) -> Result<Vec<u32>, MathError> {
+    let mut exponents = Vec::new();
+    for k in 2..=limit {
+        let k_is_prime = (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0);
+        if !k_is_prime {
+            continue;
+        }
+        let r = repunit(k, base)?;
+        if miller_rabin_u128(r, REPUNIT_MR_ROUNDS)?.is_prime() {
+            exponents.push(k);
+        }
+    }
+    Ok(exponents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "internal-bigint")]
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn test_repunit() {
+        assert_eq!(repunit(0, 10), Ok(0));
+        assert_eq!(repunit(1, 10), Ok(1));
+        assert_eq!(repunit(4, 10), Ok(1111));
+        assert_eq!(repunit(4, 2), Ok(15));
+        assert_eq!(repunit(3, 16), Ok(0x111));
+        assert_eq!(
+            repunit(38, 10),
+            Ok(11_111_111_111_111_111_111_111_111_111_111_111_111)
+        );
+    }
+
+    #[test]
+    fn test_repunit_errors() {
+        assert!(repunit(39, 10).is_ok());
+        assert_eq!(repunit(40, 10), Err(MathError::Overflow));
+        assert_eq!(repunit(128, 2), Ok(u128::MAX));
+        assert_eq!(repunit(129, 2), Err(MathError::Overflow));
+        assert_eq!(repunit(3, 1), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_is_repdigit() {
+        assert_eq!(is_repdigit(777, 10), Ok(true));
+        assert_eq!(is_repdigit(7, 10), Ok(true));
+        assert_eq!(is_repdigit(0, 10), Ok(true));
+        assert_eq!(is_repdigit(778, 10), Ok(false));
+        assert_eq!(is_repdigit(0b1111, 2), Ok(true));
+        assert_eq!(is_repdigit(0xAAAA, 16), Ok(true));
+        assert_eq!(is_repdigit(5, 0), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_repunit_primes_base_10() {
+        assert_eq!(repunit_prime_exponents_upto(38, 10), Ok(vec![2, 19, 23]));
+        assert_eq!(
+            repunit_prime_exponents_upto(41, 10),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_repunit_primes_base_2_are_mersenne() {
+        assert_eq!(
+            repunit_prime_exponents_upto(127, 2),
+            Ok(vec![2, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127])
+        );
+    }
+
+    #[cfg(feature = "internal-bigint")]
+    #[test]
+    fn test_repunit_big() {
+        assert_eq!(repunit_big(38, 10).unwrap().to_u128(), repunit(38, 10).ok());
+        let r = repunit_big(50, 10).unwrap();
+        assert_eq!(r.to_string(), "1".repeat(50));
+        assert!(repunit_big(2, 1).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::algebra::modular::mod_pow;
+use crate::algebra::prime_nbr::miller_rabin;
+use crate::educational_crypto::{PRIME_MR_ROUNDS, random_prime};
+use crate::errors::MathError;
+
+/// Public group parameters: a safe prime `p = 2q + 1` and a primitive root `g` mod `p`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DhParams {
+    pub p: u64,
+    pub g: u64,
+}
+
+/// Safe prime of exactly `bits` bits and its smallest primitive root, derived from `seed`.
+///
+/// With `p = 2q + 1` the group order `p - 1` has only the prime factors 2 and `q`, so
+/// `g` generates all of `(Z/pZ)*` exactly when `g² ≠ 1` and `g^q ≠ 1`. Errors with
+/// `OutOfRange` unless `16 <= bits <= 63`.
+pub fn generate_params(bits: u32, seed: u64) -> Result<DhParams, MathError> {
+    if !(16..=63).contains(&bits) {
+        return Err(MathError::OutOfRange);
+    }
+    let mut state = seed;
+    let safe =
+        |q: u64| miller_rabin((2 * q + 1) as i64, PRIME_MR_ROUNDS).is_ok_and(|r| r.is_prime());
+    let q = random_prime(bits - 1, &mut state, safe)?;
+    let p = 2 * q + 1;
+
+    let mut g = 2;
+    while mod_pow(g, 2, p)? == 1 || mod_pow(g, q, p)? == 1 {
+        g += 1;
+    }
+    Ok(DhParams { p, g })
+}
+
+/// `g^secret mod p`, the value sent to the peer; `OutOfRange` unless `1 <= secret <= p - 2`.
+pub fn public_value(params: &DhParams, secret: u64) -> Result<u64, MathError> {
+    if secret == 0 || secret > params.p - 2 {
+        return Err(MathError::OutOfRange);
+    }
+    mod_pow(params.g, secret, params.p)
+}
+
+/// Shared secret `peer_public^secret = g^(ab) mod p`.
+///
+/// Rejects `peer_public` outside `[2, p - 2]` with `OutOfRange`: the values 0, 1 and
+/// `p - 1` would force the shared secret into a subgroup of order at most 2.
+pub fn derive_shared(params: &DhParams, secret: u64, peer_public: u64) -> Result<u64, MathError> {
+    if peer_public < 2 || peer_public > params.p - 2 {
+        return Err(MathError::OutOfRange);
+    }
+    if secret == 0 || secret > params.p - 2 {
+        return Err(MathError::OutOfRange);
+    }
+    mod_pow(peer_public, secret, params.p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_textbook_exchange() {
+        let params = DhParams { p: 23, g: 5 };
+        let (a, b) = (6, 15);
+        let (alice, bob) = (
+            public_value(&params, a).unwrap(),
+            public_value(&params, b).unwrap(),
+        );
+        assert_eq!((alice, bob), (8, 19));
+        assert_eq!(derive_shared(&params, a, bob), Ok(2));
+        assert_eq!(derive_shared(&params, b, alice), Ok(2));
+    }
+
+    #[test]
+    fn test_generated_params() {
+        for (bits, seed) in [(16, 1), (32, 2), (63, 3)] {
+            let params = generate_params(bits, seed).unwrap();
+            let (p, g) = (params.p, params.g);
+            let q = (p - 1) / 2;
+            assert_eq!(64 - p.leading_zeros(), bits);
+            assert!(miller_rabin(q as i64, 20).unwrap().is_prime());
+            assert!(miller_rabin(p as i64, 20).unwrap().is_prime());
+            assert_ne!(mod_pow(g, 2, p), Ok(1));
+            assert_ne!(mod_pow(g, q, p), Ok(1));
+
+            let (a, b) = (123_456 % (p - 2) + 1, 987_654_321 % (p - 2) + 1);
+            let alice = public_value(&params, a).unwrap();
+            let bob = public_value(&params, b).unwrap();
+            assert_eq!(
+                derive_shared(&params, a, bob),
+                derive_shared(&params, b, alice)
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let params = DhParams { p: 23, g: 5 };
+        assert_eq!(public_value(&params, 0), Err(MathError::OutOfRange));
+        assert_eq!(public_value(&params, 22), Err(MathError::OutOfRange));
+        assert_eq!(derive_shared(&params, 6, 1), Err(MathError::OutOfRange));
+        assert_eq!(derive_shared(&params, 6, 22), Err(MathError::OutOfRange));
+        assert_eq!(generate_params(64, 0), Err(MathError::OutOfRange));
+    }
+}
@@ -0,0 +1,35 @@
+//! Textbook public-key schemes assembled from the crate's own primitives.
+//!
+//! **Insecure by design**: 64-bit moduli, no padding, deterministic key generation
+//! from a caller-supplied seed. Meant for following each step by hand, never for
+//! protecting data.
+
+use crate::algebra::prime_nbr::{miller_rabin, splitmix64};
+use crate::errors::MathError;
+
+pub mod dh_toy;
+pub mod rabin;
+pub mod rsa_toy;
+
+const PRIME_MR_ROUNDS: u32 = 32;
+
+/// Pseudo-random prime with exactly `bits` bits (top two bits set) accepted by `accept`.
+///
+/// Setting the two top bits guarantees that the product of two such primes has
+/// exactly the sum of their bit lengths.
+pub(crate) fn random_prime(
+    bits: u32,
+    state: &mut u64,
+    accept: impl Fn(u64) -> bool,
+) -> Result<u64, MathError> {
+    if !(8..=63).contains(&bits) {
+        return Err(MathError::OutOfRange);
+    }
+    let top = 0b11 << (bits - 2);
+    loop {
+        let candidate = (splitmix64(state) >> (64 - bits)) | top | 1;
+        if accept(candidate) && miller_rabin(candidate as i64, PRIME_MR_ROUNDS)?.is_prime() {
+            return Ok(candidate);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use alloc::vec::Vec;
+
+use crate::algebra::modular::{crt, mod_pow};
+use crate::educational_crypto::random_prime;
+use crate::errors::MathError;
+
+/// Public modulus `n = p·q`; encryption is squaring mod `n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RabinPublicKey {
+    pub n: u64,
+}
+
+/// The factors `p, q ≡ 3 (mod 4)` of `n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RabinPrivateKey {
+    pub p: u64,
+    pub q: u64,
+}
+
+/// Key pair with an `n` of exactly `bits` bits, derived deterministically from `seed`.
+///
+/// Both primes are `≡ 3 (mod 4)` so square roots mod each are a single exponentiation.
+/// Errors with `OutOfRange` unless `16 <= bits <= 64`.
+pub fn keygen(bits: u32, seed: u64) -> Result<(RabinPublicKey, RabinPrivateKey), MathError> {
+    if !(16..=64).contains(&bits) {
+        return Err(MathError::OutOfRange);
+    }
+    let mut state = seed;
+    let blum = |p: u64| p % 4 == 3;
+    let p = random_prime(bits / 2, &mut state, blum)?;
+    let q = loop {
+        let q = random_prime(bits - bits / 2, &mut state, blum)?;
+        if q != p {
+            break q;
+        }
+    };
+    Ok((RabinPublicKey { n: p * q }, RabinPrivateKey { p, q }))
+}
+
+/// `c = m² mod n`; `OutOfRange` if `m >= n`.
+pub fn encrypt(message: u64, key: &RabinPublicKey) -> Result<u64, MathError> {
+    if message >= key.n {
+        return Err(MathError::OutOfRange);
+    }
+    mod_pow(message, 2, key.n)
+}
+
+/// The square roots of `c` mod `n`, sorted; the plaintext is one of them.
+///
+/// For `p ≡ 3 (mod 4)`, `c^((p+1)/4)` is a root mod `p`; the roots `±r_p`, `±r_q` are
+/// combined by CRT into (up to) four candidates. Real deployments add redundancy to
+/// the message to tell them apart. `OutOfRange` if `c >= n`.
+pub fn decrypt(ciphertext: u64, key: &RabinPrivateKey) -> Result<Vec<u64>, MathError> {
+    let (p, q) = (key.p, key.q);
+    if ciphertext >= p * q {
+        return Err(MathError::OutOfRange);
+    }
+    let rp = mod_pow(ciphertext, (p + 1) / 4, p)?;
+    let rq = mod_pow(ciphertext, (q + 1) / 4, q)?;
+
+    let mut roots = Vec::with_capacity(4);
+    for xp in [rp, (p - rp) % p] {
+        for xq in [rq, (q - rq) % q] {
+            roots.push(crt(&[(xp, p), (xq, q)])?.0);
+        }
+    }
+    roots.sort_unstable();
+    roots.dedup();
+    Ok(roots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_round_trip() {
+        for (bits, seed) in [(16, 1), (40, 5), (64, 77)] {
+            let (public, private) = keygen(bits, seed).unwrap();
+            assert_eq!(64 - public.n.leading_zeros(), bits);
+            assert_eq!(private.p % 4, 3);
+            assert_eq!(private.q % 4, 3);
+            let m = 4_242 % public.n;
+            let roots = decrypt(encrypt(m, &public).unwrap(), &private).unwrap();
+            assert_eq!(roots.len(), 4);
+            assert!(roots.contains(&m));
+            assert!(roots.contains(&(public.n - m)));
+        }
+    }
+
+    #[test]
+    fn test_small_example() {
+        // n = 7 · 11 = 77: 20² = 400 ≡ 15 and the other roots are 64, 13, 57
+        let private = RabinPrivateKey { p: 7, q: 11 };
+        assert_eq!(encrypt(20, &RabinPublicKey { n: 77 }), Ok(15));
+        assert_eq!(decrypt(15, &private), Ok(vec![13, 20, 57, 64]));
+        assert_eq!(decrypt(0, &private), Ok(vec![0]));
+        assert_eq!(decrypt(77, &private), Err(MathError::OutOfRange));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::algebra::modular::{crt, mod_inverse, mod_pow};
+use crate::educational_crypto::random_prime;
+use crate::errors::MathError;
+
+/// Public exponent `e = 2^16 + 1`.
+pub const PUBLIC_EXPONENT: u64 = 65_537;
+
+/// `(n, e)`: anyone can encrypt with `c = m^e mod n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RsaPublicKey {
+    pub n: u64,
+    pub e: u64,
+}
+
+/// The private exponent `d = e⁻¹ mod φ(n)` together with the factors of `n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RsaPrivateKey {
+    pub n: u64,
+    pub d: u64,
+    pub p: u64,
+    pub q: u64,
+}
+
+/// Key pair with an `n` of exactly `bits` bits, derived deterministically from `seed`.
+///
+/// `p` and `q` are distinct primes of about `bits / 2` bits with `e ∤ p - 1`, so that
+/// `e` is invertible mod `φ(n) = (p - 1)(q - 1)`. Errors with `OutOfRange` unless
+/// `16 <= bits <= 64`.
+pub fn keygen(bits: u32, seed: u64) -> Result<(RsaPublicKey, RsaPrivateKey), MathError> {
+    if !(16..=64).contains(&bits) {
+        return Err(MathError::OutOfRange);
+    }
+    let mut state = seed;
+    let coprime_to_e = |p: u64| !(p - 1).is_multiple_of(PUBLIC_EXPONENT);
+    let p = random_prime(bits / 2, &mut state, coprime_to_e)?;
+    let q = loop {
+        let q = random_prime(bits - bits / 2, &mut state, coprime_to_e)?;
+        if q != p {
+            break q;
+        }
+    };
+
+    let n = p * q;
+    let d = mod_inverse(PUBLIC_EXPONENT, (p - 1) * (q - 1))?;
+    Ok((
+        RsaPublicKey {
+            n,
+            e: PUBLIC_EXPONENT,
+        },
+        RsaPrivateKey { n, d, p, q },
+    ))
+}
+
+/// Textbook encryption `c = m^e mod n`; `OutOfRange` if `m >= n`.
+pub fn encrypt(message: u64, key: &RsaPublicKey) -> Result<u64, MathError> {
+    if message >= key.n {
+        return Err(MathError::OutOfRange);
+    }
+    mod_pow(message, key.e, key.n)
+}
+
+/// Decryption `m = c^d mod n`, computed mod `p` and mod `q` and recombined by CRT.
+///
+/// Works with exponents `d mod (p - 1)` and `d mod (q - 1)` on half-size moduli
+/// (Garner's speed-up). `OutOfRange` if `c >= n`.
+pub fn decrypt(ciphertext: u64, key: &RsaPrivateKey) -> Result<u64, MathError> {
+    if ciphertext >= key.n {
+        return Err(MathError::OutOfRange);
+    }
+    let mp = mod_pow(ciphertext, key.d % (key.p - 1), key.p)?;
+    let mq = mod_pow(ciphertext, key.d % (key.q - 1), key.q)?;
+    Ok(crt(&[(mp, key.p), (mq, key.q)])?.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for (bits, seed) in [(16, 1), (32, 7), (48, 42), (64, 2026)] {
+            let (public, private) = keygen(bits, seed).unwrap();
+            assert_eq!(64 - public.n.leading_zeros(), bits);
+            assert_eq!(public.n, private.p * private.q);
+            for m in [0, 1, 2, 12_345, public.n - 1] {
+                let c = encrypt(m, &public).unwrap();
+                assert_eq!(decrypt(c, &private), Ok(m));
+            }
+        }
+    }
+
+    #[test]
+    fn test_textbook_example() {
+        // p = 61, q = 53, e = 17, d = 2753
+        let public = RsaPublicKey { n: 3233, e: 17 };
+        let private = RsaPrivateKey {
+            n: 3233,
+            d: 2753,
+            p: 61,
+            q: 53,
+        };
+        assert_eq!(encrypt(65, &public), Ok(2790));
+        assert_eq!(decrypt(2790, &private), Ok(65));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(keygen(8, 0), Err(MathError::OutOfRange));
+        assert_eq!(keygen(65, 0), Err(MathError::OutOfRange));
+        let (public, private) = keygen(32, 3).unwrap();
+        assert_eq!(encrypt(public.n, &public), Err(MathError::OutOfRange));
+        assert_eq!(decrypt(public.n, &private), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_deterministic_from_seed() {
+        assert_eq!(keygen(40, 9), keygen(40, 9));
+        assert_ne!(keygen(40, 9), keygen(40, 10));
+    }
+}
@@ -1,16 +1,323 @@
+use core::fmt;
+
 use thiserror::Error;
 
+/// Every error of the crate: a `Domain` error when the input is outside what a function
+/// accepts, a `Compute` error when valid input has no representable answer. Match the
+/// outer variant to tell "fix the input" from "retry with a wider type or accept that
+/// there is no solution":
+/// - `MathError::Domain(_)`: negative, zero, empty or mismatched input
+/// - `MathError::Compute(_)`: overflow, no solution, no inverse
+///
+/// The associated constants keep the flat spellings, `MathError::Overflow` for
+/// `MathError::Compute(ComputeError::Overflow)`, usable in expressions and patterns.
+///
+/// `Display` writes English through `English`; `code` and the variants themselves are the
+/// locale-neutral data, and `render` writes the message through any `ErrorFormatter`.
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum MathError {
-    #[error("Division by zero")]
-    DivisionByZero,
+    #[error(transparent)]
+    Domain(#[from] DomainError),
+
+    #[error(transparent)]
+    Compute(#[from] ComputeError),
+}
 
-    #[error("Positif integer required")]
+/// The input is outside the domain of the function.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum DomainError {
+    DivisionByZero,
     PositifIntegerRequired,
+    OutOfRange,
+    EmptyInput,
+    DimensionMismatch,
+    DuplicateInput,
+}
 
-    #[error("Overflow")]
+/// The input is valid but the result does not exist or does not fit.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum ComputeError {
     Overflow,
+    /// The computation overflowed at this index of its input or output.
+    OverflowAt(usize),
+    NoSolution,
+    NoInverse,
+}
 
-    #[error("Out of range")]
-    OutOfRange,
+impl fmt::Display for DomainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        English.domain(self, f)
+    }
+}
+
+impl fmt::Display for ComputeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        English.compute(self, f)
+    }
+}
+
+#[allow(non_upper_case_globals)]
+impl MathError {
+    pub const DivisionByZero: MathError = MathError::Domain(DomainError::DivisionByZero);
+    pub const PositifIntegerRequired: MathError =
+        MathError::Domain(DomainError::PositifIntegerRequired);
+    pub const OutOfRange: MathError = MathError::Domain(DomainError::OutOfRange);
+    pub const EmptyInput: MathError = MathError::Domain(DomainError::EmptyInput);
+    pub const DimensionMismatch: MathError = MathError::Domain(DomainError::DimensionMismatch);
+    pub const DuplicateInput: MathError = MathError::Domain(DomainError::DuplicateInput);
+    pub const Overflow: MathError = MathError::Compute(ComputeError::Overflow);
+    pub const NoSolution: MathError = MathError::Compute(ComputeError::NoSolution);
+    pub const NoInverse: MathError = MathError::Compute(ComputeError::NoInverse);
+
+    pub fn is_domain(&self) -> bool {
+        matches!(self, MathError::Domain(_))
+    }
+
+    pub fn is_compute(&self) -> bool {
+        matches!(self, MathError::Compute(_))
+    }
+
+    /// A stable identifier of the error, the same in every language, for applications
+    /// that look messages up in their own catalogs:
+    /// - `MathError::DivisionByZero.code() = "division_by_zero"`
+    /// - `MathError::Compute(ComputeError::OverflowAt(3)).code() = "overflow_at"`, the
+    ///   index being in the variant
+    pub fn code(&self) -> &'static str {
+        match self {
+            MathError::Domain(e) => match e {
+                DomainError::DivisionByZero => "division_by_zero",
+                DomainError::PositifIntegerRequired => "positive_integer_required",
+                DomainError::OutOfRange => "out_of_range",
+                DomainError::EmptyInput => "empty_input",
+                DomainError::DimensionMismatch => "dimension_mismatch",
+                DomainError::DuplicateInput => "duplicate_input",
+            },
+            MathError::Compute(e) => match e {
+                ComputeError::Overflow => "overflow",
+                ComputeError::OverflowAt(_) => "overflow_at",
+                ComputeError::NoSolution => "no_solution",
+                ComputeError::NoInverse => "no_inverse",
+            },
+        }
+    }
+
+    /// The message in the language of `formatter`, as a value to print:
+    /// - `MathError::NoSolution.render(&English).to_string() = "No solution"`
+    /// - `MathError::NoSolution.render(&French).to_string() = "Aucune solution"`, with
+    ///   the `locale-fr` feature
+    pub fn render<'a, F: ErrorFormatter>(&'a self, formatter: &'a F) -> Rendered<'a, F> {
+        Rendered {
+            error: self,
+            formatter,
+        }
+    }
+}
+
+/// Writes error messages in one language. Implement it to present the crate's errors
+/// in another, matching on the variants rather than parsing the English text.
+pub trait ErrorFormatter {
+    fn domain(&self, error: &DomainError, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    fn compute(&self, error: &ComputeError, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Dispatches to `domain` or `compute`.
+    fn format(&self, error: &MathError, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match error {
+            MathError::Domain(e) => self.domain(e, f),
+            MathError::Compute(e) => self.compute(e, f),
+        }
+    }
+}
+
+/// An error paired with a formatter, returned by `MathError::render`.
+#[derive(Debug, Clone, Copy)]
+pub struct Rendered<'a, F> {
+    error: &'a MathError,
+    formatter: &'a F,
+}
+
+impl<F: ErrorFormatter> fmt::Display for Rendered<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.formatter.format(self.error, f)
+    }
+}
+
+/// The default messages, used by `Display`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+impl ErrorFormatter for English {
+    fn domain(&self, error: &DomainError, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match error {
+            DomainError::DivisionByZero => "Division by zero",
+            DomainError::PositifIntegerRequired => "Positive integer required",
+            DomainError::OutOfRange => "Out of range",
+            DomainError::EmptyInput => "Empty input",
+            DomainError::DimensionMismatch => "Dimension mismatch",
+            DomainError::DuplicateInput => "Duplicate input",
+        })
+    }
+
+    fn compute(&self, error: &ComputeError, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match error {
+            ComputeError::Overflow => f.write_str("Overflow"),
+            ComputeError::OverflowAt(index) => write!(f, "Overflow at index {index}"),
+            ComputeError::NoSolution => f.write_str("No solution"),
+            ComputeError::NoInverse => f.write_str("No inverse"),
+        }
+    }
+}
+
+/// French messages.
+#[cfg(feature = "locale-fr")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct French;
+
+#[cfg(feature = "locale-fr")]
+impl ErrorFormatter for French {
+    fn domain(&self, error: &DomainError, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match error {
+            DomainError::DivisionByZero => "Division par zéro",
+            DomainError::PositifIntegerRequired => "Entier strictement positif requis",
+            DomainError::OutOfRange => "Hors de l'intervalle admis",
+            DomainError::EmptyInput => "Entrée vide",
+            DomainError::DimensionMismatch => "Dimensions incompatibles",
+            DomainError::DuplicateInput => "Entrée en double",
+        })
+    }
+
+    fn compute(&self, error: &ComputeError, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match error {
+            ComputeError::Overflow => f.write_str("Dépassement de capacité"),
+            ComputeError::OverflowAt(index) => {
+                write!(f, "Dépassement de capacité à l'indice {index}")
+            }
+            ComputeError::NoSolution => f.write_str("Aucune solution"),
+            ComputeError::NoInverse => f.write_str("Pas d'inverse"),
+        }
+    }
+}
+
+impl TryFrom<MathError> for DomainError {
+    type Error = ComputeError;
+
+    /// Splits a `MathError` back into its two halves.
+    fn try_from(error: MathError) -> Result<Self, ComputeError> {
+        match error {
+            MathError::Domain(e) => Ok(e),
+            MathError::Compute(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_nested_errors() {
+        assert_eq!(
+            MathError::from(DomainError::EmptyInput),
+            MathError::EmptyInput
+        );
+        assert_eq!(
+            MathError::from(ComputeError::Overflow),
+            MathError::Compute(ComputeError::Overflow)
+        );
+        assert!(MathError::OutOfRange.is_domain());
+        assert!(MathError::NoSolution.is_compute());
+        assert!(matches!(MathError::Overflow, MathError::Overflow));
+        assert!(!matches!(MathError::NoInverse, MathError::Overflow));
+        assert_eq!(
+            DomainError::try_from(MathError::DivisionByZero),
+            Ok(DomainError::DivisionByZero)
+        );
+        assert_eq!(
+            DomainError::try_from(MathError::Compute(ComputeError::OverflowAt(3))),
+            Err(ComputeError::OverflowAt(3))
+        );
+        assert_eq!(MathError::Overflow.to_string(), "Overflow");
+        assert_eq!(
+            MathError::Compute(ComputeError::OverflowAt(3)).to_string(),
+            "Overflow at index 3"
+        );
+    }
+
+    #[test]
+    fn test_rendering() {
+        assert_eq!(
+            MathError::PositifIntegerRequired.to_string(),
+            "Positive integer required"
+        );
+        assert_eq!(
+            MathError::NoSolution.render(&English).to_string(),
+            "No solution"
+        );
+        assert_eq!(MathError::DivisionByZero.code(), "division_by_zero");
+        assert_eq!(
+            MathError::Compute(ComputeError::OverflowAt(3)).code(),
+            "overflow_at"
+        );
+        // Display and English agree on every error
+        let all = [
+            MathError::DivisionByZero,
+            MathError::PositifIntegerRequired,
+            MathError::OutOfRange,
+            MathError::EmptyInput,
+            MathError::DimensionMismatch,
+            MathError::DuplicateInput,
+            MathError::Overflow,
+            MathError::Compute(ComputeError::OverflowAt(7)),
+            MathError::NoSolution,
+            MathError::NoInverse,
+        ];
+        for error in &all {
+            assert_eq!(error.to_string(), error.render(&English).to_string());
+        }
+        let mut codes: alloc::vec::Vec<_> = all.iter().map(MathError::code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), all.len());
+
+        // a formatter written by an application
+        struct Terse;
+        impl ErrorFormatter for Terse {
+            fn domain(&self, _: &DomainError, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("bad input")
+            }
+            fn compute(&self, e: &ComputeError, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "failed: {e}")
+            }
+        }
+        assert_eq!(
+            MathError::EmptyInput.render(&Terse).to_string(),
+            "bad input"
+        );
+        assert_eq!(
+            MathError::NoInverse.render(&Terse).to_string(),
+            "failed: No inverse"
+        );
+    }
+
+    #[cfg(feature = "locale-fr")]
+    #[test]
+    fn test_french() {
+        assert_eq!(
+            MathError::NoSolution.render(&French).to_string(),
+            "Aucune solution"
+        );
+        assert_eq!(
+            MathError::Compute(ComputeError::OverflowAt(3))
+                .render(&French)
+                .to_string(),
+            "Dépassement de capacité à l'indice 3"
+        );
+        assert_eq!(
+            MathError::PositifIntegerRequired
+                .render(&French)
+                .to_string(),
+            "Entier strictement positif requis"
+        );
+    }
 }
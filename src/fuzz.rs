@@ -0,0 +1,234 @@
+use alloc::vec::Vec;
+
+use crate::algebra::factorization::factorize_u64;
+use crate::algebra::gcd::{extended_gcd, gcd_i64, gcd_u64};
+use crate::algebra::mod_expression::simplify_mod_expression;
+use crate::algebra::mod_trace::{crt_trace, mod_inverse_trace, mod_pow_trace};
+use crate::algebra::modular::{crt, mod_inverse, mod_mul, mod_pow};
+use crate::algebra::prime_nbr::is_prime_u64;
+use crate::algebra::rational::Rational;
+
+/// Largest input of `fuzz_factorize`, so trial division answers in milliseconds.
+const FACTORIZE_LIMIT: u64 = 1 << 40;
+
+/// The most congruences `fuzz_crt` builds from one input.
+const MAX_CONGRUENCES: usize = 4;
+
+/// Entry points for `cargo fuzz` and OSS-Fuzz, one per algorithm family, each taking the
+/// raw bytes of a fuzz input:
+/// - `fuzz_target!(|data: &[u8]| eucleia::fuzz::fuzz_gcd(data));`
+///
+/// Integers are read as little-endian words, zero-padded once the bytes run out, so
+/// every slice, empty included, is a valid input. The wrappers discard `MathError`s,
+/// which are expected answers, and assert what the results must satisfy (Bézout
+/// identities, products of factors, congruences, agreement between two
+/// implementations). They do not panic on any input: a panic is a bug in the crate,
+/// either a panicking path in the algorithm or a wrong answer.
+pub fn fuzz_gcd(data: &[u8]) {
+    let mut input = Bytes(data);
+    let (a, b) = (input.i64(), input.i64());
+    let g = gcd_i64(a, b);
+    assert_eq!(g, gcd_u64(a.unsigned_abs(), b.unsigned_abs()));
+    if g != 0 {
+        assert!(a.unsigned_abs().is_multiple_of(g) && b.unsigned_abs().is_multiple_of(g));
+    }
+    if let Ok((g2, x, y)) = extended_gcd(a, b) {
+        assert_eq!(g2 as u64, g);
+        assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, g2 as i128);
+    }
+}
+
+/// Factors the first 8 bytes, reduced below `2^40`, and checks the product and primality
+/// of the factors.
+pub fn fuzz_factorize(data: &[u8]) {
+    let n = Bytes(data).u64() % FACTORIZE_LIMIT;
+    let Ok(factors) = factorize_u64(n) else {
+        assert_eq!(n, 0);
+        return;
+    };
+    assert!(factors.windows(2).all(|w| w[0].0 < w[1].0));
+    let product = factors.iter().fold(1u128, |acc, &(p, e)| {
+        assert!(is_prime_u64(p) && e > 0);
+        acc * (p as u128).pow(e)
+    });
+    assert_eq!(product, n as u128);
+}
+
+/// `mod_pow` against its square-and-multiply trace and `b^e = (b^⌊e/2⌋)²·b^(e mod 2)`.
+pub fn fuzz_mod_pow(data: &[u8]) {
+    let mut input = Bytes(data);
+    let (base, exp, m) = (input.u64(), input.u64(), input.u64());
+    let Ok(value) = mod_pow(base, exp, m) else {
+        assert_eq!(m, 0);
+        return;
+    };
+    assert_eq!(mod_pow_trace(base, exp, m).map(|t| t.value), Ok(value));
+    let half = mod_pow(base, exp / 2, m).and_then(|h| mod_mul(h, h, m));
+    let odd = mod_pow(base, exp % 2, m);
+    assert_eq!(half.and_then(|h| mod_mul(h, odd?, m)), Ok(value));
+}
+
+/// `mod_inverse` against its Euclidean table, and `a·a⁻¹ ≡ 1` when it exists.
+pub fn fuzz_mod_inverse(data: &[u8]) {
+    let mut input = Bytes(data);
+    let (a, m) = (input.u64(), input.u64());
+    let inverse = mod_inverse(a, m);
+    let Ok(trace) = mod_inverse_trace(a, m) else {
+        assert_eq!(m, 0);
+        return;
+    };
+    assert_eq!(trace.gcd, gcd_u64(a % m, m));
+    match inverse {
+        Ok(inv) => {
+            assert_eq!(trace.inverse, Some(inv));
+            assert_eq!(mod_mul(a, inv, m), Ok(1 % m));
+        }
+        Err(_) => assert_eq!(trace.inverse, None),
+    }
+}
+
+/// Up to four congruences `(r, m)`; the solution of `crt` must satisfy each one and
+/// agree with `crt_trace`.
+pub fn fuzz_crt(data: &[u8]) {
+    let mut input = Bytes(data);
+    let mut congruences = Vec::new();
+    while !input.is_empty() && congruences.len() < MAX_CONGRUENCES {
+        congruences.push((input.u64(), input.u64()));
+    }
+    let solution = crt(&congruences);
+    assert_eq!(crt_trace(&congruences).map(|t| t.value), solution);
+    if let Ok((x, lcm)) = solution {
+        assert!(x < lcm);
+        for &(r, m) in &congruences {
+            assert_eq!(x % m, r % m);
+            assert!(lcm.is_multiple_of(m));
+        }
+    }
+}
+
+/// A modulus from the first 2 bytes, then the rest as an expression for
+/// `simplify_mod_expression`; invalid UTF-8 is skipped.
+pub fn fuzz_mod_expression(data: &[u8]) {
+    let mut input = Bytes(data);
+    let m = input.u16();
+    let Ok(expr) = core::str::from_utf8(input.0) else {
+        return;
+    };
+    if let Ok(value) = simplify_mod_expression(expr, m as u64) {
+        assert!(value < m as u64);
+    }
+}
+
+/// Two rationals from four `i64`s through the checked operations, with
+/// `(x + y) - y = x` and `(x·y)/y = x` when they do not overflow.
+pub fn fuzz_rational(data: &[u8]) {
+    let mut input = Bytes(data);
+    let (Ok(x), Ok(y)) = (
+        Rational::new(input.i64(), input.i64()),
+        Rational::new(input.i64(), input.i64()),
+    ) else {
+        return;
+    };
+    assert!(x.denom() > 0 && gcd_i64(x.numer(), x.denom()) == 1);
+    if let Ok(back) = x.checked_add(&y).and_then(|s| s.checked_sub(&y)) {
+        assert_eq!(back, x);
+    }
+    if let Ok(back) = x.checked_mul(&y).and_then(|p| p.checked_div(&y)) {
+        assert_eq!(back, x);
+    }
+    if y.is_zero() {
+        assert!(x.checked_div(&y).is_err() && y.recip().is_err());
+    }
+}
+
+/// The fuzz input, consumed from the front.
+struct Bytes<'a>(&'a [u8]);
+
+impl Bytes<'_> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let mut word = [0; N];
+        let n = N.min(self.0.len());
+        word[..n].copy_from_slice(&self.0[..n]);
+        self.0 = &self.0[n..];
+        word
+    }
+
+    fn u16(&mut self) -> u16 {
+        u16::from_le_bytes(self.take())
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.take())
+    }
+
+    fn i64(&mut self) -> i64 {
+        i64::from_le_bytes(self.take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::prime_nbr::splitmix64;
+    use alloc::vec;
+
+    const TARGETS: [fn(&[u8]); 7] = [
+        fuzz_gcd,
+        fuzz_factorize,
+        fuzz_mod_pow,
+        fuzz_mod_inverse,
+        fuzz_crt,
+        fuzz_mod_expression,
+        fuzz_rational,
+    ];
+
+    #[test]
+    fn test_targets_accept_edge_inputs() {
+        let mut inputs: Vec<Vec<u8>> = vec![vec![], vec![0; 40], vec![0xff; 40], vec![1]];
+        for byte in [0x00, 0x01, 0x7f, 0x80, 0xff] {
+            for len in [7, 8, 9, 15, 16, 17, 33] {
+                inputs.push(vec![byte; len]);
+            }
+        }
+        // i64::MIN and -1 operands
+        inputs.push([i64::MIN.to_le_bytes(), (-1i64).to_le_bytes()].concat());
+        inputs.push([(-1i64).to_le_bytes(), i64::MIN.to_le_bytes()].concat());
+        for target in TARGETS {
+            for input in &inputs {
+                target(input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_targets_on_random_bytes() {
+        let mut state = 801u64;
+        for round in 0..2_000 {
+            let len = round % 48;
+            let data: Vec<u8> = (0..len).map(|_| splitmix64(&mut state) as u8).collect();
+            for target in TARGETS {
+                target(&data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_expression_target() {
+        for expr in [
+            "3^(10^100) + 2",
+            "(5 - 8) * 4 / 3",
+            "((((",
+            "2^-1",
+            "1 / 0",
+            "9^9^9^9",
+        ] {
+            let data = [&7u16.to_le_bytes()[..], expr.as_bytes()].concat();
+            fuzz_mod_expression(&data);
+        }
+        fuzz_mod_expression(&[7, 0, 0xff, 0xfe]);
+    }
+}
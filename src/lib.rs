@@ -1,2 +1,24 @@
+#![no_std]
+
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
 pub mod algebra;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod codes;
+pub mod combinatorics;
+#[cfg(feature = "crosscheck")]
+pub mod crosscheck;
+pub mod digits;
+#[cfg(feature = "educational_crypto")]
+pub mod educational_crypto;
 pub mod errors;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+pub mod outcome;
+pub mod sequences;
+#[cfg(feature = "testgen")]
+pub mod testgen;
+pub mod verify;
@@ -0,0 +1,87 @@
+use core::ops::Deref;
+use core::time::Duration;
+
+/// A result together with the work spent producing it, returned by the `*_outcome`
+/// variants of the heavier algorithms for logging and accounting:
+/// - `value`: what the plain function returns
+/// - `method`: the algorithm that ran, e.g. `"baby-step giant-step"`
+/// - `iterations`: its unit of work, documented by each function (group operations,
+///   trial divisions, primes...)
+/// - `duration`: wall-clock time, `None` without the `std` feature
+///
+/// Dereferences to the value; `into_value` drops the metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome<T> {
+    pub value: T,
+    pub method: &'static str,
+    pub iterations: u64,
+    pub duration: Option<Duration>,
+}
+
+impl<T> Outcome<T> {
+    /// An untimed outcome; see `measure` for the duration.
+    pub fn new(value: T, method: &'static str, iterations: u64) -> Self {
+        Outcome {
+            value,
+            method,
+            iterations,
+            duration: None,
+        }
+    }
+
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Transforms the value, keeping the metadata.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Outcome<U> {
+        Outcome {
+            value: f(self.value),
+            method: self.method,
+            iterations: self.iterations,
+            duration: self.duration,
+        }
+    }
+}
+
+impl<T> Deref for Outcome<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+/// Runs `f` and records its wall-clock time in the outcome, when `std` is available.
+pub(crate) fn measure<T, E>(f: impl FnOnce() -> Result<Outcome<T>, E>) -> Result<Outcome<T>, E> {
+    #[cfg(feature = "std")]
+    {
+        let start = std::time::Instant::now();
+        let mut outcome = f()?;
+        outcome.duration = Some(start.elapsed());
+        Ok(outcome)
+    }
+    #[cfg(not(feature = "std"))]
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outcome() {
+        let outcome = Outcome::new(21u64, "doubling", 3);
+        assert_eq!(*outcome + 1, 22);
+        let doubled = outcome.map(|x| x * 2);
+        assert_eq!((doubled.method, doubled.iterations), ("doubling", 3));
+        assert_eq!(doubled.into_value(), 42);
+    }
+
+    #[test]
+    fn test_measure() {
+        let outcome = measure(|| Ok::<_, ()>(Outcome::new((), "noop", 0))).unwrap();
+        assert_eq!(outcome.duration.is_some(), cfg!(feature = "std"));
+        assert_eq!(measure(|| Err::<Outcome<()>, _>(7)), Err(7));
+    }
+}
@@ -0,0 +1,108 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "internal-bigint")]
+use crate::algebra::big_rational::BigRational;
+use crate::algebra::polynomial::Field;
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Bernoulli number `Bₙ`, with `B₁ = +1/2`:
+/// - `bernoulli(0) = 1`, `bernoulli(1) = 1/2`, `bernoulli(2) = 1/6`, `bernoulli(4) = -1/30`
+/// - `bernoulli(12) = -691/2730`
+///
+/// The `+1/2` convention makes `Σ_{k=1}^{m} k^p = Σ_j C(p + 1, j)·Bⱼ·m^(p+1-j) / (p + 1)`
+/// (Faulhaber) hold as written; the other convention only flips `B₁`. Odd indices past 1
+/// are 0. Computed by the Akiyama–Tanigawa triangle in checked `Rational` arithmetic,
+/// O(n²) steps. The numbers grow like `2·n!/(2π)ⁿ`: `B₃₄` is the last even one that
+/// fits, and from `B₃₆` on the error is `Overflow`, where `bernoulli_big` takes over.
+pub fn bernoulli(n: u32) -> Result<Rational, MathError> {
+    if n > 1 && n % 2 == 1 {
+        return Ok(Rational::ZERO);
+    }
+    Ok(bernoulli_numbers(n)?.swap_remove(n as usize))
+}
+
+/// `Bₙ` as an arbitrary-precision fraction, same convention and method as `bernoulli`:
+/// - `bernoulli_big(60) = -1215233140483755572040304994079820246041491/56786730`
+#[cfg(feature = "internal-bigint")]
+pub fn bernoulli_big(n: u32) -> BigRational {
+    if n > 1 && n % 2 == 1 {
+        return BigRational::from(Rational::ZERO);
+    }
+    // BigRational arithmetic cannot overflow and `1/(m + 1)` is always defined
+    bernoulli_numbers(n)
+        .map(|mut b| b.swap_remove(n as usize))
+        .unwrap_or_else(|_| BigRational::from(Rational::ZERO))
+}
+
+/// `B₀, …, Bₙ` by the Akiyama–Tanigawa triangle: `a_m = 1/(m + 1)`, then
+/// `a_{j-1} = j·(a_{j-1} - a_j)` down the row, after which `a_0 = B_m`.
+pub(crate) fn bernoulli_numbers<T: Field + From<Rational>>(n: u32) -> Result<Vec<T>, MathError> {
+    let mut row: Vec<T> = Vec::with_capacity(n as usize + 1);
+    let mut numbers = Vec::with_capacity(n as usize + 1);
+    for m in 0..=n as i64 {
+        row.push(T::from(Rational::new(1, m + 1)?));
+        for j in (1..=m as usize).rev() {
+            let difference = row[j - 1].checked_sub(&row[j])?;
+            row[j - 1] = difference.checked_mul(&T::from(Rational::from(j as i64)))?;
+        }
+        numbers.push(row[0].clone());
+    }
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    #[test]
+    fn test_bernoulli() {
+        let expected = [
+            r(1, 1),
+            r(1, 2),
+            r(1, 6),
+            r(0, 1),
+            r(-1, 30),
+            r(0, 1),
+            r(1, 42),
+            r(0, 1),
+            r(-1, 30),
+            r(0, 1),
+            r(5, 66),
+            r(0, 1),
+            r(-691, 2730),
+            r(0, 1),
+            r(7, 6),
+        ];
+        for (n, &b) in expected.iter().enumerate() {
+            assert_eq!(bernoulli(n as u32), Ok(b), "n = {n}");
+        }
+        assert_eq!(bernoulli(20), Ok(r(-174611, 330)));
+        assert_eq!(bernoulli(1001), Ok(Rational::ZERO));
+        assert_eq!(bernoulli(34), Ok(r(2577687858367, 6)));
+        assert_eq!(bernoulli(36), Err(MathError::Overflow));
+    }
+
+    #[cfg(feature = "internal-bigint")]
+    #[test]
+    fn test_bernoulli_big() {
+        use alloc::string::ToString;
+
+        for n in 0..=20 {
+            assert_eq!(
+                bernoulli_big(n),
+                BigRational::from(bernoulli(n).unwrap()),
+                "n = {n}"
+            );
+        }
+        assert_eq!(
+            bernoulli_big(60).to_string(),
+            "-1215233140483755572040304994079820246041491/56786730"
+        );
+        assert_eq!(bernoulli_big(101).to_string(), "0");
+    }
+}
@@ -0,0 +1,153 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// The Euclidean rhythm `E(k, n)`: `k` onsets spread as evenly as possible over `n` steps,
+/// by Bjorklund's algorithm, `true` for an onset:
+/// - `euclidean_rhythm(3, 8) = x..x..x.` (tresillo)
+/// - `euclidean_rhythm(5, 8) = x.xx.xx.` (cinquillo)
+///
+/// Starts from `k` groups `[x]` and `n - k` groups `[.]`, then repeatedly appends one
+/// remainder group to each leading group, the subtraction steps of Euclid's algorithm
+/// on `(k, n - k)`, until at most one remainder is left. Always begins with an onset
+/// when `k > 0`; the gaps between onsets take only the values `⌊n/k⌋` and `⌈n/k⌉`.
+/// `MathError::OutOfRange` if `k > n`.
+pub fn euclidean_rhythm(k: usize, n: usize) -> Result<Vec<bool>, MathError> {
+    if k > n {
+        return Err(MathError::OutOfRange);
+    }
+    let mut heads: Vec<Vec<bool>> = vec![vec![true]; k];
+    let mut tails: Vec<Vec<bool>> = vec![vec![false]; n - k];
+    while tails.len() > 1 && !heads.is_empty() {
+        let paired = heads.len().min(tails.len());
+        let rest = if heads.len() > paired {
+            heads.split_off(paired)
+        } else {
+            tails.split_off(paired)
+        };
+        for (head, tail) in heads.iter_mut().zip(tails) {
+            head.extend(tail);
+        }
+        tails = rest;
+    }
+    Ok(heads.into_iter().chain(tails).flatten().collect())
+}
+
+/// Gaps between the `n` points `{i·α}`, `0 <= i < n`, on the unit circle, as
+/// `(length, count)` ascending by length:
+/// - `three_distance_gaps(5/13, 4) = [(2/13, 1), (3/13, 2), (5/13, 1)]`
+///
+/// The three-distance theorem says there are never more than three lengths, and when
+/// there are three the largest is the sum of the other two. `α` is taken mod 1; once
+/// `n` exceeds its denominator points repeat and a gap of 0 appears. Sorts the points,
+/// O(n log n). `MathError::EmptyInput` if `n == 0`.
+pub fn three_distance_gaps(alpha: Rational, n: usize) -> Result<Vec<(Rational, usize)>, MathError> {
+    if n == 0 {
+        return Err(MathError::EmptyInput);
+    }
+    let q = alpha.denom() as u128;
+    let p = alpha.numer().rem_euclid(alpha.denom()) as u128;
+    // positions in units of 1/q; i·p < 2^64·2^63
+    let mut points: Vec<u128> = (0..n as u128).map(|i| i * p % q).collect();
+    points.sort_unstable();
+    let mut gaps = BTreeMap::new();
+    for pair in points.windows(2) {
+        *gaps.entry(pair[1] - pair[0]).or_insert(0) += 1;
+    }
+    *gaps.entry(q - points[n - 1] + points[0]).or_insert(0) += 1;
+    gaps.into_iter()
+        .map(|(gap, count)| Ok((Rational::new(gap as i64, q as i64)?, count)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(s: &str) -> Vec<bool> {
+        s.chars().map(|c| c == 'x').collect()
+    }
+
+    fn r(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    #[test]
+    fn test_euclidean_rhythm() {
+        assert_eq!(euclidean_rhythm(3, 8), Ok(pattern("x..x..x.")));
+        assert_eq!(euclidean_rhythm(5, 8), Ok(pattern("x.xx.xx.")));
+        assert_eq!(euclidean_rhythm(2, 5), Ok(pattern("x.x..")));
+        assert_eq!(euclidean_rhythm(4, 12), Ok(pattern("x..x..x..x..")));
+        assert_eq!(euclidean_rhythm(7, 12), Ok(pattern("x.xx.x.xx.x.")));
+        assert_eq!(euclidean_rhythm(0, 4), Ok(pattern("....")));
+        assert_eq!(euclidean_rhythm(4, 4), Ok(pattern("xxxx")));
+        assert_eq!(euclidean_rhythm(0, 0), Ok(vec![]));
+        assert_eq!(euclidean_rhythm(5, 4), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_euclidean_rhythm_is_even() {
+        for n in 1..=40 {
+            for k in 1..=n {
+                let rhythm = euclidean_rhythm(k, n).unwrap();
+                assert_eq!(rhythm.len(), n);
+                assert!(rhythm[0]);
+                // a rotation of the Bresenham pattern i·k mod n < k
+                let bresenham: Vec<bool> = (0..n).map(|i| i * k % n < k).collect();
+                assert!(
+                    (0..n).any(|s| (0..n).all(|i| rhythm[(i + s) % n] == bresenham[i])),
+                    "E({k}, {n})"
+                );
+                let onsets: Vec<usize> = (0..n).filter(|&i| rhythm[i]).collect();
+                let gaps: Vec<usize> = (0..k)
+                    .map(|j| (onsets[(j + 1) % k] + n - onsets[j] - 1) % n + 1)
+                    .collect();
+                assert!(gaps.iter().all(|&g| g == n / k || g == n.div_ceil(k)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_three_distance_gaps() {
+        assert_eq!(
+            three_distance_gaps(r(5, 13), 4),
+            Ok(vec![(r(2, 13), 1), (r(3, 13), 2), (r(5, 13), 1)])
+        );
+        assert_eq!(
+            three_distance_gaps(r(13, 21), 5),
+            Ok(vec![(r(3, 21), 2), (r(5, 21), 3)])
+        );
+        // α is taken mod 1
+        assert_eq!(
+            three_distance_gaps(r(-8, 13), 4),
+            three_distance_gaps(r(5, 13), 4)
+        );
+        assert_eq!(
+            three_distance_gaps(r(1, 3), 1),
+            Ok(vec![(Rational::ONE, 1)])
+        );
+        assert_eq!(
+            three_distance_gaps(r(1, 2), 3),
+            Ok(vec![(Rational::ZERO, 1), (r(1, 2), 2)])
+        );
+        assert_eq!(three_distance_gaps(r(1, 2), 0), Err(MathError::EmptyInput));
+    }
+
+    #[test]
+    fn test_three_distance_theorem() {
+        // convergents of √2 - 1 and a few other fractions
+        for alpha in [r(408, 985), r(355, 113), r(7, 30), r(1_000_003, 2_718_281)] {
+            for n in 1..300 {
+                let gaps = three_distance_gaps(alpha, n).unwrap();
+                assert!(gaps.len() <= 3, "{alpha} {n}");
+                assert_eq!(gaps.iter().map(|g| g.1).sum::<usize>(), n);
+                if let [(a, _), (b, _), (c, _)] = gaps[..] {
+                    assert_eq!((a + b).unwrap(), c);
+                }
+            }
+        }
+    }
+}
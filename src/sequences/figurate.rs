@@ -0,0 +1,114 @@
+use crate::errors::MathError;
+
+/// The `n`-th `s`-gonal number, `((s - 2)n² - (s - 4)n) / 2`, counted from
+/// `polygonal(s, 0) = 0`:
+/// - `polygonal(3, 4) = 10`, a triangular number
+/// - `polygonal(5, 4) = 22`, a pentagonal number
+///
+/// Computed as `(s - 2)·n(n - 1)/2 + n` in `u128`, exact for every `u64` input that fits.
+/// `MathError::OutOfRange` if `s < 3`, `Overflow` past `u128`.
+pub fn polygonal(s: u64, n: u64) -> Result<u128, MathError> {
+    if s < 3 {
+        return Err(MathError::OutOfRange);
+    }
+    let n = n as u128;
+    let triangle = n * n.saturating_sub(1) / 2;
+    triangle
+        .checked_mul(s as u128 - 2)
+        .and_then(|v| v.checked_add(n))
+        .ok_or(MathError::Overflow)
+}
+
+/// The `n` with `polygonal(s, n) = x`, if `x` is `s`-gonal:
+/// - `polygonal_index(6, 28) = Some(4)`, the fourth hexagonal number
+/// - `polygonal_index(6, 29) = None`
+///
+/// `n` is the positive root of `(s - 2)n² - (s - 4)n - 2x = 0`, within 5 of
+/// `isqrt(2⌊x / (s - 2)⌋)` once the floors and the linear term are accounted for, and
+/// the sequence is increasing, so checking that window exactly settles it. This avoids
+/// the discriminant `8(s - 2)x + (s - 4)²`, which can leave `u128` long before `x` does.
+/// `MathError::OutOfRange` if `s < 3`.
+pub fn polygonal_index(s: u64, x: u128) -> Result<Option<u64>, MathError> {
+    if s < 3 {
+        return Err(MathError::OutOfRange);
+    }
+    let t = x / (s as u128 - 2);
+    let estimate = match t.checked_mul(2) {
+        Some(double) => double.isqrt(),
+        None => 2 * (t / 2).isqrt(),
+    };
+    let first = u64::try_from(estimate.saturating_sub(5)).unwrap_or(u64::MAX);
+    for n in first..=first.saturating_add(10) {
+        match polygonal(s, n) {
+            Ok(v) if v == x => return Ok(Some(n)),
+            Ok(v) if v < x => continue,
+            _ => break,
+        }
+    }
+    Ok(None)
+}
+
+/// Whether `x` is an `s`-gonal number, 0 and 1 included:
+/// - `is_polygonal(5, 35) = true`
+///
+/// `MathError::OutOfRange` if `s < 3`.
+pub fn is_polygonal(s: u64, x: u128) -> Result<bool, MathError> {
+    polygonal_index(s, x).map(|n| n.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polygonal() {
+        let first = |s| {
+            (0..6)
+                .map(|n| polygonal(s, n).unwrap())
+                .collect::<alloc::vec::Vec<_>>()
+        };
+        assert_eq!(first(3), [0, 1, 3, 6, 10, 15]);
+        assert_eq!(first(4), [0, 1, 4, 9, 16, 25]);
+        assert_eq!(first(5), [0, 1, 5, 12, 22, 35]);
+        assert_eq!(first(6), [0, 1, 6, 15, 28, 45]);
+        assert_eq!(polygonal(u64::MAX, 1), Ok(1));
+        assert_eq!(polygonal(u64::MAX, 2), Ok(u64::MAX as u128));
+        assert_eq!(
+            polygonal(3, u64::MAX),
+            Ok(u64::MAX as u128 * (u64::MAX as u128 + 1) / 2)
+        );
+        assert_eq!(polygonal(u64::MAX, u64::MAX), Err(MathError::Overflow));
+        assert_eq!(polygonal(2, 5), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_polygonal_index() {
+        assert_eq!(polygonal_index(6, 28), Ok(Some(4)));
+        assert_eq!(polygonal_index(6, 29), Ok(None));
+        assert_eq!(is_polygonal(5, 35), Ok(true));
+        assert_eq!(polygonal_index(3, 0), Ok(Some(0)));
+        assert_eq!(polygonal_index(2, 1), Err(MathError::OutOfRange));
+
+        // against the terms themselves
+        for s in 3..40 {
+            let mut terms = alloc::collections::BTreeMap::new();
+            for n in 0..200 {
+                terms.insert(polygonal(s, n).unwrap(), n);
+            }
+            for x in 0..3000u128 {
+                assert_eq!(polygonal_index(s, x), Ok(terms.get(&x).copied()), "s = {s}");
+            }
+        }
+
+        // far out, including right below the u128 limit
+        for s in [3, 4, 5, 6, 1000, 1 << 40, u64::MAX] {
+            for n in [1u64 << 20, 1 << 32, 1 << 40, u64::MAX / 3, u64::MAX] {
+                let Ok(x) = polygonal(s, n) else { continue };
+                assert_eq!(polygonal_index(s, x), Ok(Some(n)), "s = {s}, n = {n}");
+                assert_eq!(polygonal_index(s, x - 1), Ok(None));
+                assert_eq!(polygonal_index(s, x + 1), Ok(None));
+            }
+            assert_eq!(polygonal_index(s, u128::MAX), Ok(None));
+        }
+    }
+}
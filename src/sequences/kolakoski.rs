@@ -0,0 +1,83 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Kolakoski sequence 1, 2, 2, 1, 1, 2, 1, 2, 2, 1, ...: the sequence of its own run lengths.
+///
+/// Generated lazily, reading each run length back from the terms already generated,
+/// so the buffer keeps every term: O(n) memory after `n` terms.
+#[derive(Debug, Clone)]
+pub struct Kolakoski {
+    terms: Vec<u8>,
+    produced: usize,
+    next_run: usize,
+}
+
+impl Kolakoski {
+    pub fn new() -> Self {
+        Kolakoski {
+            terms: vec![1, 2, 2],
+            produced: 0,
+            next_run: 2,
+        }
+    }
+}
+
+impl Default for Kolakoski {
+    fn default() -> Self {
+        Kolakoski::new()
+    }
+}
+
+impl Iterator for Kolakoski {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        while self.produced >= self.terms.len() {
+            // term i is the length of run i; runs alternate 1s and 2s
+            let value = if self.next_run.is_multiple_of(2) {
+                1
+            } else {
+                2
+            };
+            let length = self.terms[self.next_run] as usize;
+            self.terms.extend(core::iter::repeat_n(value, length));
+            self.next_run += 1;
+        }
+        let term = self.terms[self.produced];
+        self.produced += 1;
+        Some(term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_terms() {
+        let terms: Vec<u8> = Kolakoski::new().take(20).collect();
+        assert_eq!(
+            terms,
+            vec![1, 2, 2, 1, 1, 2, 1, 2, 2, 1, 2, 2, 1, 1, 2, 1, 1, 2, 2, 1]
+        );
+    }
+
+    #[test]
+    fn test_self_describing() {
+        let terms: Vec<u8> = Kolakoski::new().take(2000).collect();
+        let runs: Vec<u8> = terms
+            .chunk_by(|a, b| a == b)
+            .map(|r| r.len() as u8)
+            .collect();
+        // the last run may be cut short by `take`
+        let full = runs.len() - 1;
+        assert_eq!(&runs[..full], &terms[..full]);
+    }
+
+    #[test]
+    fn test_ones_density() {
+        let ones = Kolakoski::new().take(10_000).filter(|&t| t == 1).count();
+        // the density of ones is conjectured to be 1/2
+        assert_eq!(ones, 4_996);
+    }
+}
@@ -0,0 +1,112 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::errors::MathError;
+
+/// Conway's look-and-say sequence: 1, 11, 21, 1211, 111221, ...
+///
+/// Terms are yielded as digit vectors, since their length grows by about 30%
+/// per step (the 50th term has close to a million digits) and quickly leaves
+/// every fixed-width integer type.
+#[derive(Debug, Clone)]
+pub struct LookAndSay {
+    current: Vec<u8>,
+}
+
+impl LookAndSay {
+    /// Sequence starting at `1`.
+    pub fn new() -> Self {
+        LookAndSay { current: vec![1] }
+    }
+
+    /// Sequence starting at any digit string (each digit `0..=9`).
+    pub fn with_seed(seed: &[u8]) -> Result<Self, MathError> {
+        if seed.is_empty() {
+            return Err(MathError::EmptyInput);
+        }
+        if seed.iter().any(|&d| d > 9) {
+            return Err(MathError::OutOfRange);
+        }
+        Ok(LookAndSay {
+            current: seed.to_vec(),
+        })
+    }
+}
+
+impl Default for LookAndSay {
+    fn default() -> Self {
+        LookAndSay::new()
+    }
+}
+
+impl Iterator for LookAndSay {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        let mut next = Vec::with_capacity(self.current.len() * 2);
+        for run in self.current.chunk_by(|a, b| a == b) {
+            // runs longer than 9 only appear with unusual seeds
+            let count = run.len();
+            let start = next.len();
+            let mut c = count;
+            while c > 0 {
+                next.insert(start, (c % 10) as u8);
+                c /= 10;
+            }
+            next.push(run[0]);
+        }
+        Some(core::mem::replace(&mut self.current, next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn as_number(digits: &[u8]) -> u64 {
+        digits.iter().fold(0, |acc, &d| acc * 10 + d as u64)
+    }
+
+    #[test]
+    fn test_first_terms() {
+        let terms: Vec<u64> = LookAndSay::new().take(8).map(|t| as_number(&t)).collect();
+        assert_eq!(
+            terms,
+            vec![1, 11, 21, 1211, 111221, 312211, 13112221, 1113213211]
+        );
+    }
+
+    #[test]
+    fn test_term_lengths() {
+        // OEIS A005341
+        let lengths: Vec<usize> = LookAndSay::new().take(12).map(|t| t.len()).collect();
+        assert_eq!(lengths, vec![1, 2, 2, 4, 6, 6, 8, 10, 14, 20, 26, 34]);
+        assert_eq!(LookAndSay::new().nth(39).unwrap().len(), 63_138);
+    }
+
+    #[test]
+    fn test_fixed_point_seed() {
+        // "22" describes itself
+        let terms: Vec<Vec<u8>> = LookAndSay::with_seed(&[2, 2]).unwrap().take(3).collect();
+        assert_eq!(terms, vec![vec![2, 2]; 3]);
+    }
+
+    #[test]
+    fn test_long_runs() {
+        let mut seq = LookAndSay::with_seed(&[7; 12]).unwrap();
+        seq.next();
+        assert_eq!(seq.next(), Some(vec![1, 2, 7]));
+    }
+
+    #[test]
+    fn test_invalid_seed() {
+        assert!(matches!(
+            LookAndSay::with_seed(&[]),
+            Err(MathError::EmptyInput)
+        ));
+        assert!(matches!(
+            LookAndSay::with_seed(&[1, 10]),
+            Err(MathError::OutOfRange)
+        ));
+    }
+}
@@ -0,0 +1,13 @@
+pub mod bernoulli;
+pub mod euclidean_rhythm;
+pub mod figurate;
+pub mod kolakoski;
+pub mod look_and_say;
+pub mod period;
+pub mod power_sums;
+pub mod recaman;
+pub mod recurrence;
+pub mod search;
+pub mod sequence;
+pub mod stern;
+pub mod stride;
@@ -0,0 +1,176 @@
+use crate::errors::MathError;
+
+/// Eventual periodicity of an iterated state: `x_{i + period} = x_i` for all `i >= preperiod`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    /// Index of the first state on the cycle (μ).
+    pub preperiod: usize,
+    /// Cycle length (λ).
+    pub period: usize,
+}
+
+/// Finds the period of `x₀, step(x₀, m), step(step(x₀, m), m), ...` with Brent's algorithm.
+///
+/// The state is whatever determines the rest of the sequence (two consecutive
+/// terms for a second-order recurrence), and `step` is expected to reduce mod
+/// `m`, which keeps the state space finite so a cycle always exists. Uses O(1)
+/// states of memory. Returns `Ok(None)` if no cycle closes within `max_terms` steps.
+///
+/// Fibonacci mod 10 has period 60 (the Pisano period):
+/// `detect_period_mod((0, 1), |&(a, b), m| (b, (a + b) % m), 10, 1000)`
+pub fn detect_period_mod<S, F>(
+    initial: S,
+    step: F,
+    m: u64,
+    max_terms: usize,
+) -> Result<Option<Period>, MathError>
+where
+    S: Clone + PartialEq,
+    F: Fn(&S, u64) -> S,
+{
+    if m == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    Ok(brent(initial, |s| step(s, m), max_terms).map(|(period, _)| period))
+}
+
+/// Brent's algorithm behind `detect_period_mod` and `cycles::find_cycle`: the period of
+/// the orbit of `initial` under `step` and its first state on the cycle, or `None` if
+/// no cycle closes within `max_terms` steps.
+///
+/// A tortoise waits at powers of two while the hare runs ahead, which gives the cycle
+/// length, then a second pass `period` steps apart meets at the cycle start.
+pub(crate) fn brent<S, F>(initial: S, mut step: F, max_terms: usize) -> Option<(Period, S)>
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // phase 1: find the cycle length with power-of-two search windows
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    let mut steps = 1;
+    while tortoise != hare {
+        if steps >= max_terms {
+            return None;
+        }
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+        steps += 1;
+    }
+
+    // phase 2: a hare `period` steps ahead meets the tortoise at the cycle start
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut preperiod = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        preperiod += 1;
+    }
+
+    Some((Period { preperiod, period }, tortoise))
+}
+
+/// Pisano period π(m): the period of the Fibonacci numbers mod `m`.
+///
+/// - `pisano_period(10) = 60`
+pub fn pisano_period(m: u64) -> Result<usize, MathError> {
+    let step = |&(a, b): &(u64, u64), m: u64| (b, ((a as u128 + b as u128) % m as u128) as u64);
+    // π(m) <= 6m, and Brent's phase 1 takes fewer than 3λ steps on a pure cycle
+    let bound = (m as usize).saturating_mul(18).saturating_add(2);
+    detect_period_mod((0, 1 % m.max(1)), step, m, bound)?
+        .map(|p| p.period)
+        .ok_or(MathError::OutOfRange)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pisano_periods() {
+        // OEIS A001175
+        let expected = [1, 3, 8, 6, 20, 24, 16, 12, 24, 60, 10, 24];
+        for (i, &p) in expected.iter().enumerate() {
+            assert_eq!(pisano_period(i as u64 + 1), Ok(p), "pi({})", i + 1);
+        }
+        assert_eq!(pisano_period(1000), Ok(1500));
+        assert_eq!(pisano_period(0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_fibonacci_is_purely_periodic() {
+        let step = |&(a, b): &(u64, u64), m: u64| (b, (a + b) % m);
+        let period = detect_period_mod((0, 1), step, 10, 1000).unwrap();
+        assert_eq!(
+            period,
+            Some(Period {
+                preperiod: 0,
+                period: 60
+            })
+        );
+    }
+
+    #[test]
+    fn test_preperiod_of_power_sequence() {
+        // 2^n mod 12: 1, 2, 4, 8, 4, 8, ... enters the cycle at index 2
+        let step = |&x: &u64, m: u64| x * 2 % m;
+        let period = detect_period_mod(1, step, 12, 100).unwrap();
+        assert_eq!(
+            period,
+            Some(Period {
+                preperiod: 2,
+                period: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_lcg_full_period() {
+        // x -> 5x + 3 mod 64 satisfies Hull-Dobell, so its period is 64
+        let step = |&x: &u64, m: u64| (5 * x + 3) % m;
+        let period = detect_period_mod(7, step, 64, 1000).unwrap();
+        assert_eq!(
+            period,
+            Some(Period {
+                preperiod: 0,
+                period: 64
+            })
+        );
+    }
+
+    #[test]
+    fn test_max_terms_exhausted() {
+        let step = |&x: &u64, m: u64| (x + 1) % m;
+        assert_eq!(detect_period_mod(0, step, 1000, 10), Ok(None));
+        assert_eq!(
+            detect_period_mod(0, step, 1000, 5000),
+            Ok(Some(Period {
+                preperiod: 0,
+                period: 1000
+            }))
+        );
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        let step = |&x: &u64, _m: u64| x;
+        assert_eq!(
+            detect_period_mod(3, step, 5, 1),
+            Ok(Some(Period {
+                preperiod: 0,
+                period: 1
+            }))
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::gcd::gcd_u128;
+use crate::algebra::polynomial::{Field, Polynomial};
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+use crate::sequences::bernoulli::bernoulli_numbers;
+#[cfg(feature = "internal-bigint")]
+use crate::{algebra::big_rational::BigRational, algebra::bigu::BigU};
+
+/// `Σ_{i=1}^{n} i^k`, exactly:
+/// - `sum_of_powers(10, 2) = 385`
+/// - `sum_of_powers(100, 3) = 25_502_500`, `(100·101/2)²`
+///
+/// Uses `Σ i^k = Σ_j S(k, j)·j!·C(n + 1, j + 1)` with `S` the Stirling numbers of the
+/// second kind, `j` up to `min(k, n)`: O(k²) whatever `n`, and every term is a positive
+/// part of the sum, so a term, or any of its factors, that leaves `u128` means the sum
+/// does too. `0` for `n == 0`, `n` for `k == 0`. `MathError::Overflow` if the sum exceeds
+/// `u128`; `sum_of_powers_big` has no limit.
+pub fn sum_of_powers(n: u64, k: u32) -> Result<u128, MathError> {
+    if k == 0 {
+        return Ok(n as u128);
+    }
+    let terms = (k as u64).min(n) as usize;
+    // S(k, 0..=terms), row by row; S(i, j) only grows with i, so no false overflow
+    let mut stirling = vec![0u128; terms + 1];
+    stirling[0] = 1;
+    for _ in 0..k {
+        for j in (1..=terms).rev() {
+            stirling[j] = (j as u128)
+                .checked_mul(stirling[j])
+                .and_then(|s| s.checked_add(stirling[j - 1]))
+                .ok_or(MathError::Overflow)?;
+        }
+        stirling[0] = 0;
+    }
+    let mut factorial = 1u128;
+    let mut sum = 0u128;
+    for (j, &s) in stirling.iter().enumerate().skip(1) {
+        factorial = factorial
+            .checked_mul(j as u128)
+            .ok_or(MathError::Overflow)?;
+        sum = binomial_u128(n as u128 + 1, j as u128 + 1)
+            .and_then(|c| c.checked_mul(factorial))
+            .and_then(|c| c.checked_mul(s))
+            .and_then(|term| term.checked_add(sum))
+            .ok_or(MathError::Overflow)?;
+    }
+    Ok(sum)
+}
+
+/// `Σ_{i=1}^{n} i^k` as a big integer:
+/// - `sum_of_powers_big(10^6, 10)` has 65 digits
+///
+/// Evaluates the Faulhaber polynomial with `BigRational` coefficients, O(k²) big
+/// operations.
+#[cfg(feature = "internal-bigint")]
+pub fn sum_of_powers_big(n: u64, k: u32) -> BigU {
+    // BigRational arithmetic cannot overflow, and the sum is an integer
+    faulhaber_coeffs::<BigRational>(k)
+        .and_then(|coeffs| {
+            let x = BigRational::new(false, BigU::from_u64(n), BigU::one())?;
+            Polynomial::new(coeffs).eval(&x)
+        })
+        .map(|sum| sum.numer().clone())
+        .unwrap_or_else(|_| BigU::zero())
+}
+
+/// The polynomial `P` of degree `k + 1` with `P(n) = Σ_{i=1}^{n} i^k` for every `n`:
+/// - `faulhaber_polynomial(1) = n²/2 + n/2`
+/// - `faulhaber_polynomial(3) = n⁴/4 + n³/2 + n²/4`
+///
+/// `P(n) = Σ_j C(k + 1, j)·Bⱼ·n^(k+1-j) / (k + 1)` with `B₁ = +1/2`, as `bernoulli`
+/// gives it; no constant term. Coefficients in checked `Rational`, `Overflow` once the
+/// Bernoulli numbers do, from `k = 36`.
+pub fn faulhaber_polynomial(k: u32) -> Result<Polynomial<Rational>, MathError> {
+    faulhaber_coeffs(k).map(Polynomial::new)
+}
+
+/// Coefficients from the constant term up, `C(k + 1, j)·Bⱼ/(k + 1)` at `n^(k+1-j)`.
+fn faulhaber_coeffs<T: Field + From<Rational>>(k: u32) -> Result<Vec<T>, MathError> {
+    let bernoulli = bernoulli_numbers::<T>(k)?;
+    let top = k as usize + 1;
+    let mut coeffs = vec![T::zero(); top + 1];
+    // C(k + 1, j) / (k + 1), updated as C(k + 1, j + 1) = C(k + 1, j)·(k + 1 - j)/(j + 1)
+    let mut weight = T::from(Rational::new(1, top as i64)?);
+    for (j, b) in bernoulli.iter().enumerate() {
+        coeffs[top - j] = weight.checked_mul(b)?;
+        weight = weight.checked_mul(&T::from(Rational::new((top - j) as i64, j as i64 + 1)?))?;
+    }
+    Ok(coeffs)
+}
+
+/// `C(n, k)`, `None` if it exceeds `u128`. Each step divides out the gcd first so the
+/// running value is always `C(n, i)` itself, never larger than the result.
+fn binomial_u128(n: u128, k: u128) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    let mut c = 1u128;
+    for i in 0..k {
+        // C(n, i + 1) = C(n, i)·(n - i)/(i + 1), and (i + 1)/g divides n - i
+        let g = gcd_u128(c, i + 1);
+        c = (c / g).checked_mul((n - i) / ((i + 1) / g))?;
+    }
+    Some(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    fn direct(n: u64, k: u32) -> u128 {
+        (1..=n as u128).map(|i| i.pow(k)).sum()
+    }
+
+    #[test]
+    fn test_sum_of_powers() {
+        assert_eq!(sum_of_powers(10, 2), Ok(385));
+        assert_eq!(sum_of_powers(100, 3), Ok(25_502_500));
+        assert_eq!(sum_of_powers(0, 5), Ok(0));
+        assert_eq!(sum_of_powers(7, 0), Ok(7));
+        for n in 0..30 {
+            for k in 0..12 {
+                assert_eq!(sum_of_powers(n, k), Ok(direct(n, k)), "n = {n}, k = {k}");
+            }
+        }
+        assert_eq!(sum_of_powers(1, 1000), Ok(1));
+        assert_eq!(sum_of_powers(2, 127), Ok((1 << 127) + 1));
+        assert_eq!(sum_of_powers(2, 128), Err(MathError::Overflow));
+        // n(n + 1)/2 right below and above the u128 limit
+        let n = (1u64 << 63) + (1 << 62);
+        assert_eq!(sum_of_powers(n, 1), Ok(n as u128 * (n as u128 + 1) / 2));
+        assert_eq!(sum_of_powers(u64::MAX, 2), Err(MathError::Overflow));
+        assert_eq!(
+            sum_of_powers(u64::MAX, 1),
+            Ok(u64::MAX as u128 * (u64::MAX as u128 + 1) / 2)
+        );
+    }
+
+    #[test]
+    fn test_faulhaber_polynomial() {
+        assert_eq!(
+            faulhaber_polynomial(1).unwrap().coeffs(),
+            [Rational::ZERO, r(1, 2), r(1, 2)]
+        );
+        assert_eq!(
+            faulhaber_polynomial(3).unwrap().coeffs(),
+            [Rational::ZERO, Rational::ZERO, r(1, 4), r(1, 2), r(1, 4)]
+        );
+        assert_eq!(
+            faulhaber_polynomial(0).unwrap().coeffs(),
+            [Rational::ZERO, Rational::ONE]
+        );
+        for k in 0..10 {
+            let p = faulhaber_polynomial(k).unwrap();
+            assert_eq!(p.degree(), Some(k as usize + 1));
+            for n in 0..12 {
+                assert_eq!(
+                    p.eval(&Rational::from(n)),
+                    Ok(Rational::from(direct(n as u64, k) as i64))
+                );
+            }
+        }
+        assert!(faulhaber_polynomial(35).is_ok());
+        assert_eq!(faulhaber_polynomial(36), Err(MathError::Overflow));
+    }
+
+    #[cfg(feature = "internal-bigint")]
+    #[test]
+    fn test_sum_of_powers_big() {
+        use alloc::string::ToString;
+
+        for (n, k) in [(0, 3), (10, 2), (1000, 7), (u64::MAX, 1)] {
+            assert_eq!(
+                sum_of_powers_big(n, k).to_u128(),
+                Some(sum_of_powers(n, k).unwrap())
+            );
+        }
+        let big = sum_of_powers_big(1_000_000, 10);
+        assert_eq!(big.to_string().len(), 65);
+        assert_eq!(sum_of_powers(1_000_000, 10), Err(MathError::Overflow));
+        // n = 2: 1 + 2^200
+        assert_eq!(
+            sum_of_powers_big(2, 200),
+            &BigU::one().shl_bits(200) + &BigU::one()
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use alloc::collections::BTreeSet;
+
+/// Recamán's sequence: `a(0) = 0`, `a(n) = a(n-1) - n` if that is positive and new,
+/// otherwise `a(n-1) + n`.
+///
+/// 0, 1, 3, 6, 2, 7, 13, 20, 12, 21, 11, ... Terms are `u64`; the iterator ends
+/// instead of overflowing (unreachable in practice, terms grow roughly linearly).
+/// Memory grows with the number of terms produced, since membership must be tracked.
+#[derive(Debug, Clone)]
+pub struct Recaman {
+    n: u64,
+    current: Option<u64>,
+    seen: BTreeSet<u64>,
+}
+
+impl Recaman {
+    pub fn new() -> Self {
+        Recaman {
+            n: 0,
+            current: Some(0),
+            seen: BTreeSet::new(),
+        }
+    }
+}
+
+impl Default for Recaman {
+    fn default() -> Self {
+        Recaman::new()
+    }
+}
+
+impl Iterator for Recaman {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let term = self.current?;
+        self.seen.insert(term);
+        self.n += 1;
+        self.current = match term.checked_sub(self.n) {
+            Some(back) if back > 0 && !self.seen.contains(&back) => Some(back),
+            _ => term.checked_add(self.n),
+        };
+        Some(term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_first_terms() {
+        let terms: Vec<u64> = Recaman::new().take(16).collect();
+        assert_eq!(
+            terms,
+            vec![0, 1, 3, 6, 2, 7, 13, 20, 12, 21, 11, 22, 10, 23, 9, 24]
+        );
+    }
+
+    #[test]
+    fn test_first_repeat() {
+        // the first repeated value is 42, at n = 24 (it first appears at n = 20)
+        let terms: Vec<u64> = Recaman::new().take(25).collect();
+        assert_eq!(terms[20], 42);
+        assert_eq!(terms[24], 42);
+    }
+
+    #[test]
+    fn test_steps_have_length_n() {
+        let terms: Vec<u64> = Recaman::new().take(5000).collect();
+        for (n, pair) in terms.windows(2).enumerate() {
+            assert_eq!(pair[0].abs_diff(pair[1]), n as u64 + 1);
+        }
+    }
+}
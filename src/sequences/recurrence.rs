@@ -0,0 +1,169 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::modular::{add_mod, mod_inverse, mul_mod};
+use crate::algebra::poly_mod::PolyMod;
+use crate::errors::MathError;
+
+/// Shortest linear recurrence `aₙ = c₁aₙ₋₁ + ... + c_L·aₙ₋L (mod p)` generating `seq`.
+///
+/// Berlekamp-Massey: O(n²) and exact as soon as `seq` holds at least `2L` terms. The
+/// returned vector is `[c₁, ..., c_L]`; an all-zero sequence gives an empty recurrence:
+/// - Fibonacci `0, 1, 1, 2, 3, 5` gives `[1, 1]`
+///
+/// `p` must be prime. Errors with `OutOfRange` if `p < 2`.
+pub fn berlekamp_massey(seq: &[u64], p: u64) -> Result<Vec<u64>, MathError> {
+    if p < 2 {
+        return Err(MathError::OutOfRange);
+    }
+    let seq: Vec<u64> = seq.iter().map(|&a| a % p).collect();
+
+    // connection polynomials C (current) and B (before the last length change)
+    let mut c = vec![1u64];
+    let mut b = vec![1u64];
+    let (mut len, mut shift, mut last_discrepancy) = (0, 1, 1);
+
+    for n in 0..seq.len() {
+        let d = (1..=len).fold(seq[n], |acc, i| {
+            add_mod(acc, mul_mod(c[i], seq[n - i], p), p)
+        });
+        if d == 0 {
+            shift += 1;
+            continue;
+        }
+        let coef = mul_mod(d, mod_inverse(last_discrepancy, p)?, p);
+        let previous = c.clone();
+        // C ← C - (d/b)·x^shift·B
+        if c.len() < b.len() + shift {
+            c.resize(b.len() + shift, 0);
+        }
+        for (i, &bi) in b.iter().enumerate() {
+            c[i + shift] = add_mod(c[i + shift], p - mul_mod(coef, bi, p), p);
+        }
+        if 2 * len <= n {
+            len = n + 1 - len;
+            b = previous;
+            last_discrepancy = d;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+
+    c.resize(len + 1, 0);
+    Ok(c[1..].iter().map(|&ci| (p - ci) % p).collect())
+}
+
+/// `aₙ mod p` for the recurrence `coeffs = [c₁, ..., c_L]` with `initial = [a₀, ..., a_{L-1}]`.
+///
+/// Kitamasa's method: `xⁿ mod (x^L - c₁x^(L-1) - ... - c_L) = Σ rᵢxⁱ` by square-and-multiply
+/// on polynomials, then `aₙ = Σ rᵢaᵢ`. O(L² log n), so `n` can be astronomically large.
+///
+/// Errors with `OutOfRange` if `p < 2` and `DimensionMismatch` unless
+/// `initial.len() == coeffs.len()`.
+pub fn kitamasa(coeffs: &[u64], initial: &[u64], n: u64, p: u64) -> Result<u64, MathError> {
+    if p < 2 {
+        return Err(MathError::OutOfRange);
+    }
+    if initial.len() != coeffs.len() {
+        return Err(MathError::DimensionMismatch);
+    }
+    if coeffs.is_empty() {
+        return Ok(0);
+    }
+
+    // characteristic polynomial, monic of degree L
+    let mut char_coeffs: Vec<u64> = coeffs.iter().rev().map(|&c| (p - c % p) % p).collect();
+    char_coeffs.push(1);
+    let modulus = PolyMod::new(char_coeffs, p)?;
+
+    let mut result = PolyMod::new(vec![1], p)?;
+    let mut base = PolyMod::new(vec![0, 1], p)?.div_rem(&modulus)?.1;
+    let mut exp = n;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.mul(&base)?.div_rem(&modulus)?.1;
+        }
+        base = base.mul(&base)?.div_rem(&modulus)?.1;
+        exp >>= 1;
+    }
+
+    Ok(result
+        .coeffs()
+        .iter()
+        .zip(initial)
+        .fold(0, |acc, (&r, &a)| add_mod(acc, mul_mod(r, a % p, p), p)))
+}
+
+/// `aₙ mod p` extrapolated from a prefix of the sequence: Berlekamp-Massey then Kitamasa.
+///
+/// The prefix must contain at least twice the recurrence order for the result to be
+/// trustworthy. Errors as `berlekamp_massey`.
+pub fn extrapolate(seq: &[u64], n: u64, p: u64) -> Result<u64, MathError> {
+    let coeffs = berlekamp_massey(seq, p)?;
+    if let Some(&a) = usize::try_from(n).ok().and_then(|i| seq.get(i)) {
+        return Ok(a % p);
+    }
+    kitamasa(&coeffs, &seq[..coeffs.len()], n, p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: u64 = 1_000_000_007;
+
+    #[test]
+    fn test_berlekamp_massey_fibonacci() {
+        assert_eq!(
+            berlekamp_massey(&[0, 1, 1, 2, 3, 5, 8, 13], P),
+            Ok(vec![1, 1])
+        );
+        assert_eq!(berlekamp_massey(&[0, 0, 0], P), Ok(vec![]));
+        assert_eq!(berlekamp_massey(&[1, 2, 4, 8, 16], P), Ok(vec![2]));
+        assert_eq!(berlekamp_massey(&[1], 1), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_berlekamp_massey_recovers_order_three() {
+        // aₙ = 2aₙ₋₁ - aₙ₋₂ + 5aₙ₋₃ mod 97
+        let p = 97;
+        let mut seq = vec![3u64, 1, 4];
+        for n in 3..12 {
+            let next = (2 * seq[n - 1] + (p - seq[n - 2]) + 5 * seq[n - 3]) % p;
+            seq.push(next);
+        }
+        assert_eq!(berlekamp_massey(&seq, p), Ok(vec![2, p - 1, 5]));
+    }
+
+    #[test]
+    fn test_kitamasa() {
+        // F(10) = 55, F(90) mod P
+        assert_eq!(kitamasa(&[1, 1], &[0, 1], 10, P), Ok(55));
+        assert_eq!(
+            kitamasa(&[1, 1], &[0, 1], 90, P),
+            Ok(2_880_067_194_370_816_120 % P)
+        );
+        assert_eq!(kitamasa(&[1, 1], &[0, 1], 0, P), Ok(0));
+        assert_eq!(
+            kitamasa(&[1, 1], &[0], 5, P),
+            Err(MathError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // squares satisfy aₙ = 3aₙ₋₁ - 3aₙ₋₂ + aₙ₋₃
+        let squares: Vec<u64> = (0..8).map(|n| n * n).collect();
+        assert_eq!(
+            extrapolate(&squares, 1_000_000, P),
+            Ok(1_000_000_000_000 % P)
+        );
+        assert_eq!(extrapolate(&squares, 5, P), Ok(25));
+        // tribonacci
+        let trib = [0, 0, 1, 1, 2, 4, 7, 13, 24];
+        let coeffs = berlekamp_massey(&trib, P).unwrap();
+        assert_eq!(coeffs, vec![1, 1, 1]);
+        assert_eq!(extrapolate(&trib, 37, P), Ok(1_132_436_852 % P));
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::vec::Vec;
+
+use crate::errors::MathError;
+use crate::sequences::sequence::Sequence;
+
+/// Terms up to `limit` that appear in both sequences, in increasing order, without repeats.
+///
+/// A single merge pass over both iterators (no nested loops), so the cost is the
+/// number of terms of each sequence below `limit`. Both sequences must be
+/// non-decreasing; a decreasing step is reported as `MathError::OutOfRange`.
+///
+/// - `intersect(&Squares, &Triangular, 1000) = [0, 1, 36]`
+pub fn intersect<A, B>(seq_a: &A, seq_b: &B, limit: u128) -> Result<Vec<u128>, MathError>
+where
+    A: Sequence + ?Sized,
+    B: Sequence + ?Sized,
+{
+    let mut a = monotone_upto(seq_a, limit);
+    let mut b = monotone_upto(seq_b, limit);
+    let mut common = Vec::new();
+
+    let (mut x, mut y) = (a.next().transpose()?, b.next().transpose()?);
+    while let (Some(u), Some(v)) = (x, y) {
+        if u < v {
+            x = a.next().transpose()?;
+        } else if v < u {
+            y = b.next().transpose()?;
+        } else {
+            if common.last() != Some(&u) {
+                common.push(u);
+            }
+            x = a.next().transpose()?;
+            y = b.next().transpose()?;
+        }
+    }
+    Ok(common)
+}
+
+/// Terms up to `limit` satisfying `pred`, in sequence order (repeats kept).
+///
+/// The sequence must be non-decreasing so that the scan can stop at `limit`;
+/// a decreasing step is reported as `MathError::OutOfRange`.
+pub fn search_with_predicate<S, P>(
+    seq: &S,
+    mut pred: P,
+    limit: u128,
+) -> Result<Vec<u128>, MathError>
+where
+    S: Sequence + ?Sized,
+    P: FnMut(u128) -> bool,
+{
+    let mut found = Vec::new();
+    for term in monotone_upto(seq, limit) {
+        let term = term?;
+        if pred(term) {
+            found.push(term);
+        }
+    }
+    Ok(found)
+}
+
+/// Terms up to `limit`, failing on the first decrease.
+fn monotone_upto<S>(seq: &S, limit: u128) -> impl Iterator<Item = Result<u128, MathError>> + '_
+where
+    S: Sequence + ?Sized,
+{
+    let mut previous = 0;
+    seq.iter().take_while(move |&t| t <= limit).map(move |t| {
+        if t < previous {
+            return Err(MathError::OutOfRange);
+        }
+        previous = t;
+        Ok(t)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequences::sequence::{Catalan, Fibonacci, Primes, Squares, Triangular};
+    use alloc::boxed::Box;
+    use alloc::vec;
+
+    struct Countdown;
+
+    impl Sequence for Countdown {
+        fn nth(&self, n: usize) -> Result<u128, MathError> {
+            10u128.checked_sub(n as u128).ok_or(MathError::Overflow)
+        }
+    }
+
+    #[test]
+    fn test_square_triangular_numbers() {
+        assert_eq!(intersect(&Squares, &Triangular, 1000), Ok(vec![0, 1, 36]));
+        assert_eq!(
+            intersect(&Squares, &Triangular, 10_000_000),
+            Ok(vec![0, 1, 36, 1225, 41616, 1413721])
+        );
+    }
+
+    #[test]
+    fn test_intersection_deduplicates() {
+        // Fibonacci repeats 1
+        assert_eq!(
+            intersect(&Fibonacci, &Squares, 1_000_000),
+            Ok(vec![0, 1, 144])
+        );
+    }
+
+    #[test]
+    fn test_intersect_with_trait_objects() {
+        let a: Box<dyn Sequence> = Box::new(Fibonacci);
+        let b: Box<dyn Sequence> = Box::new(Catalan);
+        assert_eq!(intersect(a.as_ref(), b.as_ref(), 10_000), Ok(vec![1, 2, 5]));
+    }
+
+    #[test]
+    fn test_search_with_predicate() {
+        let prime_fibs = search_with_predicate(
+            &Fibonacci,
+            |f| Primes.contains(f) == Some(true),
+            1_000_000_000_000,
+        );
+        assert_eq!(
+            prime_fibs,
+            Ok(vec![
+                2, 3, 5, 13, 89, 233, 1597, 28657, 514229, 433494437, 2971215073
+            ])
+        );
+        let even_squares = search_with_predicate(&Squares, |s| s % 2 == 0, 100);
+        assert_eq!(even_squares, Ok(vec![0, 4, 16, 36, 64, 100]));
+    }
+
+    #[test]
+    fn test_non_monotone_sequence() {
+        assert_eq!(
+            intersect(&Countdown, &Squares, 100),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(
+            search_with_predicate(&Countdown, |_| true, 100),
+            Err(MathError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_limit_bounds() {
+        assert_eq!(intersect(&Squares, &Triangular, 0), Ok(vec![0]));
+        assert_eq!(search_with_predicate(&Primes, |_| true, 1), Ok(vec![]));
+    }
+}
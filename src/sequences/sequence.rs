@@ -0,0 +1,290 @@
+use alloc::boxed::Box;
+
+use crate::algebra::prime_nbr::miller_rabin_u128;
+use crate::combinatorics::catalan::catalan;
+use crate::errors::MathError;
+use crate::sequences::figurate::{is_polygonal, polygonal};
+
+/// Miller-Rabin rounds behind `Primes::contains` (error below 2^-64).
+const PRIME_MR_ROUNDS: u32 = 32;
+
+/// Integer sequence indexed from 0, with terms in `u128`.
+///
+/// Only `nth` is required. `iter` walks the terms until the first one that
+/// cannot be represented, and `contains` is an optional fast membership test
+/// (`None` when a sequence does not provide one).
+pub trait Sequence {
+    /// Term at index `n`; `MathError::Overflow` when it does not fit in `u128`.
+    fn nth(&self, n: usize) -> Result<u128, MathError>;
+
+    /// Terms in index order, ending at the first unrepresentable one.
+    fn iter(&self) -> Box<dyn Iterator<Item = u128> + '_> {
+        Box::new((0..).map_while(|n| self.nth(n).ok()))
+    }
+
+    /// Whether `x` is a term, if the sequence supports a direct test.
+    fn contains(&self, _x: u128) -> Option<bool> {
+        None
+    }
+}
+
+/// Primes 2, 3, 5, 7, ...; membership by Miller-Rabin (error below 2^-64).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Primes;
+
+/// Fibonacci numbers 0, 1, 1, 2, 3, 5, ...
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fibonacci;
+
+/// Catalan numbers 1, 1, 2, 5, 14, ...
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Catalan;
+
+/// Squares 0, 1, 4, 9, ...
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Squares;
+
+/// Triangular numbers 0, 1, 3, 6, 10, ...
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Triangular;
+
+/// Pentagonal numbers 0, 1, 5, 12, 22, ...
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pentagonal;
+
+/// Hexagonal numbers 0, 1, 6, 15, 28, ..., the odd-indexed triangular numbers
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hexagonal;
+
+impl Sequence for Primes {
+    /// Linear scan, O(n log n) primality tests; prefer `iter` to walk many primes.
+    fn nth(&self, n: usize) -> Result<u128, MathError> {
+        self.iter().nth(n).ok_or(MathError::Overflow)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = u128> + '_> {
+        let odd_primes = (3..=u128::MAX)
+            .step_by(2)
+            .filter(|&n| self.contains(n) == Some(true));
+        Box::new(core::iter::once(2).chain(odd_primes))
+    }
+
+    fn contains(&self, x: u128) -> Option<bool> {
+        Some(x >= 2 && miller_rabin_u128(x, PRIME_MR_ROUNDS).is_ok_and(|r| r.is_prime()))
+    }
+}
+
+impl Sequence for Fibonacci {
+    /// Iterative, since F(186) is already the last term that fits in `u128`.
+    fn nth(&self, n: usize) -> Result<u128, MathError> {
+        self.iter().nth(n).ok_or(MathError::Overflow)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = u128> + '_> {
+        let pairs = core::iter::successors(Some((0u128, Some(1u128))), |&(a, b)| {
+            b.map(|b| (b, a.checked_add(b)))
+        });
+        Box::new(pairs.map(|(a, _)| a))
+    }
+
+    fn contains(&self, x: u128) -> Option<bool> {
+        // x is Fibonacci iff 5x² + 4 or 5x² - 4 is a perfect square
+        let five_x2 = x.checked_mul(x).and_then(|sq| sq.checked_mul(5));
+        match five_x2 {
+            Some(v) => Some(is_square(v + 4) || (v >= 4 && is_square(v - 4))),
+            None => Some(self.iter().any(|f| f == x)),
+        }
+    }
+}
+
+impl Sequence for Catalan {
+    fn nth(&self, n: usize) -> Result<u128, MathError> {
+        catalan(n)
+    }
+}
+
+impl Sequence for Squares {
+    fn nth(&self, n: usize) -> Result<u128, MathError> {
+        (n as u128)
+            .checked_mul(n as u128)
+            .ok_or(MathError::Overflow)
+    }
+
+    fn contains(&self, x: u128) -> Option<bool> {
+        Some(is_square(x))
+    }
+}
+
+impl Sequence for Triangular {
+    fn nth(&self, n: usize) -> Result<u128, MathError> {
+        let n = n as u128;
+        n.checked_mul(n + 1)
+            .map(|v| v / 2)
+            .ok_or(MathError::Overflow)
+    }
+
+    fn contains(&self, x: u128) -> Option<bool> {
+        // x = k(k+1)/2 forces k = isqrt(2x); when 2x leaves u128, 2·isqrt(x/2) is at
+        // most 2 below it, and the even factor is halved first so k(k+1)/2 cannot overflow
+        let (k, slack) = match x.checked_mul(2) {
+            Some(double) => (double.isqrt(), 0),
+            None => (2 * (x / 2).isqrt(), 2),
+        };
+        let triangle = |k: u128| match k.is_multiple_of(2) {
+            true => (k / 2).checked_mul(k + 1),
+            false => k.checked_mul(k.div_ceil(2)),
+        };
+        Some((k..=k + slack).any(|k| triangle(k) == Some(x)))
+    }
+}
+
+impl Sequence for Pentagonal {
+    fn nth(&self, n: usize) -> Result<u128, MathError> {
+        polygonal(5, n as u64)
+    }
+
+    fn contains(&self, x: u128) -> Option<bool> {
+        is_polygonal(5, x).ok()
+    }
+}
+
+impl Sequence for Hexagonal {
+    fn nth(&self, n: usize) -> Result<u128, MathError> {
+        polygonal(6, n as u64)
+    }
+
+    fn contains(&self, x: u128) -> Option<bool> {
+        is_polygonal(6, x).ok()
+    }
+}
+
+fn is_square(x: u128) -> bool {
+    let r = x.isqrt();
+    r * r == x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_prime_terms() {
+        let primes: Vec<u128> = Primes.iter().take(10).collect();
+        assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(Primes.nth(999), Ok(7919));
+        assert_eq!(Primes.contains(1), Some(false));
+        assert_eq!(Primes.contains((1 << 127) - 1), Some(true));
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        let fib: Vec<u128> = Fibonacci.iter().take(12).collect();
+        assert_eq!(fib, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89]);
+        assert_eq!(Fibonacci.nth(100), Ok(354_224_848_179_261_915_075));
+        assert!(Fibonacci.nth(186).is_ok());
+        assert_eq!(Fibonacci.nth(187), Err(MathError::Overflow));
+        assert_eq!(Fibonacci.iter().count(), 187);
+    }
+
+    #[test]
+    fn test_fibonacci_nth_matches_iter() {
+        for (n, f) in Fibonacci.iter().enumerate() {
+            assert_eq!(Fibonacci.nth(n), Ok(f));
+        }
+    }
+
+    #[test]
+    fn test_fibonacci_contains() {
+        assert_eq!(Fibonacci.contains(0), Some(true));
+        assert_eq!(Fibonacci.contains(144), Some(true));
+        assert_eq!(Fibonacci.contains(145), Some(false));
+        let big = Fibonacci.nth(180).unwrap();
+        assert_eq!(Fibonacci.contains(big), Some(true));
+        assert_eq!(Fibonacci.contains(big + 1), Some(false));
+    }
+
+    #[test]
+    fn test_catalan_default_iter() {
+        let terms: Vec<u128> = Catalan.iter().take(6).collect();
+        assert_eq!(terms, vec![1, 1, 2, 5, 14, 42]);
+        assert_eq!(Catalan.contains(14), None);
+        assert_eq!(Catalan.nth(200_000), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_squares_and_triangular() {
+        assert_eq!(Squares.nth(12), Ok(144));
+        assert_eq!(Squares.contains(144), Some(true));
+        assert_eq!(Squares.contains(145), Some(false));
+        assert_eq!(
+            Triangular.iter().take(5).collect::<Vec<_>>(),
+            vec![0, 1, 3, 6, 10]
+        );
+        assert_eq!(Triangular.contains(5050), Some(true));
+        assert_eq!(Triangular.contains(5051), Some(false));
+        assert_eq!(Triangular.contains(u128::MAX / 8 + 1), Some(false));
+        assert_eq!(Triangular.contains(u128::MAX), Some(false));
+        let largest = u64::MAX as u128 * (u64::MAX as u128 + 1) / 2;
+        assert_eq!(Triangular.contains(largest), Some(true));
+        assert_eq!(Triangular.contains(largest + 1), Some(false));
+        let k = (1u128 << 64) + 12346;
+        assert_eq!(Triangular.contains(k / 2 * (k + 1)), Some(true));
+        assert_eq!(Triangular.contains(k / 2 * (k + 1) - 1), Some(false));
+    }
+
+    #[test]
+    fn test_pentagonal_and_hexagonal() {
+        assert_eq!(
+            Pentagonal.iter().take(6).collect::<Vec<_>>(),
+            vec![0, 1, 5, 12, 22, 35]
+        );
+        assert_eq!(
+            Hexagonal.iter().take(6).collect::<Vec<_>>(),
+            vec![0, 1, 6, 15, 28, 45]
+        );
+        assert_eq!(Pentagonal.contains(1001), Some(true));
+        assert_eq!(Pentagonal.contains(1000), Some(false));
+        // H(n) = T(2n - 1)
+        for (n, h) in Hexagonal.iter().enumerate().skip(1).take(100) {
+            assert_eq!(Triangular.nth(2 * n - 1), Ok(h));
+            assert_eq!(Hexagonal.contains(h), Some(true));
+            assert_eq!(Triangular.contains(h), Some(true));
+        }
+        // 40755 is triangular, pentagonal and hexagonal
+        let t = 40755;
+        assert!(
+            [
+                Triangular.contains(t),
+                Pentagonal.contains(t),
+                Hexagonal.contains(t)
+            ]
+            .iter()
+            .all(|&c| c == Some(true))
+        );
+    }
+
+    #[test]
+    fn test_prime_fibonacci_numbers() {
+        let prime_fibs: Vec<u128> = Fibonacci
+            .iter()
+            .take_while(|&f| f < 1_000_000_000_000)
+            .filter(|&f| Primes.contains(f) == Some(true))
+            .collect();
+        assert_eq!(
+            prime_fibs,
+            vec![
+                2, 3, 5, 13, 89, 233, 1597, 28657, 514229, 433494437, 2971215073
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trait_objects() {
+        let sequences: Vec<Box<dyn Sequence>> =
+            vec![Box::new(Squares), Box::new(Triangular), Box::new(Catalan)];
+        let fifth: Vec<u128> = sequences.iter().map(|s| s.nth(5).unwrap()).collect();
+        assert_eq!(fifth, vec![25, 15, 42]);
+    }
+}
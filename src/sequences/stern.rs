@@ -0,0 +1,210 @@
+use crate::algebra::rational::Rational;
+use crate::errors::MathError;
+
+/// Stern's diatomic sequence 0, 1, 1, 2, 1, 3, 2, 3, 1, 4, ...:
+/// `s(2n) = s(n)`, `s(2n + 1) = s(n) + s(n + 1)`.
+/// - `stern(5) = 3`
+/// - `stern(2^k) = 1`
+///
+/// Reads the bits of `n` from the lowest, keeping the two coefficients that express the
+/// current term in `s(m)` and `s(m + 1)`: O(log n), no recursion. Every value fits, as
+/// `s(n)` stays below the Fibonacci number `F(⌊log₂ n⌋ + 2)`.
+pub fn stern(n: u64) -> u64 {
+    fusc(n as u128)
+}
+
+/// Ways to write `n` as a sum of powers of 2, each used at most twice:
+/// - `hyperbinary_count(4) = 3`: `4`, `2 + 2`, `2 + 1 + 1`
+///
+/// Equal to `stern(n + 1)`, defined for every `u64` including `u64::MAX`.
+pub fn hyperbinary_count(n: u64) -> u64 {
+    fusc(n as u128 + 1)
+}
+
+/// The `n`-th positive rational in the Calkin-Wilf order `1, 1/2, 2, 1/3, 3/2, 2/3, 3, ...`,
+/// counting from `nth_rational(1) = 1`:
+/// - `nth_rational(5) = 3/2`
+///
+/// `s(n) / s(n + 1)`: consecutive terms of Stern's sequence are coprime and every
+/// positive rational appears exactly once, at the node of the Calkin-Wilf tree numbered
+/// `n` breadth first. `rational_index` is the inverse. `MathError::OutOfRange` if
+/// `n == 0`.
+pub fn nth_rational(n: u64) -> Result<Rational, MathError> {
+    if n == 0 {
+        return Err(MathError::OutOfRange);
+    }
+    // both below F(66) < 2^63
+    Rational::new(stern(n) as i64, hyperbinary_count(n) as i64)
+}
+
+/// Position of a positive rational in the order of `nth_rational`:
+/// - `rational_index(3/2) = 5`
+///
+/// Climbs the Calkin-Wilf tree, where `a/b` has the children `a/(a + b)` and
+/// `(a + b)/b`, taking runs of identical steps with one division as in Euclid's
+/// algorithm. The path read back from the root is the binary expansion of the index.
+/// Errors with `OutOfRange` if `r <= 0` and `Overflow` if the index exceeds `u64`.
+pub fn rational_index(r: Rational) -> Result<u64, MathError> {
+    if r.numer() <= 0 {
+        return Err(MathError::OutOfRange);
+    }
+    let (mut p, mut q) = (r.numer() as u64, r.denom() as u64);
+    // runs of (bit, length) from the node up to the root
+    let mut runs = alloc::vec::Vec::new();
+    while (p, q) != (1, 1) {
+        if p < q {
+            let k = (q - 1) / p;
+            q -= k * p;
+            runs.push((0, k));
+        } else {
+            let k = (p - 1) / q;
+            p -= k * q;
+            runs.push((1, k));
+        }
+    }
+    let mut index = 1u64;
+    for &(bit, length) in runs.iter().rev() {
+        if length > u64::from(index.leading_zeros()) {
+            return Err(MathError::Overflow);
+        }
+        index = index << length | if bit == 1 { (1 << length) - 1 } else { 0 };
+    }
+    Ok(index)
+}
+
+/// Every positive rational, each once, in the order of `nth_rational`.
+pub fn positive_rationals() -> PositiveRationals {
+    PositiveRationals { next: Some((1, 1)) }
+}
+
+/// Iterator returned by `positive_rationals`.
+///
+/// Newman's formula gives each term from the previous one, `x ↦ 1 / (2⌊x⌋ + 1 - x)`, so
+/// nothing is stored; it stops once a term no longer fits in `Rational`.
+#[derive(Debug, Clone)]
+pub struct PositiveRationals {
+    next: Option<(i64, i64)>,
+}
+
+impl Iterator for PositiveRationals {
+    type Item = Rational;
+
+    fn next(&mut self) -> Option<Rational> {
+        let (a, b) = self.next?;
+        // a/b ↦ b / ((2⌊a/b⌋ + 1)·b - a)
+        self.next = (2 * (a / b) + 1).checked_mul(b).map(|d| (b, d - a));
+        Rational::new(a, b).ok()
+    }
+}
+
+/// `s(n)` for any `n < 2^128`: with `s(n) = a·s(m) + b·s(m + 1)` as `m` drops the low bit.
+fn fusc(mut n: u128) -> u64 {
+    let (mut a, mut b) = (1u64, 0u64);
+    while n > 0 {
+        if n & 1 == 1 {
+            b += a;
+        } else {
+            a += b;
+        }
+        n >>= 1;
+    }
+    b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn r(n: i64, d: i64) -> Rational {
+        Rational::new(n, d).unwrap()
+    }
+
+    #[test]
+    fn test_stern() {
+        let first: Vec<u64> = (0..16).map(stern).collect();
+        assert_eq!(first, vec![0, 1, 1, 2, 1, 3, 2, 3, 1, 4, 3, 5, 2, 5, 3, 4]);
+        // against the recurrence
+        let mut s = vec![0u64, 1];
+        for n in 2..5000 {
+            s.push(if n % 2 == 0 {
+                s[n / 2]
+            } else {
+                s[n / 2] + s[n / 2 + 1]
+            });
+        }
+        for (n, &v) in s.iter().enumerate() {
+            assert_eq!(stern(n as u64), v, "s({n})");
+        }
+        assert_eq!(stern(1 << 63), 1);
+        // the maximum over [2^k, 2^(k+1)] is the Fibonacci number F(k + 2)
+        let alternating = 0x5555_5555_5555_5555u64;
+        assert_eq!(stern(alternating), 10_610_209_857_723);
+        assert_eq!(hyperbinary_count(u64::MAX), 1);
+    }
+
+    #[test]
+    fn test_hyperbinary_count() {
+        assert_eq!(hyperbinary_count(0), 1);
+        assert_eq!(hyperbinary_count(4), 3);
+        // brute force over digit strings in {0, 1, 2}
+        fn count(n: u64, power: u64) -> u64 {
+            if n == 0 {
+                return 1;
+            }
+            if power > n {
+                return 0;
+            }
+            (0..=2)
+                .filter(|&k| k * power <= n)
+                .map(|k| count(n - k * power, power * 2))
+                .sum()
+        }
+        for n in 0..300 {
+            assert_eq!(hyperbinary_count(n), count(n, 1), "{n}");
+        }
+    }
+
+    #[test]
+    fn test_nth_rational_and_index() {
+        let first: Vec<Rational> = (1..=7).map(|n| nth_rational(n).unwrap()).collect();
+        assert_eq!(
+            first,
+            vec![
+                r(1, 1),
+                r(1, 2),
+                r(2, 1),
+                r(1, 3),
+                r(3, 2),
+                r(2, 3),
+                r(3, 1)
+            ]
+        );
+        assert_eq!(nth_rational(0), Err(MathError::OutOfRange));
+        for n in 1..3000 {
+            assert_eq!(rational_index(nth_rational(n).unwrap()), Ok(n));
+        }
+        assert_eq!(rational_index(r(3, 2)), Ok(5));
+        assert_eq!(
+            rational_index(nth_rational(u64::MAX).unwrap()),
+            Ok(u64::MAX)
+        );
+        // 1/64 is 63 left steps below the root, 1/65 one more
+        assert_eq!(rational_index(r(1, 64)), Ok(1 << 63));
+        assert_eq!(rational_index(r(1, 65)), Err(MathError::Overflow));
+        assert_eq!(rational_index(r(0, 1)), Err(MathError::OutOfRange));
+        assert_eq!(rational_index(r(-1, 2)), Err(MathError::OutOfRange));
+    }
+
+    #[test]
+    fn test_positive_rationals() {
+        let listed: Vec<Rational> = positive_rationals().take(2000).collect();
+        let indexed: Vec<Rational> = (1..=2000).map(|n| nth_rational(n).unwrap()).collect();
+        assert_eq!(listed, indexed);
+        let mut sorted = listed.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 2000);
+    }
+}
@@ -0,0 +1,252 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::algebra::factorization::factorize_u64;
+use crate::algebra::gcd::gcd_u64;
+use crate::errors::MathError;
+
+/// Values lying on one arithmetic progression, up to a few outliers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArithmeticStructure {
+    /// Every inlier is `offset + k·stride` for some `k >= 0`; 0 when the inliers are all
+    /// equal.
+    pub stride: u64,
+    /// The smallest inlier.
+    pub offset: i64,
+    /// Indices of the values off the progression, ascending.
+    pub outliers: Vec<usize>,
+}
+
+/// GCD of the successive differences, the largest `g` with all values congruent mod `g`:
+/// - `common_difference(&[3, 7, 15, 11]) = 4`
+///
+/// 0 when all values are equal, including a single value. Differences are taken in
+/// `i128`, so the full `i64` range is fine. `MathError::EmptyInput` for no values.
+pub fn common_difference(values: &[i64]) -> Result<u64, MathError> {
+    if values.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    Ok(values
+        .windows(2)
+        .fold(0, |g, pair| gcd_u64(g, distance(pair[0], pair[1]))))
+}
+
+/// The largest stride that all but at most `max_outliers` of the values share a residue
+/// modulo, with that progression's start and the outliers:
+/// - `detect_arithmetic_structure(&[3, 7, 15, 11, 20], 1)`: stride 4, offset 3, outlier
+///   at index 4
+///
+/// A stride of 0 (all inliers equal) counts as the strongest structure. With no
+/// outliers allowed this is `common_difference`; otherwise a single stray value would
+/// collapse that GCD to 1. Among any `max_outliers + 1` values one is an inlier, and the
+/// true stride divides the gcd of its differences to two other inliers, so the
+/// candidates are those gcds over a few anchors and partners, each tried from its
+/// largest divisor down against all values. Cheap for a handful of outliers; candidates
+/// are factored by trial division. Errors with `EmptyInput` for no values and
+/// `OutOfRange` if fewer than two values would remain as inliers.
+pub fn detect_arithmetic_structure(
+    values: &[i64],
+    max_outliers: usize,
+) -> Result<ArithmeticStructure, MathError> {
+    if values.is_empty() {
+        return Err(MathError::EmptyInput);
+    }
+    let n = values.len();
+    if n < max_outliers + 2 {
+        return Err(MathError::OutOfRange);
+    }
+    let fits = |stride: u64, anchor: i64| {
+        let inliers = values
+            .iter()
+            .filter(|&&v| match stride {
+                0 => v == anchor,
+                _ => (v as i128 - anchor as i128).rem_euclid(stride as i128) == 0,
+            })
+            .count();
+        inliers + max_outliers >= n
+    };
+
+    // (stride, anchor) candidates; stride 0 means "equal to the anchor"
+    let mut candidates = BTreeSet::new();
+    for &anchor in &values[..=max_outliers] {
+        candidates.insert((0, anchor));
+        let partners: Vec<u64> = values
+            .iter()
+            .filter(|&&v| v != anchor)
+            .take(max_outliers + 2)
+            .map(|&v| distance(v, anchor))
+            .collect();
+        for (i, &d) in partners.iter().enumerate() {
+            for &e in &partners[i..] {
+                candidates.insert((gcd_u64(d, e), anchor));
+            }
+        }
+    }
+
+    let mut best: Option<(u64, i64)> = None;
+    for &(candidate, anchor) in &candidates {
+        if candidate == 0 {
+            if fits(0, anchor) {
+                best = Some((0, anchor));
+                break;
+            }
+            continue;
+        }
+        for stride in divisors_descending(candidate)? {
+            if best.is_some_and(|(s, _)| stride <= s) {
+                break;
+            }
+            if fits(stride, anchor) {
+                best = Some((stride, anchor));
+                break;
+            }
+        }
+    }
+    // stride 1 always fits, so some candidate succeeded
+    let (stride, anchor) = best.ok_or(MathError::NoSolution)?;
+    let on_progression = |v: i64| match stride {
+        0 => v == anchor,
+        _ => (v as i128 - anchor as i128).rem_euclid(stride as i128) == 0,
+    };
+    Ok(ArithmeticStructure {
+        stride,
+        offset: values
+            .iter()
+            .copied()
+            .filter(|&v| on_progression(v))
+            .min()
+            .unwrap_or(anchor),
+        outliers: (0..n).filter(|&i| !on_progression(values[i])).collect(),
+    })
+}
+
+fn distance(a: i64, b: i64) -> u64 {
+    (a as i128 - b as i128).unsigned_abs() as u64
+}
+
+fn divisors_descending(n: u64) -> Result<Vec<u64>, MathError> {
+    let mut divisors = Vec::from([1u64]);
+    for (p, e) in factorize_u64(n)? {
+        let current = divisors.len();
+        let mut power = 1;
+        for _ in 0..e {
+            power *= p;
+            for i in 0..current {
+                divisors.push(divisors[i] * power);
+            }
+        }
+    }
+    divisors.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(divisors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_common_difference() {
+        assert_eq!(common_difference(&[3, 7, 15, 11]), Ok(4));
+        assert_eq!(common_difference(&[5]), Ok(0));
+        assert_eq!(common_difference(&[5, 5, 5]), Ok(0));
+        assert_eq!(common_difference(&[-6, 9, 0]), Ok(3));
+        assert_eq!(common_difference(&[i64::MIN, i64::MAX]), Ok(u64::MAX));
+        assert_eq!(common_difference(&[]), Err(MathError::EmptyInput));
+    }
+
+    #[test]
+    fn test_detect_arithmetic_structure() {
+        let found = detect_arithmetic_structure(&[3, 7, 15, 11, 20], 1).unwrap();
+        assert_eq!(
+            found,
+            ArithmeticStructure {
+                stride: 4,
+                offset: 3,
+                outliers: vec![4],
+            }
+        );
+        let strict = detect_arithmetic_structure(&[3, 7, 15, 11, 20], 0).unwrap();
+        assert_eq!((strict.stride, strict.offset), (1, 3));
+        assert!(strict.outliers.is_empty());
+
+        // timestamps every 250 ms with two glitches, the first one up front
+        let mut stamps: Vec<i64> = (0..40).map(|i| 1_000 + 250 * i).collect();
+        stamps[0] = 1_013;
+        stamps[17] = 5_321;
+        let found = detect_arithmetic_structure(&stamps, 2).unwrap();
+        assert_eq!((found.stride, found.offset), (250, 1_250));
+        assert_eq!(found.outliers, vec![0, 17]);
+
+        // the first values agree with each other but not with the progression's stride
+        let found = detect_arithmetic_structure(&[5, 5, 5, 5, 8, 11, 100], 1).unwrap();
+        assert_eq!(
+            (found.stride, found.offset, found.outliers),
+            (3, 5, vec![6])
+        );
+        // a larger stride wins even if it makes a different value the outlier
+        let found = detect_arithmetic_structure(&[5, 5, 5, 5, 8, 100], 1).unwrap();
+        assert_eq!(
+            (found.stride, found.offset, found.outliers),
+            (95, 5, vec![4])
+        );
+        let found = detect_arithmetic_structure(&[7, 7, 9, 7], 1).unwrap();
+        assert_eq!(
+            (found.stride, found.offset, found.outliers),
+            (0, 7, vec![2])
+        );
+    }
+
+    #[test]
+    fn test_detect_matches_brute_force() {
+        let mut state = 12_345u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            (state >> 33) as i64
+        };
+        for round in 0..300 {
+            let len = 3 + round % 6;
+            let stride = 1 + next() % 12;
+            let mut values: Vec<i64> = (0..len).map(|_| 7 + stride * (next() % 9)).collect();
+            for _ in 0..round % 3 {
+                let i = (next() as usize) % len;
+                values[i] = next() % 120;
+            }
+            for k in 0..len - 1 {
+                let found = detect_arithmetic_structure(&values, k).unwrap();
+                let fits = |g: i64| {
+                    values.iter().any(|&a| {
+                        values
+                            .iter()
+                            .filter(|&&v| if g == 0 { v == a } else { (v - a) % g == 0 })
+                            .count()
+                            + k
+                            >= len
+                    })
+                };
+                let expected = if fits(0) {
+                    0
+                } else {
+                    (1..=200).rev().find(|&g| fits(g)).unwrap()
+                };
+                assert_eq!(found.stride as i64, expected, "{values:?} {k}");
+                assert!(found.outliers.len() <= k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_detect_errors() {
+        assert_eq!(
+            detect_arithmetic_structure(&[], 0),
+            Err(MathError::EmptyInput)
+        );
+        assert_eq!(
+            detect_arithmetic_structure(&[1, 2], 1),
+            Err(MathError::OutOfRange)
+        );
+        assert!(detect_arithmetic_structure(&[1, 2, 3], 1).is_ok());
+    }
+}
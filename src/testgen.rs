@@ -0,0 +1,256 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::algebra::factorization::factorize_u64;
+use crate::algebra::prime_nbr::{is_prime_u64, splitmix64, strong_pseudoprimes};
+use crate::errors::MathError;
+
+/// Largest `k` tried for Chernick's `(6k + 1)(12k + 1)(18k + 1)`, which is then below
+/// `2^113`.
+const CHERNICK_MAX_K: u64 = 1 << 34;
+
+/// Bases and limit of the strong pseudoprimes in `test_suite`.
+const SUITE_BASES: [u64; 3] = [2, 3, 5];
+const SUITE_PSEUDOPRIME_LIMIT: usize = 1_000_000;
+
+/// What a `LabeledNumber` is known to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Label {
+    Prime,
+    /// Composite, squarefree, and `p - 1 | n - 1` for every prime `p | n` (Korselt).
+    Carmichael,
+    /// Odd composite passing the Miller-Rabin round for `base`.
+    StrongPseudoprime {
+        base: u64,
+    },
+    /// Product of two primes, possibly equal.
+    Semiprime,
+}
+
+/// A number with its label and its factorization, primes ascending.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabeledNumber {
+    pub n: u128,
+    pub label: Label,
+    pub factors: Vec<(u128, u32)>,
+}
+
+/// `count` primes of 2 to 64 bits, sizes and values drawn from `seed`:
+/// - `prime_vectors(1, 3)`: 26143853, 414760597725563179 and 8837, on every run and
+///   every target
+///
+/// Each prime is the first after a random start within its bit size, found with
+/// `is_prime_u64`; the same prime may come up twice.
+pub fn prime_vectors(seed: u64, count: usize) -> Vec<LabeledNumber> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            let bits = 2 + (splitmix64(&mut state) % 63) as u32;
+            let p = random_prime(&mut state, bits);
+            LabeledNumber {
+                n: p as u128,
+                label: Label::Prime,
+                factors: alloc::vec![(p as u128, 1)],
+            }
+        })
+        .collect()
+}
+
+/// `count` distinct Carmichael numbers below `2^128`, drawn from `seed`, ascending:
+/// - `1729 = 7·13·19` is the smallest of them, for `k = 1`
+///
+/// Chernick's `(6k + 1)(12k + 1)(18k + 1)` is a Carmichael number whenever its three
+/// factors are prime, as each `p - 1` divides `36k`, which divides `n - 1`. The `k` are
+/// the first valid ones after random starts below `2^34`, so the factors fit in `u64`.
+pub fn carmichael_vectors(seed: u64, count: usize) -> Vec<LabeledNumber> {
+    let mut state = seed;
+    let mut ks = BTreeSet::new();
+    while ks.len() < count {
+        let mut k = 1 + splitmix64(&mut state) % CHERNICK_MAX_K;
+        while !chernick_factors(k).iter().all(|&p| is_prime_u64(p)) {
+            k = k % CHERNICK_MAX_K + 1;
+        }
+        ks.insert(k);
+    }
+    ks.into_iter()
+        .map(|k| {
+            let factors = chernick_factors(k);
+            LabeledNumber {
+                n: factors.iter().map(|&p| p as u128).product(),
+                label: Label::Carmichael,
+                factors: factors.iter().map(|&p| (p as u128, 1)).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Every strong pseudoprime to `base` up to `limit`, ascending, with its factors:
+/// - `strong_pseudoprime_vectors(2, 10_000)`: 2047 = 23·89, 3277, 4033, 4681 and 8321
+///
+/// The list of `prime_nbr::strong_pseudoprimes`, factored by trial division; O(limit)
+/// memory. `MathError::OutOfRange` if `base < 2`.
+pub fn strong_pseudoprime_vectors(
+    base: u64,
+    limit: usize,
+) -> Result<Vec<LabeledNumber>, MathError> {
+    strong_pseudoprimes(base, limit)?
+        .into_iter()
+        .map(|n| {
+            Ok(LabeledNumber {
+                n: n as u128,
+                label: Label::StrongPseudoprime { base },
+                factors: factorize_u64(n)?
+                    .into_iter()
+                    .map(|(p, e)| (p as u128, e))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// `count` products of two random primes of `factor_bits` bits each, drawn from `seed`:
+/// - `semiprime_vectors(7, 10, 64)`: ten 127- or 128-bit numbers, hard for trial
+///   division, easy to check against their factors
+///
+/// `MathError::OutOfRange` unless `factor_bits` is in `2..=64`.
+pub fn semiprime_vectors(
+    seed: u64,
+    count: usize,
+    factor_bits: u32,
+) -> Result<Vec<LabeledNumber>, MathError> {
+    if !(2..=64).contains(&factor_bits) {
+        return Err(MathError::OutOfRange);
+    }
+    let mut state = seed;
+    Ok((0..count)
+        .map(|_| {
+            let p = random_prime(&mut state, factor_bits) as u128;
+            let q = random_prime(&mut state, factor_bits) as u128;
+            let factors = match p.cmp(&q) {
+                core::cmp::Ordering::Less => alloc::vec![(p, 1), (q, 1)],
+                core::cmp::Ordering::Equal => alloc::vec![(p, 2)],
+                core::cmp::Ordering::Greater => alloc::vec![(q, 1), (p, 1)],
+            };
+            LabeledNumber {
+                n: p * q,
+                label: Label::Semiprime,
+                factors,
+            }
+        })
+        .collect())
+}
+
+/// A mixed suite for downstream crates, reproducible from `seed`: `count` primes,
+/// `count` Carmichael numbers, `count` semiprimes with 32-bit and with 64-bit factors,
+/// and the first `count` strong pseudoprimes below `10^6` to each of the bases 2, 3
+/// and 5, in that order:
+/// - `for v in test_suite(42, 20) { assert_eq!(my_is_prime(v.n), v.label == Label::Prime) }`
+///
+/// The labels come from this crate's reference routines and are checked by its tests
+/// against independent definitions (Korselt's criterion, the factor products, one
+/// Miller-Rabin round), so a disagreement points at the other side.
+pub fn test_suite(seed: u64, count: usize) -> Vec<LabeledNumber> {
+    let mut state = seed;
+    let mut suite = prime_vectors(splitmix64(&mut state), count);
+    suite.extend(carmichael_vectors(splitmix64(&mut state), count));
+    for bits in [32, 64] {
+        // the bit sizes are valid
+        suite.extend(semiprime_vectors(splitmix64(&mut state), count, bits).unwrap_or_default());
+    }
+    for base in SUITE_BASES {
+        let pseudoprimes = strong_pseudoprime_vectors(base, SUITE_PSEUDOPRIME_LIMIT);
+        suite.extend(pseudoprimes.unwrap_or_default().into_iter().take(count));
+    }
+    suite
+}
+
+/// A prime of exactly `bits` bits, `2 <= bits <= 64`: the first after a random start,
+/// drawing again if the bit size runs out before one.
+fn random_prime(state: &mut u64, bits: u32) -> u64 {
+    let low = 1u64 << (bits - 1);
+    let high = low - 1 + low;
+    loop {
+        let mut n = low + splitmix64(state) % low;
+        while !is_prime_u64(n) && n < high {
+            n += 1;
+        }
+        if is_prime_u64(n) {
+            return n;
+        }
+    }
+}
+
+fn chernick_factors(k: u64) -> [u64; 3] {
+    [6 * k + 1, 12 * k + 1, 18 * k + 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::prime_nbr::is_strong_probable_prime;
+
+    /// Checks a label against its definition, independently of how it was produced.
+    fn check(v: &LabeledNumber) {
+        let product = v.factors.iter().fold(1u128, |acc, &(p, e)| acc * p.pow(e));
+        assert_eq!(product, v.n, "{v:?}");
+        assert!(v.factors.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(
+            v.factors
+                .iter()
+                .all(|&(p, _)| p <= u64::MAX as u128 && is_prime_u64(p as u64))
+        );
+        let prime_count: u32 = v.factors.iter().map(|&(_, e)| e).sum();
+        match v.label {
+            Label::Prime => assert_eq!(prime_count, 1),
+            Label::Semiprime => assert_eq!(prime_count, 2),
+            Label::Carmichael => {
+                assert!(prime_count >= 3);
+                assert!(
+                    v.factors
+                        .iter()
+                        .all(|&(p, e)| e == 1 && (v.n - 1).is_multiple_of(p - 1))
+                );
+            }
+            Label::StrongPseudoprime { base } => {
+                assert!(prime_count >= 2);
+                assert_eq!(is_strong_probable_prime(v.n as u64, base), Ok(true));
+            }
+        }
+    }
+
+    #[test]
+    fn test_vectors_hold() {
+        let suite = test_suite(42, 20);
+        assert_eq!(suite.len(), 20 * 4 + 20 * SUITE_BASES.len());
+        suite.iter().for_each(check);
+        assert_eq!(suite, test_suite(42, 20));
+        assert_ne!(suite, test_suite(43, 20));
+        for bits in [2, 3, 17, 64] {
+            let semiprimes = semiprime_vectors(bits as u64, 30, bits).unwrap();
+            semiprimes.iter().for_each(check);
+            assert!(semiprimes.iter().all(|v| v.factors[0].0 >> (bits - 1) == 1));
+        }
+        assert!(prime_vectors(9, 200).iter().all(|v| v.n > 1));
+    }
+
+    #[test]
+    fn test_known_values() {
+        let pseudoprimes = strong_pseudoprime_vectors(2, 10_000).unwrap();
+        let ns: Vec<u128> = pseudoprimes.iter().map(|v| v.n).collect();
+        assert_eq!(ns, [2047, 3277, 4033, 4681, 8321]);
+        assert_eq!(pseudoprimes[0].factors, [(23, 1), (89, 1)]);
+        assert_eq!(
+            strong_pseudoprime_vectors(1, 100),
+            Err(MathError::OutOfRange)
+        );
+        assert_eq!(semiprime_vectors(1, 1, 1), Err(MathError::OutOfRange));
+        assert_eq!(semiprime_vectors(1, 1, 65), Err(MathError::OutOfRange));
+        assert_eq!(chernick_factors(1), [7, 13, 19]);
+        let carmichael = carmichael_vectors(5, 10);
+        assert_eq!(carmichael.len(), 10);
+        assert!(carmichael.windows(2).all(|w| w[0].n < w[1].n));
+        assert!(prime_vectors(3, 0).is_empty());
+        let primes: Vec<u128> = prime_vectors(1, 3).iter().map(|v| v.n).collect();
+        assert_eq!(primes, [26143853, 414760597725563179, 8837]);
+    }
+}
@@ -0,0 +1,217 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::divisors::sigma;
+use crate::algebra::factorization::{mobius, totient};
+use crate::algebra::gcd::{gcd_generic, gcd_i64, gcd_secure_generic, gcd_u64};
+use crate::algebra::lcm::lcm_u64;
+use crate::algebra::modular::{mod_inverse, mod_pow};
+use crate::algebra::prime_nbr::{is_prime_generic, is_prime_u64, sieve};
+use crate::algebra::sieve::Sieve;
+
+/// Outcome of one family of identities in `run_selfcheck`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: &'static str,
+    /// Inputs tried.
+    pub cases: u64,
+    /// First failing input, `(n, 0)` for checks on a single integer.
+    pub first_failure: Option<(u64, u64)>,
+}
+
+impl CheckResult {
+    pub fn passed(&self) -> bool {
+        self.first_failure.is_none()
+    }
+
+    fn new(name: &'static str) -> Self {
+        CheckResult {
+            name,
+            cases: 0,
+            first_failure: None,
+        }
+    }
+
+    fn record(&mut self, ok: bool, input: (u64, u64)) {
+        self.cases += 1;
+        if !ok && self.first_failure.is_none() {
+            self.first_failure = Some(input);
+        }
+    }
+}
+
+/// Every check run by `run_selfcheck`, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfCheckReport {
+    pub limit: u64,
+    pub checks: Vec<CheckResult>,
+}
+
+impl SelfCheckReport {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(CheckResult::passed)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks.iter().filter(|c| !c.passed())
+    }
+}
+
+/// Cross-checks independent implementations and classical identities for every
+/// `n <= limit`, as a runtime sanity check of a new target or toolchain:
+/// - `gcd_lcm`: the gcd variants agree, divide both inputs, leave coprime cofactors,
+///   and `gcd·lcm = a·b`
+/// - `primality`: `is_prime_u64`, `is_prime_generic`, `Sieve` and `prime_nbr::sieve` agree
+/// - `totient`: `totient(n)` against a sieved table, and `φ(ab) = φ(a)φ(b)` for coprime
+///   pairs
+/// - `divisor_sums`: `Σ_{d|n} μ(d) = [n = 1]` (Möbius inversion), `Σ_{d|n} φ(d) = n`
+///   and `sigma(n)` against a sieved table
+/// - `fermat`: `a^(p-1) ≡ 1` and `a·a⁻¹ ≡ 1 (mod p)` for primes `p` and a few `a`
+///
+/// An `Err` where a value was expected counts as a failure. Pairs range over
+/// `a, b <= √limit` (`b >= 1`), so every check costs about `limit` cases; `totient`
+/// and `mobius` factor each `n` by trial division, which dominates at O(limit^(3/2)).
+/// Memory is O(limit). `run_selfcheck(100_000)` takes well under a second in a release
+/// build on a desktop.
+pub fn run_selfcheck(limit: u64) -> SelfCheckReport {
+    let n = limit as usize;
+    let root = limit.isqrt();
+    let is_prime_table = sieve(n);
+
+    let mut gcd_lcm = CheckResult::new("gcd_lcm");
+    // b = 0 is a `DivisionByZero` for the generic gcds
+    for a in 0..=root {
+        for b in 1..=root {
+            let g = gcd_u64(a, b);
+            let cofactors = a % g == 0 && b % g == 0 && gcd_u64(a / g, b / g) == 1;
+            let product = lcm_u64(a, b).map(|l| g as u128 * l as u128);
+            let ok = cofactors
+                && gcd_generic(a, b) == Ok(g)
+                && gcd_secure_generic(a, b) == Ok(g)
+                && gcd_i64(a as i64, -(b as i64)) == g
+                && product == Ok(a as u128 * b as u128);
+            gcd_lcm.record(ok, (a, b));
+        }
+    }
+
+    let mut primality = CheckResult::new("primality");
+    let segmented = Sieve::new(n);
+    for (i, &expected) in is_prime_table.iter().enumerate() {
+        let generic = match i {
+            0 | 1 => true,
+            _ => is_prime_generic(i as u64) == Ok(expected),
+        };
+        let ok =
+            generic && is_prime_u64(i as u64) == expected && segmented.is_prime(i) == Ok(expected);
+        primality.record(ok, (i as u64, 0));
+    }
+
+    // φ, μ and σ sieved independently of the factorization
+    let mut phi: Vec<u64> = (0..=limit).collect();
+    let mut mu = vec![1i64; n + 1];
+    let mut sigma_table = vec![0u128; n + 1];
+    for p in (2..=n).filter(|&p| is_prime_table[p]) {
+        for m in (p..=n).step_by(p) {
+            phi[m] -= phi[m] / p as u64;
+            mu[m] = -mu[m];
+        }
+        for m in (p * p..=n).step_by(p * p) {
+            mu[m] = 0;
+        }
+    }
+    let (mut mobius_sum, mut phi_sum) = (vec![0i64; n + 1], vec![0u64; n + 1]);
+    for d in 1..=n {
+        for m in (d..=n).step_by(d) {
+            mobius_sum[m] += mu[d];
+            phi_sum[m] += phi[d];
+            sigma_table[m] += d as u128;
+        }
+    }
+
+    let mut totients = CheckResult::new("totient");
+    for i in 1..=limit {
+        totients.record(totient(i) == Ok(phi[i as usize] as u128), (i, 0));
+    }
+    for a in 1..=root {
+        for b in (1..=root).filter(|&b| gcd_u64(a, b) == 1) {
+            let ok = phi[(a * b) as usize] == phi[a as usize] * phi[b as usize];
+            totients.record(ok, (a, b));
+        }
+    }
+
+    let mut divisor_sums = CheckResult::new("divisor_sums");
+    for i in 1..=n {
+        let ok = mobius(i as u64).map(i64::from) == Ok(mu[i])
+            && mobius_sum[i] == i64::from(i == 1)
+            && phi_sum[i] == i as u64
+            && sigma(i as i64) == Ok(sigma_table[i]);
+        divisor_sums.record(ok, (i as u64, 0));
+    }
+
+    let mut fermat = CheckResult::new("fermat");
+    for p in (3..=limit).filter(|&p| is_prime_table[p as usize]) {
+        for a in [2, p / 2, p - 1] {
+            let ok = mod_pow(a, p - 1, p) == Ok(1)
+                && mod_inverse(a, p).map(|inv| a as u128 * inv as u128 % p as u128) == Ok(1);
+            fermat.record(ok, (a, p));
+        }
+    }
+
+    SelfCheckReport {
+        limit,
+        checks: vec![gcd_lcm, primality, totients, divisor_sums, fermat],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_selfcheck() {
+        let report = run_selfcheck(5_000);
+        assert!(
+            report.passed(),
+            "{:?}",
+            report.failures().collect::<Vec<_>>()
+        );
+        let names: Vec<&str> = report.checks.iter().map(|c| c.name).collect();
+        assert_eq!(
+            names,
+            ["gcd_lcm", "primality", "totient", "divisor_sums", "fermat"]
+        );
+        // 71·70 pairs, 0..=5000 and 5000 + coprime pairs
+        assert_eq!(report.checks[0].cases, 71 * 70);
+        assert_eq!(report.checks[1].cases, 5_001);
+        assert!(report.checks[2].cases > 5_000);
+        assert_eq!(report.checks[3].cases, 5_000);
+        // three bases for each of the 668 odd primes below 5000
+        assert_eq!(report.checks[4].cases, 3 * 668);
+    }
+
+    #[test]
+    fn test_small_limits() {
+        for limit in 0..30 {
+            assert!(run_selfcheck(limit).passed(), "{limit}");
+        }
+    }
+
+    #[test]
+    fn test_record_keeps_first_failure() {
+        let mut check = CheckResult::new("demo");
+        check.record(true, (1, 0));
+        check.record(false, (2, 0));
+        check.record(false, (3, 0));
+        assert_eq!(check.cases, 3);
+        assert_eq!(check.first_failure, Some((2, 0)));
+        let report = SelfCheckReport {
+            limit: 3,
+            checks: vec![check, CheckResult::new("fine")],
+        };
+        assert!(!report.passed());
+        assert_eq!(
+            report.failures().map(|c| c.name).collect::<Vec<_>>(),
+            ["demo"]
+        );
+    }
+}